@@ -22,8 +22,10 @@ pub mod i18n;
 pub mod i18n_bundle;
 pub mod qa;
 pub use qa::{
-    apply_store, describe, get_answer_schema, get_example_answers, next, next_with_ctx,
-    render_card, render_json_ui, render_text, submit_all, submit_patch, validate_answers,
+    apply_store, back, describe, describe_with_ctx, diff_answers, flow_next, get_answer_schema,
+    get_example_answers, get_session, next, next_with_ctx, render_card, render_json_ui,
+    render_markdown, render_summary, render_text, resume_session, submit_all, submit_patch,
+    validate_answers, validate_field,
 };
 
 const COMPONENT_NAME: &str = "component-qa";
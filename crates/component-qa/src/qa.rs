@@ -1,6 +1,10 @@
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use greentic_types::i18n_text::I18nText;
 use greentic_types::schemas::component::v0_6_0::{
@@ -10,11 +14,20 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value, json};
 use thiserror::Error;
 
+use qa_spec::spec::flow::{CardMode, OnLimit};
 use qa_spec::{
-    FormSpec, ProgressContext, QuestionType, RenderPayload, StoreContext, StoreError, StoreOp,
-    VisibilityMode, answers_schema, build_render_payload, example_answers, next_question,
-    render_card as qa_render_card, render_json_ui as qa_render_json_ui,
-    render_text as qa_render_text, resolve_visibility, validate,
+    AnswerChange, AnswerSet, CardOptions, Clock, CompiledFormSpec, ComputedReport,
+    DefaultValueError, FieldValidationError, FlowEngine, FlowError, FlowState, FormSpec,
+    MergeError, MergeOptions, MessageCatalog, Meta, MigrationError, ProgressContext, ProgressState,
+    QAFlowSpec, QuestionType, RenderOptions, RenderPayload, RenderProgress, SessionError,
+    SignatureError, SpecParseError, StepOutcome, StoreContext, StoreError, StoreOp, SystemClock,
+    VisibilityMap, VisibilityMode, apply_normalization_with_changes, autofill_defaults,
+    coerce_answers, computed_conflicts, effective_store_ops, example_answers, next_question,
+    next_question_in_flow, previous_question, read_only_changes, redact_answers, redacted_marker,
+    reject_oversized, render_card_with_options as qa_render_card,
+    render_json_ui as qa_render_json_ui, render_markdown as qa_render_markdown,
+    render_summary as qa_render_summary, render_text as qa_render_text,
+    resolve_visibility_with_ctx, secret_pointer, validate, visibility_errors,
 };
 
 const MISSING_QA_FORM_CONFIG_MESSAGE: &str =
@@ -38,6 +51,12 @@ enum ComponentError {
         #[source]
         source: serde_json::Error,
     },
+    #[error("invalid QA form '{path}': {source}")]
+    QaFormSpec {
+        path: String,
+        #[source]
+        source: Box<SpecParseError>,
+    },
     #[error("failed to read i18n locale file '{path}': {source}")]
     I18nRead {
         path: String,
@@ -68,6 +87,82 @@ enum ComponentError {
     Include(String),
     #[error("store apply failed: {0}")]
     Store(#[from] StoreError),
+    #[error("failed to parse flow: {0}")]
+    FlowParse(#[source] serde_json::Error),
+    #[error("flow execution failed: {0}")]
+    Flow(#[from] FlowError),
+    #[error("failed to merge prefill answers: {0}")]
+    Prefill(#[from] MergeError),
+    #[error("signing key '{0}' is not available in ctx.secrets")]
+    SigningKeyUnavailable(String),
+    #[error("form has no question '{0}'")]
+    UnknownQuestion(String),
+    #[error("invalid form default: {0}")]
+    InvalidDefault(#[from] DefaultValueError),
+    #[error("failed to parse session: {0}")]
+    SessionParse(#[source] serde_json::Error),
+    #[error("cannot resume session: {0}")]
+    Session(#[from] SessionError),
+    #[error("invalid {argument} JSON: {source}")]
+    InvalidInput {
+        argument: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+impl ComponentError {
+    /// Response body for a failed call.
+    fn to_response(&self) -> Value {
+        match self {
+            Self::InvalidInput { argument, source } => json!({
+                "status": "error",
+                "error": {
+                    "code": "invalid_input",
+                    "argument": argument,
+                    "line": source.line(),
+                    "column": source.column(),
+                    "message": self.to_string(),
+                },
+            }),
+            Self::UnknownQuestion(question_id) => json!({
+                "status": "error",
+                "error": {
+                    "code": "unknown_question",
+                    "question_id": question_id,
+                    "message": self.to_string(),
+                },
+            }),
+            Self::InvalidDefault(error) => json!({
+                "status": "error",
+                "error": {
+                    "code": "invalid_default",
+                    "question_id": error.question_id,
+                    "message": self.to_string(),
+                },
+            }),
+            Self::Session(error) => {
+                let (code, expected, found) = match error {
+                    SessionError::FormMismatch { expected, found } => {
+                        ("session_form_mismatch", expected, found)
+                    }
+                    SessionError::VersionMismatch {
+                        expected, found, ..
+                    } => ("session_version_mismatch", expected, found),
+                };
+                json!({
+                    "status": "error",
+                    "error": {
+                        "code": code,
+                        "expected": expected,
+                        "found": found,
+                        "message": self.to_string(),
+                    },
+                })
+            }
+            _ => json!({ "error": self.to_string() }),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
@@ -76,6 +171,32 @@ struct ComponentConfig {
     qa_form_asset_path: Option<String>,
     #[serde(default)]
     include_registry: BTreeMap<String, String>,
+    /// How `ctx.prefill_answers` is merged under the caller's answers.
+    #[serde(default)]
+    prefill_merge: MergeOptions,
+    /// Signature `submit_all` requires on incoming answer sets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    answer_signature: Option<SignatureRequirement>,
+    /// Migrate answer sets recorded against an older spec version with the
+    /// spec's `migrations` before submitting them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    migrate_answers: bool,
+    /// Reject malformed ctx/answers/value JSON with `invalid_input` instead of
+    /// treating it as an empty object.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    strict_inputs: bool,
+    /// `QAFlowSpec` JSON that `next` follows instead of the question order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    flow_spec_json: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SignatureRequirement {
+    /// Key id the answer set must be signed with.
+    key_id: String,
+    /// Path of the HMAC key under `ctx.secrets`; defaults to `key_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secret_ref: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -84,15 +205,103 @@ struct LoadedFormValue {
     form_asset_path: String,
 }
 
-fn load_form_spec(config_json: &str) -> Result<FormSpec, ComponentError> {
-    let loaded = load_form_spec_value(config_json)?;
-    let spec: FormSpec =
-        serde_json::from_value(loaded.spec_value).map_err(ComponentError::ConfigParse)?;
-    validate_form_i18n_keys(&spec, &loaded.form_asset_path)?;
+/// Raw form asset and include registry named by a component config.
+struct FormSource {
+    raw_spec: String,
+    form_asset_path: String,
+    include_registry: BTreeMap<String, String>,
+}
+
+impl FormSource {
+    fn cache_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (
+            &self.form_asset_path,
+            &self.raw_spec,
+            &self.include_registry,
+        )
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Forms kept by [`load_form_spec`]; the least recently used one is evicted
+/// past this.
+const FORM_CACHE_CAPACITY: usize = 32;
+
+/// Compiled forms keyed by [`FormSource::cache_key`], in least recently used
+/// order.
+#[derive(Default)]
+struct FormCache {
+    entries: HashMap<u64, Arc<CompiledFormSpec>>,
+    order: VecDeque<u64>,
+}
+
+impl FormCache {
+    fn get(&mut self, key: u64) -> Option<Arc<CompiledFormSpec>> {
+        let spec = Arc::clone(self.entries.get(&key)?);
+        self.touch(key);
+        Some(spec)
+    }
+
+    fn insert(&mut self, key: u64, spec: Arc<CompiledFormSpec>) {
+        if !self.entries.contains_key(&key)
+            && self.entries.len() >= FORM_CACHE_CAPACITY
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(key, spec);
+        self.touch(key);
+    }
+
+    fn touch(&mut self, key: u64) {
+        self.order.retain(|entry| *entry != key);
+        self.order.push_back(key);
+    }
+}
+
+/// Compiled forms keyed by their source, so a hot form is parsed, expanded,
+/// and compiled once. Editing the form file or the include registry changes
+/// the key.
+fn form_cache() -> &'static Mutex<FormCache> {
+    static CACHE: OnceLock<Mutex<FormCache>> = OnceLock::new();
+    CACHE.get_or_init(Mutex::default)
+}
+
+fn load_form_spec(config_json: &str) -> Result<Arc<CompiledFormSpec>, ComponentError> {
+    let source = read_form_source(config_json)?;
+    let key = source.cache_key();
+    let cached = form_cache()
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.get(key));
+    let spec = match cached {
+        Some(spec) => spec,
+        None => {
+            let loaded = parse_form_source(&source)?;
+            let spec = FormSpec::from_value(loaded.spec_value).map_err(|source| {
+                ComponentError::QaFormSpec {
+                    path: loaded.form_asset_path.clone(),
+                    source: Box::new(source),
+                }
+            })?;
+            let spec = Arc::new(spec.compile());
+            if let Ok(mut cache) = form_cache().lock() {
+                cache.insert(key, Arc::clone(&spec));
+            }
+            spec
+        }
+    };
+    validate_form_i18n_keys(&spec, &source.form_asset_path)?;
     Ok(spec)
 }
 
 fn load_form_spec_value(config_json: &str) -> Result<LoadedFormValue, ComponentError> {
+    parse_form_source(&read_form_source(config_json)?)
+}
+
+fn read_form_source(config_json: &str) -> Result<FormSource, ComponentError> {
     if config_json.trim().is_empty() {
         return Err(ComponentError::MissingQaFormAssetPath);
     }
@@ -105,19 +314,27 @@ fn load_form_spec_value(config_json: &str) -> Result<LoadedFormValue, ComponentE
         .map(str::trim)
         .filter(|path| !path.is_empty())
         .ok_or(ComponentError::MissingQaFormAssetPath)?;
-    let (raw_spec, resolved_path) = read_qa_form_asset(qa_form_asset_path)?;
+    let (raw_spec, form_asset_path) = read_qa_form_asset(qa_form_asset_path)?;
+    Ok(FormSource {
+        raw_spec,
+        form_asset_path,
+        include_registry: config.include_registry,
+    })
+}
+
+fn parse_form_source(source: &FormSource) -> Result<LoadedFormValue, ComponentError> {
     let mut spec_value: Value =
-        serde_json::from_str(&raw_spec).map_err(|source| ComponentError::QaFormParse {
-            path: resolved_path.clone(),
-            source,
+        serde_json::from_str(&source.raw_spec).map_err(|error| ComponentError::QaFormParse {
+            path: source.form_asset_path.clone(),
+            source: error,
         })?;
-    let include_registry_values = parse_include_registry(config.include_registry)?;
+    let include_registry_values = parse_include_registry(source.include_registry.clone())?;
     if !include_registry_values.is_empty() {
         spec_value = expand_includes_value(&spec_value, &include_registry_values)?;
     }
     Ok(LoadedFormValue {
         spec_value,
-        form_asset_path: resolved_path,
+        form_asset_path: source.form_asset_path.clone(),
     })
 }
 
@@ -255,10 +472,10 @@ fn collect_question_i18n_keys(question: &qa_spec::QuestionSpec, keys: &mut BTree
     if let Some(text) = &question.description_i18n {
         keys.insert(text.key.clone());
     }
-    if let Some(list) = &question.list {
-        for field in &list.fields {
-            collect_question_i18n_keys(field, keys);
-        }
+    let list_fields = question.list.iter().flat_map(|list| &list.fields);
+    let object_fields = question.object.iter().flat_map(|object| &object.fields);
+    for field in list_fields.chain(object_fields) {
+        collect_question_i18n_keys(field, keys);
     }
 }
 
@@ -408,12 +625,8 @@ fn expand_form_value(
     Ok(Value::Object(out))
 }
 
-fn parse_context(ctx_json: &str) -> Value {
-    serde_json::from_str(ctx_json).unwrap_or_else(|_| Value::Object(Map::new()))
-}
-
-fn parse_runtime_context(ctx_json: &str) -> Value {
-    let parsed = parse_context(ctx_json);
+/// The `ctx` object of an envelope, or the whole value for a bare context.
+fn runtime_context(parsed: Value) -> Value {
     parsed
         .get("ctx")
         .and_then(Value::as_object)
@@ -421,6 +634,51 @@ fn parse_runtime_context(ctx_json: &str) -> Value {
         .unwrap_or(parsed)
 }
 
+/// Parser for the ctx/answers/value JSON arguments of the exported functions.
+///
+/// Malformed input is treated as an empty object unless the config sets
+/// `strict_inputs`, which turns it into an `invalid_input` error naming the
+/// argument and the parse position.
+#[derive(Debug, Clone, Copy)]
+struct Inputs {
+    strict: bool,
+}
+
+impl Inputs {
+    fn from_config(config_json: &str) -> Self {
+        let strict = serde_json::from_str::<ComponentConfig>(config_json)
+            .map(|config| config.strict_inputs)
+            .unwrap_or(false);
+        Self { strict }
+    }
+
+    fn parse(self, argument: &'static str, raw: &str) -> Result<Value, ComponentError> {
+        match serde_json::from_str(raw) {
+            Ok(value) => Ok(value),
+            Err(source) if self.strict => Err(ComponentError::InvalidInput { argument, source }),
+            Err(_) => Ok(Value::Object(Map::new())),
+        }
+    }
+
+    fn context(self, ctx_json: &str) -> Result<Value, ComponentError> {
+        self.parse("ctx", ctx_json).map(runtime_context)
+    }
+
+    fn answers(self, answers_json: &str) -> Result<Value, ComponentError> {
+        self.parse("answers", answers_json)
+    }
+
+    /// Error for an argument that has no lenient fallback: `invalid_input`
+    /// in strict mode, a plain parse error otherwise.
+    fn reject(self, argument: &'static str, source: serde_json::Error) -> ComponentError {
+        if self.strict {
+            ComponentError::InvalidInput { argument, source }
+        } else {
+            ComponentError::ConfigParse(source)
+        }
+    }
+}
+
 fn combine_prefix(parent: &str, child: Option<&str>) -> String {
     match (parent.is_empty(), child.unwrap_or("").is_empty()) {
         (true, true) => String::new(),
@@ -473,16 +731,24 @@ fn prefix_question_value(question: &mut Value, prefix: &str) {
     if let Some(visible_if) = question.get_mut("visible_if") {
         prefix_expr_value(visible_if, prefix);
     }
+    if let Some(required_if) = question.get_mut("required_if") {
+        prefix_expr_value(required_if, prefix);
+    }
+    if let Some(read_only_if) = question.get_mut("read_only_if") {
+        prefix_expr_value(read_only_if, prefix);
+    }
     if let Some(computed) = question.get_mut("computed") {
         prefix_expr_value(computed, prefix);
     }
-    if let Some(fields) = question
-        .get_mut("list")
-        .and_then(|list| list.get_mut("fields"))
-        .and_then(Value::as_array_mut)
-    {
-        for field in fields {
-            prefix_question_value(field, prefix);
+    for container in ["list", "object"] {
+        if let Some(fields) = question
+            .get_mut(container)
+            .and_then(|container| container.get_mut("fields"))
+            .and_then(Value::as_array_mut)
+        {
+            for field in fields {
+                prefix_question_value(field, prefix);
+            }
         }
     }
 }
@@ -491,7 +757,7 @@ fn prefix_expr_value(expr: &mut Value, prefix: &str) {
     if let Some(obj) = expr.as_object_mut() {
         if matches!(
             obj.get("op").and_then(Value::as_str),
-            Some("answer") | Some("is_set")
+            Some("answer") | Some("is_set") | Some("is_empty")
         ) && let Some(path) = obj.get_mut("path")
             && let Some(raw) = path.as_str()
         {
@@ -506,11 +772,21 @@ fn prefix_expr_value(expr: &mut Value, prefix: &str) {
         if let Some(right) = obj.get_mut("right") {
             prefix_expr_value(right, prefix);
         }
+        for operand in ["needle", "haystack", "of", "on", "default"] {
+            if let Some(operand) = obj.get_mut(operand) {
+                prefix_expr_value(operand, prefix);
+            }
+        }
         if let Some(items) = obj.get_mut("expressions").and_then(Value::as_array_mut) {
             for item in items {
                 prefix_expr_value(item, prefix);
             }
         }
+        if let Some(arms) = obj.get_mut("arms").and_then(Value::as_array_mut) {
+            for then in arms.iter_mut().filter_map(|arm| arm.get_mut("then")) {
+                prefix_expr_value(then, prefix);
+            }
+        }
     }
 }
 
@@ -520,8 +796,24 @@ fn resolve_context_answers(ctx: &Value) -> Value {
         .unwrap_or_else(|| Value::Object(Map::new()))
 }
 
-fn parse_answers(answers_json: &str) -> Value {
-    serde_json::from_str(answers_json).unwrap_or_else(|_| Value::Object(Map::new()))
+/// Merge `ctx.prefill_answers` (base) with the caller's answers (overlay)
+/// using the config's `prefill_merge` options.
+fn merge_prefill(
+    spec: &FormSpec,
+    config_json: &str,
+    ctx: &Value,
+    answers: Value,
+) -> Result<Value, ComponentError> {
+    let Some(prefill) = ctx.get("prefill_answers").filter(|value| value.is_object()) else {
+        return Ok(answers);
+    };
+    let config: ComponentConfig =
+        serde_json::from_str(config_json).map_err(ComponentError::ConfigParse)?;
+    let mut base = AnswerSet::new(spec.id.clone(), spec.version.clone());
+    base.answers = prefill.clone();
+    let mut overlay = AnswerSet::new(spec.id.clone(), spec.version.clone());
+    overlay.answers = answers;
+    Ok(AnswerSet::merge(&base, &overlay, &config.prefill_merge)?.answers)
 }
 
 fn secrets_host_available(ctx: &Value) -> bool {
@@ -541,21 +833,34 @@ fn respond(result: Result<Value, ComponentError>) -> String {
         Ok(value) => serde_json::to_string(&value).unwrap_or_else(|error| {
             json!({"error": format!("json encode: {}", error)}).to_string()
         }),
-        Err(err) => json!({ "error": err.to_string() }).to_string(),
+        Err(err) => err.to_response().to_string(),
     }
 }
 
 pub fn describe(form_id: &str, config_json: &str) -> String {
+    describe_with_ctx(form_id, config_json, "{}")
+}
+
+/// Like [`describe`]; with `ctx.analyze: true` the spec also carries the
+/// `issues` found by [`qa_spec::FormSpec::analyze`] (unknown question
+/// references, unreachable questions, defaults outside the choices).
+pub fn describe_with_ctx(form_id: &str, config_json: &str, ctx_json: &str) -> String {
     respond(load_form_spec(config_json).and_then(|spec| {
         if spec.id != form_id {
-            Err(ComponentError::FormUnavailable(form_id.to_string()))
-        } else {
-            serde_json::to_value(spec).map_err(ComponentError::JsonEncode)
+            return Err(ComponentError::FormUnavailable(form_id.to_string()));
         }
+        let ctx = Inputs::from_config(config_json).context(ctx_json)?;
+        let mut described =
+            serde_json::to_value(spec.spec()).map_err(ComponentError::JsonEncode)?;
+        if ctx.get("analyze").and_then(Value::as_bool).unwrap_or(false) {
+            described["issues"] =
+                serde_json::to_value(spec.analyze()).map_err(ComponentError::JsonEncode)?;
+        }
+        Ok(described)
     }))
 }
 
-fn ensure_form(form_id: &str, config_json: &str) -> Result<FormSpec, ComponentError> {
+fn ensure_form(form_id: &str, config_json: &str) -> Result<Arc<CompiledFormSpec>, ComponentError> {
     let spec = load_form_spec(config_json)?;
     if spec.id != form_id {
         Err(ComponentError::FormUnavailable(form_id.to_string()))
@@ -565,89 +870,381 @@ fn ensure_form(form_id: &str, config_json: &str) -> Result<FormSpec, ComponentEr
 }
 
 pub fn get_answer_schema(form_id: &str, config_json: &str, ctx_json: &str) -> String {
-    let schema = ensure_form(form_id, config_json).map(|spec| {
-        let ctx = parse_runtime_context(ctx_json);
+    let schema = ensure_form(form_id, config_json).and_then(|spec| {
+        let ctx = Inputs::from_config(config_json).context(ctx_json)?;
         let answers = resolve_context_answers(&ctx);
-        let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
-        answers_schema(&spec, &visibility)
+        let visibility =
+            resolve_visibility_with_ctx(&spec, &answers, &ctx, VisibilityMode::Visible).map;
+        Ok(spec.answers_schema(&visibility))
     });
     respond(schema)
 }
 
 pub fn get_example_answers(form_id: &str, config_json: &str, ctx_json: &str) -> String {
-    let result = ensure_form(form_id, config_json).map(|spec| {
-        let ctx = parse_runtime_context(ctx_json);
+    let result = ensure_form(form_id, config_json).and_then(|spec| {
+        let ctx = Inputs::from_config(config_json).context(ctx_json)?;
         let answers = resolve_context_answers(&ctx);
-        let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
-        example_answers(&spec, &visibility)
+        let visibility =
+            resolve_visibility_with_ctx(&spec, &answers, &ctx, VisibilityMode::Visible).map;
+        Ok(example_answers(&spec, &visibility))
     });
     respond(result)
 }
 
 pub fn validate_answers(form_id: &str, config_json: &str, answers_json: &str) -> String {
     let validation = ensure_form(form_id, config_json).and_then(|spec| {
-        let answers = serde_json::from_str(answers_json).map_err(ComponentError::ConfigParse)?;
-        serde_json::to_value(validate(&spec, &answers)).map_err(ComponentError::JsonEncode)
+        let answers = serde_json::from_str(answers_json)
+            .map_err(|source| Inputs::from_config(config_json).reject("answers", source))?;
+        serde_json::to_value(spec.validate(&answers)).map_err(ComponentError::JsonEncode)
     });
     respond(validation)
 }
 
+/// Validates one question of `answers`, for hosts checking a field as the
+/// user leaves it. Responds with `status` `valid` or `invalid` and the scoped
+/// `validation`, or `not_visible` when the question is currently hidden.
+pub fn validate_field(
+    form_id: &str,
+    config_json: &str,
+    answers_json: &str,
+    question_id: &str,
+) -> String {
+    respond(ensure_form(form_id, config_json).and_then(|spec| {
+        let answers = serde_json::from_str(answers_json)
+            .map_err(|source| Inputs::from_config(config_json).reject("answers", source))?;
+        match spec.validate_field(&answers, question_id) {
+            Ok(validation) => Ok(json!({
+                "status": if validation.valid { "valid" } else { "invalid" },
+                "question_id": question_id,
+                "validation": serde_json::to_value(validation)
+                    .map_err(ComponentError::JsonEncode)?,
+            })),
+            Err(FieldValidationError::NotVisible(_)) => Ok(json!({
+                "status": "not_visible",
+                "question_id": question_id,
+            })),
+            Err(FieldValidationError::UnknownQuestion(id)) => {
+                Err(ComponentError::UnknownQuestion(id))
+            }
+        }
+    }))
+}
+
 pub fn next_with_ctx(
     form_id: &str,
     config_json: &str,
     ctx_json: &str,
     answers_json: &str,
 ) -> String {
-    let result = ensure_form(form_id, config_json).map(|spec| {
-        let ctx = parse_runtime_context(ctx_json);
-        let answers = parse_answers(answers_json);
-        let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
-        let progress_ctx = ProgressContext::new(answers.clone(), &ctx);
+    let result = ensure_form(form_id, config_json).and_then(|spec| {
+        let inputs = Inputs::from_config(config_json);
+        let ctx = inputs.context(ctx_json)?;
+        let (answers, _) = autofill_defaults(&spec, &inputs.answers(answers_json)?, &ctx)?;
+        let config: ComponentConfig =
+            serde_json::from_str(config_json).map_err(ComponentError::ConfigParse)?;
+        let answers = spec.materialize_computed(&answers, &ctx);
+        let visibility =
+            resolve_visibility_with_ctx(&spec, &answers, &ctx, VisibilityMode::Visible).map;
+        let progress_ctx = ProgressContext::new(&answers, &ctx);
+        let progress = RenderProgress::measure(&spec, &progress_ctx, &visibility);
+        if let Some(flow_json) = &config.flow_spec_json {
+            let flow: QAFlowSpec =
+                serde_json::from_str(flow_json).map_err(ComponentError::FlowParse)?;
+            let position = next_question_in_flow(&flow, &spec, &ctx, &answers)?;
+            let mut response = json!({
+                "status": flow_status(&position.outcome),
+                "next_question_id": position.question_id(),
+                "step_id": position.step_id,
+                "messages": position.messages,
+                "progress": progress,
+            });
+            if let Some(error) = flow_limit_error(&position.outcome) {
+                response["error"] = error;
+            }
+            return Ok(response);
+        }
         let next_q = next_question(&spec, &progress_ctx, &visibility);
-        let answered = progress_ctx.answered_count(&spec, &visibility);
-        let total = visibility.values().filter(|visible| **visible).count();
-        json!({
+        Ok(json!({
             "status": if next_q.is_some() { "need_input" } else { "complete" },
             "next_question_id": next_q,
-            "progress": {
-                "answered": answered,
-                "total": total
+            "progress": progress,
+        }))
+    });
+    respond(result)
+}
+
+/// Step back from `current_question_id` to the question asked before it.
+///
+/// Returns the `next_with_ctx` envelope pointing at that question, with its
+/// `current_value` (`null` when unanswered, redacted for secrets unless
+/// `ctx.reveal_secrets`) so the UI can prefill it. Before the first question
+/// the status is `at_start` and `next_question_id` is `null`.
+pub fn back(
+    form_id: &str,
+    config_json: &str,
+    ctx_json: &str,
+    answers_json: &str,
+    current_question_id: &str,
+) -> String {
+    let result = ensure_form(form_id, config_json).and_then(|spec| {
+        if !spec
+            .questions
+            .iter()
+            .any(|question| question.id == current_question_id)
+        {
+            return Err(ComponentError::UnknownQuestion(
+                current_question_id.to_string(),
+            ));
+        }
+        let inputs = Inputs::from_config(config_json);
+        let ctx = inputs.context(ctx_json)?;
+        let (answers, _) = autofill_defaults(&spec, &inputs.answers(answers_json)?, &ctx)?;
+        let computed_answers = spec.materialize_computed(&answers, &ctx);
+        let visibility =
+            resolve_visibility_with_ctx(&spec, &computed_answers, &ctx, VisibilityMode::Visible)
+                .map;
+        let progress_ctx = ProgressContext::new(&computed_answers, &ctx);
+        let previous = previous_question(&spec, &progress_ctx, &visibility, current_question_id);
+        let progress = RenderProgress::measure(&spec, &progress_ctx, &visibility);
+        let Some(previous) = previous else {
+            return Ok(json!({
+                "status": "at_start",
+                "next_question_id": null,
+                "progress": progress,
+            }));
+        };
+        let answers = if reveal_secrets(&ctx) {
+            answers
+        } else {
+            redact_answers(&spec, &answers)
+        };
+        Ok(json!({
+            "status": "need_input",
+            "next_question_id": previous,
+            "current_value": answers.get(&previous).cloned().unwrap_or(Value::Null),
+            "progress": progress,
+        }))
+    });
+    respond(result)
+}
+
+/// Snapshot of the session for `answers_json`: a [`ProgressState`] with the
+/// form id, spec version, answers, next question and per-question statuses,
+/// for a host to store and later hand to [`resume_session`].
+///
+/// Pass the previous snapshot as `ctx.session` to keep attempt counts and
+/// the times answers were given. Secret answers are redacted unless
+/// `ctx.reveal_secrets`, so hosts that resume secrets must set it.
+pub fn get_session(form_id: &str, config_json: &str, ctx_json: &str, answers_json: &str) -> String {
+    let result = render_inputs(form_id, config_json, ctx_json, answers_json).and_then(
+        |(spec, ctx, answers)| {
+            let payload = payload_for(&spec, &ctx, &answers);
+            let mut state = ctx
+                .get("session")
+                .and_then(|state| serde_json::from_value::<ProgressState>(state.clone()).ok())
+                .unwrap_or_default();
+            state.snapshot(&SystemClock, &payload, &answers);
+            if !reveal_secrets(&ctx) {
+                state.answers = state.answers.map(|answers| redact_answers(&spec, &answers));
             }
-        })
+            serde_json::to_value(&state).map_err(ComponentError::JsonEncode)
+        },
+    );
+    respond(result)
+}
+
+/// Restore a session saved by [`get_session`].
+///
+/// Fails with `session_version_mismatch` (or `session_form_mismatch`) and the
+/// `expected`/`found` values when the snapshot was taken against another
+/// spec. Otherwise returns the `next` envelope for the stored answers,
+/// pointing at the stored question while it can still be asked, and the
+/// `answers` to continue from (secrets redacted unless `ctx.reveal_secrets`).
+pub fn resume_session(
+    form_id: &str,
+    config_json: &str,
+    ctx_json: &str,
+    session_json: &str,
+) -> String {
+    let result = ensure_form(form_id, config_json).and_then(|spec| {
+        let inputs = Inputs::from_config(config_json);
+        let ctx = inputs.context(ctx_json)?;
+        let state: ProgressState =
+            serde_json::from_str(session_json).map_err(ComponentError::SessionParse)?;
+        state.check_resumable(&spec)?;
+        let answers = state.answers.unwrap_or_else(|| json!({}));
+        let payload = payload_for(&spec, &ctx, &answers);
+        let current = state
+            .current_question_id
+            .filter(|id| {
+                payload
+                    .questions
+                    .iter()
+                    .any(|question| question.id == *id && question.visible && !question.read_only)
+            })
+            .or(payload.next_question_id);
+        let answers = if reveal_secrets(&ctx) {
+            answers
+        } else {
+            redact_answers(&spec, &answers)
+        };
+        Ok(json!({
+            "status": if current.is_some() { "need_input" } else { "complete" },
+            "next_question_id": current,
+            "answers": answers,
+            "progress": payload.progress,
+        }))
     });
     respond(result)
 }
 
+/// Structured change list between two answer documents; secret questions are masked.
+///
+/// Each side may be a full `AnswerSet` or a bare answers object.
+pub fn diff_answers(form_id: &str, config_json: &str, old_json: &str, new_json: &str) -> String {
+    let result = ensure_form(form_id, config_json).and_then(|spec| {
+        let inputs = Inputs::from_config(config_json);
+        let old = parse_answer_set(&spec, inputs, "old", old_json)?;
+        let new = parse_answer_set(&spec, inputs, "new", new_json)?;
+        serde_json::to_value(old.diff_with_spec(&new, &spec)).map_err(ComponentError::JsonEncode)
+    });
+    respond(result)
+}
+
+fn parse_answer_set(
+    spec: &FormSpec,
+    inputs: Inputs,
+    argument: &'static str,
+    raw: &str,
+) -> Result<AnswerSet, ComponentError> {
+    let value: Value =
+        serde_json::from_str(raw).map_err(|source| inputs.reject(argument, source))?;
+    if let Ok(set) = serde_json::from_value::<AnswerSet>(value.clone()) {
+        return Ok(set);
+    }
+    let mut set = AnswerSet::new(spec.id.clone(), spec.version.clone());
+    set.answers = value;
+    Ok(set)
+}
+
 pub fn next(form_id: &str, config_json: &str, answers_json: &str) -> String {
     next_with_ctx(form_id, config_json, "{}", answers_json)
 }
 
+/// Advance a flow run persisted under `ctx.flow_state` by one observable step.
+///
+/// Policy limits configured with `on_limit: error` are surfaced as a structured
+/// `flow_limit_exceeded` error carrying the visited step history.
+pub fn flow_next(
+    form_id: &str,
+    config_json: &str,
+    flow_json: &str,
+    ctx_json: &str,
+    answers_json: &str,
+) -> String {
+    let result = ensure_form(form_id, config_json).and_then(|spec| {
+        let flow: QAFlowSpec =
+            serde_json::from_str(flow_json).map_err(ComponentError::FlowParse)?;
+        let inputs = Inputs::from_config(config_json);
+        let ctx = inputs.context(ctx_json)?;
+        let answers = inputs.answers(answers_json)?;
+        let mut engine = match ctx
+            .get("flow_state")
+            .and_then(|state| serde_json::from_value::<FlowState>(state.clone()).ok())
+        {
+            Some(state) => FlowEngine::resume(&flow, &spec, state),
+            None => FlowEngine::new(&flow, &spec),
+        };
+        let outcome = engine.advance(&answers, &ctx)?;
+        let flow_state =
+            serde_json::to_value(engine.state()).map_err(ComponentError::JsonEncode)?;
+        let mut response = json!({
+            "status": flow_status(&outcome),
+            "outcome": serde_json::to_value(&outcome).map_err(ComponentError::JsonEncode)?,
+            "flow_state": flow_state,
+        });
+        if let Some(error) = flow_limit_error(&outcome) {
+            response["error"] = error;
+        }
+        Ok(response)
+    });
+    respond(result)
+}
+
+/// The `flow_limit_exceeded` error for a limit configured with `on_limit: error`.
+fn flow_limit_error(outcome: &StepOutcome) -> Option<Value> {
+    let StepOutcome::LimitReached {
+        limit,
+        max,
+        step,
+        on_limit: OnLimit::Error,
+        history,
+    } = outcome
+    else {
+        return None;
+    };
+    Some(json!({
+        "code": "flow_limit_exceeded",
+        "limit": limit,
+        "max": max,
+        "step": step,
+        "history": history,
+    }))
+}
+
+fn flow_status(outcome: &StepOutcome) -> &'static str {
+    match outcome {
+        StepOutcome::AskQuestion { .. } => "need_input",
+        StepOutcome::ShowMessage { .. } | StepOutcome::Decided { .. } => "in_progress",
+        StepOutcome::Completed => "complete",
+        StepOutcome::LimitReached { on_limit, .. } => match on_limit {
+            OnLimit::Error => "error",
+            OnLimit::CompletePartial => "partial",
+        },
+    }
+}
+
 pub fn apply_store(form_id: &str, ctx_json: &str, answers_json: &str) -> String {
     let result = ensure_form(form_id, ctx_json).and_then(|spec| {
-        let ctx = parse_runtime_context(ctx_json);
-        let answers = parse_answers(answers_json);
-        let mut store_ctx = StoreContext::from_value(&ctx);
-        store_ctx.answers = answers;
+        let inputs = Inputs::from_config(ctx_json);
+        let ctx = inputs.context(ctx_json)?;
+        let mut store_ctx = StoreContext::with_answers(&ctx, inputs.answers(answers_json)?);
         let host_available = secrets_host_available(&ctx);
         store_ctx.apply_ops(&spec.store, spec.secrets_policy.as_ref(), host_available)?;
-        Ok(store_ctx.to_value())
+        Ok(store_ctx.into_value())
     });
     respond(result)
 }
 
+/// The form, runtime context and answers (with prefill) a render works from.
+fn render_inputs(
+    form_id: &str,
+    config_json: &str,
+    ctx_json: &str,
+    answers_json: &str,
+) -> Result<(Arc<CompiledFormSpec>, Value, Value), ComponentError> {
+    let spec = ensure_form(form_id, config_json)?;
+    let inputs = Inputs::from_config(config_json);
+    let ctx = inputs.context(ctx_json)?;
+    let answers = merge_prefill(&spec, config_json, &ctx, inputs.answers(answers_json)?)?;
+    let (answers, _) = autofill_defaults(&spec, &answers, &ctx)?;
+    Ok((spec, ctx, answers))
+}
+
 fn render_payload(
     form_id: &str,
     config_json: &str,
     ctx_json: &str,
     answers_json: &str,
 ) -> Result<RenderPayload, ComponentError> {
-    let spec = ensure_form(form_id, config_json)?;
-    let ctx = parse_runtime_context(ctx_json);
-    let answers = parse_answers(answers_json);
-    let mut payload = build_render_payload(&spec, &ctx, &answers);
-    let loaded = load_form_spec_value(config_json)?;
-    apply_i18n_to_payload(&mut payload, &loaded.spec_value, &ctx);
-    Ok(payload)
+    let (spec, ctx, answers) = render_inputs(form_id, config_json, ctx_json, answers_json)?;
+    Ok(payload_for(&spec, &ctx, &answers))
+}
+
+fn payload_for(spec: &CompiledFormSpec, ctx: &Value, answers: &Value) -> RenderPayload {
+    let mut payload = spec.build_render_payload(ctx, answers, None, &render_options(spec, ctx));
+    apply_i18n_to_payload(&mut payload, spec, ctx);
+    payload
 }
 
 type ResolvedI18nMap = BTreeMap<String, String>;
@@ -671,14 +1268,13 @@ fn i18n_debug_enabled(ctx: &Value) -> bool {
         .unwrap_or(false)
 }
 
-fn attach_i18n_debug_metadata(card: &mut Value, payload: &RenderPayload, spec_value: &Value) {
-    let keys = build_question_i18n_key_map(spec_value);
+fn attach_i18n_debug_metadata(card: &mut Value, payload: &RenderPayload) {
     let question_metadata = payload
         .questions
         .iter()
         .filter_map(|question| {
-            let (title_key, description_key) =
-                keys.get(&question.id).cloned().unwrap_or((None, None));
+            let title_key = question.title_i18n_key.as_deref();
+            let description_key = question.description_i18n_key.as_deref();
             if title_key.is_none() && description_key.is_none() {
                 return None;
             }
@@ -706,33 +1302,6 @@ fn attach_i18n_debug_metadata(card: &mut Value, payload: &RenderPayload, spec_va
     }
 }
 
-fn build_question_i18n_key_map(
-    spec_value: &Value,
-) -> BTreeMap<String, (Option<String>, Option<String>)> {
-    let mut map = BTreeMap::new();
-    for question in spec_value
-        .get("questions")
-        .and_then(Value::as_array)
-        .cloned()
-        .unwrap_or_default()
-    {
-        if let Some(id) = question.get("id").and_then(Value::as_str) {
-            let title_key = question
-                .get("title_i18n")
-                .and_then(|value| value.get("key"))
-                .and_then(Value::as_str)
-                .map(str::to_string);
-            let description_key = question
-                .get("description_i18n")
-                .and_then(|value| value.get("key"))
-                .and_then(Value::as_str)
-                .map(str::to_string);
-            map.insert(id.to_string(), (title_key, description_key));
-        }
-    }
-    map
-}
-
 fn resolve_i18n_value(
     resolved: &ResolvedI18nMap,
     key: &str,
@@ -750,46 +1319,25 @@ fn resolve_i18n_value(
     resolved.get(key).cloned()
 }
 
-fn apply_i18n_to_payload(payload: &mut RenderPayload, spec_value: &Value, ctx: &Value) {
+fn apply_i18n_to_payload(payload: &mut RenderPayload, spec: &FormSpec, ctx: &Value) {
     let resolved = parse_resolved_i18n(ctx);
     if resolved.is_empty() {
         return;
     }
     let requested_locale = ctx.get("locale").and_then(Value::as_str);
-    let default_locale = spec_value
-        .get("presentation")
-        .and_then(|value| value.get("default_locale"))
-        .and_then(Value::as_str);
-
-    let mut by_id = BTreeMap::new();
-    for question in spec_value
-        .get("questions")
-        .and_then(Value::as_array)
-        .cloned()
-        .unwrap_or_default()
-    {
-        if let Some(id) = question.get("id").and_then(Value::as_str) {
-            by_id.insert(id.to_string(), question);
-        }
-    }
+    let default_locale = spec
+        .presentation
+        .as_ref()
+        .and_then(|presentation| presentation.default_locale.as_deref());
 
     for question in &mut payload.questions {
-        let Some(spec_question) = by_id.get(&question.id) else {
-            continue;
-        };
-        if let Some(key) = spec_question
-            .get("title_i18n")
-            .and_then(|value| value.get("key"))
-            .and_then(Value::as_str)
+        if let Some(key) = &question.title_i18n_key
             && let Some(value) =
                 resolve_i18n_value(&resolved, key, requested_locale, default_locale)
         {
             question.title = value;
         }
-        if let Some(key) = spec_question
-            .get("description_i18n")
-            .and_then(|value| value.get("key"))
-            .and_then(Value::as_str)
+        if let Some(key) = &question.description_i18n_key
             && let Some(value) =
                 resolve_i18n_value(&resolved, key, requested_locale, default_locale)
         {
@@ -801,7 +1349,7 @@ fn apply_i18n_to_payload(payload: &mut RenderPayload, spec_value: &Value, ctx: &
 fn respond_string(result: Result<String, ComponentError>) -> String {
     match result {
         Ok(value) => value,
-        Err(err) => json!({ "error": err.to_string() }).to_string(),
+        Err(err) => err.to_response().to_string(),
     }
 }
 
@@ -812,6 +1360,48 @@ pub fn render_text(form_id: &str, config_json: &str, ctx_json: &str, answers_jso
     )
 }
 
+/// Render the form as Markdown for chat tools and documentation.
+pub fn render_markdown(
+    form_id: &str,
+    config_json: &str,
+    ctx_json: &str,
+    answers_json: &str,
+) -> String {
+    respond_string(
+        render_payload(form_id, config_json, ctx_json, answers_json)
+            .map(|payload| qa_render_markdown(&payload)),
+    )
+}
+
+/// Review screen of the visible answers before the final submit: `entries`
+/// of `{id, title, value, status}` with `status` `answered`, `missing` or
+/// `invalid`, plus the same summary as `text`. Secrets stay masked and list
+/// answers show their item count.
+pub fn render_summary(
+    form_id: &str,
+    config_json: &str,
+    ctx_json: &str,
+    answers_json: &str,
+) -> String {
+    respond(
+        render_inputs(form_id, config_json, ctx_json, answers_json).and_then(
+            |(spec, ctx, answers)| {
+                let payload = payload_for(&spec, &ctx, &answers);
+                let summary = qa_render_summary(&payload, &spec.validate_with_ctx(&answers, &ctx));
+                let mut response =
+                    serde_json::to_value(&summary).map_err(ComponentError::JsonEncode)?;
+                response["text"] = Value::String(summary.to_text());
+                Ok(response)
+            },
+        ),
+    )
+}
+
+/// Render the form as the JSON UI payload.
+///
+/// With `ctx.debug: true` the payload also lists `visibility_diagnostics`:
+/// each `visible_if` that could not be evaluated, why, and the part of the
+/// condition that failed.
 pub fn render_json_ui(
     form_id: &str,
     config_json: &str,
@@ -819,31 +1409,69 @@ pub fn render_json_ui(
     answers_json: &str,
 ) -> String {
     respond(
-        render_payload(form_id, config_json, ctx_json, answers_json)
-            .map(|payload| qa_render_json_ui(&payload)),
+        render_inputs(form_id, config_json, ctx_json, answers_json).and_then(
+            |(spec, ctx, answers)| {
+                let payload = payload_for(&spec, &ctx, &answers);
+                let mut ui = qa_render_json_ui(&payload);
+                if ctx.get("debug").and_then(Value::as_bool).unwrap_or(false) {
+                    let (answers, _) = spec.compute_answers(&answers, &ctx);
+                    let resolved =
+                        resolve_visibility_with_ctx(&spec, &answers, &ctx, VisibilityMode::Visible);
+                    ui["visibility_diagnostics"] = serde_json::to_value(resolved.diagnostics)
+                        .map_err(ComponentError::JsonEncode)?;
+                }
+                Ok(ui)
+            },
+        ),
     )
 }
 
+/// Render the pending question(s) as an Adaptive Card.
+///
+/// The layout follows the form presentation `card_mode`; hosts driving a flow
+/// pass the active step's mode as `ctx.card_mode` to override it. `page`
+/// cards show the page holding the next question unless `ctx.card.page`
+/// names one, which is how hosts follow a card's previous action.
 pub fn render_card(form_id: &str, config_json: &str, ctx_json: &str, answers_json: &str) -> String {
     respond(
-        render_payload(form_id, config_json, ctx_json, answers_json).map(|payload| {
-            let mut card = qa_render_card(&payload);
-            let ctx = parse_runtime_context(ctx_json);
-            if i18n_debug_enabled(&ctx)
-                && let Ok(spec_value) = load_form_spec_value(config_json)
-            {
-                attach_i18n_debug_metadata(&mut card, &payload, &spec_value.spec_value);
+        render_inputs(form_id, config_json, ctx_json, answers_json).map(|(spec, ctx, answers)| {
+            let payload = payload_for(&spec, &ctx, &answers);
+            let mut card = qa_render_card(&payload, &render_options(&spec, &ctx));
+            if i18n_debug_enabled(&ctx) {
+                attach_i18n_debug_metadata(&mut card, &payload);
             }
             card
         }),
     )
 }
 
-fn submission_progress(payload: &RenderPayload) -> Value {
-    json!({
-        "answered": payload.progress.answered,
-        "total": payload.progress.total,
-    })
+/// Render options from the form defaults and the runtime context.
+///
+/// `ctx.card_mode` overrides the layout and `ctx.card` the Adaptive Card
+/// version and features (`CardOptions`); `ctx.full_current_values: true`
+/// embeds long list answers whole instead of their first items.
+fn render_options(spec: &FormSpec, ctx: &Value) -> RenderOptions {
+    let mut options = RenderOptions::for_form(spec);
+    if let Some(card_mode) = ctx
+        .get("card_mode")
+        .and_then(|mode| serde_json::from_value::<CardMode>(mode.clone()).ok())
+    {
+        options = options.with_card_mode(card_mode);
+    }
+    if let Some(card) = ctx
+        .get("card")
+        .and_then(|card| serde_json::from_value::<CardOptions>(card.clone()).ok())
+    {
+        options = options.with_card_options(card);
+    }
+    if ctx
+        .get("full_current_values")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        options = options.with_max_current_value_items(None);
+    }
+    options
 }
 
 fn build_error_response(
@@ -855,16 +1483,17 @@ fn build_error_response(
     Ok(json!({
         "status": "error",
         "next_question_id": payload.next_question_id,
-        "progress": submission_progress(payload),
+        "progress": payload.progress,
         "answers": answers,
         "validation": validation_value,
     }))
 }
 
 fn build_success_response(
+    spec: &FormSpec,
     payload: &RenderPayload,
     answers: Value,
-    store_ctx: &StoreContext,
+    store_ctx: StoreContext,
 ) -> Value {
     let status = if payload.next_question_id.is_some() {
         "need_input"
@@ -872,13 +1501,34 @@ fn build_success_response(
         "complete"
     };
 
-    json!({
+    let mut response = json!({
         "status": status,
         "next_question_id": payload.next_question_id,
-        "progress": submission_progress(payload),
+        "progress": payload.progress,
         "answers": answers,
-        "store": store_ctx.to_value(),
-    })
+        "store": store_ctx.into_value(),
+    });
+    let secret_writes = secret_path_writes(spec, &response["answers"]);
+    if !secret_writes.is_empty() {
+        response["audit"] = json!({ "secret_writes": secret_writes });
+    }
+    response
+}
+
+/// Secrets written implicitly through `secret_path`, without their values.
+fn secret_path_writes(spec: &FormSpec, answers: &Value) -> Vec<Value> {
+    spec.questions
+        .iter()
+        .filter(|question| question.secret && answers.get(&question.id).is_some())
+        .filter_map(|question| {
+            question.secret_path.as_deref().map(|path| {
+                json!({
+                    "question_id": question.id,
+                    "path": secret_pointer(path),
+                })
+            })
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -887,13 +1537,81 @@ struct SubmissionPlan {
     validation: qa_spec::ValidationResult,
     payload: RenderPayload,
     effects: Vec<StoreOp>,
+    computed: Vec<ComputedReport>,
+    normalized: Vec<AnswerChange>,
+    pruned: Vec<String>,
 }
 
-fn build_submission_plan(spec: &FormSpec, ctx: &Value, answers: Value) -> SubmissionPlan {
-    let validation = validate(spec, &answers);
-    let payload = build_render_payload(spec, ctx, &answers);
+/// `previous` holds the answers a patch edits, so changes to read-only
+/// answers can be rejected.
+fn build_submission_plan(
+    spec: &CompiledFormSpec,
+    ctx: &Value,
+    answers: Value,
+    previous: Option<&Value>,
+) -> SubmissionPlan {
+    let coerce = |answers: Value| {
+        if coerce_answers_requested(ctx) {
+            coerce_answers(spec, &answers)
+        } else {
+            answers
+        }
+    };
+    let answers = coerce(answers);
+    let (mut submitted, normalized) = apply_normalization_with_changes(spec, &answers);
+    let (mut answers, mut computed) = spec.compute_answers(&submitted, ctx);
+    // One visibility pass serves validation, the payload, and its schema.
+    let strict = strict_visibility_requested(ctx);
+    let mode = if strict {
+        VisibilityMode::Error
+    } else {
+        VisibilityMode::Visible
+    };
+    let mut resolved = resolve_visibility_with_ctx(spec, &answers, ctx, mode);
+    let pruned = if prune_hidden_requested(ctx) {
+        prune_hidden_answers(&mut submitted, &resolved.map)
+    } else {
+        Vec::new()
+    };
+    if !pruned.is_empty() {
+        // Computed answers and conditions may have read the pruned values.
+        (answers, computed) = spec.compute_answers(&submitted, ctx);
+        resolved = resolve_visibility_with_ctx(spec, &answers, ctx, mode);
+    }
+    let visibility = resolved.map;
+    let mut validation = spec.validate_with_visibility_and_ctx(&answers, ctx, &visibility);
+    if strict && !resolved.diagnostics.is_empty() {
+        validation.valid = false;
+        validation
+            .errors
+            .extend(visibility_errors(&resolved.diagnostics));
+    }
+    if let Some(previous) = previous {
+        let previous = apply_normalization_with_changes(spec, &coerce(previous.clone())).0;
+        let changes = read_only_changes(spec, &previous, &answers, ctx, &visibility);
+        if !changes.is_empty() {
+            validation.valid = false;
+            validation.errors.extend(changes);
+        }
+    }
+    // Validation only sees the computed answers, so overrides are checked here.
+    let conflicts = computed_conflicts(spec, &submitted, &answers, &visibility);
+    if !conflicts.is_empty() {
+        validation.valid = false;
+        validation.errors.extend(conflicts);
+    }
+    if let Some(catalog) = message_catalog(spec, ctx) {
+        catalog.localize(spec, &mut validation);
+    }
+    let payload = spec.build_render_payload_with_visibility(
+        ctx,
+        &answers,
+        None,
+        &render_options(spec, ctx),
+        &visibility,
+    );
     let effects = if validation.valid {
-        spec.store.clone()
+        effective_store_ops(spec, &answers)
     } else {
         Vec::new()
     };
@@ -902,7 +1620,205 @@ fn build_submission_plan(spec: &FormSpec, ctx: &Value, answers: Value) -> Submis
         validation,
         payload,
         effects,
+        computed,
+        normalized,
+        pruned,
+    }
+}
+
+/// Removes the answers of questions `visibility` hides and returns their ids
+/// in answer order.
+fn prune_hidden_answers(answers: &mut Value, visibility: &VisibilityMap) -> Vec<String> {
+    let Some(answers) = answers.as_object_mut() else {
+        return Vec::new();
+    };
+    let hidden = answers
+        .keys()
+        .filter(|id| visibility.get(id.as_str()) == Some(&false))
+        .cloned()
+        .collect::<Vec<_>>();
+    for id in &hidden {
+        answers.remove(id);
+    }
+    hidden
+}
+
+/// Execute a submission plan: report validation errors or apply its store effects.
+fn execute_submission(
+    spec: &FormSpec,
+    ctx: &Value,
+    plan: SubmissionPlan,
+    attempted: &[String],
+) -> Result<Value, ComponentError> {
+    let progress_state = advance_progress(spec, ctx, &plan.validated_patch, attempted);
+    let mut response = if !plan.validation.valid {
+        build_error_response(&plan.payload, plan.validated_patch, &plan.validation)?
+    } else {
+        let mut store_ctx = StoreContext::with_answers(ctx, plan.validated_patch.clone());
+        let host_available = secrets_host_available(ctx);
+        store_ctx.apply_ops(&plan.effects, spec.secrets_policy.as_ref(), host_available)?;
+        build_success_response(spec, &plan.payload, plan.validated_patch, store_ctx)
+    };
+    if !plan.computed.is_empty() {
+        response["computed"] =
+            serde_json::to_value(&plan.computed).map_err(ComponentError::JsonEncode)?;
+    }
+    if !plan.pruned.is_empty() {
+        response["pruned"] =
+            serde_json::to_value(&plan.pruned).map_err(ComponentError::JsonEncode)?;
+    }
+    // Answers as stored after `normalize` transforms, secrets withheld.
+    if !plan.normalized.is_empty() {
+        response["normalized"] =
+            serde_json::to_value(&plan.normalized).map_err(ComponentError::JsonEncode)?;
+    }
+    // Warnings never block the submission, so both outcomes carry them.
+    if !plan.validation.warnings.is_empty() {
+        response["warnings"] =
+            serde_json::to_value(&plan.validation.warnings).map_err(ComponentError::JsonEncode)?;
+    }
+    if !reveal_secrets(ctx) {
+        response["answers"] = redact_answers(spec, &response["answers"]);
+        if let Some(store_answers) = response.pointer_mut("/store/answers") {
+            *store_answers = redact_answers(spec, store_answers);
+        }
+    }
+    if let Some(meta) = advance_meta(ctx, &SystemClock) {
+        response["meta"] = serde_json::to_value(meta).map_err(ComponentError::JsonEncode)?;
+    }
+    if let Some(progress_state) = progress_state {
+        response["progress_state"] =
+            serde_json::to_value(progress_state).map_err(ComponentError::JsonEncode)?;
+    }
+    Ok(response)
+}
+
+/// Response answers redact secret questions unless the caller sets
+/// `ctx.reveal_secrets` (e.g. a host that round-trips answers between calls).
+fn reveal_secrets(ctx: &Value) -> bool {
+    ctx.get("reveal_secrets")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Validation messages are written out when the caller sets `ctx.locale` or
+/// `ctx.messages`; otherwise they stay message keys. `ctx.messages` maps
+/// locales (`id`, `pt-BR`) to [`MessageCatalog`]s. The catalog for the
+/// requested locale (else the spec's default locale), its language, or `en` is
+/// used, with the built-in English messages filling any gaps.
+fn message_catalog(spec: &FormSpec, ctx: &Value) -> Option<MessageCatalog> {
+    let requested = ctx.get("locale").and_then(Value::as_str);
+    let messages = ctx.get("messages").and_then(Value::as_object);
+    if requested.is_none() && messages.is_none() {
+        return None;
+    }
+    let default_locale = spec
+        .presentation
+        .as_ref()
+        .and_then(|presentation| presentation.default_locale.as_deref());
+    let english = MessageCatalog::english();
+    let catalog = normalize_locale_chain(requested.or(default_locale))
+        .iter()
+        .find_map(|locale| messages?.get(locale))
+        .and_then(|catalog| serde_json::from_value::<MessageCatalog>(catalog.clone()).ok());
+    Some(match catalog {
+        Some(catalog) => catalog.or(&english),
+        None => english,
+    })
+}
+
+/// String answers are converted to their question types (`"42"` for an
+/// integer) when the caller sets `ctx.qa_coerce`, as hosts that collect HTML
+/// form or adaptive card inputs do. The converted values are stored.
+fn coerce_answers_requested(ctx: &Value) -> bool {
+    ctx.get("qa_coerce")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// With `ctx.prune_hidden` set, answers to questions hidden by the submitted
+/// answers are dropped before validation and storage, so flipping a toggle
+/// discards the branch it closes. They are reported under `pruned` and stay
+/// gone when the branch is shown again.
+fn prune_hidden_requested(ctx: &Value) -> bool {
+    ctx.get("prune_hidden")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// With `ctx.strict_visibility` set, a `visible_if` that cannot be evaluated
+/// rejects the submission with a `visibility_error` instead of showing the
+/// question.
+fn strict_visibility_requested(ctx: &Value) -> bool {
+    ctx.get("strict_visibility")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Per-question progress when the caller opts in with `ctx.track_progress`.
+///
+/// A previous response's `progress_state` passed back as `ctx.progress_state`
+/// is updated; otherwise tracking starts fresh. Every `attempted` question
+/// counts one attempt, whether or not the submission validates.
+fn advance_progress(
+    spec: &FormSpec,
+    ctx: &Value,
+    answers: &Value,
+    attempted: &[String],
+) -> Option<ProgressState> {
+    if !ctx
+        .get("track_progress")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let mut state = ctx
+        .get("progress_state")
+        .and_then(|state| serde_json::from_value::<ProgressState>(state.clone()).ok())
+        .unwrap_or_default();
+    for question_id in attempted {
+        state.record_attempt(question_id);
+    }
+    state.refresh(spec, answers, ctx);
+    if !reveal_secrets(ctx) {
+        for question in spec.questions.iter().filter(|question| question.secret) {
+            if let Some(last_value) = state
+                .questions
+                .get_mut(&question.id)
+                .and_then(|entry| entry.last_value.as_mut())
+            {
+                *last_value = redacted_marker();
+            }
+        }
+    }
+    Some(state)
+}
+
+/// Meta for this submission when the caller opts in with `ctx.track_meta`.
+///
+/// A previous response's `meta` passed back as `ctx.meta` is advanced; otherwise
+/// a fresh record is started. `ctx.actor`/`ctx.channel` fill in the source.
+fn advance_meta(ctx: &Value, clock: &dyn Clock) -> Option<Meta> {
+    if !ctx
+        .get("track_meta")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let mut meta = ctx
+        .get("meta")
+        .and_then(|meta| serde_json::from_value::<Meta>(meta.clone()).ok())
+        .unwrap_or_default();
+    if let Some(actor) = ctx.get("actor").and_then(Value::as_str) {
+        meta.actor = Some(actor.to_string());
+    }
+    if let Some(channel) = ctx.get("channel").and_then(Value::as_str) {
+        meta.channel = Some(channel.to_string());
     }
+    meta.touch(clock);
+    Some(meta)
 }
 
 pub fn submit_patch(
@@ -916,25 +1832,23 @@ pub fn submit_patch(
     // Compatibility wrapper: this endpoint now follows a deterministic
     // plan->execute split internally while preserving existing response shape.
     respond(ensure_form(form_id, config_json).and_then(|spec| {
-        let ctx = parse_runtime_context(ctx_json);
-        let value: Value = serde_json::from_str(value_json).map_err(ComponentError::ConfigParse)?;
-        let mut answers = parse_answers(answers_json)
-            .as_object()
-            .cloned()
-            .unwrap_or_default();
+        let inputs = Inputs::from_config(config_json);
+        let ctx = inputs.context(ctx_json)?;
+        let value: Value =
+            serde_json::from_str(value_json).map_err(|err| inputs.reject("value", err))?;
+        let previous = inputs.answers(answers_json)?;
+        let mut answers = previous.as_object().cloned().unwrap_or_default();
         answers.insert(question_id.to_string(), value);
-        let plan = build_submission_plan(&spec, &ctx, Value::Object(answers));
-
-        if !plan.validation.valid {
-            return build_error_response(&plan.payload, plan.validated_patch, &plan.validation);
+        let answers = Value::Object(answers);
+        if let Some(response) = oversized_response(&spec, &answers)? {
+            return Ok(response);
         }
-
-        let mut store_ctx = StoreContext::from_value(&ctx);
-        store_ctx.answers = plan.validated_patch.clone();
-        let host_available = secrets_host_available(&ctx);
-        store_ctx.apply_ops(&plan.effects, spec.secrets_policy.as_ref(), host_available)?;
-        let response = build_success_response(&plan.payload, plan.validated_patch, &store_ctx);
-        Ok(response)
+        let answers = merge_prefill(&spec, config_json, &ctx, answers)?;
+        let previous = merge_prefill(&spec, config_json, &ctx, previous)?;
+        let (answers, _) = autofill_defaults(&spec, &answers, &ctx)?;
+        let (previous, _) = autofill_defaults(&spec, &previous, &ctx)?;
+        let plan = build_submission_plan(&spec, &ctx, answers, Some(&previous));
+        execute_submission(&spec, &ctx, plan, &[question_id.to_string()])
     }))
 }
 
@@ -942,23 +1856,167 @@ pub fn submit_all(form_id: &str, config_json: &str, ctx_json: &str, answers_json
     // Compatibility wrapper: this endpoint now follows a deterministic
     // plan->execute split internally while preserving existing response shape.
     respond(ensure_form(form_id, config_json).and_then(|spec| {
-        let ctx = parse_runtime_context(ctx_json);
-        let answers = parse_answers(answers_json);
-        let plan = build_submission_plan(&spec, &ctx, answers);
-
-        if !plan.validation.valid {
-            return build_error_response(&plan.payload, plan.validated_patch, &plan.validation);
+        let inputs = Inputs::from_config(config_json);
+        let ctx = inputs.context(ctx_json)?;
+        let config: ComponentConfig =
+            serde_json::from_str(config_json).map_err(ComponentError::ConfigParse)?;
+        let submitted = match verify_answer_signature(&spec, &config, &ctx, answers_json)? {
+            SignatureCheck::NotRequired => {
+                submitted_answer_set(&spec, &config, &inputs, answers_json)?
+            }
+            SignatureCheck::Verified(set) => *set,
+            SignatureCheck::Rejected(response) => return Ok(response),
+        };
+        let (answers, migration) = match negotiate_version(&spec, &config, submitted) {
+            Ok(negotiated) => negotiated,
+            Err(error) => return Ok(migration_rejected(&error)),
+        };
+        let attempted = answers
+            .as_object()
+            .map(|answers| answers.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        if let Some(response) = oversized_response(&spec, &answers)? {
+            return Ok(response);
+        }
+        let answers = merge_prefill(&spec, config_json, &ctx, answers)?;
+        let (answers, _) = autofill_defaults(&spec, &answers, &ctx)?;
+        let plan = build_submission_plan(&spec, &ctx, answers, None);
+        let mut response = execute_submission(&spec, &ctx, plan, &attempted)?;
+        if let (Some(report), Some(object)) = (migration, response.as_object_mut()) {
+            object.insert(
+                "migration".into(),
+                serde_json::to_value(report).map_err(ComponentError::JsonEncode)?,
+            );
         }
-
-        let mut store_ctx = StoreContext::from_value(&ctx);
-        store_ctx.answers = plan.validated_patch.clone();
-        let host_available = secrets_host_available(&ctx);
-        store_ctx.apply_ops(&plan.effects, spec.secrets_policy.as_ref(), host_available)?;
-        let response = build_success_response(&plan.payload, plan.validated_patch, &store_ctx);
         Ok(response)
     }))
 }
 
+/// Answers over the spec's byte limits are turned away with their
+/// `payload_too_large` validation before anything else walks them; the
+/// answers are not echoed back.
+fn oversized_response(spec: &FormSpec, answers: &Value) -> Result<Option<Value>, ComponentError> {
+    reject_oversized(spec, answers)
+        .map(|validation| {
+            Ok(json!({
+                "status": "error",
+                "validation": serde_json::to_value(validation)
+                    .map_err(ComponentError::JsonEncode)?,
+            }))
+        })
+        .transpose()
+}
+
+/// Submitted answers as an answer set. With `migrate_answers` configured an
+/// `AnswerSet` document for this form keeps its recorded version; anything
+/// else is taken as plain answers for the current version.
+fn submitted_answer_set(
+    spec: &FormSpec,
+    config: &ComponentConfig,
+    inputs: &Inputs,
+    answers_json: &str,
+) -> Result<AnswerSet, ComponentError> {
+    if config.migrate_answers
+        && let Ok(set) = serde_json::from_str::<AnswerSet>(answers_json)
+        && set.form_id == spec.id
+    {
+        return Ok(set);
+    }
+    let mut set = AnswerSet::new(spec.id.clone(), spec.version.clone());
+    set.answers = inputs.answers(answers_json)?;
+    Ok(set)
+}
+
+/// Bring an answer set recorded against another spec version up to date.
+///
+/// Returns the answers and, when a migration ran, its report.
+fn negotiate_version(
+    spec: &FormSpec,
+    config: &ComponentConfig,
+    set: AnswerSet,
+) -> Result<(Value, Option<qa_spec::MigrationReport>), MigrationError> {
+    if !config.migrate_answers || set.spec_version == spec.version {
+        return Ok((set.answers, None));
+    }
+    let (migrated, report) = set.migrate(spec)?;
+    Ok((migrated.answers, Some(report)))
+}
+
+fn migration_rejected(error: &MigrationError) -> Value {
+    json!({
+        "status": "error",
+        "error": {
+            "code": "answer_migration_failed",
+            "message": error.to_string(),
+            "stuck_fields": error.stuck_fields(),
+        },
+    })
+}
+
+enum SignatureCheck {
+    NotRequired,
+    Verified(Box<AnswerSet>),
+    Rejected(Value),
+}
+
+/// Enforce the config's `answer_signature` requirement on a submitted answer set.
+///
+/// Unsigned sets are rejected with `answer_signature_missing`; sets whose
+/// signature does not match (wrong key, other form, modified answers) with
+/// `answer_signature_invalid`.
+fn verify_answer_signature(
+    spec: &FormSpec,
+    config: &ComponentConfig,
+    ctx: &Value,
+    answers_json: &str,
+) -> Result<SignatureCheck, ComponentError> {
+    let Some(requirement) = &config.answer_signature else {
+        return Ok(SignatureCheck::NotRequired);
+    };
+    let secret_ref = requirement
+        .secret_ref
+        .as_deref()
+        .unwrap_or(&requirement.key_id);
+    let key = ctx
+        .get("secrets")
+        .and_then(|secrets| secrets.pointer(&secret_pointer(secret_ref)))
+        .and_then(Value::as_str)
+        .ok_or_else(|| ComponentError::SigningKeyUnavailable(secret_ref.to_string()))?;
+
+    let Ok(set) = serde_json::from_str::<AnswerSet>(answers_json) else {
+        return Ok(signature_rejected(
+            &requirement.key_id,
+            SignatureError::Missing,
+        ));
+    };
+    // Older versions are accepted when they are migrated after verification.
+    let version_accepted = set.spec_version == spec.version || config.migrate_answers;
+    let outcome = if set.form_id != spec.id || !version_accepted {
+        Err(SignatureError::Invalid)
+    } else {
+        set.verify_with_key_id(&requirement.key_id, key.as_bytes())
+    };
+    Ok(match outcome {
+        Ok(()) => SignatureCheck::Verified(Box::new(set)),
+        Err(error) => signature_rejected(&requirement.key_id, error),
+    })
+}
+
+fn signature_rejected(key_id: &str, error: SignatureError) -> SignatureCheck {
+    let code = match error {
+        SignatureError::Missing => "answer_signature_missing",
+        _ => "answer_signature_invalid",
+    };
+    SignatureCheck::Rejected(json!({
+        "status": "error",
+        "error": {
+            "code": code,
+            "key_id": key_id,
+            "message": error.to_string(),
+        },
+    }))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NormalizedMode {
     Setup,
@@ -1031,6 +2089,9 @@ fn payload_config_json(payload: &Value) -> String {
 }
 
 fn payload_answers(payload: &Value) -> Value {
+    if let Some(set) = payload_answer_set(payload) {
+        return set.answers;
+    }
     if let Some(answers) = payload.get("answers") {
         if let Some(raw) = answers.as_str() {
             return serde_json::from_str(raw).unwrap_or_else(|_| Value::Object(Map::new()));
@@ -1046,6 +2107,51 @@ fn payload_answers(payload: &Value) -> Value {
     Value::Object(Map::new())
 }
 
+/// Answer set passed as canonical CBOR under `answer_set_cbor`, either hex
+/// encoded or as an array of bytes.
+fn payload_answer_set(payload: &Value) -> Option<AnswerSet> {
+    let bytes = match payload.get("answer_set_cbor")? {
+        Value::String(hex) => decode_hex(hex)?,
+        Value::Array(items) => items
+            .iter()
+            .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+    AnswerSet::from_cbor(&bytes).ok()
+}
+
+/// Migrate an `answer_set_cbor` recorded against an older spec version when
+/// the config enables `migrate_answers`; other answers pass through.
+fn migrate_payload_answers(
+    spec: &FormSpec,
+    config_json: &str,
+    payload: &Value,
+    answers: Value,
+) -> Result<Value, MigrationError> {
+    let config: ComponentConfig = serde_json::from_str(config_json).unwrap_or_default();
+    match payload_answer_set(payload) {
+        Some(set) if set.form_id == spec.id => {
+            negotiate_version(spec, &config, set).map(|(answers, _)| answers)
+        }
+        _ => Ok(answers),
+    }
+}
+
+fn decode_hex(raw: &str) -> Option<Vec<u8>> {
+    if !raw.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..raw.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(raw.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
 fn payload_ctx_json(payload: &Value) -> String {
     if let Some(ctx_json) = payload.get("ctx_json").and_then(Value::as_str) {
         return ctx_json.to_string();
@@ -1124,7 +2230,10 @@ fn answered_bootstrap_path(answers: &Value) -> Option<String> {
 fn question_kind(question: &qa_spec::QuestionSpec) -> QuestionKind {
     match question.kind {
         QuestionType::Boolean => QuestionKind::Bool,
-        QuestionType::Integer | QuestionType::Number => QuestionKind::Number,
+        QuestionType::Integer
+        | QuestionType::Number
+        | QuestionType::Duration
+        | QuestionType::Rating => QuestionKind::Number,
         QuestionType::Enum => {
             let options = question
                 .choices
@@ -1141,7 +2250,16 @@ fn question_kind(question: &qa_spec::QuestionSpec) -> QuestionKind {
                 .collect();
             QuestionKind::Choice { options }
         }
-        QuestionType::List | QuestionType::String => QuestionKind::Text,
+        QuestionType::List
+        | QuestionType::Object
+        | QuestionType::String
+        | QuestionType::Date
+        | QuestionType::MultiEnum
+        | QuestionType::StringList
+        | QuestionType::Path
+        | QuestionType::Color
+        | QuestionType::Markdown
+        | QuestionType::Time => QuestionKind::Text,
     }
 }
 
@@ -1184,9 +2302,9 @@ fn component_qa_spec(
 ) -> Result<ComponentQaSpec, ComponentError> {
     let spec = ensure_form(form_id, config_json)?;
     let loaded = load_form_spec_value(config_json)?;
-    let ctx = parse_runtime_context(ctx_json);
+    let ctx = Inputs::from_config(config_json).context(ctx_json)?;
     let locale = ctx.get("locale").and_then(Value::as_str);
-    let visibility = resolve_visibility(&spec, answers, VisibilityMode::Visible);
+    let visibility = resolve_visibility_with_ctx(&spec, answers, &ctx, VisibilityMode::Visible).map;
     let (title_key, description_key) = mode_title(mode);
     let questions = spec
         .questions
@@ -1243,9 +2361,10 @@ fn component_qa_spec(
 
     Ok(ComponentQaSpec {
         mode: mode.to_qa_mode(),
-        title: I18nText::new(title_key, Some(spec.title)),
+        title: I18nText::new(title_key, Some(spec.title.clone())),
         description: spec
             .description
+            .clone()
             .map(|description| I18nText::new(description_key, Some(description))),
         questions,
         defaults: BTreeMap::new(),
@@ -1329,6 +2448,24 @@ pub fn apply_answers(mode: NormalizedMode, payload: &Value) -> Value {
 
     match ensure_form(&form_id, &config_json) {
         Ok(spec) => {
+            let answers = match migrate_payload_answers(&spec, &config_json, payload, answers) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    return json!({
+                        "ok": false,
+                        "warnings": [],
+                        "errors": [{
+                            "key": "qa.error.answer_migration_failed",
+                            "message": error.to_string(),
+                            "stuck_fields": error.stuck_fields(),
+                        }],
+                        "meta": {
+                            "mode": mode.as_str(),
+                            "version": "v1"
+                        }
+                    });
+                }
+            };
             let validation = validate(&spec, &answers);
             if !validation.valid {
                 return json!({
@@ -1346,18 +2483,22 @@ pub fn apply_answers(mode: NormalizedMode, payload: &Value) -> Value {
                 Value::Object(map) => map,
                 _ => Map::new(),
             };
-            if let Value::Object(answers) = answers {
+            if let Value::Object(answers) = &answers {
                 for (key, value) in answers {
-                    config.insert(key, value);
+                    config.insert(key.clone(), value.clone());
                 }
             }
             if mode == NormalizedMode::Remove {
                 config.insert("enabled".to_string(), Value::Bool(false));
             }
+            let mut answer_set = AnswerSet::new(spec.id.clone(), spec.version.clone());
+            answer_set.answers = answers;
+            let answer_set_cbor = answer_set.to_cbor().map(|bytes| encode_hex(&bytes)).ok();
 
             json!({
                 "ok": true,
                 "config": config,
+                "answer_set_cbor": answer_set_cbor,
                 "warnings": [],
                 "errors": [],
                 "meta": {
@@ -0,0 +1,110 @@
+use component_qa::{back, next, render_json_ui, submit_all};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn submit_all_stores_computed_totals() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("order.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "order",
+            "title": "Order",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "price", "type": "integer", "title": "Price", "required": true },
+                { "id": "quantity", "type": "integer", "title": "Quantity", "required": true },
+                {
+                    "id": "total",
+                    "type": "integer",
+                    "title": "Total",
+                    "computed": {
+                        "op": "mul",
+                        "left": { "op": "answer", "path": "price" },
+                        "right": { "op": "answer", "path": "quantity" }
+                    }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let submit = |answers: Value| -> Value {
+        let raw = submit_all("order", &config, "{}", &answers.to_string());
+        serde_json::from_str(&raw).expect("json")
+    };
+
+    let response = submit(json!({ "price": 12, "quantity": 3 }));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["total"], 36);
+    assert_eq!(response["computed"][0]["status"], "computed");
+
+    let response = submit(json!({ "price": 12, "quantity": "three" }));
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["computed"][0]["status"], "unresolved");
+    assert!(response["answers"].get("total").is_none(), "{response}");
+}
+
+#[test]
+fn questions_hidden_by_computed_values_are_skipped_by_next_and_back() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("sum.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "sum",
+            "title": "Sum",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "a", "type": "integer", "title": "A", "required": true },
+                { "id": "b", "type": "integer", "title": "B", "required": true },
+                {
+                    "id": "total",
+                    "type": "integer",
+                    "title": "Total",
+                    "computed": {
+                        "op": "add",
+                        "left": { "op": "answer", "path": "a" },
+                        "right": { "op": "answer", "path": "b" }
+                    }
+                },
+                {
+                    "id": "why_small",
+                    "type": "string",
+                    "title": "Why so small?",
+                    "required": true,
+                    "visible_if": {
+                        "op": "lt",
+                        "left": { "op": "answer", "path": "total" },
+                        "right": { "op": "literal", "value": 10 }
+                    }
+                },
+                { "id": "notes", "type": "string", "title": "Notes" }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let parse = |raw: String| -> Value { serde_json::from_str(&raw).expect("json") };
+
+    let large = json!({ "a": 6, "b": 6 }).to_string();
+    let response = parse(next("sum", &config, &large));
+    assert_eq!(response["next_question_id"], "notes", "{response}");
+    let ui = parse(render_json_ui("sum", &config, "{}", &large));
+    assert_eq!(ui["next_question_id"], response["next_question_id"], "{ui}");
+    assert_eq!(ui["progress"], response["progress"], "{ui}");
+    let submitted = parse(submit_all("sum", &config, "{}", &large));
+    assert_eq!(submitted["status"], response["status"], "{submitted}");
+    assert_eq!(submitted["next_question_id"], "notes");
+    let previous = parse(back("sum", &config, "{}", &large, "notes"));
+    assert_eq!(previous["next_question_id"], "b", "{previous}");
+
+    let small = json!({ "a": 1, "b": 2 }).to_string();
+    let response = parse(next("sum", &config, &small));
+    assert_eq!(response["next_question_id"], "why_small", "{response}");
+    let previous = parse(back("sum", &config, "{}", &small, "notes"));
+    assert_eq!(previous["next_question_id"], "why_small", "{previous}");
+}
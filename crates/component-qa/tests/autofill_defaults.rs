@@ -0,0 +1,152 @@
+use component_qa::{next, render_card, render_json_ui, submit_all, submit_patch};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir, questions: Value) -> String {
+    write_form_with_policy(
+        dir,
+        json!({ "skip_answered": true, "autofill_defaults": true }),
+        questions,
+    )
+}
+
+fn write_form_with_policy(dir: &TempDir, policy: Value, questions: Value) -> String {
+    let form_path = dir.path().join("service.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "service",
+            "title": "Service",
+            "version": "1.0.0",
+            "progress_policy": policy,
+            "questions": questions
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn parse(raw: String) -> Value {
+    serde_json::from_str(&raw).expect("json")
+}
+
+#[test]
+fn defaulted_forms_complete_with_typed_answers() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(
+        &dir,
+        json!([
+            { "id": "public", "type": "boolean", "title": "Public", "required": true, "default_value": "true" },
+            { "id": "replicas", "type": "integer", "title": "Replicas", "required": true, "default_value": "3" },
+            {
+                "id": "tier",
+                "type": "enum",
+                "title": "Tier",
+                "required": true,
+                "choices": ["free", "pro"],
+                "default_value": "pro"
+            },
+            {
+                "id": "quota",
+                "type": "number",
+                "title": "Quota",
+                "required": true,
+                "default_value": "2.5",
+                "visible_if": { "op": "eq", "left": { "op": "answer", "path": "tier" }, "right": { "op": "literal", "value": "free" } }
+            }
+        ]),
+    );
+
+    let response = parse(submit_all("service", &config, "{}", "{}"));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(
+        response["answers"],
+        json!({ "public": true, "replicas": 3, "tier": "pro" }),
+        "hidden questions are not autofilled"
+    );
+    assert_eq!(response["store"]["answers"], response["answers"]);
+
+    // Progression sees the same materialized defaults as submit.
+    let progress = parse(next("service", &config, "{}"));
+    assert_eq!(progress["status"], "complete", "{progress}");
+    assert_eq!(progress["next_question_id"], Value::Null);
+    let ui = parse(render_json_ui("service", &config, "{}", "{}"));
+    assert_eq!(ui["status"], "complete", "{ui}");
+    assert_eq!(ui["next_question_id"], Value::Null);
+
+    // Answers the caller gives win over defaults.
+    let response = parse(submit_patch(
+        "service", &config, "{}", "{}", "replicas", "5",
+    ));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["replicas"], 5);
+    assert_eq!(response["answers"]["public"], true);
+}
+
+#[test]
+fn malformed_defaults_are_reported_with_their_question() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(
+        &dir,
+        json!([
+            { "id": "replicas", "type": "integer", "title": "Replicas", "default_value": "three" }
+        ]),
+    );
+
+    let response = parse(submit_all("service", &config, "{}", "{}"));
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["error"]["code"], "invalid_default");
+    assert_eq!(response["error"]["question_id"], "replicas");
+    assert_eq!(
+        response["error"]["message"],
+        "invalid form default: default_value 'three' of question 'replicas' is not a valid integer"
+    );
+}
+
+#[test]
+fn counted_defaults_are_skipped_and_stored_only_when_required() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form_with_policy(
+        &dir,
+        json!({ "skip_answered": true, "treat_default_as_answered": true }),
+        json!([
+            { "id": "advanced", "type": "boolean", "title": "Advanced" },
+            { "id": "replicas", "type": "integer", "title": "Replicas", "required": true, "default_value": "2" },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "default_value": "eu",
+                "visible_if": { "op": "answer", "path": "advanced" }
+            },
+            { "id": "name", "type": "string", "title": "Name", "required": true }
+        ]),
+    );
+    let answers = json!({ "advanced": false }).to_string();
+
+    let response = parse(next("service", &config, &answers));
+    assert_eq!(response["next_question_id"], "name", "{response}");
+    // The hidden `region` default is not counted.
+    assert_eq!(
+        response["progress"],
+        json!({ "answered": 2, "total": 3, "remaining": ["name"], "percent": 66.7 })
+    );
+
+    let ui = parse(render_json_ui("service", &config, "{}", &answers));
+    assert_eq!(ui["progress"]["answered"], 2, "{ui}");
+    let card = render_card("service", &config, "{}", &answers);
+    assert!(
+        card.contains(r#"{"title":"Answered","value":"2"}"#),
+        "{card}"
+    );
+
+    let answers = json!({ "advanced": true, "name": "api" }).to_string();
+    let response = parse(submit_all("service", &config, "{}", &answers));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(
+        response["answers"],
+        json!({ "advanced": true, "name": "api", "replicas": 2 }),
+        "only the required default is written"
+    );
+}
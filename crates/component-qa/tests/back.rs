@@ -0,0 +1,71 @@
+use component_qa::back;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir) -> String {
+    let form_path = dir.path().join("deploy.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "deploy",
+            "title": "Deploy",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name", "required": true },
+                { "id": "token", "type": "string", "title": "Token", "secret": true },
+                {
+                    "id": "region",
+                    "type": "string",
+                    "title": "Region",
+                    "visible_if": { "op": "answer", "path": "advanced" }
+                },
+                { "id": "advanced", "type": "boolean", "title": "Advanced" }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn parse(raw: String) -> Value {
+    serde_json::from_str(&raw).expect("json")
+}
+
+#[test]
+fn back_points_at_the_previous_question_with_its_answer() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+    let answers = json!({ "name": "api", "token": "s3cret", "advanced": false }).to_string();
+
+    // `region` is hidden, so going back from `advanced` lands on `token`.
+    let response = parse(back("deploy", &config, "{}", &answers, "advanced"));
+    assert_eq!(response["status"], "need_input", "{response}");
+    assert_eq!(response["next_question_id"], "token");
+    assert_eq!(response["current_value"], json!({ "$redacted": true }));
+    assert_eq!(
+        response["progress"],
+        json!({ "answered": 3, "total": 3, "remaining": [], "percent": 100.0 })
+    );
+
+    let ctx = json!({ "reveal_secrets": true }).to_string();
+    let response = parse(back("deploy", &config, &ctx, &answers, "advanced"));
+    assert_eq!(response["current_value"], "s3cret");
+
+    let response = parse(back("deploy", &config, "{}", &answers, "token"));
+    assert_eq!(response["next_question_id"], "name");
+    assert_eq!(response["current_value"], "api");
+}
+
+#[test]
+fn back_from_the_first_question_is_at_start() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+
+    let response = parse(back("deploy", &config, "{}", "{}", "name"));
+    assert_eq!(response["status"], "at_start", "{response}");
+    assert!(response["next_question_id"].is_null());
+
+    let response = parse(back("deploy", &config, "{}", "{}", "missing"));
+    assert_eq!(response["error"]["code"], "unknown_question", "{response}");
+}
@@ -0,0 +1,83 @@
+use component_qa::render_card;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir) -> String {
+    let form_path = dir.path().join("replicas.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "replicas",
+            "title": "Replicas",
+            "version": "1.0.0",
+            "questions": [{
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "required": true,
+                "constraint": { "min": 1, "max": 9 }
+            }]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn input(card: &Value) -> &Value {
+    &card["body"][2]["items"][1]
+}
+
+#[test]
+fn ctx_card_selects_the_card_version_and_inputs() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+
+    let card: Value =
+        serde_json::from_str(&render_card("replicas", &config, "{}", "{}")).expect("json");
+    assert_eq!(card["version"], "1.3");
+    assert_eq!(input(&card)["type"], "Input.Text", "{card}");
+
+    let ctx = json!({ "card": { "version": "1.5" } }).to_string();
+    let card: Value =
+        serde_json::from_str(&render_card("replicas", &config, &ctx, "{}")).expect("json");
+    assert_eq!(card["version"], "1.5");
+    assert_eq!(input(&card)["type"], "Input.Number", "{card}");
+    assert_eq!(input(&card)["min"], 1);
+    assert_eq!(input(&card)["max"], 9);
+}
+
+#[test]
+fn ctx_card_pages_through_a_page_mode_form() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("paged.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "paged",
+            "title": "Paged",
+            "version": "1.0.0",
+            "presentation": { "card_mode": "page", "page_size": 2 },
+            "questions": [
+                { "id": "a", "type": "string", "title": "A" },
+                { "id": "b", "type": "string", "title": "B" },
+                { "id": "c", "type": "string", "title": "C" }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let answers = json!({ "a": "1", "b": "2" }).to_string();
+
+    let card: Value =
+        serde_json::from_str(&render_card("paged", &config, "{}", &answers)).expect("json");
+    assert_eq!(card["body"][2]["text"], "Page 2 of 2", "{card}");
+    assert_eq!(card["actions"][0]["data"]["qa"]["mode"], "page");
+
+    let ctx = json!({ "card": { "page": 0 } }).to_string();
+    let card: Value =
+        serde_json::from_str(&render_card("paged", &config, &ctx, &answers)).expect("json");
+    assert_eq!(card["body"][2]["text"], "Page 1 of 2", "{card}");
+    assert_eq!(card["body"][3]["items"][1]["value"], "1");
+}
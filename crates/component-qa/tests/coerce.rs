@@ -0,0 +1,60 @@
+use component_qa::submit_patch;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn qa_coerce_stores_typed_values() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("service.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "service",
+            "title": "Service",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "replicas", "type": "integer", "title": "Replicas", "required": true },
+                { "id": "public", "type": "boolean", "title": "Public", "required": true }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let submit = |ctx: Value, answers: Value, value: &str| -> Value {
+        let raw = submit_patch(
+            "service",
+            &config,
+            &ctx.to_string(),
+            &answers.to_string(),
+            "public",
+            &json!(value).to_string(),
+        );
+        serde_json::from_str(&raw).expect("json")
+    };
+
+    let response = submit(json!({}), json!({ "replicas": "3" }), "true");
+    assert_eq!(response["status"], "error", "{response}");
+
+    let response = submit(
+        json!({ "qa_coerce": true }),
+        json!({ "replicas": "3" }),
+        "true",
+    );
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(
+        response["answers"],
+        json!({ "replicas": 3, "public": true })
+    );
+
+    let response = submit(
+        json!({ "qa_coerce": true }),
+        json!({ "replicas": "3" }),
+        "1",
+    );
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(
+        response["validation"]["errors"][0]["code"], "type_mismatch",
+        "{response}"
+    );
+}
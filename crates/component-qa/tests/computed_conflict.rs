@@ -0,0 +1,60 @@
+use component_qa::{render_json_ui, submit_all, submit_patch};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn overriding_a_computed_answer_is_rejected() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("pricing.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "pricing",
+            "title": "Pricing",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "seats", "type": "integer", "title": "Seats", "required": true },
+                {
+                    "id": "total",
+                    "type": "integer",
+                    "title": "Total",
+                    "computed": { "op": "answer", "path": "seats" }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let parse = |raw: String| -> Value { serde_json::from_str(&raw).expect("json") };
+
+    let response = parse(submit_all(
+        "pricing",
+        &config,
+        "{}",
+        &json!({ "seats": 3, "total": 99 }).to_string(),
+    ));
+    assert_eq!(response["status"], "error", "{response}");
+    let error = &response["validation"]["errors"][0];
+    assert_eq!(error["code"], "computed_conflict");
+    assert_eq!(error["params"]["expected"], "3");
+
+    let response = parse(submit_patch(
+        "pricing",
+        &config,
+        "{}",
+        &json!({ "seats": 3 }).to_string(),
+        "total",
+        "3",
+    ));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["total"], 3);
+
+    let ui = parse(render_json_ui(
+        "pricing",
+        &config,
+        "{}",
+        &json!({ "seats": 3 }).to_string(),
+    ));
+    assert_eq!(ui["questions"][1]["read_only"], true, "{ui}");
+}
@@ -0,0 +1,53 @@
+use component_qa::{next_with_ctx, render_json_ui, submit_all};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn config_flag_hides_the_secrets_question() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("deploy.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "deploy",
+            "title": "Deploy",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name", "required": true },
+                {
+                    "id": "secret_ref",
+                    "type": "string",
+                    "title": "Secret reference",
+                    "required": true,
+                    "visible_if": { "op": "var", "path": "config.secrets_host_available" }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let parse = |raw: String| -> Value { serde_json::from_str(&raw).expect("json") };
+    let ctx =
+        |available: bool| json!({ "config": { "secrets_host_available": available } }).to_string();
+    let answers = json!({ "name": "api" }).to_string();
+
+    let ui = parse(render_json_ui("deploy", &config, &ctx(false), &answers));
+    assert_eq!(ui["questions"][1]["visible"], false, "{ui}");
+    let ui = parse(render_json_ui("deploy", &config, &ctx(true), &answers));
+    assert_eq!(ui["questions"][1]["visible"], true, "{ui}");
+
+    let next = parse(next_with_ctx("deploy", &config, &ctx(false), &answers));
+    assert_eq!(next["status"], "complete", "{next}");
+    let next = parse(next_with_ctx("deploy", &config, &ctx(true), &answers));
+    assert_eq!(next["next_question_id"], "secret_ref", "{next}");
+
+    let response = parse(submit_all("deploy", &config, &ctx(false), &answers));
+    assert_eq!(response["status"], "complete", "{response}");
+    let response = parse(submit_all("deploy", &config, &ctx(true), &answers));
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(
+        response["validation"]["missing_required"],
+        json!(["secret_ref"])
+    );
+}
@@ -0,0 +1,101 @@
+use component_qa::submit_all;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn submit_rejects_answers_failing_a_cross_field_rule() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("booking.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "booking",
+            "title": "Booking",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "check_in", "type": "integer", "title": "Check-in day", "required": true },
+                { "id": "check_out", "type": "integer", "title": "Check-out day", "required": true }
+            ],
+            "validations": [{
+                "id": "stay_length",
+                "message": "Check-out must be after check-in",
+                "code": "check_out_before_check_in",
+                "fields": ["check_out", "check_in"],
+                "condition": {
+                    "op": "gte",
+                    "left": { "op": "answer", "path": "check_in" },
+                    "right": { "op": "answer", "path": "check_out" }
+                }
+            }]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let submit = |answers: Value| -> Value {
+        serde_json::from_str(&submit_all("booking", &config, "{}", &answers.to_string()))
+            .expect("json")
+    };
+
+    let response = submit(json!({ "check_in": 3, "check_out": 5 }));
+    assert_eq!(response["status"], "complete", "{response}");
+
+    let response = submit(json!({ "check_in": 5, "check_out": 5 }));
+    assert_eq!(response["status"], "error", "{response}");
+    let error = &response["validation"]["errors"][0];
+    assert_eq!(error["code"], "check_out_before_check_in");
+    assert_eq!(error["path"], "/check_out");
+    assert_eq!(error["paths"], json!(["/check_out", "/check_in"]));
+    assert_eq!(error["params"]["validation"], "stay_length");
+}
+
+#[test]
+fn warnings_are_returned_without_blocking_submission() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("storage.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "storage",
+            "title": "Storage",
+            "version": "1.0.0",
+            "questions": [
+                {
+                    "id": "bucket",
+                    "type": "string",
+                    "title": "Bucket name",
+                    "required": true,
+                    "constraint": {
+                        "warnings": {
+                            "pattern": "^[a-z0-9-]+$",
+                            "messages": { "pattern_mismatch": "This bucket name looks unusual" }
+                        }
+                    }
+                },
+                { "id": "region", "type": "string", "title": "Region", "required": true }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let submit = |answers: Value| -> Value {
+        serde_json::from_str(&submit_all("storage", &config, "{}", &answers.to_string()))
+            .expect("json")
+    };
+
+    let response = submit(json!({ "bucket": "Team_Bucket", "region": "eu" }));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(
+        response["warnings"][0]["message"],
+        "This bucket name looks unusual"
+    );
+
+    let response = submit(json!({ "bucket": "Team_Bucket", "region": 7 }));
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["warnings"][0]["path"], "/bucket");
+    assert_eq!(response["validation"]["warnings"][0]["path"], "/bucket");
+
+    let response = submit(json!({ "bucket": "team-bucket", "region": "eu" }));
+    assert!(response.get("warnings").is_none(), "{response}");
+}
@@ -0,0 +1,49 @@
+use component_qa::{describe, describe_with_ctx};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn describe_lists_issues_when_asked() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("deploy.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "deploy",
+            "title": "Deploy",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "environment", "type": "string", "title": "Environment" },
+                {
+                    "id": "replicas",
+                    "type": "integer",
+                    "title": "Replicas",
+                    "visible_if": { "op": "answer", "path": "enviroment" }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let parse = |raw: String| -> Value { serde_json::from_str(&raw).expect("json") };
+
+    let described = parse(describe_with_ctx(
+        "deploy",
+        &config,
+        &json!({ "analyze": true }).to_string(),
+    ));
+    assert_eq!(described["id"], "deploy", "{described}");
+    assert_eq!(
+        described["issues"],
+        json!([{
+            "severity": "error",
+            "code": "unknown_reference",
+            "path": "/questions/1/visible_if",
+            "message": "expression reads unknown question 'enviroment'"
+        }])
+    );
+
+    let described = parse(describe("deploy", &config));
+    assert!(described.get("issues").is_none(), "{described}");
+}
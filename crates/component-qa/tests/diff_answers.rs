@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use component_qa::diff_answers;
+use serde_json::{Value, json};
+
+fn config_json() -> String {
+    let form_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/generated/forms/support.form.json");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+#[test]
+fn diff_answers_masks_secret_questions() {
+    let old = json!({ "api_key": "first", "enabled": false }).to_string();
+    let new = json!({
+        "form_id": "support-form",
+        "spec_version": "1.0.0",
+        "answers": { "api_key": "second", "enabled": true }
+    })
+    .to_string();
+
+    let raw = diff_answers("support-form", &config_json(), &old, &new);
+    let response: Value = serde_json::from_str(&raw).expect("json response");
+
+    assert_eq!(response["summary"]["changed"], 2);
+    let changes = response["changes"].as_array().expect("changes");
+    let api_key = changes
+        .iter()
+        .find(|change| change["path"] == "/api_key")
+        .expect("api_key change");
+    assert_eq!(api_key["masked"], true);
+    assert!(api_key.get("old").is_none() && api_key.get("new").is_none());
+    let enabled = changes
+        .iter()
+        .find(|change| change["path"] == "/enabled")
+        .expect("enabled change");
+    assert_eq!(enabled["new"], true);
+}
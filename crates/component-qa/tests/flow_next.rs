@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+
+use component_qa::{flow_next, next};
+use serde_json::{Value, json};
+
+fn config_json() -> String {
+    let form_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/generated/forms/support.form.json");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn looping_flow(policies: Value) -> String {
+    json!({
+        "id": "support-flow",
+        "title": "Support",
+        "version": "1.0.0",
+        "entry": "ask_key",
+        "steps": {
+            "ask_key": { "type": "question", "question_id": "api_key", "next": "check" },
+            "check": {
+                "type": "decision",
+                "on": { "op": "answer", "path": "enabled" },
+                "cases": [{ "if": { "op": "literal", "value": true }, "goto": "end" }],
+                "default": "ask_key"
+            },
+            "end": { "type": "end" }
+        },
+        "policies": policies
+    })
+    .to_string()
+}
+
+fn run_until_stop(flow: &str) -> Value {
+    let config = config_json();
+    let answers = json!({ "api_key": "secret", "enabled": false }).to_string();
+    let mut ctx = json!({});
+    for _ in 0..10 {
+        let raw = flow_next("support-form", &config, flow, &ctx.to_string(), &answers);
+        let response: Value = serde_json::from_str(&raw).expect("json response");
+        if response["status"] != "in_progress" {
+            return response;
+        }
+        ctx = json!({ "flow_state": response["flow_state"] });
+    }
+    panic!("flow did not stop");
+}
+
+#[test]
+fn flow_limit_is_reported_as_structured_error() {
+    let response = run_until_stop(&looping_flow(json!({ "max_repeats_per_step": 2 })));
+    assert_eq!(response["status"], "error");
+    assert_eq!(response["error"]["code"], "flow_limit_exceeded");
+    assert_eq!(response["error"]["limit"], "max_repeats_per_step");
+    assert_eq!(response["error"]["step"], "ask_key");
+    assert_eq!(
+        response["error"]["history"],
+        json!(["ask_key", "check", "ask_key", "check", "ask_key"])
+    );
+}
+
+#[test]
+fn complete_partial_limit_is_not_an_error() {
+    let response = run_until_stop(&looping_flow(
+        json!({ "max_steps": 4, "on_limit": "complete_partial" }),
+    ));
+    assert_eq!(response["status"], "partial");
+    assert!(response.get("error").is_none());
+    assert_eq!(response["flow_state"]["completed"], true);
+}
+
+fn graph_flow_config(policies: Option<Value>) -> String {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut flow: Value =
+        serde_json::from_str(include_str!("fixtures/graph_flow.json")).expect("flow fixture");
+    if let Some(policies) = policies {
+        flow["policies"] = policies;
+    }
+    json!({
+        "qa_form_asset_path": fixtures.join("simple_form.json"),
+        "flow_spec_json": flow.to_string()
+    })
+    .to_string()
+}
+
+#[test]
+fn next_follows_the_configured_flow() {
+    let config = graph_flow_config(None);
+    let parse = |raw: String| -> Value { serde_json::from_str(&raw).expect("json") };
+
+    let response = parse(next("example-form", &config, "{}"));
+    assert_eq!(response["status"], "need_input", "{response}");
+    assert_eq!(response["next_question_id"], "q1");
+    assert_eq!(response["step_id"], "ask_name");
+    assert_eq!(response["messages"], json!(["Welcome to the QA wizard!"]));
+
+    // The flow ends once `q1` is answered, although `q2` became visible.
+    let response = parse(next("example-form", &config, r#"{"q1": "yes"}"#));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert!(response["next_question_id"].is_null());
+    assert!(response["step_id"].is_null());
+    assert_eq!(
+        response["progress"],
+        json!({ "answered": 1, "total": 2, "remaining": ["q2"], "percent": 50.0 })
+    );
+}
+
+#[test]
+fn next_reports_flow_loops() {
+    let parse = |raw: String| -> Value { serde_json::from_str(&raw).expect("json") };
+    let answers = r#"{"q1": false}"#;
+
+    let response = parse(next("example-form", &graph_flow_config(None), answers));
+    assert_eq!(
+        response["error"],
+        "flow execution failed: flow cycle through answered steps starting at 'ask_name'"
+    );
+
+    let config = graph_flow_config(Some(json!({ "max_steps": 4 })));
+    let response = parse(next("example-form", &config, answers));
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["step_id"], "decision");
+    assert_eq!(response["error"]["code"], "flow_limit_exceeded");
+    assert_eq!(response["error"]["limit"], "max_steps");
+}
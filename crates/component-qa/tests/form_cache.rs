@@ -0,0 +1,48 @@
+use component_qa::{describe, validate_answers};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(path: &std::path::Path, pattern: &str) {
+    let form = json!({
+        "id": "service",
+        "title": "Service",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "name",
+                "type": "string",
+                "title": "Name",
+                "required": true,
+                "constraint": { "pattern": pattern }
+            }
+        ]
+    });
+    std::fs::write(path, form.to_string()).expect("write form");
+}
+
+fn validate(config: &str, answers: Value) -> Value {
+    serde_json::from_str(&validate_answers("service", config, &answers.to_string()))
+        .expect("json response")
+}
+
+#[test]
+fn edited_forms_are_recompiled() {
+    let dir = TempDir::new().expect("tempdir");
+    let path = dir.path().join("service.form.json");
+    let config = json!({ "qa_form_asset_path": path.to_string_lossy() }).to_string();
+
+    write_form(&path, "^[a-z]+$");
+    for _ in 0..2 {
+        assert_eq!(validate(&config, json!({ "name": "api" }))["valid"], true);
+        assert_eq!(validate(&config, json!({ "name": "API" }))["valid"], false);
+    }
+
+    write_form(&path, "^[A-Z]+$");
+    assert_eq!(validate(&config, json!({ "name": "api" }))["valid"], false);
+    assert_eq!(validate(&config, json!({ "name": "API" }))["valid"], true);
+    let described: Value = serde_json::from_str(&describe("service", &config)).expect("json");
+    assert_eq!(
+        described["questions"][0]["constraint"]["pattern"],
+        "^[A-Z]+$"
+    );
+}
@@ -0,0 +1,57 @@
+use component_qa::validate_answers;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn included_required_if_reads_the_prefixed_answers() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("deploy.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "deploy",
+            "title": "Deploy",
+            "version": "1.0.0",
+            "includes": [{ "form_ref": "database", "prefix": "db" }],
+            "questions": [
+                { "id": "tls", "type": "boolean", "title": "TLS for the web tier" }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let database = json!({
+        "id": "database",
+        "title": "Database",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "tls", "type": "boolean", "title": "TLS" },
+            {
+                "id": "cert",
+                "type": "string",
+                "title": "Certificate",
+                "required_if": { "op": "answer", "path": "tls" }
+            }
+        ]
+    });
+    let config = json!({
+        "qa_form_asset_path": form_path,
+        "include_registry": { "database": database.to_string() }
+    })
+    .to_string();
+    let missing = |answers: Value| -> Value {
+        let validation: Value =
+            serde_json::from_str(&validate_answers("deploy", &config, &answers.to_string()))
+                .expect("json");
+        validation["missing_required"].clone()
+    };
+
+    assert_eq!(
+        missing(json!({ "tls": false, "db.tls": true })),
+        json!(["db.cert"])
+    );
+    assert_eq!(
+        missing(json!({ "tls": true, "db.tls": false })),
+        Value::Null
+    );
+}
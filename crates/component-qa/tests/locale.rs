@@ -0,0 +1,37 @@
+use component_qa::{render_card, render_text};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn ctx_locale_picks_the_inline_strings_and_labels() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("signup.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "signup",
+            "title": "Signup",
+            "version": "1.0.0",
+            "i18n": { "id": { "title": "Pendaftaran" } },
+            "questions": [{
+                "id": "name",
+                "type": "string",
+                "title": "Name",
+                "i18n": { "id": { "title": "Nama" } }
+            }]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let ctx = json!({ "locale": "id" }).to_string();
+
+    let text = render_text("signup", &config, &ctx, "{}");
+    assert!(text.starts_with("Formulir: Pendaftaran (signup)"), "{text}");
+    assert!(text.contains("  Judul: Nama"), "{text}");
+
+    let card: Value =
+        serde_json::from_str(&render_card("signup", &config, &ctx, "{}")).expect("json");
+    assert_eq!(card["actions"][0]["title"], "Lanjut ➡️");
+    assert!(render_text("signup", &config, "{}", "{}").starts_with("Form: Signup"));
+}
@@ -0,0 +1,44 @@
+use component_qa::submit_patch;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn ctx_locale_and_messages_localize_validation() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("profile.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "profile",
+            "title": "Profile",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "age", "type": "integer", "title": "Age", "required": true, "constraint": { "min": 18 } }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let message = |ctx: Value| -> Value {
+        let raw = submit_patch("profile", &config, &ctx.to_string(), "{}", "age", "12");
+        let response: Value = serde_json::from_str(&raw).expect("json");
+        assert_eq!(response["status"], "error", "{response}");
+        response["validation"]["errors"][0]["message"].clone()
+    };
+    let messages = json!({ "id": { "codes": { "min": "Umur minimal {min}." } } });
+
+    assert_eq!(message(json!({})), "qa_spec.min");
+    assert_eq!(
+        message(json!({ "locale": "en" })),
+        "Value must be at least 18."
+    );
+    assert_eq!(
+        message(json!({ "locale": "id-ID", "messages": messages })),
+        "Umur minimal 18."
+    );
+    assert_eq!(
+        message(json!({ "locale": "fr", "messages": messages })),
+        "Value must be at least 18."
+    );
+}
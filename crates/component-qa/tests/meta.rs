@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use component_qa::submit_all;
+use serde_json::{Value, json};
+
+fn config_json() -> String {
+    let form_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/generated/forms/support.form.json");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn submit(ctx: Value) -> Value {
+    let answers = json!({ "api_key": "secret", "enabled": true }).to_string();
+    let raw = submit_all("support-form", &config_json(), &ctx.to_string(), &answers);
+    serde_json::from_str(&raw).expect("json response")
+}
+
+#[test]
+fn meta_is_only_tracked_on_request() {
+    assert!(submit(json!({})).get("meta").is_none());
+}
+
+#[test]
+fn meta_advances_across_calls() {
+    let first = submit(json!({ "track_meta": true, "actor": "alice", "channel": "teams" }));
+    let meta = &first["meta"];
+    assert_eq!(meta["actor"], "alice");
+    assert_eq!(meta["channel"], "teams");
+    assert_eq!(meta["attempts"], 1);
+    assert!(meta["created_at"].is_string());
+
+    let second = submit(json!({ "track_meta": true, "meta": meta.clone() }));
+    assert_eq!(second["meta"]["attempts"], 2);
+    assert_eq!(second["meta"]["actor"], "alice");
+    assert_eq!(second["meta"]["created_at"], meta["created_at"]);
+}
@@ -0,0 +1,122 @@
+use component_qa::qa::{NormalizedMode, apply_answers};
+use component_qa::submit_all;
+use qa_spec::AnswerSet;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir) -> String {
+    let form = json!({
+        "id": "database",
+        "title": "Database",
+        "version": "2.0.0",
+        "migrations": [{
+            "from": "1.0.0",
+            "to": "2.0.0",
+            "ops": [
+                { "op": "rename", "from": "db_host", "to": "database_host" },
+                { "op": "retype", "field": "db_port", "convert": "string_to_integer" }
+            ]
+        }],
+        "questions": [
+            { "id": "database_host", "type": "string", "title": "Host", "required": true },
+            { "id": "db_port", "type": "integer", "title": "Port", "required": true }
+        ]
+    });
+    let path = dir.path().join("database.form.json");
+    std::fs::write(&path, form.to_string()).expect("write form");
+    path.to_string_lossy().to_string()
+}
+
+fn config_json(form_path: &str, migrate: bool) -> String {
+    json!({ "qa_form_asset_path": form_path, "migrate_answers": migrate }).to_string()
+}
+
+fn v1_answer_set(port: &str) -> AnswerSet {
+    let mut set = AnswerSet::new("database", "1.0.0");
+    set.answers = json!({ "db_host": "db.internal", "db_port": port });
+    set
+}
+
+fn submit(config: &str, set: &AnswerSet) -> Value {
+    let raw = submit_all(
+        "database",
+        config,
+        "{}",
+        &serde_json::to_string(set).expect("encode"),
+    );
+    serde_json::from_str(&raw).expect("json response")
+}
+
+#[test]
+fn submit_all_migrates_older_answer_sets_when_configured() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config_json(&write_form(&dir), true);
+
+    let response = submit(&config, &v1_answer_set("5432"));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(
+        response["answers"],
+        json!({ "database_host": "db.internal", "db_port": 5432 })
+    );
+    assert_eq!(response["migration"]["from_version"], "1.0.0");
+    assert_eq!(response["migration"]["applied"][0]["op"]["op"], "rename");
+}
+
+#[test]
+fn stuck_fields_are_returned_as_errors() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config_json(&write_form(&dir), true);
+
+    let response = submit(&config, &v1_answer_set("fifty"));
+    assert_eq!(response["status"], "error");
+    assert_eq!(response["error"]["code"], "answer_migration_failed");
+    assert_eq!(
+        response["error"]["stuck_fields"],
+        json!([{
+            "field": "db_port",
+            "op": "retype",
+            "reason": "cannot parse \"fifty\" as an integer"
+        }])
+    );
+}
+
+#[test]
+fn migration_is_opt_in() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config_json(&write_form(&dir), false);
+
+    let response = submit(&config, &v1_answer_set("5432"));
+    assert!(response.get("migration").is_none(), "{response}");
+    assert_ne!(response["status"], "complete");
+}
+
+#[test]
+fn apply_answers_migrates_cbor_answer_sets() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = write_form(&dir);
+    let cbor = v1_answer_set("5432").to_cbor().expect("encode");
+    let payload = json!({
+        "form_id": "database",
+        "config": { "qa_form_asset_path": form_path, "migrate_answers": true },
+        "answer_set_cbor": cbor,
+    });
+
+    let result = apply_answers(NormalizedMode::Update, &payload);
+    assert_eq!(result["ok"], true, "{result}");
+    assert_eq!(result["config"]["database_host"], "db.internal");
+    assert_eq!(result["config"]["db_port"], 5432);
+
+    let stuck = v1_answer_set("x").to_cbor().expect("encode");
+    let payload = json!({
+        "form_id": "database",
+        "config": { "qa_form_asset_path": form_path, "migrate_answers": true },
+        "answer_set_cbor": stuck,
+    });
+    let result = apply_answers(NormalizedMode::Update, &payload);
+    assert_eq!(result["ok"], false);
+    assert_eq!(
+        result["errors"][0]["key"],
+        "qa.error.answer_migration_failed"
+    );
+    assert_eq!(result["errors"][0]["stuck_fields"][0]["field"], "db_port");
+}
@@ -0,0 +1,56 @@
+use component_qa::submit_all;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn submits_echo_normalized_answers_without_secrets() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("login.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "login",
+            "title": "Login",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "email", "type": "string", "title": "Email", "required": true, "normalize": ["trim", "lowercase"] },
+                {
+                    "id": "password",
+                    "type": "string",
+                    "title": "Password",
+                    "required": true,
+                    "secret": true,
+                    "normalize": ["trim"],
+                    "constraint": { "min_len": 12 }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let submit = |answers: Value| -> (String, Value) {
+        let raw = submit_all("login", &config, "{}", &answers.to_string());
+        let response = serde_json::from_str(&raw).expect("json");
+        (raw, response)
+    };
+
+    let (raw, response) = submit(json!({
+        "email": " Ada@Example.COM\n",
+        "password": " correct-horse-battery \n"
+    }));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(
+        response["normalized"],
+        json!([
+            { "path": "/email", "kind": "changed", "old": " Ada@Example.COM\n", "new": "ada@example.com" },
+            { "path": "/password", "kind": "changed", "masked": true }
+        ])
+    );
+    assert!(!raw.contains("correct-horse-battery"), "{raw}");
+
+    let (raw, response) = submit(json!({ "email": "ada@example.com", "password": " tiny-pass " }));
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["normalized"][0]["masked"], true);
+    assert!(!raw.contains("tiny-pass"), "{raw}");
+}
@@ -0,0 +1,57 @@
+use component_qa::{submit_all, submit_patch};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn oversized_submissions_are_rejected_without_echoing_answers() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("notes.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "notes",
+            "title": "Notes",
+            "version": "1.0.0",
+            "max_answer_bytes": 64,
+            "questions": [
+                { "id": "body", "type": "string", "title": "Body", "required": true }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let body = "x".repeat(100);
+
+    let raw = submit_patch(
+        "notes",
+        &config,
+        "{}",
+        "{}",
+        "body",
+        &json!(body).to_string(),
+    );
+    let response: Value = serde_json::from_str(&raw).expect("json");
+    assert_eq!(response["status"], "error", "{response}");
+    let error = &response["validation"]["errors"][0];
+    assert_eq!(error["code"], "payload_too_large");
+    assert_eq!(error["params"]["size"], "102");
+    assert_eq!(error["params"]["limit"], "64");
+    assert!(response.get("answers").is_none());
+
+    let raw = submit_all("notes", &config, "{}", &json!({ "body": body }).to_string());
+    let response: Value = serde_json::from_str(&raw).expect("json");
+    assert_eq!(
+        response["validation"]["errors"][0]["code"],
+        "payload_too_large"
+    );
+
+    let raw = submit_all(
+        "notes",
+        &config,
+        "{}",
+        &json!({ "body": "short" }).to_string(),
+    );
+    let response: Value = serde_json::from_str(&raw).expect("json");
+    assert_eq!(response["status"], "complete", "{response}");
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use component_qa::submit_all;
+use serde_json::{Value, json};
+
+fn config_json(strategy: &str) -> String {
+    let form_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/generated/forms/support.form.json");
+    json!({
+        "qa_form_asset_path": form_path,
+        "prefill_merge": { "strategy": strategy }
+    })
+    .to_string()
+}
+
+fn submit(strategy: &str, answers: Value) -> Value {
+    let ctx = json!({
+        "prefill_answers": { "api_key": "prefilled", "enabled": false },
+        "reveal_secrets": true
+    });
+    let raw = submit_all(
+        "support-form",
+        &config_json(strategy),
+        &ctx.to_string(),
+        &answers.to_string(),
+    );
+    serde_json::from_str(&raw).expect("json response")
+}
+
+#[test]
+fn prefill_answers_are_merged_before_validation() {
+    let response = submit("overlay_wins", json!({ "enabled": true }));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["api_key"], "prefilled");
+    assert_eq!(response["answers"]["enabled"], true);
+
+    let response = submit("base_wins", json!({ "enabled": true }));
+    assert_eq!(response["answers"]["enabled"], false);
+}
+
+#[test]
+fn prefill_conflicts_can_be_rejected() {
+    let response = submit("error_on_conflict", json!({ "enabled": true }));
+    let error = response["error"].as_str().expect("error message");
+    assert!(error.contains("/enabled"), "{error}");
+}
@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use component_qa::{submit_all, submit_patch};
+use serde_json::{Value, json};
+
+fn config_json() -> String {
+    let form_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/generated/forms/support.form.json");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn parse(raw: String) -> Value {
+    serde_json::from_str(&raw).expect("json response")
+}
+
+#[test]
+fn progress_state_is_only_tracked_on_request() {
+    let response = parse(submit_all(
+        "support-form",
+        &config_json(),
+        "{}",
+        &json!({ "enabled": true }).to_string(),
+    ));
+    assert!(response.get("progress_state").is_none());
+}
+
+#[test]
+fn progress_state_counts_attempts_across_submits() {
+    let first = parse(submit_patch(
+        "support-form",
+        &config_json(),
+        &json!({ "track_progress": true }).to_string(),
+        "{}",
+        "api_key",
+        "\"sk-1\"",
+    ));
+    let state = &first["progress_state"];
+    assert_eq!(state["questions"]["api_key"]["status"], "answered");
+    assert_eq!(state["questions"]["api_key"]["attempts"], 1);
+    assert_eq!(state["questions"]["enabled"]["status"], "pending");
+
+    let ctx = json!({ "track_progress": true, "progress_state": state.clone() });
+    let second = parse(submit_all(
+        "support-form",
+        &config_json(),
+        &ctx.to_string(),
+        &json!({ "api_key": "sk-2", "enabled": false }).to_string(),
+    ));
+    let questions = &second["progress_state"]["questions"];
+    assert_eq!(questions["api_key"]["attempts"], 2);
+    assert_eq!(questions["enabled"]["attempts"], 1);
+    assert_eq!(questions["enabled"]["status"], "answered");
+    assert!(!second.to_string().contains("sk-2"));
+}
@@ -0,0 +1,106 @@
+use component_qa::{submit_all, submit_patch};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir) -> String {
+    let form_path = dir.path().join("deploy.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "deploy",
+            "title": "Deploy",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "advanced", "type": "boolean", "title": "Advanced", "required": true },
+                {
+                    "id": "replicas",
+                    "type": "integer",
+                    "title": "Replicas",
+                    "required": true,
+                    "visible_if": { "op": "answer", "path": "advanced" }
+                },
+                {
+                    "id": "instances",
+                    "type": "integer",
+                    "title": "Instances",
+                    "computed": {
+                        "op": "coalesce",
+                        "expressions": [
+                            { "op": "answer", "path": "replicas" },
+                            { "op": "literal", "value": 1 }
+                        ]
+                    }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn parse(raw: String) -> Value {
+    serde_json::from_str(&raw).expect("json")
+}
+
+#[test]
+fn hiding_a_branch_prunes_its_answers_in_one_round_trip() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+    let ctx = json!({ "prune_hidden": true }).to_string();
+    let answers = json!({ "advanced": true, "replicas": 3 });
+
+    let response = parse(submit_all("deploy", &config, &ctx, &answers.to_string()));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["replicas"], 3);
+    assert!(response.get("pruned").is_none(), "{response}");
+
+    // Turning the toggle off hides `replicas` for the same submission.
+    let response = parse(submit_patch(
+        "deploy",
+        &config,
+        &ctx,
+        &answers.to_string(),
+        "advanced",
+        "false",
+    ));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["pruned"], json!(["replicas"]));
+    assert!(response["answers"].get("replicas").is_none(), "{response}");
+    assert!(
+        response["store"]["answers"].get("replicas").is_none(),
+        "{response}"
+    );
+    // Computed answers no longer see the pruned value.
+    assert_eq!(response["answers"]["instances"], 1);
+
+    // Showing the branch again does not bring the pruned answer back.
+    let response = parse(submit_patch(
+        "deploy",
+        &config,
+        &ctx,
+        &response["answers"].to_string(),
+        "advanced",
+        "true",
+    ));
+    assert_eq!(
+        response["validation"]["missing_required"],
+        json!(["replicas"]),
+        "{response}"
+    );
+    assert_eq!(response["next_question_id"], "replicas");
+    assert!(response["answers"].get("replicas").is_none(), "{response}");
+    assert!(response.get("pruned").is_none(), "{response}");
+}
+
+#[test]
+fn hidden_answers_are_kept_without_the_flag() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+    let answers = json!({ "advanced": false, "replicas": 3 });
+
+    let response = parse(submit_all("deploy", &config, "{}", &answers.to_string()));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["replicas"], 3);
+    assert!(response.get("pruned").is_none(), "{response}");
+}
@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 
 use component_qa::qa::{NormalizedMode, apply_answers, normalize_mode, qa_spec_json};
+use qa_spec::AnswerSet;
 use serde_json::{Value, json};
 use tempfile::TempDir;
 
@@ -329,3 +330,31 @@ fn apply_answers_uses_current_config_and_returns_non_empty_config() {
         Some(true)
     );
 }
+
+#[test]
+fn apply_answers_round_trips_cbor_answer_sets() {
+    let _guard = lock_env();
+    let assets = setup_generated_assets();
+    // Guarded by process-wide mutex to avoid concurrent env mutation across tests.
+    unsafe { std::env::set_var("QA_FORM_ASSET_BASE", assets.path()) };
+
+    let mut answer_set = AnswerSet::new("support-form", "1.0.0");
+    answer_set.answers = json!({ "api_key": "sk_test_123", "enabled": true });
+    let cbor = answer_set.to_cbor().expect("encode");
+    let hex = cbor
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    for encoded in [json!(hex), json!(cbor)] {
+        let payload = json!({
+            "form_id": "support-form",
+            "current_config": { "qa_form_asset_path": "qa/forms/support.form.json" },
+            "answer_set_cbor": encoded
+        });
+        let result = apply_answers(NormalizedMode::Setup, &payload);
+        assert_eq!(result["ok"], true, "{result}");
+        assert_eq!(result["config"]["api_key"], "sk_test_123");
+        assert_eq!(result["answer_set_cbor"], hex);
+    }
+}
@@ -0,0 +1,52 @@
+use component_qa::{render_json_ui, submit_patch};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn patches_cannot_change_locked_answers() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("deploy.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "deploy",
+            "title": "Deploy",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "locked", "type": "boolean", "title": "Locked" },
+                {
+                    "id": "region",
+                    "type": "string",
+                    "title": "Region",
+                    "read_only_if": { "op": "answer", "path": "locked" }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let parse = |raw: String| -> Value { serde_json::from_str(&raw).expect("json") };
+    let answers = json!({ "locked": true, "region": "eu" }).to_string();
+
+    let ui = parse(render_json_ui("deploy", &config, "{}", &answers));
+    assert_eq!(ui["questions"][1]["read_only"], true, "{ui}");
+
+    let response = parse(submit_patch(
+        "deploy", &config, "{}", &answers, "region", "\"us\"",
+    ));
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["validation"]["errors"][0]["code"], "read_only");
+
+    // Unlocking in the same patch is allowed, and the answer is editable again.
+    let response = parse(submit_patch(
+        "deploy", &config, "{}", &answers, "locked", "false",
+    ));
+    assert_eq!(response["status"], "complete", "{response}");
+    let unlocked = response["answers"].to_string();
+    let response = parse(submit_patch(
+        "deploy", &config, "{}", &unlocked, "region", "\"us\"",
+    ));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["region"], "us");
+}
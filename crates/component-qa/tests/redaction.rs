@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use component_qa::{
+    render_card, render_json_ui, render_markdown, render_text, submit_all, submit_patch,
+};
+use serde_json::{Value, json};
+
+fn config_json() -> String {
+    let form_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/generated/forms/support.form.json");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+#[test]
+fn default_responses_never_contain_secret_values() {
+    let config = config_json();
+    let answers = json!({ "api_key": "sk-hunter2", "enabled": true }).to_string();
+    let outputs = [
+        submit_all("support-form", &config, "{}", &answers),
+        submit_patch(
+            "support-form",
+            &config,
+            "{}",
+            &answers,
+            "api_key",
+            "\"sk-hunter2\"",
+        ),
+        render_text("support-form", &config, "{}", &answers),
+        render_json_ui("support-form", &config, "{}", &answers),
+        render_card("support-form", &config, "{}", &answers),
+        render_markdown("support-form", &config, "{}", &answers),
+    ];
+    for output in &outputs {
+        assert!(!output.contains("hunter2"), "{output}");
+    }
+
+    let response: Value = serde_json::from_str(&outputs[0]).expect("json response");
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["api_key"], json!({ "$redacted": true }));
+    assert_eq!(response["answers"]["enabled"], true);
+}
+
+#[test]
+fn reveal_secrets_opts_out_of_redaction() {
+    let ctx = json!({ "reveal_secrets": true }).to_string();
+    let answers = json!({ "api_key": "sk-hunter2", "enabled": true }).to_string();
+    let raw = submit_all("support-form", &config_json(), &ctx, &answers);
+    let response: Value = serde_json::from_str(&raw).expect("json response");
+    assert_eq!(response["answers"]["api_key"], "sk-hunter2");
+
+    // Renders are for people, so they stay masked and only say a value is set.
+    let raw = render_json_ui("support-form", &config_json(), &ctx, &answers);
+    assert!(!raw.contains("hunter2"), "{raw}");
+    let ui: Value = serde_json::from_str(&raw).expect("json ui");
+    let api_key = ui["questions"]
+        .as_array()
+        .expect("questions")
+        .iter()
+        .find(|question| question["id"] == "api_key")
+        .expect("api_key question");
+    assert_eq!(api_key["has_value"], true);
+}
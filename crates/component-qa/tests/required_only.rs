@@ -0,0 +1,67 @@
+use component_qa::{next_with_ctx, submit_all};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir) -> String {
+    let form_path = dir.path().join("quick.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "quick",
+            "title": "Quick",
+            "version": "1.0.0",
+            "progress_policy": { "skip_answered": true, "required_only": true },
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name", "required": true },
+                { "id": "region", "type": "string", "title": "Region", "default_value": "eu" },
+                { "id": "port", "type": "integer", "title": "Port", "required": true }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn parse(raw: String) -> Value {
+    serde_json::from_str(&raw).expect("json")
+}
+
+#[test]
+fn required_only_forms_complete_without_optional_answers() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+
+    let response = parse(next_with_ctx("quick", &config, "{}", r#"{"name":"api"}"#));
+    assert_eq!(response["next_question_id"], "port", "{response}");
+    assert_eq!(response["progress"]["total"], 2);
+
+    let response = parse(submit_all(
+        "quick",
+        &config,
+        "{}",
+        r#"{"name":"api","port":80}"#,
+    ));
+    assert_eq!(response["status"], "complete", "{response}");
+
+    // Optional answers are still accepted when given.
+    let response = parse(submit_all(
+        "quick",
+        &config,
+        "{}",
+        r#"{"name":"api","region":"us","port":80}"#,
+    ));
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["region"], "us");
+}
+
+#[test]
+fn ctx_can_turn_required_only_off() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+    let ctx = json!({ "progress": { "required_only": false } }).to_string();
+
+    let response = parse(next_with_ctx("quick", &config, &ctx, r#"{"name":"api"}"#));
+    assert_eq!(response["next_question_id"], "region", "{response}");
+    assert_eq!(response["progress"]["total"], 3);
+}
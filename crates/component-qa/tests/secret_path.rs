@@ -0,0 +1,45 @@
+use component_qa::submit_all;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn submit_writes_secret_path_and_reports_it_in_audit() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("provider.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "provider",
+            "title": "Provider",
+            "version": "1.0.0",
+            "secrets_policy": {
+                "enabled": true,
+                "read_enabled": true,
+                "write_enabled": true,
+                "allow": ["aws/*"]
+            },
+            "questions": [{
+                "id": "api_key",
+                "type": "string",
+                "title": "API key",
+                "required": true,
+                "secret": true,
+                "secret_path": "aws/api_key"
+            }]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let ctx = json!({ "secrets_host_available": true }).to_string();
+    let answers = json!({ "api_key": "s3cr3t" }).to_string();
+
+    let response: Value =
+        serde_json::from_str(&submit_all("provider", &config, &ctx, &answers)).expect("json");
+    assert_eq!(response["status"], "complete");
+    assert_eq!(response["store"]["secrets"]["aws"]["api_key"], "s3cr3t");
+    assert_eq!(
+        response["audit"]["secret_writes"],
+        json!([{ "question_id": "api_key", "path": "/aws/api_key" }])
+    );
+}
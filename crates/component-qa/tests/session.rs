@@ -0,0 +1,89 @@
+use component_qa::{get_session, resume_session};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir, version: &str) -> String {
+    let form_path = dir.path().join(format!("service-{version}.form.json"));
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "service",
+            "title": "Service",
+            "version": version,
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name", "required": true },
+                { "id": "token", "type": "string", "title": "Token", "secret": true },
+                { "id": "replicas", "type": "integer", "title": "Replicas", "required": true }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+fn parse(raw: String) -> Value {
+    serde_json::from_str(&raw).expect("json")
+}
+
+#[test]
+fn sessions_resume_from_their_answers_and_question() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir, "1.0.0");
+    let ctx = json!({ "reveal_secrets": true }).to_string();
+
+    let session = parse(get_session(
+        "service",
+        &config,
+        &ctx,
+        r#"{"name":"api","token":"s3cret"}"#,
+    ));
+    assert_eq!(session["form_id"], "service");
+    assert_eq!(session["spec_version"], "1.0.0");
+    assert_eq!(session["current_question_id"], "replicas");
+    assert_eq!(session["questions"]["name"]["status"], "answered");
+    assert!(session["questions"]["name"]["answered_at"].is_string());
+
+    let resumed = parse(resume_session(
+        "service",
+        &config,
+        &ctx,
+        &session.to_string(),
+    ));
+    assert_eq!(resumed["status"], "need_input", "{resumed}");
+    assert_eq!(resumed["next_question_id"], "replicas");
+    assert_eq!(
+        resumed["answers"],
+        json!({ "name": "api", "token": "s3cret" })
+    );
+    assert_eq!(resumed["progress"]["answered"], 2);
+
+    // Without `reveal_secrets` the snapshot does not carry the secret.
+    let redacted = parse(get_session(
+        "service",
+        &config,
+        "{}",
+        r#"{"name":"api","token":"s3cret"}"#,
+    ));
+    assert_ne!(redacted["answers"]["token"], "s3cret");
+}
+
+#[test]
+fn sessions_from_another_spec_version_are_rejected() {
+    let dir = TempDir::new().expect("tempdir");
+    let old_config = write_form(&dir, "1.0.0");
+    let session = get_session("service", &old_config, "{}", r#"{"name":"api"}"#);
+
+    let new_config = write_form(&dir, "2.0.0");
+    let response = parse(resume_session("service", &new_config, "{}", &session));
+    assert_eq!(response["status"], "error");
+    assert_eq!(
+        response["error"],
+        json!({
+            "code": "session_version_mismatch",
+            "expected": "2.0.0",
+            "found": "1.0.0",
+            "message": "cannot resume session: session was saved with version 1.0.0 of form 'service', but the spec is version 2.0.0"
+        })
+    );
+}
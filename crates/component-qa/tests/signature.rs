@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use component_qa::submit_all;
+use qa_spec::AnswerSet;
+use serde_json::{Value, json};
+
+const KEY: &str = "k3y";
+
+fn config_json() -> String {
+    let form_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/generated/forms/support.form.json");
+    json!({
+        "qa_form_asset_path": form_path,
+        "answer_signature": { "key_id": "answers-2026", "secret_ref": "qa/answers_hmac" }
+    })
+    .to_string()
+}
+
+fn ctx_json() -> String {
+    json!({ "secrets": { "qa": { "answers_hmac": KEY } } }).to_string()
+}
+
+fn answer_set() -> AnswerSet {
+    let mut set = AnswerSet::new("support-form", "1.0.0");
+    set.answers = json!({ "api_key": "secret", "enabled": true });
+    set
+}
+
+fn submit(set: &AnswerSet) -> Value {
+    let raw = submit_all(
+        "support-form",
+        &config_json(),
+        &ctx_json(),
+        &serde_json::to_string(set).expect("encode"),
+    );
+    serde_json::from_str(&raw).expect("json response")
+}
+
+#[test]
+fn signed_answer_sets_are_accepted() {
+    let mut set = answer_set();
+    set.sign_with_key_id("answers-2026", KEY.as_bytes());
+    let response = submit(&set);
+    assert_eq!(response["status"], "complete", "{response}");
+    assert_eq!(response["answers"]["enabled"], true);
+}
+
+#[test]
+fn unsigned_or_modified_sets_are_rejected_with_distinct_codes() {
+    let response = submit(&answer_set());
+    assert_eq!(response["status"], "error");
+    assert_eq!(response["error"]["code"], "answer_signature_missing");
+
+    let mut tampered = answer_set();
+    tampered.sign_with_key_id("answers-2026", KEY.as_bytes());
+    tampered.answers["enabled"] = json!(false);
+    let response = submit(&tampered);
+    assert_eq!(response["error"]["code"], "answer_signature_invalid");
+
+    let mut wrong_key = answer_set();
+    wrong_key.sign_with_key_id("answers-2026", b"other");
+    assert_eq!(
+        submit(&wrong_key)["error"]["code"],
+        "answer_signature_invalid"
+    );
+
+    let mut wrong_key_id = answer_set();
+    wrong_key_id.sign_with_key_id("answers-2025", KEY.as_bytes());
+    assert_eq!(
+        submit(&wrong_key_id)["error"]["code"],
+        "answer_signature_invalid"
+    );
+}
+
+#[test]
+fn missing_signing_key_is_a_configuration_error() {
+    let mut set = answer_set();
+    set.sign_with_key_id("answers-2026", KEY.as_bytes());
+    let raw = submit_all(
+        "support-form",
+        &config_json(),
+        "{}",
+        &serde_json::to_string(&set).expect("encode"),
+    );
+    let response: Value = serde_json::from_str(&raw).expect("json response");
+    let error = response["error"].as_str().expect("error message");
+    assert!(error.contains("qa/answers_hmac"), "{error}");
+}
@@ -0,0 +1,138 @@
+use component_qa::{
+    diff_answers, flow_next, get_answer_schema, next_with_ctx, render_card, render_json_ui,
+    render_text, submit_all, submit_patch, validate_answers,
+};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+const MALFORMED: &str = "{\"name\": ";
+
+fn write_form(dir: &TempDir) -> String {
+    let form = json!({
+        "id": "service",
+        "title": "Service",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true }
+        ]
+    });
+    let path = dir.path().join("service.form.json");
+    std::fs::write(&path, form.to_string()).expect("write form");
+    path.to_string_lossy().to_string()
+}
+
+fn config(dir: &TempDir, strict: bool) -> String {
+    json!({ "qa_form_asset_path": write_form(dir), "strict_inputs": strict }).to_string()
+}
+
+fn parse(raw: &str) -> Value {
+    serde_json::from_str(raw).expect("json response")
+}
+
+fn assert_invalid_input(raw: &str, argument: &str) {
+    let response = parse(raw);
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["error"]["code"], "invalid_input", "{response}");
+    assert_eq!(response["error"]["argument"], argument, "{response}");
+    assert_eq!(response["error"]["line"], 1, "{response}");
+    assert_eq!(response["error"]["column"], MALFORMED.len(), "{response}");
+    let message = response["error"]["message"].as_str().expect("message");
+    assert!(
+        message.starts_with(&format!("invalid {argument} JSON")),
+        "{message}"
+    );
+}
+
+#[test]
+fn malformed_ctx_is_rejected_by_every_function_taking_ctx() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config(&dir, true);
+    let flow = json!({ "id": "flow", "title": "Flow", "version": "1", "entry": "q", "steps": {
+        "q": { "type": "question", "question_id": "name", "next": null }
+    } })
+    .to_string();
+
+    assert_invalid_input(&get_answer_schema("service", &config, MALFORMED), "ctx");
+    assert_invalid_input(&next_with_ctx("service", &config, MALFORMED, "{}"), "ctx");
+    assert_invalid_input(
+        &flow_next("service", &config, &flow, MALFORMED, "{}"),
+        "ctx",
+    );
+    assert_invalid_input(&render_json_ui("service", &config, MALFORMED, "{}"), "ctx");
+    assert_invalid_input(&render_card("service", &config, MALFORMED, "{}"), "ctx");
+    assert_invalid_input(&render_text("service", &config, MALFORMED, "{}"), "ctx");
+    assert_invalid_input(
+        &submit_patch("service", &config, MALFORMED, "{}", "name", "\"api\""),
+        "ctx",
+    );
+    assert_invalid_input(&submit_all("service", &config, MALFORMED, "{}"), "ctx");
+}
+
+#[test]
+fn malformed_answers_are_rejected_by_every_function_taking_answers() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config(&dir, true);
+
+    assert_invalid_input(&validate_answers("service", &config, MALFORMED), "answers");
+    assert_invalid_input(
+        &next_with_ctx("service", &config, "{}", MALFORMED),
+        "answers",
+    );
+    assert_invalid_input(
+        &render_json_ui("service", &config, "{}", MALFORMED),
+        "answers",
+    );
+    assert_invalid_input(
+        &submit_patch("service", &config, "{}", MALFORMED, "name", "\"api\""),
+        "answers",
+    );
+    assert_invalid_input(&submit_all("service", &config, "{}", MALFORMED), "answers");
+}
+
+#[test]
+fn malformed_patch_value_is_rejected() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config(&dir, true);
+
+    assert_invalid_input(
+        &submit_patch("service", &config, "{}", "{}", "name", MALFORMED),
+        "value",
+    );
+}
+
+#[test]
+fn malformed_diff_sides_name_the_argument() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config(&dir, true);
+
+    assert_invalid_input(&diff_answers("service", &config, MALFORMED, "{}"), "old");
+    assert_invalid_input(&diff_answers("service", &config, "{}", MALFORMED), "new");
+}
+
+#[test]
+fn lenient_mode_keeps_falling_back_to_empty_inputs() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = config(&dir, false);
+
+    let next = parse(&next_with_ctx("service", &config, MALFORMED, MALFORMED));
+    assert_eq!(next["status"], "need_input", "{next}");
+    assert_eq!(next["next_question_id"], "name");
+
+    let ui = parse(&render_json_ui("service", &config, MALFORMED, MALFORMED));
+    assert_eq!(ui["next_question_id"], "name", "{ui}");
+
+    let card = parse(&render_card("service", &config, MALFORMED, MALFORMED));
+    assert_eq!(card["type"], "AdaptiveCard", "{card}");
+
+    let submitted = parse(&submit_all("service", &config, MALFORMED, MALFORMED));
+    assert_ne!(submitted["error"]["code"], "invalid_input", "{submitted}");
+    assert_ne!(submitted["status"], "complete");
+
+    let strict_ok = parse(&submit_all(
+        "service",
+        &self::config(&dir, true),
+        "{}",
+        &json!({ "name": "api" }).to_string(),
+    ));
+    assert_eq!(strict_ok["status"], "complete", "{strict_ok}");
+}
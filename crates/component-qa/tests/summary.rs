@@ -0,0 +1,50 @@
+use component_qa::render_summary;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn render_summary_reports_entries_and_text() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("signup.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "signup",
+            "title": "Signup",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name", "required": true },
+                { "id": "email", "type": "string", "title": "Email", "required": true },
+                { "id": "password", "type": "string", "title": "Password", "secret": true }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let answers = json!({ "name": "Ada", "password": "hunter2" }).to_string();
+
+    let raw = render_summary("signup", &config, "{}", &answers);
+    assert!(!raw.contains("hunter2"), "{raw}");
+    let summary: Value = serde_json::from_str(&raw).expect("json");
+    assert_eq!(summary["form_id"], "signup");
+    assert_eq!(summary["complete"], false);
+    assert_eq!(
+        summary["entries"]
+            .as_array()
+            .expect("entries")
+            .iter()
+            .map(|entry| (entry["id"].clone(), entry["status"].clone()))
+            .collect::<Vec<_>>(),
+        [
+            (json!("name"), json!("answered")),
+            (json!("email"), json!("missing")),
+            (json!("password"), json!("answered")),
+        ]
+    );
+    assert_eq!(summary["entries"][2]["value"], "********");
+    assert_eq!(
+        summary["text"],
+        "Review: Signup (signup)\n - name (Name) = Ada\n - email (Email) [missing]\n - password (Password) = ********"
+    );
+}
@@ -0,0 +1,59 @@
+use component_qa::validate_field;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+#[test]
+fn validate_field_scopes_to_one_question() {
+    let dir = TempDir::new().expect("tempdir");
+    let form_path = dir.path().join("account.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "account",
+            "title": "Account",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "kind", "type": "enum", "title": "Kind", "required": true, "choices": ["personal", "team"] },
+                { "id": "age", "type": "integer", "title": "Age", "required": true, "constraint": { "min": 18 } },
+                {
+                    "id": "team_name",
+                    "type": "string",
+                    "title": "Team name",
+                    "required": true,
+                    "visible_if": {
+                        "op": "eq",
+                        "left": { "op": "answer", "path": "kind" },
+                        "right": { "op": "literal", "value": "team" }
+                    }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    let config = json!({ "qa_form_asset_path": form_path }).to_string();
+    let check = |answers: Value, question_id: &str| -> Value {
+        let raw = validate_field("account", &config, &answers.to_string(), question_id);
+        serde_json::from_str(&raw).expect("json")
+    };
+
+    let response = check(json!({ "age": 30 }), "age");
+    assert_eq!(response["status"], "valid", "{response}");
+    assert_eq!(response["question_id"], "age");
+    assert_eq!(response["validation"]["valid"], true);
+
+    let response = check(json!({ "age": 12 }), "age");
+    assert_eq!(response["status"], "invalid", "{response}");
+    assert_eq!(response["validation"]["errors"][0]["path"], "/age");
+
+    let response = check(json!({ "kind": "personal" }), "team_name");
+    assert_eq!(
+        response,
+        json!({ "status": "not_visible", "question_id": "team_name" })
+    );
+
+    let response = check(json!({}), "nickname");
+    assert_eq!(response["status"], "error", "{response}");
+    assert_eq!(response["error"]["code"], "unknown_question");
+    assert_eq!(response["error"]["question_id"], "nickname");
+}
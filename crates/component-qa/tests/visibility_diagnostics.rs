@@ -0,0 +1,83 @@
+use component_qa::{render_json_ui, submit_all};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_form(dir: &TempDir) -> String {
+    let form_path = dir.path().join("deploy.form.json");
+    std::fs::write(
+        &form_path,
+        json!({
+            "id": "deploy",
+            "title": "Deploy",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name" },
+                {
+                    "id": "region",
+                    "type": "string",
+                    "title": "Region",
+                    "visible_if": {
+                        "op": "eq",
+                        "left": { "op": "answer", "path": "cloud" },
+                        "right": { "op": "literal", "value": "aws" }
+                    }
+                }
+            ]
+        })
+        .to_string(),
+    )
+    .expect("write form");
+    json!({ "qa_form_asset_path": form_path }).to_string()
+}
+
+#[test]
+fn debug_json_ui_lists_visibility_diagnostics() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+    let render = |ctx: Value| -> Value {
+        serde_json::from_str(&render_json_ui("deploy", &config, &ctx.to_string(), "{}"))
+            .expect("json")
+    };
+
+    let ui = render(json!({ "debug": true }));
+    assert_eq!(
+        ui["visibility_diagnostics"],
+        json!([{
+            "question_id": "region",
+            "reason": "missing_value",
+            "expr_fragment": { "op": "answer", "path": "cloud" }
+        }])
+    );
+    assert_eq!(ui["questions"][1]["visible"], true);
+
+    let ui = render(json!({}));
+    assert!(ui.get("visibility_diagnostics").is_none(), "{ui}");
+}
+
+#[test]
+fn strict_submissions_reject_unevaluable_conditions() {
+    let dir = TempDir::new().expect("tempdir");
+    let config = write_form(&dir);
+    let submit = |ctx: Value, answers: Value| -> Value {
+        serde_json::from_str(&submit_all(
+            "deploy",
+            &config,
+            &ctx.to_string(),
+            &answers.to_string(),
+        ))
+        .expect("json")
+    };
+
+    // Leniently, `region` is shown and simply still unanswered.
+    let response = submit(json!({}), json!({ "name": "api" }));
+    assert_eq!(response["status"], "need_input", "{response}");
+
+    let strict = json!({ "strict_visibility": true });
+    let response = submit(strict, json!({ "name": "api" }));
+    assert_eq!(response["status"], "error", "{response}");
+    let error = &response["validation"]["errors"][0];
+    assert_eq!(error["code"], "visibility_error");
+    assert_eq!(error["question_id"], "region");
+    assert_eq!(error["params"]["reason"], "missing_value");
+    assert_eq!(error["params"]["path"], "cloud");
+}
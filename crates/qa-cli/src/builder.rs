@@ -14,7 +14,10 @@ use qa_spec::{
     spec::{
         flow::{QAFlowSpec, QuestionStep, StepSpec},
         form::{FormPresentation, FormSpec, ProgressPolicy},
-        question::{Constraint, ListSpec, QuestionPolicy, QuestionSpec, QuestionType},
+        question::{
+            ChoiceCatalog, Constraint, ListSpec, ObjectSpec, QuestionPolicy, QuestionSpec,
+            QuestionType, StringFormat, decimal_places_of, split_choices,
+        },
         validation::CrossFieldValidation,
     },
     visibility::{VisibilityMode, resolve_visibility},
@@ -42,6 +45,9 @@ pub struct FormInput {
     pub description: Option<String>,
     #[serde(default)]
     pub progress_policy: Option<ProgressPolicyInput>,
+    /// Built-in theme name written to `presentation.theme`.
+    #[serde(default)]
+    pub theme: Option<String>,
 }
 
 /// Optional progress directives.
@@ -53,6 +59,17 @@ pub struct ProgressPolicyInput {
     pub autofill_defaults: bool,
     #[serde(default)]
     pub treat_default_as_answered: bool,
+    #[serde(default)]
+    pub required_only: bool,
+}
+
+/// Codes of a built-in choice catalog, owned so they can stand in for `choices`.
+pub fn catalog_choices(catalog: ChoiceCatalog) -> Vec<String> {
+    catalog
+        .choices()
+        .iter()
+        .map(|code| code.to_string())
+        .collect()
 }
 
 fn default_skip_answered() -> bool {
@@ -65,6 +82,7 @@ impl Default for ProgressPolicyInput {
             skip_answered: true,
             autofill_defaults: false,
             treat_default_as_answered: false,
+            required_only: false,
         }
     }
 }
@@ -84,18 +102,80 @@ pub struct QuestionInput {
     pub default_value: Option<String>,
     #[serde(default)]
     pub choices: Option<Vec<String>>,
+    /// Built-in list used instead of `choices` for enum questions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choices_catalog: Option<ChoiceCatalog>,
     #[serde(default)]
     pub secret: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub list: Option<ListInput>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<ObjectInput>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub visible_if: Option<Expr>,
+    /// Condition under which the question is required; overrides `required`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_if: Option<Expr>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub constraint: Option<Constraint>,
+    /// Shape string answers must have; shorthand for `constraint.format`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<StringFormat>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub computed: Option<Expr>,
     #[serde(default)]
     pub computed_overridable: bool,
+    /// Position the question is asked in; see `QuestionSpec::order`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i32>,
+}
+
+impl QuestionInput {
+    /// Choices an answer must come from: the explicit list, else the catalog's.
+    pub fn known_choices(&self) -> Option<Vec<String>> {
+        self.choices
+            .clone()
+            .or_else(|| self.choices_catalog.map(catalog_choices))
+    }
+
+    /// `constraint` with the `format` shorthand folded in.
+    pub fn effective_constraint(&self) -> Option<Constraint> {
+        let Some(format) = self.format else {
+            return self.constraint.clone();
+        };
+        let mut constraint = self.constraint.clone().unwrap_or_default();
+        constraint.format = Some(format);
+        Some(constraint)
+    }
+
+    /// Whether the default value, or each of its items for a string list, has
+    /// `format`. Questions without a default always match.
+    pub fn default_matches_format(&self, format: StringFormat) -> bool {
+        let Some(default_value) = &self.default_value else {
+            return true;
+        };
+        match self.kind {
+            CliQuestionType::StringList => {
+                split_choices(default_value).all(|item| format.matches(item))
+            }
+            _ => format.matches(default_value),
+        }
+    }
+
+    /// Whether a numeric default has no more decimals than the constraint's
+    /// `decimal_places`.
+    pub fn default_fits_precision(&self) -> bool {
+        let places = self
+            .constraint
+            .as_ref()
+            .and_then(|constraint| constraint.decimal_places);
+        match (places, self.default_value.as_deref().map(str::parse::<f64>)) {
+            (Some(places), Some(Ok(value))) => decimal_places_of(value) <= usize::from(places),
+            _ => true,
+        }
+    }
 }
 
 fn default_required() -> bool {
@@ -113,6 +193,18 @@ pub enum CliQuestionType {
     Number,
     Enum,
     List,
+    Date,
+    #[serde(rename = "multi_enum")]
+    MultiEnum,
+    Path,
+    Duration,
+    Object,
+    #[serde(rename = "string_list")]
+    StringList,
+    Rating,
+    Color,
+    Markdown,
+    Time,
 }
 
 impl fmt::Display for CliQuestionType {
@@ -124,6 +216,16 @@ impl fmt::Display for CliQuestionType {
             CliQuestionType::Number => write!(f, "number"),
             CliQuestionType::Enum => write!(f, "enum"),
             CliQuestionType::List => write!(f, "list"),
+            CliQuestionType::Date => write!(f, "date"),
+            CliQuestionType::MultiEnum => write!(f, "multi_enum"),
+            CliQuestionType::Path => write!(f, "path"),
+            CliQuestionType::Duration => write!(f, "duration"),
+            CliQuestionType::Object => write!(f, "object"),
+            CliQuestionType::StringList => write!(f, "string_list"),
+            CliQuestionType::Rating => write!(f, "rating"),
+            CliQuestionType::Color => write!(f, "color"),
+            CliQuestionType::Markdown => write!(f, "markdown"),
+            CliQuestionType::Time => write!(f, "time"),
         }
     }
 }
@@ -135,6 +237,15 @@ pub struct ListInput {
     pub min_items: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_items: Option<usize>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unique: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<QuestionInput>,
+}
+
+/// Sub-fields of an object question.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectInput {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<QuestionInput>,
 }
@@ -150,6 +261,16 @@ impl std::str::FromStr for CliQuestionType {
             "number" | "float" => Ok(CliQuestionType::Number),
             "enum" | "choice" => Ok(CliQuestionType::Enum),
             "list" => Ok(CliQuestionType::List),
+            "date" => Ok(CliQuestionType::Date),
+            "multi_enum" | "multi-enum" | "multiselect" => Ok(CliQuestionType::MultiEnum),
+            "path" | "file" => Ok(CliQuestionType::Path),
+            "duration" | "timeout" => Ok(CliQuestionType::Duration),
+            "object" | "group" => Ok(CliQuestionType::Object),
+            "string_list" | "string-list" | "strings" => Ok(CliQuestionType::StringList),
+            "rating" | "score" => Ok(CliQuestionType::Rating),
+            "color" | "colour" => Ok(CliQuestionType::Color),
+            "markdown" | "md" => Ok(CliQuestionType::Markdown),
+            "time" | "time_of_day" | "time-of-day" => Ok(CliQuestionType::Time),
             _ => Err(tf(
                 "cli.builder.unknown_question_type",
                 &[("value", value.to_string())],
@@ -175,10 +296,10 @@ pub fn build_bundle(input: &GenerationInput) -> Result<GeneratedBundle, String>
         .map(to_question_spec)
         .collect::<Vec<_>>();
 
-    let presentation = input.summary_md.as_ref().map(|intro| {
+    let presentation = (input.summary_md.is_some() || input.form.theme.is_some()).then(|| {
         serde_json::from_value::<FormPresentation>(json!({
-            "intro": intro,
-            "theme": null,
+            "intro": input.summary_md,
+            "theme": input.form.theme,
             "default_locale": null
         }))
         .expect("FormPresentation JSON should deserialize")
@@ -201,10 +322,16 @@ pub fn build_bundle(input: &GenerationInput) -> Result<GeneratedBundle, String>
     }))
     .expect("FormSpec JSON should deserialize");
 
+    // The written spec keeps `choices_catalog` compact; schema and examples
+    // need the expanded lists.
+    let mut resolved = form.clone();
+    resolved.resolve_catalogs();
     let answers = Value::Object(Map::new());
-    let visibility = resolve_visibility(&form, &answers, VisibilityMode::Visible);
-    let schema = answers_schema(&form, &visibility);
-    let examples = example_answers(&form, &visibility);
+    // No runtime ctx exists at generation time; conditions on `config`,
+    // `state` or `env` cannot be evaluated and keep their questions visible.
+    let visibility = resolve_visibility(&resolved, &answers, VisibilityMode::Visible);
+    let schema = answers_schema(&resolved, &visibility);
+    let examples = example_answers(&resolved, &visibility);
     let flow = build_flow_spec(&form, &input.questions);
 
     Ok(GeneratedBundle {
@@ -215,6 +342,61 @@ pub fn build_bundle(input: &GenerationInput) -> Result<GeneratedBundle, String>
     })
 }
 
+/// Rejects constraints no answer can satisfy: a step that is not positive,
+/// `min` above `max`, equal bounds with either one exclusive, and inverted
+/// length or selection counts.
+pub fn check_constraint_ranges(constraint: &Constraint) -> Result<(), String> {
+    if let Some(step) = constraint.multiple_of
+        && step <= 0.0
+    {
+        return Err(tf(
+            "cli.builder.multiple_of_not_positive",
+            &[("value", step.to_string())],
+        ));
+    }
+    if let (Some(min), Some(max)) = (constraint.min, constraint.max)
+        && min > max
+    {
+        return Err(tf(
+            "cli.builder.constraint_min_gt_max",
+            &[("min", min.to_string()), ("max", max.to_string())],
+        ));
+    }
+    if let (Some(min), Some(max)) = (constraint.min, constraint.max)
+        && min == max
+        && (constraint.exclusive_min || constraint.exclusive_max)
+    {
+        return Err(tf(
+            "cli.builder.constraint_empty_range",
+            &[("min", min.to_string()), ("max", max.to_string())],
+        ));
+    }
+    if let (Some(min_len), Some(max_len)) = (constraint.min_len, constraint.max_len)
+        && min_len > max_len
+    {
+        return Err(tf(
+            "cli.builder.constraint_min_len_gt_max_len",
+            &[
+                ("min_len", min_len.to_string()),
+                ("max_len", max_len.to_string()),
+            ],
+        ));
+    }
+    if let (Some(min_selected), Some(max_selected)) =
+        (constraint.min_selected, constraint.max_selected)
+        && min_selected > max_selected
+    {
+        return Err(tf(
+            "cli.builder.constraint_min_selected_gt_max_selected",
+            &[
+                ("min_selected", min_selected.to_string()),
+                ("max_selected", max_selected.to_string()),
+            ],
+        ));
+    }
+    Ok(())
+}
+
 fn validate_input(input: &GenerationInput) -> Result<(), String> {
     if input.dir_name.trim().is_empty() {
         return Err(t("cli.builder.dir_name_required"));
@@ -237,12 +419,13 @@ fn validate_input(input: &GenerationInput) -> Result<(), String> {
                 &[("id", question.id.clone())],
             ));
         }
-        if matches!(question.kind, CliQuestionType::Enum) {
+        if matches!(
+            question.kind,
+            CliQuestionType::Enum | CliQuestionType::MultiEnum
+        ) {
             let has_choices = question
-                .choices
-                .as_ref()
-                .map(|choices| !choices.is_empty())
-                .unwrap_or(false);
+                .known_choices()
+                .is_some_and(|choices| !choices.is_empty());
             if !has_choices {
                 return Err(tf(
                     "cli.builder.enum_question_choices_required",
@@ -292,24 +475,114 @@ fn validate_input(input: &GenerationInput) -> Result<(), String> {
             }
         }
 
-        if let Some(constraint) = &question.constraint {
-            if let (Some(min), Some(max)) = (constraint.min, constraint.max)
+        if matches!(question.kind, CliQuestionType::StringList)
+            && let Some(list) = &question.list
+        {
+            if !list.fields.is_empty() {
+                return Err(tf(
+                    "cli.builder.string_list_fields_not_allowed",
+                    &[("id", question.id.clone())],
+                ));
+            }
+            if let (Some(min), Some(max)) = (list.min_items, list.max_items)
                 && min > max
             {
                 return Err(tf(
-                    "cli.builder.constraint_min_gt_max",
-                    &[("min", min.to_string()), ("max", max.to_string())],
+                    "cli.builder.list_question_min_gt_max",
+                    &[("id", question.id.clone())],
+                ));
+            }
+        }
+
+        if matches!(question.kind, CliQuestionType::Rating)
+            && let Some(constraint) = &question.constraint
+            && [constraint.min, constraint.max]
+                .into_iter()
+                .flatten()
+                .any(|bound| bound.fract() != 0.0)
+        {
+            return Err(tf(
+                "cli.builder.rating_bounds_not_whole",
+                &[("id", question.id.clone())],
+            ));
+        }
+
+        if matches!(question.kind, CliQuestionType::Object) {
+            let fields = question
+                .object
+                .as_ref()
+                .map(|object| object.fields.as_slice())
+                .unwrap_or_default();
+            if fields.is_empty() {
+                return Err(tf(
+                    "cli.builder.object_question_fields_required",
+                    &[("id", question.id.clone())],
+                ));
+            }
+            let mut seen_fields = HashSet::new();
+            for field in fields {
+                if field.id.trim().is_empty() {
+                    return Err(t("cli.builder.object_field_id_empty"));
+                }
+                if !seen_fields.insert(field.id.clone()) {
+                    return Err(tf(
+                        "cli.builder.duplicate_object_field_id",
+                        &[
+                            ("field_id", field.id.clone()),
+                            ("question_id", question.id.clone()),
+                        ],
+                    ));
+                }
+                if matches!(field.kind, CliQuestionType::List | CliQuestionType::Object) {
+                    return Err(t("cli.builder.object_fields_must_be_scalar"));
+                }
+            }
+        }
+
+        if let Some(format) = question.format
+            && let Some(declared) = question
+                .constraint
+                .as_ref()
+                .and_then(|constraint| constraint.format)
+            && declared != format
+        {
+            return Err(tf(
+                "cli.builder.format_conflict",
+                &[
+                    ("id", question.id.clone()),
+                    ("format", format.to_string()),
+                    ("declared", declared.to_string()),
+                ],
+            ));
+        }
+
+        if let Some(constraint) = &question.effective_constraint() {
+            if constraint.format.is_some()
+                && !matches!(
+                    question.kind,
+                    CliQuestionType::String | CliQuestionType::StringList
+                )
+            {
+                return Err(tf(
+                    "cli.builder.format_requires_string",
+                    &[("id", question.id.clone())],
                 ));
             }
-            if let (Some(min_len), Some(max_len)) = (constraint.min_len, constraint.max_len)
-                && min_len > max_len
+            check_constraint_ranges(constraint)?;
+            if let Some(format) = constraint.enforced_format()
+                && !question.default_matches_format(format)
             {
                 return Err(tf(
-                    "cli.builder.constraint_min_len_gt_max_len",
-                    &[
-                        ("min_len", min_len.to_string()),
-                        ("max_len", max_len.to_string()),
-                    ],
+                    "cli.builder.default_format_mismatch",
+                    &[("id", question.id.clone()), ("format", format.to_string())],
+                ));
+            }
+            if let Some(places) = constraint.decimal_places
+                && !question.default_fits_precision()
+            {
+                return Err(tf(
+                    "cli.builder.default_precision_mismatch",
+                    &[("id", question.id.clone()), ("places", places.to_string())],
                 ));
             }
         }
@@ -351,19 +624,27 @@ fn compute_progress_policy(input: Option<&ProgressPolicyInput>) -> ProgressPolic
         skip_answered: policy.skip_answered,
         autofill_defaults: policy.autofill_defaults,
         treat_default_as_answered: policy.treat_default_as_answered,
+        required_only: policy.required_only,
     }
 }
 
 fn to_question_spec(question: &QuestionInput) -> QuestionSpec {
-    let choices = match question.kind {
-        CliQuestionType::Enum => question.choices.clone(),
-        _ => None,
+    let (choices, choices_catalog) = match question.kind {
+        CliQuestionType::Enum | CliQuestionType::MultiEnum => {
+            (question.choices.clone(), question.choices_catalog)
+        }
+        _ => (None, None),
     };
     let list = question.list.as_ref().map(|list| ListSpec {
         min_items: list.min_items,
         max_items: list.max_items,
+        unique: list.unique,
+        unique_by: None,
         fields: list.fields.iter().map(to_question_spec).collect::<Vec<_>>(),
     });
+    let object = question.object.as_ref().map(|object| ObjectSpec {
+        fields: object.fields.iter().map(to_question_spec).collect(),
+    });
 
     serde_json::from_value::<QuestionSpec>(json!({
         "id": question.id,
@@ -373,15 +654,20 @@ fn to_question_spec(question: &QuestionInput) -> QuestionSpec {
         "description": question.description,
         "description_i18n": null,
         "required": question.required,
+        "required_if": question.required_if,
         "choices": choices,
+        "choices_catalog": choices_catalog,
         "default_value": question.default_value,
         "secret": question.secret,
+        "secret_path": question.secret_path,
         "visible_if": question.visible_if,
-        "constraint": question.constraint,
+        "constraint": question.effective_constraint(),
         "list": list,
+        "object": object,
         "policy": QuestionPolicy::default(),
         "computed": question.computed,
-        "computed_overridable": question.computed_overridable
+        "computed_overridable": question.computed_overridable,
+        "order": question.order
     }))
     .expect("QuestionSpec JSON should deserialize")
 }
@@ -395,6 +681,16 @@ impl CliQuestionType {
             CliQuestionType::Number => QuestionType::Number,
             CliQuestionType::Enum => QuestionType::Enum,
             CliQuestionType::List => QuestionType::List,
+            CliQuestionType::Date => QuestionType::Date,
+            CliQuestionType::MultiEnum => QuestionType::MultiEnum,
+            CliQuestionType::Path => QuestionType::Path,
+            CliQuestionType::Duration => QuestionType::Duration,
+            CliQuestionType::Object => QuestionType::Object,
+            CliQuestionType::StringList => QuestionType::StringList,
+            CliQuestionType::Rating => QuestionType::Rating,
+            CliQuestionType::Color => QuestionType::Color,
+            CliQuestionType::Markdown => QuestionType::Markdown,
+            CliQuestionType::Time => QuestionType::Time,
         }
     }
 }
@@ -416,6 +712,7 @@ fn build_flow_spec(form: &FormSpec, questions: &[QuestionInput]) -> QAFlowSpec {
             StepSpec::Question(QuestionStep {
                 question_id: question.id.clone(),
                 next: next_step,
+                card_mode: None,
             }),
         );
     }
@@ -41,6 +41,11 @@ pub fn apply_localized_help(mut cmd: Command) -> Command {
                 a.help(t("cli.help.wizard.i18n_resolved"))
             })
             .mut_arg("i18n_debug", |a| a.help(t("cli.help.wizard.i18n_debug")))
+            .mut_arg("actor", |a| a.help(t("cli.help.wizard.actor")))
+            .mut_arg("output", |a| a.help(t("cli.help.wizard.output")))
+            .mut_arg("reveal_secrets", |a| {
+                a.help(t("cli.help.wizard.reveal_secrets"))
+            })
     });
     cmd = cmd.mut_subcommand("new", |sc| {
         sc.about(t("cli.help.new.about"))
@@ -55,10 +60,30 @@ pub fn apply_localized_help(mut cmd: Command) -> Command {
             .mut_arg("force", |a| a.help(t("cli.help.generate.force")))
             .mut_arg("verbose", |a| a.help(t("cli.help.generate.verbose")))
     });
-    cmd.mut_subcommand("validate", |sc| {
+    cmd = cmd.mut_subcommand("validate", |sc| {
         sc.about(t("cli.help.validate.about"))
             .mut_arg("spec", |a| a.help(t("cli.help.validate.spec")))
             .mut_arg("answers", |a| a.help(t("cli.help.validate.answers")))
+    });
+    cmd.mut_subcommand("answers", |sc| {
+        sc.about(t("cli.help.answers.about"))
+            .mut_subcommand("diff", |diff| {
+                diff.about(t("cli.help.answers.diff.about"))
+                    .mut_arg("old", |a| a.help(t("cli.help.answers.diff.old")))
+                    .mut_arg("new", |a| a.help(t("cli.help.answers.diff.new")))
+                    .mut_arg("spec", |a| a.help(t("cli.help.answers.diff.spec")))
+                    .mut_arg("json", |a| a.help(t("cli.help.answers.diff.json")))
+            })
+            .mut_subcommand("migrate", |migrate| {
+                migrate
+                    .about(t("cli.help.answers.migrate.about"))
+                    .mut_arg("spec", |a| a.help(t("cli.help.answers.migrate.spec")))
+                    .mut_arg("answers", |a| a.help(t("cli.help.answers.migrate.answers")))
+                    .mut_arg("from_version", |a| {
+                        a.help(t("cli.help.answers.migrate.from_version"))
+                    })
+                    .mut_arg("output", |a| a.help(t("cli.help.answers.migrate.output")))
+            })
     })
 }
 
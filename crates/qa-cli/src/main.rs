@@ -1,26 +1,42 @@
 pub mod builder;
 
 mod cli_i18n;
+mod secret_input;
 mod wizard;
 
 use builder::{
-    CliQuestionType, FormInput, GeneratedBundle, GenerationInput, ListInput, QuestionInput,
-    build_bundle, write_bundle,
+    CliQuestionType, FormInput, GeneratedBundle, GenerationInput, ListInput, ObjectInput,
+    QuestionInput, build_bundle, catalog_choices, check_constraint_ranges, write_bundle,
 };
 use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use cli_i18n::{apply_localized_help, init_from_cli_args};
 use greentic_qa_lib::{I18nConfig, ResolvedI18nMap, WizardDriver, WizardFrontend, WizardRunConfig};
 use qa_spec::{
-    FormSpec, ValidationResult, expr::Expr, spec::question::Constraint,
-    spec::validation::CrossFieldValidation, validate,
+    AnswerSet, ChangeKind, FormSpec, KNOWN_THEMES, LintIssue, LintSeverity, Meta, ProgressContext,
+    ProgressState, SystemClock, ValidationResult, VisibilityMode,
+    color::{COLOR_SYNTAX, is_hex_color, normalize_color},
+    date::{is_iso_date, normalize_date},
+    duration::{DURATION_SYNTAX, parse_duration},
+    expr::{CaseArm, Expr},
+    has_errors, resolve_visibility_with_ctx,
+    spec::question::{
+        ChoiceCatalog, Constraint, PathKind, StringFormat, decimal_places_of, split_choices,
+    },
+    spec::validation::{CrossFieldValidation, ValidationSeverity},
+    time_of_day::{TIME_SYNTAX, normalize_time, seconds_of_day},
+    validate,
+    visibility::list_item_context,
 };
-use serde_json::{Number, Value, json};
+use serde_json::{Map, Number, Value, json};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use wizard::{AnswerParseError, PromptContext, Verbosity, WizardPayload, WizardPresenter};
+use wizard::{
+    AnswerParseError, PromptContext, Verbosity, WizardPayload, WizardPresenter, WizardQuestion,
+};
 
 pub(crate) use cli_i18n::{t, tf};
 
@@ -46,6 +62,7 @@ enum RenderMode {
     Text,
     Card,
     Json,
+    Markdown,
 }
 
 #[derive(Subcommand)]
@@ -55,7 +72,7 @@ enum Command {
         /// Path to the FormSpec JSON describing the wizard.
         #[arg(long, value_name = "SPEC")]
         spec: PathBuf,
-        /// Optional JSON file containing initial answers.
+        /// Optional JSON file (or `.cbor` AnswerSet) containing initial answers.
         #[arg(long, value_name = "ANSWERS")]
         answers: Option<PathBuf>,
         /// Show verbose output (statuses, visible questions, parse expectations).
@@ -73,6 +90,21 @@ enum Command {
         /// Attach i18n debug metadata to rendered payloads.
         #[arg(long)]
         i18n_debug: bool,
+        /// Actor recorded in the answer metadata (defaults to the OS user).
+        #[arg(long, value_name = "NAME")]
+        actor: Option<String>,
+        /// Write the completed AnswerSet (answers + meta) as JSON to this file.
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Print and write secret answers instead of redacting them.
+        #[arg(long)]
+        reveal_secrets: bool,
+        /// Ask only required questions; optional ones keep their defaults.
+        #[arg(long)]
+        required_only: bool,
+        /// Session file (`.cbor` for CBOR): resumed when it exists, written on exit.
+        #[arg(long, value_name = "FILE")]
+        session: Option<PathBuf>,
     },
     /// Interactive form generator that creates a bundle of derived artifacts.
     New {
@@ -110,6 +142,45 @@ enum Command {
         #[arg(long, value_name = "ANSWERS")]
         answers: PathBuf,
     },
+    /// Inspect and compare answer documents.
+    Answers {
+        #[command(subcommand)]
+        command: AnswersCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum AnswersCommand {
+    /// Show what changed between two answer documents.
+    Diff {
+        /// Previous answers (an AnswerSet, a plain answers object, or a `.cbor` AnswerSet).
+        #[arg(long, value_name = "OLD")]
+        old: PathBuf,
+        /// Updated answers (an AnswerSet, a plain answers object, or a `.cbor` AnswerSet).
+        #[arg(long, value_name = "NEW")]
+        new: PathBuf,
+        /// Optional FormSpec used to mask secret answers.
+        #[arg(long, value_name = "SPEC")]
+        spec: Option<PathBuf>,
+        /// Print the structured diff as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Migrate answers to the spec's version using its `migrations`.
+    Migrate {
+        /// FormSpec whose migrations are applied.
+        #[arg(long, value_name = "SPEC")]
+        spec: PathBuf,
+        /// Answers to migrate (an AnswerSet, a plain answers object, or a `.cbor` AnswerSet).
+        #[arg(long, value_name = "ANSWERS")]
+        answers: PathBuf,
+        /// Version the answers were recorded against; defaults to the AnswerSet's `spec_version`.
+        #[arg(long, value_name = "VERSION")]
+        from_version: Option<String>,
+        /// Write the migrated AnswerSet here (CBOR for `.cbor`) instead of stdout.
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
 }
 
 struct WizardCliOptions {
@@ -121,6 +192,11 @@ struct WizardCliOptions {
     locale: Option<String>,
     i18n_resolved: Option<PathBuf>,
     i18n_debug: bool,
+    actor: Option<String>,
+    output: Option<PathBuf>,
+    reveal_secrets: bool,
+    required_only: bool,
+    session: Option<PathBuf>,
 }
 
 fn main() -> CliResult<()> {
@@ -138,6 +214,11 @@ fn main() -> CliResult<()> {
             format,
             i18n_resolved,
             i18n_debug,
+            actor,
+            output,
+            reveal_secrets,
+            required_only,
+            session,
         } => run_wizard(WizardCliOptions {
             spec_path: spec,
             answers_path: answers,
@@ -147,6 +228,11 @@ fn main() -> CliResult<()> {
             locale: cli.locale,
             i18n_resolved,
             i18n_debug,
+            actor,
+            output,
+            reveal_secrets,
+            required_only,
+            session,
         }),
         Command::New {
             out,
@@ -160,6 +246,24 @@ fn main() -> CliResult<()> {
             verbose,
         } => run_generate(input, out, force, verbose),
         Command::Validate { spec, answers } => run_validate(spec, answers),
+        Command::Answers {
+            command:
+                AnswersCommand::Diff {
+                    old,
+                    new,
+                    spec,
+                    json,
+                },
+        } => run_answers_diff(old, new, spec, json),
+        Command::Answers {
+            command:
+                AnswersCommand::Migrate {
+                    spec,
+                    answers,
+                    from_version,
+                    output,
+                },
+        } => run_answers_migrate(spec, answers, from_version, output),
     }
 }
 
@@ -203,16 +307,20 @@ fn run_new(out_dir: Option<PathBuf>, force: bool, verbose: bool) -> CliResult<()
         let kind = prompt_question_type()?;
         let required = prompt_bool(&t("cli.prompt.required"), true)?;
         let question_description = prompt_optional(&t("cli.prompt.question_description"))?;
-        let choices = if matches!(kind, CliQuestionType::Enum) {
-            Some(prompt_enum_choices()?)
-        } else {
-            None
-        };
+        let (choices, choices_catalog) =
+            if matches!(kind, CliQuestionType::Enum | CliQuestionType::MultiEnum) {
+                prompt_enum_choices()?
+            } else {
+                (None, None)
+            };
+        let known_choices = choices
+            .clone()
+            .or_else(|| choices_catalog.map(catalog_choices));
         let default_prompt = default_prompt_for(kind, choices.as_deref());
         let default_value = loop {
             let candidate = prompt_optional(&default_prompt)?;
             if let Some(value) = &candidate
-                && let Err(err) = ensure_default_matches_type(kind, value, choices.as_deref())
+                && let Err(err) = ensure_default_matches_type(kind, value, known_choices.as_deref())
             {
                 let hint = describe_type_hint(kind, choices.as_deref(), None);
                 println!(
@@ -236,8 +344,18 @@ fn run_new(out_dir: Option<PathBuf>, force: bool, verbose: bool) -> CliResult<()
         } else {
             false
         };
-        let list = if matches!(kind, CliQuestionType::List) {
-            Some(prompt_list_input()?)
+        let secret_path = if secret {
+            prompt_optional(&t("cli.prompt.secret_path"))?
+        } else {
+            None
+        };
+        let list = match kind {
+            CliQuestionType::List => Some(prompt_list_input()?),
+            CliQuestionType::StringList => prompt_string_list_input()?,
+            _ => None,
+        };
+        let object = if matches!(kind, CliQuestionType::Object) {
+            Some(prompt_object_input()?)
         } else {
             None
         };
@@ -246,12 +364,27 @@ fn run_new(out_dir: Option<PathBuf>, force: bool, verbose: bool) -> CliResult<()
         } else {
             None
         };
+        let required_if = if advanced_features {
+            prompt_required_condition(&questions)?
+        } else {
+            None
+        };
         let constraint = prompt_constraint(kind)?;
+        let format = if matches!(kind, CliQuestionType::String | CliQuestionType::StringList) {
+            prompt_string_format()?
+        } else {
+            None
+        };
         let (computed, computed_overridable) = if advanced_features {
             prompt_computed_field(kind, &questions)?
         } else {
             (None, false)
         };
+        let order = if advanced_features {
+            prompt_optional_i32(&t("cli.prompt.question_order"))?
+        } else {
+            None
+        };
 
         let question = QuestionInput {
             id: question_id,
@@ -261,16 +394,31 @@ fn run_new(out_dir: Option<PathBuf>, force: bool, verbose: bool) -> CliResult<()
             required,
             default_value,
             choices,
+            choices_catalog,
             secret,
+            secret_path,
             list,
+            object,
             visible_if,
+            required_if,
             constraint,
+            format,
             computed,
             computed_overridable,
+            order,
         };
 
         if let Err(err) = validate_question_input(&question) {
-            let list_fields = question.list.as_ref().map(|list| list.fields.as_slice());
+            let list_fields = question
+                .list
+                .as_ref()
+                .map(|list| list.fields.as_slice())
+                .or_else(|| {
+                    question
+                        .object
+                        .as_ref()
+                        .map(|object| object.fields.as_slice())
+                });
             let hint = describe_type_hint(question.kind, question.choices.as_deref(), list_fields);
             println!(
                 "{}",
@@ -294,6 +442,7 @@ fn run_new(out_dir: Option<PathBuf>, force: bool, verbose: bool) -> CliResult<()
     }
 
     let validations = prompt_cross_field_validations(&questions)?;
+    let theme = prompt_theme()?;
     let input = GenerationInput {
         dir_name,
         summary_md: summary,
@@ -303,6 +452,7 @@ fn run_new(out_dir: Option<PathBuf>, force: bool, verbose: bool) -> CliResult<()
             version,
             description,
             progress_policy: None,
+            theme,
         },
         questions,
         validations,
@@ -339,12 +489,13 @@ fn run_new(out_dir: Option<PathBuf>, force: bool, verbose: bool) -> CliResult<()
 }
 
 fn validate_question_input(question: &QuestionInput) -> Result<(), String> {
-    if matches!(question.kind, CliQuestionType::Enum) {
+    if matches!(
+        question.kind,
+        CliQuestionType::Enum | CliQuestionType::MultiEnum
+    ) {
         let has_choices = question
-            .choices
-            .as_ref()
-            .map(|choices| !choices.is_empty())
-            .unwrap_or(false);
+            .known_choices()
+            .is_some_and(|choices| !choices.is_empty());
         if !has_choices {
             return Err(t("cli.new.enum_choices_required"));
         }
@@ -363,9 +514,45 @@ fn validate_question_input(question: &QuestionInput) -> Result<(), String> {
             return Err(t("cli.new.list_min_gt_max"));
         }
     }
+    if matches!(question.kind, CliQuestionType::Object)
+        && question
+            .object
+            .as_ref()
+            .is_none_or(|object| object.fields.is_empty())
+    {
+        return Err(t("cli.new.object_fields_required"));
+    }
+    if let Some(constraint) = &question.constraint {
+        check_constraint_ranges(constraint)?;
+    }
 
     if let Some(default_value) = &question.default_value {
-        ensure_default_matches_type(question.kind, default_value, question.choices.as_deref())?;
+        ensure_default_matches_type(
+            question.kind,
+            default_value,
+            question.known_choices().as_deref(),
+        )?;
+    }
+    if let Some(format) = question
+        .effective_constraint()
+        .and_then(|constraint| constraint.enforced_format())
+        && !question.default_matches_format(format)
+    {
+        return Err(tf(
+            "cli.builder.default_format_mismatch",
+            &[("id", question.id.clone()), ("format", format.to_string())],
+        ));
+    }
+    if let Some(places) = question
+        .constraint
+        .as_ref()
+        .and_then(|constraint| constraint.decimal_places)
+        && !question.default_fits_precision()
+    {
+        return Err(tf(
+            "cli.builder.default_precision_mismatch",
+            &[("id", question.id.clone()), ("places", places.to_string())],
+        ));
     }
 
     Ok(())
@@ -390,11 +577,18 @@ fn ensure_default_matches_type(
 ) -> Result<(), String> {
     match kind {
         CliQuestionType::Boolean => parse_boolean_default(default),
-        CliQuestionType::Integer => parse_integer_default(default),
+        CliQuestionType::Integer | CliQuestionType::Rating => parse_integer_default(default),
         CliQuestionType::Number => parse_number_default(default),
         CliQuestionType::Enum => parse_enum_default(default, choices),
-        CliQuestionType::String => Ok(()),
+        CliQuestionType::MultiEnum => parse_multi_enum_default(default, choices),
+        CliQuestionType::Date => parse_date_default(default),
+        CliQuestionType::Color => parse_color_default(default),
+        CliQuestionType::Time => parse_time_default(default),
+        CliQuestionType::Path => parse_path_default(default),
+        CliQuestionType::Duration => parse_duration_default(default),
+        CliQuestionType::String | CliQuestionType::StringList | CliQuestionType::Markdown => Ok(()),
         CliQuestionType::List => Err(t("cli.new.list_default_not_allowed")),
+        CliQuestionType::Object => Err(t("cli.new.object_default_not_allowed")),
     }
 }
 
@@ -423,6 +617,42 @@ fn parse_number_default(raw: &str) -> Result<(), String> {
         })
 }
 
+fn parse_date_default(raw: &str) -> Result<(), String> {
+    if is_iso_date(raw) {
+        Ok(())
+    } else {
+        Err(t("cli.new.date_default_invalid"))
+    }
+}
+
+fn parse_color_default(raw: &str) -> Result<(), String> {
+    if is_hex_color(raw) {
+        Ok(())
+    } else {
+        Err(t("cli.new.color_default_invalid"))
+    }
+}
+
+fn parse_time_default(raw: &str) -> Result<(), String> {
+    seconds_of_day(raw)
+        .map(|_| ())
+        .ok_or_else(|| t("cli.new.time_default_invalid"))
+}
+
+fn parse_duration_default(raw: &str) -> Result<(), String> {
+    parse_duration(raw)
+        .map(|_| ())
+        .ok_or_else(|| t("cli.new.duration_default_invalid"))
+}
+
+fn parse_path_default(raw: &str) -> Result<(), String> {
+    if raw.trim().is_empty() || raw.contains('\0') {
+        Err(t("cli.new.path_default_invalid"))
+    } else {
+        Ok(())
+    }
+}
+
 fn parse_enum_default(raw: &str, choices: Option<&[String]>) -> Result<(), String> {
     let choices = choices.ok_or_else(|| t("cli.new.enum_default_no_choices"))?;
     if choices.iter().any(|choice| choice == raw) {
@@ -435,6 +665,18 @@ fn parse_enum_default(raw: &str, choices: Option<&[String]>) -> Result<(), Strin
     }
 }
 
+fn parse_multi_enum_default(raw: &str, choices: Option<&[String]>) -> Result<(), String> {
+    let choices = choices.ok_or_else(|| t("cli.new.enum_default_no_choices"))?;
+    if split_choices(raw).all(|selected| choices.iter().any(|choice| choice == selected)) {
+        Ok(())
+    } else {
+        Err(tf(
+            "cli.new.multi_enum_default_must_match",
+            &[("choices", choices.join(", "))],
+        ))
+    }
+}
+
 fn run_generate(
     input_path: PathBuf,
     out_dir: Option<PathBuf>,
@@ -476,10 +718,12 @@ fn run_generate(
 
 fn run_validate(spec_path: PathBuf, answers_path: PathBuf) -> CliResult<()> {
     let spec_json = fs::read_to_string(&spec_path)?;
-    let spec: FormSpec = serde_json::from_str(&spec_json)?;
+    let spec = FormSpec::from_json_str(&spec_json)?;
     let answers_json = fs::read_to_string(answers_path)?;
     let answers: Value = serde_json::from_str(&answers_json)?;
 
+    let issues = spec.analyze();
+    describe_spec_issues(&issues);
     let result = validate(&spec, &answers);
     println!(
         "{}",
@@ -497,21 +741,200 @@ fn run_validate(spec_path: PathBuf, answers_path: PathBuf) -> CliResult<()> {
     );
     describe_validation(&result);
 
-    if result.valid {
+    if !result.valid {
+        Err(t("cli.validate.failed").into())
+    } else if has_errors(&issues) {
+        Err(t("cli.validate.spec_failed").into())
+    } else {
         Ok(())
+    }
+}
+
+/// Prints what [`FormSpec::analyze`] found, errors first.
+fn describe_spec_issues(issues: &[LintIssue]) {
+    for (header, severity) in [
+        ("cli.validate.spec_errors_header", LintSeverity::Error),
+        ("cli.validate.spec_warnings_header", LintSeverity::Warning),
+    ] {
+        let mut matching = issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .peekable();
+        if matching.peek().is_none() {
+            continue;
+        }
+        println!("{}", t(header));
+        for issue in matching {
+            println!("  {} - {}", issue.path, issue.message);
+        }
+    }
+}
+
+fn run_answers_diff(
+    old_path: PathBuf,
+    new_path: PathBuf,
+    spec_path: Option<PathBuf>,
+    as_json: bool,
+) -> CliResult<()> {
+    let old = load_answer_set(&old_path)?;
+    let new = load_answer_set(&new_path)?;
+    let diff = match spec_path {
+        Some(path) => {
+            let spec = FormSpec::from_json_str(&fs::read_to_string(path)?)?;
+            old.diff_with_spec(&new, &spec)
+        }
+        None => old.diff(&new),
+    };
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+    println!(
+        "{}",
+        tf(
+            "cli.answers.diff.summary",
+            &[
+                ("added", diff.summary.added.to_string()),
+                ("removed", diff.summary.removed.to_string()),
+                ("changed", diff.summary.changed.to_string()),
+            ]
+        )
+    );
+    let display = |value: &Option<Value>, masked: bool| {
+        if masked {
+            t("cli.answers.diff.masked")
+        } else {
+            value.as_ref().map(Value::to_string).unwrap_or_default()
+        }
+    };
+    for change in &diff.changes {
+        let old_value = display(&change.old, change.masked);
+        let new_value = display(&change.new, change.masked);
+        let line = match change.kind {
+            ChangeKind::Added => format!("  + {}: {}", change.path, new_value),
+            ChangeKind::Removed => format!("  - {}: {}", change.path, old_value),
+            ChangeKind::Changed => {
+                format!("  ~ {}: {} -> {}", change.path, old_value, new_value)
+            }
+        };
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Reads an answers file holding either a full `AnswerSet` or a bare answers object.
+/// Reads an `AnswerSet` (or plain answers) from JSON, or from CBOR for `.cbor` files.
+fn run_answers_migrate(
+    spec_path: PathBuf,
+    answers_path: PathBuf,
+    from_version: Option<String>,
+    output: Option<PathBuf>,
+) -> CliResult<()> {
+    let spec = FormSpec::from_json_str(&fs::read_to_string(spec_path)?)?;
+    let mut set = load_answer_set(&answers_path)?;
+    if let Some(version) = from_version {
+        set.spec_version = version;
+    }
+    if set.spec_version.is_empty() {
+        return Err(t("cli.answers.migrate.version_required").into());
+    }
+    if set.form_id.is_empty() {
+        set.form_id = spec.id.clone();
+    }
+
+    let (migrated, report) = match set.migrate(&spec) {
+        Ok(migrated) => migrated,
+        Err(error) if !error.stuck_fields().is_empty() => {
+            for stuck in error.stuck_fields() {
+                eprintln!(
+                    "{}",
+                    tf(
+                        "cli.answers.migrate.stuck",
+                        &[
+                            ("field", stuck.field.clone()),
+                            ("op", stuck.op.clone()),
+                            ("reason", stuck.reason.clone()),
+                        ]
+                    )
+                );
+            }
+            return Err(error.into());
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    let summary = tf(
+        "cli.answers.migrate.summary",
+        &[
+            ("from", report.from_version.clone()),
+            ("to", report.to_version.clone()),
+            ("count", report.applied.len().to_string()),
+        ],
+    );
+    match output {
+        Some(path) => {
+            if path.extension().is_some_and(|ext| ext == "cbor") {
+                fs::write(&path, migrated.to_cbor()?)?;
+            } else {
+                fs::write(&path, serde_json::to_string_pretty(&migrated)?)?;
+            }
+            println!("{}", summary);
+        }
+        None => {
+            println!("{}", serde_json::to_string_pretty(&migrated)?);
+            eprintln!("{}", summary);
+        }
+    }
+    Ok(())
+}
+
+fn load_answer_set(path: &Path) -> CliResult<AnswerSet> {
+    if path.extension().is_some_and(|ext| ext == "cbor") {
+        return Ok(AnswerSet::from_cbor(&fs::read(path)?)?);
+    }
+    let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if let Ok(set) = serde_json::from_value::<AnswerSet>(value.clone()) {
+        return Ok(set);
+    }
+    let mut set = AnswerSet::new("", "");
+    set.answers = value;
+    Ok(set)
+}
+
+/// Session JSON stored at `path`, decoding `.cbor` snapshots.
+fn read_session(path: &Path) -> CliResult<String> {
+    if path.extension().is_some_and(|ext| ext == "cbor") {
+        let state = ProgressState::from_cbor(&fs::read(path)?)?;
+        return Ok(serde_json::to_string(&state)?);
+    }
+    Ok(fs::read_to_string(path)?)
+}
+
+/// Stores a session snapshot at `path`, as CBOR for `.cbor` files.
+fn write_session(path: &Path, session_json: &str) -> CliResult<()> {
+    let state: ProgressState = serde_json::from_str(session_json)?;
+    if path.extension().is_some_and(|ext| ext == "cbor") {
+        fs::write(path, state.to_cbor()?)?;
     } else {
-        Err(t("cli.validate.failed").into())
+        fs::write(path, serde_json::to_string_pretty(&state)?)?;
     }
+    Ok(())
 }
 
 fn describe_validation(result: &ValidationResult) {
-    if !result.errors.is_empty() {
-        println!("{}", t("cli.validate.errors_header"));
-        for error in &result.errors {
-            let unknown = t("cli.common.unknown");
+    for (header, list) in [
+        ("cli.validate.errors_header", &result.errors),
+        ("cli.validate.warnings_header", &result.warnings),
+    ] {
+        if list.is_empty() {
+            continue;
+        }
+        println!("{}", t(header));
+        for error in list {
             println!(
                 "  {} - {}",
-                error.path.as_deref().unwrap_or(unknown.as_str()),
+                error_location(error),
                 format_validation_error(error)
             );
         }
@@ -536,6 +959,18 @@ fn describe_validation(result: &ValidationResult) {
     }
 }
 
+/// Where an error applies: every pointer of a multi-field error, otherwise
+/// its single path.
+fn error_location(error: &qa_spec::ValidationError) -> String {
+    if !error.paths.is_empty() {
+        return error.paths.join(", ");
+    }
+    error
+        .path
+        .clone()
+        .unwrap_or_else(|| t("cli.common.unknown"))
+}
+
 fn format_validation_error(error: &qa_spec::ValidationError) -> String {
     if error.message.starts_with("qa_spec.") {
         let key = format!("cli.validate.error.{}", error.message);
@@ -648,10 +1083,13 @@ fn canonicalize_target(path: &Path) -> CliResult<PathBuf> {
 
 fn run_wizard(options: WizardCliOptions) -> CliResult<()> {
     let spec_json = fs::read_to_string(options.spec_path)?;
-    let initial_answers_json = if let Some(path) = options.answers_path {
-        Some(fs::read_to_string(path)?)
-    } else {
-        None
+    let spec = FormSpec::from_json_str(&spec_json)?;
+    let initial_answers_json = match options.answers_path {
+        Some(path) if path.extension().is_some_and(|ext| ext == "cbor") => {
+            Some(serde_json::to_string(&load_answer_set(&path)?.answers)?)
+        }
+        Some(path) => Some(fs::read_to_string(path)?),
+        None => None,
     };
     let resolved = if let Some(path) = options.i18n_resolved {
         Some(load_resolved_i18n_map(&path)?)
@@ -663,6 +1101,7 @@ fn run_wizard(options: WizardCliOptions) -> CliResult<()> {
         RenderMode::Text => WizardFrontend::Text,
         RenderMode::Card => WizardFrontend::Card,
         RenderMode::Json => WizardFrontend::JsonUi,
+        RenderMode::Markdown => WizardFrontend::Markdown,
     };
 
     let config = WizardRunConfig {
@@ -675,14 +1114,106 @@ fn run_wizard(options: WizardCliOptions) -> CliResult<()> {
             debug: options.i18n_debug,
         },
         verbose: options.verbose,
+        required_only: options.required_only,
     };
     let mut driver = WizardDriver::new(config)?;
+    let actor = options.actor.unwrap_or_else(default_actor);
+    let mut meta = Meta::now(actor, "cli");
 
     let mut presenter = WizardPresenter::new(
         Verbosity::from_verbose(options.verbose),
         options.answers_json,
     );
 
+    // Set by `back`: the question to ask instead of the next one, with its answer.
+    let mut revisit: Option<(String, Value)> = None;
+    if let Some(path) = options.session.as_deref().filter(|path| path.exists()) {
+        let resumed: Value =
+            serde_json::from_str(&driver.resume_session_json(&read_session(path)?)?)?;
+        println!(
+            "{}",
+            tf(
+                "cli.wizard.session_resumed",
+                &[("path", path.display().to_string())]
+            )
+        );
+        let next: Value = serde_json::from_str(&driver.next_payload_json()?)?;
+        if let Some(current) = resumed["next_question_id"].as_str()
+            && next["next_question_id"].as_str() != Some(current)
+        {
+            revisit = Some((current.to_string(), resumed["answers"][current].clone()));
+        }
+    }
+    let outcome = run_wizard_loop(
+        &mut driver,
+        options.format,
+        &spec,
+        &mut presenter,
+        &mut meta,
+        revisit,
+    );
+    if let Some(path) = &options.session {
+        write_session(path, &driver.session_json()?)?;
+        println!(
+            "{}",
+            tf(
+                "cli.wizard.session_saved",
+                &[("path", path.display().to_string())]
+            )
+        );
+    }
+    outcome?;
+
+    let ctx: Value = serde_json::from_str(driver.ctx_json())?;
+    let mut answer_set = driver.finish()?.answer_set;
+    answer_set.meta = Some(meta);
+    // Optional questions a full run would still ask: visible as the wizard
+    // saw them, but counted without its `required_only`.
+    let skipped = options.required_only.then(|| {
+        let visibility =
+            resolve_visibility_with_ctx(&spec, &answer_set.answers, &ctx, VisibilityMode::Visible)
+                .map;
+        ProgressContext::new(&answer_set.answers, &Value::Null)
+            .remaining_questions(&spec, &visibility)
+            .len()
+    });
+    if !options.reveal_secrets {
+        answer_set = answer_set.redacted(&spec);
+    }
+    presenter.show_completion(&answer_set);
+    if let Some(skipped) = skipped {
+        println!(
+            "{}",
+            tf(
+                "cli.wizard.optional_skipped",
+                &[("count", skipped.to_string())]
+            )
+        );
+    }
+    if let Some(path) = options.output {
+        fs::write(&path, answer_set.to_json_pretty()?)?;
+        println!(
+            "{}",
+            tf(
+                "cli.wizard.output_written",
+                &[("path", path.display().to_string())]
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// Asks questions until the form is complete, starting with `revisit` when a
+/// resumed session stopped on an answered question.
+fn run_wizard_loop(
+    driver: &mut WizardDriver,
+    format: RenderMode,
+    spec: &FormSpec,
+    presenter: &mut WizardPresenter,
+    meta: &mut Meta,
+    mut revisit: Option<(String, Value)>,
+) -> CliResult<()> {
     loop {
         let frontend_payload = driver.next_payload_json()?;
         let ui_raw = driver
@@ -690,34 +1221,71 @@ fn run_wizard(options: WizardCliOptions) -> CliResult<()> {
             .ok_or_else(|| t("cli.wizard.ui_payload_unavailable"))?
             .to_string();
         let ui: Value = serde_json::from_str(&ui_raw)?;
-        print_render_output(options.format, &frontend_payload, Some(&ui_raw))?;
+        print_render_output(format, &frontend_payload, Some(&ui_raw))?;
 
         let payload = WizardPayload::from_json(&ui)
             .map_err(|err| tf("cli.wizard.ui_error", &[("error", err)]))?;
         presenter.show_header(&payload);
         presenter.show_status(&payload);
 
-        if payload.status == wizard::RenderStatus::Complete {
-            break;
-        }
-        let question_id = ui["next_question_id"]
-            .as_str()
-            .ok_or_else(|| t("cli.wizard.next_question_missing"))?
-            .to_string();
+        let (question_id, current) = match revisit.take() {
+            Some(revisit) => revisit,
+            None if payload.status == wizard::RenderStatus::Complete => {
+                match review_answers(driver, &ui)? {
+                    Some(edit) => edit,
+                    None => break,
+                }
+            }
+            None => (
+                ui["next_question_id"]
+                    .as_str()
+                    .ok_or_else(|| t("cli.wizard.next_question_missing"))?
+                    .to_string(),
+                Value::Null,
+            ),
+        };
 
-        let question = find_question(&ui, &question_id)?;
+        let mut question = find_question(&ui, &question_id)?;
+        if let Some(text) = answer_as_input(&current) {
+            if question["secret"].as_bool().unwrap_or(false) {
+                println!("{}", t("cli.wizard.current_answer_hidden"));
+            } else {
+                println!(
+                    "{}",
+                    tf("cli.wizard.current_answer", &[("value", text.clone())])
+                );
+            }
+            question["default"] = Value::String(text);
+        }
         let question_info = payload.question(&question_id).ok_or_else(|| {
             tf(
                 "cli.wizard.payload_missing_question",
                 &[("id", question_id.clone())],
             )
         })?;
-        let prompt = PromptContext::new(question_info, &payload.progress);
-        let answer = prompt_question(&prompt, &question, &presenter)?;
+        let prompt = PromptContext::new(question_info, &payload.progress)
+            .with_max_answer_bytes(spec.answer_byte_limit());
+        let answer = match prompt_question(&prompt, &question, presenter)? {
+            Prompted::Answer(answer) => answer,
+            Prompted::Back => {
+                let back: Value = serde_json::from_str(&driver.back_json(&question_id)?)?;
+                revisit = Some(match back["next_question_id"].as_str() {
+                    Some(previous) => (previous.to_string(), back["current_value"].clone()),
+                    None => {
+                        println!("{}", t("cli.wizard.at_start"));
+                        (question_id, current)
+                    }
+                });
+                continue;
+            }
+        };
 
-        let submit = driver.submit_patch_json(&json!({ question_id: answer }).to_string())?;
+        let submit =
+            driver.submit_patch_json(&json!({ question_id.clone(): answer }).to_string())?;
+        meta.touch(&SystemClock);
         let submit_value: Value = serde_json::from_str(&submit.response_json)?;
         let validation = gather_validation_details(&submit_value);
+        print_validation_warnings(&validation.warnings);
 
         if submit_value["status"] == "error" {
             if !validation.errors.is_empty() || !validation.unknown_fields.is_empty() {
@@ -727,13 +1295,68 @@ fn run_wizard(options: WizardCliOptions) -> CliResult<()> {
             if !validation.missing_required.is_empty() {
                 print_validation_errors(&validation)?;
             }
+        } else if let Some(stored) = submit_value["answers"].get(&question_id)
+            && stored != &answer
+            && !question["secret"].as_bool().unwrap_or(false)
+        {
+            let shown = stored
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| stored.to_string());
+            println!("{}", tf("cli.wizard.normalized", &[("value", shown)]));
         }
     }
+    Ok(())
+}
 
-    let result = driver.finish()?;
-    presenter.show_completion(&result.answer_set);
+/// Shows the answer summary and asks whether it looks good. Returns the
+/// question to ask again with its current answer when the user says no, or
+/// `None` to finish; an empty answer or the end of input confirms.
+fn review_answers(driver: &WizardDriver, ui: &Value) -> CliResult<Option<(String, Value)>> {
+    let summary: Value = serde_json::from_str(&driver.summary_json()?)?;
+    let ids = summary["entries"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| entry["id"].as_str())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    println!("{}", summary["text"].as_str().unwrap_or_default());
+    loop {
+        print!("{} ", t("cli.wizard.summary_confirm"));
+        io::stdout().flush()?;
+        let confirm = secret_input::read_line(false)?;
+        match confirm.trim().to_ascii_lowercase().as_str() {
+            "" | "y" | "yes" => return Ok(None),
+            "exit" => return Err(t("cli.wizard.aborted").into()),
+            "n" | "no" => {}
+            _ => continue,
+        }
+        print!("{} ", t("cli.wizard.summary_edit"));
+        io::stdout().flush()?;
+        let edit = secret_input::read_line(false)?;
+        let edit = edit.trim();
+        if ids.contains(&edit) {
+            let current = find_question(ui, edit)?["current_value"].clone();
+            return Ok(Some((edit.to_string(), current)));
+        }
+        println!(
+            "{}",
+            tf(
+                "cli.wizard.summary_unknown_question",
+                &[("id", edit.to_string()), ("ids", ids.join(", "))]
+            )
+        );
+    }
+}
 
-    Ok(())
+/// OS user name used as the default wizard actor.
+fn default_actor() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| t("cli.common.unknown"))
 }
 
 fn find_question(ui: &Value, question_id: &str) -> CliResult<Value> {
@@ -755,40 +1378,153 @@ fn find_question(ui: &Value, question_id: &str) -> CliResult<Value> {
     Ok(question)
 }
 
+/// Answers as typed at a prompt: the stored answer's input form, or `None`
+/// for no answer and for objects, which are prompted field by field.
+fn answer_as_input(answer: &Value) -> Option<String> {
+    match answer {
+        Value::Null | Value::Object(_) => None,
+        Value::String(text) => Some(text.clone()),
+        Value::Array(items) if items.iter().all(Value::is_string) => Some(
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        other => Some(other.to_string()),
+    }
+}
+
+/// What was entered at a prompt: an answer, or `back` to return to the
+/// previous question.
+enum Prompted {
+    Answer(Value),
+    Back,
+}
+
 fn prompt_question(
     prompt: &PromptContext,
     question: &Value,
     presenter: &WizardPresenter,
-) -> CliResult<Value> {
+) -> CliResult<Prompted> {
+    if question["type"] == "object" {
+        return prompt_object(prompt, question, presenter);
+    }
+    let secret = question["secret"].as_bool().unwrap_or(false);
     loop {
         presenter.show_prompt(prompt);
         print!("> ");
         io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = if question["type"] == "markdown" {
+            read_multiline(secret)?
+        } else {
+            secret_input::read_line(secret)?
+        };
 
         let trimmed = input.trim();
         if trimmed.eq_ignore_ascii_case("exit") {
             return Err(t("cli.wizard.aborted").into());
         }
+        if trimmed.eq_ignore_ascii_case("back") {
+            return Ok(Prompted::Back);
+        }
 
+        if question["type"] == "list"
+            && let Err(err) = check_list_size(trimmed, prompt.max_answer_bytes)
+        {
+            presenter.show_parse_error(&err);
+            continue;
+        }
         match parse_answer(question, trimmed) {
-            Ok(value) => return Ok(value),
+            Ok(value) => {
+                // Color names are saved as hex and times as HH:MM:SS; show
+                // what was stored.
+                if (question["type"] == "color" || question["type"] == "time")
+                    && !secret
+                    && let Some(stored) = value.as_str()
+                    && stored != trimmed
+                {
+                    println!(
+                        "{}",
+                        tf("cli.wizard.normalized", &[("value", stored.into())])
+                    );
+                }
+                return Ok(Prompted::Answer(value));
+            }
             Err(err) => presenter.show_parse_error(&err),
         }
     }
 }
 
-fn parse_answer(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
-    let prompt_value = if raw.is_empty() {
-        question
-            .get("default")
-            .and_then(Value::as_str)
-            .unwrap_or("")
-            .trim()
-            .to_string()
-    } else {
-        raw.trim().to_string()
+/// Line that ends a multi-line answer.
+const MULTILINE_END: &str = ".";
+
+/// Reads lines for a `markdown` answer until one holding only
+/// [`MULTILINE_END`] or the end of input.
+fn read_multiline(secret: bool) -> io::Result<String> {
+    let mut lines = Vec::new();
+    loop {
+        let line = secret_input::read_line(secret)?;
+        if line.is_empty() || line.trim() == MULTILINE_END {
+            break;
+        }
+        lines.push(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Prompts each field of an `object` question in turn. Fields whose
+/// `visible_if` is false for the fields entered so far are skipped, and
+/// blank optional fields are left out of the answer.
+fn prompt_object(
+    prompt: &PromptContext,
+    question: &Value,
+    presenter: &WizardPresenter,
+) -> CliResult<Prompted> {
+    presenter.show_prompt(prompt);
+    let fields = question["object"]["fields"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut entry = Map::new();
+    for field in fields {
+        let id = field["id"].as_str().unwrap_or_default().to_string();
+        let visible = serde_json::from_value::<Expr>(field["visible_if"].clone())
+            .ok()
+            .and_then(|expr| {
+                let item = Value::Object(entry.clone());
+                expr.evaluate_bool(&list_item_context(&json!({}), &item))
+            })
+            .unwrap_or(true);
+        if !visible {
+            continue;
+        }
+        let field_info = WizardQuestion::from_json(field)?;
+        let Prompted::Answer(value) =
+            prompt_question(&prompt.for_field(&field_info), field, presenter)?
+        else {
+            return Ok(Prompted::Back);
+        };
+        if !value.is_null() {
+            entry.insert(id, value);
+        }
+    }
+    if entry.is_empty() && !question["required"].as_bool().unwrap_or(false) {
+        return Ok(Prompted::Answer(Value::Null));
+    }
+    Ok(Prompted::Answer(Value::Object(entry)))
+}
+
+fn parse_answer(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
+    let prompt_value = if raw.is_empty() {
+        question
+            .get("default")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    } else {
+        raw.trim().to_string()
     };
 
     if prompt_value.is_empty() {
@@ -808,10 +1544,17 @@ fn parse_answer(question: &Value, raw: &str) -> Result<Value, AnswerParseError>
         .unwrap_or("string")
     {
         "boolean" => parse_boolean(&prompt_value),
-        "integer" => parse_integer(&prompt_value),
-        "number" => parse_number(&prompt_value),
+        "integer" | "rating" => parse_integer(&prompt_value),
+        "number" => parse_number(question, &prompt_value),
         "enum" => parse_enum(question, &prompt_value),
+        "multi_enum" => parse_multi_enum(question, &prompt_value),
+        "string_list" => parse_string_list(&prompt_value),
         "list" => parse_list(question, &prompt_value),
+        "date" => parse_date(&prompt_value),
+        "color" => parse_color(&prompt_value),
+        "time" => parse_time(&prompt_value),
+        "path" => parse_path(question, &prompt_value),
+        "duration" => parse_duration_answer(&prompt_value),
         _ => Ok(Value::String(prompt_value)),
     }
 }
@@ -839,7 +1582,13 @@ fn parse_integer(raw: &str) -> Result<Value, AnswerParseError> {
         })
 }
 
-fn parse_number(raw: &str) -> Result<Value, AnswerParseError> {
+/// Parses a number, rejecting more decimals than the question allows. Errors
+/// quote `raw` as typed, since the parsed value may print differently.
+fn parse_number(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
+    let places = question
+        .get("constraint")
+        .and_then(|constraint| constraint.get("decimal_places"))
+        .and_then(Value::as_u64);
     raw.parse::<f64>()
         .map_err(|_| {
             AnswerParseError::new(
@@ -847,6 +1596,16 @@ fn parse_number(raw: &str) -> Result<Value, AnswerParseError> {
                 Some("expected number".to_string()),
             )
         })
+        .and_then(|value| match places {
+            Some(places) if decimal_places_of(value) as u64 > places => Err(AnswerParseError::new(
+                tf(
+                    "cli.parse.decimal_places",
+                    &[("value", raw.to_string()), ("places", places.to_string())],
+                ),
+                Some(format!("expected at most {places} decimal places")),
+            )),
+            _ => Ok(value),
+        })
         .and_then(|value| {
             serde_json::Number::from_f64(value)
                 .map(Value::Number)
@@ -859,6 +1618,84 @@ fn parse_number(raw: &str) -> Result<Value, AnswerParseError> {
         })
 }
 
+fn parse_date(raw: &str) -> Result<Value, AnswerParseError> {
+    normalize_date(raw).map(Value::String).ok_or_else(|| {
+        AnswerParseError::new(
+            t("cli.parse.date_prompt"),
+            Some("expected date (YYYY-MM-DD)".to_string()),
+        )
+    })
+}
+
+/// Accepts a hex color or a common color name, which is translated to hex.
+fn parse_color(raw: &str) -> Result<Value, AnswerParseError> {
+    normalize_color(raw).map(Value::String).ok_or_else(|| {
+        AnswerParseError::new(
+            t("cli.parse.color_prompt"),
+            Some(format!("expected color ({COLOR_SYNTAX} or a color name)")),
+        )
+    })
+}
+
+/// Accepts `14:30`, `14:30:15`, or 12-hour times such as `9am`, stored as
+/// `HH:MM:SS`.
+fn parse_time(raw: &str) -> Result<Value, AnswerParseError> {
+    normalize_time(raw).map(Value::String).ok_or_else(|| {
+        AnswerParseError::new(
+            t("cli.parse.time_prompt"),
+            Some(format!("expected time ({TIME_SYNTAX}, or e.g. 9am)")),
+        )
+    })
+}
+
+fn parse_duration_answer(raw: &str) -> Result<Value, AnswerParseError> {
+    parse_duration(raw).map(Value::from).ok_or_else(|| {
+        AnswerParseError::new(
+            t("cli.parse.duration_prompt"),
+            Some(format!("expected duration ({DURATION_SYNTAX})")),
+        )
+    })
+}
+
+fn parse_path(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
+    if raw.contains('\0') {
+        return Err(AnswerParseError::new(
+            t("cli.parse.path_invalid"),
+            Some("path must not contain NUL bytes".to_string()),
+        ));
+    }
+    let constraint = question.get("constraint");
+    let must_exist = constraint
+        .and_then(|constraint| constraint.get("must_exist"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if !must_exist {
+        return Ok(Value::String(raw.to_string()));
+    }
+
+    let path_arg = [("path", raw.to_string())];
+    let metadata = fs::metadata(raw).map_err(|err| {
+        AnswerParseError::new(
+            tf("cli.parse.path_missing", &path_arg),
+            Some(err.to_string()),
+        )
+    })?;
+    match constraint
+        .and_then(|constraint| constraint.get("kind"))
+        .and_then(Value::as_str)
+    {
+        Some("file") if !metadata.is_file() => Err(AnswerParseError::new(
+            tf("cli.parse.path_not_file", &path_arg),
+            Some("expected a regular file".to_string()),
+        )),
+        Some("dir") if !metadata.is_dir() => Err(AnswerParseError::new(
+            tf("cli.parse.path_not_dir", &path_arg),
+            Some("expected a directory".to_string()),
+        )),
+        _ => Ok(Value::String(raw.to_string())),
+    }
+}
+
 fn parse_enum(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
     let choices = question
         .get("choices")
@@ -890,16 +1727,106 @@ fn parse_enum(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
     }
 }
 
+/// Splits a comma-separated line into items. A JSON array of strings is
+/// taken as-is so that items may themselves contain commas.
+fn parse_string_list(raw: &str) -> Result<Value, AnswerParseError> {
+    if raw.starts_with('[') {
+        return match serde_json::from_str::<Vec<String>>(raw) {
+            Ok(items) => Ok(Value::Array(items.into_iter().map(Value::String).collect())),
+            Err(_) => Err(AnswerParseError::new(
+                t("cli.parse.string_list_invalid"),
+                Some(t("cli.type_hint.string_list.expected")),
+            )),
+        };
+    }
+    Ok(Value::Array(
+        split_choices(raw)
+            .map(|item| Value::String(item.to_string()))
+            .collect(),
+    ))
+}
+
+fn parse_multi_enum(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
+    let allowed = question
+        .get("choices")
+        .and_then(Value::as_array)
+        .ok_or_else(|| AnswerParseError::new(t("cli.parse.choices_missing"), None))?
+        .iter()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>();
+
+    let mut selected = Vec::new();
+    let mut unknown = Vec::new();
+    for raw_choice in split_choices(raw) {
+        match allowed
+            .iter()
+            .find(|choice| choice.eq_ignore_ascii_case(raw_choice))
+        {
+            Some(choice) if !selected.contains(choice) => selected.push(*choice),
+            Some(_) => {}
+            None => unknown.push(raw_choice),
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(Value::Array(
+            selected
+                .into_iter()
+                .map(|choice| Value::String(choice.to_string()))
+                .collect(),
+        ))
+    } else {
+        Err(AnswerParseError::new(
+            tf(
+                "cli.parse.unknown_choices",
+                &[
+                    ("values", unknown.join(", ")),
+                    ("choices", allowed.join(", ")),
+                ],
+            ),
+            Some(tf(
+                "cli.parse.allowed_values",
+                &[("choices", allowed.join(", "))],
+            )),
+        ))
+    }
+}
+
+/// Refuses list JSON longer than the form accepts for one answer, before it
+/// is parsed or submitted.
+fn check_list_size(raw: &str, max_answer_bytes: usize) -> Result<(), AnswerParseError> {
+    if raw.len() <= max_answer_bytes {
+        return Ok(());
+    }
+    Err(AnswerParseError::new(
+        tf(
+            "cli.wizard.list_too_large",
+            &[
+                ("size", raw.len().to_string()),
+                ("limit", max_answer_bytes.to_string()),
+            ],
+        ),
+        None,
+    ))
+}
+
 fn parse_list(question: &Value, raw: &str) -> Result<Value, AnswerParseError> {
     match serde_json::from_str::<Value>(raw) {
         Ok(value) if value.is_array() => Ok(value),
-        Ok(_) => Err(AnswerParseError::new(
-            t("cli.parse.list_array"),
-            Some(tf(
-                "cli.parse.list_expected_fields",
-                &[("fields", describe_list_fields(question))],
-            )),
-        )),
+        Ok(_) => {
+            let fields = describe_list_fields(question);
+            let expected = match question.get("list").and_then(wizard::describe_item_count) {
+                Some(size) => tf(
+                    "cli.parse.list_expected_sized",
+                    &[("fields", fields), ("size", size)],
+                ),
+                None => tf("cli.parse.list_expected_fields", &[("fields", fields)]),
+            };
+            Err(AnswerParseError::new(
+                t("cli.parse.list_array"),
+                Some(expected),
+            ))
+        }
         Err(err) => Err(AnswerParseError::new(
             t("cli.parse.list_invalid"),
             Some(err.to_string()),
@@ -916,7 +1843,7 @@ fn describe_list_fields(question: &Value) -> String {
         .map(|fields| {
             fields
                 .iter()
-                .filter_map(|field| field.get("id").and_then(Value::as_str))
+                .filter_map(wizard::list_field_label)
                 .collect::<Vec<_>>()
                 .join(", ")
         })
@@ -1018,6 +1945,38 @@ fn describe_type_hint(
             expected: t("cli.type_hint.number.expected"),
             example: t("cli.type_hint.number.example"),
         },
+        CliQuestionType::Date => TypeHint {
+            expected: t("cli.type_hint.date.expected"),
+            example: t("cli.type_hint.date.example"),
+        },
+        CliQuestionType::Color => TypeHint {
+            expected: t("cli.type_hint.color.expected"),
+            example: t("cli.type_hint.color.example"),
+        },
+        CliQuestionType::Time => TypeHint {
+            expected: t("cli.type_hint.time.expected"),
+            example: t("cli.type_hint.time.example"),
+        },
+        CliQuestionType::Markdown => TypeHint {
+            expected: t("cli.type_hint.markdown.expected"),
+            example: t("cli.type_hint.markdown.example"),
+        },
+        CliQuestionType::Duration => TypeHint {
+            expected: t("cli.type_hint.duration.expected"),
+            example: t("cli.type_hint.duration.example"),
+        },
+        CliQuestionType::Path => TypeHint {
+            expected: t("cli.type_hint.path.expected"),
+            example: t("cli.type_hint.path.example"),
+        },
+        CliQuestionType::Rating => TypeHint {
+            expected: t("cli.type_hint.rating.expected"),
+            example: t("cli.type_hint.rating.example"),
+        },
+        CliQuestionType::StringList => TypeHint {
+            expected: t("cli.type_hint.string_list.expected"),
+            example: t("cli.type_hint.string_list.example"),
+        },
         CliQuestionType::Enum => {
             let mut expected = t("cli.type_hint.enum.expected");
             if let Some(values) = choices
@@ -1034,6 +1993,29 @@ fn describe_type_hint(
                 .unwrap_or_else(|| t("cli.type_hint.enum.example"));
             TypeHint { expected, example }
         }
+        CliQuestionType::MultiEnum => {
+            let mut expected = t("cli.type_hint.multi_enum.expected");
+            if let Some(values) = choices
+                && !values.is_empty()
+            {
+                expected = tf(
+                    "cli.type_hint.multi_enum.any_of",
+                    &[("choices", values.join(", "))],
+                );
+            }
+            let example = choices
+                .filter(|values| !values.is_empty())
+                .map(|values| {
+                    values
+                        .iter()
+                        .take(2)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_else(|| t("cli.type_hint.multi_enum.example"));
+            TypeHint { expected, example }
+        }
         CliQuestionType::List => {
             let fields_desc = list_fields
                 .map(summarize_list_fields)
@@ -1043,6 +2025,15 @@ fn describe_type_hint(
                 example: t("cli.type_hint.list.example"),
             }
         }
+        CliQuestionType::Object => {
+            let fields_desc = list_fields
+                .map(summarize_list_fields)
+                .unwrap_or_else(|| t("cli.type_hint.list.fields"));
+            TypeHint {
+                expected: tf("cli.type_hint.object.expected", &[("fields", fields_desc)]),
+                example: t("cli.type_hint.object.example"),
+            }
+        }
     }
 }
 
@@ -1061,6 +2052,23 @@ fn prompt_visibility_condition(questions: &[QuestionInput]) -> CliResult<Option<
     Ok(Some(expr))
 }
 
+/// Condition making the question required only while it holds; it then
+/// takes precedence over the `required` answer given earlier.
+fn prompt_required_condition(questions: &[QuestionInput]) -> CliResult<Option<Expr>> {
+    if questions.is_empty() || !prompt_bool(&t("cli.prompt.add_required_condition"), false)? {
+        return Ok(None);
+    }
+    println!(
+        "{}",
+        tf(
+            "cli.prompt.existing_questions",
+            &[("ids", existing_question_ids(questions))]
+        )
+    );
+    let expr = prompt_boolean_expression(questions, 0)?;
+    Ok(Some(expr))
+}
+
 fn prompt_boolean_expression(questions: &[QuestionInput], depth: usize) -> CliResult<Expr> {
     const MAX_DEPTH: usize = 4;
     let mut prompt = t("cli.prompt.expr_type_prefix");
@@ -1071,6 +2079,8 @@ fn prompt_boolean_expression(questions: &[QuestionInput], depth: usize) -> CliRe
     let choice = prompt_line(&prompt, Some(&t("cli.prompt.expr_type_default")))?;
     match choice.trim().to_lowercase().as_str() {
         "is_set" => prompt_is_set_expression(questions),
+        "is_empty" => prompt_is_empty_expression(questions),
+        "coalesce" => prompt_coalesce_expression(questions),
         "and" if depth < MAX_DEPTH => {
             let left = prompt_boolean_expression(questions, depth + 1)?;
             let right = prompt_boolean_expression(questions, depth + 1)?;
@@ -1110,6 +2120,13 @@ fn prompt_comparison_expression(questions: &[QuestionInput]) -> CliResult<Expr>
     let normalized = operator.trim().to_lowercase();
     let left_id = prompt_non_empty(&t("cli.prompt.question_id_compare"), None)?;
     let left_expr = Expr::Answer { path: left_id };
+    if normalized == "matches" {
+        let pattern = prompt_non_empty(&t("cli.prompt.match_pattern"), None)?;
+        return Ok(Expr::Matches {
+            expression: Box::new(left_expr),
+            pattern,
+        });
+    }
     let operand = prompt_line(
         &t("cli.prompt.right_operand_type"),
         Some(&t("cli.prompt.right_operand_default")),
@@ -1141,6 +2158,39 @@ fn prompt_is_set_expression(questions: &[QuestionInput]) -> CliResult<Expr> {
     Ok(Expr::IsSet { path: target })
 }
 
+fn prompt_is_empty_expression(questions: &[QuestionInput]) -> CliResult<Expr> {
+    println!(
+        "{}",
+        tf(
+            "cli.prompt.existing_questions",
+            &[("ids", existing_question_ids(questions))]
+        )
+    );
+    let target = prompt_non_empty(&t("cli.prompt.question_id_empty"), None)?;
+    Ok(Expr::IsEmpty { path: target })
+}
+
+/// The answer to a question, or a fallback while it is unset.
+fn prompt_coalesce_expression(questions: &[QuestionInput]) -> CliResult<Expr> {
+    println!(
+        "{}",
+        tf(
+            "cli.prompt.existing_questions",
+            &[("ids", existing_question_ids(questions))]
+        )
+    );
+    let target = prompt_non_empty(&t("cli.prompt.question_id_coalesce"), None)?;
+    let fallback = prompt_non_empty(&t("cli.prompt.coalesce_fallback"), None)?;
+    Ok(Expr::Coalesce {
+        expressions: vec![
+            Expr::Answer { path: target },
+            Expr::Literal {
+                value: parse_expression_literal(&fallback),
+            },
+        ],
+    })
+}
+
 fn prompt_cross_field_validations(
     questions: &[QuestionInput],
 ) -> CliResult<Vec<CrossFieldValidation>> {
@@ -1150,12 +2200,18 @@ fn prompt_cross_field_validations(
         let message = prompt_non_empty(&t("cli.prompt.validation_message"), None)?;
         let fields = prompt_validation_fields(questions)?;
         let condition = prompt_boolean_expression(questions, 0)?;
+        let severity = if prompt_bool(&t("cli.prompt.validation_warning_only"), false)? {
+            ValidationSeverity::Warning
+        } else {
+            ValidationSeverity::Error
+        };
         validations.push(CrossFieldValidation {
             id,
             message,
             fields,
             condition,
             code: None,
+            severity,
         });
     }
     Ok(validations)
@@ -1209,7 +2265,7 @@ fn prompt_computed_field(
     kind: CliQuestionType,
     existing: &[QuestionInput],
 ) -> CliResult<(Option<Expr>, bool)> {
-    if matches!(kind, CliQuestionType::List)
+    if matches!(kind, CliQuestionType::List | CliQuestionType::Object)
         || !prompt_bool(&t("cli.prompt.compute_question_value"), false)?
     {
         return Ok((None, false));
@@ -1243,6 +2299,12 @@ fn prompt_computed_field(
                     overrides,
                 ));
             }
+            "case" => {
+                if let Some(expr) = prompt_case_expression(existing)? {
+                    let overrides = prompt_bool(&t("cli.prompt.allow_override_computed"), false)?;
+                    return Ok((Some(expr), overrides));
+                }
+            }
             _ => {
                 println!(
                     "{}",
@@ -1256,26 +2318,139 @@ fn prompt_computed_field(
     }
 }
 
-fn prompt_constraint(kind: CliQuestionType) -> CliResult<Option<Constraint>> {
-    let mut constraint = Constraint {
-        pattern: None,
-        min: None,
-        max: None,
-        min_len: None,
-        max_len: None,
+/// A value per choice of an enum question, e.g. a label per region. Returns
+/// `None` when the source question has no choices to map.
+fn prompt_case_expression(existing: &[QuestionInput]) -> CliResult<Option<Expr>> {
+    let source = prompt_non_empty(&t("cli.prompt.source_question_id"), None)?;
+    let Some(choices) = existing
+        .iter()
+        .find(|question| question.id == source)
+        .and_then(|question| question.choices.as_deref())
+        .filter(|choices| !choices.is_empty())
+    else {
+        println!("{}", tf("cli.prompt.case_needs_choices", &[("id", source)]));
+        return Ok(None);
     };
+    let mut arms = Vec::new();
+    for choice in choices {
+        if let Some(value) = prompt_optional(&tf(
+            "cli.prompt.case_arm_value",
+            &[("choice", choice.clone())],
+        ))? {
+            arms.push((choice.clone(), value));
+        }
+    }
+    let fallback = prompt_optional(&t("cli.prompt.case_fallback"))?;
+    Ok(Some(build_case_expression(&source, arms, fallback)))
+}
+
+/// `case` over the answer to `source`, with literal values parsed like other
+/// expression literals.
+fn build_case_expression(
+    source: &str,
+    arms: Vec<(String, String)>,
+    fallback: Option<String>,
+) -> Expr {
+    Expr::Case {
+        on: Box::new(Expr::Answer {
+            path: source.to_string(),
+        }),
+        arms: arms
+            .into_iter()
+            .map(|(choice, value)| CaseArm {
+                when: Value::String(choice),
+                then: Expr::Literal {
+                    value: parse_expression_literal(&value),
+                },
+            })
+            .collect(),
+        default: fallback.map(|value| {
+            Box::new(Expr::Literal {
+                value: parse_expression_literal(&value),
+            })
+        }),
+    }
+}
+
+fn prompt_constraint(kind: CliQuestionType) -> CliResult<Option<Constraint>> {
+    let mut constraint = Constraint::default();
     let mut changed = false;
     if matches!(kind, CliQuestionType::Integer | CliQuestionType::Number) {
         if let Some(min) = prompt_optional_f64(&t("cli.prompt.min_numeric_value"))? {
             constraint.min = Some(min);
+            constraint.exclusive_min = prompt_bound_exclusive()?;
             changed = true;
         }
         if let Some(max) = prompt_optional_f64(&t("cli.prompt.max_numeric_value"))? {
             constraint.max = Some(max);
+            constraint.exclusive_max = prompt_bound_exclusive()?;
+            changed = true;
+        }
+    }
+    if matches!(kind, CliQuestionType::Rating) {
+        if let Some(lowest) = prompt_optional_usize(&t("cli.prompt.rating_min"))? {
+            constraint.min = Some(lowest as f64);
+            changed = true;
+        }
+        if let Some(highest) = prompt_optional_usize(&t("cli.prompt.rating_max"))? {
+            constraint.max = Some(highest as f64);
+            changed = true;
+        }
+    }
+    if matches!(kind, CliQuestionType::Integer | CliQuestionType::Number)
+        && let Some(step) = prompt_optional_multiple_of()?
+    {
+        constraint.multiple_of = Some(step);
+        changed = true;
+    }
+    if matches!(kind, CliQuestionType::Number)
+        && let Some(places) = prompt_optional_usize(&t("cli.prompt.decimal_places"))?
+    {
+        constraint.decimal_places = Some(u8::try_from(places).unwrap_or(u8::MAX));
+        changed = true;
+    }
+    if matches!(kind, CliQuestionType::Duration) {
+        if let Some(min) = prompt_optional_duration(&t("cli.prompt.min_duration"))? {
+            constraint.min = Some(min as f64);
+            changed = true;
+        }
+        if let Some(max) = prompt_optional_duration(&t("cli.prompt.max_duration"))? {
+            constraint.max = Some(max as f64);
+            changed = true;
+        }
+    }
+    if matches!(kind, CliQuestionType::Time) {
+        if let Some(earliest) = prompt_optional_time(&t("cli.prompt.min_time"))? {
+            constraint.min = Some(f64::from(earliest));
             changed = true;
         }
+        if let Some(latest) = prompt_optional_time(&t("cli.prompt.max_time"))? {
+            constraint.max = Some(f64::from(latest));
+            changed = true;
+        }
+    }
+    if matches!(kind, CliQuestionType::MultiEnum) {
+        if let Some(min_selected) = prompt_optional_usize(&t("cli.prompt.min_selected"))? {
+            constraint.min_selected = Some(min_selected);
+            changed = true;
+        }
+        if let Some(max_selected) = prompt_optional_usize(&t("cli.prompt.max_selected"))? {
+            constraint.max_selected = Some(max_selected);
+            changed = true;
+        }
+    }
+    if matches!(kind, CliQuestionType::Path) {
+        constraint.must_exist = prompt_bool(&t("cli.prompt.path_must_exist"), false)?;
+        constraint.path_kind = prompt_path_kind()?;
+        changed |= constraint.must_exist || constraint.path_kind.is_some();
     }
-    if matches!(kind, CliQuestionType::String | CliQuestionType::Enum) {
+    if matches!(
+        kind,
+        CliQuestionType::String
+            | CliQuestionType::Enum
+            | CliQuestionType::StringList
+            | CliQuestionType::Markdown
+    ) {
         if let Some(min_len) = prompt_optional_usize(&t("cli.prompt.min_length"))? {
             constraint.min_len = Some(min_len);
             changed = true;
@@ -1284,12 +2459,18 @@ fn prompt_constraint(kind: CliQuestionType) -> CliResult<Option<Constraint>> {
             constraint.max_len = Some(max_len);
             changed = true;
         }
-        if let Some(pattern) = prompt_optional(&t("cli.prompt.regex_pattern"))?
-            && !pattern.trim().is_empty()
-        {
-            constraint.pattern = Some(pattern);
-            changed = true;
-        }
+    }
+    if matches!(
+        kind,
+        CliQuestionType::String | CliQuestionType::Enum | CliQuestionType::StringList
+    ) && let Some(pattern) = prompt_optional(&t("cli.prompt.regex_pattern"))?
+        && !pattern.trim().is_empty()
+    {
+        constraint.pattern = Some(pattern);
+        changed = true;
+    }
+    if changed && prompt_bool(&t("cli.prompt.custom_error_messages"), false)? {
+        constraint.messages = prompt_error_messages(&constraint)?;
     }
     if changed {
         Ok(Some(constraint))
@@ -1298,46 +2479,186 @@ fn prompt_constraint(kind: CliQuestionType) -> CliResult<Option<Constraint>> {
     }
 }
 
-fn prompt_optional_f64(prompt: &str) -> CliResult<Option<f64>> {
+/// Asks for a replacement message for each error `constraint` can report;
+/// blank answers keep the built-in message.
+fn prompt_error_messages(constraint: &Constraint) -> CliResult<Option<BTreeMap<String, String>>> {
+    let mut messages = BTreeMap::new();
+    for code in constraint.error_codes() {
+        let prompt = tf("cli.prompt.error_message_for", &[("code", code.clone())]);
+        if let Some(message) = prompt_optional(&prompt)? {
+            messages.insert(code, message.trim().to_string());
+        }
+    }
+    Ok((!messages.is_empty()).then_some(messages))
+}
+
+fn prompt_string_format() -> CliResult<Option<StringFormat>> {
     loop {
-        let raw = prompt_line(prompt, None)?;
-        let trimmed = raw.trim();
-        if trimmed.is_empty() {
+        let Some(raw) = prompt_optional(&t("cli.prompt.string_format"))? else {
             return Ok(None);
-        }
-        match trimmed.parse::<f64>() {
-            Ok(value) => return Ok(Some(value)),
+        };
+        match raw.trim().to_lowercase().parse::<StringFormat>() {
+            Ok(format) => return Ok(Some(format)),
             Err(_) => {
-                println!("{}", t("cli.prompt.enter_number_or_blank"));
+                println!(
+                    "{}",
+                    tf(
+                        "cli.prompt.invalid_string_format",
+                        &[("answer", raw.trim().to_string())]
+                    )
+                );
             }
         }
     }
 }
 
-fn parse_expression_literal(raw: &str) -> Value {
-    let trimmed = raw.trim();
-    if trimmed.eq_ignore_ascii_case("true") {
-        return Value::Bool(true);
-    }
-    if trimmed.eq_ignore_ascii_case("false") {
-        return Value::Bool(false);
-    }
-    if let Ok(int_val) = trimmed.parse::<i64>() {
-        return Value::Number(Number::from(int_val));
-    }
-    if let Ok(float_val) = trimmed.parse::<f64>()
-        && let Some(number) = Number::from_f64(float_val)
-    {
-        return Value::Number(number);
-    }
-    if let Ok(json_val) = serde_json::from_str::<Value>(trimmed) {
-        return json_val;
+/// Asks for one of the built-in theme names; blank leaves the form unthemed.
+fn prompt_theme() -> CliResult<Option<String>> {
+    let themes = KNOWN_THEMES.join(", ");
+    loop {
+        let Some(raw) =
+            prompt_optional(&tf("cli.prompt.form_theme", &[("themes", themes.clone())]))?
+        else {
+            return Ok(None);
+        };
+        let name = raw.trim().to_lowercase();
+        if KNOWN_THEMES.contains(&name.as_str()) {
+            return Ok(Some(name));
+        }
+        println!(
+            "{}",
+            tf(
+                "cli.prompt.invalid_theme",
+                &[
+                    ("answer", raw.trim().to_string()),
+                    ("themes", themes.clone())
+                ]
+            )
+        );
     }
-    Value::String(trimmed.to_string())
 }
 
-fn existing_question_ids(questions: &[QuestionInput]) -> String {
-    if questions.is_empty() {
+fn prompt_path_kind() -> CliResult<Option<PathKind>> {
+    loop {
+        let line = prompt_line(&t("cli.prompt.path_kind"), Some("any"))?;
+        match line.trim().to_lowercase().as_str() {
+            "" | "any" => return Ok(None),
+            "file" | "f" => return Ok(Some(PathKind::File)),
+            "dir" | "d" | "directory" => return Ok(Some(PathKind::Dir)),
+            other => {
+                println!(
+                    "{}",
+                    tf(
+                        "cli.prompt.invalid_path_kind",
+                        &[("answer", other.to_string())]
+                    )
+                );
+            }
+        }
+    }
+}
+
+fn prompt_bound_exclusive() -> CliResult<bool> {
+    loop {
+        let line = prompt_line(&t("cli.prompt.bound_kind"), Some("inclusive"))?;
+        match line.trim().to_lowercase().as_str() {
+            "" | "i" | "inclusive" => return Ok(false),
+            "e" | "exclusive" => return Ok(true),
+            other => {
+                println!(
+                    "{}",
+                    tf(
+                        "cli.prompt.invalid_bound_kind",
+                        &[("answer", other.to_string())]
+                    )
+                );
+            }
+        }
+    }
+}
+
+fn prompt_optional_f64(prompt: &str) -> CliResult<Option<f64>> {
+    loop {
+        let raw = prompt_line(prompt, None)?;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        match trimmed.parse::<f64>() {
+            Ok(value) => return Ok(Some(value)),
+            Err(_) => {
+                println!("{}", t("cli.prompt.enter_number_or_blank"));
+            }
+        }
+    }
+}
+
+fn prompt_optional_multiple_of() -> CliResult<Option<f64>> {
+    loop {
+        match prompt_optional_f64(&t("cli.prompt.multiple_of"))? {
+            Some(step) if step <= 0.0 => println!("{}", t("cli.prompt.multiple_of_positive")),
+            step => return Ok(step),
+        }
+    }
+}
+
+fn prompt_optional_duration(prompt: &str) -> CliResult<Option<u64>> {
+    loop {
+        let raw = prompt_line(prompt, None)?;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        match parse_duration(trimmed) {
+            Some(seconds) => return Ok(Some(seconds)),
+            None => {
+                println!("{}", t("cli.prompt.enter_duration_or_blank"));
+            }
+        }
+    }
+}
+
+/// Reads an optional time of day, returned as seconds after midnight.
+fn prompt_optional_time(prompt: &str) -> CliResult<Option<u32>> {
+    loop {
+        let raw = prompt_line(prompt, None)?;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        match normalize_time(trimmed).as_deref().and_then(seconds_of_day) {
+            Some(seconds) => return Ok(Some(seconds)),
+            None => {
+                println!("{}", t("cli.prompt.enter_time_or_blank"));
+            }
+        }
+    }
+}
+
+fn parse_expression_literal(raw: &str) -> Value {
+    let trimmed = raw.trim();
+    if trimmed.eq_ignore_ascii_case("true") {
+        return Value::Bool(true);
+    }
+    if trimmed.eq_ignore_ascii_case("false") {
+        return Value::Bool(false);
+    }
+    if let Ok(int_val) = trimmed.parse::<i64>() {
+        return Value::Number(Number::from(int_val));
+    }
+    if let Ok(float_val) = trimmed.parse::<f64>()
+        && let Some(number) = Number::from_f64(float_val)
+    {
+        return Value::Number(number);
+    }
+    if let Ok(json_val) = serde_json::from_str::<Value>(trimmed) {
+        return json_val;
+    }
+    Value::String(trimmed.to_string())
+}
+
+fn existing_question_ids(questions: &[QuestionInput]) -> String {
+    if questions.is_empty() {
         t("cli.common.none")
     } else {
         questions
@@ -1374,6 +2695,22 @@ fn build_binary_expression(operator: &str, left: Expr, right: Expr) -> Expr {
             left: Box::new(left),
             right: Box::new(right),
         },
+        "in" => Expr::In {
+            needle: Box::new(left),
+            haystack: Box::new(right),
+        },
+        "contains" => Expr::Contains {
+            left: Box::new(left),
+            right: Box::new(right),
+        },
+        "starts_with" => Expr::StartsWith {
+            left: Box::new(left),
+            right: Box::new(right),
+        },
+        "ends_with" => Expr::EndsWith {
+            left: Box::new(left),
+            right: Box::new(right),
+        },
         _ => Expr::Eq {
             left: Box::new(left),
             right: Box::new(right),
@@ -1428,7 +2765,23 @@ fn prompt_question_type() -> CliResult<CliQuestionType> {
     }
 }
 
-fn prompt_enum_choices() -> CliResult<Vec<String>> {
+/// Offers the built-in catalogs first; a blank answer falls back to typing
+/// the choices by hand.
+fn prompt_enum_choices() -> CliResult<(Option<Vec<String>>, Option<ChoiceCatalog>)> {
+    while let Some(raw) = prompt_optional(&t("cli.prompt.choices_catalog"))? {
+        match raw.trim().to_lowercase().parse::<ChoiceCatalog>() {
+            Ok(catalog) => return Ok((None, Some(catalog))),
+            Err(_) => {
+                println!(
+                    "{}",
+                    tf(
+                        "cli.prompt.invalid_choices_catalog",
+                        &[("answer", raw.trim().to_string())]
+                    )
+                );
+            }
+        }
+    }
     loop {
         let raw = prompt_line(&t("cli.prompt.enum_choices"), None)?;
         let normalized = raw
@@ -1441,7 +2794,7 @@ fn prompt_enum_choices() -> CliResult<Vec<String>> {
             println!("{}", t("cli.prompt.enum_choices_required"));
             continue;
         }
-        return Ok(normalized);
+        return Ok((Some(normalized), None));
     }
 }
 
@@ -1461,6 +2814,22 @@ fn prompt_optional_usize(prompt: &str) -> CliResult<Option<usize>> {
     }
 }
 
+fn prompt_optional_i32(prompt: &str) -> CliResult<Option<i32>> {
+    loop {
+        let raw = prompt_line(prompt, None)?;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        match trimmed.parse::<i32>() {
+            Ok(value) => return Ok(Some(value)),
+            Err(_) => {
+                println!("{}", t("cli.prompt.enter_whole_number_or_blank"));
+            }
+        }
+    }
+}
+
 fn prompt_list_input() -> CliResult<ListInput> {
     loop {
         let min_items = prompt_optional_usize(&t("cli.prompt.min_items"))?;
@@ -1507,11 +2876,57 @@ fn prompt_list_input() -> CliResult<ListInput> {
         return Ok(ListInput {
             min_items,
             max_items,
+            unique: false,
             fields,
         });
     }
 }
 
+/// Item bounds for a string list; `None` when none are set.
+fn prompt_string_list_input() -> CliResult<Option<ListInput>> {
+    loop {
+        let min_items = prompt_optional_usize(&t("cli.prompt.min_items"))?;
+        let max_items = prompt_optional_usize(&t("cli.prompt.max_items"))?;
+        if let (Some(min), Some(max)) = (min_items, max_items)
+            && min > max
+        {
+            println!("{}", t("cli.prompt.min_items_gt_max_items"));
+            continue;
+        }
+        let unique = prompt_bool(&t("cli.prompt.unique_items"), false)?;
+        if min_items.is_none() && max_items.is_none() && !unique {
+            return Ok(None);
+        }
+        return Ok(Some(ListInput {
+            min_items,
+            max_items,
+            unique,
+            fields: Vec::new(),
+        }));
+    }
+}
+
+fn prompt_object_input() -> CliResult<ObjectInput> {
+    loop {
+        let fields = prompt_list_fields()?;
+        if fields.is_empty() {
+            println!("{}", t("cli.prompt.object_requires_field"));
+            continue;
+        }
+        println!(
+            "{}",
+            tf(
+                "cli.prompt.defined_object_fields",
+                &[
+                    ("count", fields.len().to_string()),
+                    ("fields", summarize_list_fields(&fields)),
+                ]
+            )
+        );
+        return Ok(ObjectInput { fields });
+    }
+}
+
 fn prompt_list_fields() -> CliResult<Vec<QuestionInput>> {
     let mut fields: Vec<QuestionInput> = Vec::new();
     loop {
@@ -1533,7 +2948,7 @@ fn prompt_list_fields() -> CliResult<Vec<QuestionInput>> {
         let field_title = prompt_non_empty(&mark_required("Field title"), Some(&field_id))?;
         let field_kind = loop {
             let kind = prompt_question_type()?;
-            if matches!(kind, CliQuestionType::List) {
+            if matches!(kind, CliQuestionType::List | CliQuestionType::Object) {
                 println!("{}", t("cli.prompt.nested_list_not_allowed"));
                 continue;
             }
@@ -1541,17 +2956,23 @@ fn prompt_list_fields() -> CliResult<Vec<QuestionInput>> {
         };
         let required = prompt_bool(&t("cli.prompt.field_required"), true)?;
         let field_description = prompt_optional(&t("cli.prompt.field_description"))?;
-        let field_choices = if matches!(field_kind, CliQuestionType::Enum) {
-            Some(prompt_enum_choices()?)
+        let (field_choices, field_catalog) = if matches!(
+            field_kind,
+            CliQuestionType::Enum | CliQuestionType::MultiEnum
+        ) {
+            prompt_enum_choices()?
         } else {
-            None
+            (None, None)
         };
+        let known_choices = field_choices
+            .clone()
+            .or_else(|| field_catalog.map(catalog_choices));
         let default_prompt = default_prompt_for(field_kind, field_choices.as_deref());
         let field_default = loop {
             let candidate = prompt_optional(&default_prompt)?;
             if let Some(value) = &candidate
                 && let Err(err) =
-                    ensure_default_matches_type(field_kind, value, field_choices.as_deref())
+                    ensure_default_matches_type(field_kind, value, known_choices.as_deref())
             {
                 println!(
                     "{}",
@@ -1569,14 +2990,20 @@ fn prompt_list_fields() -> CliResult<Vec<QuestionInput>> {
             title: field_title,
             description: field_description,
             required,
+            required_if: None,
             default_value: field_default,
             choices: field_choices,
+            choices_catalog: field_catalog,
             secret: field_secret,
+            secret_path: None,
             list: None,
+            object: None,
             visible_if: None,
             constraint: None,
+            format: None,
             computed: None,
             computed_overridable: false,
+            order: None,
         };
         if let Err(err) = validate_question_input(&field_input) {
             println!(
@@ -1615,8 +3042,14 @@ fn prompt_list_fields() -> CliResult<Vec<QuestionInput>> {
 fn default_prompt_for(kind: CliQuestionType, choices: Option<&[String]>) -> String {
     match kind {
         CliQuestionType::Boolean => t("cli.prompt.default_value_boolean"),
-        CliQuestionType::Integer => t("cli.prompt.default_value_integer"),
+        CliQuestionType::Integer | CliQuestionType::Rating => t("cli.prompt.default_value_integer"),
         CliQuestionType::Number => t("cli.prompt.default_value_number"),
+        CliQuestionType::Date => t("cli.prompt.default_value_date"),
+        CliQuestionType::Color => t("cli.prompt.default_value_color"),
+        CliQuestionType::Time => t("cli.prompt.default_value_time"),
+        CliQuestionType::Path => t("cli.prompt.default_value_path"),
+        CliQuestionType::Duration => t("cli.prompt.default_value_duration"),
+        CliQuestionType::StringList => t("cli.prompt.default_value_string_list"),
         CliQuestionType::Enum => match choices {
             Some(choices) if !choices.is_empty() => tf(
                 "cli.prompt.default_value_enum_one_of",
@@ -1624,12 +3057,20 @@ fn default_prompt_for(kind: CliQuestionType, choices: Option<&[String]>) -> Stri
             ),
             _ => t("cli.prompt.default_value_enum"),
         },
+        CliQuestionType::MultiEnum => match choices {
+            Some(choices) if !choices.is_empty() => tf(
+                "cli.prompt.default_value_multi_enum_any_of",
+                &[("choices", choices.join("/"))],
+            ),
+            _ => t("cli.prompt.default_value_multi_enum"),
+        },
         _ => t("cli.prompt.default_value"),
     }
 }
 
 struct ValidationDetails {
     errors: Vec<(String, String)>,
+    warnings: Vec<(String, String)>,
     missing_required: Vec<String>,
     unknown_fields: Vec<String>,
 }
@@ -1637,30 +3078,8 @@ struct ValidationDetails {
 fn gather_validation_details(response: &Value) -> ValidationDetails {
     let validation = response.get("validation");
 
-    let errors = validation
-        .and_then(|value| value.get("errors"))
-        .and_then(Value::as_array)
-        .map(|array| {
-            array
-                .iter()
-                .map(|error| {
-                    let unknown = t("cli.common.unknown");
-                    let failed = t("cli.validate.failed");
-                    let path = error
-                        .get("path")
-                        .and_then(Value::as_str)
-                        .unwrap_or(unknown.as_str())
-                        .to_string();
-                    let message = error
-                        .get("message")
-                        .and_then(Value::as_str)
-                        .unwrap_or(failed.as_str())
-                        .to_string();
-                    (path, message)
-                })
-                .collect::<Vec<_>>()
-        })
-        .unwrap_or_default();
+    let errors = described_errors(validation.and_then(|value| value.get("errors")));
+    let warnings = described_errors(response.get("warnings"));
 
     let missing_required = validation
         .and_then(|value| value.get("missing_required"))
@@ -1688,11 +3107,48 @@ fn gather_validation_details(response: &Value) -> ValidationDetails {
 
     ValidationDetails {
         errors,
+        warnings,
         missing_required,
         unknown_fields,
     }
 }
 
+/// `(path, message)` pairs for a response's list of validation errors.
+fn described_errors(list: Option<&Value>) -> Vec<(String, String)> {
+    list.and_then(Value::as_array)
+        .map(|array| {
+            array
+                .iter()
+                .map(|error| {
+                    match serde_json::from_value::<qa_spec::ValidationError>(error.clone()) {
+                        // Built-in messages are keys to localize; custom
+                        // messages from the spec are printed as written.
+                        Ok(error) => (error_location(&error), format_validation_error(&error)),
+                        Err(_) => (t("cli.common.unknown"), t("cli.validate.failed")),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+}
+
+/// Prints warnings to stderr, in yellow when stderr is a terminal that
+/// accepts colors. The answer is kept, so nothing is asked again.
+fn print_validation_warnings(warnings: &[(String, String)]) {
+    let color = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    for (path, message) in warnings {
+        let line = tf(
+            "cli.wizard.warning",
+            &[("path", path.clone()), ("message", message.clone())],
+        );
+        if color {
+            eprintln!("\x1b[33m{}\x1b[0m", line);
+        } else {
+            eprintln!("{}", line);
+        }
+    }
+}
+
 fn print_validation_errors(details: &ValidationDetails) -> CliResult<()> {
     if !details.errors.is_empty() {
         eprintln!("{}", t("cli.validate.errors_header"));
@@ -1758,6 +3214,18 @@ fn print_render_output(
             }
             Ok(())
         }
+        RenderMode::Markdown => {
+            let wrapped: Value = serde_json::from_str(frontend_payload_json)?;
+            let markdown = wrapped
+                .get("markdown")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            println!(
+                "{}",
+                tf("cli.output.markdown", &[("payload", markdown.to_string())])
+            );
+            Ok(())
+        }
     }
 }
 
@@ -1837,76 +3305,574 @@ mod tests {
     }
 
     #[test]
-    fn parse_answer_integer_handles_numbers() {
-        let question = json!({ "type": "integer" });
+    fn prompt_hint_describes_exclusive_bounds() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Limits",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "ratio",
+                "title": "Ratio",
+                "type": "number",
+                "constraint": { "min": 0.0, "exclusive_min": true, "max": 1.0 }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
         assert_eq!(
-            parse_answer(&question, "42").unwrap(),
-            Value::Number(Number::from(42))
+            prompt.hint.as_deref(),
+            Some("(number, greater than 0, at most 1)")
         );
     }
 
     #[test]
-    fn parse_answer_enum_checks_choices() {
-        let question = json!({
-            "type": "enum",
-            "choices": ["alpha", "beta"],
-            "required": true
-        });
-        assert!(parse_answer(&question, "gamma").is_err());
+    fn prompt_percent_rounds_down() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Survey",
+            "progress": { "answered": 2, "total": 3, "remaining": ["q3"], "percent": 66.7 },
+            "questions": [{ "id": "q3", "title": "Third", "type": "string" }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(prompt.percent, 66);
+    }
+
+    #[test]
+    fn prompt_hint_names_email_format() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Contact",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "contact",
+                "title": "Contact email",
+                "type": "string",
+                "constraint": { "format": "email" }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(prompt.hint.as_deref(), Some("(email)"));
+    }
+
+    #[test]
+    fn prompt_hint_names_url_format() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Links",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "homepage",
+                "title": "Homepage",
+                "type": "string",
+                "constraint": { "format": "url" }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
         assert_eq!(
-            parse_answer(&question, "alpha").unwrap(),
-            Value::String("alpha".into())
+            prompt.hint.as_deref(),
+            Some("(url, e.g. https://example.com)")
         );
     }
 
     #[test]
-    fn parse_answer_list_accepts_array() {
-        let question = json!({
-            "type": "list",
-            "required": true,
-            "list": {
-                "fields": [
-                    { "id": "name" },
-                    { "id": "value" }
-                ]
-            }
-        });
-        let value = parse_answer(&question, r#"[{"name": "alpha", "value": "v1"}]"#).unwrap();
-        assert!(value.is_array());
+    fn prompt_hint_names_uuid_format() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Tenancy",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "tenant_id",
+                "title": "Tenant ID",
+                "type": "string",
+                "constraint": { "format": "uuid" }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(prompt.hint.as_deref(), Some("(uuid)"));
     }
 
     #[test]
-    fn parse_answer_list_rejects_non_array() {
-        let question = json!({
-            "type": "list",
-            "required": true,
-            "list": {
-                "fields": [
-                    { "id": "name" }
-                ]
-            }
-        });
-        assert!(parse_answer(&question, r#"{"name": "alpha"}"#).is_err());
+    fn object_questions_prompt_each_field_under_the_object_title() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Mail",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "smtp",
+                "title": "SMTP",
+                "type": "object",
+                "required": true,
+                "object": {
+                    "fields": [
+                        { "id": "port", "title": "Port", "type": "integer", "required": true }
+                    ]
+                }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(
+            prompt.hint.as_deref(),
+            Some("(object, one field at a time)")
+        );
+
+        let field = WizardQuestion::from_json(&json!({
+            "id": "port", "title": "Port", "type": "integer", "required": true
+        }))
+        .unwrap();
+        let field_prompt = prompt.for_field(&field);
+        assert_eq!(field_prompt.title, "SMTP › Port");
+        assert!(field_prompt.required);
+        assert!(ensure_default_matches_type(CliQuestionType::Object, "{}", None).is_err());
     }
 
     #[test]
-    fn parse_answer_respects_defaults() {
-        let question = json!({
-            "type": "string",
-            "default": "default-value",
-            "required": true
-        });
+    fn parse_answer_reports_excess_decimals_as_typed() {
+        let question = json!({ "type": "number", "constraint": { "decimal_places": 2 } });
+        assert_eq!(parse_answer(&question, "19.90").unwrap(), json!(19.9));
+
+        let err = parse_answer(&question, "19.999").unwrap_err();
+        assert!(err.user_message.contains("19.999"), "{}", err.user_message);
+        assert!(err.user_message.contains('2'), "{}", err.user_message);
+
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Shop",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "price",
+                "title": "Price",
+                "type": "number",
+                "constraint": { "min": 0.0, "decimal_places": 2 }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
         assert_eq!(
-            parse_answer(&question, "").unwrap(),
-            Value::String("default-value".into())
+            prompt.hint.as_deref(),
+            Some("(number, at least 0, up to 2 decimal places)")
         );
     }
 
     #[test]
-    fn load_resolved_i18n_map_requires_flat_string_map() {
-        let dir = TempDir::new().expect("temp dir");
-        let valid = dir.path().join("valid.json");
-        fs::write(&valid, r#"{"q1.title":"Naam"}"#).expect("write valid");
+    fn multiple_of_is_shown_in_the_prompt_hint() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Server",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "memory",
+                "title": "Memory (MB)",
+                "type": "integer",
+                "constraint": { "min": 256.0, "multiple_of": 256.0 }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(
+            prompt.hint.as_deref(),
+            Some("(integer, at least 256, multiple of 256)")
+        );
+
+        let constraint = Constraint {
+            multiple_of: Some(0.0),
+            ..Constraint::default()
+        };
+        assert!(check_constraint_ranges(&constraint).is_err());
+    }
+
+    #[test]
+    fn rating_prompts_show_the_scale() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Feedback",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "satisfaction",
+                "title": "Satisfaction",
+                "type": "rating",
+                "range": { "min": 1, "max": 5 }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(prompt.hint.as_deref(), Some("(1-5)"));
+
+        let question = json!({ "type": "rating", "required": true });
+        assert_eq!(parse_answer(&question, "4").unwrap(), json!(4));
+        assert!(parse_answer(&question, "four").is_err());
+        assert!(ensure_default_matches_type(CliQuestionType::Rating, "3", None).is_ok());
+    }
+
+    #[test]
+    fn color_answers_accept_names_and_hex() {
+        let question = json!({ "type": "color", "required": true });
+        assert_eq!(
+            parse_answer(&question, "#336699").unwrap(),
+            json!("#336699")
+        );
+        assert_eq!(parse_answer(&question, "Navy").unwrap(), json!("#000080"));
+        assert!(parse_answer(&question, "#3366").is_err());
+        assert!(parse_answer(&question, "reddish").is_err());
+
+        assert!(ensure_default_matches_type(CliQuestionType::Color, "#336699", None).is_ok());
+        assert!(ensure_default_matches_type(CliQuestionType::Color, "red", None).is_err());
+        assert!(matches!(
+            "colour".parse::<CliQuestionType>(),
+            Ok(CliQuestionType::Color)
+        ));
+
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Theme",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{ "id": "accent", "title": "Accent", "type": "color" }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(
+            prompt.hint.as_deref(),
+            Some("(color, #RRGGBB or a name like red)")
+        );
+    }
+
+    #[test]
+    fn time_answers_are_normalized_to_seconds() {
+        let question = json!({ "type": "time", "required": true });
+        assert_eq!(parse_answer(&question, "14:30").unwrap(), json!("14:30:00"));
+        assert_eq!(parse_answer(&question, "9am").unwrap(), json!("09:00:00"));
+        assert_eq!(
+            parse_answer(&question, "12:15 PM").unwrap(),
+            json!("12:15:00")
+        );
+        assert_eq!(parse_answer(&question, "12am").unwrap(), json!("00:00:00"));
+        assert!(parse_answer(&question, "25:00").is_err());
+        assert!(parse_answer(&question, "14").is_err());
+        assert!(parse_answer(&question, "13pm").is_err());
+
+        assert!(ensure_default_matches_type(CliQuestionType::Time, "08:00", None).is_ok());
+        assert!(ensure_default_matches_type(CliQuestionType::Time, "8am", None).is_err());
+        assert!(matches!(
+            "time-of-day".parse::<CliQuestionType>(),
+            Ok(CliQuestionType::Time)
+        ));
+
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Shift",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "start",
+                "title": "Start",
+                "type": "time",
+                "constraint": { "min": 28800, "max": 63000 }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        let hint = prompt.hint.expect("time hint");
+        assert!(hint.contains("08:00:00"), "{hint}");
+        assert!(hint.contains("17:30:00"), "{hint}");
+    }
+
+    #[test]
+    fn list_hints_mention_the_item_limits() {
+        let question = json!({
+            "id": "servers",
+            "title": "Servers",
+            "type": "list",
+            "list": {
+                "min_items": 1,
+                "max_items": 3,
+                "fields": [{ "id": "host", "type": "string", "title": "Host" }]
+            }
+        });
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Servers",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [question.clone()]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(
+            prompt.hint.as_deref(),
+            Some("(repeatable list, 1 to 3 items)")
+        );
+
+        let err = parse_answer(&question, r#"{"host": "a"}"#).unwrap_err();
+        let expected = err.debug_message.expect("expected shape");
+        assert!(expected.starts_with("expected 1 to 3 items"), "{expected}");
+    }
+
+    #[test]
+    fn parse_answer_integer_handles_numbers() {
+        let question = json!({ "type": "integer" });
+        assert_eq!(
+            parse_answer(&question, "42").unwrap(),
+            Value::Number(Number::from(42))
+        );
+    }
+
+    #[test]
+    fn parse_answer_enum_checks_choices() {
+        let question = json!({
+            "type": "enum",
+            "choices": ["alpha", "beta"],
+            "required": true
+        });
+        assert!(parse_answer(&question, "gamma").is_err());
+        assert_eq!(
+            parse_answer(&question, "alpha").unwrap(),
+            Value::String("alpha".into())
+        );
+    }
+
+    #[test]
+    fn parse_answer_list_accepts_array() {
+        let question = json!({
+            "type": "list",
+            "required": true,
+            "list": {
+                "fields": [
+                    { "id": "name" },
+                    { "id": "value" }
+                ]
+            }
+        });
+        let value = parse_answer(&question, r#"[{"name": "alpha", "value": "v1"}]"#).unwrap();
+        assert!(value.is_array());
+    }
+
+    #[test]
+    fn check_list_size_refuses_oversized_input() {
+        assert!(check_list_size(r#"[{"name": "alpha"}]"#, 64).is_ok());
+
+        let raw = format!(r#"[{{"name": "{}"}}]"#, "a".repeat(100));
+        let err = check_list_size(&raw, 64).expect_err("too large");
+        assert!(err.user_message.contains(&raw.len().to_string()));
+        assert!(err.user_message.contains("64"));
+    }
+
+    #[test]
+    fn parse_answer_list_rejects_non_array() {
+        let question = json!({
+            "type": "list",
+            "required": true,
+            "list": {
+                "fields": [
+                    { "id": "name" }
+                ]
+            }
+        });
+        assert!(parse_answer(&question, r#"{"name": "alpha"}"#).is_err());
+    }
+
+    #[test]
+    fn parse_answer_respects_defaults() {
+        let question = json!({
+            "type": "string",
+            "default": "default-value",
+            "required": true
+        });
+        assert_eq!(
+            parse_answer(&question, "").unwrap(),
+            Value::String("default-value".into())
+        );
+    }
+
+    #[test]
+    fn parse_answer_normalizes_dates() {
+        let question = json!({ "type": "date", "required": true });
+        assert_eq!(
+            parse_answer(&question, "2026/3/7").unwrap(),
+            Value::String("2026-03-07".into())
+        );
+        assert!(parse_answer(&question, "2026-02-30").is_err());
+        assert!(parse_answer(&question, "07/03/2026").is_err());
+    }
+
+    #[test]
+    fn parse_answer_converts_durations_to_seconds() {
+        let question = json!({ "type": "duration", "required": true });
+        assert_eq!(parse_answer(&question, "5m").unwrap(), json!(300));
+        assert_eq!(parse_answer(&question, "1h30m").unwrap(), json!(5_400));
+        assert_eq!(parse_answer(&question, "45").unwrap(), json!(45));
+        assert!(parse_answer(&question, "soon").is_err());
+
+        let defaulted = json!({ "type": "duration", "required": true, "default": "2h" });
+        assert_eq!(parse_answer(&defaulted, "").unwrap(), json!(7_200));
+
+        assert!(ensure_default_matches_type(CliQuestionType::Duration, "30s", None).is_ok());
+        assert!(ensure_default_matches_type(CliQuestionType::Duration, "half", None).is_err());
+    }
+
+    #[test]
+    fn prompt_hint_explains_duration_syntax() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Timeouts",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "timeout",
+                "title": "Timeout",
+                "type": "duration",
+                "constraint": { "min": 1, "max": 3600 }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(
+            prompt.hint.as_deref(),
+            Some("(duration, e.g. 30s, 5m, 2h; at least 1, at most 3600 seconds)")
+        );
+    }
+
+    #[test]
+    fn parse_answer_checks_existing_paths() {
+        let dir = TempDir::new().expect("temp dir");
+        let file = dir.path().join("app.toml");
+        fs::write(&file, "").expect("write file");
+        let dir_path = dir.path().display().to_string();
+        let file_path = file.display().to_string();
+        let missing_path = dir.path().join("missing.toml").display().to_string();
+
+        let any = json!({ "type": "path", "required": true });
+        assert_eq!(
+            parse_answer(&any, &missing_path).unwrap(),
+            Value::String(missing_path.clone())
+        );
+
+        let existing_file = json!({
+            "type": "path",
+            "required": true,
+            "constraint": { "must_exist": true, "kind": "file" }
+        });
+        assert_eq!(
+            parse_answer(&existing_file, &file_path).unwrap(),
+            Value::String(file_path.clone())
+        );
+        let err = parse_answer(&existing_file, &missing_path).unwrap_err();
+        assert!(err.user_message.contains("does not exist"), "{err:?}");
+        let err = parse_answer(&existing_file, &dir_path).unwrap_err();
+        assert!(err.user_message.contains("is not a file"), "{err:?}");
+
+        let existing_dir = json!({
+            "type": "path",
+            "required": true,
+            "constraint": { "must_exist": true, "kind": "dir" }
+        });
+        assert!(parse_answer(&existing_dir, &dir_path).is_ok());
+        let err = parse_answer(&existing_dir, &file_path).unwrap_err();
+        assert!(err.user_message.contains("is not a directory"), "{err:?}");
+    }
+
+    #[test]
+    fn path_defaults_reject_nul_bytes() {
+        assert!(ensure_default_matches_type(CliQuestionType::Path, "./app.toml", None).is_ok());
+        assert!(ensure_default_matches_type(CliQuestionType::Path, "bad\0path", None).is_err());
+    }
+
+    #[test]
+    fn prompt_hint_describes_path_requirements() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Deploy",
+            "progress": { "answered": 0, "total": 2 },
+            "questions": [
+                {
+                    "id": "config",
+                    "title": "Config file",
+                    "type": "path",
+                    "constraint": { "must_exist": true, "kind": "file" }
+                },
+                { "id": "out", "title": "Output", "type": "path" }
+            ]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(prompt.hint.as_deref(), Some("(path, existing file)"));
+        let prompt = PromptContext::new(&payload.questions[1], &payload.progress);
+        assert_eq!(prompt.hint.as_deref(), Some("(path)"));
+    }
+
+    #[test]
+    fn parse_answer_splits_string_lists() {
+        let question = json!({ "type": "string_list", "required": true });
+        assert_eq!(
+            parse_answer(&question, " api.example.com,, www.example.com ").unwrap(),
+            json!(["api.example.com", "www.example.com"])
+        );
+        assert_eq!(
+            parse_answer(&question, r#"["a, b", "c"]"#).unwrap(),
+            json!(["a, b", "c"])
+        );
+        assert!(parse_answer(&question, "[1, 2]").is_err());
+
+        let optional = json!({ "type": "string_list", "required": false, "default": "a, b" });
+        assert_eq!(parse_answer(&optional, "").unwrap(), json!(["a", "b"]));
+    }
+
+    #[test]
+    fn prompt_hint_states_string_list_size() {
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Proxy",
+            "progress": { "answered": 0, "total": 2 },
+            "questions": [
+                {
+                    "id": "hosts",
+                    "title": "Hosts",
+                    "type": "string_list",
+                    "list": { "min_items": 1, "max_items": 3 }
+                },
+                { "id": "tags", "title": "Tags", "type": "string_list" }
+            ]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(
+            prompt.hint.as_deref(),
+            Some("(comma-separated, 1 to 3 items)")
+        );
+        let prompt = PromptContext::new(&payload.questions[1], &payload.progress);
+        assert_eq!(prompt.hint.as_deref(), Some("(comma-separated)"));
+    }
+
+    #[test]
+    fn parse_answer_splits_multi_enum_selections() {
+        let question = json!({
+            "type": "multi_enum",
+            "required": true,
+            "choices": ["us", "eu", "apac"]
+        });
+        assert_eq!(
+            parse_answer(&question, "EU, apac,,eu").unwrap(),
+            json!(["eu", "apac"])
+        );
+
+        let err = parse_answer(&question, "eu, mars").unwrap_err();
+        assert!(err.user_message.contains("mars"), "{}", err.user_message);
+        assert!(
+            err.user_message.contains("us, eu, apac"),
+            "{}",
+            err.user_message
+        );
+    }
+
+    #[test]
+    fn multi_enum_defaults_must_list_known_choices() {
+        let choices = vec!["us".to_string(), "eu".to_string()];
+        assert!(
+            ensure_default_matches_type(CliQuestionType::MultiEnum, "us, eu", Some(&choices))
+                .is_ok()
+        );
+        assert!(
+            ensure_default_matches_type(CliQuestionType::MultiEnum, "us, mars", Some(&choices))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn date_defaults_must_be_iso_dates() {
+        assert!(ensure_default_matches_type(CliQuestionType::Date, "2026-03-07", None).is_ok());
+        assert!(ensure_default_matches_type(CliQuestionType::Date, "2026/03/07", None).is_err());
+    }
+
+    #[test]
+    fn load_resolved_i18n_map_requires_flat_string_map() {
+        let dir = TempDir::new().expect("temp dir");
+        let valid = dir.path().join("valid.json");
+        fs::write(&valid, r#"{"q1.title":"Naam"}"#).expect("write valid");
         let loaded = load_resolved_i18n_map(&valid).expect("flat map should load");
         assert_eq!(loaded.get("q1.title").map(String::as_str), Some("Naam"));
 
@@ -1915,6 +3881,247 @@ mod tests {
         assert!(load_resolved_i18n_map(&invalid).is_err());
     }
 
+    fn url_generation_input(extra: Value) -> GenerationInput {
+        let mut question = json!({
+            "id": "homepage",
+            "type": "string",
+            "title": "Homepage",
+            "format": "url"
+        });
+        if let (Some(question), Some(extra)) = (question.as_object_mut(), extra.as_object()) {
+            question.extend(extra.clone());
+        }
+        from_str(
+            &json!({
+                "dir_name": "links",
+                "form": { "id": "links", "title": "Links", "version": "1.0.0" },
+                "questions": [question]
+            })
+            .to_string(),
+        )
+        .expect("generation input")
+    }
+
+    #[test]
+    fn generation_input_format_reaches_the_spec() {
+        let bundle = build_bundle(&url_generation_input(json!({}))).expect("bundle");
+        let constraint = bundle.spec.questions[0]
+            .constraint
+            .as_ref()
+            .expect("constraint");
+        assert_eq!(constraint.format, Some(StringFormat::Url));
+        assert_eq!(bundle.schema["properties"]["homepage"]["format"], "uri");
+        assert_eq!(bundle.examples["homepage"], "https://example.com");
+    }
+
+    #[test]
+    fn generation_input_theme_reaches_the_presentation() {
+        let input: GenerationInput = serde_json::from_value(json!({
+            "dir_name": "themed",
+            "form": { "id": "themed", "title": "Themed", "version": "1.0.0", "theme": "dark" },
+            "questions": [{ "id": "name", "type": "string", "title": "Name" }]
+        }))
+        .expect("generation input");
+        let bundle = build_bundle(&input).expect("bundle");
+        let presentation = bundle.spec.presentation.expect("presentation");
+        assert_eq!(presentation.theme, Some("dark".into()));
+        assert_eq!(presentation.intro, None);
+    }
+
+    #[test]
+    fn generation_input_order_reaches_the_spec() {
+        let input: GenerationInput = serde_json::from_value(json!({
+            "dir_name": "ordered",
+            "form": { "id": "ordered", "title": "Ordered", "version": "1.0.0" },
+            "questions": [
+                { "id": "name", "type": "string", "title": "Name" },
+                { "id": "region", "type": "string", "title": "Region", "order": -1 }
+            ]
+        }))
+        .expect("generation input");
+        let bundle = build_bundle(&input).expect("bundle");
+        assert_eq!(bundle.spec.questions[1].order, Some(-1));
+        assert_eq!(bundle.spec.ordered_questions()[0].id, "region");
+    }
+
+    #[test]
+    fn case_expressions_map_choices_to_values() {
+        let expr = build_case_expression(
+            "region",
+            vec![
+                ("us-east-1".into(), "US East".into()),
+                ("eu-west-1".into(), "EU West".into()),
+            ],
+            Some("Other".into()),
+        );
+        let label = |region: &str| expr.evaluate_value(&json!({ "region": region }));
+        assert_eq!(label("us-east-1"), Some(json!("US East")));
+        assert_eq!(label("eu-west-1"), Some(json!("EU West")));
+        assert_eq!(label("ap-south-1"), Some(json!("Other")));
+
+        let expr = build_case_expression("size", vec![("small".into(), "1".into())], None);
+        assert_eq!(
+            expr.evaluate_value(&json!({ "size": "small" })),
+            Some(json!(1))
+        );
+        assert_eq!(expr.evaluate_value(&json!({ "size": "large" })), None);
+    }
+
+    #[test]
+    fn binary_expressions_cover_string_operators() {
+        let answer = || Expr::Answer { path: "url".into() };
+        let literal = || Expr::Literal {
+            value: json!("s3://"),
+        };
+        for (operator, op) in [
+            ("starts_with", "starts_with"),
+            ("ends_with", "ends_with"),
+            ("in", "in"),
+            ("unknown", "eq"),
+        ] {
+            let expr = build_binary_expression(operator, answer(), literal());
+            assert_eq!(serde_json::to_value(&expr).unwrap()["op"], op);
+        }
+        let expr = build_binary_expression("starts_with", answer(), literal());
+        assert_eq!(
+            expr.evaluate_bool(&json!({ "url": "s3://bucket/key" })),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn generation_input_required_if_reaches_the_spec() {
+        let input: GenerationInput = from_str(
+            &json!({
+                "dir_name": "proxy",
+                "form": { "id": "proxy", "title": "Proxy", "version": "1.0.0" },
+                "questions": [
+                    { "id": "user", "type": "string", "title": "User" },
+                    {
+                        "id": "password",
+                        "type": "string",
+                        "title": "Password",
+                        "required_if": { "op": "is_set", "path": "user" }
+                    }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("generation input");
+        let bundle = build_bundle(&input).expect("bundle");
+        assert!(bundle.spec.questions[1].required_if.is_some());
+        assert_eq!(
+            bundle.schema["allOf"][0]["then"]["required"],
+            json!(["password"])
+        );
+    }
+
+    #[test]
+    fn generation_input_carries_phone_format() {
+        let input: GenerationInput = from_str(
+            &json!({
+                "dir_name": "contacts",
+                "form": { "id": "contacts", "title": "Contacts", "version": "1.0.0" },
+                "questions": [{
+                    "id": "mobile",
+                    "type": "string",
+                    "title": "Mobile",
+                    "format": "phone",
+                    "default_value": "+4930123456"
+                }]
+            })
+            .to_string(),
+        )
+        .expect("generation input");
+        let bundle = build_bundle(&input).expect("bundle");
+        let constraint = bundle.spec.questions[0]
+            .constraint
+            .as_ref()
+            .expect("constraint");
+        assert_eq!(constraint.format, Some(StringFormat::Phone));
+        assert_eq!(bundle.schema["properties"]["mobile"]["x-format"], "phone");
+
+        let mut bad_default = input.clone();
+        bad_default.questions[0].default_value = Some("030 123456".into());
+        assert!(build_bundle(&bad_default).is_err());
+
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Contacts",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "mobile",
+                "title": "Mobile",
+                "type": "string",
+                "constraint": { "format": "phone" }
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(prompt.hint.as_deref(), Some("(+<country><number>)"));
+    }
+
+    #[test]
+    fn generation_input_keeps_choice_catalogs_compact() {
+        let input: GenerationInput = from_str(
+            &json!({
+                "dir_name": "locale",
+                "form": { "id": "locale", "title": "Locale", "version": "1.0.0" },
+                "questions": [{
+                    "id": "country",
+                    "type": "enum",
+                    "title": "Country",
+                    "choices_catalog": "iso3166-alpha2",
+                    "default_value": "DE"
+                }]
+            })
+            .to_string(),
+        )
+        .expect("generation input");
+        assert!(validate_question_input(&input.questions[0]).is_ok());
+        let bundle = build_bundle(&input).expect("bundle");
+        let question = &bundle.spec.questions[0];
+        assert_eq!(question.choices, None);
+        assert_eq!(question.choices_catalog, Some(ChoiceCatalog::Iso3166Alpha2));
+        let countries = bundle.schema["properties"]["country"]["enum"]
+            .as_array()
+            .expect("enum");
+        assert!(countries.contains(&json!("DE")));
+
+        let mut bad_default = input.questions[0].clone();
+        bad_default.default_value = Some("XX".into());
+        assert!(validate_question_input(&bad_default).is_err());
+
+        let payload = WizardPayload::from_json(&json!({
+            "form_title": "Locale",
+            "progress": { "answered": 0, "total": 1 },
+            "questions": [{
+                "id": "zones",
+                "title": "Time zones",
+                "type": "multi_enum",
+                "catalog": "timezone",
+                "choices": ["Europe/Berlin", "UTC"]
+            }]
+        }))
+        .unwrap();
+        let prompt = PromptContext::new(&payload.questions[0], &payload.progress);
+        assert_eq!(
+            prompt.hint.as_deref(),
+            Some("(time zone, e.g. Europe/Berlin; comma-separated)")
+        );
+    }
+
+    #[test]
+    fn generation_input_format_must_be_consistent() {
+        let bad_default = url_generation_input(json!({ "default_value": "example.com" }));
+        assert!(build_bundle(&bad_default).is_err());
+
+        let conflict = url_generation_input(json!({ "constraint": { "format": "email" } }));
+        assert!(build_bundle(&conflict).is_err());
+
+        let not_string = url_generation_input(json!({ "type": "integer" }));
+        assert!(build_bundle(&not_string).is_err());
+    }
+
     const FIXTURE: &str = include_str!("../../../ci/fixtures/sample_form_generation.json");
 
     #[test]
@@ -1950,6 +4157,882 @@ mod tests {
         assert_eq!(spec_value["id"].as_str(), Some("smoke-form"));
     }
 
+    #[test]
+    fn validate_reports_spec_errors_with_pointer() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let answers_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "requird": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+        fs::write(&answers_path, "{}").expect("write answers");
+
+        let output = qa_cli_command()
+            .arg("validate")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--answers")
+            .arg(&answers_path)
+            .output()
+            .expect("run validate");
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("/questions/0/requird"), "{stderr}");
+        assert!(stderr.contains("\"required\""), "{stderr}");
+    }
+
+    #[test]
+    fn validate_prints_custom_constraint_messages() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let answers_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "project",
+                "title": "Project",
+                "version": "1.0.0",
+                "questions": [{
+                    "id": "key",
+                    "type": "string",
+                    "title": "Project key",
+                    "constraint": {
+                        "pattern": "^[A-Z]{3,10}$",
+                        "messages": {
+                            "pattern_mismatch": "Project keys must be 3-10 uppercase letters"
+                        }
+                    }
+                }]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+        fs::write(&answers_path, json!({ "key": "ab" }).to_string()).expect("write answers");
+
+        let output = qa_cli_command()
+            .arg("validate")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--answers")
+            .arg(&answers_path)
+            .output()
+            .expect("run validate");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("/key - Project keys must be 3-10 uppercase letters"),
+            "{stdout}"
+        );
+    }
+
+    #[test]
+    fn validate_reports_spec_issues() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let answers_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "deploy",
+                "title": "Deploy",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "environment", "type": "string", "title": "Environment" },
+                    {
+                        "id": "replicas",
+                        "type": "integer",
+                        "title": "Replicas",
+                        "visible_if": { "op": "answer", "path": "enviroment" }
+                    },
+                    {
+                        "id": "legacy",
+                        "type": "boolean",
+                        "title": "Legacy",
+                        "visible_if": { "op": "literal", "value": false }
+                    }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+        fs::write(&answers_path, json!({ "environment": "prod" }).to_string())
+            .expect("write answers");
+
+        let output = qa_cli_command()
+            .arg("validate")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--answers")
+            .arg(&answers_path)
+            .output()
+            .expect("run validate");
+        assert!(!output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Spec errors:"), "{stdout}");
+        assert!(
+            stdout.contains(
+                "/questions/1/visible_if - expression reads unknown question 'enviroment'"
+            ),
+            "{stdout}"
+        );
+        assert!(stdout.contains("Spec warnings:"), "{stdout}");
+        assert!(
+            stdout.contains("/questions/2/visible_if - question 'legacy' is never visible"),
+            "{stdout}"
+        );
+        assert!(stdout.contains("Validation result: valid"), "{stdout}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("the spec has errors"), "{stderr}");
+    }
+
+    #[test]
+    fn validate_lists_warnings_and_still_passes() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let answers_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "storage",
+                "title": "Storage",
+                "version": "1.0.0",
+                "questions": [{
+                    "id": "bucket",
+                    "type": "string",
+                    "title": "Bucket name",
+                    "constraint": {
+                        "warnings": {
+                            "pattern": "^[a-z0-9-]+$",
+                            "messages": { "pattern_mismatch": "This bucket name looks unusual" }
+                        }
+                    }
+                }]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+        fs::write(
+            &answers_path,
+            json!({ "bucket": "Team_Bucket" }).to_string(),
+        )
+        .expect("write answers");
+
+        let output = qa_cli_command()
+            .arg("validate")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--answers")
+            .arg(&answers_path)
+            .output()
+            .expect("run validate");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Warnings:"), "{stdout}");
+        assert!(
+            stdout.contains("/bucket - This bucket name looks unusual"),
+            "{stdout}"
+        );
+    }
+
+    #[test]
+    fn wizard_collects_warnings_from_submit_responses() {
+        let details = gather_validation_details(&json!({
+            "status": "need_input",
+            "warnings": [
+                { "path": "/bucket", "message": "This bucket name looks unusual" }
+            ]
+        }));
+        assert!(details.errors.is_empty());
+        assert_eq!(
+            details.warnings,
+            vec![(
+                "/bucket".to_string(),
+                "This bucket name looks unusual".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn wizard_validation_errors_are_localized_or_custom() {
+        let details = gather_validation_details(&json!({
+            "validation": {
+                "errors": [
+                    { "path": "/name", "message": "qa_spec.min_length", "code": "min_length" },
+                    { "path": "/key", "message": "Use uppercase letters", "code": "pattern_mismatch" },
+                    {
+                        "path": "/check_out",
+                        "paths": ["/check_out", "/check_in"],
+                        "message": "Check-out must be after check-in"
+                    }
+                ]
+            }
+        }));
+        assert_eq!(
+            details.errors,
+            vec![
+                (
+                    "/name".to_string(),
+                    "String is shorter than minimum length.".to_string()
+                ),
+                ("/key".to_string(), "Use uppercase letters".to_string()),
+                (
+                    "/check_out, /check_in".to_string(),
+                    "Check-out must be after check-in".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn answers_diff_prints_nested_changes() {
+        let dir = TempDir::new().expect("temp dir");
+        let old_path = dir.path().join("old.json");
+        let new_path = dir.path().join("new.json");
+        fs::write(
+            &old_path,
+            json!({ "servers": [{ "host": "a" }, { "host": "b" }] }).to_string(),
+        )
+        .expect("write old");
+        fs::write(
+            &new_path,
+            json!({ "servers": [{ "host": "a" }, { "host": "c" }] }).to_string(),
+        )
+        .expect("write new");
+
+        let output = qa_cli_command()
+            .args(["answers", "diff", "--old"])
+            .arg(&old_path)
+            .arg("--new")
+            .arg(&new_path)
+            .output()
+            .expect("run answers diff");
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("0 added, 0 removed, 1 changed"), "{stdout}");
+        assert!(
+            stdout.contains("~ /servers/1/host: \"b\" -> \"c\""),
+            "{stdout}"
+        );
+    }
+
+    #[test]
+    fn answers_migrate_rewrites_answer_files_and_lists_stuck_fields() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let answers_path = dir.path().join("answers.json");
+        let output_path = dir.path().join("migrated.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "database",
+                "title": "Database",
+                "version": "2.0.0",
+                "migrations": [{
+                    "from": "1.0.0",
+                    "to": "2.0.0",
+                    "ops": [
+                        { "op": "rename", "from": "db_host", "to": "database_host" },
+                        { "op": "retype", "field": "db_port", "convert": "string_to_integer" }
+                    ]
+                }],
+                "questions": [
+                    { "id": "database_host", "type": "string", "title": "Host", "required": true },
+                    { "id": "db_port", "type": "integer", "title": "Port", "required": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+        fs::write(
+            &answers_path,
+            json!({ "db_host": "db.internal", "db_port": "5432" }).to_string(),
+        )
+        .expect("write answers");
+
+        let output = qa_cli_command()
+            .args(["answers", "migrate", "--spec"])
+            .arg(&spec_path)
+            .arg("--answers")
+            .arg(&answers_path)
+            .args(["--from-version", "1.0.0", "--output"])
+            .arg(&output_path)
+            .output()
+            .expect("run answers migrate");
+        assert!(output.status.success(), "{:?}", output);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("from 1.0.0 to 2.0.0 (2 operations"),
+            "{stdout}"
+        );
+        let migrated: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("json output");
+        assert_eq!(migrated["form_id"], "database");
+        assert_eq!(migrated["spec_version"], "2.0.0");
+        assert_eq!(
+            migrated["answers"],
+            json!({ "database_host": "db.internal", "db_port": 5432 })
+        );
+
+        fs::write(
+            &answers_path,
+            json!({
+                "form_id": "database",
+                "spec_version": "1.0.0",
+                "answers": { "db_host": "db", "db_port": "fifty" }
+            })
+            .to_string(),
+        )
+        .expect("write stuck answers");
+        let output = qa_cli_command()
+            .args(["answers", "migrate", "--spec"])
+            .arg(&spec_path)
+            .arg("--answers")
+            .arg(&answers_path)
+            .output()
+            .expect("run answers migrate");
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("Stuck field db_port (retype): cannot parse \"fifty\" as an integer"),
+            "{stderr}"
+        );
+    }
+
+    #[test]
+    fn wizard_output_includes_meta() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "required": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .args(["--actor", "alice", "--output"])
+            .arg(&output_path)
+            .write_stdin("Greentic\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("answer set json");
+        assert_eq!(written["answers"]["name"], "Greentic");
+        assert_eq!(written["meta"]["actor"], "alice");
+        assert_eq!(written["meta"]["channel"], "cli");
+        assert_eq!(written["meta"]["attempts"], 1);
+    }
+
+    #[test]
+    fn verbose_wizard_explains_unresolved_conditions() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "required": true },
+                    {
+                        "id": "region",
+                        "type": "string",
+                        "title": "Region",
+                        "visible_if": {
+                            "op": "eq",
+                            "left": { "op": "answer", "path": "cloud" },
+                            "right": { "op": "literal", "value": "aws" }
+                        }
+                    }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--verbose")
+            .write_stdin("Greentic\neu\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains(r#" - region: missing_value at {"op":"answer","path":"cloud"}"#),
+            "{stdout}"
+        );
+    }
+
+    #[test]
+    fn wizard_reads_markdown_until_a_lone_dot() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "release",
+                "title": "Release",
+                "version": "1.0.0",
+                "questions": [{
+                    "id": "notes",
+                    "type": "markdown",
+                    "title": "Release notes",
+                    "required": true,
+                    "constraint": { "max_len": 200 }
+                }]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--output")
+            .arg(&output_path)
+            .write_stdin("## Fixes\n\n- Faster startup\n.\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("answer set json");
+        assert_eq!(written["answers"]["notes"], "## Fixes\n\n- Faster startup");
+    }
+
+    #[test]
+    fn wizard_goes_back_to_the_previous_question() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "required": true },
+                    { "id": "replicas", "type": "integer", "title": "Replicas", "required": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--output")
+            .arg(&output_path)
+            .write_stdin("back\napi\nback\nweb\nback\nback\n\n3\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout.contains("Already at the first question."),
+            "{stdout}"
+        );
+        assert!(
+            stdout.contains("Current answer: api (press Enter to keep it)"),
+            "{stdout}"
+        );
+        assert!(stdout.contains("Current answer: web"), "{stdout}");
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("answer set json");
+        assert_eq!(written["answers"], json!({ "name": "web", "replicas": 3 }));
+    }
+
+    #[test]
+    fn wizard_required_only_skips_optional_questions() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "required": true },
+                    { "id": "region", "type": "string", "title": "Region", "required": false },
+                    { "id": "notes", "type": "string", "title": "Notes", "required": false },
+                    { "id": "replicas", "type": "integer", "title": "Replicas", "required": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--required-only")
+            .arg("--output")
+            .arg(&output_path)
+            .write_stdin("api\n3\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("2 of 2 (50%) Replicas"), "{stdout}");
+        assert!(
+            stdout.contains("Skipped 2 optional question(s)"),
+            "{stdout}"
+        );
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("answer set json");
+        assert_eq!(written["answers"], json!({ "name": "api", "replicas": 3 }));
+    }
+
+    #[test]
+    fn wizard_markdown_format_prints_markdown_progress() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "required": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--format")
+            .arg("markdown")
+            .write_stdin("api\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Markdown:\n## Form\n"), "{stdout}");
+        assert!(
+            stdout.contains("> **Next question:** Name (`name`)"),
+            "{stdout}"
+        );
+        assert!(
+            stdout.contains("| Name (`name`) | string | yes | api |"),
+            "{stdout}"
+        );
+    }
+
+    #[test]
+    fn wizard_reviews_answers_and_edits_a_question_before_finishing() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "required": true },
+                    { "id": "token", "type": "string", "title": "Token", "secret": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--output")
+            .arg(&output_path)
+            .arg("--reveal-secrets")
+            .write_stdin("api\nhunter2\nn\nmissing\nn\nname\nweb\ny\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            stdout
+                .contains("Review: Form (form)\n - name (Name) = api\n - token (Token) = ********"),
+            "{stdout}"
+        );
+        assert!(
+            stdout.contains("No visible question 'missing'; pick one of: name, token"),
+            "{stdout}"
+        );
+        assert!(stdout.contains(" - name (Name) = web\n"), "{stdout}");
+        assert!(!stdout.contains("hunter2"), "{stdout}");
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("answers"))
+                .expect("json");
+        assert_eq!(
+            written["answers"],
+            json!({ "name": "web", "token": "hunter2" })
+        );
+    }
+
+    #[test]
+    fn wizard_stores_computed_totals_without_asking() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "order",
+                "title": "Order",
+                "version": "1.0.0",
+                "questions": [
+                    {
+                        "id": "total",
+                        "type": "number",
+                        "title": "Total",
+                        "computed": {
+                            "op": "mul",
+                            "left": { "op": "answer", "path": "price" },
+                            "right": { "op": "answer", "path": "quantity" }
+                        },
+                        "computed_overridable": true
+                    },
+                    { "id": "price", "type": "number", "title": "Price", "required": true },
+                    { "id": "quantity", "type": "integer", "title": "Quantity", "required": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--output")
+            .arg(&output_path)
+            .write_stdin("12.5\n4\n")
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // Not prompted for; the answer review still lists it.
+        assert!(!stdout.contains("%) Total"), "{stdout}");
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("answer set json");
+        assert_eq!(
+            written["answers"],
+            json!({ "price": 12.5, "quantity": 4, "total": 50.0 })
+        );
+    }
+
+    #[test]
+    fn wizard_sessions_resume_where_they_stopped() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let session_path = dir.path().join("session.cbor");
+        let output_path = dir.path().join("answers.json");
+        let write_spec = |version: &str| {
+            fs::write(
+                &spec_path,
+                json!({
+                    "id": "form",
+                    "title": "Form",
+                    "version": version,
+                    "questions": [
+                        { "id": "name", "type": "string", "title": "Name", "required": true },
+                        { "id": "replicas", "type": "integer", "title": "Replicas", "required": true }
+                    ]
+                })
+                .to_string(),
+            )
+            .expect("write spec");
+        };
+        let run = |stdin: &str| {
+            qa_cli_command()
+                .arg("wizard")
+                .arg("--spec")
+                .arg(&spec_path)
+                .arg("--session")
+                .arg(&session_path)
+                .arg("--output")
+                .arg(&output_path)
+                .write_stdin(stdin.to_string())
+                .output()
+                .expect("run wizard")
+        };
+        write_spec("1.0.0");
+
+        let output = run("api\nexit\n");
+        assert!(!output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Session saved to"), "{stdout}");
+        let session =
+            ProgressState::from_cbor(&fs::read(&session_path).expect("read session")).unwrap();
+        assert_eq!(session.answers, Some(json!({ "name": "api" })));
+        assert_eq!(session.current_question_id.as_deref(), Some("replicas"));
+
+        write_spec("2.0.0");
+        let output = run("3\n");
+        assert!(!output.status.success(), "{output:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains(
+                "session was saved with version 1.0.0 of form 'form', but the spec is version 2.0.0"
+            ),
+            "{stderr}"
+        );
+
+        write_spec("1.0.0");
+        let output = run("3\n");
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("Resuming session from"), "{stdout}");
+        assert!(!stdout.contains("%) Name"), "{stdout}");
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("answer set json");
+        assert_eq!(written["answers"], json!({ "name": "api", "replicas": 3 }));
+        let session =
+            ProgressState::from_cbor(&fs::read(&session_path).expect("read session")).unwrap();
+        assert!(session.completed);
+    }
+
+    #[test]
+    fn wizard_accepts_cbor_initial_answers() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let answers_path = dir.path().join("answers.cbor");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "name", "type": "string", "title": "Name", "required": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+        let mut initial = AnswerSet::new("form", "1.0.0");
+        initial.answers = json!({ "name": "From CBOR" });
+        fs::write(&answers_path, initial.to_cbor().expect("encode")).expect("write answers");
+
+        let output = qa_cli_command()
+            .arg("wizard")
+            .arg("--spec")
+            .arg(&spec_path)
+            .arg("--answers")
+            .arg(&answers_path)
+            .arg("--output")
+            .arg(&output_path)
+            .output()
+            .expect("run wizard");
+        assert!(output.status.success(), "{output:?}");
+
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).expect("read output"))
+                .expect("answer set json");
+        assert_eq!(written["answers"]["name"], "From CBOR");
+    }
+
+    #[test]
+    fn wizard_redacts_secret_answers_unless_revealed() {
+        let dir = TempDir::new().expect("temp dir");
+        let spec_path = dir.path().join("form.json");
+        let output_path = dir.path().join("answers.json");
+        fs::write(
+            &spec_path,
+            json!({
+                "id": "form",
+                "title": "Form",
+                "version": "1.0.0",
+                "questions": [
+                    { "id": "token", "type": "string", "title": "Token", "secret": true }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("write spec");
+
+        let run = |extra: &[&str]| {
+            qa_cli_command()
+                .arg("wizard")
+                .arg("--spec")
+                .arg(&spec_path)
+                .args(["--answers-json", "--output"])
+                .arg(&output_path)
+                .args(extra)
+                .write_stdin("tok-hunter2\n")
+                .output()
+                .expect("run wizard")
+        };
+
+        let output = run(&[]);
+        assert!(output.status.success(), "{output:?}");
+        let printed = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(!printed.contains("hunter2"), "{printed}");
+        let written = fs::read_to_string(&output_path).expect("read output");
+        assert!(!written.contains("hunter2"), "{written}");
+        assert!(written.contains("$redacted"));
+
+        let output = run(&["--reveal-secrets"]);
+        assert!(output.status.success(), "{output:?}");
+        let written = fs::read_to_string(&output_path).expect("read output");
+        assert!(written.contains("tok-hunter2"), "{written}");
+    }
+
     #[test]
     fn default_validation_accepts_boolean_values() {
         assert!(ensure_default_matches_type(CliQuestionType::Boolean, "y", None).is_ok());
@@ -1982,18 +5065,70 @@ mod tests {
             title: "Bool".into(),
             description: None,
             required: true,
+            required_if: None,
             default_value: Some("we".into()),
             choices: None,
+            choices_catalog: None,
             secret: false,
+            secret_path: None,
             list: None,
+            object: None,
             visible_if: None,
             constraint: None,
+            format: None,
             computed: None,
             computed_overridable: false,
+            order: None,
         };
         assert!(validate_question_input(&question).is_err());
     }
 
+    #[test]
+    fn validate_question_input_rejects_empty_exclusive_ranges() {
+        let mut question = QuestionInput {
+            id: "rate".into(),
+            kind: CliQuestionType::Number,
+            title: "Rate".into(),
+            description: None,
+            required: true,
+            required_if: None,
+            default_value: None,
+            choices: None,
+            choices_catalog: None,
+            secret: false,
+            secret_path: None,
+            list: None,
+            object: None,
+            visible_if: None,
+            constraint: Some(Constraint {
+                min: Some(0.0),
+                exclusive_min: true,
+                ..Constraint::default()
+            }),
+            format: None,
+            computed: None,
+            computed_overridable: false,
+            order: None,
+        };
+        assert!(validate_question_input(&question).is_ok());
+
+        question.constraint = Some(Constraint {
+            min: Some(1.0),
+            max: Some(1.0),
+            exclusive_max: true,
+            ..Constraint::default()
+        });
+        let err = validate_question_input(&question).expect_err("empty range");
+        assert!(err.contains('1'), "{err}");
+
+        question.constraint = Some(Constraint {
+            min: Some(2.0),
+            max: Some(1.0),
+            ..Constraint::default()
+        });
+        assert!(validate_question_input(&question).is_err());
+    }
+
     #[test]
     fn ensure_allowed_root_accepts_writable_paths_outside_allowed_roots() {
         let allowed_root = TempDir::new().expect("temp dir");
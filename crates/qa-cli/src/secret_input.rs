@@ -0,0 +1,68 @@
+//! Line input for wizard prompts, hiding what is typed for secret questions.
+
+use std::io::{self, BufRead, IsTerminal};
+
+/// Reads one line from stdin. When `secret` is set and stdin is a terminal the
+/// typed characters are not echoed; piped input is read as-is.
+pub fn read_line(secret: bool) -> io::Result<String> {
+    let stdin = io::stdin();
+    if secret && stdin.is_terminal() {
+        return read_hidden_line(&stdin);
+    }
+    let mut line = String::new();
+    stdin.lock().read_line(&mut line)?;
+    Ok(line)
+}
+
+#[cfg(unix)]
+fn read_hidden_line(stdin: &io::Stdin) -> io::Result<String> {
+    use std::os::fd::AsRawFd;
+
+    let fd = stdin.as_raw_fd();
+    let mut original = std::mem::MaybeUninit::<libc::termios>::uninit();
+    // SAFETY: `fd` is the open stdin descriptor and `original` is a valid
+    // out-pointer that `tcgetattr` fully initialises when it returns 0.
+    if unsafe { libc::tcgetattr(fd, original.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: initialised by the successful `tcgetattr` above.
+    let original = unsafe { original.assume_init() };
+    let mut hidden = original;
+    hidden.c_lflag &= !libc::ECHO;
+    // Still echo the newline so the next prompt starts on its own line.
+    hidden.c_lflag |= libc::ECHONL;
+    // SAFETY: `hidden` is a valid termios copied from the terminal's own.
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &hidden) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let _restore = EchoRestore { fd, original };
+
+    let mut line = String::new();
+    stdin.lock().read_line(&mut line)?;
+    Ok(line)
+}
+
+/// Puts the terminal settings back even when reading fails.
+#[cfg(unix)]
+struct EchoRestore {
+    fd: std::os::fd::RawFd,
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl Drop for EchoRestore {
+    fn drop(&mut self) {
+        // SAFETY: restores the settings read from the same descriptor.
+        unsafe {
+            libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Terminals without termios fall back to echoed input.
+#[cfg(not(unix))]
+fn read_hidden_line(stdin: &io::Stdin) -> io::Result<String> {
+    let mut line = String::new();
+    stdin.lock().read_line(&mut line)?;
+    Ok(line)
+}
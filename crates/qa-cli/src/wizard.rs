@@ -1,7 +1,7 @@
 use std::fmt::Write;
 
-use crate::{t, tf};
-use qa_spec::AnswerSet;
+use crate::{describe_list_size, t, tf};
+use qa_spec::{AnswerSet, spec::ChoiceCatalog, time_of_day::format_time_of_day};
 use serde_json::Value;
 
 /// Controls which bits of state the wizard prints.
@@ -87,11 +87,35 @@ impl WizardPresenter {
             }
             println!("{}", entry);
         }
+        if !payload.visibility_diagnostics.is_empty() {
+            println!("{}", t("cli.wizard.visibility_diagnostics"));
+            for note in &payload.visibility_diagnostics {
+                println!(
+                    "{}",
+                    tf(
+                        "cli.wizard.visibility_diagnostic",
+                        &[
+                            ("id", note.question_id.clone()),
+                            ("reason", note.reason.clone()),
+                            ("fragment", note.fragment.clone()),
+                        ]
+                    )
+                );
+            }
+        }
     }
 
     pub fn show_prompt(&self, prompt: &PromptContext) {
         let mut line = if prompt.total > 0 {
-            format!("{}/{} {}", prompt.index, prompt.total, prompt.title)
+            let position = tf(
+                "cli.wizard.prompt_position",
+                &[
+                    ("index", prompt.index.to_string()),
+                    ("total", prompt.total.to_string()),
+                    ("percent", prompt.percent.to_string()),
+                ],
+            );
+            format!("{} {}", position, prompt.title)
         } else {
             format!("{} {}", prompt.index, prompt.title)
         };
@@ -185,6 +209,17 @@ pub struct WizardPayload {
     pub status: RenderStatus,
     pub progress: RenderProgress,
     pub questions: Vec<WizardQuestion>,
+    /// `visible_if` conditions that could not be evaluated, as reported by
+    /// the JSON UI in debug mode.
+    pub visibility_diagnostics: Vec<VisibilityNote>,
+}
+
+/// One `visibility_diagnostics` entry of the JSON UI.
+pub struct VisibilityNote {
+    pub question_id: String,
+    pub reason: String,
+    /// The failed part of the condition as compact JSON.
+    pub fragment: String,
 }
 
 impl WizardPayload {
@@ -212,6 +247,10 @@ impl WizardPayload {
             .and_then(Value::as_u64)
             .unwrap_or(0) as usize;
         let total = progress.get("total").and_then(Value::as_u64).unwrap_or(0) as usize;
+        let percent = progress
+            .get("percent")
+            .and_then(Value::as_f64)
+            .unwrap_or_default();
         let questions = json
             .get("questions")
             .and_then(Value::as_array)
@@ -219,12 +258,39 @@ impl WizardPayload {
             .iter()
             .map(WizardQuestion::from_json)
             .collect::<Result<_, _>>()?;
+        let visibility_diagnostics = json
+            .get("visibility_diagnostics")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|entry| VisibilityNote {
+                question_id: entry
+                    .get("question_id")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                reason: entry
+                    .get("reason")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                fragment: entry
+                    .get("expr_fragment")
+                    .map(Value::to_string)
+                    .unwrap_or_default(),
+            })
+            .collect();
         Ok(Self {
             form_title,
             help,
             status,
-            progress: RenderProgress { answered, total },
+            progress: RenderProgress {
+                answered,
+                total,
+                percent,
+            },
             questions,
+            visibility_diagnostics,
         })
     }
 
@@ -244,6 +310,7 @@ impl WizardPayload {
 pub struct RenderProgress {
     pub answered: usize,
     pub total: usize,
+    pub percent: f64,
 }
 
 /// Status returned by the renderer.
@@ -280,12 +347,17 @@ pub struct WizardQuestion {
     pub kind: QuestionKind,
     pub required: bool,
     pub choices: Vec<String>,
+    /// Description of the built-in catalog `choices` came from, shown instead of the full list.
+    pub catalog: Option<String>,
     pub visible: bool,
     pub list_fields: Vec<String>,
+    pub bounds: Option<String>,
+    pub format: Option<String>,
+    pub path_requirement: Option<String>,
 }
 
 impl WizardQuestion {
-    fn from_json(value: &Value) -> Result<Self, String> {
+    pub(crate) fn from_json(value: &Value) -> Result<Self, String> {
         let id = value
             .get("id")
             .and_then(Value::as_str)
@@ -320,6 +392,7 @@ impl WizardQuestion {
                     .collect::<Vec<_>>()
             })
             .unwrap_or_default();
+        let catalog = value.get("catalog").and_then(describe_catalog);
         let visible = value
             .get("visible")
             .and_then(Value::as_bool)
@@ -332,11 +405,28 @@ impl WizardQuestion {
             .map(|fields| {
                 fields
                     .iter()
-                    .filter_map(|field| field.get("id").and_then(Value::as_str))
-                    .map(String::from)
+                    .filter_map(list_field_label)
                     .collect::<Vec<_>>()
             })
             .unwrap_or_default();
+        let bounds = match kind {
+            QuestionKind::StringList | QuestionKind::List => {
+                value.get("list").and_then(describe_item_count)
+            }
+            QuestionKind::Rating => value.get("range").and_then(describe_rating_scale),
+            QuestionKind::Time => value.get("constraint").and_then(|constraint| {
+                describe_bounds(constraint, |seconds| format_time_of_day(seconds as u32))
+            }),
+            _ => value
+                .get("constraint")
+                .and_then(|constraint| describe_bounds(constraint, |value| value.to_string())),
+        };
+        let format = value
+            .get("constraint")
+            .and_then(|constraint| constraint.get("format"))
+            .and_then(Value::as_str)
+            .map(String::from);
+        let path_requirement = value.get("constraint").and_then(describe_path_requirement);
         Ok(Self {
             id,
             title,
@@ -344,8 +434,12 @@ impl WizardQuestion {
             kind,
             required,
             choices,
+            catalog,
             visible,
             list_fields,
+            bounds,
+            format,
+            path_requirement,
         })
     }
 }
@@ -354,28 +448,75 @@ impl WizardQuestion {
 pub struct PromptContext {
     pub index: usize,
     pub total: usize,
+    /// Whole percent of the form answered, rounded down so 100 means done.
+    pub percent: u32,
     pub title: String,
     pub description: Option<String>,
     pub required: bool,
     pub hint: Option<String>,
     pub choices: Vec<String>,
     pub list_fields: Vec<String>,
+    /// Largest answer the form accepts, in bytes of JSON.
+    pub max_answer_bytes: usize,
 }
 
 impl PromptContext {
     pub fn new(question: &WizardQuestion, progress: &RenderProgress) -> Self {
         let index = progress.answered + 1;
         let total = progress.total;
-        let hint = question.kind.hint(&question.choices);
+        let hint = question.kind.hint(
+            &question.choices,
+            question.catalog.as_deref(),
+            question.bounds.as_deref(),
+            question.format.as_deref(),
+            question.path_requirement.as_deref(),
+        );
         Self {
             index: index.max(1),
             total,
+            percent: progress.percent.floor() as u32,
             title: question.title.clone(),
             description: question.description.clone(),
             required: question.required,
             hint,
             choices: question.choices.clone(),
             list_fields: question.list_fields.clone(),
+            max_answer_bytes: qa_spec::DEFAULT_MAX_ANSWER_BYTES,
+        }
+    }
+
+    /// Applies the form's per-answer byte limit.
+    pub fn with_max_answer_bytes(mut self, max_answer_bytes: usize) -> Self {
+        self.max_answer_bytes = max_answer_bytes;
+        self
+    }
+
+    /// Prompt for one sub-field of the `object` question this prompt is for;
+    /// it keeps the object's position in the form.
+    pub fn for_field(&self, field: &WizardQuestion) -> Self {
+        Self {
+            index: self.index,
+            total: self.total,
+            percent: self.percent,
+            title: tf(
+                "cli.wizard.object_field_title",
+                &[
+                    ("object", self.title.clone()),
+                    ("field", field.title.clone()),
+                ],
+            ),
+            description: field.description.clone(),
+            required: field.required,
+            hint: field.kind.hint(
+                &field.choices,
+                field.catalog.as_deref(),
+                field.bounds.as_deref(),
+                field.format.as_deref(),
+                field.path_requirement.as_deref(),
+            ),
+            choices: field.choices.clone(),
+            list_fields: field.list_fields.clone(),
+            max_answer_bytes: self.max_answer_bytes,
         }
     }
 }
@@ -387,8 +528,18 @@ pub enum QuestionKind {
     Boolean,
     Integer,
     Number,
+    Date,
     Enum,
+    MultiEnum,
     List,
+    Path,
+    Duration,
+    Object,
+    StringList,
+    Rating,
+    Color,
+    Markdown,
+    Time,
     Unknown,
 }
 
@@ -399,22 +550,123 @@ impl QuestionKind {
             "boolean" => QuestionKind::Boolean,
             "integer" => QuestionKind::Integer,
             "number" => QuestionKind::Number,
+            "date" => QuestionKind::Date,
             "enum" => QuestionKind::Enum,
+            "multi_enum" => QuestionKind::MultiEnum,
             "list" => QuestionKind::List,
+            "path" => QuestionKind::Path,
+            "duration" => QuestionKind::Duration,
+            "object" => QuestionKind::Object,
+            "string_list" => QuestionKind::StringList,
+            "rating" => QuestionKind::Rating,
+            "color" => QuestionKind::Color,
+            "markdown" => QuestionKind::Markdown,
+            "time" => QuestionKind::Time,
             _ => QuestionKind::Unknown,
         }
     }
 
-    fn hint(&self, choices: &[String]) -> Option<String> {
+    fn hint(
+        &self,
+        choices: &[String],
+        catalog: Option<&str>,
+        bounds: Option<&str>,
+        format: Option<&str>,
+        path_requirement: Option<&str>,
+    ) -> Option<String> {
+        if let Some(catalog) = catalog {
+            match self {
+                QuestionKind::Enum => {
+                    return Some(tf(
+                        "cli.wizard.hint.enum_catalog",
+                        &[("catalog", catalog.to_string())],
+                    ));
+                }
+                QuestionKind::MultiEnum => {
+                    return Some(tf(
+                        "cli.wizard.hint.multi_enum_catalog",
+                        &[("catalog", catalog.to_string())],
+                    ));
+                }
+                _ => {}
+            }
+        }
+        match (self, bounds) {
+            (QuestionKind::Integer, Some(bounds)) => {
+                return Some(tf(
+                    "cli.wizard.hint.integer_bounded",
+                    &[("bounds", bounds.to_string())],
+                ));
+            }
+            (QuestionKind::Number, Some(bounds)) => {
+                return Some(tf(
+                    "cli.wizard.hint.number_bounded",
+                    &[("bounds", bounds.to_string())],
+                ));
+            }
+            (QuestionKind::Duration, Some(bounds)) => {
+                return Some(tf(
+                    "cli.wizard.hint.duration_bounded",
+                    &[("bounds", bounds.to_string())],
+                ));
+            }
+            (QuestionKind::Time, Some(bounds)) => {
+                return Some(tf(
+                    "cli.wizard.hint.time_bounded",
+                    &[("bounds", bounds.to_string())],
+                ));
+            }
+            (QuestionKind::Rating, Some(scale)) => {
+                return Some(tf(
+                    "cli.wizard.hint.rating",
+                    &[("scale", scale.to_string())],
+                ));
+            }
+            (QuestionKind::List, Some(bounds)) => {
+                return Some(tf(
+                    "cli.wizard.hint.list_bounded",
+                    &[("bounds", bounds.to_string())],
+                ));
+            }
+            (QuestionKind::StringList, Some(bounds)) => {
+                return Some(tf(
+                    "cli.wizard.hint.string_list_bounded",
+                    &[("bounds", bounds.to_string())],
+                ));
+            }
+            _ => {}
+        }
         match self {
+            QuestionKind::String if format == Some("email") => Some(t("cli.wizard.hint.email")),
+            QuestionKind::String if format == Some("url") => Some(t("cli.wizard.hint.url")),
+            QuestionKind::String if format == Some("uuid") => Some(t("cli.wizard.hint.uuid")),
+            QuestionKind::String if format == Some("phone") => Some(t("cli.wizard.hint.phone")),
             QuestionKind::Boolean => Some(t("cli.wizard.hint.boolean")),
             QuestionKind::Integer => Some(t("cli.wizard.hint.integer")),
             QuestionKind::Number => Some(t("cli.wizard.hint.number")),
+            QuestionKind::Date => Some(t("cli.wizard.hint.date")),
+            QuestionKind::Color => Some(t("cli.wizard.hint.color")),
+            QuestionKind::Markdown => Some(t("cli.wizard.hint.markdown")),
+            QuestionKind::Time => Some(t("cli.wizard.hint.time")),
+            QuestionKind::Duration => Some(t("cli.wizard.hint.duration")),
             QuestionKind::Enum if !choices.is_empty() => Some(tf(
                 "cli.wizard.hint.enum",
                 &[("choices", choices.join("/"))],
             )),
+            QuestionKind::MultiEnum if !choices.is_empty() => Some(tf(
+                "cli.wizard.hint.multi_enum",
+                &[("choices", choices.join("/"))],
+            )),
             QuestionKind::List => Some(t("cli.wizard.hint.list")),
+            QuestionKind::Object => Some(t("cli.wizard.hint.object")),
+            QuestionKind::StringList => Some(t("cli.wizard.hint.string_list")),
+            QuestionKind::Path => Some(match path_requirement {
+                Some(requirement) => tf(
+                    "cli.wizard.hint.path_requirement",
+                    &[("requirement", requirement.to_string())],
+                ),
+                None => t("cli.wizard.hint.path"),
+            }),
             _ => None,
         }
     }
@@ -443,3 +695,114 @@ fn encode_hex(bytes: &[u8]) -> String {
     }
     encoded
 }
+
+/// Localized numeric range and precision for a rendered constraint, e.g.
+/// "greater than 0, at most 10, up to 2 decimal places". Each bound is
+/// written with `show`.
+fn describe_bounds(constraint: &Value, show: impl Fn(f64) -> String) -> Option<String> {
+    let bound = |key: &str, exclusive_key: &str, inclusive: &str, exclusive: &str| {
+        let value = constraint.get(key).and_then(Value::as_f64)?;
+        let strict = constraint
+            .get(exclusive_key)
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        Some(tf(
+            if strict { exclusive } else { inclusive },
+            &[("value", show(value))],
+        ))
+    };
+    let parts = [
+        bound(
+            "min",
+            "exclusive_min",
+            "cli.wizard.hint.at_least",
+            "cli.wizard.hint.greater_than",
+        ),
+        bound(
+            "max",
+            "exclusive_max",
+            "cli.wizard.hint.at_most",
+            "cli.wizard.hint.less_than",
+        ),
+        constraint
+            .get("multiple_of")
+            .and_then(Value::as_f64)
+            .map(|step| {
+                tf(
+                    "cli.wizard.hint.multiple_of",
+                    &[("value", step.to_string())],
+                )
+            }),
+        constraint
+            .get("decimal_places")
+            .and_then(Value::as_u64)
+            .map(|places| {
+                tf(
+                    "cli.wizard.hint.decimal_places",
+                    &[("places", places.to_string())],
+                )
+            }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Item count a `list` or `string_list` answer must have, e.g. "1 to 5 items".
+pub(crate) fn describe_item_count(list: &Value) -> Option<String> {
+    let count = |key: &str| {
+        list.get(key)
+            .and_then(Value::as_u64)
+            .map(|count| count as usize)
+    };
+    let (min_items, max_items) = (count("min_items"), count("max_items"));
+    (min_items.is_some() || max_items.is_some()).then(|| describe_list_size(min_items, max_items))
+}
+
+/// Scale of a rendered `rating` question's `range`, e.g. "1-5".
+fn describe_rating_scale(range: &Value) -> Option<String> {
+    let min = range.get("min").and_then(Value::as_i64)?;
+    let max = range.get("max").and_then(Value::as_i64)?;
+    Some(format!("{min}-{max}"))
+}
+
+/// What a rendered question's `catalog` marker holds, e.g. "ISO country code".
+fn describe_catalog(catalog: &Value) -> Option<String> {
+    let key = match catalog.as_str()?.parse::<ChoiceCatalog>().ok()? {
+        ChoiceCatalog::Iso3166Alpha2 => "cli.wizard.hint.catalog.country",
+        ChoiceCatalog::Iso6391 => "cli.wizard.hint.catalog.language",
+        ChoiceCatalog::Timezone => "cli.wizard.hint.catalog.timezone",
+    };
+    Some(t(key))
+}
+
+fn describe_path_requirement(constraint: &Value) -> Option<String> {
+    let must_exist = constraint
+        .get("must_exist")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let key = match (must_exist, constraint.get("kind").and_then(Value::as_str)) {
+        (true, Some("file")) => "cli.wizard.hint.existing_file",
+        (true, Some("dir")) => "cli.wizard.hint.existing_dir",
+        (true, _) => "cli.wizard.hint.must_exist",
+        (false, Some("file")) => "cli.wizard.hint.file",
+        (false, Some("dir")) => "cli.wizard.hint.dir",
+        (false, _) => return None,
+    };
+    Some(t(key))
+}
+
+/// Label for a list sub-field; fields with `visible_if` are marked conditional
+/// since they only apply to some entries.
+pub(crate) fn list_field_label(field: &Value) -> Option<String> {
+    let id = field.get("id").and_then(Value::as_str)?;
+    if field.get("visible_if").is_some_and(|expr| !expr.is_null()) {
+        Some(tf(
+            "cli.wizard.list_field_conditional",
+            &[("field", id.to_string())],
+        ))
+    } else {
+        Some(id.to_string())
+    }
+}
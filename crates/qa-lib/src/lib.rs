@@ -1,6 +1,9 @@
 use std::collections::BTreeMap;
 
-use component_qa::{render_card, render_json_ui, render_text, submit_patch};
+use component_qa::{
+    back, get_session, render_card, render_json_ui, render_markdown, render_summary, render_text,
+    resume_session, submit_patch,
+};
 use qa_spec::AnswerSet;
 use serde_json::{Map, Value, json};
 use tempfile::TempDir;
@@ -13,6 +16,7 @@ pub enum WizardFrontend {
     Text,
     JsonUi,
     Card,
+    Markdown,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -29,6 +33,8 @@ pub struct WizardRunConfig {
     pub frontend: WizardFrontend,
     pub i18n: I18nConfig,
     pub verbose: bool,
+    /// Ask only required questions (`ctx.progress.required_only`).
+    pub required_only: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -147,7 +153,7 @@ impl WizardDriver {
             form_id,
             spec_version,
             config_json: json!({ "qa_form_asset_path": form_asset_path }).to_string(),
-            ctx_json: build_ctx_json(&config.i18n),
+            ctx_json: build_ctx_json(&config.i18n, config.verbose, config.required_only),
             frontend: config.frontend,
             answers,
             complete: false,
@@ -184,15 +190,29 @@ impl WizardDriver {
                 parse_component_result(&card_raw)?;
                 Ok(card_raw)
             }
-            WizardFrontend::Text => {
-                let text = render_text(
-                    &self.form_id,
-                    &self.config_json,
-                    &self.ctx_json,
-                    &answers_json,
-                );
+            WizardFrontend::Text | WizardFrontend::Markdown => {
+                let (key, text) = match self.frontend {
+                    WizardFrontend::Markdown => (
+                        "markdown",
+                        render_markdown(
+                            &self.form_id,
+                            &self.config_json,
+                            &self.ctx_json,
+                            &answers_json,
+                        ),
+                    ),
+                    _ => (
+                        "text",
+                        render_text(
+                            &self.form_id,
+                            &self.config_json,
+                            &self.ctx_json,
+                            &answers_json,
+                        ),
+                    ),
+                };
                 let wrapped = json!({
-                    "text": text,
+                    key: text,
                     "status": ui_value.get("status").cloned().unwrap_or(Value::String("need_input".into())),
                     "next_question_id": ui_value.get("next_question_id").cloned().unwrap_or(Value::Null),
                     "progress": ui_value.get("progress").cloned().unwrap_or_else(|| json!({"answered":0,"total":0}))
@@ -256,6 +276,70 @@ impl WizardDriver {
         })
     }
 
+    /// The `component_qa::back` envelope for stepping back from
+    /// `current_question_id`: the earlier question and its current answer,
+    /// or `at_start`.
+    pub fn back_json(&self, current_question_id: &str) -> Result<String, QaLibError> {
+        let back_raw = back(
+            &self.form_id,
+            &self.config_json,
+            &self.ctx_json,
+            &self.answers.to_string(),
+            current_question_id,
+        );
+        parse_component_result(&back_raw)?;
+        Ok(back_raw)
+    }
+
+    /// `component_qa::render_summary` review of the answers so far, shown
+    /// before the wizard is finished.
+    pub fn summary_json(&self) -> Result<String, QaLibError> {
+        let summary_raw = render_summary(
+            &self.form_id,
+            &self.config_json,
+            &self.ctx_json,
+            &self.answers.to_string(),
+        );
+        parse_component_result(&summary_raw)?;
+        Ok(summary_raw)
+    }
+
+    /// `component_qa::get_session` snapshot of the answers so far, to be
+    /// stored and passed to [`WizardDriver::resume_session_json`] later.
+    pub fn session_json(&self) -> Result<String, QaLibError> {
+        let session_raw = get_session(
+            &self.form_id,
+            &self.config_json,
+            &self.ctx_json,
+            &self.answers.to_string(),
+        );
+        parse_component_result(&session_raw)?;
+        Ok(session_raw)
+    }
+
+    /// Continues from a stored session: takes over its answers and returns
+    /// the `component_qa::resume_session` envelope with the question to ask.
+    /// Fails when the session was saved against another spec version.
+    pub fn resume_session_json(&mut self, session_json: &str) -> Result<String, QaLibError> {
+        let resume_raw = resume_session(
+            &self.form_id,
+            &self.config_json,
+            &self.ctx_json,
+            session_json,
+        );
+        let resumed = parse_component_result(&resume_raw)?;
+        if let Some(answers) = resumed.get("answers") {
+            self.answers = normalize_answers(answers.clone());
+        }
+        self.complete = resumed["status"] == "complete";
+        Ok(resume_raw)
+    }
+
+    /// The runtime ctx the driver passes to every component call.
+    pub fn ctx_json(&self) -> &str {
+        &self.ctx_json
+    }
+
     pub fn is_complete(&self) -> bool {
         self.complete
     }
@@ -287,8 +371,10 @@ impl WizardDriver {
     }
 }
 
-fn build_ctx_json(i18n: &I18nConfig) -> String {
+fn build_ctx_json(i18n: &I18nConfig, verbose: bool, required_only: bool) -> String {
     let mut map = Map::new();
+    // The driver keeps answers from component responses, so it needs secrets unredacted.
+    map.insert("reveal_secrets".into(), Value::Bool(true));
     if let Some(locale) = &i18n.locale {
         map.insert("locale".into(), Value::String(locale.clone()));
     }
@@ -301,12 +387,27 @@ fn build_ctx_json(i18n: &I18nConfig) -> String {
         map.insert("i18n_debug".into(), Value::Bool(true));
         map.insert("debug_i18n".into(), Value::Bool(true));
     }
+    // Verbose runs show why conditions could not be evaluated.
+    if verbose {
+        map.insert("debug".into(), Value::Bool(true));
+    }
+    if required_only {
+        map.insert("progress".into(), json!({ "required_only": true }));
+    }
     Value::Object(map).to_string()
 }
 
 fn parse_component_result(raw: &str) -> Result<Value, QaLibError> {
     let value: Value = serde_json::from_str(raw)?;
-    if let Some(error) = value.get("error").and_then(Value::as_str) {
+    // Plain errors are strings; structured ones carry a code and a message.
+    let error = value.get("error").and_then(|error| {
+        error.as_str().or_else(|| {
+            (value["status"] == "error")
+                .then(|| error.get("message")?.as_str())
+                .flatten()
+        })
+    });
+    if let Some(error) = error {
         Err(QaLibError::Component(error.to_string()))
     } else {
         Ok(value)
@@ -379,13 +480,15 @@ fn collect_question_i18n_defaults(question: &Value, en_map: &mut BTreeMap<String
         en_map.entry(key.to_string()).or_insert(fallback);
     }
 
-    if let Some(fields) = question
-        .get("list")
-        .and_then(|list| list.get("fields"))
-        .and_then(Value::as_array)
-    {
-        for field in fields {
-            collect_question_i18n_defaults(field, en_map);
+    for container in ["list", "object"] {
+        if let Some(fields) = question
+            .get(container)
+            .and_then(|container| container.get("fields"))
+            .and_then(Value::as_array)
+        {
+            for field in fields {
+                collect_question_i18n_defaults(field, en_map);
+            }
         }
     }
 }
@@ -0,0 +1,86 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use serde_json::{Map, Value, json};
+
+use qa_spec::{FormSpec, RenderOptions, build_render_payload_with_options, validate};
+
+const QUESTIONS: usize = 200;
+
+/// Pattern-constrained strings, each followed by a computed copy of it.
+fn spec() -> FormSpec {
+    let mut questions = Vec::new();
+    for index in 0..QUESTIONS / 2 {
+        questions.push(json!({
+            "id": format!("name{index}"),
+            "type": "string",
+            "title": format!("Name {index}"),
+            "required": true,
+            "constraint": { "pattern": format!("^[a-z]+{index}$") }
+        }));
+        questions.push(json!({
+            "id": format!("copy{index}"),
+            "type": "string",
+            "title": format!("Copy {index}"),
+            "required": false,
+            "computed": { "op": "answer", "path": format!("name{index}") }
+        }));
+    }
+    serde_json::from_value(json!({
+        "id": "large",
+        "title": "Large",
+        "version": "1.0.0",
+        "questions": questions,
+    }))
+    .expect("spec")
+}
+
+fn answers() -> Value {
+    let mut answers = Map::new();
+    for index in 0..QUESTIONS / 2 {
+        answers.insert(format!("name{index}"), json!(format!("service{index}")));
+    }
+    Value::Object(answers)
+}
+
+fn time(label: &str, iterations: u32, mut run: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        run();
+    }
+    let per_run = start.elapsed() / iterations;
+    println!("  {label:<36} {:>10.3} ms", per_run.as_secs_f64() * 1_000.0);
+}
+
+/// Compares the `FormSpec` entry points with a `CompiledFormSpec`.
+///
+/// Run with `cargo bench -p qa-spec --bench compiled`.
+fn main() {
+    let spec = spec();
+    let compiled = spec.clone().compile();
+    let ctx = json!({});
+    let answers = answers();
+    let options = RenderOptions::for_form(&spec);
+    println!("{QUESTIONS} questions, half patterned, half computed");
+
+    time("validate: FormSpec", 50, || {
+        black_box(validate(&spec, &answers));
+    });
+    time("validate: CompiledFormSpec", 50, || {
+        black_box(compiled.validate(&answers));
+    });
+    time("render: FormSpec", 50, || {
+        black_box(build_render_payload_with_options(
+            &spec, &ctx, &answers, None, &options,
+        ));
+    });
+    time("render: CompiledFormSpec", 50, || {
+        black_box(compiled.build_render_payload(&ctx, &answers, None, &options));
+    });
+    time("compute_answers", 50, || {
+        black_box(compiled.compute_answers(&answers, &ctx));
+    });
+    time("compile", 50, || {
+        black_box(spec.clone().compile());
+    });
+}
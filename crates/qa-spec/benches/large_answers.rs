@@ -0,0 +1,95 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, ProgressContext, RenderOptions, VisibilityMode, build_render_payload_with_options,
+    next_question, render_json_ui, resolve_visibility, validate,
+};
+
+fn spec() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "inventory",
+        "title": "Inventory",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "owner", "type": "string", "title": "Owner", "required": true },
+            {
+                "id": "hosts",
+                "type": "list",
+                "title": "Hosts",
+                "required": true,
+                "list": {
+                    "fields": [
+                        { "id": "name", "type": "string", "title": "Name", "required": true },
+                        { "id": "tls", "type": "boolean", "title": "TLS", "required": false },
+                        {
+                            "id": "cert",
+                            "type": "string",
+                            "title": "Certificate",
+                            "required": false,
+                            "visible_if": { "op": "var", "path": "item.tls" }
+                        }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn answers(items: usize) -> Value {
+    let hosts = (0..items)
+        .map(|index| json!({ "name": format!("host-{index}"), "tls": index % 2 == 0, "cert": "pem" }))
+        .collect::<Vec<_>>();
+    json!({ "owner": "ops", "hosts": hosts })
+}
+
+fn time(label: &str, iterations: u32, mut run: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        run();
+    }
+    let per_run = start.elapsed() / iterations;
+    println!("  {label:<36} {:>10.3} ms", per_run.as_secs_f64() * 1_000.0);
+}
+
+/// Times the per-call pipeline on a form with one big list answer.
+///
+/// Run with `cargo bench -p qa-spec --bench large_answers`. Rendering with the
+/// default options embeds only the first items of the list, so it stays flat
+/// as the list grows; `full current values` shows the cost of embedding it whole,
+/// and `owned answers` the deep copy a borrowed `ProgressContext` avoids.
+fn main() {
+    let spec = spec();
+    let ctx = json!({});
+    let truncated = RenderOptions::for_form(&spec);
+    let full = truncated.with_max_current_value_items(None);
+    for items in [1_000, 10_000] {
+        let answers = answers(items);
+        println!("{items} list items");
+        time("next_question", 20, || {
+            let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+            let progress = ProgressContext::new(&answers, &ctx);
+            black_box(next_question(&spec, &progress, &visibility));
+        });
+        time("next_question (owned answers)", 20, || {
+            let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+            let progress = ProgressContext::new(answers.clone(), &ctx);
+            black_box(next_question(&spec, &progress, &visibility));
+        });
+        time("render (default options)", 20, || {
+            let payload =
+                build_render_payload_with_options(&spec, &ctx, &answers, None, &truncated);
+            black_box(render_json_ui(&payload));
+        });
+        time("render (full current values)", 20, || {
+            let payload = build_render_payload_with_options(&spec, &ctx, &answers, None, &full);
+            black_box(render_json_ui(&payload));
+        });
+        time("validate", 20, || {
+            black_box(validate(&spec, &answers));
+        });
+    }
+}
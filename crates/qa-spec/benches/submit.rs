@@ -0,0 +1,132 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use serde_json::{Map, Value, json};
+
+use qa_spec::{
+    FormSpec, ProgressContext, RenderOptions, VisibilityMode, build_render_payload_with_options,
+    next_question, resolve_visibility, validate,
+};
+
+const QUESTIONS: usize = 500;
+
+/// Sections of ten questions; each question in a section is shown only while
+/// the section toggle is on and the previous answer stays below a limit.
+fn spec_json() -> String {
+    let questions = (0..QUESTIONS)
+        .map(|index| {
+            if index % 10 == 0 {
+                return json!({
+                    "id": format!("q{index}"),
+                    "type": "boolean",
+                    "title": format!("Section {}", index / 10),
+                });
+            }
+            json!({
+                "id": format!("q{index}"),
+                "type": "integer",
+                "title": format!("Question {index}"),
+                "required": index % 3 == 0,
+                "visible_if": {
+                    "op": "and",
+                    "expressions": [
+                        { "op": "answer", "path": format!("q{}", index - index % 10) },
+                        {
+                            "op": "lt",
+                            "left": {
+                                "op": "coalesce",
+                                "expressions": [
+                                    { "op": "answer", "path": format!("q{}", index - 1) },
+                                    { "op": "literal", "value": 0 }
+                                ]
+                            },
+                            "right": { "op": "literal", "value": 1_000 }
+                        }
+                    ]
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "id": "large",
+        "title": "Large",
+        "version": "1.0.0",
+        "questions": questions,
+    })
+    .to_string()
+}
+
+fn answers() -> Value {
+    let mut answers = Map::new();
+    for index in 0..QUESTIONS / 2 {
+        let value = if index % 10 == 0 {
+            json!(true)
+        } else {
+            json!(index)
+        };
+        answers.insert(format!("q{index}"), value);
+    }
+    Value::Object(answers)
+}
+
+fn time(label: &str, iterations: u32, mut run: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        run();
+    }
+    let per_run = start.elapsed() / iterations;
+    println!("  {label:<36} {:>10.3} ms", per_run.as_secs_f64() * 1_000.0);
+}
+
+/// The work behind one component submit on a large form: validation, the
+/// render payload, and the next question.
+///
+/// The first path parses the spec and resolves visibility in every step, as
+/// the component did before caching compiled forms; the second reuses a
+/// compiled spec and one visibility pass.
+///
+/// Run with `cargo bench -p qa-spec --bench submit`.
+fn main() {
+    let raw = spec_json();
+    let ctx = json!({});
+    let answers = answers();
+    println!("{QUESTIONS} questions in sections of ten, half answered");
+
+    time("submit: parse + three visibility passes", 50, || {
+        let spec = FormSpec::from_json_str(&raw).expect("spec");
+        let options = RenderOptions::for_form(&spec);
+        black_box(validate(&spec, &answers));
+        black_box(build_render_payload_with_options(
+            &spec, &ctx, &answers, None, &options,
+        ));
+        let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+        let progress = ProgressContext::new(&answers, &ctx);
+        black_box(next_question(&spec, &progress, &visibility));
+    });
+
+    let compiled = FormSpec::from_json_str(&raw).expect("spec").compile();
+    let options = RenderOptions::for_form(&compiled);
+    time("submit: cached spec + one pass", 50, || {
+        let visibility = resolve_visibility(&compiled, &answers, VisibilityMode::Visible);
+        black_box(compiled.validate_with_visibility(&answers, &visibility));
+        let payload = compiled.build_render_payload_with_visibility(
+            &ctx,
+            &answers,
+            None,
+            &options,
+            &visibility,
+        );
+        black_box(payload.next_question_id);
+    });
+
+    time("parse spec", 50, || {
+        black_box(FormSpec::from_json_str(&raw).expect("spec"));
+    });
+    time("resolve visibility", 50, || {
+        black_box(resolve_visibility(
+            &compiled,
+            &answers,
+            VisibilityMode::Visible,
+        ));
+    });
+}
@@ -0,0 +1,111 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use serde_json::{Map, Value, json};
+
+use qa_spec::{
+    FormSpec, RenderOptions, VisibilityCache, VisibilityMode, build_render_payload,
+    build_render_payload_with_cache, build_render_payload_with_options,
+    build_render_payload_with_visibility, resolve_visibility, validate, validate_with_visibility,
+};
+
+const QUESTIONS: usize = 200;
+
+/// Every question after the first is shown only when the previous answer is
+/// set and below a threshold, so each render evaluates 199 conditions.
+fn spec() -> FormSpec {
+    let questions = (0..QUESTIONS)
+        .map(|index| {
+            let mut question = json!({
+                "id": format!("q{index}"),
+                "type": "integer",
+                "title": format!("Question {index}"),
+                "required": index % 3 == 0,
+            });
+            if index > 0 {
+                question["visible_if"] = json!({
+                    "op": "and",
+                    "expressions": [
+                        { "op": "is_set", "path": format!("q{}", index - 1) },
+                        {
+                            "op": "lt",
+                            "left": { "op": "answer", "path": format!("q{}", index - 1) },
+                            "right": { "op": "literal", "value": 1_000 }
+                        }
+                    ]
+                });
+            }
+            question
+        })
+        .collect::<Vec<_>>();
+    serde_json::from_value(json!({
+        "id": "heavy",
+        "title": "Heavy",
+        "version": "1.0.0",
+        "questions": questions,
+    }))
+    .expect("spec")
+}
+
+fn answers() -> Value {
+    let mut answers = Map::new();
+    for index in 0..QUESTIONS / 2 {
+        answers.insert(format!("q{index}"), json!(index));
+    }
+    Value::Object(answers)
+}
+
+fn time(label: &str, iterations: u32, mut run: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        run();
+    }
+    let per_run = start.elapsed() / iterations;
+    println!("  {label:<36} {:>10.3} ms", per_run.as_secs_f64() * 1_000.0);
+}
+
+/// Times a submit (validate + render) and repeated renders of one state.
+///
+/// Run with `cargo bench -p qa-spec --bench visibility`.
+fn main() {
+    let spec = spec();
+    let ctx = json!({});
+    let answers = answers();
+    let options = RenderOptions::for_form(&spec);
+    println!("{QUESTIONS} questions with visible_if");
+
+    time("submit: separate visibility passes", 200, || {
+        black_box(validate(&spec, &answers));
+        black_box(build_render_payload(&spec, &ctx, &answers));
+    });
+    time("submit: shared visibility", 200, || {
+        let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+        black_box(validate_with_visibility(&spec, &answers, &visibility));
+        black_box(build_render_payload_with_visibility(
+            &spec,
+            &ctx,
+            &answers,
+            None,
+            &options,
+            &visibility,
+        ));
+    });
+
+    time("render: uncached", 200, || {
+        black_box(build_render_payload_with_options(
+            &spec, &ctx, &answers, None, &options,
+        ));
+    });
+    let mut cache = VisibilityCache::default();
+    time("render: cached (same state)", 200, || {
+        black_box(build_render_payload_with_cache(
+            &spec, &ctx, &answers, None, &options, &mut cache,
+        ));
+    });
+    time("visibility: uncached", 200, || {
+        black_box(resolve_visibility(&spec, &answers, VisibilityMode::Visible));
+    });
+    time("visibility: cached (same state)", 200, || {
+        black_box(cache.resolve(&spec, &answers, VisibilityMode::Visible));
+    });
+}
@@ -1,16 +1,69 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_cbor::{to_vec, value::to_value};
-use serde_json::Value;
+use serde_cbor::{from_slice, to_vec, value::to_value};
+use serde_json::{Number, Value};
+use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::clock::{Clock, SystemClock};
+use crate::merge::MergeProvenance;
+use crate::signature::AnswerSignature;
 
 /// Optional metadata paired with an `AnswerSet`.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Meta {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<String>,
+    /// Who produced the answers (user name, service id, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Channel the answers arrived through (e.g. `cli`, `teams`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Number of submissions recorded through [`Meta::touch`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u32>,
+    /// Set when the answers were produced by [`AnswerSet::merge`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge: Option<MergeProvenance>,
+    /// Integrity signature over the answers; see [`crate::signature`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<AnswerSignature>,
+}
+
+impl Meta {
+    /// Fresh metadata stamped with the system clock.
+    pub fn now(actor: impl Into<String>, channel: impl Into<String>) -> Self {
+        Self::now_with(&SystemClock, actor, channel)
+    }
+
+    /// Fresh metadata stamped with `clock`; created and updated times match.
+    pub fn now_with(
+        clock: &dyn Clock,
+        actor: impl Into<String>,
+        channel: impl Into<String>,
+    ) -> Self {
+        let now = clock.now_rfc3339();
+        Self {
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            actor: Some(actor.into()),
+            channel: Some(channel.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Record another submission: bumps `updated_at` and the attempt counter,
+    /// filling `created_at` if it was never set.
+    pub fn touch(&mut self, clock: &dyn Clock) {
+        let now = clock.now_rfc3339();
+        self.created_at.get_or_insert_with(|| now.clone());
+        self.updated_at = Some(now);
+        self.attempts = Some(self.attempts.unwrap_or(0) + 1);
+    }
 }
 
 /// Represents in-progress answers for a given form spec version.
@@ -35,25 +88,140 @@ impl AnswerSet {
     }
 
     /// Serializes the answers set as canonical CBOR bytes.
+    ///
+    /// Encoding is deterministic: map keys are ordered shortest first, then
+    /// bytewise (RFC 7049 canonical CBOR), integers and lengths use their
+    /// shortest form, and floats use the narrowest width that keeps their value.
+    /// Bytes produced here decode with [`AnswerSet::from_cbor`] and re-encode to
+    /// the same bytes.
     pub fn to_cbor(&self) -> Result<Vec<u8>, serde_cbor::Error> {
         let canonical = to_value(self)?;
         to_vec(&canonical)
     }
 
+    /// Decodes an answer set produced by [`AnswerSet::to_cbor`].
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, serde_cbor::Error> {
+        from_slice(bytes)
+    }
+
     /// Serializes the answers set as indented JSON for debugging.
     pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
 }
 
-/// Progress tracking state for flows.
+/// Current [`ProgressState::version`]. Records without a version are version 1,
+/// which predates per-question tracking.
+pub const PROGRESS_STATE_VERSION: u32 = 2;
+
+fn legacy_progress_version() -> u32 {
+    1
+}
+
+/// Progress tracking state for flows and forms.
+///
+/// Besides the per-question statuses kept by `track_progress`, a record built
+/// with [`ProgressState::from_payload`] is a session snapshot: the form and
+/// spec version it belongs to, the answers so far and the question to ask
+/// next, enough for a host to resume an interrupted wizard.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ProgressState {
+    /// Layout version of this record; [`ProgressState::refresh`] upgrades older ones.
+    #[serde(default = "legacy_progress_version")]
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_id: Option<String>,
+    /// Spec version the answers were given against; resuming under another
+    /// version fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answers: Option<Value>,
+    /// Question the session was waiting on, `None` once complete.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_question_id: Option<String>,
+    /// When the snapshot was taken.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub current_step: Option<String>,
+    #[serde(default)]
     pub completed: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub history: Vec<String>,
+    /// Per-question status keyed by question id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub questions: BTreeMap<String, QuestionProgress>,
+}
+
+impl Default for ProgressState {
+    fn default() -> Self {
+        Self {
+            version: PROGRESS_STATE_VERSION,
+            form_id: None,
+            spec_version: None,
+            answers: None,
+            current_question_id: None,
+            updated_at: None,
+            current_step: None,
+            completed: false,
+            history: Vec::new(),
+            questions: BTreeMap::new(),
+        }
+    }
+}
+
+impl ProgressState {
+    /// Serializes the record as canonical CBOR, like [`AnswerSet::to_cbor`].
+    pub fn to_cbor(&self) -> Result<Vec<u8>, serde_cbor::Error> {
+        let canonical = to_value(self)?;
+        to_vec(&canonical)
+    }
+
+    /// Decodes a record produced by [`ProgressState::to_cbor`].
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, serde_cbor::Error> {
+        from_slice(bytes)
+    }
+}
+
+/// Where a question stands in a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionStatus {
+    /// Visible and still waiting for an answer.
+    #[default]
+    Pending,
+    /// The caller provided the answer.
+    Answered,
+    /// Explicitly skipped without an answer.
+    Skipped,
+    /// Satisfied without asking: an answered-by-default value or a value
+    /// already present in a `skip_if_present_in` target.
+    AutoFilled,
+    /// Filled in by the question's `computed` expression.
+    Computed,
+    /// Not visible with the current answers.
+    Hidden,
+}
+
+/// Tracking record for one question.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct QuestionProgress {
+    pub status: QuestionStatus,
+    /// Submissions that included this question, valid or not.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub attempts: u32,
+    /// Answer held when the question was last hidden, kept so it can be
+    /// offered again if the question becomes visible.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_value: Option<Value>,
+    /// When the current answer was first seen by a session snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_at: Option<String>,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
 }
 
 /// Validation error metadata reported by the engine.
@@ -61,8 +229,13 @@ pub struct ProgressState {
 pub struct ValidationError {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub question_id: Option<String>,
+    /// JSON pointer into the answers (`/servers/0/port`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    /// Pointers of every answer involved when an error concerns several
+    /// fields, such as a cross-field validation. `path` is the first of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
     pub message: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
@@ -70,6 +243,20 @@ pub struct ValidationError {
     pub params: BTreeMap<String, String>,
 }
 
+/// JSON pointer (RFC 6901) made of `segments`, escaping `~` as `~0` and `/`
+/// as `~1`, so the question `a/b` is addressed as `/a~1b`.
+pub fn json_pointer<S: AsRef<str>>(segments: impl IntoIterator<Item = S>) -> String {
+    segments
+        .into_iter()
+        .map(|segment| {
+            format!(
+                "/{}",
+                segment.as_ref().replace('~', "~0").replace('/', "~1")
+            )
+        })
+        .collect()
+}
+
 /// Result returned from `validate_answers`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationResult {
@@ -80,4 +267,178 @@ pub struct ValidationResult {
     pub missing_required: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub unknown_fields: Vec<String>,
+    /// Failed checks with warning severity. They do not affect `valid`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ValidationError>,
+}
+
+/// Largest integer magnitude every JSON implementation represents exactly (2^53).
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// Options for [`canonicalize`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CanonicalOptions {
+    /// Apply Unicode NFC normalization to strings and object keys.
+    pub nfc: bool,
+}
+
+/// Rewrites `value` so that semantically equal answers compare equal.
+///
+/// Integer-valued numbers with a magnitude up to 2^53 become integers (`1.0`
+/// becomes `1`, `-0.0` becomes `0`); other numbers are kept as-is. With
+/// [`CanonicalOptions::nfc`], strings and object keys are NFC normalized; keys
+/// that collide after normalization keep the value of the last one in key order.
+/// Object key order and whitespace are handled by [`canonical_json`].
+pub fn canonicalize(value: &Value, options: &CanonicalOptions) -> Value {
+    match value {
+        Value::Number(number) => Value::Number(canonical_number_value(number)),
+        Value::String(text) if options.nfc => Value::String(text.nfc().collect()),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| canonicalize(item, options))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, item)| {
+                    let key = if options.nfc {
+                        key.nfc().collect()
+                    } else {
+                        key.clone()
+                    };
+                    (key, canonicalize(item, options))
+                })
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+pub(crate) fn canonical_number_value(number: &Number) -> Number {
+    match number.as_f64() {
+        Some(float)
+            if !number.is_i64()
+                && !number.is_u64()
+                && float.fract() == 0.0
+                && float.abs() <= MAX_SAFE_INTEGER =>
+        {
+            Number::from(float as i64)
+        }
+        _ => number.clone(),
+    }
+}
+
+/// SHA-256 of the [`canonical_json`] encoding of `value` after [`canonicalize`]
+/// with default options (no NFC normalization).
+pub fn canonical_hash(value: &Value) -> [u8; 32] {
+    let canonical = canonicalize(value, &CanonicalOptions::default());
+    Sha256::digest(canonical_json(&canonical).as_bytes()).into()
+}
+
+/// Whether `left` and `right` are equal once canonicalized (so `1` equals `1.0`).
+pub(crate) fn canonical_eq(left: &Value, right: &Value) -> bool {
+    left == right
+        || canonicalize(left, &CanonicalOptions::default())
+            == canonicalize(right, &CanonicalOptions::default())
+}
+
+/// Writes `value` as canonical JSON text.
+///
+/// The output has no whitespace and follows these rules:
+///
+/// - object members are sorted by key, comparing the keys' UTF-8 bytes;
+/// - strings escape `"` as `\"`, `\` as `\\` and U+0000..U+001F as `\u00xx`
+///   (lowercase hex); every other character is written as raw UTF-8;
+/// - integers that fit `i64`/`u64` are written in plain decimal;
+/// - other numbers are written like ECMAScript's `Number.prototype.toString`
+///   (shortest round-trip digits, exponent form such as `1e+21` or `1.5e-7`
+///   only below `1e-6` or from `1e21` upwards), with `-0` written as `0`;
+/// - `true`, `false` and `null` are written as-is.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(flag) => out.push_str(if *flag { "true" } else { "false" }),
+        Value::Number(number) => out.push_str(&canonical_number(number)),
+        Value::String(text) => write_string(text, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by(|(left, _), (right, _)| left.as_bytes().cmp(right.as_bytes()));
+            out.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(key, out);
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(text: &str, out: &mut String) {
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{0}'..='\u{1f}' => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            _ => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+fn canonical_number(number: &Number) -> String {
+    if let Some(int) = number.as_i64() {
+        return int.to_string();
+    }
+    if let Some(int) = number.as_u64() {
+        return int.to_string();
+    }
+    let float = number.as_f64().unwrap_or_default();
+    if float == 0.0 {
+        return "0".to_string();
+    }
+    let sign = if float < 0.0 { "-" } else { "" };
+    // `{:e}` yields the shortest round-trip digits, e.g. `1.2345e2`.
+    let scientific = format!("{:e}", float.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap_or_default() + 1;
+    let body = if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        let exp_sign = if n - 1 < 0 { '-' } else { '+' };
+        let fraction = if k > 1 {
+            format!(".{}", &digits[1..])
+        } else {
+            String::new()
+        };
+        format!("{}{}e{}{}", &digits[..1], fraction, exp_sign, (n - 1).abs())
+    };
+    format!("{}{}", sign, body)
 }
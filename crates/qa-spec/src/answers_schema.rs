@@ -1,22 +1,49 @@
-use serde_json::{Map, Value};
+use serde_json::{Map, Value, json};
 
+use crate::color::HEX_COLOR_PATTERN;
+use crate::duration::parse_duration;
+use crate::expr::Expr;
 use crate::spec::form::FormSpec;
-use crate::spec::question::{Constraint, QuestionSpec, QuestionType};
+use crate::spec::question::{Constraint, QuestionSpec, QuestionType, rating_range, split_choices};
+use crate::time_of_day::{format_time_of_day, seconds_of_day};
 use crate::visibility::VisibilityMap;
 
 /// Generates an answer JSON schema restricted to the visible questions.
 pub fn generate(spec: &FormSpec, visibility: &VisibilityMap) -> Value {
+    generate_with(spec, None, visibility)
+}
+
+/// Like [`generate`], cloning per-question schemas from `precomputed` (one
+/// per question, see [`question_schemas`]) instead of building them.
+pub(crate) fn generate_with(
+    spec: &FormSpec,
+    precomputed: Option<&[Value]>,
+    visibility: &VisibilityMap,
+) -> Value {
     let mut properties = Map::new();
     let mut required = Vec::new();
+    let mut conditionals = Vec::new();
 
-    for question in &spec.questions {
+    for (index, question) in spec.questions.iter().enumerate() {
         if !visibility.get(&question.id).copied().unwrap_or(true) {
             continue;
         }
-        let schema = question_schema(question);
+        let schema = match precomputed {
+            Some(schemas) => schemas[index].clone(),
+            None => question_schema(question),
+        };
         properties.insert(question.id.clone(), schema);
-        if question.required {
-            required.push(Value::String(question.id.clone()));
+        match &question.required_if {
+            Some(expr) => {
+                if let Some(condition) = condition_schema(expr, answer_field) {
+                    conditionals.push(serde_json::json!({
+                        "if": condition,
+                        "then": { "required": [question.id] },
+                    }));
+                }
+            }
+            None if question.required => required.push(Value::String(question.id.clone())),
+            None => {}
         }
     }
 
@@ -26,10 +53,60 @@ pub fn generate(spec: &FormSpec, visibility: &VisibilityMap) -> Value {
     if !required.is_empty() {
         root.insert("required".into(), Value::Array(required));
     }
+    if !conditionals.is_empty() {
+        root.insert("allOf".into(), Value::Array(conditionals));
+    }
 
     Value::Object(root)
 }
 
+/// Object schema for list entries and `object` answers: one property per
+/// field, with unconditionally required fields listed in `required` and
+/// conditionally required ones expressed as `if`/`then` clauses.
+fn fields_schema(fields: &[QuestionSpec]) -> Map<String, Value> {
+    let mut props = Map::new();
+    let mut required_fields = Vec::new();
+    let mut conditionals = Vec::new();
+    for field in fields {
+        props.insert(field.id.clone(), question_schema(field));
+        let required_when = match (&field.visible_if, &field.required_if) {
+            (Some(visible), Some(required)) => Some(Some(Expr::And {
+                expressions: vec![visible.clone(), required.clone()],
+            })),
+            (None, Some(required)) => Some(Some(required.clone())),
+            (visible, None) if field.required => Some(visible.clone()),
+            _ => None,
+        };
+        match required_when {
+            Some(None) => required_fields.push(Value::String(field.id.clone())),
+            Some(Some(expr)) => {
+                if let Some(condition) = condition_schema(&expr, item_field) {
+                    conditionals.push(serde_json::json!({
+                        "if": condition,
+                        "then": { "required": [field.id] },
+                    }));
+                }
+            }
+            None => {}
+        }
+    }
+    let mut schema = Map::new();
+    schema.insert("type".into(), Value::String("object".into()));
+    schema.insert("properties".into(), Value::Object(props));
+    if !required_fields.is_empty() {
+        schema.insert("required".into(), Value::Array(required_fields));
+    }
+    if !conditionals.is_empty() {
+        schema.insert("allOf".into(), Value::Array(conditionals));
+    }
+    schema
+}
+
+/// Schema of every question in spec order; visibility does not affect them.
+pub(crate) fn question_schemas(spec: &FormSpec) -> Vec<Value> {
+    spec.questions.iter().map(question_schema).collect()
+}
+
 fn question_schema(question: &QuestionSpec) -> Value {
     let mut schema = Map::new();
     match question.kind {
@@ -45,6 +122,50 @@ fn question_schema(question: &QuestionSpec) -> Value {
         QuestionType::Number => {
             schema.insert("type".into(), Value::String("number".into()));
         }
+        QuestionType::Rating => {
+            let range = rating_range(question.constraint.as_ref());
+            schema.insert("type".into(), Value::String("integer".into()));
+            schema.insert("minimum".into(), Value::Number((*range.start()).into()));
+            schema.insert("maximum".into(), Value::Number((*range.end()).into()));
+        }
+        QuestionType::Duration => {
+            schema.insert("type".into(), Value::String("integer".into()));
+            schema.insert("minimum".into(), Value::Number(0.into()));
+            schema.insert("x-unit".into(), Value::String("seconds".into()));
+        }
+        QuestionType::Path => {
+            schema.insert("type".into(), Value::String("string".into()));
+            schema.insert("minLength".into(), Value::Number(1.into()));
+            if let Some(path_kind) = question
+                .constraint
+                .as_ref()
+                .and_then(|constraint| constraint.path_kind)
+            {
+                schema.insert(
+                    "x-path-kind".into(),
+                    Value::String(path_kind.as_str().into()),
+                );
+            }
+        }
+        QuestionType::Date => {
+            schema.insert("type".into(), Value::String("string".into()));
+            schema.insert("format".into(), Value::String("date".into()));
+        }
+        QuestionType::Color => {
+            schema.insert("type".into(), Value::String("string".into()));
+            schema.insert("pattern".into(), Value::String(HEX_COLOR_PATTERN.into()));
+        }
+        QuestionType::Time => {
+            schema.insert("type".into(), Value::String("string".into()));
+            schema.insert("format".into(), Value::String("time".into()));
+        }
+        QuestionType::Markdown => {
+            schema.insert("type".into(), Value::String("string".into()));
+            schema.insert(
+                "contentMediaType".into(),
+                Value::String("text/markdown".into()),
+            );
+        }
         QuestionType::Enum => {
             schema.insert("type".into(), Value::String("string".into()));
             if let Some(choices) = &question.choices {
@@ -59,6 +180,24 @@ fn question_schema(question: &QuestionSpec) -> Value {
                 );
             }
         }
+        QuestionType::MultiEnum => {
+            schema.insert("type".into(), Value::String("array".into()));
+            let mut items = Map::new();
+            items.insert("type".into(), Value::String("string".into()));
+            if let Some(choices) = &question.choices {
+                items.insert(
+                    "enum".into(),
+                    Value::Array(
+                        choices
+                            .iter()
+                            .map(|value| Value::String(value.clone()))
+                            .collect(),
+                    ),
+                );
+            }
+            schema.insert("items".into(), Value::Object(items));
+            schema.insert("uniqueItems".into(), Value::Bool(true));
+        }
         QuestionType::List => {
             schema.insert("type".into(), Value::String("array".into()));
             if let Some(list) = &question.list {
@@ -68,47 +207,125 @@ fn question_schema(question: &QuestionSpec) -> Value {
                 if let Some(max_items) = list.max_items {
                     schema.insert("maxItems".into(), Value::Number(max_items.into()));
                 }
-                let mut item_props = Map::new();
-                let mut required_fields = Vec::new();
-                for field in &list.fields {
-                    item_props.insert(field.id.clone(), question_schema(field));
-                    if field.required {
-                        required_fields.push(Value::String(field.id.clone()));
-                    }
+                if list.unique {
+                    schema.insert("uniqueItems".into(), Value::Bool(true));
                 }
-                let mut item_schema = Map::new();
-                item_schema.insert("type".into(), Value::String("object".into()));
-                item_schema.insert("properties".into(), Value::Object(item_props));
-                if !required_fields.is_empty() {
-                    item_schema.insert("required".into(), Value::Array(required_fields));
+                if let Some(fields) = &list.unique_by {
+                    schema.insert(
+                        "x-unique-by".into(),
+                        Value::Array(fields.iter().cloned().map(Value::String).collect()),
+                    );
                 }
-                schema.insert("items".into(), Value::Object(item_schema));
+                schema.insert("items".into(), Value::Object(fields_schema(&list.fields)));
             } else {
                 schema.insert("items".into(), Value::Object(Map::new()));
             }
         }
+        QuestionType::StringList => {
+            schema.insert("type".into(), Value::String("array".into()));
+            let item = QuestionSpec {
+                kind: QuestionType::String,
+                default_value: None,
+                secret: false,
+                ..question.clone()
+            };
+            schema.insert("items".into(), question_schema(&item));
+            if let Some(list) = &question.list {
+                if let Some(min_items) = list.min_items {
+                    schema.insert("minItems".into(), Value::Number(min_items.into()));
+                }
+                if let Some(max_items) = list.max_items {
+                    schema.insert("maxItems".into(), Value::Number(max_items.into()));
+                }
+                if list.unique {
+                    schema.insert("uniqueItems".into(), Value::Bool(true));
+                }
+            }
+        }
+        QuestionType::Object => {
+            let fields = question
+                .object
+                .as_ref()
+                .map(|object| object.fields.as_slice())
+                .unwrap_or_default();
+            schema.extend(fields_schema(fields));
+        }
     }
 
     if let Some(Constraint {
         pattern,
         min,
         max,
+        exclusive_min,
+        exclusive_max,
         min_len,
         max_len,
+        decimal_places,
+        multiple_of,
+        format,
+        min_selected,
+        max_selected,
+        must_exist: _,
+        path_kind: _,
+        messages,
+        warnings: _,
     }) = &question.constraint
+        && !matches!(question.kind, QuestionType::StringList)
     {
         if let Some(pattern) = pattern {
             schema.insert("pattern".into(), Value::String(pattern.clone()));
         }
-        if let Some(min) = min
+        if matches!(question.kind, QuestionType::Time) {
+            // Times are strings, so bounds use the `format*` keywords of
+            // ajv-formats rather than `minimum`/`maximum`.
+            if let Some(min) = min {
+                let key = if *exclusive_min {
+                    "formatExclusiveMinimum"
+                } else {
+                    "formatMinimum"
+                };
+                schema.insert(key.into(), Value::String(format_time_of_day(*min as u32)));
+            }
+            if let Some(max) = max {
+                let key = if *exclusive_max {
+                    "formatExclusiveMaximum"
+                } else {
+                    "formatMaximum"
+                };
+                schema.insert(key.into(), Value::String(format_time_of_day(*max as u32)));
+            }
+        } else if let Some(min) = min
             && let Some(num) = number_from_f64(*min)
         {
-            schema.insert("minimum".into(), num);
+            let key = if *exclusive_min {
+                "exclusiveMinimum"
+            } else {
+                "minimum"
+            };
+            schema.insert(key.into(), num);
         }
-        if let Some(max) = max
+        if !matches!(question.kind, QuestionType::Time)
+            && let Some(max) = max
             && let Some(num) = number_from_f64(*max)
         {
-            schema.insert("maximum".into(), num);
+            let key = if *exclusive_max {
+                "exclusiveMaximum"
+            } else {
+                "maximum"
+            };
+            schema.insert(key.into(), num);
+        }
+        if let Some(places) = decimal_places
+            && let Ok(step) = format!("1e-{places}").parse::<f64>()
+            && let Some(step) = number_from_f64(step)
+        {
+            schema.insert("multipleOf".into(), step);
+        }
+        if let Some(step) = multiple_of.and_then(number_from_f64) {
+            // A decimal precision step already set is kept as a second rule.
+            if let Some(precision) = schema.insert("multipleOf".into(), step) {
+                schema.insert("allOf".into(), json!([{ "multipleOf": precision }]));
+            }
         }
         if let Some(min_len) = min_len {
             schema.insert("minLength".into(), Value::Number((*min_len).into()));
@@ -116,10 +333,54 @@ fn question_schema(question: &QuestionSpec) -> Value {
         if let Some(max_len) = max_len {
             schema.insert("maxLength".into(), Value::Number((*max_len).into()));
         }
+        if let Some(min_selected) = min_selected {
+            schema.insert("minItems".into(), Value::Number((*min_selected).into()));
+        }
+        if let Some(max_selected) = max_selected {
+            schema.insert("maxItems".into(), Value::Number((*max_selected).into()));
+        }
+        if let Some(format) = format {
+            match format.schema_format() {
+                Some(keyword) => schema.insert("format".into(), Value::String(keyword.into())),
+                None => schema.insert("x-format".into(), Value::String(format.as_str().into())),
+            };
+        }
+        // `errorMessage` as understood by ajv-errors: keyword -> message.
+        let error_messages = messages
+            .iter()
+            .flatten()
+            .filter_map(|(code, message)| {
+                let keyword = schema_keyword(code, question.kind)?;
+                Some((keyword.to_string(), Value::String(message.clone())))
+            })
+            .collect::<Map<_, _>>();
+        if !error_messages.is_empty() {
+            schema.insert("errorMessage".into(), Value::Object(error_messages));
+        }
     }
 
     if let Some(default_value) = &question.default_value {
-        schema.insert("default".into(), Value::String(default_value.clone()));
+        let default = if matches!(
+            question.kind,
+            QuestionType::MultiEnum | QuestionType::StringList
+        ) {
+            Value::Array(
+                split_choices(default_value)
+                    .map(|choice| Value::String(choice.to_string()))
+                    .collect(),
+            )
+        } else if matches!(question.kind, QuestionType::Duration)
+            && let Some(seconds) = parse_duration(default_value)
+        {
+            Value::from(seconds)
+        } else if matches!(question.kind, QuestionType::Time)
+            && let Some(seconds) = seconds_of_day(default_value)
+        {
+            Value::String(format_time_of_day(seconds))
+        } else {
+            Value::String(default_value.clone())
+        };
+        schema.insert("default".into(), default);
     }
 
     if question.secret {
@@ -129,6 +390,94 @@ fn question_schema(question: &QuestionSpec) -> Value {
     Value::Object(schema)
 }
 
+/// Translates a condition into an `if` schema over the object `field` maps
+/// paths into: [`item_field`] for list and object sub-fields, [`answer_field`]
+/// for top-level questions.
+/// Only conditions that reference that object alone are expressible;
+/// anything else is left to runtime validation.
+fn condition_schema(expr: &Expr, field: fn(&str) -> Option<&str>) -> Option<Value> {
+    match expr {
+        Expr::Var { path } | Expr::Answer { path } => {
+            item_field_schema(field(path)?, Value::Bool(true))
+        }
+        Expr::IsSet { path } => Some(serde_json::json!({ "required": [field(path)?] })),
+        Expr::Eq { left, right } => match (left.as_ref(), right.as_ref()) {
+            (Expr::Var { path } | Expr::Answer { path }, Expr::Literal { value })
+            | (Expr::Literal { value }, Expr::Var { path } | Expr::Answer { path }) => {
+                item_field_schema(field(path)?, value.clone())
+            }
+            _ => None,
+        },
+        Expr::Not { expression } => Some(serde_json::json!({
+            "not": condition_schema(expression, field)?,
+        })),
+        Expr::And { expressions } => Some(serde_json::json!({
+            "allOf": expressions
+                .iter()
+                .map(|expression| condition_schema(expression, field))
+                .collect::<Option<Vec<_>>>()?,
+        })),
+        Expr::Or { expressions } => Some(serde_json::json!({
+            "anyOf": expressions
+                .iter()
+                .map(|expression| condition_schema(expression, field))
+                .collect::<Option<Vec<_>>>()?,
+        })),
+        _ => None,
+    }
+}
+
+fn item_field(path: &str) -> Option<&str> {
+    let field = answer_path(path)
+        .strip_prefix("item.")
+        .or_else(|| answer_path(path).strip_prefix("item/"))?;
+    (!field.is_empty() && !field.contains(['.', '/'])).then_some(field)
+}
+
+fn answer_field(path: &str) -> Option<&str> {
+    let field = answer_path(path);
+    (!field.is_empty() && !field.contains(['.', '/'])).then_some(field)
+}
+
+fn answer_path(path: &str) -> &str {
+    let path = path.trim_start_matches('/');
+    path.strip_prefix("answers.")
+        .or_else(|| path.strip_prefix("answers/"))
+        .unwrap_or(path)
+}
+
+fn item_field_schema(field: &str, value: Value) -> Option<Value> {
+    Some(serde_json::json!({
+        "properties": { field: { "const": value } },
+        "required": [field],
+    }))
+}
+
+/// Schema keyword that checks what validation reports as `code`.
+fn schema_keyword(code: &str, kind: QuestionType) -> Option<&'static str> {
+    let time = matches!(kind, QuestionType::Time);
+    let keyword = match code {
+        "type_mismatch" => "type",
+        "pattern_mismatch" => "pattern",
+        "min_length" => "minLength",
+        "max_length" => "maxLength",
+        "min" if time => "formatMinimum",
+        "max" if time => "formatMaximum",
+        "exclusive_min" if time => "formatExclusiveMinimum",
+        "exclusive_max" if time => "formatExclusiveMaximum",
+        "min" => "minimum",
+        "max" => "maximum",
+        "exclusive_min" => "exclusiveMinimum",
+        "exclusive_max" => "exclusiveMaximum",
+        "decimal_precision" | "multiple_of" => "multipleOf",
+        "min_selected" => "minItems",
+        "max_selected" => "maxItems",
+        code if code.starts_with("format_") => "format",
+        _ => return None,
+    };
+    Some(keyword)
+}
+
 fn number_from_f64(value: f64) -> Option<Value> {
     serde_json::Number::from_f64(value).map(Value::Number)
 }
@@ -0,0 +1,104 @@
+//! Lenient conversion of string answers into the types questions expect.
+//!
+//! HTML forms and adaptive cards submit every input as a string. Coercion
+//! turns the unambiguous ones into typed values before validation; anything
+//! else is left untouched and fails validation as before.
+
+use serde_json::{Map, Number, Value};
+
+use crate::duration::parse_duration;
+use crate::spec::form::FormSpec;
+use crate::spec::question::{QuestionSpec, QuestionType};
+
+/// Returns `answers` with string answers converted where the question type
+/// calls for it, including list entry and object fields:
+///
+/// - `boolean`: `true` or `false`, in any case. `1`, `yes` and `on` are
+///   ambiguous and stay strings.
+/// - `integer` and `rating`: whole numbers such as `42` or `-3`.
+/// - `number`: any finite number, kept as an integer when it is written as one.
+/// - `duration`: anything [`parse_duration`] accepts.
+/// - `enum`: the choice matching the text exactly, after trimming, or
+///   ignoring case when only one choice matches.
+/// - `multi_enum`: comma-separated choices, each matched like an `enum`.
+/// - `list`: a JSON array of entries, as the card's list input submits it;
+///   the entries' fields are then coerced in turn.
+pub fn coerce_answers(spec: &FormSpec, answers: &Value) -> Value {
+    let mut map = answers.as_object().cloned().unwrap_or_default();
+    coerce_fields(&spec.questions, &mut map);
+    Value::Object(map)
+}
+
+fn coerce_fields(questions: &[QuestionSpec], map: &mut Map<String, Value>) {
+    for question in questions {
+        let Some(value) = map.get_mut(&question.id) else {
+            continue;
+        };
+        if let Some(text) = value.as_str()
+            && let Some(coerced) = coerce_text(question, text)
+        {
+            *value = coerced;
+        }
+        if let Some(list) = &question.list
+            && let Some(entries) = value.as_array_mut()
+        {
+            for entry in entries.iter_mut().filter_map(Value::as_object_mut) {
+                coerce_fields(&list.fields, entry);
+            }
+        }
+        if let Some(object) = &question.object
+            && let Some(entry) = value.as_object_mut()
+        {
+            coerce_fields(&object.fields, entry);
+        }
+    }
+}
+
+pub(crate) fn coerce_text(question: &QuestionSpec, text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    match question.kind {
+        QuestionType::Boolean => match trimmed.to_ascii_lowercase().as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        QuestionType::Integer | QuestionType::Rating => {
+            trimmed.parse::<i64>().ok().map(Value::from)
+        }
+        QuestionType::Number => match trimmed.parse::<i64>() {
+            Ok(integer) => Some(Value::from(integer)),
+            Err(_) => trimmed
+                .parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number),
+        },
+        QuestionType::Duration => parse_duration(trimmed).map(Value::from),
+        QuestionType::Enum => matching_choice(question, trimmed).map(Value::String),
+        QuestionType::MultiEnum => trimmed
+            .split(',')
+            .map(str::trim)
+            .filter(|choice| !choice.is_empty())
+            .map(|choice| matching_choice(question, choice).map(Value::String))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        QuestionType::List => serde_json::from_str::<Value>(trimmed)
+            .ok()
+            .filter(Value::is_array),
+        _ => None,
+    }
+}
+
+fn matching_choice(question: &QuestionSpec, text: &str) -> Option<String> {
+    let choices = question.choices.as_deref()?;
+    if let Some(choice) = choices.iter().find(|choice| *choice == text) {
+        return Some(choice.clone());
+    }
+    let mut folded = choices
+        .iter()
+        .filter(|choice| choice.eq_ignore_ascii_case(text));
+    match (folded.next(), folded.next()) {
+        (Some(choice), None) => Some(choice.clone()),
+        _ => None,
+    }
+}
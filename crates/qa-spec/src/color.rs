@@ -0,0 +1,63 @@
+//! Hex colors (`#RRGGBB` or `#RRGGBBAA`) answered by `color` questions.
+
+/// Accepted shapes, as shown to people filling in a form.
+pub const COLOR_SYNTAX: &str = "#RRGGBB or #RRGGBBAA";
+
+/// JSON Schema `pattern` matching [`is_hex_color`].
+pub const HEX_COLOR_PATTERN: &str = "^#([0-9A-Fa-f]{6}|[0-9A-Fa-f]{8})$";
+
+/// Common color names and their hex values, using the CSS definitions.
+const NAMED_COLORS: &[(&str, &str)] = &[
+    ("aqua", "#00ffff"),
+    ("black", "#000000"),
+    ("blue", "#0000ff"),
+    ("brown", "#a52a2a"),
+    ("cyan", "#00ffff"),
+    ("fuchsia", "#ff00ff"),
+    ("gold", "#ffd700"),
+    ("gray", "#808080"),
+    ("green", "#008000"),
+    ("grey", "#808080"),
+    ("indigo", "#4b0082"),
+    ("lime", "#00ff00"),
+    ("magenta", "#ff00ff"),
+    ("maroon", "#800000"),
+    ("navy", "#000080"),
+    ("olive", "#808000"),
+    ("orange", "#ffa500"),
+    ("pink", "#ffc0cb"),
+    ("purple", "#800080"),
+    ("red", "#ff0000"),
+    ("silver", "#c0c0c0"),
+    ("teal", "#008080"),
+    ("violet", "#ee82ee"),
+    ("white", "#ffffff"),
+    ("yellow", "#ffff00"),
+];
+
+/// Whether `text` is `#` followed by exactly six or eight hex digits.
+pub fn is_hex_color(text: &str) -> bool {
+    text.strip_prefix('#').is_some_and(|digits| {
+        matches!(digits.len(), 6 | 8) && digits.bytes().all(|byte| byte.is_ascii_hexdigit())
+    })
+}
+
+/// Hex value of a common color name such as `red` or `Navy`.
+pub fn named_color(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_ascii_lowercase();
+    NAMED_COLORS
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, hex)| *hex)
+}
+
+/// Normalizes a typed color to hex: surrounding whitespace is dropped and
+/// color names are looked up with [`named_color`]. Returns `None` for
+/// anything that is neither a hex color nor a known name.
+pub fn normalize_color(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if is_hex_color(trimmed) {
+        return Some(trimmed.to_string());
+    }
+    named_color(trimmed).map(str::to_string)
+}
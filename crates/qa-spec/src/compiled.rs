@@ -0,0 +1,301 @@
+//! Form specs with their derived lookups computed once.
+//!
+//! Validation and rendering of a plain [`FormSpec`] rebuild the question-id
+//! set, computed-field order, compiled patterns, and per-question answer
+//! schemas on every call. A [`CompiledFormSpec`] keeps them, so hosts that
+//! serve the same form repeatedly pay for them once.
+
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::answers::ValidationResult;
+use crate::answers_schema;
+use crate::computed::{ComputedPlan, ComputedReport, compute_answers_with_plan};
+use crate::i18n::ResolvedI18nMap;
+use crate::normalize::apply_normalization;
+use crate::pattern::CompiledPatterns;
+use crate::render::{RenderOptions, RenderPayload, render_computed};
+use crate::spec::form::FormSpec;
+use crate::spec::question::QuestionSpec;
+use crate::validate::{
+    FieldValidationError, reject_unbounded, scope_rejection, validate_computed,
+    validate_field_computed,
+};
+use crate::visibility::{VisibilityMap, VisibilityMode, resolve_visibility_with_ctx};
+
+/// Lookups derived from a spec that do not depend on answers.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Derived {
+    pub(crate) question_index: HashMap<String, usize>,
+    pub(crate) computed: ComputedPlan,
+    pub(crate) patterns: CompiledPatterns,
+    pub(crate) question_schemas: Vec<Value>,
+}
+
+impl Derived {
+    fn new(spec: &FormSpec) -> Self {
+        let mut patterns = CompiledPatterns::new();
+        collect_patterns(&spec.questions, &mut patterns);
+        Self {
+            question_index: spec
+                .questions
+                .iter()
+                .enumerate()
+                .map(|(index, question)| (question.id.clone(), index))
+                .collect(),
+            computed: ComputedPlan::new(spec),
+            patterns,
+            question_schemas: answers_schema::question_schemas(spec),
+        }
+    }
+}
+
+fn collect_patterns(questions: &[QuestionSpec], patterns: &mut CompiledPatterns) {
+    for question in questions {
+        let constraint = question.constraint.as_ref();
+        let warnings = constraint.and_then(|constraint| constraint.warnings.as_deref());
+        for pattern in [constraint, warnings]
+            .into_iter()
+            .flatten()
+            .filter_map(|constraint| constraint.pattern.as_ref())
+        {
+            patterns
+                .entry(pattern.clone())
+                .or_insert_with(|| Regex::new(pattern));
+        }
+        if let Some(list) = &question.list {
+            collect_patterns(&list.fields, patterns);
+        }
+        if let Some(object) = &question.object {
+            collect_patterns(&object.fields, patterns);
+        }
+    }
+}
+
+/// A [`FormSpec`] plus the data validation and rendering derive from it.
+///
+/// Dereferences to the spec. The methods mirror the free functions that take
+/// a `&FormSpec` and return the same results.
+#[derive(Debug, Clone)]
+pub struct CompiledFormSpec {
+    spec: FormSpec,
+    derived: Derived,
+}
+
+impl CompiledFormSpec {
+    pub fn new(spec: FormSpec) -> Self {
+        let derived = Derived::new(&spec);
+        Self { spec, derived }
+    }
+
+    pub fn spec(&self) -> &FormSpec {
+        &self.spec
+    }
+
+    pub fn into_spec(self) -> FormSpec {
+        self.spec
+    }
+
+    /// Question with `id`, looked up without scanning the spec.
+    pub fn question(&self, id: &str) -> Option<&QuestionSpec> {
+        self.derived
+            .question_index
+            .get(id)
+            .map(|index| &self.spec.questions[*index])
+    }
+
+    /// Same as [`crate::compute_answers`].
+    pub fn compute_answers(&self, answers: &Value, ctx: &Value) -> (Value, Vec<ComputedReport>) {
+        compute_answers_with_plan(&self.spec, &self.derived.computed, answers, ctx)
+    }
+
+    /// Same as [`crate::materialize_computed`].
+    pub fn materialize_computed(&self, answers: &Value, ctx: &Value) -> Value {
+        self.compute_answers(answers, ctx).0
+    }
+
+    /// Same as [`crate::validate`].
+    pub fn validate(&self, answers: &Value) -> ValidationResult {
+        self.validate_with_ctx(answers, &Value::Null)
+    }
+
+    /// Same as [`crate::validate_with_ctx`].
+    pub fn validate_with_ctx(&self, answers: &Value, ctx: &Value) -> ValidationResult {
+        if let Some(rejected) = reject_unbounded(&self.spec, answers) {
+            return rejected;
+        }
+        let (normalized, computed_answers) = self.validation_answers(answers, ctx);
+        let visibility = resolve_visibility_with_ctx(
+            &self.spec,
+            &computed_answers,
+            ctx,
+            VisibilityMode::Visible,
+        )
+        .map;
+        validate_computed(
+            &self.spec,
+            Some(&self.derived),
+            ctx,
+            &normalized,
+            computed_answers,
+            &visibility,
+        )
+    }
+
+    /// Same as [`crate::validate_field`].
+    pub fn validate_field(
+        &self,
+        answers: &Value,
+        question_id: &str,
+    ) -> Result<ValidationResult, FieldValidationError> {
+        self.validate_field_with_ctx(answers, &Value::Null, question_id)
+    }
+
+    /// Same as [`crate::validate_field_with_ctx`].
+    pub fn validate_field_with_ctx(
+        &self,
+        answers: &Value,
+        ctx: &Value,
+        question_id: &str,
+    ) -> Result<ValidationResult, FieldValidationError> {
+        if !self.derived.question_index.contains_key(question_id) {
+            return Err(FieldValidationError::UnknownQuestion(
+                question_id.to_string(),
+            ));
+        }
+        if let Some(rejected) = reject_unbounded(&self.spec, answers) {
+            return Ok(scope_rejection(rejected, question_id));
+        }
+        let (normalized, computed_answers) = self.validation_answers(answers, ctx);
+        let visibility = resolve_visibility_with_ctx(
+            &self.spec,
+            &computed_answers,
+            ctx,
+            VisibilityMode::Visible,
+        )
+        .map;
+        validate_field_computed(
+            &self.spec,
+            Some(&self.derived),
+            ctx,
+            &normalized,
+            computed_answers,
+            &visibility,
+            question_id,
+        )
+    }
+
+    /// Same as [`crate::validate_with_visibility`].
+    pub fn validate_with_visibility(
+        &self,
+        answers: &Value,
+        visibility: &VisibilityMap,
+    ) -> ValidationResult {
+        self.validate_with_visibility_and_ctx(answers, &Value::Null, visibility)
+    }
+
+    /// Same as [`crate::validate_with_visibility_and_ctx`].
+    pub fn validate_with_visibility_and_ctx(
+        &self,
+        answers: &Value,
+        ctx: &Value,
+        visibility: &VisibilityMap,
+    ) -> ValidationResult {
+        if let Some(rejected) = reject_unbounded(&self.spec, answers) {
+            return rejected;
+        }
+        let (normalized, computed_answers) = self.validation_answers(answers, ctx);
+        validate_computed(
+            &self.spec,
+            Some(&self.derived),
+            ctx,
+            &normalized,
+            computed_answers,
+            visibility,
+        )
+    }
+
+    /// The normalized answers and the computed answers derived from them.
+    fn validation_answers(&self, answers: &Value, ctx: &Value) -> (Value, Value) {
+        let normalized = apply_normalization(&self.spec, answers);
+        let computed_answers = self.materialize_computed(&normalized, ctx);
+        (normalized, computed_answers)
+    }
+
+    /// Same as [`crate::build_render_payload_with_options`].
+    pub fn build_render_payload(
+        &self,
+        ctx: &Value,
+        answers: &Value,
+        resolved_i18n: Option<&ResolvedI18nMap>,
+        options: &RenderOptions,
+    ) -> RenderPayload {
+        let computed_answers = self.materialize_computed(answers, ctx);
+        let visibility = resolve_visibility_with_ctx(
+            &self.spec,
+            &computed_answers,
+            ctx,
+            VisibilityMode::Visible,
+        )
+        .map;
+        render_computed(
+            &self.spec,
+            Some(&self.derived),
+            ctx,
+            computed_answers,
+            resolved_i18n,
+            options,
+            &visibility,
+        )
+    }
+
+    /// Same as [`crate::build_render_payload_with_visibility`].
+    pub fn build_render_payload_with_visibility(
+        &self,
+        ctx: &Value,
+        answers: &Value,
+        resolved_i18n: Option<&ResolvedI18nMap>,
+        options: &RenderOptions,
+        visibility: &VisibilityMap,
+    ) -> RenderPayload {
+        let computed_answers = self.materialize_computed(answers, ctx);
+        render_computed(
+            &self.spec,
+            Some(&self.derived),
+            ctx,
+            computed_answers,
+            resolved_i18n,
+            options,
+            visibility,
+        )
+    }
+
+    /// Same as [`crate::answers_schema`].
+    pub fn answers_schema(&self, visibility: &VisibilityMap) -> Value {
+        answers_schema::generate_with(&self.spec, Some(&self.derived.question_schemas), visibility)
+    }
+}
+
+impl Deref for CompiledFormSpec {
+    type Target = FormSpec;
+
+    fn deref(&self) -> &FormSpec {
+        &self.spec
+    }
+}
+
+impl From<FormSpec> for CompiledFormSpec {
+    fn from(spec: FormSpec) -> Self {
+        Self::new(spec)
+    }
+}
+
+impl FormSpec {
+    /// Precomputes the spec's derived data; see [`CompiledFormSpec`].
+    pub fn compile(self) -> CompiledFormSpec {
+        CompiledFormSpec::new(self)
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use thiserror::Error;
 
+use crate::expr::CaseArm;
 use crate::{Expr, FormSpec, QuestionSpec, spec::validation::CrossFieldValidation};
 
 #[derive(Debug, Error)]
@@ -101,6 +102,8 @@ fn apply_prefix_question(question: &QuestionSpec, prefix: &str) -> QuestionSpec
     let mut out = question.clone();
     out.id = prefix_key(prefix, &out.id);
     out.visible_if = out.visible_if.map(|expr| prefix_expr(expr, prefix));
+    out.required_if = out.required_if.map(|expr| prefix_expr(expr, prefix));
+    out.read_only_if = out.read_only_if.map(|expr| prefix_expr(expr, prefix));
     out.computed = out.computed.map(|expr| prefix_expr(expr, prefix));
     if let Some(list) = &mut out.list {
         list.fields = list
@@ -109,6 +112,13 @@ fn apply_prefix_question(question: &QuestionSpec, prefix: &str) -> QuestionSpec
             .map(|field| apply_prefix_question(field, prefix))
             .collect();
     }
+    if let Some(object) = &mut out.object {
+        object.fields = object
+            .fields
+            .iter()
+            .map(|field| apply_prefix_question(field, prefix))
+            .collect();
+    }
     out
 }
 
@@ -120,6 +130,9 @@ fn prefix_expr(expr: Expr, prefix: &str) -> Expr {
         Expr::IsSet { path } => Expr::IsSet {
             path: prefix_path(prefix, &path),
         },
+        Expr::IsEmpty { path } => Expr::IsEmpty {
+            path: prefix_path(prefix, &path),
+        },
         Expr::And { expressions } => Expr::And {
             expressions: expressions
                 .into_iter()
@@ -159,6 +172,71 @@ fn prefix_expr(expr: Expr, prefix: &str) -> Expr {
             left: Box::new(prefix_expr(*left, prefix)),
             right: Box::new(prefix_expr(*right, prefix)),
         },
+        Expr::In { needle, haystack } => Expr::In {
+            needle: Box::new(prefix_expr(*needle, prefix)),
+            haystack: Box::new(prefix_expr(*haystack, prefix)),
+        },
+        Expr::Contains { left, right } => Expr::Contains {
+            left: Box::new(prefix_expr(*left, prefix)),
+            right: Box::new(prefix_expr(*right, prefix)),
+        },
+        Expr::StartsWith { left, right } => Expr::StartsWith {
+            left: Box::new(prefix_expr(*left, prefix)),
+            right: Box::new(prefix_expr(*right, prefix)),
+        },
+        Expr::EndsWith { left, right } => Expr::EndsWith {
+            left: Box::new(prefix_expr(*left, prefix)),
+            right: Box::new(prefix_expr(*right, prefix)),
+        },
+        Expr::Matches {
+            expression,
+            pattern,
+        } => Expr::Matches {
+            expression: Box::new(prefix_expr(*expression, prefix)),
+            pattern,
+        },
+        Expr::Length { of } => Expr::Length {
+            of: Box::new(prefix_expr(*of, prefix)),
+        },
+        Expr::Add { left, right } => Expr::Add {
+            left: Box::new(prefix_expr(*left, prefix)),
+            right: Box::new(prefix_expr(*right, prefix)),
+        },
+        Expr::Sub { left, right } => Expr::Sub {
+            left: Box::new(prefix_expr(*left, prefix)),
+            right: Box::new(prefix_expr(*right, prefix)),
+        },
+        Expr::Mul { left, right } => Expr::Mul {
+            left: Box::new(prefix_expr(*left, prefix)),
+            right: Box::new(prefix_expr(*right, prefix)),
+        },
+        Expr::Div { left, right } => Expr::Div {
+            left: Box::new(prefix_expr(*left, prefix)),
+            right: Box::new(prefix_expr(*right, prefix)),
+        },
+        Expr::Concat { expressions } => Expr::Concat {
+            expressions: expressions
+                .into_iter()
+                .map(|expr| prefix_expr(expr, prefix))
+                .collect(),
+        },
+        Expr::Coalesce { expressions } => Expr::Coalesce {
+            expressions: expressions
+                .into_iter()
+                .map(|expr| prefix_expr(expr, prefix))
+                .collect(),
+        },
+        Expr::Case { on, arms, default } => Expr::Case {
+            on: Box::new(prefix_expr(*on, prefix)),
+            arms: arms
+                .into_iter()
+                .map(|arm| CaseArm {
+                    when: arm.when,
+                    then: prefix_expr(arm.then, prefix),
+                })
+                .collect(),
+            default: default.map(|default| Box::new(prefix_expr(*default, prefix))),
+        },
         other => other,
     }
 }
@@ -1,8 +1,25 @@
-use crate::spec::form::FormSpec;
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
+use crate::spec::form::FormSpec;
+use crate::spec::question::QuestionSpec;
+
+/// Members of the runtime context that expressions can read as top-level
+/// roots, e.g. `config.secrets_host_available`.
+pub const CONTEXT_ROOTS: [&str; 3] = ["state", "config", "env"];
+
 /// Builds a context used by expressions so answers can be addressed via question ids and the special `answers` key.
 pub fn build_expression_context(answers: &Value) -> Value {
+    build_expression_context_with_ctx(answers, &Value::Null)
+}
+
+/// [`build_expression_context`] plus the [`CONTEXT_ROOTS`] found in the
+/// runtime `ctx`, so conditions can depend on deployment state or host
+/// config. A question whose id matches a root shadows it.
+pub fn build_expression_context_with_ctx(answers: &Value, ctx: &Value) -> Value {
     let mut map = Map::new();
     if let Some(object) = answers.as_object() {
         for (key, value) in object {
@@ -10,26 +27,331 @@ pub fn build_expression_context(answers: &Value) -> Value {
         }
     }
     map.insert("answers".into(), answers.clone());
+    insert_context_roots(&mut map, ctx);
     Value::Object(map)
 }
 
+fn insert_context_roots(map: &mut Map<String, Value>, ctx: &Value) {
+    for root in CONTEXT_ROOTS {
+        if let Some(value) = ctx.get(root) {
+            map.entry(root).or_insert_with(|| value.clone());
+        }
+    }
+}
+
+/// How a computed question was resolved by [`compute_answers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComputedStatus {
+    /// The expression produced the stored value.
+    Computed,
+    /// An overridable field kept the caller's answer.
+    KeptAnswer,
+    /// The expression could not be evaluated; any previous value was dropped.
+    Unresolved,
+    /// The field depends on itself through other computed fields and was left untouched.
+    Cycle,
+}
+
+/// Describes what happened to a single computed question.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ComputedReport {
+    pub question_id: String,
+    pub status: ComputedStatus,
+    /// Question ids the expression reads.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+    /// For `unresolved` fields, the sources that had no answer yet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+/// Evaluates computed expressions in dependency order and returns the updated answers.
+///
+/// Overridable fields only fill in missing answers; other computed fields always
+/// overwrite. Expressions can read the runtime context under the `ctx` key, and its
+/// [`CONTEXT_ROOTS`] directly, unless a question already uses that id.
+pub fn compute_answers(
+    spec: &FormSpec,
+    answers: &Value,
+    ctx: &Value,
+) -> (Value, Vec<ComputedReport>) {
+    compute_answers_with_plan(spec, &ComputedPlan::new(spec), answers, ctx)
+}
+
+/// Evaluation order of a spec's computed questions, as indices into
+/// `spec.questions`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ComputedPlan {
+    order: Vec<usize>,
+    cycles: Vec<usize>,
+}
+
+impl ComputedPlan {
+    pub(crate) fn new(spec: &FormSpec) -> Self {
+        let computed = spec
+            .questions
+            .iter()
+            .enumerate()
+            .filter(|(_, question)| question.computed.is_some())
+            .map(|(index, question)| (question.id.as_str(), (index, question)))
+            .collect::<BTreeMap<_, _>>();
+        let questions = computed
+            .iter()
+            .map(|(id, (_, question))| (*id, *question))
+            .collect();
+        let (order, cycles) = dependency_order(spec, &questions);
+        let cycles = cycles.into_iter().flatten().collect::<BTreeSet<_>>();
+        Self {
+            order: order.into_iter().map(|id| computed[id].0).collect(),
+            cycles: cycles.into_iter().map(|id| computed[id].0).collect(),
+        }
+    }
+}
+
+/// Computed questions that depend on each other, each cycle listed in
+/// dependency order from the first member reached in spec order
+/// (`["a", "b"]` when `a` reads `b` and `b` reads `a`).
+pub(crate) fn computed_cycles(spec: &FormSpec) -> Vec<Vec<String>> {
+    let computed = spec
+        .questions
+        .iter()
+        .filter(|question| question.computed.is_some())
+        .map(|question| (question.id.as_str(), question))
+        .collect::<BTreeMap<_, _>>();
+    dependency_order(spec, &computed)
+        .1
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(str::to_string).collect())
+        .collect()
+}
+
+pub(crate) fn compute_answers_with_plan(
+    spec: &FormSpec,
+    plan: &ComputedPlan,
+    answers: &Value,
+    ctx: &Value,
+) -> (Value, Vec<ComputedReport>) {
+    let mut context = ExpressionContext::new(answers.as_object().cloned().unwrap_or_default(), ctx);
+
+    let mut reports = BTreeMap::new();
+    for &index in &plan.order {
+        let question = &spec.questions[index];
+        let id = question.id.as_str();
+        let Some(expr) = &question.computed else {
+            continue;
+        };
+        let sources = expr.referenced_answers();
+        let report = if question.computed_overridable && context.answers().contains_key(id) {
+            ComputedReport {
+                question_id: id.to_string(),
+                status: ComputedStatus::KeptAnswer,
+                sources,
+                missing: Vec::new(),
+                value: context.answers().get(id).cloned(),
+            }
+        } else {
+            match expr.evaluate_value(context.as_value()) {
+                Some(value) => {
+                    context.insert(id, value.clone());
+                    ComputedReport {
+                        question_id: id.to_string(),
+                        status: ComputedStatus::Computed,
+                        sources,
+                        missing: Vec::new(),
+                        value: Some(value),
+                    }
+                }
+                None => {
+                    context.remove(id);
+                    let missing = sources
+                        .iter()
+                        .filter(|source| !context.answers().contains_key(source.as_str()))
+                        .cloned()
+                        .collect();
+                    ComputedReport {
+                        question_id: id.to_string(),
+                        status: ComputedStatus::Unresolved,
+                        sources,
+                        missing,
+                        value: None,
+                    }
+                }
+            }
+        };
+        reports.insert(id, report);
+    }
+    for &index in &plan.cycles {
+        let question = &spec.questions[index];
+        let sources = question
+            .computed
+            .as_ref()
+            .map(|expr| expr.referenced_answers())
+            .unwrap_or_default();
+        reports.insert(
+            question.id.as_str(),
+            ComputedReport {
+                question_id: question.id.clone(),
+                status: ComputedStatus::Cycle,
+                sources,
+                missing: Vec::new(),
+                value: None,
+            },
+        );
+    }
+
+    let reports = spec
+        .questions
+        .iter()
+        .filter_map(|question| reports.remove(question.id.as_str()))
+        .collect();
+    (Value::Object(context.into_answers()), reports)
+}
+
+/// The answers with every computed question materialized, in dependency
+/// order: what progress, rendering and submits work from. A computed
+/// question whose expression cannot be evaluated yet (usually because a
+/// source is unanswered) is left out; [`compute_answers`] reports why.
+pub fn materialize_computed(spec: &FormSpec, answers: &Value, ctx: &Value) -> Value {
+    compute_answers(spec, answers, ctx).0
+}
+
 /// Applies computed expressions defined in the spec and returns a new answer map that includes the derived values.
 pub fn apply_computed_answers(spec: &FormSpec, answers: &Value) -> Value {
-    let mut map = answers.as_object().cloned().unwrap_or_default();
+    materialize_computed(spec, answers, &Value::Null)
+}
 
-    for question in &spec.questions {
+/// [`build_expression_context`] output plus the runtime context under `ctx`,
+/// updated in place as computed values land instead of rebuilt per question.
+struct ExpressionContext<'a> {
+    context: Value,
+    ctx: &'a Value,
+}
+
+impl<'a> ExpressionContext<'a> {
+    fn new(answers: Map<String, Value>, ctx: &'a Value) -> Self {
+        let mut context = answers.clone();
+        context.insert("answers".into(), Value::Object(answers));
+        if ctx.is_object() {
+            context.entry("ctx").or_insert_with(|| ctx.clone());
+        }
+        insert_context_roots(&mut context, ctx);
+        Self {
+            context: Value::Object(context),
+            ctx,
+        }
+    }
+
+    fn as_value(&self) -> &Value {
+        &self.context
+    }
+
+    fn answers(&self) -> &Map<String, Value> {
+        self.context["answers"]
+            .as_object()
+            .expect("expression context holds the answers object")
+    }
+
+    fn entries_mut(&mut self) -> &mut Map<String, Value> {
+        self.context
+            .as_object_mut()
+            .expect("expression context is an object")
+    }
+
+    fn answers_mut(&mut self) -> &mut Map<String, Value> {
+        self.entries_mut()
+            .get_mut("answers")
+            .and_then(Value::as_object_mut)
+            .expect("expression context holds the answers object")
+    }
+
+    fn insert(&mut self, id: &str, value: Value) {
+        if id != "answers" {
+            self.entries_mut().insert(id.to_string(), value.clone());
+        }
+        self.answers_mut().insert(id.to_string(), value);
+    }
+
+    fn remove(&mut self, id: &str) {
+        self.answers_mut().remove(id);
+        if id == "answers" {
+            return;
+        }
+        self.entries_mut().remove(id);
+        if id == "ctx" && self.ctx.is_object() {
+            let ctx = self.ctx.clone();
+            self.entries_mut().insert("ctx".into(), ctx);
+        }
+        if CONTEXT_ROOTS.contains(&id) {
+            let ctx = self.ctx;
+            insert_context_roots(self.entries_mut(), ctx);
+        }
+    }
+
+    fn into_answers(mut self) -> Map<String, Value> {
+        std::mem::take(self.answers_mut())
+    }
+}
+
+/// Orders computed questions so dependencies are evaluated first (spec order
+/// otherwise) and returns the dependency cycles separately, each as the path
+/// of ids that loops back to its first entry.
+fn dependency_order<'a>(
+    spec: &'a FormSpec,
+    computed: &BTreeMap<&'a str, &'a QuestionSpec>,
+) -> (Vec<&'a str>, Vec<Vec<&'a str>>) {
+    struct Walk<'a> {
+        done: BTreeSet<&'a str>,
+        order: Vec<&'a str>,
+        cycles: BTreeSet<&'a str>,
+        paths: Vec<Vec<&'a str>>,
+    }
+
+    fn visit<'a>(
+        id: &'a str,
+        computed: &BTreeMap<&'a str, &'a QuestionSpec>,
+        visiting: &mut Vec<&'a str>,
+        walk: &mut Walk<'a>,
+    ) {
+        if walk.done.contains(id) || walk.cycles.contains(id) {
+            return;
+        }
+        if let Some(start) = visiting.iter().position(|entry| *entry == id) {
+            let path = &visiting[start..];
+            walk.cycles.extend(path.iter().copied());
+            walk.paths.push(path.to_vec());
+            return;
+        }
+        let Some((&key, question)) = computed.get_key_value(id) else {
+            return;
+        };
+        visiting.push(key);
         if let Some(expr) = &question.computed {
-            if map.contains_key(&question.id) && question.computed_overridable {
-                continue;
-            }
-            let context = build_expression_context(&Value::Object(map.clone()));
-            if let Some(value) = expr.evaluate_value(&context) {
-                map.insert(question.id.clone(), value);
-            } else {
-                map.remove(&question.id);
+            for dependency in expr.referenced_answers() {
+                if let Some((&dependency, _)) = computed.get_key_value(dependency.as_str()) {
+                    visit(dependency, computed, visiting, walk);
+                }
             }
         }
+        visiting.pop();
+        if !walk.cycles.contains(key) {
+            walk.done.insert(key);
+            walk.order.push(key);
+        }
     }
 
-    Value::Object(map)
+    let mut walk = Walk {
+        done: BTreeSet::new(),
+        order: Vec::new(),
+        cycles: BTreeSet::new(),
+        paths: Vec::new(),
+    };
+    for question in &spec.questions {
+        if computed.contains_key(question.id.as_str()) {
+            visit(&question.id, computed, &mut Vec::new(), &mut walk);
+        }
+    }
+    (walk.order, walk.paths)
 }
@@ -57,15 +57,23 @@ pub fn push_synthetic_question(form_spec: &mut FormSpec, key: &str, secret: bool
         description: None,
         description_i18n: None,
         required: false,
+        required_if: None,
+        read_only_if: None,
+        order: None,
         choices: None,
+        choices_catalog: None,
         default_value: None,
         secret,
+        secret_path: None,
+        normalize: Vec::new(),
         visible_if: None,
         constraint: None,
         list: None,
+        object: None,
         computed: None,
         policy: Default::default(),
         computed_overridable: false,
+        i18n: None,
     });
 }
 
@@ -85,15 +93,23 @@ pub fn make_minimal_form_spec(provider_id: &str, config: &Value) -> FormSpec {
                         description: None,
                         description_i18n: None,
                         required: false,
+                        required_if: None,
+                        read_only_if: None,
+                        order: None,
                         choices: None,
+                        choices_catalog: None,
                         default_value: None,
                         secret,
+                        secret_path: None,
+                        normalize: Vec::new(),
                         visible_if: None,
                         constraint: None,
                         list: None,
+                        object: None,
                         computed: None,
                         policy: Default::default(),
                         computed_overridable: false,
+                        i18n: None,
                     }
                 })
                 .collect()
@@ -111,6 +127,10 @@ pub fn make_minimal_form_spec(provider_id: &str, config: &Value) -> FormSpec {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions,
     }
 }
@@ -125,10 +145,7 @@ pub fn infer_question_properties(id: &str) -> (QuestionType, bool, Option<Constr
             false,
             Some(Constraint {
                 pattern: Some(r"^https?://\S+".to_string()),
-                min: None,
-                max: None,
-                min_len: None,
-                max_len: None,
+                ..Constraint::default()
             }),
         ),
         id if id.ends_with("_token") || id.contains("secret") || id.contains("password") => {
@@ -302,6 +319,10 @@ mod tests {
             store: vec![],
             validations: vec![],
             includes: vec![],
+            migrations: vec![],
+            max_answer_bytes: None,
+            max_total_bytes: None,
+            i18n: None,
             questions: vec![],
         };
         push_synthetic_question(&mut spec, "key1", true);
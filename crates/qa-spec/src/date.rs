@@ -0,0 +1,53 @@
+//! ISO-8601 calendar dates (`YYYY-MM-DD`) answered by `date` questions.
+
+/// Whether `text` is a real calendar date written exactly as `YYYY-MM-DD`.
+pub fn is_iso_date(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let digits = |range: std::ops::Range<usize>| {
+        text.get(range)
+            .filter(|part| part.bytes().all(|byte| byte.is_ascii_digit()))
+            .and_then(|part| part.parse::<u32>().ok())
+    };
+    match (digits(0..4), digits(5..7), digits(8..10)) {
+        (Some(year), Some(month), Some(day)) => is_calendar_date(year, month, day),
+        _ => false,
+    }
+}
+
+/// Normalizes a loosely written date to `YYYY-MM-DD`.
+///
+/// Accepts surrounding whitespace, `-`, `/`, or `.` separators, and month or
+/// day written without a leading zero (`2026/3/7`). The year needs all four
+/// digits. Returns `None` for anything that is not a real calendar date in
+/// year-month-day order.
+pub fn normalize_date(raw: &str) -> Option<String> {
+    let mut parts = raw.trim().split(['-', '/', '.']);
+    let mut next = |lengths: std::ops::RangeInclusive<usize>| {
+        parts
+            .next()
+            .filter(|part| {
+                lengths.contains(&part.len()) && part.bytes().all(|byte| byte.is_ascii_digit())
+            })
+            .and_then(|part| part.parse::<u32>().ok())
+    };
+    let (year, month, day) = (next(4..=4)?, next(1..=2)?, next(1..=2)?);
+    if parts.next().is_some() || !is_calendar_date(year, month, day) {
+        return None;
+    }
+    Some(format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+fn is_calendar_date(year: u32, month: u32, day: u32) -> bool {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
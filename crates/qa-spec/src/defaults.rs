@@ -0,0 +1,124 @@
+//! Typed `default_value`s for forms whose progress policy autofills them.
+//!
+//! Defaults are written as strings in the spec. When
+//! `progress_policy.autofill_defaults` is set they are converted to the
+//! question's type and stored like any other answer, so a form made only of
+//! defaulted questions completes without asking anything.
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::coerce::coerce_text;
+use crate::spec::form::FormSpec;
+use crate::spec::question::{QuestionSpec, QuestionType, split_choices};
+use crate::time_of_day::{format_time_of_day, seconds_of_day};
+use crate::visibility::{VisibilityMode, resolve_visibility_with_ctx};
+
+/// A `default_value` that cannot be read as its question's type.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("default_value '{value}' of question '{question_id}' is not a valid {expected}")]
+pub struct DefaultValueError {
+    pub question_id: String,
+    pub value: String,
+    /// The question type, as written in the spec (`boolean`, `enum`, ...).
+    pub expected: String,
+}
+
+/// The question's `default_value` converted to its type, or `None` when it
+/// has none.
+///
+/// Booleans, numbers, durations and choices are read like coerced answers;
+/// `string_list` and `multi_enum` defaults are comma-separated and `time`
+/// defaults are normalized to `HH:MM:SS`. Other types keep the string.
+/// `list` and `object` questions cannot have a string default.
+pub fn typed_default(question: &QuestionSpec) -> Result<Option<Value>, DefaultValueError> {
+    let Some(raw) = question.default_value.as_deref() else {
+        return Ok(None);
+    };
+    let typed = match question.kind {
+        QuestionType::Boolean
+        | QuestionType::Integer
+        | QuestionType::Rating
+        | QuestionType::Number
+        | QuestionType::Duration
+        | QuestionType::Enum
+        | QuestionType::MultiEnum => coerce_text(question, raw),
+        QuestionType::StringList => Some(Value::Array(
+            split_choices(raw)
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        )),
+        QuestionType::Time => seconds_of_day(raw).map(|seconds| format_time_of_day(seconds).into()),
+        QuestionType::List | QuestionType::Object => None,
+        QuestionType::String
+        | QuestionType::Date
+        | QuestionType::Path
+        | QuestionType::Color
+        | QuestionType::Markdown => Some(Value::String(raw.to_string())),
+    };
+    typed.map(Some).ok_or_else(|| DefaultValueError {
+        question_id: question.id.clone(),
+        value: raw.to_string(),
+        expected: serde_json::to_value(question.kind)
+            .ok()
+            .and_then(|kind| kind.as_str().map(str::to_string))
+            .unwrap_or_default(),
+    })
+}
+
+/// Returns `answers` with the typed default of every unanswered, visible
+/// question filled in, plus the ids that were filled, when the spec's
+/// progress policy has `autofill_defaults`; otherwise `answers` unchanged.
+///
+/// Under `treat_default_as_answered` alone, progress counts defaults without
+/// storing them, so only `required` questions are filled, to let the form
+/// complete with them.
+///
+/// Computed questions and questions whose policy sets
+/// `editable_if_from_default` are left alone, the latter so they are still
+/// asked. A default that does not fit its question's type is an error.
+pub fn autofill_defaults(
+    spec: &FormSpec,
+    answers: &Value,
+    ctx: &Value,
+) -> Result<(Value, Vec<String>), DefaultValueError> {
+    let mut filled = answers.as_object().cloned().unwrap_or_default();
+    let policy = spec.progress_policy.unwrap_or_default();
+    if !policy.autofill_defaults && !policy.treat_default_as_answered {
+        return Ok((Value::Object(filled), Vec::new()));
+    }
+
+    let mut ids = Vec::new();
+    for question in &spec.questions {
+        if filled.contains_key(&question.id)
+            || question.computed.is_some()
+            || question.policy.editable_if_from_default
+            || !(policy.autofill_defaults || question.required)
+        {
+            continue;
+        }
+        if let Some(value) = typed_default(question)? {
+            filled.insert(question.id.clone(), value);
+            ids.push(question.id.clone());
+        }
+    }
+    if ids.is_empty() {
+        return Ok((Value::Object(filled), ids));
+    }
+
+    // A default must not make a hidden question look answered.
+    let visibility = resolve_visibility_with_ctx(
+        spec,
+        &Value::Object(filled.clone()),
+        ctx,
+        VisibilityMode::Visible,
+    );
+    ids.retain(|id| {
+        let visible = visibility.map.get(id).copied().unwrap_or(true);
+        if !visible {
+            filled.remove(id);
+        }
+        visible
+    });
+    Ok((Value::Object(filled), ids))
+}
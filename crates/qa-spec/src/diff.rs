@@ -0,0 +1,202 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::answers::{AnswerSet, canonical_eq, json_pointer};
+use crate::spec::form::FormSpec;
+use crate::spec::question::QuestionSpec;
+
+/// Kind of change recorded for a single answer path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference between two answer sets, addressed by JSON pointer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AnswerChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<Value>,
+    /// Values were withheld because the path covers a secret question.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub masked: bool,
+}
+
+/// Per-kind totals for an [`AnswerDiff`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+/// Structured change list between two answer sets.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AnswerDiff {
+    pub changes: Vec<AnswerChange>,
+    pub summary: DiffSummary,
+}
+
+impl AnswerDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn push(&mut self, change: AnswerChange) {
+        match change.kind {
+            ChangeKind::Added => self.summary.added += 1,
+            ChangeKind::Removed => self.summary.removed += 1,
+            ChangeKind::Changed => self.summary.changed += 1,
+        }
+        self.changes.push(change);
+    }
+}
+
+impl AnswerSet {
+    /// Lists what changed going from `self` to `other`.
+    ///
+    /// Objects and lists are compared element by element, so editing one field
+    /// of one list entry yields a single change at `/list/<index>/<field>`.
+    pub fn diff(&self, other: &AnswerSet) -> AnswerDiff {
+        diff_answers(&self.answers, &other.answers, None)
+    }
+
+    /// Same as [`AnswerSet::diff`], withholding values of secret questions.
+    pub fn diff_with_spec(&self, other: &AnswerSet, spec: &FormSpec) -> AnswerDiff {
+        diff_answers(&self.answers, &other.answers, Some(spec))
+    }
+}
+
+pub(crate) fn diff_answers(old: &Value, new: &Value, spec: Option<&FormSpec>) -> AnswerDiff {
+    let mut diff = AnswerDiff::default();
+    let mut path = Vec::new();
+    walk(old, new, &mut path, spec, &mut diff);
+    diff
+}
+
+fn walk(
+    old: &Value,
+    new: &Value,
+    path: &mut Vec<String>,
+    spec: Option<&FormSpec>,
+    diff: &mut AnswerDiff,
+) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                path.push(key.clone());
+                match new_map.get(key) {
+                    Some(new_value) => walk(old_value, new_value, path, spec, diff),
+                    None => record(ChangeKind::Removed, Some(old_value), None, path, spec, diff),
+                }
+                path.pop();
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    path.push(key.clone());
+                    record(ChangeKind::Added, None, Some(new_value), path, spec, diff);
+                    path.pop();
+                }
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            for index in 0..old_items.len().max(new_items.len()) {
+                path.push(index.to_string());
+                match (old_items.get(index), new_items.get(index)) {
+                    (Some(old_item), Some(new_item)) => walk(old_item, new_item, path, spec, diff),
+                    (old_item, new_item) => {
+                        let kind = if old_item.is_some() {
+                            ChangeKind::Removed
+                        } else {
+                            ChangeKind::Added
+                        };
+                        record(kind, old_item, new_item, path, spec, diff);
+                    }
+                }
+                path.pop();
+            }
+        }
+        _ if !canonical_eq(old, new) => {
+            record(ChangeKind::Changed, Some(old), Some(new), path, spec, diff)
+        }
+        _ => {}
+    }
+}
+
+fn record(
+    kind: ChangeKind,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    path: &[String],
+    spec: Option<&FormSpec>,
+    diff: &mut AnswerDiff,
+) {
+    let masked = spec.is_some_and(|spec| {
+        [old, new]
+            .into_iter()
+            .flatten()
+            .any(|value| covers_secret(spec, path, value))
+    });
+    let pointer = json_pointer(path);
+    diff.push(AnswerChange {
+        path: pointer,
+        kind,
+        old: old.filter(|_| !masked).cloned(),
+        new: new.filter(|_| !masked).cloned(),
+        masked,
+    });
+}
+
+/// Whether `value` at `path` holds (or is part of) a secret answer.
+fn covers_secret(spec: &FormSpec, path: &[String], value: &Value) -> bool {
+    let Some(question) = path
+        .first()
+        .and_then(|id| spec.questions.iter().find(|question| &question.id == id))
+    else {
+        return false;
+    };
+    if question.secret {
+        return true;
+    }
+    if let Some(object) = &question.object {
+        let secret_fields = object
+            .fields
+            .iter()
+            .filter(|field| field.secret)
+            .collect::<Vec<_>>();
+        return match path.get(1) {
+            Some(field_id) => secret_fields.iter().any(|field| &field.id == field_id),
+            None => entry_has_secret(value, &secret_fields),
+        };
+    }
+    let Some(list) = &question.list else {
+        return false;
+    };
+    let secret_fields = list
+        .fields
+        .iter()
+        .filter(|field| field.secret)
+        .collect::<Vec<_>>();
+    match path.get(2) {
+        Some(field_id) => secret_fields.iter().any(|field| &field.id == field_id),
+        None if path.len() == 2 => entry_has_secret(value, &secret_fields),
+        None => value.as_array().is_some_and(|items| {
+            items
+                .iter()
+                .any(|item| entry_has_secret(item, &secret_fields))
+        }),
+    }
+}
+
+fn entry_has_secret(entry: &Value, secret_fields: &[&QuestionSpec]) -> bool {
+    secret_fields
+        .iter()
+        .any(|field| entry.get(&field.id).is_some())
+}
@@ -0,0 +1,47 @@
+//! Durations answered by `duration` questions, stored as whole seconds.
+
+/// Human-readable summary of the syntax accepted by [`parse_duration`].
+pub const DURATION_SYNTAX: &str =
+    "seconds, or amounts with units s/m/h/d (e.g. 30s, 5m, 2h, 1h30m)";
+
+/// Parses a human-written duration into whole seconds.
+///
+/// Accepts a bare number of seconds (`300`) or one or more amounts followed
+/// by a unit (`30s`, `5m`, `2h`, `1d`, `1h30m`, `1h 30m`). Units may be
+/// spelled out (`sec`, `mins`, `hours`, `days`) and are case-insensitive.
+/// Returns `None` for anything else, including totals that overflow `u64`.
+pub fn parse_duration(raw: &str) -> Option<u64> {
+    let text = raw.trim().to_ascii_lowercase();
+    if text.is_empty() {
+        return None;
+    }
+    if text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return text.parse().ok();
+    }
+
+    let mut total: u64 = 0;
+    let mut rest = text.as_str();
+    while !rest.is_empty() {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        let amount: u64 = rest[..digits].parse().ok()?;
+        rest = rest[digits..].trim_start();
+        let letters = rest.bytes().take_while(u8::is_ascii_alphabetic).count();
+        let unit = unit_seconds(&rest[..letters])?;
+        rest = rest[letters..].trim_start();
+        total = total.checked_add(amount.checked_mul(unit)?)?;
+    }
+    Some(total)
+}
+
+fn unit_seconds(unit: &str) -> Option<u64> {
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(60),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(60 * 60),
+        "d" | "day" | "days" => Some(24 * 60 * 60),
+        _ => None,
+    }
+}
@@ -1,7 +1,11 @@
 use serde_json::{Map, Number, Value};
 
+use crate::duration::parse_duration;
 use crate::spec::form::FormSpec;
-use crate::spec::question::{QuestionSpec, QuestionType};
+use crate::spec::question::{
+    Constraint, QuestionSpec, QuestionType, StringFormat, rating_range, split_choices,
+};
+use crate::time_of_day::{format_time_of_day, seconds_of_day};
 use crate::visibility::VisibilityMap;
 
 pub fn generate(spec: &FormSpec, visibility: &VisibilityMap) -> Value {
@@ -17,20 +21,269 @@ pub fn generate(spec: &FormSpec, visibility: &VisibilityMap) -> Value {
     Value::Object(output)
 }
 
+const EXAMPLE_DATE: &str = "2026-01-01";
+const EXAMPLE_COLOR: &str = "#336699";
+const EXAMPLE_EMAIL: &str = "user@example.com";
+const EXAMPLE_URL: &str = "https://example.com";
+const EXAMPLE_UUID: &str = "123e4567-e89b-12d3-a456-426614174000";
+const EXAMPLE_PHONE: &str = "+15551234567";
+const EXAMPLE_DURATION_SECS: u64 = 300;
+/// 09:00:00.
+const EXAMPLE_TIME_SECS: u32 = 9 * 3600;
+
+/// Example value honouring the question's choices, bounds, lengths, formats,
+/// and list sizes. Patterns are not taken into account.
 fn example_for(question: &QuestionSpec) -> Value {
     if let Some(default_value) = &question.default_value {
+        if matches!(question.kind, QuestionType::Duration)
+            && let Some(seconds) = parse_duration(default_value)
+        {
+            return Value::Number(Number::from(seconds));
+        }
+        if matches!(question.kind, QuestionType::Time)
+            && let Some(seconds) = seconds_of_day(default_value)
+        {
+            return Value::String(format_time_of_day(seconds));
+        }
+        if matches!(
+            question.kind,
+            QuestionType::MultiEnum | QuestionType::StringList
+        ) {
+            return Value::Array(
+                split_choices(default_value)
+                    .map(|choice| Value::String(choice.to_string()))
+                    .collect(),
+            );
+        }
         return Value::String(default_value.clone());
     }
 
+    let constraint = question.constraint.as_ref();
     match question.kind {
-        QuestionType::String | QuestionType::Enum => {
-            Value::String(format!("example-{}", question.id))
+        QuestionType::String => Value::String(example_string(
+            format!("example-{}", question.id),
+            constraint,
+        )),
+        QuestionType::Markdown => Value::String(fit_length(
+            format!("**example-{}**", question.id),
+            constraint,
+        )),
+        QuestionType::StringList => {
+            let count = question
+                .list
+                .as_ref()
+                .and_then(|list| list.min_items)
+                .unwrap_or(1);
+            Value::Array(
+                (1..=count)
+                    .map(|n| {
+                        Value::String(example_string(
+                            format!("example-{}-{n}", question.id),
+                            constraint,
+                        ))
+                    })
+                    .collect(),
+            )
+        }
+        QuestionType::Enum => Value::String(
+            question
+                .choices
+                .as_ref()
+                .and_then(|choices| choices.first().cloned())
+                .unwrap_or_else(|| format!("example-{}", question.id)),
+        ),
+        QuestionType::MultiEnum => {
+            let count = constraint
+                .and_then(|constraint| constraint.min_selected)
+                .unwrap_or(1);
+            Value::Array(
+                question
+                    .choices
+                    .iter()
+                    .flatten()
+                    .take(count)
+                    .map(|choice| Value::String(choice.clone()))
+                    .collect(),
+            )
         }
         QuestionType::Boolean => Value::Bool(false),
-        QuestionType::Integer => Value::Number(Number::from(1)),
-        QuestionType::Number => {
-            Value::Number(Number::from_f64(1.0).unwrap_or_else(|| Number::from(1)))
+        QuestionType::Date => Value::String(EXAMPLE_DATE.into()),
+        QuestionType::Color => Value::String(EXAMPLE_COLOR.into()),
+        QuestionType::Time => Value::String(format_time_of_day(example_time(constraint))),
+        QuestionType::Path => Value::String(format!("./{}", question.id)),
+        QuestionType::Integer => Value::Number(Number::from(example_integer(constraint))),
+        QuestionType::Rating => Value::Number(Number::from(*rating_range(constraint).end())),
+        QuestionType::Duration => Value::Number(Number::from(example_duration(constraint))),
+        QuestionType::Number => Value::Number(
+            Number::from_f64(example_number(constraint)).unwrap_or_else(|| Number::from(1)),
+        ),
+        QuestionType::Object => Value::Object(
+            question
+                .object
+                .iter()
+                .flat_map(|object| &object.fields)
+                .map(|field| (field.id.clone(), example_for(field)))
+                .collect(),
+        ),
+        QuestionType::List => {
+            let min_items = question
+                .list
+                .as_ref()
+                .and_then(|list| list.min_items)
+                .unwrap_or(0);
+            let entry = question
+                .list
+                .as_ref()
+                .map(|list| {
+                    list.fields
+                        .iter()
+                        .map(|field| (field.id.clone(), example_for(field)))
+                        .collect::<Map<_, _>>()
+                })
+                .unwrap_or_default();
+            Value::Array(vec![Value::Object(entry); min_items])
         }
-        QuestionType::List => Value::Array(Vec::new()),
     }
 }
+
+/// `fallback` fitted to the length bounds, unless a format dictates the shape.
+fn example_string(fallback: String, constraint: Option<&Constraint>) -> String {
+    match constraint.and_then(|constraint| constraint.format) {
+        Some(StringFormat::Email) => EXAMPLE_EMAIL.into(),
+        Some(StringFormat::Url) => EXAMPLE_URL.into(),
+        Some(StringFormat::Uuid) => EXAMPLE_UUID.into(),
+        Some(StringFormat::Phone) => EXAMPLE_PHONE.into(),
+        None => fit_length(fallback, constraint),
+    }
+}
+
+fn fit_length(mut text: String, constraint: Option<&Constraint>) -> String {
+    if let Some(min_len) = constraint.and_then(|constraint| constraint.min_len) {
+        while text.len() < min_len {
+            text.push('x');
+        }
+    }
+    if let Some(max_len) = constraint.and_then(|constraint| constraint.max_len) {
+        text.truncate(max_len);
+    }
+    text
+}
+
+fn example_integer(constraint: Option<&Constraint>) -> i64 {
+    let Some(constraint) = constraint else {
+        return 1;
+    };
+    let lower = constraint.min.map(|min| {
+        let floor = min.ceil() as i64;
+        if constraint.exclusive_min && floor as f64 <= min {
+            floor + 1
+        } else {
+            floor
+        }
+    });
+    let upper = constraint.max.map(|max| {
+        let ceiling = max.floor() as i64;
+        if constraint.exclusive_max && ceiling as f64 >= max {
+            ceiling - 1
+        } else {
+            ceiling
+        }
+    });
+    let value = match (lower, upper) {
+        (Some(lower), _) => lower,
+        (None, Some(upper)) => upper.min(1),
+        (None, None) => 1,
+    };
+    let snapped = snap_to_multiple(value as f64, constraint);
+    if snapped.fract() == 0.0 {
+        snapped as i64
+    } else {
+        value
+    }
+}
+
+/// Five minutes, unless the bounds rule it out.
+fn example_duration(constraint: Option<&Constraint>) -> u64 {
+    if constraint.is_none_or(|constraint| within_bounds(EXAMPLE_DURATION_SECS as f64, constraint)) {
+        EXAMPLE_DURATION_SECS
+    } else {
+        example_integer(constraint).max(0) as u64
+    }
+}
+
+/// Seconds after midnight of the example `time` answer.
+fn example_time(constraint: Option<&Constraint>) -> u32 {
+    if constraint.is_none_or(|constraint| within_bounds(EXAMPLE_TIME_SECS as f64, constraint)) {
+        EXAMPLE_TIME_SECS
+    } else {
+        example_integer(constraint).clamp(0, 86_399) as u32
+    }
+}
+
+fn example_number(constraint: Option<&Constraint>) -> f64 {
+    let Some(constraint) = constraint else {
+        return 1.0;
+    };
+    let value = match (constraint.min, constraint.max) {
+        (Some(min), Some(max)) if constraint.exclusive_min || constraint.exclusive_max => {
+            (min + max) / 2.0
+        }
+        (Some(min), _) if constraint.exclusive_min => min + 1.0,
+        (Some(min), _) => min,
+        (None, Some(max)) if constraint.exclusive_max => (max - 1.0).min(1.0),
+        (None, Some(max)) => max.min(1.0),
+        (None, None) => 1.0,
+    };
+    let value = snap_to_multiple(value, constraint);
+    match constraint.decimal_places {
+        Some(places) => round_to_places(value, places, constraint),
+        None => value,
+    }
+}
+
+/// `value` cut to `places` decimals, preferring the nearest rounding that
+/// stays within the bounds.
+fn round_to_places(value: f64, places: u8, constraint: &Constraint) -> f64 {
+    let scale = 10f64.powi(i32::from(places));
+    let scaled = value * scale;
+    [scaled.round(), scaled.floor(), scaled.ceil()]
+        .into_iter()
+        .map(|candidate| candidate / scale)
+        .find(|candidate| within_bounds(*candidate, constraint))
+        .unwrap_or_else(|| scaled.round() / scale)
+}
+
+/// `value` moved to a multiple of `constraint.multiple_of` that stays within
+/// the bounds, trying the next one up first.
+fn snap_to_multiple(value: f64, constraint: &Constraint) -> f64 {
+    let Some(step) = constraint.multiple_of.filter(|step| *step > 0.0) else {
+        return value;
+    };
+    let steps = value / step;
+    if (steps - steps.round()).abs() <= 1e-9 {
+        return value;
+    }
+    [steps.ceil(), steps.floor(), steps.ceil() + 1.0]
+        .into_iter()
+        .map(|count| count * step)
+        .find(|candidate| within_bounds(*candidate, constraint))
+        .unwrap_or(value)
+}
+
+fn within_bounds(value: f64, constraint: &Constraint) -> bool {
+    let above = constraint.min.is_none_or(|min| {
+        if constraint.exclusive_min {
+            value > min
+        } else {
+            value >= min
+        }
+    });
+    let below = constraint.max.is_none_or(|max| {
+        if constraint.exclusive_max {
+            value < max
+        } else {
+            value <= max
+        }
+    });
+    above && below
+}
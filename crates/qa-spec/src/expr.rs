@@ -1,70 +1,381 @@
+use std::fmt;
+
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use crate::limits::DEFAULT_MAX_EXPR_DEPTH;
+use crate::pattern::compile_pattern;
+
 /// Lightweight expression AST used for `visible_if`, computed fields, and validations.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+///
+/// Operands of the binary operators may also be written as a bare JSON
+/// pointer string, the shape older specs used (`"left": "/answers/env"`);
+/// it reads as [`Expr::Var`] with that path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "op", rename_all = "snake_case")]
 pub enum Expr {
-    Literal { value: Value },
-    Var { path: String },
-    Answer { path: String },
-    IsSet { path: String },
-    And { expressions: Vec<Expr> },
-    Or { expressions: Vec<Expr> },
-    Not { expression: Box<Expr> },
-    Eq { left: Box<Expr>, right: Box<Expr> },
-    Ne { left: Box<Expr>, right: Box<Expr> },
-    Lt { left: Box<Expr>, right: Box<Expr> },
-    Lte { left: Box<Expr>, right: Box<Expr> },
-    Gt { left: Box<Expr>, right: Box<Expr> },
-    Gte { left: Box<Expr>, right: Box<Expr> },
+    Literal {
+        value: Value,
+    },
+    /// Value at a JSON pointer or dotted path in the whole context: answers
+    /// by question id, `answers`, and the runtime `state`, `config` and `env`
+    /// (e.g. `config.secrets_host_available`).
+    Var {
+        path: String,
+    },
+    /// Answer at a JSON pointer or dotted path under `answers`.
+    Answer {
+        path: String,
+    },
+    /// Whether the answer at `path` exists and is not `null`.
+    IsSet {
+        path: String,
+    },
+    /// Whether the answer at `path` is missing, `null`, `""`, `[]` or `{}`.
+    IsEmpty {
+        path: String,
+    },
+    And {
+        expressions: Vec<Expr>,
+    },
+    Or {
+        expressions: Vec<Expr>,
+    },
+    Not {
+        expression: Box<Expr>,
+    },
+    Eq {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    Ne {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    Lt {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    Lte {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    Gt {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    Gte {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    /// Whether `needle` is an element of an array `haystack`, a substring of
+    /// a string one, or a key of an object one.
+    In {
+        #[serde(deserialize_with = "operand")]
+        needle: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        haystack: Box<Expr>,
+    },
+    /// [`Expr::In`] with the operands the other way round: whether `left`
+    /// contains `right`.
+    Contains {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    /// Whether the string `left` starts with the string `right`.
+    StartsWith {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    /// Whether the string `left` ends with the string `right`.
+    EndsWith {
+        #[serde(deserialize_with = "operand")]
+        left: Box<Expr>,
+        #[serde(deserialize_with = "operand")]
+        right: Box<Expr>,
+    },
+    /// Whether the string `expression` matches the regex `pattern`. A pattern
+    /// that does not compile evaluates to `None`.
+    Matches {
+        expression: Box<Expr>,
+        pattern: String,
+    },
+    /// Number of items in the array `of`, or of characters (not bytes) in
+    /// the string `of`, so `"äö"` has length 2.
+    Length {
+        of: Box<Expr>,
+    },
+    /// Sum of two numbers. Like the other arithmetic operators it stays an
+    /// integer while both operands are integers and the result fits, and is
+    /// `None` for non-numbers or a result that is not finite.
+    Add {
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Sub {
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Mul {
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    /// Quotient of two numbers; `None` when dividing by zero.
+    Div {
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    /// Strings, numbers and booleans joined as text; `None` if any
+    /// expression yields something else.
+    Concat {
+        expressions: Vec<Expr>,
+    },
+    /// The first expression with a value other than `null`, e.g. an answer
+    /// with a literal fallback; `None` if there is none. Unlike [`Expr::IsSet`]
+    /// it yields the value itself, so it also works in computed fields.
+    Coalesce {
+        expressions: Vec<Expr>,
+    },
+    /// The `then` of the first arm whose `when` equals the value of `on`,
+    /// else `default`; `None` if `on` has no value or nothing matches
+    /// without a default.
+    Case {
+        on: Box<Expr>,
+        arms: Vec<CaseArm>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default: Option<Box<Expr>>,
+    },
+}
+
+/// One branch of an [`Expr::Case`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CaseArm {
+    pub when: Value,
+    pub then: Expr,
+}
+
+/// Why an expression could not be evaluated, see [`Expr::explain_bool_failure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EvaluationFailure {
+    /// An `answer` or `var` path has no value, e.g. an unanswered question.
+    MissingValue,
+    /// The operands cannot be combined: ordering a string against a number,
+    /// arithmetic on text, dividing by zero.
+    TypeMismatch,
+    /// A `matches` pattern does not compile.
+    InvalidPattern,
+    /// The expression produced a value that is not a boolean.
+    NotBoolean,
+    /// The expression nests deeper than [`DEFAULT_MAX_EXPR_DEPTH`].
+    TooDeep,
+}
+
+/// Reads a binary operand: an expression object, or a legacy pointer string.
+fn operand<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<Expr>, D::Error> {
+    struct OperandVisitor;
+
+    impl<'de> Visitor<'de> for OperandVisitor {
+        type Value = Box<Expr>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an expression object or a JSON pointer string")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, path: &str) -> Result<Self::Value, E> {
+            Ok(Box::new(Expr::Var {
+                path: path.to_string(),
+            }))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            Expr::deserialize(MapAccessDeserializer::new(map)).map(Box::new)
+        }
+    }
+
+    deserializer.deserialize_any(OperandVisitor)
 }
 
 impl Expr {
     /// Evaluates the expression and returns a JSON value when possible.
+    ///
+    /// Expressions nested deeper than [`DEFAULT_MAX_EXPR_DEPTH`] evaluate to
+    /// `None`; see [`Expr::evaluate_value_within`].
     pub fn evaluate_value(&self, ctx: &Value) -> Option<Value> {
+        self.evaluate_value_within(ctx, DEFAULT_MAX_EXPR_DEPTH)
+    }
+
+    /// Evaluates the expression and coerces the result into a boolean when possible.
+    pub fn evaluate_bool(&self, ctx: &Value) -> Option<bool> {
+        self.evaluate_bool_within(ctx, DEFAULT_MAX_EXPR_DEPTH)
+    }
+
+    /// Like [`Expr::evaluate_value`], giving up with `None` instead of
+    /// recursing through more than `max_depth` nested expressions.
+    pub fn evaluate_value_within(&self, ctx: &Value, max_depth: usize) -> Option<Value> {
+        let budget = max_depth.checked_sub(1)?;
         match self {
             Expr::Literal { value } => Some(value.clone()),
             Expr::Var { path } => Self::lookup(ctx, path).cloned(),
             Expr::Answer { path } => Self::lookup_answer(ctx, path).cloned(),
             Expr::IsSet { path } => {
-                let present = Self::lookup_answer(ctx, path).is_some();
+                let present = Self::lookup_answer(ctx, path).is_some_and(|value| !value.is_null());
                 Some(Value::Bool(present))
             }
-            Expr::And { expressions } => Self::evaluate_and(expressions, ctx),
-            Expr::Or { expressions } => Self::evaluate_or(expressions, ctx),
+            Expr::IsEmpty { path } => {
+                let empty = match Self::lookup_answer(ctx, path) {
+                    None | Some(Value::Null) => true,
+                    Some(Value::String(text)) => text.is_empty(),
+                    Some(Value::Array(items)) => items.is_empty(),
+                    Some(Value::Object(map)) => map.is_empty(),
+                    Some(_) => false,
+                };
+                Some(Value::Bool(empty))
+            }
+            Expr::And { expressions } => Self::evaluate_and(expressions, ctx, budget),
+            Expr::Or { expressions } => Self::evaluate_or(expressions, ctx, budget),
             Expr::Not { expression } => expression
-                .evaluate_bool(ctx)
+                .evaluate_bool_within(ctx, budget)
                 .map(|value| Value::Bool(!value)),
             Expr::Eq { left, right } => {
-                let left_value = left.evaluate_value(ctx)?;
-                let right_value = right.evaluate_value(ctx)?;
+                let left_value = left.evaluate_value_within(ctx, budget)?;
+                let right_value = right.evaluate_value_within(ctx, budget)?;
                 Some(Value::Bool(left_value == right_value))
             }
             Expr::Ne { left, right } => {
-                let left_value = left.evaluate_value(ctx)?;
-                let right_value = right.evaluate_value(ctx)?;
+                let left_value = left.evaluate_value_within(ctx, budget)?;
+                let right_value = right.evaluate_value_within(ctx, budget)?;
                 Some(Value::Bool(left_value != right_value))
             }
-            Expr::Lt { left, right } => {
-                Self::evaluate_compare(left, right, ctx, |o| matches!(o, std::cmp::Ordering::Less))
-            }
-            Expr::Lte { left, right } => Self::evaluate_compare(left, right, ctx, |o| {
+            Expr::Lt { left, right } => Self::evaluate_compare(left, right, ctx, budget, |o| {
+                matches!(o, std::cmp::Ordering::Less)
+            }),
+            Expr::Lte { left, right } => Self::evaluate_compare(left, right, ctx, budget, |o| {
                 matches!(o, std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
             }),
-            Expr::Gt { left, right } => Self::evaluate_compare(left, right, ctx, |o| {
+            Expr::Gt { left, right } => Self::evaluate_compare(left, right, ctx, budget, |o| {
                 matches!(o, std::cmp::Ordering::Greater)
             }),
-            Expr::Gte { left, right } => Self::evaluate_compare(left, right, ctx, |o| {
+            Expr::Gte { left, right } => Self::evaluate_compare(left, right, ctx, budget, |o| {
                 matches!(o, std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
             }),
+            Expr::In { needle, haystack }
+            | Expr::Contains {
+                left: haystack,
+                right: needle,
+            } => {
+                let needle = needle.evaluate_value_within(ctx, budget)?;
+                let haystack = haystack.evaluate_value_within(ctx, budget)?;
+                Self::contains(&haystack, &needle).map(Value::Bool)
+            }
+            Expr::StartsWith { left, right } => {
+                Self::evaluate_strings(left, right, ctx, budget, |text, part| {
+                    text.starts_with(part)
+                })
+            }
+            Expr::EndsWith { left, right } => {
+                Self::evaluate_strings(left, right, ctx, budget, |text, part| text.ends_with(part))
+            }
+            Expr::Matches {
+                expression,
+                pattern,
+            } => {
+                let value = expression.evaluate_value_within(ctx, budget)?;
+                let regex = compile_pattern(pattern).ok()?;
+                Some(Value::Bool(regex.is_match(value.as_str()?)))
+            }
+            Expr::Length { of } => match of.evaluate_value_within(ctx, budget)? {
+                Value::String(text) => Some(Value::from(text.chars().count())),
+                Value::Array(items) => Some(Value::from(items.len())),
+                _ => None,
+            },
+            Expr::Add { left, right } => Self::evaluate_arithmetic(
+                left,
+                right,
+                ctx,
+                budget,
+                i64::checked_add,
+                |left, right| left + right,
+            ),
+            Expr::Sub { left, right } => Self::evaluate_arithmetic(
+                left,
+                right,
+                ctx,
+                budget,
+                i64::checked_sub,
+                |left, right| left - right,
+            ),
+            Expr::Mul { left, right } => Self::evaluate_arithmetic(
+                left,
+                right,
+                ctx,
+                budget,
+                i64::checked_mul,
+                |left, right| left * right,
+            ),
+            Expr::Div { left, right } => Self::evaluate_arithmetic(
+                left,
+                right,
+                ctx,
+                budget,
+                |left, right| {
+                    (left.checked_rem(right)? == 0)
+                        .then(|| left.checked_div(right))
+                        .flatten()
+                },
+                |left, right| left / right,
+            ),
+            Expr::Concat { expressions } => {
+                let mut text = String::new();
+                for expression in expressions {
+                    match expression.evaluate_value_within(ctx, budget)? {
+                        Value::String(part) => text.push_str(&part),
+                        value @ (Value::Number(_) | Value::Bool(_)) => {
+                            text.push_str(&value.to_string())
+                        }
+                        _ => return None,
+                    }
+                }
+                Some(Value::String(text))
+            }
+            Expr::Coalesce { expressions } => expressions.iter().find_map(|expression| {
+                expression
+                    .evaluate_value_within(ctx, budget)
+                    .filter(|value| !value.is_null())
+            }),
+            Expr::Case { on, arms, default } => {
+                let value = on.evaluate_value_within(ctx, budget)?;
+                match arms.iter().find(|arm| arm.when == value) {
+                    Some(arm) => arm.then.evaluate_value_within(ctx, budget),
+                    None => default.as_ref()?.evaluate_value_within(ctx, budget),
+                }
+            }
         }
     }
 
-    /// Evaluates the expression and coerces the result into a boolean when possible.
-    pub fn evaluate_bool(&self, ctx: &Value) -> Option<bool> {
-        let value = self.evaluate_value(ctx)?;
+    /// Like [`Expr::evaluate_bool`] with the depth limit of
+    /// [`Expr::evaluate_value_within`].
+    pub fn evaluate_bool_within(&self, ctx: &Value, max_depth: usize) -> Option<bool> {
+        let value = self.evaluate_value_within(ctx, max_depth)?;
         match value {
             Value::Bool(value) => Some(value),
             Value::Number(number) => number.as_f64().map(|value| value != 0.0),
@@ -78,10 +389,144 @@ impl Expr {
         }
     }
 
-    fn evaluate_and(expressions: &[Expr], ctx: &Value) -> Option<Value> {
+    /// Why [`Expr::evaluate_bool`] gives `None` for `ctx`, with the innermost
+    /// sub-expression that failed. `None` when the expression evaluates.
+    pub fn explain_bool_failure(&self, ctx: &Value) -> Option<(EvaluationFailure, &Expr)> {
+        if self.evaluate_bool(ctx).is_some() {
+            return None;
+        }
+        if self.depth() > DEFAULT_MAX_EXPR_DEPTH {
+            return Some((EvaluationFailure::TooDeep, self));
+        }
+        if self.evaluate_value(ctx).is_some() {
+            return Some((EvaluationFailure::NotBoolean, self));
+        }
+        Some(self.explain_value_failure(ctx))
+    }
+
+    /// Follows the first operand that fails down to the expression that
+    /// failed with operands that evaluated.
+    fn explain_value_failure(&self, ctx: &Value) -> (EvaluationFailure, &Expr) {
+        let logical = matches!(self, Expr::And { .. } | Expr::Or { .. } | Expr::Not { .. });
+        for child in self.children() {
+            if child.evaluate_value(ctx).is_none() {
+                return child.explain_value_failure(ctx);
+            }
+            if logical && child.evaluate_bool(ctx).is_none() {
+                return (EvaluationFailure::NotBoolean, child);
+            }
+        }
+        let reason = match self {
+            Expr::Var { .. } | Expr::Answer { .. } | Expr::Coalesce { .. } => {
+                EvaluationFailure::MissingValue
+            }
+            Expr::Matches { pattern, .. } if compile_pattern(pattern).is_err() => {
+                EvaluationFailure::InvalidPattern
+            }
+            _ => EvaluationFailure::TypeMismatch,
+        };
+        (reason, self)
+    }
+
+    /// Regex patterns of the [`Expr::Matches`] nodes, in first-use order.
+    pub fn patterns(&self) -> Vec<&str> {
+        let mut patterns = Vec::new();
+        let mut pending = vec![self];
+        while let Some(expr) = pending.pop() {
+            if let Expr::Matches { pattern, .. } = expr {
+                patterns.push(pattern.as_str());
+            }
+            let at = pending.len();
+            pending.extend(expr.children());
+            pending[at..].reverse();
+        }
+        patterns
+    }
+
+    /// Question ids this expression reads, in first-use order.
+    pub fn referenced_answers(&self) -> Vec<String> {
+        let mut refs = Vec::new();
+        let mut pending = vec![self];
+        while let Some(expr) = pending.pop() {
+            if let Expr::Var { path }
+            | Expr::Answer { path }
+            | Expr::IsSet { path }
+            | Expr::IsEmpty { path } = expr
+            {
+                let mut segments = path.split(['/', '.']).filter(|segment| !segment.is_empty());
+                let root = match segments.next() {
+                    Some("answers") => segments.next(),
+                    other => other,
+                };
+                if let Some(root) = root
+                    && !refs.iter().any(|existing| existing == root)
+                {
+                    refs.push(root.to_string());
+                }
+            }
+            let at = pending.len();
+            pending.extend(expr.children());
+            pending[at..].reverse();
+        }
+        refs
+    }
+
+    /// Nesting depth of the expression; a leaf has depth 1. Computed without
+    /// recursion so arbitrarily deep trees can be measured safely.
+    pub fn depth(&self) -> usize {
+        let mut deepest = 0;
+        let mut pending = vec![(self, 1)];
+        while let Some((expr, depth)) = pending.pop() {
+            deepest = deepest.max(depth);
+            pending.extend(expr.children().map(|child| (child, depth + 1)));
+        }
+        deepest
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Expr> {
+        let arms: &[CaseArm] = match self {
+            Expr::Case { arms, .. } => arms,
+            _ => &[],
+        };
+        let (list, [first, second]): (&[Expr], [Option<&Expr>; 2]) = match self {
+            Expr::Literal { .. }
+            | Expr::Var { .. }
+            | Expr::Answer { .. }
+            | Expr::IsSet { .. }
+            | Expr::IsEmpty { .. } => (&[], [None, None]),
+            Expr::And { expressions }
+            | Expr::Or { expressions }
+            | Expr::Concat { expressions }
+            | Expr::Coalesce { expressions } => (expressions, [None, None]),
+            Expr::Not { expression } => (&[], [Some(expression), None]),
+            Expr::Eq { left, right }
+            | Expr::Ne { left, right }
+            | Expr::Lt { left, right }
+            | Expr::Lte { left, right }
+            | Expr::Gt { left, right }
+            | Expr::Gte { left, right }
+            | Expr::Contains { left, right }
+            | Expr::StartsWith { left, right }
+            | Expr::EndsWith { left, right }
+            | Expr::Add { left, right }
+            | Expr::Sub { left, right }
+            | Expr::Mul { left, right }
+            | Expr::Div { left, right } => (&[], [Some(left), Some(right)]),
+            Expr::Matches { expression, .. } => (&[], [Some(expression), None]),
+            Expr::Length { of } => (&[], [Some(of), None]),
+            Expr::In { needle, haystack } => (&[], [Some(needle), Some(haystack)]),
+            Expr::Case { on, default, .. } => (&[], [Some(on), default.as_deref()]),
+        };
+        list.iter()
+            .chain(first)
+            .chain(arms.iter().map(|arm| &arm.then))
+            .chain(second)
+    }
+
+    fn evaluate_and(expressions: &[Expr], ctx: &Value, budget: usize) -> Option<Value> {
         let mut seen_none = false;
         for expression in expressions {
-            match expression.evaluate_bool(ctx) {
+            match expression.evaluate_bool_within(ctx, budget) {
                 Some(false) => return Some(Value::Bool(false)),
                 Some(true) => continue,
                 None => seen_none = true,
@@ -94,10 +539,10 @@ impl Expr {
         }
     }
 
-    fn evaluate_or(expressions: &[Expr], ctx: &Value) -> Option<Value> {
+    fn evaluate_or(expressions: &[Expr], ctx: &Value, budget: usize) -> Option<Value> {
         let mut seen_none = false;
         for expression in expressions {
-            match expression.evaluate_bool(ctx) {
+            match expression.evaluate_bool_within(ctx, budget) {
                 Some(true) => return Some(Value::Bool(true)),
                 Some(false) => continue,
                 None => seen_none = true,
@@ -110,12 +555,18 @@ impl Expr {
         }
     }
 
-    fn evaluate_compare<F>(left: &Expr, right: &Expr, ctx: &Value, predicate: F) -> Option<Value>
+    fn evaluate_compare<F>(
+        left: &Expr,
+        right: &Expr,
+        ctx: &Value,
+        budget: usize,
+        predicate: F,
+    ) -> Option<Value>
     where
         F: Fn(std::cmp::Ordering) -> bool,
     {
-        let left_value = left.evaluate_value(ctx)?;
-        let right_value = right.evaluate_value(ctx)?;
+        let left_value = left.evaluate_value_within(ctx, budget)?;
+        let right_value = right.evaluate_value_within(ctx, budget)?;
         let ordering = Self::compare_values(&left_value, &right_value)?;
         if predicate(ordering) {
             Some(Value::Bool(true))
@@ -124,6 +575,62 @@ impl Expr {
         }
     }
 
+    /// Applies `integer` when both operands are integers, falling back to
+    /// `float` when they are not or `integer` gives `None` (overflow, or a
+    /// division that is not exact).
+    fn evaluate_arithmetic(
+        left: &Expr,
+        right: &Expr,
+        ctx: &Value,
+        budget: usize,
+        integer: fn(i64, i64) -> Option<i64>,
+        float: fn(f64, f64) -> f64,
+    ) -> Option<Value> {
+        let left_value = left.evaluate_value_within(ctx, budget)?;
+        let right_value = right.evaluate_value_within(ctx, budget)?;
+        let (Value::Number(left_number), Value::Number(right_number)) = (&left_value, &right_value)
+        else {
+            return None;
+        };
+        if let (Some(left), Some(right)) = (left_number.as_i64(), right_number.as_i64())
+            && let Some(result) = integer(left, right)
+        {
+            return Some(Value::from(result));
+        }
+        let result = float(left_number.as_f64()?, right_number.as_f64()?);
+        serde_json::Number::from_f64(result).map(Value::Number)
+    }
+
+    fn evaluate_strings(
+        left: &Expr,
+        right: &Expr,
+        ctx: &Value,
+        budget: usize,
+        predicate: fn(&str, &str) -> bool,
+    ) -> Option<Value> {
+        let left_value = left.evaluate_value_within(ctx, budget)?;
+        let right_value = right.evaluate_value_within(ctx, budget)?;
+        Some(Value::Bool(predicate(
+            left_value.as_str()?,
+            right_value.as_str()?,
+        )))
+    }
+
+    /// Membership test behind [`Expr::In`]; `None` when `haystack` cannot
+    /// hold `needle` (a number in a string, anything in `null`).
+    fn contains(haystack: &Value, needle: &Value) -> Option<bool> {
+        match (haystack, needle) {
+            (Value::Array(items), _) => {
+                Some(items.iter().any(|item| {
+                    Self::compare_values(item, needle) == Some(std::cmp::Ordering::Equal)
+                }))
+            }
+            (Value::String(text), Value::String(part)) => Some(text.contains(part.as_str())),
+            (Value::Object(map), Value::String(key)) => Some(map.contains_key(key)),
+            _ => None,
+        }
+    }
+
     fn compare_values(left: &Value, right: &Value) -> Option<std::cmp::Ordering> {
         match (left, right) {
             (Value::Number(left), Value::Number(right)) => {
@@ -161,18 +668,27 @@ impl Expr {
         if path.starts_with('/') {
             return value.pointer(path);
         }
-        let mut current = value;
-        for segment in path.split('.') {
-            if segment.is_empty() {
-                continue;
-            }
-            current = if let Ok(index) = segment.parse::<usize>() {
-                current.get(index)?
-            } else {
-                current.get(segment)?
-            };
+        let segments = path
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        Self::fetch_segments(value, &segments)
+    }
+
+    /// Walks `segments`, also matching keys that contain dots themselves,
+    /// such as the `db.tls` answers of a form included with prefix `db`.
+    /// Longer keys are tried first.
+    fn fetch_segments<'a>(value: &'a Value, segments: &[&str]) -> Option<&'a Value> {
+        if segments.is_empty() {
+            return Some(value);
         }
-        Some(current)
+        (1..=segments.len()).rev().find_map(|taken| {
+            let next = match (taken, segments[0].parse::<usize>()) {
+                (1, Ok(index)) if value.is_array() => value.get(index),
+                _ => value.get(segments[..taken].join(".")),
+            }?;
+            Self::fetch_segments(next, &segments[taken..])
+        })
     }
 
     fn normalize_pointer(path: &str) -> String {
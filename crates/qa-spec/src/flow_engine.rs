@@ -0,0 +1,259 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::computed::{build_expression_context_with_ctx, materialize_computed};
+use crate::progress::{ProgressContext, should_skip};
+use crate::spec::flow::{CardMode, DecisionStep, OnLimit, QAFlowSpec, StepId, StepSpec};
+use crate::spec::form::FormSpec;
+use crate::template::{TemplateContext, TemplateEngine};
+use crate::visibility::{VisibilityMode, resolve_visibility_with_ctx};
+
+/// Result of advancing a flow by one observable step.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StepOutcome {
+    /// The flow waits for an answer to the given question.
+    AskQuestion { question_id: String },
+    /// A message step should be shown before continuing.
+    ShowMessage { text: String },
+    /// A decision step picked the next step.
+    Decided { next: StepId },
+    /// The flow reached an end step.
+    Completed,
+    /// The run exceeded a `FlowPolicy` limit and was stopped.
+    LimitReached {
+        limit: FlowLimit,
+        max: usize,
+        step: StepId,
+        on_limit: OnLimit,
+        history: Vec<StepId>,
+    },
+}
+
+/// Limit that stopped a flow run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowLimit {
+    MaxSteps,
+    MaxRepeatsPerStep,
+}
+
+/// Serializable position of a flow run so hosts can persist and resume it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FlowState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_step: Option<StepId>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub visits: BTreeMap<StepId, usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<StepId>,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// Errors raised while executing a flow.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FlowError {
+    #[error("unknown flow step '{0}'")]
+    UnknownStep(StepId),
+    #[error("flow step '{step}' references unknown question '{question_id}'")]
+    UnknownQuestion { step: StepId, question_id: String },
+    #[error("decision step '{0}' has no matching case and no default")]
+    NoMatchingCase(StepId),
+    #[error("flow cycle through answered steps starting at '{0}'")]
+    Cycle(StepId),
+    #[error("message step '{step}' failed to render: {message}")]
+    Template { step: StepId, message: String },
+}
+
+/// Pure executor for a `QAFlowSpec` bound to the form that owns its questions.
+#[derive(Debug, Clone)]
+pub struct FlowEngine<'a> {
+    flow: &'a QAFlowSpec,
+    form: &'a FormSpec,
+    state: FlowState,
+    visibility_mode: VisibilityMode,
+}
+
+impl<'a> FlowEngine<'a> {
+    /// Start a new run positioned at the flow entry.
+    pub fn new(flow: &'a QAFlowSpec, form: &'a FormSpec) -> Self {
+        let mut engine = Self {
+            flow,
+            form,
+            state: FlowState::default(),
+            visibility_mode: VisibilityMode::Visible,
+        };
+        engine.enter(Some(flow.entry.clone()));
+        engine
+    }
+
+    /// Resume a run from a previously persisted state.
+    pub fn resume(flow: &'a QAFlowSpec, form: &'a FormSpec, state: FlowState) -> Self {
+        Self {
+            flow,
+            form,
+            state,
+            visibility_mode: VisibilityMode::Visible,
+        }
+    }
+
+    /// How question steps whose `visible_if` cannot be evaluated are treated;
+    /// [`VisibilityMode::Visible`] by default, as in [`crate::next_question`]
+    /// callers.
+    pub fn with_visibility_mode(mut self, mode: VisibilityMode) -> Self {
+        self.visibility_mode = mode;
+        self
+    }
+
+    /// Step the run is currently positioned on.
+    pub fn current_step(&self) -> Option<&str> {
+        self.state.current_step.as_deref()
+    }
+
+    /// Card layout for the active step, falling back to the form presentation default.
+    pub fn card_mode(&self) -> CardMode {
+        match self
+            .current_step()
+            .and_then(|step| self.flow.steps.get(step))
+        {
+            Some(StepSpec::Question(question)) => {
+                question.card_mode.unwrap_or_else(|| self.form.card_mode())
+            }
+            _ => self.form.card_mode(),
+        }
+    }
+
+    /// Snapshot of the run state suitable for persistence.
+    pub fn state(&self) -> &FlowState {
+        &self.state
+    }
+
+    /// Consume the engine and return its state.
+    pub fn into_state(self) -> FlowState {
+        self.state
+    }
+
+    /// Advance the run until the next observable outcome.
+    ///
+    /// Question steps whose question is already answered, hidden, or skipped by
+    /// policy are passed through without producing an outcome. Visibility,
+    /// decisions and message templates see the answers plus the
+    /// `payload`/`state`/`config`/`env` roots in `ctx`.
+    pub fn advance(&mut self, answers: &Value, ctx: &Value) -> Result<StepOutcome, FlowError> {
+        let computed_answers = materialize_computed(self.form, answers, ctx);
+        let visibility =
+            resolve_visibility_with_ctx(self.form, &computed_answers, ctx, self.visibility_mode)
+                .map;
+        let progress_ctx = ProgressContext::new(&computed_answers, ctx);
+        let progress_policy = self.form.progress_policy.unwrap_or_default();
+        let expr_ctx = build_expression_context_with_ctx(&computed_answers, ctx);
+
+        let mut passed = BTreeSet::new();
+        loop {
+            let Some(step_id) = self.state.current_step.clone() else {
+                self.state.completed = true;
+                return Ok(StepOutcome::Completed);
+            };
+            if !passed.insert(step_id.clone()) {
+                return Err(FlowError::Cycle(step_id));
+            }
+            let step = self
+                .flow
+                .steps
+                .get(&step_id)
+                .ok_or_else(|| FlowError::UnknownStep(step_id.clone()))?;
+            if let Some(outcome) = self.check_limits(&step_id) {
+                return Ok(outcome);
+            }
+
+            match step {
+                StepSpec::Message(message) => {
+                    let text = TemplateEngine::new(self.form.template_mode())
+                        .resolve_string(
+                            &message.template,
+                            &TemplateContext::from_runtime(ctx, &computed_answers),
+                        )
+                        .map_err(|err| FlowError::Template {
+                            step: step_id.clone(),
+                            message: err.to_string(),
+                        })?;
+                    self.enter(message.next.clone());
+                    return Ok(StepOutcome::ShowMessage { text });
+                }
+                StepSpec::Question(question_step) => {
+                    let question = self
+                        .form
+                        .questions
+                        .iter()
+                        .find(|question| question.id == question_step.question_id)
+                        .ok_or_else(|| FlowError::UnknownQuestion {
+                            step: step_id.clone(),
+                            question_id: question_step.question_id.clone(),
+                        })?;
+                    let visible = visibility.get(&question.id).copied().unwrap_or(true);
+                    if visible && !should_skip(question, &progress_ctx, &progress_policy) {
+                        return Ok(StepOutcome::AskQuestion {
+                            question_id: question.id.clone(),
+                        });
+                    }
+                    self.enter(question_step.next.clone());
+                }
+                StepSpec::Decision(decision) => {
+                    let next = decide(&step_id, decision, &expr_ctx)?;
+                    self.enter(Some(next.clone()));
+                    return Ok(StepOutcome::Decided { next });
+                }
+                StepSpec::Action { .. } | StepSpec::End => {
+                    self.enter(None);
+                    self.state.completed = true;
+                    return Ok(StepOutcome::Completed);
+                }
+            }
+        }
+    }
+
+    fn enter(&mut self, step: Option<StepId>) {
+        if let Some(step_id) = &step {
+            *self.state.visits.entry(step_id.clone()).or_default() += 1;
+            self.state.history.push(step_id.clone());
+        }
+        self.state.current_step = step;
+    }
+
+    fn check_limits(&mut self, step_id: &str) -> Option<StepOutcome> {
+        let policy = self.flow.policies.as_ref()?;
+        let exceeded = if let Some(max) = policy.max_steps
+            && self.state.history.len() > max
+        {
+            Some((FlowLimit::MaxSteps, max))
+        } else if let Some(max) = policy.max_repeats_per_step
+            && self.state.visits.get(step_id).copied().unwrap_or_default() > max
+        {
+            Some((FlowLimit::MaxRepeatsPerStep, max))
+        } else {
+            None
+        };
+        let (limit, max) = exceeded?;
+        self.state.current_step = None;
+        self.state.completed = true;
+        Some(StepOutcome::LimitReached {
+            limit,
+            max,
+            step: step_id.to_string(),
+            on_limit: policy.on_limit,
+            history: self.state.history.clone(),
+        })
+    }
+}
+
+fn decide(step_id: &str, decision: &DecisionStep, ctx: &Value) -> Result<StepId, FlowError> {
+    decision
+        .select(ctx)
+        .cloned()
+        .ok_or_else(|| FlowError::NoMatchingCase(step_id.to_string()))
+}
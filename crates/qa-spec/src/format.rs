@@ -0,0 +1,102 @@
+//! Checks for the well-known string shapes named by
+//! [`StringFormat`](crate::spec::question::StringFormat).
+
+/// Part of a value that kept it from matching its format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatProblem {
+    /// The value does not have the expected shape at all.
+    Invalid,
+    /// A URL without a `scheme:` prefix, e.g. `example.com/path`.
+    MissingScheme,
+    /// A URL whose host is missing or malformed.
+    InvalidHost,
+}
+
+impl FormatProblem {
+    /// Stable label reported in validation error params.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FormatProblem::Invalid => "invalid",
+            FormatProblem::MissingScheme => "missing_scheme",
+            FormatProblem::InvalidHost => "invalid_host",
+        }
+    }
+}
+
+/// Checks that `text` is an absolute URL with a host.
+///
+/// Text that parses as a scheme plus an opaque path (`localhost:8080`,
+/// `mailto:ops@example.com`) is reported as [`FormatProblem::InvalidHost`].
+pub fn check_url(text: &str) -> Result<(), FormatProblem> {
+    use url::ParseError;
+
+    match url::Url::parse(text) {
+        Ok(url) if url.host_str().is_some_and(|host| !host.is_empty()) => Ok(()),
+        Ok(_) => Err(FormatProblem::InvalidHost),
+        Err(ParseError::RelativeUrlWithoutBase) => Err(FormatProblem::MissingScheme),
+        Err(
+            ParseError::EmptyHost
+            | ParseError::IdnaError
+            | ParseError::InvalidPort
+            | ParseError::InvalidIpv4Address
+            | ParseError::InvalidIpv6Address
+            | ParseError::InvalidDomainCharacter,
+        ) => Err(FormatProblem::InvalidHost),
+        Err(_) => Err(FormatProblem::Invalid),
+    }
+}
+
+/// Whether `text` is a UUID in the hyphenated form JSON Schema's `uuid`
+/// format expects. Any version or variant is accepted; the simple, braced,
+/// and URN spellings the `uuid` crate also understands are not.
+pub fn is_uuid(text: &str) -> bool {
+    text.len() == 36 && uuid::Uuid::try_parse(text).is_ok()
+}
+
+/// Whether `text` is an E.164 phone number: `+`, a country code that does
+/// not start with 0, and 8 to 15 digits in total. Spaces, dashes, and
+/// brackets are not accepted.
+pub fn is_e164(text: &str) -> bool {
+    text.strip_prefix('+').is_some_and(|digits| {
+        (8..=15).contains(&digits.len())
+            && !digits.starts_with('0')
+            && digits.bytes().all(|byte| byte.is_ascii_digit())
+    })
+}
+
+/// Whether `text` looks like a deliverable email address: a dot-atom local
+/// part, a single `@`, and a dotted domain of letter/digit/hyphen labels.
+/// Quoted local parts and IP-literal domains are not accepted.
+pub fn is_email(text: &str) -> bool {
+    let Some((local, domain)) = text.split_once('@') else {
+        return false;
+    };
+    is_local_part(local) && is_domain(domain)
+}
+
+fn is_local_part(local: &str) -> bool {
+    local.len() <= 64
+        && local.split('.').all(|atom| {
+            !atom.is_empty()
+                && atom.bytes().all(|byte| {
+                    byte.is_ascii_alphanumeric() || b"!#$%&'*+/=?^_`{|}~-".contains(&byte)
+                })
+        })
+}
+
+fn is_domain(domain: &str) -> bool {
+    let labels = domain.split('.').collect::<Vec<_>>();
+    domain.len() <= 253
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            (1..=63).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+        })
+        && labels
+            .last()
+            .is_some_and(|tld| tld.len() >= 2 && tld.bytes().all(|byte| byte.is_ascii_alphabetic()))
+}
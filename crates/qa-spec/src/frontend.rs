@@ -1,12 +1,16 @@
 use serde_json::Value;
 
-use crate::{RenderPayload, render_card, render_json_ui, render_text};
+use crate::{RenderPayload, render_card, render_json_ui, render_markdown, render_text};
 
 /// Abstraction over UI frontends that render the same payload into different transports.
 pub trait QaFrontend {
     fn render_text_ui(&self, payload: &RenderPayload) -> String;
     fn render_json_ui(&self, payload: &RenderPayload) -> Value;
     fn render_adaptive_card(&self, payload: &RenderPayload) -> Value;
+
+    fn render_markdown_ui(&self, payload: &RenderPayload) -> String {
+        render_markdown(payload)
+    }
 }
 
 /// Default frontend implementation that reuses existing renderer functions.
@@ -0,0 +1,708 @@
+//! proptest strategies for specs and answers (feature `proptest`).
+//!
+//! [`form_spec`] builds random but well-formed forms whose size is bounded by
+//! [`SpecSizes`]. [`answers_for`] produces answers that pass validation for a
+//! given spec and [`malformed_answers_for`] answers that carry one deliberate
+//! defect, for fuzzing hosts and property-testing invariants.
+//!
+//! Generated specs use string, boolean, integer, number, enum, string list,
+//! and list questions with constraints and `visible_if` conditions. They leave out
+//! computed fields, normalization, defaults, and cross-field validations so
+//! that conforming answers can be generated for them.
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+use serde_json::{Map, Number, Value, json};
+
+use crate::expr::{CaseArm, Expr};
+use crate::spec::form::FormSpec;
+use crate::spec::question::{
+    Constraint, ListSpec, ObjectSpec, QuestionPolicy, QuestionSpec, QuestionType, StringFormat,
+    rating_range,
+};
+use crate::time_of_day::format_time_of_day;
+
+/// Constraint patterns used by generated specs, each paired with an
+/// unanchored regex that generates matching answers.
+pub const PATTERNS: &[(&str, &str)] = &[
+    ("^[a-z]+$", "[a-z]{1,8}"),
+    ("^[A-Z]{2,4}-[0-9]{1,3}$", "[A-Z]{2,4}-[0-9]{1,3}"),
+    ("^[a-z0-9-]{1,12}$", "[a-z0-9-]{1,12}"),
+    ("^(dev|staging|prod)$", "(dev|staging|prod)"),
+];
+
+/// Size limits for generated specs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecSizes {
+    pub max_questions: usize,
+    /// Fields of a generated list question.
+    pub max_list_fields: usize,
+    pub max_choices: usize,
+    /// Nesting depth of `visible_if` expressions; `0` disables them.
+    pub max_expr_depth: u32,
+}
+
+impl Default for SpecSizes {
+    fn default() -> Self {
+        Self {
+            max_questions: 8,
+            max_list_fields: 3,
+            max_choices: 4,
+            max_expr_depth: 3,
+        }
+    }
+}
+
+/// Any question type; `List` and `Object` only when `allow_list` is set.
+pub fn question_type(allow_list: bool) -> BoxedStrategy<QuestionType> {
+    let scalar = prop_oneof![
+        Just(QuestionType::String),
+        Just(QuestionType::Boolean),
+        Just(QuestionType::Integer),
+        Just(QuestionType::Number),
+        Just(QuestionType::Enum),
+        Just(QuestionType::Date),
+        Just(QuestionType::MultiEnum),
+        Just(QuestionType::Path),
+        Just(QuestionType::Duration),
+        Just(QuestionType::StringList),
+        Just(QuestionType::Rating),
+        Just(QuestionType::Color),
+        Just(QuestionType::Markdown),
+        Just(QuestionType::Time),
+    ];
+    if allow_list {
+        prop_oneof![
+            8 => scalar,
+            1 => Just(QuestionType::List),
+            1 => Just(QuestionType::Object),
+        ]
+        .boxed()
+    } else {
+        scalar.boxed()
+    }
+}
+
+/// Constraint that answers of `kind` can satisfy, or none.
+pub fn constraint(kind: QuestionType) -> BoxedStrategy<Option<Constraint>> {
+    let empty = Constraint::default();
+    match kind {
+        QuestionType::String => {
+            let pattern_empty = empty.clone();
+            let pattern = select(PATTERNS).prop_map(move |(pattern, _)| Constraint {
+                pattern: Some(pattern.to_string()),
+                ..pattern_empty.clone()
+            });
+            let format_empty = empty.clone();
+            let format = select(vec![
+                StringFormat::Email,
+                StringFormat::Url,
+                StringFormat::Uuid,
+                StringFormat::Phone,
+            ])
+            .prop_map(move |format| Constraint {
+                format: Some(format),
+                ..format_empty.clone()
+            });
+            let length = (0usize..4, 0usize..8).prop_map(move |(min_len, extra)| Constraint {
+                min_len: Some(min_len),
+                max_len: Some(min_len + extra + 1),
+                ..empty.clone()
+            });
+            prop_oneof![
+                2 => Just(None),
+                1 => pattern.prop_map(Some),
+                1 => length.prop_map(Some),
+                1 => format.prop_map(Some),
+            ]
+            .boxed()
+        }
+        QuestionType::Integer | QuestionType::Number => {
+            let bounds = (
+                -100i64..100,
+                2i64..50,
+                any::<bool>(),
+                any::<bool>(),
+                any::<bool>(),
+            )
+                .prop_map(move |(min, span, has_max, exclusive_min, exclusive_max)| {
+                    let max = has_max.then(|| (min + span) as f64);
+                    Constraint {
+                        min: Some(min as f64),
+                        max,
+                        exclusive_min,
+                        exclusive_max: exclusive_max && max.is_some(),
+                        ..empty.clone()
+                    }
+                });
+            prop_oneof![Just(None), bounds.prop_map(Some)].boxed()
+        }
+        QuestionType::Rating => {
+            let scale = (0i64..2, 2i64..10).prop_map(move |(min, span)| Constraint {
+                min: Some(min as f64),
+                max: Some((min + span) as f64),
+                ..empty.clone()
+            });
+            prop_oneof![Just(None), scale.prop_map(Some)].boxed()
+        }
+        QuestionType::Time => {
+            // Whole hours, so every bound has a time strictly inside it.
+            let window = (0u32..12, 1u32..12, any::<bool>(), any::<bool>()).prop_map(
+                move |(start, hours, exclusive_min, exclusive_max)| Constraint {
+                    min: Some(f64::from(start * 3600)),
+                    max: Some(f64::from((start + hours) * 3600)),
+                    exclusive_min,
+                    exclusive_max,
+                    ..empty.clone()
+                },
+            );
+            prop_oneof![Just(None), window.prop_map(Some)].boxed()
+        }
+        QuestionType::Markdown => {
+            let length = (0usize..4, 0usize..16).prop_map(move |(min_len, extra)| Constraint {
+                min_len: Some(min_len),
+                max_len: Some(min_len + extra + 1),
+                ..empty.clone()
+            });
+            prop_oneof![Just(None), length.prop_map(Some)].boxed()
+        }
+        QuestionType::MultiEnum => {
+            // Generated multi_enum questions always offer at least one choice.
+            let counts = (0usize..=1, proptest::option::of(1usize..4)).prop_map(
+                move |(min_selected, max_selected)| Constraint {
+                    min_selected: Some(min_selected),
+                    max_selected,
+                    ..empty.clone()
+                },
+            );
+            prop_oneof![Just(None), counts.prop_map(Some)].boxed()
+        }
+        _ => Just(None).boxed(),
+    }
+}
+
+/// Expression reading the answers in `ids`, nested up to `depth` levels.
+pub fn expr(ids: Vec<String>, depth: u32) -> BoxedStrategy<Expr> {
+    let literal = prop_oneof![
+        any::<bool>().prop_map(Value::Bool),
+        (-10i64..10).prop_map(Value::from),
+        "[a-z]{0,4}".prop_map(Value::String),
+    ]
+    .prop_map(|value| Expr::Literal { value });
+    let leaf = if ids.is_empty() {
+        literal.boxed()
+    } else {
+        let answer = select(ids.clone()).prop_map(|path| Expr::Answer { path });
+        let is_set = select(ids.clone()).prop_map(|path| Expr::IsSet { path });
+        let is_empty = select(ids).prop_map(|path| Expr::IsEmpty { path });
+        prop_oneof![literal, answer, is_set, is_empty].boxed()
+    };
+    leaf.prop_recursive(depth, 24, 3, |inner| {
+        let pair = (inner.clone(), inner.clone());
+        prop_oneof![
+            vec(inner.clone(), 1..3).prop_map(|expressions| Expr::And { expressions }),
+            vec(inner.clone(), 1..3).prop_map(|expressions| Expr::Or { expressions }),
+            vec(inner.clone(), 1..3).prop_map(|expressions| Expr::Concat { expressions }),
+            vec(inner.clone(), 1..3).prop_map(|expressions| Expr::Coalesce { expressions }),
+            inner.clone().prop_map(|expression| Expr::Not {
+                expression: Box::new(expression)
+            }),
+            inner
+                .clone()
+                .prop_map(|of| Expr::Length { of: Box::new(of) }),
+            (
+                inner.clone(),
+                vec(inner.clone(), 1..3),
+                proptest::option::of(inner.clone())
+            )
+                .prop_map(|(on, thens, default)| Expr::Case {
+                    on: Box::new(on),
+                    arms: thens
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, then)| CaseArm {
+                            when: Value::from(index),
+                            then,
+                        })
+                        .collect(),
+                    default: default.map(Box::new),
+                }),
+            (inner, select(vec!["^[a-c]", "[0-9]$"])).prop_map(|(expression, pattern)| {
+                Expr::Matches {
+                    expression: Box::new(expression),
+                    pattern: pattern.to_string(),
+                }
+            }),
+            (0u8..14, pair).prop_map(|(op, (left, right))| {
+                let (left, right) = (Box::new(left), Box::new(right));
+                match op {
+                    0 => Expr::Eq { left, right },
+                    1 => Expr::Ne { left, right },
+                    2 => Expr::Lt { left, right },
+                    3 => Expr::Lte { left, right },
+                    4 => Expr::Gt { left, right },
+                    5 => Expr::Gte { left, right },
+                    6 => Expr::In {
+                        needle: left,
+                        haystack: right,
+                    },
+                    7 => Expr::Contains { left, right },
+                    8 => Expr::StartsWith { left, right },
+                    9 => Expr::EndsWith { left, right },
+                    10 => Expr::Add { left, right },
+                    11 => Expr::Sub { left, right },
+                    12 => Expr::Mul { left, right },
+                    _ => Expr::Div { left, right },
+                }
+            }),
+        ]
+    })
+    .boxed()
+}
+
+/// List definition whose fields are scalar questions without conditions.
+pub fn list_spec(sizes: SpecSizes) -> BoxedStrategy<ListSpec> {
+    let fields = (1..=sizes.max_list_fields.max(1)).prop_flat_map(move |count| {
+        (0..count)
+            .map(|index| question_spec(format!("f{index}"), Vec::new(), sizes, false))
+            .collect::<Vec<_>>()
+    });
+    (0usize..3, proptest::option::of(0usize..3), fields)
+        .prop_map(|(min_items, extra, fields)| ListSpec {
+            min_items: (min_items > 0).then_some(min_items),
+            max_items: extra.map(|extra| min_items + extra + 1),
+            unique: false,
+            unique_by: None,
+            fields,
+        })
+        .boxed()
+}
+
+/// Item bounds for a `string_list` question; it has no fields.
+pub fn string_list_spec() -> BoxedStrategy<ListSpec> {
+    (0usize..3, proptest::option::of(0usize..3), any::<bool>())
+        .prop_map(|(min_items, extra, unique)| ListSpec {
+            min_items: (min_items > 0).then_some(min_items),
+            max_items: extra.map(|extra| min_items + extra + 1),
+            unique,
+            unique_by: None,
+            fields: Vec::new(),
+        })
+        .boxed()
+}
+
+/// Object definition whose fields are scalar questions without conditions.
+pub fn object_spec(sizes: SpecSizes) -> BoxedStrategy<ObjectSpec> {
+    (1..=sizes.max_list_fields.max(1))
+        .prop_flat_map(move |count| {
+            (0..count)
+                .map(|index| question_spec(format!("f{index}"), Vec::new(), sizes, false))
+                .collect::<Vec<_>>()
+        })
+        .prop_map(|fields| ObjectSpec { fields })
+        .boxed()
+}
+
+/// Question `id` whose `visible_if` (if any) reads answers in `ids`.
+pub fn question_spec(
+    id: String,
+    ids: Vec<String>,
+    sizes: SpecSizes,
+    allow_list: bool,
+) -> BoxedStrategy<QuestionSpec> {
+    question_type(allow_list)
+        .prop_flat_map(move |kind| {
+            let choices = match kind {
+                QuestionType::Enum | QuestionType::MultiEnum => (1..=sizes.max_choices.max(1))
+                    .prop_map(|count| {
+                        Some((0..count).map(|index| format!("choice-{index}")).collect())
+                    })
+                    .boxed(),
+                _ => Just(None).boxed(),
+            };
+            let list = match kind {
+                QuestionType::List => list_spec(sizes).prop_map(Some).boxed(),
+                QuestionType::StringList => string_list_spec().prop_map(Some).boxed(),
+                _ => Just(None).boxed(),
+            };
+            let object = match kind {
+                QuestionType::Object => object_spec(sizes).prop_map(Some).boxed(),
+                _ => Just(None).boxed(),
+            };
+            let visible_if = if ids.is_empty() || sizes.max_expr_depth == 0 {
+                Just(None).boxed()
+            } else {
+                prop_oneof![
+                    3 => Just(None),
+                    1 => expr(ids.clone(), sizes.max_expr_depth).prop_map(Some),
+                ]
+                .boxed()
+            };
+            (
+                Just(kind),
+                any::<bool>(),
+                any::<bool>(),
+                constraint(kind),
+                choices,
+                list,
+                object,
+                visible_if,
+            )
+        })
+        .prop_map(
+            move |(kind, required, secret, constraint, choices, list, object, visible_if)| {
+                QuestionSpec {
+                    id: id.clone(),
+                    kind,
+                    title: format!("Question {id}"),
+                    title_i18n: None,
+                    description: None,
+                    description_i18n: None,
+                    required,
+                    required_if: None,
+                    read_only_if: None,
+                    order: None,
+                    choices,
+                    choices_catalog: None,
+                    default_value: None,
+                    secret,
+                    secret_path: None,
+                    normalize: Vec::new(),
+                    visible_if,
+                    constraint,
+                    list,
+                    object,
+                    computed: None,
+                    policy: QuestionPolicy::default(),
+                    computed_overridable: false,
+                    i18n: None,
+                }
+            },
+        )
+        .boxed()
+}
+
+/// Form with up to `sizes.max_questions` questions `q0`, `q1`, ...; each
+/// `visible_if` reads earlier questions.
+pub fn form_spec(sizes: SpecSizes) -> BoxedStrategy<FormSpec> {
+    (1..=sizes.max_questions.max(1))
+        .prop_flat_map(move |count| {
+            (0..count)
+                .map(|index| {
+                    let earlier = (0..index).map(|earlier| format!("q{earlier}")).collect();
+                    question_spec(format!("q{index}"), earlier, sizes, true)
+                })
+                .collect::<Vec<_>>()
+        })
+        .prop_map(|questions| FormSpec {
+            id: "generated".into(),
+            title: "Generated".into(),
+            version: "1.0.0".into(),
+            description: None,
+            presentation: None,
+            progress_policy: None,
+            secrets_policy: None,
+            store: Vec::new(),
+            validations: Vec::new(),
+            includes: Vec::new(),
+            migrations: Vec::new(),
+            max_answer_bytes: None,
+            max_total_bytes: None,
+            i18n: None,
+            questions,
+        })
+        .boxed()
+}
+
+/// Answers that pass [`crate::validate`] for a spec built by [`form_spec`].
+///
+/// Required questions are always answered and optional ones sometimes, hidden
+/// or not.
+pub fn answers_for(spec: &FormSpec) -> BoxedStrategy<Value> {
+    entries(&spec.questions)
+        .prop_map(|entries| Value::Object(entries.into_iter().flatten().collect()))
+        .boxed()
+}
+
+fn entries(questions: &[QuestionSpec]) -> Vec<BoxedStrategy<Option<(String, Value)>>> {
+    questions
+        .iter()
+        .map(|question| {
+            let id = question.id.clone();
+            let value = value_for(question).prop_map(move |value| (id.clone(), value));
+            if question.required {
+                value.prop_map(Some).boxed()
+            } else {
+                proptest::option::of(value).boxed()
+            }
+        })
+        .collect()
+}
+
+/// Value of `question` satisfying its type, choices, constraint, and list rules.
+pub fn value_for(question: &QuestionSpec) -> BoxedStrategy<Value> {
+    let constraint = question.constraint.clone().unwrap_or_default();
+    match question.kind {
+        QuestionType::String => {
+            let generator = constraint.pattern.as_deref().and_then(|pattern| {
+                PATTERNS
+                    .iter()
+                    .find(|(known, _)| *known == pattern)
+                    .map(|(_, generator)| *generator)
+            });
+            let format = constraint.format.map(|format| match format {
+                StringFormat::Email => {
+                    "[a-z0-9]{1,6}(\\.[a-z0-9-]{0,3}[a-z0-9])?@[a-z]{1,8}\\.[a-z]{2,4}"
+                }
+                StringFormat::Url => "https?://[a-z]{1,8}\\.[a-z]{2,4}(/[a-z0-9-]{1,6}){0,2}",
+                StringFormat::Uuid => {
+                    "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}"
+                }
+                StringFormat::Phone => "\\+[1-9][0-9]{7,14}",
+            });
+            if let Some(generator) = format {
+                return proptest::string::string_regex(generator)
+                    .expect("format regexes are valid")
+                    .prop_map(Value::String)
+                    .boxed();
+            }
+            match generator {
+                Some(generator) => proptest::string::string_regex(generator)
+                    .expect("generator regexes are valid")
+                    .prop_map(Value::String)
+                    .boxed(),
+                None => {
+                    let min = constraint.min_len.unwrap_or(0);
+                    let max = constraint.max_len.unwrap_or(12).max(min);
+                    proptest::string::string_regex(&format!("[a-z ]{{{min},{max}}}"))
+                        .expect("length regex is valid")
+                        .prop_map(Value::String)
+                        .boxed()
+                }
+            }
+        }
+        QuestionType::Boolean => any::<bool>().prop_map(Value::Bool).boxed(),
+        QuestionType::Date => (1970u32..2100, 1u32..=12, 1u32..=28)
+            .prop_map(|(year, month, day)| {
+                Value::String(format!("{:04}-{:02}-{:02}", year, month, day))
+            })
+            .boxed(),
+        QuestionType::Integer => {
+            let (low, high) = integer_range(&constraint);
+            (low..=high).prop_map(Value::from).boxed()
+        }
+        QuestionType::Rating => rating_range(question.constraint.as_ref())
+            .prop_map(Value::from)
+            .boxed(),
+        QuestionType::Duration => {
+            let (low, high) = match (constraint.min, constraint.max) {
+                (None, None) => (0, 86_400),
+                _ => integer_range(&constraint),
+            };
+            (low.max(0)..=high.max(0)).prop_map(Value::from).boxed()
+        }
+        QuestionType::Number => {
+            let (low, high) = integer_range(&constraint);
+            (low..=high, 0u8..4)
+                .prop_map(move |(whole, quarter)| {
+                    let value = whole as f64 + f64::from(quarter) * 0.25;
+                    let value = if value > high as f64 {
+                        whole as f64
+                    } else {
+                        value
+                    };
+                    Value::Number(Number::from_f64(value).unwrap_or_else(|| Number::from(whole)))
+                })
+                .boxed()
+        }
+        QuestionType::Enum => {
+            let choices = question.choices.clone().unwrap_or_default();
+            if choices.is_empty() {
+                Just(Value::String(String::new())).boxed()
+            } else {
+                select(choices).prop_map(Value::String).boxed()
+            }
+        }
+        QuestionType::Path => "[a-z]{1,8}(/[a-z0-9._-]{1,8}){0,3}"
+            .prop_map(Value::String)
+            .boxed(),
+        QuestionType::Color => "#[0-9a-f]{6}([0-9a-f]{2})?".prop_map(Value::String).boxed(),
+        QuestionType::Time => {
+            let (low, high) = match (constraint.min, constraint.max) {
+                (None, None) => (0, 86_399),
+                _ => integer_range(&constraint),
+            };
+            (low as u32..=high as u32)
+                .prop_map(|seconds| Value::String(format_time_of_day(seconds)))
+                .boxed()
+        }
+        QuestionType::Markdown => {
+            let min = constraint.min_len.unwrap_or(0);
+            let max = constraint.max_len.unwrap_or(24).max(min);
+            proptest::string::string_regex(&format!("[a-z#*_\\n ]{{{min},{max}}}"))
+                .expect("length regex is valid")
+                .prop_map(Value::String)
+                .boxed()
+        }
+        QuestionType::MultiEnum => {
+            let choices = question.choices.clone().unwrap_or_default();
+            let min = constraint.min_selected.unwrap_or(0).min(choices.len());
+            let max = constraint
+                .max_selected
+                .unwrap_or(choices.len())
+                .clamp(min, choices.len());
+            proptest::sample::subsequence(choices, min..=max)
+                .prop_map(|selected| selected.into_iter().map(Value::String).collect())
+                .prop_map(Value::Array)
+                .boxed()
+        }
+        QuestionType::StringList => {
+            let list = question.list.clone().unwrap_or_default();
+            let min = list.min_items.unwrap_or(0);
+            let max = list.max_items.unwrap_or(min + 3).max(min);
+            // Distinct items satisfy `unique` whether or not it is set.
+            proptest::collection::btree_set("[a-z]{1,8}", min..=max)
+                .prop_map(|items| Value::Array(items.into_iter().map(Value::String).collect()))
+                .boxed()
+        }
+        QuestionType::Object => {
+            let fields = question
+                .object
+                .as_ref()
+                .map(|object| object.fields.clone())
+                .unwrap_or_default();
+            entries(&fields)
+                .prop_map(|entries| Value::Object(entries.into_iter().flatten().collect()))
+                .boxed()
+        }
+        QuestionType::List => {
+            let list = question.list.clone().unwrap_or_default();
+            let min = list.min_items.unwrap_or(0);
+            let max = list.max_items.unwrap_or(min + 3).max(min);
+            let fields = list.fields.clone();
+            vec(
+                entries(&fields)
+                    .prop_map(|entries| Value::Object(entries.into_iter().flatten().collect())),
+                min..=max,
+            )
+            .prop_map(Value::Array)
+            .boxed()
+        }
+    }
+}
+
+/// Inclusive integer range allowed by `constraint`.
+fn integer_range(constraint: &Constraint) -> (i64, i64) {
+    let low = constraint.min.map_or(-1_000, |min| {
+        let floor = min.ceil() as i64;
+        if constraint.exclusive_min && floor as f64 <= min {
+            floor + 1
+        } else {
+            floor
+        }
+    });
+    let high = constraint.max.map_or(low + 1_000, |max| {
+        let ceiling = max.floor() as i64;
+        if constraint.exclusive_max && ceiling as f64 >= max {
+            ceiling - 1
+        } else {
+            ceiling
+        }
+    });
+    (low, high.max(low))
+}
+
+/// Defect planted by [`malformed_answers_for`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Defect {
+    /// An answer for a question id the spec does not define.
+    UnknownField(String),
+    /// A value of the wrong JSON type for the question.
+    WrongType(String),
+    /// The answers document is not a JSON object.
+    NotAnObject,
+}
+
+/// Conforming answers with exactly one [`Defect`] planted, alongside the defect.
+pub fn malformed_answers_for(spec: &FormSpec) -> BoxedStrategy<(Value, Defect)> {
+    let questions = spec
+        .questions
+        .iter()
+        .map(|question| (question.id.clone(), question.kind))
+        .collect::<Vec<_>>();
+    let wrong_type = if questions.is_empty() {
+        Just(None).boxed()
+    } else {
+        select(questions).prop_map(Some).boxed()
+    };
+    (answers_for(spec), 0u8..3, wrong_type, "[a-z]{1,6}")
+        .prop_map(|(answers, defect, target, suffix)| {
+            let mut map = answers.as_object().cloned().unwrap_or_default();
+            match (defect, target) {
+                (0, _) | (_, None) => {
+                    let id = format!("unknown-{suffix}");
+                    map.insert(id.clone(), json!(suffix));
+                    (Value::Object(map), Defect::UnknownField(id))
+                }
+                (1, Some((id, kind))) => {
+                    map.insert(id.clone(), wrong_value(kind));
+                    (Value::Object(map), Defect::WrongType(id))
+                }
+                _ => (
+                    Value::Array(map.into_values().collect()),
+                    Defect::NotAnObject,
+                ),
+            }
+        })
+        .boxed()
+}
+
+fn wrong_value(kind: QuestionType) -> Value {
+    match kind {
+        QuestionType::String | QuestionType::Enum => json!(42),
+        QuestionType::Boolean => json!("yes"),
+        QuestionType::Integer => json!(1.5),
+        QuestionType::Number => json!("1"),
+        QuestionType::List => Value::Object(Map::new()),
+        QuestionType::Date => json!("2026-02-30"),
+        QuestionType::MultiEnum => json!("choice-0"),
+        QuestionType::Path => json!(42),
+        QuestionType::Duration => json!("5m"),
+        QuestionType::Object => json!([]),
+        QuestionType::StringList => json!("a, b"),
+        QuestionType::Rating => json!("5"),
+        QuestionType::Color => json!("blue"),
+        QuestionType::Markdown => json!(["# Notes"]),
+        QuestionType::Time => json!("25:00"),
+    }
+}
+
+impl Arbitrary for FormSpec {
+    type Parameters = SpecSizes;
+    type Strategy = BoxedStrategy<FormSpec>;
+
+    fn arbitrary_with(sizes: SpecSizes) -> Self::Strategy {
+        form_spec(sizes)
+    }
+}
+
+impl Arbitrary for QuestionType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<QuestionType>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        question_type(true)
+    }
+}
+
+impl Arbitrary for Expr {
+    /// Question ids the expression may read and the nesting depth.
+    type Parameters = (Vec<String>, u32);
+    type Strategy = BoxedStrategy<Expr>;
+
+    fn arbitrary_with((ids, depth): (Vec<String>, u32)) -> Self::Strategy {
+        expr(ids, depth)
+    }
+}
@@ -76,3 +76,147 @@ fn interpolate_args(template: &str, args: Option<&BTreeMap<String, Value>>) -> S
     }
     output
 }
+
+/// Display strings for one locale, given inline in a spec under `i18n`.
+///
+/// Missing entries fall back to the spec's own strings. `help` replaces the
+/// form's intro; `choices` maps choice values to the labels shown for them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LocaleStrings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub choices: BTreeMap<String, String>,
+}
+
+/// The entry of `strings` for `locale`, else for its language (`id` for
+/// `id-ID` or `id_ID`).
+pub fn locale_strings<'a>(
+    strings: Option<&'a BTreeMap<String, LocaleStrings>>,
+    locale: Option<&str>,
+) -> Option<&'a LocaleStrings> {
+    let (strings, locale) = (strings?, locale?);
+    strings.get(locale).or_else(|| {
+        let language = locale.split(['-', '_']).next()?;
+        strings.get(language)
+    })
+}
+
+/// Fixed words the renderers put around the spec's own strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderLabels {
+    pub form: &'static str,
+    pub status: &'static str,
+    pub help: &'static str,
+    pub next_question: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub required: &'static str,
+    /// Lowercase tag after required questions in lists.
+    pub required_tag: &'static str,
+    pub yes: &'static str,
+    pub default: &'static str,
+    pub current_value: &'static str,
+    pub visible_questions: &'static str,
+    pub all_answered: &'static str,
+    pub answered: &'static str,
+    pub total: &'static str,
+    pub complete: &'static str,
+    pub progress: &'static str,
+    /// `{answered}` and `{total}` are filled in.
+    pub answered_of_total: &'static str,
+    pub question: &'static str,
+    pub kind: &'static str,
+    pub value: &'static str,
+    pub choices: &'static str,
+    /// `{page}` and `{pages}` are filled in.
+    pub page_of_pages: &'static str,
+    pub next_action: &'static str,
+    pub previous_action: &'static str,
+    pub submit_action: &'static str,
+    pub review: &'static str,
+    pub missing: &'static str,
+    pub invalid: &'static str,
+    /// `{count}` is filled in.
+    pub item_count: &'static str,
+}
+
+impl RenderLabels {
+    pub const EN: RenderLabels = RenderLabels {
+        form: "Form",
+        status: "Status",
+        help: "Help",
+        next_question: "Next question",
+        title: "Title",
+        description: "Description",
+        required: "Required",
+        required_tag: "required",
+        yes: "yes",
+        default: "Default",
+        current_value: "Current value",
+        visible_questions: "Visible questions",
+        all_answered: "All visible questions are answered.",
+        answered: "Answered",
+        total: "Total",
+        complete: "Complete",
+        progress: "Progress",
+        answered_of_total: "{answered} of {total} answered",
+        question: "Question",
+        kind: "Type",
+        value: "Value",
+        choices: "Choices",
+        page_of_pages: "Page {page} of {pages}",
+        next_action: "Next ➡️",
+        previous_action: "⬅️ Previous",
+        submit_action: "Submit ✅",
+        review: "Review",
+        missing: "missing",
+        invalid: "invalid",
+        item_count: "{count} item(s)",
+    };
+
+    pub const ID: RenderLabels = RenderLabels {
+        form: "Formulir",
+        status: "Status",
+        help: "Bantuan",
+        next_question: "Pertanyaan berikutnya",
+        title: "Judul",
+        description: "Deskripsi",
+        required: "Wajib",
+        required_tag: "wajib",
+        yes: "ya",
+        default: "Bawaan",
+        current_value: "Nilai saat ini",
+        visible_questions: "Pertanyaan yang terlihat",
+        all_answered: "Semua pertanyaan yang terlihat sudah dijawab.",
+        answered: "Terjawab",
+        total: "Total",
+        complete: "Selesai",
+        progress: "Kemajuan",
+        answered_of_total: "{answered} dari {total} terjawab",
+        question: "Pertanyaan",
+        kind: "Tipe",
+        value: "Nilai",
+        choices: "Pilihan",
+        page_of_pages: "Halaman {page} dari {pages}",
+        next_action: "Lanjut ➡️",
+        previous_action: "⬅️ Sebelumnya",
+        submit_action: "Kirim ✅",
+        review: "Tinjauan",
+        missing: "belum diisi",
+        invalid: "tidak valid",
+        item_count: "{count} item",
+    };
+
+    /// Labels for `locale`'s language; English when there is no table for it.
+    pub fn for_locale(locale: Option<&str>) -> &'static RenderLabels {
+        match locale.and_then(|locale| locale.split(['-', '_']).next()) {
+            Some("id") => &Self::ID,
+            _ => &Self::EN,
+        }
+    }
+}
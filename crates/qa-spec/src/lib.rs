@@ -2,47 +2,126 @@
 
 pub mod answers;
 pub mod answers_schema;
+pub mod catalog;
+pub mod clock;
+pub mod coerce;
+pub mod color;
+pub mod compiled;
 pub mod compose;
 pub mod computed;
 pub mod convert;
+pub mod date;
+pub mod defaults;
+pub mod diff;
+pub mod duration;
 pub mod examples;
 pub mod expr;
+pub mod flow_engine;
+pub mod format;
 pub mod frontend;
+#[cfg(feature = "proptest")]
+pub mod generators;
 pub mod i18n;
+pub mod limits;
+pub mod lint;
+pub mod merge;
+pub mod messages;
+pub mod migrate;
+pub mod normalize;
+pub mod pattern;
 pub mod progress;
+pub mod redact;
 pub mod render;
 pub mod runner;
 pub mod secrets;
+pub mod signature;
 pub mod spec;
 pub mod store;
+pub mod summary;
 pub mod template;
+pub mod theme;
+pub mod time_of_day;
 pub mod validate;
 pub mod visibility;
 
-pub use answers::{AnswerSet, Meta, ProgressState, ValidationError, ValidationResult};
+pub use answers::{
+    AnswerSet, CanonicalOptions, Meta, PROGRESS_STATE_VERSION, ProgressState, QuestionProgress,
+    QuestionStatus, ValidationError, ValidationResult, canonical_hash, canonical_json,
+    canonicalize, json_pointer,
+};
 pub use answers_schema::generate as answers_schema;
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use coerce::coerce_answers;
+pub use compiled::CompiledFormSpec;
 pub use compose::{IncludeError, expand_includes};
-pub use computed::{apply_computed_answers, build_expression_context};
+pub use computed::{
+    CONTEXT_ROOTS, ComputedReport, ComputedStatus, apply_computed_answers,
+    build_expression_context, build_expression_context_with_ctx, compute_answers,
+    materialize_computed,
+};
+pub use defaults::{DefaultValueError, autofill_defaults, typed_default};
+pub use diff::{AnswerChange, AnswerDiff, ChangeKind, DiffSummary};
 pub use examples::generate as example_answers;
-pub use expr::Expr;
+pub use expr::{CaseArm, EvaluationFailure, Expr};
+pub use flow_engine::{FlowEngine, FlowError, FlowLimit, FlowState, StepOutcome};
 pub use frontend::{DefaultQaFrontend, QaFrontend};
-pub use i18n::{I18nText, ResolvedI18nMap, resolve_i18n_text, resolve_i18n_text_with_locale};
-pub use progress::{ProgressContext, next_question};
+pub use i18n::{
+    I18nText, LocaleStrings, RenderLabels, ResolvedI18nMap, locale_strings, resolve_i18n_text,
+    resolve_i18n_text_with_locale,
+};
+pub use limits::{
+    DEFAULT_MAX_ANSWER_BYTES, DEFAULT_MAX_EXPR_DEPTH, DEFAULT_MAX_TOTAL_BYTES,
+    DEFAULT_MAX_VALUE_DEPTH, SpecLimits,
+};
+pub use lint::{LintIssue, LintSeverity, has_errors, lint_flow, lint_form};
+pub use merge::{MergeError, MergeOptions, MergeProvenance, MergeStrategy};
+pub use messages::{MessageCatalog, validate_localized};
+pub use migrate::{AppliedMigration, MigrationError, MigrationReport, StuckField, migrate_answers};
+pub use normalize::{apply_normalization, apply_normalization_with_changes};
+pub use pattern::{PATTERN_CACHE_CAPACITY, PatternCache, compile_pattern, pattern_compilations};
+pub use progress::{
+    FlowPosition, ProgressAnswers, ProgressContext, SessionError, next_question,
+    next_question_in_flow, previous_question,
+};
+pub use redact::{REDACTED_KEY, is_redacted, redact_answers, redacted_marker};
 pub use render::{
-    RenderPayload, RenderProgress, RenderQuestion, RenderStatus, build_render_payload,
-    build_render_payload_with_i18n, render_card, render_json_ui, render_text,
+    CardOptions, CardVersion, DEFAULT_MAX_CURRENT_VALUE_ITEMS, RenderOptions, RenderPayload,
+    RenderProgress, RenderQuestion, RenderStatus, build_render_payload,
+    build_render_payload_in_locale, build_render_payload_with_cache,
+    build_render_payload_with_i18n, build_render_payload_with_options,
+    build_render_payload_with_visibility, render_card, render_card_with_options, render_json_ui,
+    render_json_ui_to, render_markdown, render_text, render_text_to,
 };
 pub use runner::{
     QaPlanV1, execute_plan_effects, normalize_answers, plan_next, plan_submit_all,
     plan_submit_patch,
 };
 pub use secrets::{SecretAccessResult, SecretAction, evaluate};
-pub use spec::{FormSpec, IncludeSpec, QAFlowSpec, QuestionSpec, QuestionType, StepId, StepSpec};
-pub use store::{StoreContext, StoreError, StoreOp, StoreTarget};
+pub use signature::{AnswerSignature, HMAC_SHA256, SignatureError};
+pub use spec::{
+    AnswerConverter, FormSpec, IncludeSpec, MigrationOp, MigrationStep, QAFlowSpec, QuestionSpec,
+    QuestionType, SpecParseError, StepId, StepSpec,
+};
+pub use store::{
+    StoreContext, StoreError, StoreOp, StoreTarget, effective_store_ops, secret_pointer,
+};
+pub use summary::{RenderSummary, SummaryEntry, SummaryStatus, render_summary};
 pub use template::{
     ResolutionMode, TemplateContext, TemplateEngine, TemplateError, register_default_helpers,
 };
-pub use validate::validate;
-pub use visibility::{VisibilityMap, VisibilityMode, resolve_visibility};
+pub use theme::{DEFAULT_THEME, KNOWN_THEMES, ResolvedTheme, Theme, ThemeMode, ThemeSpec};
+pub use validate::{
+    FieldValidationError, ValidationOptions, computed_conflicts, read_only_changes,
+    reject_oversized, validate, validate_field, validate_field_with_ctx, validate_strict,
+    validate_strict_with_ctx, validate_update, validate_update_with_ctx, validate_with,
+    validate_with_ctx, validate_with_visibility, validate_with_visibility_and_ctx,
+    visibility_errors,
+};
+pub use visibility::{
+    ResolvedVisibility, VisibilityCache, VisibilityDiagnostic, VisibilityMap, VisibilityMode,
+    VisibilityOptions, list_field_visible, list_item_context, resolve_visibility,
+    resolve_visibility_with_ctx, resolve_visibility_with_diagnostics,
+    resolve_visibility_with_options, try_resolve_visibility, try_resolve_visibility_with_ctx,
+};
 
 pub use convert::QaMode;
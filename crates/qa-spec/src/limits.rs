@@ -0,0 +1,207 @@
+//! Nesting limits guarding parsing, evaluation, and validation against
+//! pathologically deep specs and answers.
+
+use std::fmt::Write as _;
+
+use serde_json::Value;
+
+use crate::expr::Expr;
+use crate::spec::form::FormSpec;
+
+/// Deepest expression nesting accepted by default.
+pub const DEFAULT_MAX_EXPR_DEPTH: usize = 64;
+
+/// Deepest JSON nesting accepted by default in spec documents and answers.
+/// Matches the recursion limit `serde_json` applies when parsing text.
+pub const DEFAULT_MAX_VALUE_DEPTH: usize = 128;
+
+/// Largest serialized answer to one question accepted by default, in bytes.
+pub const DEFAULT_MAX_ANSWER_BYTES: usize = 256 * 1024;
+
+/// Largest serialized answers object accepted by default, in bytes.
+pub const DEFAULT_MAX_TOTAL_BYTES: usize = 1024 * 1024;
+
+/// Nesting limits applied to specs and answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecLimits {
+    /// Deepest `visible_if`/`computed`/validation expression; a leaf
+    /// expression has depth 1.
+    pub max_expr_depth: usize,
+    /// Deepest JSON nesting of a spec document or an answers object; a
+    /// scalar has depth 0 and each enclosing array or object adds one.
+    pub max_value_depth: usize,
+}
+
+impl Default for SpecLimits {
+    fn default() -> Self {
+        Self {
+            max_expr_depth: DEFAULT_MAX_EXPR_DEPTH,
+            max_value_depth: DEFAULT_MAX_VALUE_DEPTH,
+        }
+    }
+}
+
+/// Length of `value` serialized as compact JSON, measured without building
+/// the text.
+pub(crate) fn json_size(value: &Value) -> usize {
+    struct Counter(usize);
+
+    impl std::io::Write for Counter {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0 += bytes.len();
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    serde_json::to_writer(&mut counter, value).expect("counting never fails");
+    counter.0
+}
+
+/// A location nested deeper than a limit allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TooDeep {
+    pub pointer: String,
+    pub depth: usize,
+}
+
+enum Key<'a> {
+    Index(usize),
+    Field(&'a str),
+}
+
+enum Children<'a> {
+    Array(std::iter::Enumerate<std::slice::Iter<'a, Value>>),
+    Object(serde_json::map::Iter<'a>),
+}
+
+impl<'a> Children<'a> {
+    fn of(value: &'a Value) -> Option<Self> {
+        match value {
+            Value::Array(items) => Some(Self::Array(items.iter().enumerate())),
+            Value::Object(map) => Some(Self::Object(map.iter())),
+            _ => None,
+        }
+    }
+
+    fn next(&mut self) -> Option<(Key<'a>, &'a Value)> {
+        match self {
+            Self::Array(items) => items.next().map(|(index, item)| (Key::Index(index), item)),
+            Self::Object(entries) => entries
+                .next()
+                .map(|(key, item)| (Key::Field(key.as_str()), item)),
+        }
+    }
+}
+
+/// First container in `value` nested deeper than `max_depth`, found without
+/// recursing so arbitrarily deep input cannot overflow the stack.
+pub(crate) fn value_too_deep(value: &Value, max_depth: usize) -> Option<TooDeep> {
+    let mut frames = Vec::new();
+    let mut path: Vec<Key<'_>> = Vec::new();
+    if let Some(children) = Children::of(value) {
+        frames.push(children);
+    }
+    while !frames.is_empty() {
+        if frames.len() > max_depth {
+            return Some(TooDeep {
+                pointer: to_pointer(&path),
+                depth: frames.len(),
+            });
+        }
+        match frames.last_mut().and_then(Children::next) {
+            Some((key, child)) => {
+                if let Some(children) = Children::of(child) {
+                    path.push(key);
+                    frames.push(children);
+                }
+            }
+            None => {
+                frames.pop();
+                path.pop();
+            }
+        }
+    }
+    None
+}
+
+/// Drops `value` without recursing; the default drop glue recurses once per
+/// nesting level and would overflow on the input [`value_too_deep`] rejects.
+pub(crate) fn drop_flat(value: Value) {
+    let mut pending = vec![value];
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::Array(items) => pending.extend(items),
+            Value::Object(map) => pending.extend(map.into_iter().map(|(_, item)| item)),
+            _ => {}
+        }
+    }
+}
+
+fn to_pointer(path: &[Key<'_>]) -> String {
+    let mut pointer = String::new();
+    for key in path {
+        let _ = match key {
+            Key::Index(index) => write!(pointer, "/{}", index),
+            Key::Field(field) => {
+                write!(pointer, "/{}", field.replace('~', "~0").replace('/', "~1"))
+            }
+        };
+    }
+    pointer
+}
+
+/// Every expression in `spec` with the JSON pointer it was declared at.
+pub(crate) fn form_expressions(spec: &FormSpec) -> Vec<(String, &Expr)> {
+    let mut out = Vec::new();
+    for (index, question) in spec.questions.iter().enumerate() {
+        let base = format!("/questions/{}", index);
+        let list_fields = question
+            .list
+            .iter()
+            .flat_map(|list| list.fields.iter().enumerate())
+            .map(|(field_index, field)| (format!("{}/list/fields/{}", base, field_index), field));
+        let object_fields = question
+            .object
+            .iter()
+            .flat_map(|object| object.fields.iter().enumerate())
+            .map(|(field_index, field)| (format!("{}/object/fields/{}", base, field_index), field));
+        let fields = list_fields.chain(object_fields);
+        for (base, question) in std::iter::once((base.clone(), question)).chain(fields) {
+            if let Some(expr) = &question.visible_if {
+                out.push((format!("{}/visible_if", base), expr));
+            }
+            if let Some(expr) = &question.required_if {
+                out.push((format!("{}/required_if", base), expr));
+            }
+            if let Some(expr) = &question.read_only_if {
+                out.push((format!("{}/read_only_if", base), expr));
+            }
+            if let Some(expr) = &question.computed {
+                out.push((format!("{}/computed", base), expr));
+            }
+        }
+    }
+    for (index, validation) in spec.validations.iter().enumerate() {
+        out.push((
+            format!("/validations/{}/condition", index),
+            &validation.condition,
+        ));
+    }
+    out
+}
+
+/// First expression in `spec` nested deeper than `max_depth`.
+pub(crate) fn expression_too_deep(spec: &FormSpec, max_depth: usize) -> Option<TooDeep> {
+    form_expressions(spec)
+        .into_iter()
+        .map(|(pointer, expr)| TooDeep {
+            pointer,
+            depth: expr.depth(),
+        })
+        .find(|found| found.depth > max_depth)
+}
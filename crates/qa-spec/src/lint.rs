@@ -0,0 +1,616 @@
+use handlebars::template::Template;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::computed::{CONTEXT_ROOTS, computed_cycles};
+use crate::expr::Expr;
+use crate::limits::{DEFAULT_MAX_EXPR_DEPTH, form_expressions};
+use crate::pattern::compile_pattern;
+use crate::secrets::{SecretAccessResult, SecretAction, evaluate};
+use crate::spec::flow::{DecisionStep, FlowPolicy, QAFlowSpec, StepId, StepSpec};
+use crate::spec::form::FormSpec;
+use crate::spec::question::{QuestionSpec, QuestionType, split_choices};
+use crate::store::{StoreTarget, secret_pointer};
+
+/// Severity attached to a lint finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+/// Single finding reported by the spec linters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    pub code: String,
+    pub path: String,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn error(code: &str, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: LintSeverity::Error,
+            code: code.into(),
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(code: &str, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: LintSeverity::Warning,
+            ..Self::error(code, path, message)
+        }
+    }
+}
+
+/// Returns whether any of the findings is an error.
+pub fn has_errors(issues: &[LintIssue]) -> bool {
+    issues
+        .iter()
+        .any(|issue| issue.severity == LintSeverity::Error)
+}
+
+/// Checks a flow graph for dangling references and decisions that can dead-end.
+///
+/// When the owning form is provided, question steps and decision selectors are
+/// checked against its questions as well.
+pub fn lint_flow(flow: &QAFlowSpec, form: Option<&FormSpec>) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    if !flow.steps.contains_key(&flow.entry) {
+        issues.push(LintIssue::error(
+            "unknown_entry",
+            "/entry",
+            format!("entry step '{}' does not exist", flow.entry),
+        ));
+    }
+
+    for (step_id, step) in &flow.steps {
+        let path = format!("/steps/{}", step_id);
+        let mut check_target = |target: &str| {
+            if !flow.steps.contains_key(target) {
+                issues.push(LintIssue::error(
+                    "unknown_step_target",
+                    path.clone(),
+                    format!("step '{}' points at unknown step '{}'", step_id, target),
+                ));
+            }
+        };
+
+        match step {
+            StepSpec::Message(message) => {
+                if let Some(next) = &message.next {
+                    check_target(next);
+                }
+                if let Err(err) = Template::compile(&message.template) {
+                    issues.push(LintIssue::error(
+                        "invalid_template",
+                        format!("{}/template", path),
+                        format!(
+                            "message step '{}' has an invalid template: {}",
+                            step_id, err
+                        ),
+                    ));
+                }
+            }
+            StepSpec::Question(question) => {
+                if let Some(next) = &question.next {
+                    check_target(next);
+                }
+                if let Some(form) = form
+                    && !form
+                        .questions
+                        .iter()
+                        .any(|candidate| candidate.id == question.question_id)
+                {
+                    issues.push(LintIssue::error(
+                        "unknown_question",
+                        path.clone(),
+                        format!(
+                            "step '{}' asks unknown question '{}'",
+                            step_id, question.question_id
+                        ),
+                    ));
+                }
+            }
+            StepSpec::Decision(decision) => {
+                for target in decision.targets() {
+                    check_target(target);
+                }
+                let expressions = decision
+                    .on
+                    .iter()
+                    .map(|on| (format!("{}/on", path), on))
+                    .chain(decision.cases.iter().enumerate().map(|(index, case)| {
+                        (format!("{}/cases/{}/if", path, index), &case.if_expr)
+                    }));
+                for (pointer, expr) in expressions {
+                    lint_expression_depth(expr, pointer, &mut issues);
+                }
+                if decision.default.is_none() && !is_exhaustive(decision, form) {
+                    issues.push(LintIssue::error(
+                        "decision_without_default",
+                        path.clone(),
+                        format!(
+                            "decision '{}' has no default and its cases are not exhaustive",
+                            step_id
+                        ),
+                    ));
+                }
+            }
+            StepSpec::Action { .. } | StepSpec::End => {}
+        }
+    }
+
+    let limited = flow.policies.as_ref().is_some_and(FlowPolicy::has_limits);
+    if !limited && let Some(step_id) = find_cycle(flow) {
+        issues.push(LintIssue::warning(
+            "cycle_without_limits",
+            "/policies",
+            format!(
+                "flow loops back through step '{}' but no max_steps or max_repeats_per_step is configured",
+                step_id
+            ),
+        ));
+    }
+
+    issues
+}
+
+impl FormSpec {
+    /// Static analysis of the spec's expressions and defaults, beyond the
+    /// parse-time [`FormSpec::check`]:
+    ///
+    /// - `unknown_reference`: a `visible_if`, `required_if`, `read_only_if`,
+    ///   `computed` or validation condition reads a question that does not exist, or a
+    ///   `store` op writes an answer for one.
+    /// - `unreachable_question` (warning): a `visible_if` built from literals
+    ///   only that is always false.
+    /// - `default_not_in_choices`: an `enum` or `multi_enum` default that is
+    ///   not one of the choices.
+    /// - `computed_cycle`: computed questions that depend on each other; the
+    ///   message spells out the loop (`a -> b -> a`).
+    /// - `duplicate_order` (warning): questions sharing an `order`; they are
+    ///   asked in spec order.
+    ///
+    /// [`lint_form`] includes these findings.
+    pub fn analyze(&self) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        let known = self
+            .questions
+            .iter()
+            .map(|question| question.id.as_str())
+            .collect::<BTreeSet<_>>();
+        for (index, question) in self.questions.iter().enumerate() {
+            let base = format!("/questions/{}", index);
+            check_question(question, &base, &known, false, &mut issues);
+            let fields = question
+                .list
+                .iter()
+                .map(|list| ("list", &list.fields))
+                .chain(
+                    question
+                        .object
+                        .iter()
+                        .map(|object| ("object", &object.fields)),
+                );
+            for (kind, fields) in fields {
+                for (field_index, field) in fields.iter().enumerate() {
+                    let field_base = format!("{}/{}/fields/{}", base, kind, field_index);
+                    check_question(field, &field_base, &known, true, &mut issues);
+                }
+            }
+        }
+        for (index, validation) in self.validations.iter().enumerate() {
+            check_references(
+                &validation.condition,
+                &format!("/validations/{}/condition", index),
+                &known,
+                false,
+                &mut issues,
+            );
+        }
+        for (index, op) in self.store.iter().enumerate() {
+            let root = op
+                .path
+                .split(['/', '.'])
+                .find(|segment| !segment.is_empty());
+            if op.target == StoreTarget::Answers
+                && let Some(root) = root
+                && !known.contains(root)
+            {
+                issues.push(LintIssue::error(
+                    "unknown_reference",
+                    format!("/store/{}/path", index),
+                    format!("store op writes the answer of unknown question '{}'", root),
+                ));
+            }
+        }
+        let mut orders = BTreeMap::new();
+        for (index, question) in self.questions.iter().enumerate() {
+            let Some(order) = question.order else {
+                continue;
+            };
+            let first = *orders.entry(order).or_insert(&question.id);
+            if first != &question.id {
+                issues.push(LintIssue::warning(
+                    "duplicate_order",
+                    format!("/questions/{}/order", index),
+                    format!(
+                        "question '{}' has order {}, like '{}'; they are asked in spec order",
+                        question.id, order, first
+                    ),
+                ));
+            }
+        }
+        for cycle in computed_cycles(self) {
+            let index = self
+                .questions
+                .iter()
+                .position(|question| question.id == cycle[0])
+                .unwrap_or_default();
+            let mut path = cycle.clone();
+            path.push(cycle[0].clone());
+            issues.push(LintIssue::error(
+                "computed_cycle",
+                format!("/questions/{}/computed", index),
+                format!("computed questions form a cycle: {}", path.join(" -> ")),
+            ));
+        }
+        issues
+    }
+}
+
+/// Checks one question or list/object field. `scoped` fields may also read
+/// their entry through `item`.
+fn check_question(
+    question: &QuestionSpec,
+    base: &str,
+    known: &BTreeSet<&str>,
+    scoped: bool,
+    issues: &mut Vec<LintIssue>,
+) {
+    let expressions = [
+        ("visible_if", &question.visible_if),
+        ("required_if", &question.required_if),
+        ("read_only_if", &question.read_only_if),
+        ("computed", &question.computed),
+    ];
+    for (key, expr) in expressions {
+        if let Some(expr) = expr {
+            check_references(expr, &format!("{}/{}", base, key), known, scoped, issues);
+        }
+    }
+    if let Some(expr) = &question.visible_if
+        && expr.referenced_answers().is_empty()
+        && expr.evaluate_bool(&Value::Object(Default::default())) == Some(false)
+    {
+        issues.push(LintIssue::warning(
+            "unreachable_question",
+            format!("{}/visible_if", base),
+            format!("question '{}' is never visible", question.id),
+        ));
+    }
+    let choices = question.choices.as_deref().unwrap_or_default();
+    if let Some(default) = &question.default_value
+        && !choices.is_empty()
+    {
+        let defaults = match question.kind {
+            QuestionType::Enum => vec![default.trim()],
+            QuestionType::MultiEnum => split_choices(default).collect(),
+            _ => Vec::new(),
+        };
+        for value in defaults {
+            if !choices.iter().any(|choice| choice == value) {
+                issues.push(LintIssue::error(
+                    "default_not_in_choices",
+                    format!("{}/default_value", base),
+                    format!(
+                        "question '{}' defaults to '{}', which is not one of its choices",
+                        question.id, value
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn check_references(
+    expr: &Expr,
+    path: &str,
+    known: &BTreeSet<&str>,
+    scoped: bool,
+    issues: &mut Vec<LintIssue>,
+) {
+    for reference in expr.referenced_answers() {
+        let context_root = reference == "ctx" || CONTEXT_ROOTS.contains(&reference.as_str());
+        if known.contains(reference.as_str()) || context_root || (scoped && reference == "item") {
+            continue;
+        }
+        issues.push(LintIssue::error(
+            "unknown_reference",
+            path,
+            format!("expression reads unknown question '{}'", reference),
+        ));
+    }
+}
+
+/// Checks form-level settings that serde cannot enforce on its own, plus
+/// [`FormSpec::analyze`].
+pub fn lint_form(spec: &FormSpec) -> Vec<LintIssue> {
+    let mut issues = spec.analyze();
+    if let Some(diagnostic) = spec
+        .presentation
+        .as_ref()
+        .and_then(|presentation| presentation.theme.as_ref())
+        .and_then(|theme| theme.resolve().diagnostic)
+    {
+        issues.push(LintIssue::warning(
+            "theme_fallback",
+            "/presentation/theme",
+            diagnostic,
+        ));
+    }
+    for (pointer, expr) in form_expressions(spec) {
+        lint_expression_patterns(expr, &pointer, &mut issues);
+        lint_expression_depth(expr, pointer, &mut issues);
+    }
+    for (index, question) in spec.questions.iter().enumerate() {
+        let base = format!("/questions/{}", index);
+        lint_constraint(question, &base, &mut issues);
+        if let Some(list) = &question.list {
+            for (field_index, field) in list.fields.iter().enumerate() {
+                let field_base = format!("{}/list/fields/{}", base, field_index);
+                lint_constraint(field, &field_base, &mut issues);
+            }
+            for (key_index, key) in list.unique_by.iter().flatten().enumerate() {
+                if !list.fields.iter().any(|field| &field.id == key) {
+                    issues.push(LintIssue::error(
+                        "unique_by_unknown_field",
+                        format!("{}/list/unique_by/{}", base, key_index),
+                        format!(
+                            "list '{}' is unique by '{}', which is not one of its fields",
+                            question.id, key
+                        ),
+                    ));
+                }
+            }
+        }
+        if let Some(object) = &question.object {
+            for (field_index, field) in object.fields.iter().enumerate() {
+                let field_base = format!("{}/object/fields/{}", base, field_index);
+                lint_constraint(field, &field_base, &mut issues);
+            }
+        }
+        let path = format!("{}/secret_path", base);
+        let Some(secret_path) = &question.secret_path else {
+            lint_list_secret_paths(question, &base, &mut issues);
+            continue;
+        };
+        if !question.secret {
+            issues.push(LintIssue::error(
+                "secret_path_without_secret",
+                path.clone(),
+                format!(
+                    "question '{}' sets secret_path but is not marked secret",
+                    question.id
+                ),
+            ));
+            continue;
+        }
+        // The host may be offline at lint time; only the policy itself is checked.
+        let key = secret_pointer(secret_path)
+            .trim_start_matches('/')
+            .to_string();
+        if let SecretAccessResult::Denied(code) = evaluate(
+            spec.secrets_policy.as_ref(),
+            &key,
+            SecretAction::Write,
+            true,
+        ) {
+            issues.push(LintIssue::error(
+                "secret_path_denied",
+                path,
+                format!(
+                    "question '{}' writes secret '{}' which the secrets policy denies ({})",
+                    question.id, key, code
+                ),
+            ));
+        }
+    }
+    issues
+}
+
+fn lint_expression_depth(expr: &Expr, path: String, issues: &mut Vec<LintIssue>) {
+    let depth = expr.depth();
+    if depth > DEFAULT_MAX_EXPR_DEPTH {
+        issues.push(LintIssue::error(
+            "expression_too_deep",
+            path,
+            format!(
+                "expression nests {} levels deep, more than the limit of {}",
+                depth, DEFAULT_MAX_EXPR_DEPTH
+            ),
+        ));
+    }
+}
+
+fn lint_expression_patterns(expr: &Expr, path: &str, issues: &mut Vec<LintIssue>) {
+    for pattern in expr.patterns() {
+        if let Err(err) = compile_pattern(pattern) {
+            issues.push(LintIssue::error(
+                "invalid_pattern",
+                path,
+                format!("expression pattern '{}' does not compile: {}", pattern, err),
+            ));
+        }
+    }
+}
+
+fn lint_constraint(question: &QuestionSpec, base: &str, issues: &mut Vec<LintIssue>) {
+    let Some(constraint) = &question.constraint else {
+        return;
+    };
+    let path = format!("{}/constraint", base);
+    if let Some(pattern) = &constraint.pattern
+        && let Err(err) = compile_pattern(pattern)
+    {
+        issues.push(LintIssue::error(
+            "invalid_pattern",
+            format!("{}/pattern", path),
+            format!(
+                "question '{}' has a pattern that does not compile: {}",
+                question.id, err
+            ),
+        ));
+    }
+    if let (Some(min), Some(max)) = (constraint.min, constraint.max) {
+        let empty =
+            min > max || (min == max && (constraint.exclusive_min || constraint.exclusive_max));
+        if empty {
+            issues.push(LintIssue::error(
+                "constraint_empty_range",
+                path.clone(),
+                format!(
+                    "question '{}' has bounds that no value can satisfy",
+                    question.id
+                ),
+            ));
+        }
+    }
+    if let Some(step) = constraint.multiple_of
+        && step <= 0.0
+    {
+        issues.push(LintIssue::error(
+            "multiple_of_not_positive",
+            format!("{}/multiple_of", path),
+            format!(
+                "question '{}' has multiple_of {}, which must be positive",
+                question.id, step
+            ),
+        ));
+    }
+    if matches!(question.kind, QuestionType::Integer) {
+        for (name, bound) in [("min", constraint.min), ("max", constraint.max)] {
+            if let Some(bound) = bound
+                && bound.fract() != 0.0
+            {
+                issues.push(LintIssue::error(
+                    "fractional_integer_bound",
+                    format!("{}/{}", path, name),
+                    format!(
+                        "integer question '{}' has fractional {} bound {}",
+                        question.id, name, bound
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn lint_list_secret_paths(question: &QuestionSpec, base: &str, issues: &mut Vec<LintIssue>) {
+    let Some(list) = &question.list else {
+        return;
+    };
+    for (index, field) in list.fields.iter().enumerate() {
+        if field.secret_path.is_some() {
+            issues.push(LintIssue::error(
+                "secret_path_in_list",
+                format!("{}/list/fields/{}/secret_path", base, index),
+                format!(
+                    "list field '{}' cannot use secret_path; list entries have no single secret location",
+                    field.id
+                ),
+            ));
+        }
+    }
+}
+
+/// Returns a step that lies on a cycle reachable in the step graph, if any.
+fn find_cycle(flow: &QAFlowSpec) -> Option<&StepId> {
+    fn visit<'a>(
+        flow: &'a QAFlowSpec,
+        step_id: &'a StepId,
+        on_path: &mut BTreeSet<&'a StepId>,
+        done: &mut BTreeSet<&'a StepId>,
+    ) -> Option<&'a StepId> {
+        if on_path.contains(step_id) {
+            return Some(step_id);
+        }
+        if done.contains(step_id) {
+            return None;
+        }
+        let (key, step) = flow.steps.get_key_value(step_id)?;
+        on_path.insert(key);
+        for next in step.successors() {
+            if let Some(found) = visit(flow, next, on_path, done) {
+                return Some(found);
+            }
+        }
+        on_path.remove(key);
+        done.insert(key);
+        None
+    }
+
+    let mut done = BTreeSet::new();
+    flow.steps
+        .keys()
+        .find_map(|step_id| visit(flow, step_id, &mut BTreeSet::new(), &mut done))
+}
+
+/// A decision is provably exhaustive when a case is unconditionally true, or when
+/// it switches on a required boolean/enum answer and covers every possible value.
+fn is_exhaustive(decision: &DecisionStep, form: Option<&FormSpec>) -> bool {
+    let Some(on) = &decision.on else {
+        return decision.cases.iter().any(
+            |case| matches!(&case.if_expr, Expr::Literal { value } if value == &Value::Bool(true)),
+        );
+    };
+
+    let Some(question) = selector_question_id(on).and_then(|id| {
+        form.and_then(|form| form.questions.iter().find(|question| question.id == id))
+    }) else {
+        return false;
+    };
+    if !question.required {
+        return false;
+    }
+
+    let covered = decision
+        .cases
+        .iter()
+        .filter_map(|case| match &case.if_expr {
+            Expr::Literal { value } => Some(value.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let expected = match question.kind {
+        QuestionType::Boolean => vec![Value::Bool(true), Value::Bool(false)],
+        QuestionType::Enum => match &question.choices {
+            Some(choices) => choices.iter().cloned().map(Value::String).collect(),
+            None => return false,
+        },
+        _ => return false,
+    };
+    expected.iter().all(|value| covered.contains(value))
+}
+
+fn selector_question_id(expr: &Expr) -> Option<&str> {
+    let path = match expr {
+        Expr::Answer { path } => path.as_str(),
+        Expr::Var { path } => path
+            .strip_prefix("/answers/")
+            .or_else(|| path.strip_prefix("answers."))
+            .unwrap_or(path),
+        _ => return None,
+    };
+    let id = path.trim_start_matches('/');
+    (!id.is_empty() && !id.contains('/')).then_some(id)
+}
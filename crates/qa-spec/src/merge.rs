@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::answers::{AnswerSet, canonical_eq, json_pointer};
+
+/// How to resolve a path answered differently by both sides of a merge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    #[default]
+    OverlayWins,
+    BaseWins,
+    ErrorOnConflict,
+}
+
+/// Options for [`AnswerSet::merge`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MergeOptions {
+    #[serde(default)]
+    pub strategy: MergeStrategy,
+    /// List questions merged entry by entry, keyed by the named field.
+    /// Lists not listed here are treated as a single value and replaced wholesale.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub list_keys: BTreeMap<String, String>,
+}
+
+impl MergeOptions {
+    pub fn new(strategy: MergeStrategy) -> Self {
+        Self {
+            strategy,
+            list_keys: BTreeMap::new(),
+        }
+    }
+
+    /// Merge entries of `question_id` by matching their `field` values.
+    pub fn with_list_key(
+        mut self,
+        question_id: impl Into<String>,
+        field: impl Into<String>,
+    ) -> Self {
+        self.list_keys.insert(question_id.into(), field.into());
+        self
+    }
+}
+
+/// Provenance recorded in [`crate::Meta`] for answer sets produced by a merge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MergeProvenance {
+    pub strategy: MergeStrategy,
+    /// JSON pointers whose value was taken from the overlay.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from_overlay: Vec<String>,
+    /// JSON pointers both sides answered differently, resolved by the strategy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+}
+
+/// Failure raised by [`AnswerSet::merge`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MergeError {
+    #[error("conflicting answers at {}", .keys.join(", "))]
+    Conflict { keys: Vec<String> },
+}
+
+impl AnswerSet {
+    /// Deep-merges `overlay` onto `base`.
+    ///
+    /// Form id and spec version come from `base`; the resulting meta records
+    /// which paths came from the overlay and which ones conflicted.
+    pub fn merge(
+        base: &AnswerSet,
+        overlay: &AnswerSet,
+        options: &MergeOptions,
+    ) -> Result<AnswerSet, MergeError> {
+        let mut merger = Merger {
+            options,
+            from_overlay: Vec::new(),
+            conflicts: Vec::new(),
+        };
+        let answers = merger.merge(&base.answers, &overlay.answers, &mut Vec::new());
+        if options.strategy == MergeStrategy::ErrorOnConflict && !merger.conflicts.is_empty() {
+            return Err(MergeError::Conflict {
+                keys: merger.conflicts,
+            });
+        }
+
+        let mut meta = overlay
+            .meta
+            .clone()
+            .or_else(|| base.meta.clone())
+            .unwrap_or_default();
+        meta.merge = Some(MergeProvenance {
+            strategy: options.strategy,
+            from_overlay: merger.from_overlay,
+            conflicts: merger.conflicts,
+        });
+        Ok(AnswerSet {
+            form_id: base.form_id.clone(),
+            spec_version: base.spec_version.clone(),
+            answers,
+            meta: Some(meta),
+        })
+    }
+}
+
+struct Merger<'a> {
+    options: &'a MergeOptions,
+    from_overlay: Vec<String>,
+    conflicts: Vec<String>,
+}
+
+impl Merger<'_> {
+    fn merge(&mut self, base: &Value, overlay: &Value, path: &mut Vec<String>) -> Value {
+        if let (Value::Array(base_items), Value::Array(overlay_items), [question_id]) =
+            (base, overlay, path.as_slice())
+            && let Some(key) = self.options.list_keys.get(question_id)
+        {
+            return Value::Array(self.merge_keyed(base_items, overlay_items, key, path));
+        }
+        match (base, overlay) {
+            (Value::Object(base_map), Value::Object(overlay_map)) => {
+                let mut merged = base_map.clone();
+                for (key, overlay_value) in overlay_map {
+                    path.push(key.clone());
+                    let value = match base_map.get(key) {
+                        Some(base_value) => self.merge(base_value, overlay_value, path),
+                        None => {
+                            self.from_overlay.push(json_pointer(path.iter()));
+                            overlay_value.clone()
+                        }
+                    };
+                    merged.insert(key.clone(), value);
+                    path.pop();
+                }
+                Value::Object(merged)
+            }
+            _ if canonical_eq(base, overlay) => base.clone(),
+            _ => {
+                let at = json_pointer(path.iter());
+                self.conflicts.push(at.clone());
+                match self.options.strategy {
+                    MergeStrategy::BaseWins => base.clone(),
+                    MergeStrategy::OverlayWins | MergeStrategy::ErrorOnConflict => {
+                        self.from_overlay.push(at);
+                        overlay.clone()
+                    }
+                }
+            }
+        }
+    }
+
+    fn merge_keyed(
+        &mut self,
+        base: &[Value],
+        overlay: &[Value],
+        key: &str,
+        path: &mut Vec<String>,
+    ) -> Vec<Value> {
+        let mut merged = base.to_vec();
+        for item in overlay {
+            let matched = item
+                .get(key)
+                .and_then(|wanted| base.iter().position(|entry| entry.get(key) == Some(wanted)));
+            match matched {
+                Some(index) => {
+                    path.push(index.to_string());
+                    merged[index] = self.merge(&base[index], item, path);
+                    path.pop();
+                }
+                None => {
+                    path.push(merged.len().to_string());
+                    self.from_overlay.push(json_pointer(path.iter()));
+                    path.pop();
+                    merged.push(item.clone());
+                }
+            }
+        }
+        merged
+    }
+}
@@ -0,0 +1,275 @@
+//! Validation messages in the language of the people filling in a form.
+//!
+//! Built-in validation errors carry a message key such as `qa_spec.min`
+//! and a machine `code`. A [`MessageCatalog`] turns them into readable text
+//! from templates keyed by code, with `{name}` placeholders filled from the
+//! error's params and from the question (`{min}`, `{max}`, `{min_len}`,
+//! `{max_len}`, `{choices}` and `{title}`).
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::answers::{ValidationError, ValidationResult};
+use crate::spec::form::FormSpec;
+use crate::spec::question::QuestionSpec;
+use crate::validate::{bound_text, validate};
+
+/// English templates for every built-in error code.
+const ENGLISH: &[(&str, &str)] = &[
+    (
+        "color_invalid",
+        "Value must be a hex color written as #RRGGBB or #RRGGBBAA.",
+    ),
+    (
+        "computed_conflict",
+        "Value is computed as {expected} and cannot be changed.",
+    ),
+    (
+        "date_invalid",
+        "Value must be a date written as YYYY-MM-DD.",
+    ),
+    (
+        "decimal_precision",
+        "Value {value} has more than {places} decimal places.",
+    ),
+    (
+        "duplicate_choice",
+        "Option '{value}' is selected more than once.",
+    ),
+    ("duplicate_item", "Item '{value}' appears more than once."),
+    (
+        "duration_invalid",
+        "Value must be a duration in whole seconds.",
+    ),
+    ("entry_type", "List entry must be an object."),
+    ("enum_mismatch", "Value must be one of: {choices}."),
+    ("exclusive_max", "Value must be less than {max}."),
+    ("exclusive_min", "Value must be greater than {min}."),
+    ("format_email", "Value is not a valid email address."),
+    ("format_phone", "Value is not a valid phone number."),
+    ("format_url", "Value is not a valid URL."),
+    ("format_uuid", "Value is not a valid UUID."),
+    (
+        "integer_out_of_range",
+        "Whole number is too large to be represented exactly.",
+    ),
+    ("list_type", "List value must be an array."),
+    ("max", "Value must be at most {max}."),
+    (
+        "max_items",
+        "Too many list entries (maximum {expected}, got {actual}).",
+    ),
+    (
+        "max_length",
+        "Value must be at most {max_len} characters long.",
+    ),
+    ("max_selected", "Select at most {expected} options."),
+    ("min", "Value must be at least {min}."),
+    (
+        "min_items",
+        "Not enough list entries (minimum {expected}, got {actual}).",
+    ),
+    (
+        "min_length",
+        "Value must be at least {min_len} characters long.",
+    ),
+    ("min_selected", "Select at least {expected} options."),
+    ("missing_field", "Field '{field}' is required."),
+    (
+        "missing_list_definition",
+        "List question metadata is missing.",
+    ),
+    (
+        "missing_object_definition",
+        "Object question metadata is missing.",
+    ),
+    ("multiple_of", "Value must be a multiple of {multiple_of}."),
+    (
+        "path_invalid",
+        "Value must be a non-empty path without NUL bytes.",
+    ),
+    (
+        "payload_too_large",
+        "Answer is {size} bytes, above the limit of {limit} bytes.",
+    ),
+    (
+        "pattern_mismatch",
+        "Value does not match the required pattern.",
+    ),
+    (
+        "read_only",
+        "This answer is read-only and cannot be changed.",
+    ),
+    (
+        "time_invalid",
+        "Value must be a time of day written as HH:MM or HH:MM:SS.",
+    ),
+    (
+        "too_deep",
+        "Answer is nested more than {limit} levels deep.",
+    ),
+    ("type_mismatch", "Value does not match the question type."),
+    (
+        "unknown_item_field",
+        "Field '{field}' is not part of this list entry.",
+    ),
+    (
+        "visibility_error",
+        "The condition showing this question could not be evaluated ({reason}).",
+    ),
+];
+
+/// Message templates for validation errors, keyed by error code.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MessageCatalog {
+    /// Templates keyed by error code, e.g. `"min": "Minimal {min}."`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub codes: BTreeMap<String, String>,
+    /// Templates for single questions, keyed by question id and then by
+    /// error code. They take precedence over `codes` and over the spec's own
+    /// `constraint.messages`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub questions: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl MessageCatalog {
+    /// The built-in English messages for every error code.
+    pub fn english() -> Self {
+        Self {
+            codes: ENGLISH
+                .iter()
+                .map(|(code, template)| (code.to_string(), template.to_string()))
+                .collect(),
+            questions: BTreeMap::new(),
+        }
+    }
+
+    /// This catalog with templates it lacks taken from `fallback`.
+    pub fn or(mut self, fallback: &MessageCatalog) -> Self {
+        for (code, template) in &fallback.codes {
+            self.codes
+                .entry(code.clone())
+                .or_insert_with(|| template.clone());
+        }
+        for (question_id, templates) in &fallback.questions {
+            let own = self.questions.entry(question_id.clone()).or_default();
+            for (code, template) in templates {
+                own.entry(code.clone()).or_insert_with(|| template.clone());
+            }
+        }
+        self
+    }
+
+    /// Template for `code`, preferring one given for `question_id`.
+    pub fn template(&self, question_id: Option<&str>, code: &str) -> Option<&str> {
+        question_id
+            .and_then(|id| self.questions.get(id))
+            .and_then(|templates| templates.get(code))
+            .or_else(|| self.codes.get(code))
+            .map(String::as_str)
+    }
+
+    /// Replaces the messages of `result`'s errors and warnings with text
+    /// from this catalog.
+    ///
+    /// Question-specific templates always apply. Code templates only replace
+    /// built-in message keys, so messages written in the spec (custom
+    /// constraint messages, cross-field validations) are kept. Errors without
+    /// a matching template keep their message.
+    pub fn localize(&self, spec: &FormSpec, result: &mut ValidationResult) {
+        for error in result.errors.iter_mut().chain(result.warnings.iter_mut()) {
+            self.localize_error(spec, error);
+        }
+    }
+
+    fn localize_error(&self, spec: &FormSpec, error: &mut ValidationError) {
+        let Some(code) = error.code.as_deref() else {
+            return;
+        };
+        let question_id = error.question_id.as_deref();
+        let template = question_id
+            .and_then(|id| self.questions.get(id))
+            .and_then(|templates| templates.get(code))
+            .or_else(|| {
+                error
+                    .message
+                    .starts_with("qa_spec.")
+                    .then(|| self.codes.get(code))
+                    .flatten()
+            });
+        let Some(template) = template else {
+            return;
+        };
+        let question = question_id.and_then(|id| find_question(&spec.questions, id));
+        error.message = fill(template, &placeholders(question, error));
+    }
+}
+
+/// [`validate`] with messages taken from `catalog`.
+pub fn validate_localized(
+    spec: &FormSpec,
+    answers: &Value,
+    catalog: &MessageCatalog,
+) -> ValidationResult {
+    let mut result = validate(spec, answers);
+    catalog.localize(spec, &mut result);
+    result
+}
+
+fn find_question<'a>(questions: &'a [QuestionSpec], id: &str) -> Option<&'a QuestionSpec> {
+    questions.iter().find_map(|question| {
+        if question.id == id {
+            return Some(question);
+        }
+        question
+            .list
+            .as_ref()
+            .and_then(|list| find_question(&list.fields, id))
+            .or_else(|| {
+                question
+                    .object
+                    .as_ref()
+                    .and_then(|object| find_question(&object.fields, id))
+            })
+    })
+}
+
+fn placeholders(
+    question: Option<&QuestionSpec>,
+    error: &ValidationError,
+) -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    if let Some(question) = question {
+        values.insert("title".to_string(), question.title.clone());
+        if let Some(choices) = &question.choices {
+            values.insert("choices".to_string(), choices.join(", "));
+        }
+        if let Some(constraint) = &question.constraint {
+            if let Some(min) = constraint.min {
+                values.insert("min".to_string(), bound_text(question, min));
+            }
+            if let Some(max) = constraint.max {
+                values.insert("max".to_string(), bound_text(question, max));
+            }
+            if let Some(min_len) = constraint.min_len {
+                values.insert("min_len".to_string(), min_len.to_string());
+            }
+            if let Some(max_len) = constraint.max_len {
+                values.insert("max_len".to_string(), max_len.to_string());
+            }
+        }
+    }
+    values.extend(error.params.clone());
+    values
+}
+
+fn fill(template: &str, values: &BTreeMap<String, String>) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
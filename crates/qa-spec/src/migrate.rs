@@ -0,0 +1,308 @@
+//! Migrating answers recorded against an older spec version.
+//!
+//! [`FormSpec::migrations`] lists [`MigrationStep`]s; [`migrate_answers`]
+//! chains them from the answers' version up to the spec's version.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use thiserror::Error;
+
+use crate::answers::AnswerSet;
+use crate::spec::form::FormSpec;
+use crate::spec::migration::{AnswerConverter, MigrationOp, MigrationStep};
+
+/// Operation that rewrote answers during a migration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AppliedMigration {
+    pub from_version: String,
+    pub to_version: String,
+    pub op: MigrationOp,
+}
+
+/// What [`migrate_answers`] did.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MigrationReport {
+    pub from_version: String,
+    pub to_version: String,
+    /// Operations that changed answers, in application order. Operations whose
+    /// source fields were unanswered are not listed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applied: Vec<AppliedMigration>,
+}
+
+impl MigrationReport {
+    /// True when no answer was rewritten.
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty()
+    }
+}
+
+/// Answer field an operation could not migrate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StuckField {
+    pub field: String,
+    /// Name of the failing operation (`rename`, `retype`, ...).
+    pub op: String,
+    pub reason: String,
+}
+
+impl fmt::Display for StuckField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}: {})", self.field, self.op, self.reason)
+    }
+}
+
+/// Failure raised by [`migrate_answers`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MigrationError {
+    #[error("answers must be a JSON object")]
+    NotAnObject,
+    #[error("no migration path from version '{from}' to '{to}'")]
+    NoPath { from: String, to: String },
+    #[error(
+        "answers could not be migrated from version '{from_version}' to '{to_version}'; stuck fields: {}",
+        .fields.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    )]
+    Stuck {
+        from_version: String,
+        to_version: String,
+        fields: Vec<StuckField>,
+    },
+}
+
+impl MigrationError {
+    /// Fields that need fixing by hand before the migration can succeed.
+    pub fn stuck_fields(&self) -> &[StuckField] {
+        match self {
+            Self::Stuck { fields, .. } => fields,
+            _ => &[],
+        }
+    }
+}
+
+/// Rewrites `answers` recorded against `from_version` so they match `spec.version`.
+///
+/// Steps are chained by version: the first step whose `from` equals the
+/// current version runs, then the next one starting at its `to`, until the
+/// spec version is reached. Every operation of a step runs even when an
+/// earlier one got stuck, so the error lists all stuck fields of that step.
+pub fn migrate_answers(
+    answers: &Value,
+    from_version: &str,
+    spec: &FormSpec,
+) -> Result<(Value, MigrationReport), MigrationError> {
+    let mut current = answers
+        .as_object()
+        .cloned()
+        .ok_or(MigrationError::NotAnObject)?;
+    let mut report = MigrationReport {
+        from_version: from_version.to_string(),
+        to_version: spec.version.clone(),
+        applied: Vec::new(),
+    };
+
+    let mut version = from_version.to_string();
+    let mut visited = BTreeSet::new();
+    while version != spec.version {
+        let step = spec
+            .migrations
+            .iter()
+            .find(|step| step.from == version)
+            .filter(|_| visited.insert(version.clone()))
+            .ok_or_else(|| MigrationError::NoPath {
+                from: from_version.to_string(),
+                to: spec.version.clone(),
+            })?;
+        apply_step(step, &mut current, &mut report)?;
+        version = step.to.clone();
+    }
+
+    Ok((Value::Object(current), report))
+}
+
+impl AnswerSet {
+    /// Migrates the answers to `spec`'s version with [`migrate_answers`].
+    ///
+    /// The result is stamped with the spec version. A signature is dropped
+    /// because it no longer matches the rewritten answers.
+    pub fn migrate(&self, spec: &FormSpec) -> Result<(AnswerSet, MigrationReport), MigrationError> {
+        let (answers, report) = migrate_answers(&self.answers, &self.spec_version, spec)?;
+        let mut migrated = self.clone();
+        migrated.answers = answers;
+        migrated.spec_version = spec.version.clone();
+        if let Some(meta) = migrated.meta.as_mut() {
+            meta.signature = None;
+        }
+        Ok((migrated, report))
+    }
+}
+
+fn apply_step(
+    step: &MigrationStep,
+    answers: &mut Map<String, Value>,
+    report: &mut MigrationReport,
+) -> Result<(), MigrationError> {
+    let mut stuck = Vec::new();
+    for op in &step.ops {
+        match apply_op(op, answers) {
+            Ok(true) => report.applied.push(AppliedMigration {
+                from_version: step.from.clone(),
+                to_version: step.to.clone(),
+                op: op.clone(),
+            }),
+            Ok(false) => {}
+            Err((field, reason)) => stuck.push(StuckField {
+                field,
+                op: op.name().to_string(),
+                reason,
+            }),
+        }
+    }
+    if stuck.is_empty() {
+        Ok(())
+    } else {
+        Err(MigrationError::Stuck {
+            from_version: step.from.clone(),
+            to_version: step.to.clone(),
+            fields: stuck,
+        })
+    }
+}
+
+/// Applies one operation; `Ok(false)` means its source fields were unanswered.
+fn apply_op(op: &MigrationOp, answers: &mut Map<String, Value>) -> Result<bool, (String, String)> {
+    match op {
+        MigrationOp::Rename { from, to } => {
+            let Some(value) = answers.get(from) else {
+                return Ok(false);
+            };
+            if from != to && answers.contains_key(to) {
+                return Err((
+                    from.clone(),
+                    format!("target field '{to}' is already answered"),
+                ));
+            }
+            let value = value.clone();
+            answers.remove(from);
+            answers.insert(to.clone(), value);
+            Ok(true)
+        }
+        MigrationOp::Split {
+            from,
+            into,
+            separator,
+        } => {
+            let Some(value) = answers.get(from) else {
+                return Ok(false);
+            };
+            let Some(text) = value.as_str() else {
+                return Err((from.clone(), format!("expected a string, got {value}")));
+            };
+            let parts = text
+                .splitn(into.len(), separator.as_str())
+                .collect::<Vec<_>>();
+            if parts.len() != into.len() || separator.is_empty() {
+                return Err((
+                    from.clone(),
+                    format!(
+                        "expected {} parts separated by '{separator}', got {value}",
+                        into.len()
+                    ),
+                ));
+            }
+            let parts = parts
+                .into_iter()
+                .map(|part| Value::String(part.to_string()))
+                .collect::<Vec<_>>();
+            answers.remove(from);
+            for (field, part) in into.iter().zip(parts) {
+                answers.insert(field.clone(), part);
+            }
+            Ok(true)
+        }
+        MigrationOp::Merge {
+            from,
+            into,
+            separator,
+        } => {
+            let mut parts = Vec::new();
+            for field in from {
+                match answers.get(field) {
+                    None | Some(Value::Null) => {}
+                    Some(value) => match scalar_text(value) {
+                        Some(text) => parts.push(text),
+                        None => {
+                            return Err((field.clone(), format!("expected a scalar, got {value}")));
+                        }
+                    },
+                }
+            }
+            if parts.is_empty() {
+                return Ok(false);
+            }
+            for field in from {
+                answers.remove(field);
+            }
+            answers.insert(into.clone(), Value::String(parts.join(separator)));
+            Ok(true)
+        }
+        MigrationOp::Retype { field, convert } => {
+            let Some(value) = answers.get(field).filter(|value| !value.is_null()) else {
+                return Ok(false);
+            };
+            let converted =
+                convert_value(value, *convert).map_err(|reason| (field.clone(), reason))?;
+            answers.insert(field.clone(), converted);
+            Ok(true)
+        }
+        MigrationOp::Drop { field } => Ok(answers.remove(field).is_some()),
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn convert_value(value: &Value, convert: AnswerConverter) -> Result<Value, String> {
+    match (convert, value) {
+        (AnswerConverter::StringToInteger, Value::Number(number))
+            if number.is_i64() || number.is_u64() =>
+        {
+            Ok(value.clone())
+        }
+        (AnswerConverter::StringToInteger, Value::String(text)) => text
+            .trim()
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("cannot parse {value} as an integer")),
+        (AnswerConverter::StringToNumber, Value::Number(_)) => Ok(value.clone()),
+        (AnswerConverter::StringToNumber, Value::String(text)) => text
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| format!("cannot parse {value} as a number")),
+        (AnswerConverter::StringToBoolean, Value::Bool(_)) => Ok(value.clone()),
+        (AnswerConverter::StringToBoolean, Value::String(text)) => {
+            match text.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" => Ok(Value::Bool(true)),
+                "false" | "no" | "0" => Ok(Value::Bool(false)),
+                _ => Err(format!("cannot parse {value} as a boolean")),
+            }
+        }
+        (AnswerConverter::ToString, _) => scalar_text(value)
+            .map(Value::String)
+            .ok_or_else(|| format!("cannot convert {value} to a string")),
+        (_, _) => Err(format!("expected a string, got {value}")),
+    }
+}
@@ -0,0 +1,79 @@
+use crate::answers::canonical_number_value;
+use crate::diff::{AnswerChange, diff_answers};
+use crate::spec::form::FormSpec;
+use crate::spec::question::{QuestionSpec, QuestionType};
+use crate::time_of_day::{format_time_of_day, seconds_of_day};
+use serde_json::{Map, Value};
+
+/// Applies each question's `normalize` transforms to its string answers (including
+/// `string_list` items and list entry and object fields) and returns the
+/// normalized answer map. `time` answers are then written out as `HH:MM:SS`,
+/// and whole floats up to 2^53 given for `integer` and `rating` questions
+/// become integers (`42.0` becomes `42`).
+pub fn apply_normalization(spec: &FormSpec, answers: &Value) -> Value {
+    let mut map = answers.as_object().cloned().unwrap_or_default();
+    normalize_fields(&spec.questions, &mut map);
+    Value::Object(map)
+}
+
+/// [`apply_normalization`], also listing the answers it changed so callers
+/// can report what is stored. Values of secret questions are withheld from
+/// the changes (see [`AnswerChange::masked`]).
+pub fn apply_normalization_with_changes(
+    spec: &FormSpec,
+    answers: &Value,
+) -> (Value, Vec<AnswerChange>) {
+    let normalized = apply_normalization(spec, answers);
+    let changes = if answers.is_object() {
+        diff_answers(answers, &normalized, Some(spec)).changes
+    } else {
+        Vec::new()
+    };
+    (normalized, changes)
+}
+
+fn normalize_fields(questions: &[QuestionSpec], map: &mut Map<String, Value>) {
+    for question in questions {
+        let Some(value) = map.get_mut(&question.id) else {
+            continue;
+        };
+        if let Some(list) = &question.list
+            && let Some(entries) = value.as_array_mut()
+        {
+            for entry in entries.iter_mut().filter_map(Value::as_object_mut) {
+                normalize_fields(&list.fields, entry);
+            }
+        }
+        if let Some(object) = &question.object
+            && let Some(entry) = value.as_object_mut()
+        {
+            normalize_fields(&object.fields, entry);
+        }
+        if matches!(question.kind, QuestionType::StringList)
+            && let Some(items) = value.as_array_mut()
+        {
+            for item in items {
+                normalize_text(question, item);
+            }
+        }
+        normalize_text(question, value);
+        if matches!(question.kind, QuestionType::Time)
+            && let Some(seconds) = value.as_str().and_then(seconds_of_day)
+        {
+            *value = Value::String(format_time_of_day(seconds));
+        }
+        if matches!(question.kind, QuestionType::Integer | QuestionType::Rating)
+            && let Value::Number(number) = value
+        {
+            *number = canonical_number_value(number);
+        }
+    }
+}
+
+fn normalize_text(question: &QuestionSpec, value: &mut Value) {
+    if let Value::String(text) = value {
+        for transform in &question.normalize {
+            *text = transform.apply(text);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Compiled `Constraint.pattern` regexes, shared across validation calls.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+use regex::Regex;
+
+/// Patterns compiled ahead of time, keyed by pattern text.
+pub(crate) type CompiledPatterns = HashMap<String, Result<Regex, regex::Error>>;
+
+/// Patterns kept by the process-wide cache used by [`compile_pattern`].
+pub const PATTERN_CACHE_CAPACITY: usize = 256;
+
+/// Bounded cache of compiled patterns, including patterns that failed to
+/// compile so they are not retried. The oldest pattern is evicted first.
+#[derive(Debug)]
+pub struct PatternCache {
+    capacity: usize,
+    entries: HashMap<String, Result<Regex, regex::Error>>,
+    order: VecDeque<String>,
+    compilations: usize,
+}
+
+impl Default for PatternCache {
+    fn default() -> Self {
+        Self::new(PATTERN_CACHE_CAPACITY)
+    }
+}
+
+impl PatternCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            compilations: 0,
+        }
+    }
+
+    /// Compiled `pattern`, compiling it on the first request only.
+    pub fn get(&mut self, pattern: &str) -> Result<Regex, regex::Error> {
+        if let Some(compiled) = self.entries.get(pattern) {
+            return compiled.clone();
+        }
+        if self.entries.len() >= self.capacity
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.entries.remove(&oldest);
+        }
+        let compiled = Regex::new(pattern);
+        self.compilations += 1;
+        self.order.push_back(pattern.to_string());
+        self.entries.insert(pattern.to_string(), compiled.clone());
+        compiled
+    }
+
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.entries.contains_key(pattern)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Patterns compiled so far, counting failures and recompiles after eviction.
+    pub fn compilations(&self) -> usize {
+        self.compilations
+    }
+}
+
+/// Compiles `pattern` through a process-wide [`PatternCache`].
+///
+/// Falls back to compiling without caching if the cache lock is poisoned.
+pub fn compile_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    match shared_cache().lock() {
+        Ok(mut cache) => cache.get(pattern),
+        Err(_) => Regex::new(pattern),
+    }
+}
+
+/// [`PatternCache::compilations`] of the cache behind [`compile_pattern`].
+pub fn pattern_compilations() -> usize {
+    shared_cache()
+        .lock()
+        .map(|cache| cache.compilations())
+        .unwrap_or_default()
+}
+
+fn shared_cache() -> &'static Mutex<PatternCache> {
+    static CACHE: OnceLock<Mutex<PatternCache>> = OnceLock::new();
+    CACHE.get_or_init(Mutex::default)
+}
@@ -1,52 +1,170 @@
+use std::collections::BTreeSet;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use thiserror::Error;
 
-use crate::spec::form::FormSpec;
+use crate::answers::{PROGRESS_STATE_VERSION, ProgressState, QuestionProgress, QuestionStatus};
+use crate::clock::{Clock, SystemClock};
+use crate::computed::{ComputedStatus, build_expression_context_with_ctx, compute_answers};
+use crate::flow_engine::{FlowEngine, FlowError, StepOutcome};
+use crate::render::{RenderPayload, RenderStatus};
+use crate::spec::flow::{FlowPolicy, QAFlowSpec, StepId};
+use crate::spec::form::{FormSpec, ProgressPolicy};
 use crate::spec::question::QuestionSpec;
 use crate::store::StoreTarget;
-use crate::visibility::VisibilityMap;
+use crate::visibility::{VisibilityMap, VisibilityMode, resolve_visibility_with_ctx};
+
+/// Answers a [`ProgressContext`] evaluates: borrowed, or owned by the context.
+///
+/// Borrowing is the cheap path. `Owned` keeps callers that still hand over an
+/// owned `Value` (the pre-borrowing `ProgressContext::new(answers.clone(), ..)`)
+/// compiling while they migrate.
+#[derive(Debug, Clone)]
+pub enum ProgressAnswers<'a> {
+    Borrowed(&'a Value),
+    Owned(Value),
+}
+
+impl ProgressAnswers<'_> {
+    fn as_object(&self) -> Option<&Map<String, Value>> {
+        match self {
+            Self::Borrowed(answers) => answers.as_object(),
+            Self::Owned(answers) => answers.as_object(),
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ProgressAnswers<'a> {
+    fn from(answers: &'a Value) -> Self {
+        Self::Borrowed(answers)
+    }
+}
+
+impl From<Value> for ProgressAnswers<'_> {
+    fn from(answers: Value) -> Self {
+        Self::Owned(answers)
+    }
+}
 
 /// Encapsulates runtime state for progress evaluation.
 #[derive(Debug, Clone)]
-pub struct ProgressContext {
-    answers: Map<String, Value>,
-    config: Value,
-    state: Value,
-    payload_out: Value,
-    secrets: Value,
-}
-
-impl ProgressContext {
-    pub fn new(answers: Value, ctx: &Value) -> Self {
-        let answers_map = answers.as_object().cloned().unwrap_or_default();
-        let default = || Value::Object(Map::new());
+pub struct ProgressContext<'a> {
+    answers: ProgressAnswers<'a>,
+    config: Option<&'a Value>,
+    state: Option<&'a Value>,
+    payload_out: Option<&'a Value>,
+    secrets: Option<&'a Value>,
+    required_only: Option<bool>,
+}
+
+impl<'a> ProgressContext<'a> {
+    /// Takes `answers` by reference (or by value, see [`ProgressAnswers`]) and
+    /// borrows the `config`/`state`/`payload_out`/`secrets` sections of `ctx`.
+    /// `ctx.progress.required_only` overrides the form's progress policy.
+    pub fn new(answers: impl Into<ProgressAnswers<'a>>, ctx: &'a Value) -> Self {
         Self {
-            answers: answers_map,
-            config: ctx.get("config").cloned().unwrap_or_else(default),
-            state: ctx.get("state").cloned().unwrap_or_else(default),
-            payload_out: ctx.get("payload_out").cloned().unwrap_or_else(default),
-            secrets: ctx.get("secrets").cloned().unwrap_or_else(default),
+            answers: answers.into(),
+            config: ctx.get("config"),
+            state: ctx.get("state"),
+            payload_out: ctx.get("payload_out"),
+            secrets: ctx.get("secrets"),
+            required_only: ctx
+                .pointer("/progress/required_only")
+                .and_then(Value::as_bool),
         }
     }
 
-    fn has_target(&self, target: StoreTarget, key: &str) -> bool {
-        match target {
-            StoreTarget::Answers => self.answers.contains_key(key),
-            StoreTarget::Config => self.config.get(key).is_some(),
-            StoreTarget::State => self.state.get(key).is_some(),
-            StoreTarget::PayloadOut => self.payload_out.get(key).is_some(),
-            StoreTarget::Secrets => self.secrets.get(key).is_some(),
+    /// The form's progress policy (or the default) with the overrides from
+    /// `ctx` applied.
+    pub fn policy(&self, spec: &FormSpec) -> ProgressPolicy {
+        let mut policy = spec.progress_policy.unwrap_or_default();
+        if let Some(required_only) = self.required_only {
+            policy.required_only = required_only;
         }
+        policy
+    }
+
+    fn answers(&self) -> Option<&Map<String, Value>> {
+        self.answers.as_object()
+    }
+
+    /// Expression context over the answers and the `state` and `config`
+    /// sections, for conditions such as `read_only_if`.
+    fn expression_context(&self) -> Value {
+        let answers = match &self.answers {
+            ProgressAnswers::Borrowed(answers) => answers,
+            ProgressAnswers::Owned(answers) => answers,
+        };
+        let mut roots = Map::new();
+        for (root, section) in [("state", self.state), ("config", self.config)] {
+            if let Some(section) = section {
+                roots.insert(root.into(), section.clone());
+            }
+        }
+        build_expression_context_with_ctx(answers, &Value::Object(roots))
+    }
+
+    fn has_target(&self, target: StoreTarget, key: &str) -> bool {
+        let section = match target {
+            StoreTarget::Answers => {
+                return self
+                    .answers()
+                    .is_some_and(|answers| answers.contains_key(key));
+            }
+            StoreTarget::Config => self.config,
+            StoreTarget::State => self.state,
+            StoreTarget::PayloadOut => self.payload_out,
+            StoreTarget::Secrets => self.secrets,
+        };
+        section.is_some_and(|section| section.get(key).is_some())
     }
 
     pub fn answered_count(&self, spec: &FormSpec, visibility: &VisibilityMap) -> usize {
+        let policy = self.policy(spec);
+        let mut expression_ctx = None;
+        spec.questions
+            .iter()
+            .filter(|question| {
+                visibility.get(&question.id).copied().unwrap_or(true)
+                    && in_scope(question, self, &policy, &mut expression_ctx)
+                    && is_answered(question, self, Some(&policy))
+            })
+            .count()
+    }
+
+    /// Visible questions progress is measured against: all of them, or the
+    /// required ones under `required_only`.
+    pub fn question_count(&self, spec: &FormSpec, visibility: &VisibilityMap) -> usize {
+        let policy = self.policy(spec);
+        let mut expression_ctx = None;
         spec.questions
             .iter()
             .filter(|question| {
                 visibility.get(&question.id).copied().unwrap_or(true)
-                    && is_answered(question, self, spec.progress_policy.as_ref())
+                    && in_scope(question, self, &policy, &mut expression_ctx)
             })
             .count()
     }
+
+    /// Visible questions still to be asked, in asking order: unanswered
+    /// (defaults count as answers when the policy says so), editable and not
+    /// filled from another store.
+    pub fn remaining_questions(&self, spec: &FormSpec, visibility: &VisibilityMap) -> Vec<String> {
+        let policy = self.policy(spec);
+        let mut expression_ctx = None;
+        spec.ordered_questions()
+            .into_iter()
+            .filter(|question| {
+                is_editable(question, self, visibility, &mut expression_ctx)
+                    && in_scope(question, self, &policy, &mut expression_ctx)
+                    && !present_elsewhere(question, self)
+                    && !is_answered(question, self, Some(&policy))
+            })
+            .map(|question| question.id.clone())
+            .collect()
+    }
 }
 
 pub fn next_question(
@@ -54,34 +172,169 @@ pub fn next_question(
     ctx: &ProgressContext,
     visibility: &VisibilityMap,
 ) -> Option<String> {
-    let progress_policy = spec.progress_policy.as_ref().copied().unwrap_or_default();
+    let progress_policy = ctx.policy(spec);
+    let mut expression_ctx = None;
 
-    for question in &spec.questions {
-        if !visibility.get(&question.id).copied().unwrap_or(true) {
-            continue;
-        }
+    spec.ordered_questions()
+        .into_iter()
+        .find(|question| {
+            is_editable(question, ctx, visibility, &mut expression_ctx)
+                && in_scope(question, ctx, &progress_policy, &mut expression_ctx)
+                && !should_skip(question, ctx, &progress_policy)
+        })
+        .map(|question| question.id.clone())
+}
+
+/// Where a flow stands for a set of answers, see [`next_question_in_flow`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FlowPosition {
+    /// The question step waiting for an answer, or the step a policy limit
+    /// stopped at; `None` once the flow ended.
+    pub step_id: Option<StepId>,
+    /// `AskQuestion`, `Completed` or `LimitReached`.
+    pub outcome: StepOutcome,
+    /// Message steps passed on the way, rendered, in order.
+    pub messages: Vec<String>,
+}
 
-        if should_skip(question, ctx, &progress_policy) {
-            continue;
+impl FlowPosition {
+    /// The question to ask next, if the flow waits for one.
+    pub fn question_id(&self) -> Option<&str> {
+        match &self.outcome {
+            StepOutcome::AskQuestion { question_id } => Some(question_id),
+            _ => None,
         }
+    }
+}
 
-        return Some(question.id.clone());
+/// [`next_question`] for a form driven by a flow: runs `flow` from its
+/// `entry` against `answers`, passing answered question steps, taking
+/// decisions and collecting messages, until a question needs an answer, an
+/// end step is reached or a `FlowPolicy` limit stops the run.
+///
+/// Without a policy limit, a decision that loops back over answered steps is
+/// a [`FlowError::Cycle`], since the same answers would take it round again.
+pub fn next_question_in_flow(
+    flow: &QAFlowSpec,
+    form: &FormSpec,
+    ctx: &Value,
+    answers: &Value,
+) -> Result<FlowPosition, FlowError> {
+    let limited = flow.policies.as_ref().is_some_and(FlowPolicy::has_limits);
+    let mut engine = FlowEngine::new(flow, form);
+    let mut messages = Vec::new();
+    let mut entered = BTreeSet::new();
+    loop {
+        let step_id = engine.current_step().map(str::to_string);
+        let outcome = engine.advance(answers, ctx)?;
+        match outcome {
+            StepOutcome::ShowMessage { text } => messages.push(text),
+            StepOutcome::Decided { .. } => {}
+            StepOutcome::AskQuestion { .. } => {
+                return Ok(FlowPosition {
+                    step_id: engine.current_step().map(str::to_string),
+                    outcome,
+                    messages,
+                });
+            }
+            StepOutcome::Completed => {
+                return Ok(FlowPosition {
+                    step_id: None,
+                    outcome,
+                    messages,
+                });
+            }
+            StepOutcome::LimitReached { ref step, .. } => {
+                return Ok(FlowPosition {
+                    step_id: Some(step.clone()),
+                    outcome,
+                    messages,
+                });
+            }
+        }
+        if let Some(step_id) = step_id
+            && !entered.insert(step_id.clone())
+            && !limited
+        {
+            return Err(FlowError::Cycle(step_id));
+        }
     }
+}
 
-    None
+/// The question asked before `current_id`, for a wizard's back button: the
+/// closest earlier one, in [`next_question`]'s order, that is visible,
+/// editable, not filled from another store and, under `required_only`,
+/// required. Answered questions are not skipped. `None` at the first
+/// question or when `current_id` is not a top-level question.
+pub fn previous_question(
+    spec: &FormSpec,
+    ctx: &ProgressContext,
+    visibility: &VisibilityMap,
+    current_id: &str,
+) -> Option<String> {
+    let questions = spec.ordered_questions();
+    let position = questions
+        .iter()
+        .position(|question| question.id == current_id)?;
+    let policy = ctx.policy(spec);
+    let mut expression_ctx = None;
+
+    questions[..position]
+        .iter()
+        .rev()
+        .find(|question| {
+            is_editable(question, ctx, visibility, &mut expression_ctx)
+                && in_scope(question, ctx, &policy, &mut expression_ctx)
+                && !present_elsewhere(question, ctx)
+        })
+        .map(|question| question.id.clone())
 }
 
-fn should_skip(
+/// Visible, not `computed` and not `read_only_if` locked; `expression_ctx`
+/// is built on the first `read_only_if`, most forms never need it.
+///
+/// Computed questions are never prompted: their values are materialized from
+/// their sources, and `computed_overridable` ones only take an answer the
+/// caller submits on its own.
+fn is_editable(
     question: &QuestionSpec,
     ctx: &ProgressContext,
-    policy: &crate::spec::form::ProgressPolicy,
+    visibility: &VisibilityMap,
+    expression_ctx: &mut Option<Value>,
 ) -> bool {
-    if question
+    if !visibility.get(&question.id).copied().unwrap_or(true) || question.computed.is_some() {
+        return false;
+    }
+    !(question.read_only_if.is_some()
+        && question.is_read_only_in(expression_ctx.get_or_insert_with(|| ctx.expression_context())))
+}
+
+/// Whether `policy` asks `question` at all: `required_only` leaves out the
+/// questions that are not required for the current answers.
+fn in_scope(
+    question: &QuestionSpec,
+    ctx: &ProgressContext,
+    policy: &ProgressPolicy,
+    expression_ctx: &mut Option<Value>,
+) -> bool {
+    !policy.required_only
+        || question.is_required(expression_ctx.get_or_insert_with(|| ctx.expression_context()))
+}
+
+fn present_elsewhere(question: &QuestionSpec, ctx: &ProgressContext) -> bool {
+    question
         .policy
         .skip_if_present_in
         .iter()
         .any(|target| ctx.has_target(*target, &question.id))
-    {
+}
+
+pub(crate) fn should_skip(
+    question: &QuestionSpec,
+    ctx: &ProgressContext,
+    policy: &crate::spec::form::ProgressPolicy,
+) -> bool {
+    if present_elsewhere(question, ctx) {
         return true;
     }
 
@@ -97,7 +350,9 @@ fn is_answered(
     ctx: &ProgressContext,
     policy: Option<&crate::spec::form::ProgressPolicy>,
 ) -> bool {
-    let has_answer = ctx.answers.contains_key(&question.id);
+    let has_answer = ctx
+        .answers()
+        .is_some_and(|answers| answers.contains_key(&question.id));
     let defaults_policy = policy
         .copied()
         .unwrap_or_else(crate::spec::form::ProgressPolicy::default);
@@ -106,12 +361,171 @@ fn is_answered(
         return true;
     }
 
-    if defaults_policy.autofill_defaults && question.default_value.is_some() {
-        if question.policy.editable_if_from_default {
-            return false;
+    // The default stands in for the answer without being written; questions
+    // that want their default confirmed are still asked.
+    defaults_policy.treat_default_as_answered
+        && question.default_value.is_some()
+        && !question.policy.editable_if_from_default
+}
+
+impl ProgressState {
+    /// Status record for `question_id`, if it has been tracked.
+    pub fn question(&self, question_id: &str) -> Option<&QuestionProgress> {
+        self.questions.get(question_id)
+    }
+
+    /// Counts a submission of `question_id` and returns the new total.
+    pub fn record_attempt(&mut self, question_id: &str) -> u32 {
+        let entry = self.questions.entry(question_id.to_string()).or_default();
+        entry.attempts += 1;
+        entry.attempts
+    }
+
+    /// Marks `question_id` as skipped; it stays skipped until answered.
+    pub fn mark_skipped(&mut self, question_id: &str) {
+        self.questions
+            .entry(question_id.to_string())
+            .or_default()
+            .status = QuestionStatus::Skipped;
+    }
+
+    /// Recomputes every question's status from `answers`, keeping attempt
+    /// counts and explicit skips, and upgrades the record to the current version.
+    ///
+    /// An answered question that becomes hidden moves to
+    /// [`QuestionStatus::Hidden`] and remembers its value in `last_value`.
+    pub fn refresh(&mut self, spec: &FormSpec, answers: &Value, ctx: &Value) {
+        let (answers, computed) = compute_answers(spec, answers, ctx);
+        let visibility =
+            resolve_visibility_with_ctx(spec, &answers, ctx, VisibilityMode::Visible).map;
+        let progress_ctx = ProgressContext::new(&answers, ctx);
+        let policy = spec.progress_policy.unwrap_or_default();
+
+        for question in &spec.questions {
+            let entry = self.questions.entry(question.id.clone()).or_default();
+            let answer = answers.get(&question.id);
+            if !visibility.get(&question.id).copied().unwrap_or(true) {
+                entry.status = QuestionStatus::Hidden;
+                if let Some(answer) = answer {
+                    entry.last_value = Some(answer.clone());
+                }
+                continue;
+            }
+            let computed_status = computed
+                .iter()
+                .find(|report| report.question_id == question.id)
+                .map(|report| report.status);
+            entry.status = if computed_status == Some(ComputedStatus::Computed) {
+                QuestionStatus::Computed
+            } else if answer.is_some() {
+                QuestionStatus::Answered
+            } else if entry.status == QuestionStatus::Skipped {
+                QuestionStatus::Skipped
+            } else if should_skip(question, &progress_ctx, &policy) {
+                QuestionStatus::AutoFilled
+            } else {
+                QuestionStatus::Pending
+            };
+            if answer.is_some() {
+                entry.last_value = None;
+            }
         }
-        return defaults_policy.treat_default_as_answered;
+        self.version = PROGRESS_STATE_VERSION;
     }
 
-    false
+    /// Session snapshot of a rendered wizard: the payload's form, spec
+    /// version, next question and completion, with `answers` (the caller's,
+    /// not the computed ones) and the time each answer was first seen.
+    pub fn from_payload(payload: &RenderPayload, answers: &Value) -> Self {
+        Self::from_payload_with(&SystemClock, payload, answers)
+    }
+
+    /// [`ProgressState::from_payload`] stamped with `clock`.
+    pub fn from_payload_with(clock: &dyn Clock, payload: &RenderPayload, answers: &Value) -> Self {
+        let mut state = Self::default();
+        state.snapshot(clock, payload, answers);
+        state
+    }
+
+    /// Updates this record to a new snapshot of the same session, keeping
+    /// attempt counts, explicit skips and the `answered_at` of answers that
+    /// did not change.
+    pub fn snapshot(&mut self, clock: &dyn Clock, payload: &RenderPayload, answers: &Value) {
+        let now = clock.now_rfc3339();
+        let previous = self.answers.take().unwrap_or(Value::Null);
+        for question in &payload.questions {
+            let entry = self.questions.entry(question.id.clone()).or_default();
+            let answer = answers.get(&question.id);
+            entry.answered_at = match answer {
+                Some(answer) if previous.get(&question.id) == Some(answer) => {
+                    entry.answered_at.take().or_else(|| Some(now.clone()))
+                }
+                Some(_) => Some(now.clone()),
+                None => None,
+            };
+            entry.status = if !question.visible {
+                if let Some(answer) = answer {
+                    entry.last_value = Some(answer.clone());
+                }
+                QuestionStatus::Hidden
+            } else if question.read_only && question.current_value.is_some() {
+                QuestionStatus::Computed
+            } else if answer.is_some() {
+                QuestionStatus::Answered
+            } else if entry.status == QuestionStatus::Skipped {
+                QuestionStatus::Skipped
+            } else {
+                QuestionStatus::Pending
+            };
+            if answer.is_some() && question.visible {
+                entry.last_value = None;
+            }
+        }
+        self.version = PROGRESS_STATE_VERSION;
+        self.form_id = Some(payload.form_id.clone());
+        self.spec_version = Some(payload.form_version.clone());
+        self.answers = Some(answers.clone());
+        self.current_question_id = payload.next_question_id.clone();
+        self.completed = payload.status == RenderStatus::Complete;
+        self.updated_at = Some(now);
+    }
+
+    /// Checks that a snapshot belongs to `spec` before resuming it: same
+    /// form id and the same spec version. Records without a form id or
+    /// version (plain `track_progress` ones) are accepted.
+    pub fn check_resumable(&self, spec: &FormSpec) -> Result<(), SessionError> {
+        if let Some(form_id) = &self.form_id
+            && *form_id != spec.id
+        {
+            return Err(SessionError::FormMismatch {
+                expected: spec.id.clone(),
+                found: form_id.clone(),
+            });
+        }
+        if let Some(version) = &self.spec_version
+            && *version != spec.version
+        {
+            return Err(SessionError::VersionMismatch {
+                form_id: spec.id.clone(),
+                expected: spec.version.clone(),
+                found: version.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A session snapshot that cannot be resumed with the current spec.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SessionError {
+    #[error("session belongs to form '{found}', not '{expected}'")]
+    FormMismatch { expected: String, found: String },
+    #[error(
+        "session was saved with version {found} of form '{form_id}', but the spec is version {expected}"
+    )]
+    VersionMismatch {
+        form_id: String,
+        expected: String,
+        found: String,
+    },
 }
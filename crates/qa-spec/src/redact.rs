@@ -0,0 +1,77 @@
+use serde_json::{Value, json};
+
+use crate::answers::AnswerSet;
+use crate::spec::form::FormSpec;
+use crate::spec::question::QuestionSpec;
+
+/// Key of the object that replaces a redacted answer: `{"$redacted": true}`.
+pub const REDACTED_KEY: &str = "$redacted";
+
+/// Marker stored in place of a secret value; its presence records that a value existed.
+pub fn redacted_marker() -> Value {
+    json!({ REDACTED_KEY: true })
+}
+
+/// Whether `value` is the marker produced by [`redact_answers`].
+pub fn is_redacted(value: &Value) -> bool {
+    value.get(REDACTED_KEY).and_then(Value::as_bool) == Some(true)
+}
+
+/// Replaces answers of `secret: true` questions (including list and object
+/// sub-fields) with [`redacted_marker`]. Unknown keys are left untouched.
+pub fn redact_answers(spec: &FormSpec, answers: &Value) -> Value {
+    let mut redacted = answers.clone();
+    if let Some(map) = redacted.as_object_mut() {
+        for question in &spec.questions {
+            if let Some(value) = map.get_mut(&question.id) {
+                redact_value(question, value);
+            }
+        }
+    }
+    redacted
+}
+
+pub(crate) fn redact_value(question: &QuestionSpec, value: &mut Value) {
+    if question.secret {
+        *value = redacted_marker();
+        return;
+    }
+    if let (Some(object), Some(entry)) = (&question.object, value.as_object_mut()) {
+        for field in &object.fields {
+            if let Some(field_value) = entry.get_mut(&field.id) {
+                redact_value(field, field_value);
+            }
+        }
+        return;
+    }
+    let (Some(list), Some(items)) = (&question.list, value.as_array_mut()) else {
+        return;
+    };
+    for item in items.iter_mut().filter_map(Value::as_object_mut) {
+        for field in &list.fields {
+            if let Some(field_value) = item.get_mut(&field.id) {
+                redact_value(field, field_value);
+            }
+        }
+    }
+}
+
+impl AnswerSet {
+    /// Copy of the answer set with secret answers replaced by [`redacted_marker`].
+    pub fn redacted(&self, spec: &FormSpec) -> AnswerSet {
+        AnswerSet {
+            answers: redact_answers(spec, &self.answers),
+            ..self.clone()
+        }
+    }
+
+    /// Indented JSON with secret answers redacted.
+    pub fn to_json_redacted(&self, spec: &FormSpec) -> Result<String, serde_json::Error> {
+        self.redacted(spec).to_json_pretty()
+    }
+
+    /// Canonical CBOR with secret answers redacted.
+    pub fn to_cbor_redacted(&self, spec: &FormSpec) -> Result<Vec<u8>, serde_cbor::Error> {
+        self.redacted(spec).to_cbor()
+    }
+}
@@ -1,15 +1,33 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+
+use serde::ser::{Error as _, Serialize, SerializeMap, Serializer};
 use serde_json::{Map, Value, json};
 
 use crate::{
     answers_schema,
-    computed::apply_computed_answers,
-    i18n::{ResolvedI18nMap, resolve_i18n_text_with_locale},
+    color::COLOR_SYNTAX,
+    compiled::Derived,
+    computed::{build_expression_context_with_ctx, materialize_computed},
+    duration::DURATION_SYNTAX,
+    i18n::{RenderLabels, ResolvedI18nMap, locale_strings, resolve_i18n_text_with_locale},
     progress::{ProgressContext, next_question},
+    redact::{is_redacted, redact_value, redacted_marker},
     spec::{
-        form::FormSpec,
-        question::{ListSpec, QuestionType},
+        flow::CardMode,
+        form::{DEFAULT_CARD_PAGE_SIZE, FormSpec},
+        question::{
+            ChoiceCatalog, Constraint, ListSpec, ObjectSpec, QuestionSpec, QuestionType,
+            StringFormat, rating_range,
+        },
+    },
+    theme::{ResolvedTheme, ThemeSpec},
+    time_of_day::{TIME_SYNTAX, format_time_of_day, seconds_of_day},
+    visibility::{
+        ListItemContexts, VisibilityCache, VisibilityMap, VisibilityMode, list_field_visible,
+        resolve_visibility_with_ctx,
     },
-    visibility::{VisibilityMode, resolve_visibility},
 };
 
 /// Status labels returned by the renderers.
@@ -35,10 +53,33 @@ impl RenderStatus {
 }
 
 /// Progress counters exposed to renderers.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct RenderProgress {
     pub answered: usize,
     pub total: usize,
+    /// Visible questions still to be asked, in asking order.
+    pub remaining: Vec<String>,
+    /// `answered` out of `total`, to one decimal; 100 when nothing is visible.
+    pub percent: f64,
+}
+
+impl RenderProgress {
+    /// Progress of `ctx`'s answers through the questions `visibility` shows.
+    pub fn measure(spec: &FormSpec, ctx: &ProgressContext, visibility: &VisibilityMap) -> Self {
+        let answered = ctx.answered_count(spec, visibility);
+        let total = ctx.question_count(spec, visibility);
+        let percent = if total == 0 {
+            100.0
+        } else {
+            (answered as f64 * 1000.0 / total as f64).round() / 10.0
+        };
+        Self {
+            answered,
+            total,
+            remaining: ctx.remaining_questions(spec, visibility),
+            percent,
+        }
+    }
 }
 
 /// Describes a single question for render outputs.
@@ -51,12 +92,27 @@ pub struct RenderQuestion {
     pub description_i18n_key: Option<String>,
     pub kind: QuestionType,
     pub required: bool,
+    /// Set for `computed` questions that cannot be overridden and while the
+    /// question's `read_only_if` holds.
+    pub read_only: bool,
     pub default: Option<String>,
     pub secret: bool,
     pub visible: bool,
     pub current_value: Option<Value>,
+    /// Length of the answer when `current_value` only holds its first
+    /// [`RenderOptions::max_current_value_items`] items.
+    pub current_value_total: Option<usize>,
     pub choices: Option<Vec<String>>,
+    /// Labels shown for `choices` in the payload's locale; choices without
+    /// one are shown as they are.
+    pub choice_labels: BTreeMap<String, String>,
+    /// Built-in list `choices` was expanded from, so UIs can use a native picker.
+    pub catalog: Option<ChoiceCatalog>,
+    pub constraint: Option<Constraint>,
     pub list: Option<ListSpec>,
+    pub object: Option<ObjectSpec>,
+    /// Visible sub-field ids for each entry of `current_value`, in entry order.
+    pub list_entry_fields: Vec<Vec<String>>,
 }
 
 /// Collected payload used by both text and JSON renderers.
@@ -69,8 +125,175 @@ pub struct RenderPayload {
     pub next_question_id: Option<String>,
     pub progress: RenderProgress,
     pub help: Option<String>,
+    /// Resolved flow message shown inline before the next question.
+    pub message: Option<String>,
     pub questions: Vec<RenderQuestion>,
     pub schema: Value,
+    /// `ctx.locale`, else the presentation's `default_locale`; picks the
+    /// spec's `i18n` strings and the renderers' [`RenderLabels`].
+    pub locale: Option<String>,
+    /// The presentation's theme, when it sets one.
+    pub theme: Option<ResolvedTheme>,
+}
+
+impl RenderPayload {
+    /// Fixed renderer words in the payload's locale.
+    pub fn labels(&self) -> &'static RenderLabels {
+        RenderLabels::for_locale(self.locale.as_deref())
+    }
+
+    /// Attach a resolved flow message (e.g. from `StepOutcome::ShowMessage`).
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// Default for [`RenderOptions::max_current_value_items`].
+pub const DEFAULT_MAX_CURRENT_VALUE_ITEMS: usize = 100;
+
+/// Adaptive Card schema version a card targets.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum CardVersion {
+    #[default]
+    #[serde(rename = "1.3")]
+    V1_3,
+    #[serde(rename = "1.5")]
+    V1_5,
+}
+
+impl CardVersion {
+    /// The card's `version` string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CardVersion::V1_3 => "1.3",
+            CardVersion::V1_5 => "1.5",
+        }
+    }
+}
+
+/// Adaptive Card features to use; the defaults give the 1.3 card hosts have
+/// always received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CardOptions {
+    pub version: CardVersion,
+    /// `Input.Number` with the constraint's `min`/`max` for integer and number
+    /// questions. Only 1.5 cards use it; 1.3 cards keep `Input.Text`.
+    pub use_native_number_input: bool,
+    /// `Input.Date`/`Input.Time` for date and time questions; otherwise they
+    /// are `Input.Text` with a format placeholder.
+    pub use_native_date_input: bool,
+    /// Leave hidden questions out of `section`/`form`/`page` cards. When off
+    /// they are included with `isVisible: false`, so hosts can toggle them.
+    pub compact: bool,
+    /// Questions per `page` card, overriding the presentation's `page_size`.
+    pub page_size: Option<usize>,
+    /// Zero-based page a `page` card shows; by default the page holding the
+    /// next question. Hosts set it to follow a card's previous action.
+    pub page: Option<usize>,
+}
+
+impl Default for CardOptions {
+    fn default() -> Self {
+        Self {
+            version: CardVersion::default(),
+            use_native_number_input: true,
+            use_native_date_input: true,
+            compact: true,
+            page_size: None,
+            page: None,
+        }
+    }
+}
+
+impl CardOptions {
+    /// Options targeting `version` with the default features.
+    pub fn for_version(version: CardVersion) -> Self {
+        Self {
+            version,
+            ..Self::default()
+        }
+    }
+
+    fn native_number_input(&self) -> bool {
+        self.use_native_number_input && self.version >= CardVersion::V1_5
+    }
+}
+
+/// Layout options for renderers that support more than one arrangement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub card_mode: CardMode,
+    /// Adaptive Card version and features.
+    pub card: CardOptions,
+    /// Questions per `page` card unless [`CardOptions::page_size`] is set.
+    pub page_size: usize,
+    /// Longest array embedded as a question's `current_value`. Longer answers
+    /// keep their first items and report their length in `current_value_total`;
+    /// `None` embeds them whole.
+    pub max_current_value_items: Option<usize>,
+    /// Embed secret answers as they are instead of `{"$redacted": true}`.
+    /// Only for trusted hosts: rendered output reaches chat surfaces and logs.
+    pub reveal_secrets: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            card_mode: CardMode::default(),
+            card: CardOptions::default(),
+            page_size: DEFAULT_CARD_PAGE_SIZE,
+            max_current_value_items: Some(DEFAULT_MAX_CURRENT_VALUE_ITEMS),
+            reveal_secrets: false,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Options derived from the form presentation defaults.
+    pub fn for_form(spec: &FormSpec) -> Self {
+        Self {
+            card_mode: spec.card_mode(),
+            page_size: spec.card_page_size(),
+            ..Self::default()
+        }
+    }
+
+    /// Change how many items of an array answer are embedded (`None` for all).
+    pub fn with_max_current_value_items(mut self, max_items: Option<usize>) -> Self {
+        self.max_current_value_items = max_items;
+        self
+    }
+
+    /// Embed secret answers unredacted (trusted hosts only).
+    pub fn with_reveal_secrets(mut self, reveal_secrets: bool) -> Self {
+        self.reveal_secrets = reveal_secrets;
+        self
+    }
+
+    /// Override the card layout (e.g. with the active flow step's mode).
+    pub fn with_card_mode(mut self, card_mode: CardMode) -> Self {
+        self.card_mode = card_mode;
+        self
+    }
+
+    /// Target another Adaptive Card version or feature set.
+    pub fn with_card_options(mut self, card: CardOptions) -> Self {
+        self.card = card;
+        self
+    }
 }
 
 /// Build the renderer payload from the specification, context, and answers.
@@ -78,6 +301,20 @@ pub fn build_render_payload(spec: &FormSpec, ctx: &Value, answers: &Value) -> Re
     build_render_payload_with_i18n(spec, ctx, answers, None)
 }
 
+/// [`build_render_payload`] in `locale` (`id`, `en-GB`) instead of
+/// `ctx.locale`: the spec's `i18n` strings for it and the matching
+/// [`RenderLabels`], with the default strings where it has none.
+pub fn build_render_payload_in_locale(
+    spec: &FormSpec,
+    ctx: &Value,
+    answers: &Value,
+    locale: &str,
+) -> RenderPayload {
+    let mut ctx = ctx.as_object().cloned().unwrap_or_default();
+    ctx.insert("locale".into(), Value::String(locale.to_string()));
+    build_render_payload(spec, &Value::Object(ctx), answers)
+}
+
 /// Build the renderer payload from the specification, context, and answers with optional i18n map.
 pub fn build_render_payload_with_i18n(
     spec: &FormSpec,
@@ -85,13 +322,100 @@ pub fn build_render_payload_with_i18n(
     answers: &Value,
     resolved_i18n: Option<&ResolvedI18nMap>,
 ) -> RenderPayload {
-    let computed_answers = apply_computed_answers(spec, answers);
-    let visibility = resolve_visibility(spec, &computed_answers, VisibilityMode::Visible);
-    let progress_ctx = ProgressContext::new(computed_answers.clone(), ctx);
-    let next_question_id = next_question(spec, &progress_ctx, &visibility);
+    build_render_payload_with_options(
+        spec,
+        ctx,
+        answers,
+        resolved_i18n,
+        &RenderOptions::for_form(spec),
+    )
+}
+
+/// Build the renderer payload, embedding current values as limited by `options`.
+pub fn build_render_payload_with_options(
+    spec: &FormSpec,
+    ctx: &Value,
+    answers: &Value,
+    resolved_i18n: Option<&ResolvedI18nMap>,
+    options: &RenderOptions,
+) -> RenderPayload {
+    let computed_answers = materialize_computed(spec, answers, ctx);
+    let visibility =
+        resolve_visibility_with_ctx(spec, &computed_answers, ctx, VisibilityMode::Visible).map;
+    render_computed(
+        spec,
+        None,
+        ctx,
+        computed_answers,
+        resolved_i18n,
+        options,
+        &visibility,
+    )
+}
+
+/// Like [`build_render_payload_with_options`], reusing a `visibility` already
+/// resolved for the computed answers instead of evaluating every `visible_if`
+/// again.
+pub fn build_render_payload_with_visibility(
+    spec: &FormSpec,
+    ctx: &Value,
+    answers: &Value,
+    resolved_i18n: Option<&ResolvedI18nMap>,
+    options: &RenderOptions,
+    visibility: &VisibilityMap,
+) -> RenderPayload {
+    let computed_answers = materialize_computed(spec, answers, ctx);
+    render_computed(
+        spec,
+        None,
+        ctx,
+        computed_answers,
+        resolved_i18n,
+        options,
+        visibility,
+    )
+}
+
+/// Like [`build_render_payload_with_options`], looking the visibility up in
+/// `cache` so rendering the same answers again skips every `visible_if`.
+pub fn build_render_payload_with_cache(
+    spec: &FormSpec,
+    ctx: &Value,
+    answers: &Value,
+    resolved_i18n: Option<&ResolvedI18nMap>,
+    options: &RenderOptions,
+    cache: &mut VisibilityCache,
+) -> RenderPayload {
+    let computed_answers = materialize_computed(spec, answers, ctx);
+    let visibility = &cache
+        .resolve_with_ctx(spec, &computed_answers, ctx, VisibilityMode::Visible)
+        .map;
+    render_computed(
+        spec,
+        None,
+        ctx,
+        computed_answers,
+        resolved_i18n,
+        options,
+        visibility,
+    )
+}
+
+/// Builds the payload from answers that already carry their computed values.
+/// `derived` comes from a [`crate::CompiledFormSpec`].
+pub(crate) fn render_computed(
+    spec: &FormSpec,
+    derived: Option<&Derived>,
+    ctx: &Value,
+    computed_answers: Value,
+    resolved_i18n: Option<&ResolvedI18nMap>,
+    options: &RenderOptions,
+    visibility: &VisibilityMap,
+) -> RenderPayload {
+    let progress_ctx = ProgressContext::new(&computed_answers, ctx);
+    let next_question_id = next_question(spec, &progress_ctx, visibility);
 
-    let answered = progress_ctx.answered_count(spec, &visibility);
-    let total = visibility.values().filter(|visible| **visible).count();
+    let progress = RenderProgress::measure(spec, &progress_ctx, visibility);
 
     let requested_locale = ctx.get("locale").and_then(Value::as_str);
     let default_locale = spec
@@ -99,48 +423,83 @@ pub fn build_render_payload_with_i18n(
         .as_ref()
         .and_then(|presentation| presentation.default_locale.as_deref());
 
+    let locale = requested_locale.or(default_locale);
+    let expression_ctx = build_expression_context_with_ctx(&computed_answers, ctx);
     let questions = spec
-        .questions
-        .iter()
-        .map(|question| RenderQuestion {
-            id: question.id.clone(),
-            title: resolve_i18n_text_with_locale(
-                &question.title,
-                question.title_i18n.as_ref(),
-                resolved_i18n,
-                requested_locale,
-                default_locale,
-            ),
-            description: resolve_description(
-                question.description.as_deref(),
-                question.description_i18n.as_ref(),
-                resolved_i18n,
-                requested_locale,
-                default_locale,
-            ),
-            title_i18n_key: question.title_i18n.as_ref().map(|text| text.key.clone()),
-            description_i18n_key: question
-                .description_i18n
-                .as_ref()
-                .map(|text| text.key.clone()),
-            kind: question.kind,
-            required: question.required,
-            default: question.default_value.clone(),
-            secret: question.secret,
-            visible: visibility.get(&question.id).copied().unwrap_or(true),
-            current_value: computed_answers.get(&question.id).cloned(),
-            choices: question.choices.clone(),
-            list: question.list.clone(),
+        .ordered_questions()
+        .into_iter()
+        .map(|question| {
+            let localized = locale_strings(question.i18n.as_ref(), locale);
+            let (current_value, current_value_total) = displayed_value(
+                question,
+                &computed_answers,
+                options.max_current_value_items,
+                options.reveal_secrets,
+            );
+            let list_entry_fields =
+                list_entry_fields(question, &computed_answers, current_value.as_ref());
+            RenderQuestion {
+                id: question.id.clone(),
+                title: resolve_i18n_text_with_locale(
+                    localized
+                        .and_then(|strings| strings.title.as_deref())
+                        .unwrap_or(&question.title),
+                    question.title_i18n.as_ref(),
+                    resolved_i18n,
+                    requested_locale,
+                    default_locale,
+                ),
+                description: resolve_description(
+                    localized
+                        .and_then(|strings| strings.description.as_deref())
+                        .or(question.description.as_deref()),
+                    question.description_i18n.as_ref(),
+                    resolved_i18n,
+                    requested_locale,
+                    default_locale,
+                ),
+                title_i18n_key: question.title_i18n.as_ref().map(|text| text.key.clone()),
+                description_i18n_key: question
+                    .description_i18n
+                    .as_ref()
+                    .map(|text| text.key.clone()),
+                kind: question.kind,
+                required: question.is_required(&expression_ctx),
+                read_only: question.is_read_only_in(&expression_ctx),
+                default: question.default_value.clone(),
+                secret: question.secret,
+                visible: visibility.get(&question.id).copied().unwrap_or(true),
+                current_value,
+                current_value_total,
+                choices: question.choices.clone(),
+                choice_labels: localized
+                    .map(|strings| strings.choices.clone())
+                    .unwrap_or_default(),
+                catalog: question.choices_catalog,
+                constraint: question.constraint.clone(),
+                list: question.list.clone(),
+                object: question.object.clone(),
+                list_entry_fields,
+            }
         })
         .collect::<Vec<_>>();
 
-    let help = spec
-        .presentation
-        .as_ref()
-        .and_then(|presentation| presentation.intro.clone())
+    let form_strings = locale_strings(spec.i18n.as_ref(), locale);
+    let help = form_strings
+        .and_then(|strings| strings.help.clone())
+        .or_else(|| {
+            spec.presentation
+                .as_ref()
+                .and_then(|presentation| presentation.intro.clone())
+        })
+        .or_else(|| form_strings.and_then(|strings| strings.description.clone()))
         .or_else(|| spec.description.clone());
 
-    let schema = answers_schema::generate(spec, &visibility);
+    let schema = answers_schema::generate_with(
+        spec,
+        derived.map(|derived| derived.question_schemas.as_slice()),
+        visibility,
+    );
 
     let status = if next_question_id.is_some() {
         RenderStatus::NeedInput
@@ -150,151 +509,476 @@ pub fn build_render_payload_with_i18n(
 
     RenderPayload {
         form_id: spec.id.clone(),
-        form_title: spec.title.clone(),
+        form_title: form_strings
+            .and_then(|strings| strings.title.clone())
+            .unwrap_or_else(|| spec.title.clone()),
         form_version: spec.version.clone(),
         status,
         next_question_id,
-        progress: RenderProgress { answered, total },
+        progress,
         help,
+        message: None,
         questions,
         schema,
+        locale: locale.map(str::to_string),
+        theme: spec
+            .presentation
+            .as_ref()
+            .and_then(|presentation| presentation.theme.as_ref())
+            .map(ThemeSpec::resolve),
     }
 }
 
 /// Render the payload as a structured JSON-friendly value.
 pub fn render_json_ui(payload: &RenderPayload) -> Value {
-    let questions = payload
-        .questions
-        .iter()
-        .map(|question| {
-            let mut map = Map::new();
-            map.insert("id".into(), Value::String(question.id.clone()));
-            map.insert("title".into(), Value::String(question.title.clone()));
-            map.insert(
-                "description".into(),
-                question
-                    .description
-                    .clone()
-                    .map(Value::String)
-                    .unwrap_or(Value::Null),
-            );
-            map.insert(
-                "type".into(),
-                Value::String(question_type_label(question.kind).to_string()),
-            );
-            map.insert("required".into(), Value::Bool(question.required));
-            if let Some(default) = &question.default {
-                map.insert("default".into(), Value::String(default.clone()));
-            }
-            if let Some(current_value) = &question.current_value {
-                map.insert("current_value".into(), current_value.clone());
-            }
-            if let Some(choices) = &question.choices {
-                map.insert(
-                    "choices".into(),
-                    Value::Array(
-                        choices
-                            .iter()
-                            .map(|choice| Value::String(choice.clone()))
-                            .collect(),
-                    ),
-                );
-            }
-            map.insert("visible".into(), Value::Bool(question.visible));
-            map.insert("secret".into(), Value::Bool(question.secret));
-            if let Some(list) = &question.list
-                && let Ok(list_value) = serde_json::to_value(list)
-            {
-                map.insert("list".into(), list_value);
-            }
-            Value::Object(map)
-        })
-        .collect::<Vec<_>>();
+    serde_json::to_value(JsonUi(payload)).expect("JSON UI serializes with string keys only")
+}
 
-    json!({
-        "form_id": payload.form_id,
-        "form_title": payload.form_title,
-        "form_version": payload.form_version,
-        "status": payload.status.as_str(),
-        "next_question_id": payload.next_question_id,
-        "progress": {
-            "answered": payload.progress.answered,
-            "total": payload.progress.total,
-        },
-        "help": payload.help,
-        "questions": questions,
-        "schema": payload.schema,
-    })
+/// Stream the [`render_json_ui`] document into `writer` without building it
+/// in memory first; the output is byte-identical to serializing
+/// [`render_json_ui`]'s value.
+pub fn render_json_ui_to<W: io::Write>(
+    payload: &RenderPayload,
+    writer: W,
+) -> serde_json::Result<()> {
+    serde_json::to_writer(writer, &JsonUi(payload))
+}
+
+/// Serializes a payload as the JSON UI document. Keys are emitted in sorted
+/// order so streamed output matches the `serde_json::Map` of
+/// [`render_json_ui`]; the schema and answers are serialized in place.
+struct JsonUi<'a>(&'a RenderPayload);
+
+impl Serialize for JsonUi<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let payload = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("form_id", &payload.form_id)?;
+        map.serialize_entry("form_title", &payload.form_title)?;
+        map.serialize_entry("form_version", &payload.form_version)?;
+        map.serialize_entry("help", &payload.help)?;
+        if let Some(message) = &payload.message {
+            map.serialize_entry("message", message)?;
+        }
+        map.serialize_entry("next_question_id", &payload.next_question_id)?;
+        map.serialize_entry(
+            "progress",
+            &json!({
+                "answered": payload.progress.answered,
+                "percent": payload.progress.percent,
+                "remaining": payload.progress.remaining,
+                "total": payload.progress.total,
+            }),
+        )?;
+        map.serialize_entry("questions", &JsonUiQuestions(&payload.questions))?;
+        map.serialize_entry("schema", &payload.schema)?;
+        map.serialize_entry("status", payload.status.as_str())?;
+        if let Some(theme) = &payload.theme {
+            // Through a `Value` so the theme's keys come out sorted too.
+            let theme = serde_json::to_value(theme).map_err(S::Error::custom)?;
+            map.serialize_entry("theme", &theme)?;
+        }
+        map.end()
+    }
+}
+
+struct JsonUiQuestions<'a>(&'a [RenderQuestion]);
+
+impl Serialize for JsonUiQuestions<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(JsonUiQuestion))
+    }
+}
+
+struct JsonUiQuestion<'a>(&'a RenderQuestion);
+
+impl Serialize for JsonUiQuestion<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let question = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(catalog) = question.catalog {
+            map.serialize_entry("catalog", catalog.as_str())?;
+        }
+        if !question.choice_labels.is_empty() {
+            map.serialize_entry("choice_labels", &question.choice_labels)?;
+        }
+        if let Some(choices) = &question.choices {
+            map.serialize_entry("choices", choices)?;
+        }
+        // Constraint, list, and object specs are small; going through `Value` keeps
+        // their keys sorted like the rest of the document.
+        if let Some(constraint) = &question.constraint
+            && let Ok(constraint_value) = serde_json::to_value(constraint)
+        {
+            map.serialize_entry("constraint", &constraint_value)?;
+        }
+        if let Some(current_value) = &question.current_value {
+            map.serialize_entry("current_value", current_value)?;
+        }
+        if let Some(total) = question.current_value_total {
+            map.serialize_entry("current_value_total", &total)?;
+        }
+        if let Some(default) = &question.default {
+            map.serialize_entry("default", default)?;
+        }
+        map.serialize_entry("description", &question.description)?;
+        if question.secret {
+            // The value itself is redacted; UIs can still show "already set".
+            map.serialize_entry("has_value", &question.current_value.is_some())?;
+        }
+        map.serialize_entry("id", &question.id)?;
+        if let Some(list) = &question.list
+            && let Ok(list_value) = serde_json::to_value(list)
+        {
+            map.serialize_entry("list", &list_value)?;
+        }
+        if let Some(object) = &question.object
+            && let Ok(object_value) = serde_json::to_value(object)
+        {
+            map.serialize_entry("object", &object_value)?;
+        }
+        if matches!(question.kind, QuestionType::Rating) {
+            let range = rating_range(question.constraint.as_ref());
+            map.serialize_entry(
+                "range",
+                &json!({ "min": range.start(), "max": range.end() }),
+            )?;
+        }
+        if question.read_only {
+            map.serialize_entry("read_only", &true)?;
+        }
+        map.serialize_entry("required", &question.required)?;
+        map.serialize_entry("secret", &question.secret)?;
+        map.serialize_entry("title", &question.title)?;
+        map.serialize_entry("type", question_type_label(question.kind))?;
+        map.serialize_entry("visible", &question.visible)?;
+        map.end()
+    }
 }
 
 /// Render the payload as human-friendly text.
 pub fn render_text(payload: &RenderPayload) -> String {
-    let mut lines = Vec::new();
-    lines.push(format!(
-        "Form: {} ({})",
-        payload.form_title, payload.form_id
-    ));
-    lines.push(format!(
-        "Status: {} ({}/{})",
+    let mut text = String::new();
+    render_text_to(payload, &mut text).expect("writing to a String cannot fail");
+    text
+}
+
+/// Write the [`render_text`] output into `out` line by line.
+pub fn render_text_to<W: fmt::Write>(payload: &RenderPayload, out: &mut W) -> fmt::Result {
+    let labels = payload.labels();
+    let mut lines = Lines { out, first: true };
+    lines.line(format_args!(
+        "{}: {} ({})",
+        labels.form, payload.form_title, payload.form_id
+    ))?;
+    lines.line(format_args!(
+        "{}: {} ({}/{})",
+        labels.status,
         payload.status.as_str(),
         payload.progress.answered,
         payload.progress.total
-    ));
+    ))?;
     if let Some(help) = &payload.help {
-        lines.push(format!("Help: {}", help));
+        lines.line(format_args!("{}: {}", labels.help, help))?;
+    }
+    if let Some(message) = &payload.message {
+        lines.line(format_args!("{}", message))?;
     }
 
     if let Some(next_question) = &payload.next_question_id {
-        lines.push(format!("Next question: {}", next_question));
+        lines.line(format_args!("{}: {}", labels.next_question, next_question))?;
         if let Some(question) = payload
             .questions
             .iter()
             .find(|question| &question.id == next_question)
         {
-            lines.push(format!("  Title: {}", question.title));
+            lines.line(format_args!("  {}: {}", labels.title, question.title))?;
             if let Some(description) = &question.description {
-                lines.push(format!("  Description: {}", description));
+                lines.line(format_args!("  {}: {}", labels.description, description))?;
             }
             if question.required {
-                lines.push("  Required: yes".to_string());
+                lines.line(format_args!("  {}: {}", labels.required, labels.yes))?;
             }
             if let Some(default) = &question.default {
-                lines.push(format!("  Default: {}", default));
+                if matches!(question.kind, QuestionType::Markdown) {
+                    lines.line(format_args!(
+                        "  {}: {}",
+                        labels.default,
+                        markdown_preview(default)
+                    ))?;
+                } else {
+                    lines.line(format_args!("  {}: {}", labels.default, default))?;
+                }
             }
-            if let Some(value) = &question.current_value {
-                lines.push(format!("  Current value: {}", value_to_display(value)));
+            for (label, detail) in input_details(question) {
+                lines.line(format_args!("  {}: {}", label, detail))?;
+            }
+            if let Some(value) = current_value_display(question) {
+                lines.line(format_args!("  {}: {}", labels.current_value, value))?;
             }
         }
     } else {
-        lines.push("All visible questions are answered.".to_string());
+        lines.line(format_args!("{}", labels.all_answered))?;
     }
 
-    lines.push("Visible questions:".to_string());
+    lines.line(format_args!("{}:", labels.visible_questions))?;
     for question in payload.questions.iter().filter(|question| question.visible) {
-        let mut entry = format!(" - {} ({})", question.id, question.title);
+        lines.line(format_args!(" - {} ({})", question.id, question.title))?;
         if question.required {
-            entry.push_str(" [required]");
+            write!(lines.out, " [{}]", labels.required_tag)?;
         }
-        if let Some(current_value) = &question.current_value {
-            entry.push_str(&format!(" = {}", value_to_display(current_value)));
+        if let Some(current_value) = current_value_display(question) {
+            write!(lines.out, " = {}", current_value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Newline-separated output without a trailing newline.
+struct Lines<'a, W> {
+    out: &'a mut W,
+    first: bool,
+}
+
+impl<W: fmt::Write> Lines<'_, W> {
+    fn line(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        if !std::mem::take(&mut self.first) {
+            self.out.write_char('\n')?;
         }
-        lines.push(entry);
+        self.out.write_fmt(args)
     }
+}
+
+/// What a question accepts beyond its type, as labelled rows: bounds, string
+/// format, precision, step, rating scale, entry syntax and object fields.
+fn input_details(question: &RenderQuestion) -> Vec<(&'static str, String)> {
+    let mut details = Vec::new();
+    let constraint = question.constraint.as_ref();
+    if let Some(bounds) = constraint.and_then(|constraint| {
+        if matches!(question.kind, QuestionType::Time) {
+            constraint.describe_bounds_with(|seconds| format_time_of_day(seconds as u32))
+        } else {
+            constraint.describe_bounds()
+        }
+    }) {
+        details.push(("Allowed", bounds));
+    }
+    if let Some(format) = constraint.and_then(|constraint| constraint.format) {
+        details.push(("Format", format.to_string()));
+    }
+    if let Some(places) = constraint.and_then(|constraint| constraint.decimal_places) {
+        details.push(("Precision", format!("{} decimal places", places)));
+    }
+    if let Some(step) = constraint.and_then(|constraint| constraint.multiple_of) {
+        details.push(("Multiple of", step.to_string()));
+    }
+    match question.kind {
+        QuestionType::Rating => {
+            let range = rating_range(constraint);
+            details.push(("Scale", format!("{}-{}", range.start(), range.end())));
+        }
+        QuestionType::Duration => details.push(("Syntax", DURATION_SYNTAX.to_string())),
+        QuestionType::Color => details.push(("Syntax", COLOR_SYNTAX.to_string())),
+        QuestionType::Time => details.push(("Syntax", TIME_SYNTAX.to_string())),
+        _ => {}
+    }
+    if let Some(object) = &question.object {
+        let fields = object
+            .fields
+            .iter()
+            .map(|field| field.id.as_str())
+            .collect::<Vec<_>>();
+        details.push(("Fields", fields.join(", ")));
+    }
+    details
+}
+
+/// Render the payload as Markdown for chat tools and documentation: a title,
+/// a progress line, a table of the visible questions and a quoted block
+/// describing the next question.
+pub fn render_markdown(payload: &RenderPayload) -> String {
+    let mut out = String::new();
+    render_markdown_to(payload, &mut out).expect("writing to a String cannot fail");
+    out
+}
+
+fn render_markdown_to<W: fmt::Write>(payload: &RenderPayload, out: &mut W) -> fmt::Result {
+    let labels = payload.labels();
+    writeln!(out, "## {}", markdown_inline(&payload.form_title))?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "**{}:** {} ({}%) · `{}`",
+        labels.progress,
+        labels
+            .answered_of_total
+            .replace("{answered}", &payload.progress.answered.to_string())
+            .replace("{total}", &payload.progress.total.to_string()),
+        payload.progress.percent,
+        payload.status.as_str()
+    )?;
+    if let Some(help) = &payload.help {
+        writeln!(out)?;
+        writeln!(out, "_{}_", markdown_inline(help))?;
+    }
+    if let Some(message) = &payload.message {
+        writeln!(out)?;
+        writeln!(out, "{}", message)?;
+    }
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "| {} | {} | {} | {} |",
+        labels.question, labels.kind, labels.required, labels.value
+    )?;
+    writeln!(out, "| --- | --- | --- | --- |")?;
+    for question in payload.questions.iter().filter(|question| question.visible) {
+        writeln!(
+            out,
+            "| {} (`{}`) | {} | {} | {} |",
+            markdown_cell(&question.title),
+            question.id,
+            question_type_label(question.kind),
+            if question.required { labels.yes } else { "" },
+            current_value_display(question)
+                .map(|value| markdown_cell(&value))
+                .unwrap_or_default()
+        )?;
+    }
+
+    writeln!(out)?;
+    let next = payload.next_question_id.as_ref().and_then(|next_question| {
+        payload
+            .questions
+            .iter()
+            .find(|question| &question.id == next_question)
+    });
+    let Some(question) = next else {
+        return write!(out, "{}", labels.all_answered);
+    };
+    write!(
+        out,
+        "> **{}:** {} (`{}`)",
+        labels.next_question,
+        markdown_inline(&question.title),
+        question.id
+    )?;
+    if let Some(description) = &question.description {
+        write!(out, "\n>")?;
+        for line in description.lines() {
+            write!(out, "\n> {}", line)?;
+        }
+    }
+    write!(out, "\n>")?;
+    write!(
+        out,
+        "\n> - {}: {}",
+        labels.kind,
+        question_type_label(question.kind)
+    )?;
+    if question.required {
+        write!(out, "\n> - {}: {}", labels.required, labels.yes)?;
+    }
+    if let Some(choices) = &question.choices {
+        let choices = choices
+            .iter()
+            .map(|choice| match question.choice_labels.get(choice) {
+                Some(label) => format!("{} (`{}`)", markdown_inline(label), choice),
+                None => format!("`{}`", choice),
+            })
+            .collect::<Vec<_>>();
+        write!(out, "\n> - {}: {}", labels.choices, choices.join(", "))?;
+    }
+    if let Some(default) = &question.default {
+        let default = if matches!(question.kind, QuestionType::Markdown) {
+            markdown_preview(default)
+        } else {
+            default.clone()
+        };
+        write!(
+            out,
+            "\n> - {}: {}",
+            labels.default,
+            markdown_inline(&default)
+        )?;
+    }
+    for (label, detail) in input_details(question) {
+        write!(out, "\n> - {}: {}", label, markdown_inline(&detail))?;
+    }
+    if let Some(list) = &question.list {
+        let fields = list
+            .fields
+            .iter()
+            .map(|field| format!("`{}`", field.id))
+            .collect::<Vec<_>>();
+        write!(out, "\n> - Entry fields: {}", fields.join(", "))?;
+    }
+    if let Some(value) = current_value_display(question) {
+        write!(
+            out,
+            "\n> - {}: {}",
+            labels.current_value,
+            markdown_inline(&value)
+        )?;
+    }
+    Ok(())
+}
 
-    lines.join("\n")
+/// Text on one Markdown line: line breaks become spaces.
+fn markdown_inline(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Text for a table cell: on one line, with `|` escaped so it cannot end the
+/// cell.
+fn markdown_cell(text: &str) -> String {
+    markdown_inline(text).replace('|', "\\|")
 }
 
 /// Render the payload as an Adaptive Card v1.3 transport.
 pub fn render_card(payload: &RenderPayload) -> Value {
+    render_card_with_options(payload, &RenderOptions::default())
+}
+
+/// Render the payload as an Adaptive Card using the given layout options.
+///
+/// `section` and `form` card modes render several inputs with a single submit
+/// that maps to a batch patch or `submit_all` respectively.
+pub fn render_card_with_options(payload: &RenderPayload, options: &RenderOptions) -> Value {
+    let labels = payload.labels();
     let mut body = Vec::new();
 
-    body.push(json!({
+    let title = json!({
         "type": "TextBlock",
         "text": payload.form_title,
         "weight": "Bolder",
         "size": "Large",
         "wrap": true,
-    }));
+    });
+    match &payload.theme {
+        // Cards cannot take the accent color itself; hosts map the `accent`
+        // container style to it.
+        Some(theme) => {
+            let mut header = Vec::new();
+            if let Some(logo) = &theme.theme.logo_url {
+                header.push(json!({
+                    "type": "Image",
+                    "url": logo,
+                    "altText": payload.form_title,
+                    "size": "Small",
+                }));
+            }
+            header.push(title);
+            body.push(json!({
+                "type": "Container",
+                "style": "accent",
+                "bleed": true,
+                "items": header,
+            }));
+        }
+        None => body.push(title),
+    }
 
     if let Some(help) = &payload.help {
         body.push(json!({
@@ -304,86 +988,491 @@ pub fn render_card(payload: &RenderPayload) -> Value {
         }));
     }
 
+    if let Some(message) = &payload.message {
+        body.push(json!({
+            "type": "TextBlock",
+            "text": message,
+            "wrap": true,
+        }));
+    }
+
     body.push(json!({
         "type": "FactSet",
         "facts": [
-            { "title": "Answered", "value": payload.progress.answered.to_string() },
-            { "title": "Total", "value": payload.progress.total.to_string() }
+            { "title": labels.answered, "value": payload.progress.answered.to_string() },
+            { "title": labels.total, "value": payload.progress.total.to_string() },
+            { "title": labels.complete, "value": format!("{}%", payload.progress.percent) }
         ]
     }));
 
     let mut actions = Vec::new();
 
-    if let Some(question_id) = &payload.next_question_id {
-        if let Some(question) = payload
+    if let Some(question) = payload.next_question_id.as_ref().and_then(|question_id| {
+        payload
             .questions
             .iter()
             .find(|question| &question.id == question_id)
-        {
-            let mut items = Vec::new();
-            items.push(json!({
-                "type": "TextBlock",
-                "text": question.title,
-                "weight": "Bolder",
-                "wrap": true,
-            }));
-            if let Some(description) = &question.description {
-                items.push(json!({
+    }) {
+        match options.card_mode {
+            CardMode::Section | CardMode::Form => {
+                let grouped = payload
+                    .questions
+                    .iter()
+                    .filter(|candidate| {
+                        options.card_mode == CardMode::Form
+                            || section_of(&candidate.id) == section_of(&question.id)
+                    })
+                    .collect::<Vec<_>>();
+                for candidate in &grouped {
+                    if candidate.visible {
+                        body.push(question_container(candidate, &options.card));
+                    } else if !options.card.compact {
+                        let mut container = question_container(candidate, &options.card);
+                        container["isVisible"] = Value::Bool(false);
+                        body.push(container);
+                    }
+                }
+
+                let qa = if options.card_mode == CardMode::Form {
+                    json!({
+                        "formId": payload.form_id,
+                        "mode": "submit_all",
+                    })
+                } else {
+                    json!({
+                        "formId": payload.form_id,
+                        "mode": "patch_batch",
+                        "questionIds": grouped
+                            .iter()
+                            .filter(|candidate| candidate.visible)
+                            .map(|candidate| candidate.id.as_str())
+                            .collect::<Vec<_>>(),
+                    })
+                };
+                actions.push(json!({
+                    "type": "Action.Submit",
+                    "title": labels.submit_action,
+                    "data": { "qa": qa }
+                }));
+            }
+            CardMode::Page => {
+                let pool = payload
+                    .questions
+                    .iter()
+                    .filter(|candidate| candidate.visible || !options.card.compact)
+                    .collect::<Vec<_>>();
+                let size = options.card.page_size.unwrap_or(options.page_size).max(1);
+                let pages = pool.len().div_ceil(size).max(1);
+                let page = options
+                    .card
+                    .page
+                    .unwrap_or_else(|| {
+                        pool.iter()
+                            .position(|candidate| candidate.id == question.id)
+                            .unwrap_or_default()
+                            / size
+                    })
+                    .min(pages - 1);
+                let shown = pool.iter().skip(page * size).take(size).collect::<Vec<_>>();
+
+                body.push(json!({
                     "type": "TextBlock",
-                    "text": description,
+                    "text": labels
+                        .page_of_pages
+                        .replace("{page}", &(page + 1).to_string())
+                        .replace("{pages}", &pages.to_string()),
+                    "isSubtle": true,
                     "wrap": true,
-                    "spacing": "Small",
                 }));
-            }
-            items.push(question_input(question));
+                for candidate in &shown {
+                    let mut container = question_container(candidate, &options.card);
+                    if !candidate.visible {
+                        container["isVisible"] = Value::Bool(false);
+                    }
+                    body.push(container);
+                }
 
-            body.push(json!({
-                "type": "Container",
-                "items": items,
-            }));
+                if page > 0 {
+                    actions.push(json!({
+                        "type": "Action.Submit",
+                        "title": labels.previous_action,
+                        "associatedInputs": "none",
+                        "data": {
+                            "qa": {
+                                "formId": payload.form_id,
+                                "mode": "page",
+                                "page": page - 1,
+                            }
+                        }
+                    }));
+                }
+                let mut qa = json!({
+                    "formId": payload.form_id,
+                    "mode": "patch_batch",
+                    "questionIds": shown
+                        .iter()
+                        .filter(|candidate| candidate.visible)
+                        .map(|candidate| candidate.id.as_str())
+                        .collect::<Vec<_>>(),
+                });
+                let last = page + 1 == pages;
+                if !last {
+                    qa["page"] = json!(page + 1);
+                }
+                actions.push(json!({
+                    "type": "Action.Submit",
+                    "title": if last { labels.submit_action } else { labels.next_action },
+                    "data": { "qa": qa }
+                }));
+            }
+            CardMode::Text | CardMode::Json | CardMode::Card => {
+                body.push(question_container(question, &options.card));
 
-            actions.push(json!({
-                "type": "Action.Submit",
-                "title": "Next ➡️",
-                "data": {
-                    "qa": {
-                        "formId": payload.form_id,
-                        "mode": "patch",
-                        "questionId": question.id,
-                        "field": "answer"
+                actions.push(json!({
+                    "type": "Action.Submit",
+                    "title": labels.next_action,
+                    "data": {
+                        "qa": {
+                            "formId": payload.form_id,
+                            "mode": "patch",
+                            "questionId": question.id,
+                            "field": "answer"
+                        }
                     }
-                }
-            }));
+                }));
+            }
         }
-    } else {
+    } else if payload.next_question_id.is_none() {
         body.push(json!({
             "type": "TextBlock",
-            "text": "All visible questions are answered.",
+            "text": labels.all_answered,
             "wrap": true,
         }));
     }
 
-    json!({
+    let mut card = json!({
         "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
         "type": "AdaptiveCard",
-        "version": "1.3",
+        "version": options.card.version.as_str(),
         "body": body,
         "actions": actions,
+    });
+    if let Some(url) = payload
+        .theme
+        .as_ref()
+        .and_then(|theme| theme.theme.background_image.as_ref())
+    {
+        card["backgroundImage"] = json!({ "url": url });
+    }
+    card
+}
+
+fn question_container(question: &RenderQuestion, card: &CardOptions) -> Value {
+    if question.read_only {
+        return read_only_container(question);
+    }
+    let mut items = Vec::new();
+    items.push(json!({
+        "type": "TextBlock",
+        "text": question.title,
+        "weight": "Bolder",
+        "wrap": true,
+    }));
+    if let Some(description) = &question.description {
+        items.push(json!({
+            "type": "TextBlock",
+            "text": description,
+            "wrap": true,
+            "spacing": "Small",
+        }));
+    }
+    items.push(question_input(question, card));
+    items.extend(list_input(question));
+    items.extend(markdown_content(question));
+    items.extend(list_entry_facts(question));
+
+    json!({
+        "type": "Container",
+        "items": items,
+    })
+}
+
+/// A read-only question as a fact row with its answer (else its default)
+/// instead of an input, so cards never offer to edit it.
+fn read_only_container(question: &RenderQuestion) -> Value {
+    let value = match (&question.current_value, &question.default) {
+        (Some(value), _) => value_to_display(value),
+        (None, Some(default)) => default.clone(),
+        (None, None) => String::new(),
+    };
+    let mut items = vec![json!({
+        "type": "FactSet",
+        "facts": [{ "title": question.title, "value": value }],
+    })];
+    if let Some(description) = &question.description {
+        items.push(json!({
+            "type": "TextBlock",
+            "text": description,
+            "wrap": true,
+            "spacing": "Small",
+        }));
+    }
+    json!({
+        "type": "Container",
+        "items": items,
     })
 }
 
-fn question_input(question: &RenderQuestion) -> Value {
+/// The current answer of a `markdown` question, shown as formatted text
+/// rather than squeezed into the input box.
+fn markdown_content(question: &RenderQuestion) -> Option<Value> {
+    if !matches!(question.kind, QuestionType::Markdown) {
+        return None;
+    }
+    let value = question.current_value.as_ref()?;
+    Some(json!({
+        "type": "TextBlock",
+        "text": value_to_display(value),
+        "wrap": true,
+        "spacing": "Small",
+    }))
+}
+
+/// One fact set per list entry, limited to the fields visible for that entry.
+/// Secret fields are never echoed back.
+fn list_entry_facts(question: &RenderQuestion) -> Vec<Value> {
+    let is_secret = |id: &str| {
+        question.list.as_ref().is_some_and(|list| {
+            list.fields
+                .iter()
+                .any(|field| field.id == id && field.secret)
+        })
+    };
+    let entries = question
+        .current_value
+        .as_ref()
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    entries
+        .iter()
+        .zip(&question.list_entry_fields)
+        .map(|(entry, fields)| {
+            let facts = fields
+                .iter()
+                .filter(|field| !is_secret(field))
+                .filter_map(|field| {
+                    entry.get(field).map(|value| {
+                        json!({
+                            "title": field,
+                            "value": value_to_display(value),
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "type": "FactSet",
+                "facts": facts,
+                "spacing": "Small",
+            })
+        })
+        .collect()
+}
+
+/// The answer to `question` as shown to users, plus the answer's full length
+/// when it was cut to `max_items`.
+///
+/// Payloads reach chat surfaces and logs, so secret values never leave as
+/// current values unless `reveal_secrets` is set.
+fn displayed_value(
+    question: &QuestionSpec,
+    answers: &Value,
+    max_items: Option<usize>,
+    reveal_secrets: bool,
+) -> (Option<Value>, Option<usize>) {
+    let Some(value) = answers.get(&question.id) else {
+        return (None, None);
+    };
+    if question.secret && !reveal_secrets {
+        return (Some(redacted_marker()), None);
+    }
+    let (mut shown, total) = match (value.as_array(), max_items) {
+        (Some(items), Some(max_items)) if items.len() > max_items => {
+            (Value::Array(items[..max_items].to_vec()), Some(items.len()))
+        }
+        _ => (value.clone(), None),
+    };
+    if !reveal_secrets {
+        redact_value(question, &mut shown);
+    }
+    (Some(shown), total)
+}
+
+/// Visible sub-fields of the entries in `shown`, evaluated against the full
+/// `answers` and the original (unredacted) entries.
+fn list_entry_fields(
+    question: &QuestionSpec,
+    answers: &Value,
+    shown: Option<&Value>,
+) -> Vec<Vec<String>> {
+    let shown = shown.and_then(Value::as_array).map(Vec::len).unwrap_or(0);
+    let (Some(list), Some(entries)) = (
+        &question.list,
+        answers.get(&question.id).and_then(Value::as_array),
+    ) else {
+        return Vec::new();
+    };
+    let mut item_contexts = ListItemContexts::new(answers);
+    entries
+        .iter()
+        .take(shown)
+        .map(|entry| {
+            let item_ctx = item_contexts.for_item(entry);
+            list.fields
+                .iter()
+                .filter(|field| list_field_visible(field, item_ctx))
+                .map(|field| field.id.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Section a question belongs to: the include prefix of its id, if any.
+fn section_of(question_id: &str) -> &str {
+    question_id
+        .rsplit_once('.')
+        .map(|(prefix, _)| prefix)
+        .unwrap_or_default()
+}
+
+fn question_input(question: &RenderQuestion, card: &CardOptions) -> Value {
     match question.kind {
-        QuestionType::String | QuestionType::Integer | QuestionType::Number => {
+        QuestionType::Integer | QuestionType::Number if card.native_number_input() => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.Number".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            let bound = |bound: f64| match question.kind {
+                QuestionType::Integer if bound.fract() == 0.0 => json!(bound as i64),
+                _ => json!(bound),
+            };
+            if let Some(constraint) = &question.constraint {
+                if let Some(min) = constraint.min {
+                    map.insert("min".into(), bound(min));
+                }
+                if let Some(max) = constraint.max {
+                    map.insert("max".into(), bound(max));
+                }
+            }
+            if let Some(value) = question
+                .current_value
+                .as_ref()
+                .filter(|value| value.is_number())
+            {
+                map.insert("value".into(), value.clone());
+            }
+            Value::Object(map)
+        }
+        QuestionType::Date | QuestionType::Time if !card.use_native_date_input => {
             let mut map = Map::new();
             map.insert("type".into(), Value::String("Input.Text".into()));
             map.insert("id".into(), Value::String(question.id.clone()));
             map.insert("isRequired".into(), Value::Bool(question.required));
-            if let Some(value) = &question.current_value {
+            let placeholder = match question.kind {
+                QuestionType::Date => "YYYY-MM-DD",
+                _ => "HH:MM",
+            };
+            map.insert("placeholder".into(), Value::String(placeholder.into()));
+            if let Some(value) = question.current_value.as_ref().and_then(Value::as_str) {
+                map.insert("value".into(), Value::String(value.to_string()));
+            }
+            Value::Object(map)
+        }
+        QuestionType::String
+        | QuestionType::Integer
+        | QuestionType::Number
+        | QuestionType::Path
+        | QuestionType::Duration
+        | QuestionType::Color => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.Text".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            match question
+                .constraint
+                .as_ref()
+                .and_then(|constraint| constraint.format)
+            {
+                Some(StringFormat::Email) => {
+                    map.insert("style".into(), Value::String("Email".into()));
+                }
+                Some(StringFormat::Url) => {
+                    map.insert("style".into(), Value::String("Url".into()));
+                    map.insert("placeholder".into(), Value::String("https://…".into()));
+                }
+                Some(StringFormat::Uuid) => {
+                    map.insert(
+                        "placeholder".into(),
+                        Value::String("xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx".into()),
+                    );
+                }
+                Some(StringFormat::Phone) => {
+                    map.insert("style".into(), Value::String("Tel".into()));
+                    map.insert("placeholder".into(), Value::String("+15551234567".into()));
+                }
+                None => {}
+            }
+            if matches!(question.kind, QuestionType::Duration) {
+                map.insert(
+                    "placeholder".into(),
+                    Value::String("seconds, e.g. 300".into()),
+                );
+            }
+            if matches!(question.kind, QuestionType::Color) {
+                map.insert("placeholder".into(), Value::String("#RRGGBB".into()));
+            }
+            if let Some(value) = &question.current_value
+                && !is_redacted(value)
+            {
                 map.insert("value".into(), Value::String(value_to_display(value)));
             }
             Value::Object(map)
         }
+        QuestionType::Time => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.Time".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            // Adaptive Cards times are `HH:MM`, so seconds are dropped here.
+            let card_time = |seconds: u32| format_time_of_day(seconds)[..5].to_string();
+            if let Some(constraint) = &question.constraint {
+                if let Some(min) = constraint.min {
+                    map.insert("min".into(), Value::String(card_time(min as u32)));
+                }
+                if let Some(max) = constraint.max {
+                    map.insert("max".into(), Value::String(card_time(max as u32)));
+                }
+            }
+            if let Some(seconds) = question
+                .current_value
+                .as_ref()
+                .and_then(Value::as_str)
+                .and_then(seconds_of_day)
+            {
+                map.insert("value".into(), Value::String(card_time(seconds)));
+            }
+            Value::Object(map)
+        }
+        QuestionType::Date => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.Date".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            if let Some(value) = question.current_value.as_ref().and_then(Value::as_str) {
+                map.insert("value".into(), Value::String(value.to_string()));
+            }
+            Value::Object(map)
+        }
         QuestionType::Boolean => {
             let mut map = Map::new();
             map.insert("type".into(), Value::String("Input.Toggle".into()));
@@ -414,17 +1503,120 @@ fn question_input(question: &RenderQuestion) -> Value {
                 .into_iter()
                 .map(|choice| {
                     json!({
-                        "title": choice,
+                        "title": choice_title(question, &choice),
+                        "value": choice,
+                    })
+                })
+                .collect::<Vec<_>>();
+            map.insert("choices".into(), Value::Array(choices));
+            if let Some(value) = &question.current_value {
+                map.insert("value".into(), Value::String(value_to_display(value)));
+            }
+            Value::Object(map)
+        }
+        QuestionType::MultiEnum => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.ChoiceSet".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("style".into(), Value::String("expanded".into()));
+            map.insert("isMultiSelect".into(), Value::Bool(true));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            let choices = question
+                .choices
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|choice| {
+                    json!({
+                        "title": choice_title(question, &choice),
                         "value": choice,
                     })
                 })
                 .collect::<Vec<_>>();
             map.insert("choices".into(), Value::Array(choices));
+            if let Some(selected) = question.current_value.as_ref().and_then(Value::as_array) {
+                let selected = selected
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                map.insert("value".into(), Value::String(selected));
+            }
+            Value::Object(map)
+        }
+        QuestionType::Rating => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.ChoiceSet".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("style".into(), Value::String("expanded".into()));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            let choices = rating_range(question.constraint.as_ref())
+                .map(|score| {
+                    json!({
+                        "title": score.to_string(),
+                        "value": score.to_string(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            map.insert("choices".into(), Value::Array(choices));
             if let Some(value) = &question.current_value {
                 map.insert("value".into(), Value::String(value_to_display(value)));
             }
             Value::Object(map)
         }
+        QuestionType::Markdown => {
+            // The current value is shown in its own text block; see `markdown_content`.
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.Text".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            map.insert("isMultiline".into(), Value::Bool(true));
+            if let Some(max_len) = question
+                .constraint
+                .as_ref()
+                .and_then(|constraint| constraint.max_len)
+            {
+                map.insert("maxLength".into(), Value::Number(max_len.into()));
+            }
+            Value::Object(map)
+        }
+        QuestionType::StringList => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("Input.Text".into()));
+            map.insert("id".into(), Value::String(question.id.clone()));
+            map.insert("isRequired".into(), Value::Bool(question.required));
+            map.insert(
+                "placeholder".into(),
+                Value::String("Separate items with commas, e.g. a, b".into()),
+            );
+            if let Some(items) = question.current_value.as_ref().and_then(Value::as_array) {
+                let items = items
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                map.insert("value".into(), Value::String(items));
+            }
+            Value::Object(map)
+        }
+        QuestionType::Object => {
+            let mut map = Map::new();
+            map.insert("type".into(), Value::String("TextBlock".into()));
+            map.insert(
+                "text".into(),
+                Value::String(format!(
+                    "Object '{}' ({} fields)",
+                    question.title,
+                    question
+                        .object
+                        .as_ref()
+                        .map(|object| object.fields.len())
+                        .unwrap_or_default()
+                )),
+            );
+            map.insert("wrap".into(), Value::Bool(true));
+            Value::Object(map)
+        }
         QuestionType::List => {
             let mut map = Map::new();
             map.insert("type".into(), Value::String("TextBlock".into()));
@@ -433,12 +1625,12 @@ fn question_input(question: &RenderQuestion) -> Value {
                 Value::String(format!(
                     "List group '{}' ({} entries)",
                     question.title,
-                    question
+                    question.current_value_total.unwrap_or_else(|| question
                         .current_value
                         .as_ref()
                         .and_then(Value::as_array)
                         .map(|entries| entries.len())
-                        .unwrap_or_default()
+                        .unwrap_or_default())
                 )),
             );
             map.insert("wrap".into(), Value::Bool(true));
@@ -447,6 +1639,44 @@ fn question_input(question: &RenderQuestion) -> Value {
     }
 }
 
+/// The editable side of a list question on a card: a multiline `Input.Text`
+/// taking the whole list as a JSON array (`qa_coerce` parses it on submit).
+/// It is prefilled with the current entries unless they were truncated or
+/// hold secret fields, which would come back redacted.
+fn list_input(question: &RenderQuestion) -> Option<Value> {
+    let list = question.list.as_ref()?;
+    let example = list
+        .fields
+        .iter()
+        .map(|field| format!("\"{}\": …", field.id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut map = Map::new();
+    map.insert("type".into(), Value::String("Input.Text".into()));
+    map.insert("id".into(), Value::String(question.id.clone()));
+    map.insert("isRequired".into(), Value::Bool(question.required));
+    map.insert("isMultiline".into(), Value::Bool(true));
+    map.insert(
+        "placeholder".into(),
+        Value::String(format!("JSON array of entries, e.g. [{{{}}}]", example)),
+    );
+    let editable =
+        question.current_value_total.is_none() && !list.fields.iter().any(|field| field.secret);
+    if editable && let Some(entries) = &question.current_value {
+        map.insert("value".into(), Value::String(entries.to_string()));
+    }
+    Some(Value::Object(map))
+}
+
+/// What a choice is called in the payload's locale.
+fn choice_title<'a>(question: &'a RenderQuestion, choice: &'a str) -> &'a str {
+    question
+        .choice_labels
+        .get(choice)
+        .map(String::as_str)
+        .unwrap_or(choice)
+}
+
 fn question_type_label(kind: QuestionType) -> &'static str {
     match kind {
         QuestionType::String => "string",
@@ -455,11 +1685,57 @@ fn question_type_label(kind: QuestionType) -> &'static str {
         QuestionType::Number => "number",
         QuestionType::Enum => "enum",
         QuestionType::List => "list",
+        QuestionType::Date => "date",
+        QuestionType::MultiEnum => "multi_enum",
+        QuestionType::Path => "path",
+        QuestionType::Duration => "duration",
+        QuestionType::Object => "object",
+        QuestionType::StringList => "string_list",
+        QuestionType::Rating => "rating",
+        QuestionType::Color => "color",
+        QuestionType::Markdown => "markdown",
+        QuestionType::Time => "time",
+    }
+}
+
+pub(crate) fn current_value_display(question: &RenderQuestion) -> Option<String> {
+    let value = question.current_value.as_ref()?;
+    if matches!(question.kind, QuestionType::Markdown) {
+        return Some(markdown_preview(&value_to_display(value)));
+    }
+    let display = value_to_display(value);
+    Some(match question.current_value_total {
+        Some(total) => format!(
+            "{} (first {} of {} items)",
+            display,
+            value.as_array().map(Vec::len).unwrap_or_default(),
+            total
+        ),
+        None => display,
+    })
+}
+
+/// Longest [`markdown_preview`], in characters, before it is cut off.
+const MARKDOWN_PREVIEW_CHARS: usize = 60;
+
+/// Markdown squeezed onto one line for text prompts: runs of whitespace,
+/// including line breaks, become single spaces and long text ends in `…`.
+fn markdown_preview(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= MARKDOWN_PREVIEW_CHARS {
+        return line;
     }
+    let mut preview = line
+        .chars()
+        .take(MARKDOWN_PREVIEW_CHARS - 1)
+        .collect::<String>();
+    preview.push('…');
+    preview
 }
 
 fn value_to_display(value: &Value) -> String {
     match value {
+        _ if is_redacted(value) => "********".to_string(),
         Value::String(text) => text.clone(),
         Value::Bool(flag) => flag.to_string(),
         Value::Number(num) => num.to_string(),
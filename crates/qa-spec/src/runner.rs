@@ -1,6 +1,13 @@
 use serde_json::{Map, Value};
 
-use crate::{FormSpec, RenderPayload, StoreOp, ValidationResult, build_render_payload, validate};
+use crate::computed::{ComputedReport, compute_answers};
+use crate::normalize::apply_normalization;
+use crate::store::effective_store_ops;
+use crate::{
+    FormSpec, RenderOptions, RenderPayload, StoreOp, ValidationResult, VisibilityMode,
+    build_render_payload_with_visibility, computed_conflicts, resolve_visibility_with_ctx,
+    validate_with_visibility_and_ctx,
+};
 
 /// Versioned deterministic plan produced by runner planning functions.
 #[derive(Debug, Clone)]
@@ -11,6 +18,8 @@ pub struct QaPlanV1 {
     pub validation: ValidationResult,
     pub payload: RenderPayload,
     pub effects: Vec<StoreOp>,
+    /// How each computed question was resolved while building the plan.
+    pub computed: Vec<ComputedReport>,
     pub warnings: Vec<String>,
     pub errors: Vec<String>,
 }
@@ -45,10 +54,27 @@ pub fn plan_next(spec: &FormSpec, ctx: &Value, answers: &Value) -> QaPlanV1 {
 }
 
 fn build_plan(spec: &FormSpec, ctx: &Value, answers: Value) -> QaPlanV1 {
-    let validation = validate(spec, &answers);
-    let payload = build_render_payload(spec, ctx, &answers);
+    let normalized = apply_normalization(spec, &answers);
+    let (answers, computed) = compute_answers(spec, &normalized, ctx);
+    // One visibility pass serves validation, the payload, and its schema.
+    let visibility = resolve_visibility_with_ctx(spec, &answers, ctx, VisibilityMode::Visible).map;
+    let mut validation = validate_with_visibility_and_ctx(spec, &answers, ctx, &visibility);
+    // Validation only sees the computed answers, so overrides are checked here.
+    let conflicts = computed_conflicts(spec, &normalized, &answers, &visibility);
+    if !conflicts.is_empty() {
+        validation.valid = false;
+        validation.errors.extend(conflicts);
+    }
+    let payload = build_render_payload_with_visibility(
+        spec,
+        ctx,
+        &answers,
+        None,
+        &RenderOptions::for_form(spec),
+        &visibility,
+    );
     let effects = if validation.valid {
-        spec.store.clone()
+        effective_store_ops(spec, &answers)
     } else {
         Vec::new()
     };
@@ -83,6 +109,7 @@ fn build_plan(spec: &FormSpec, ctx: &Value, answers: Value) -> QaPlanV1 {
         validation,
         payload,
         effects,
+        computed,
         warnings: Vec::new(),
         errors,
     }
@@ -0,0 +1,147 @@
+//! Integrity signatures over answer sets.
+//!
+//! # Signed bytes
+//!
+//! The signature covers the UTF-8 bytes of the canonical JSON encoding
+//! ([`canonical_json`] after [`canonicalize`]) of
+//!
+//! ```json
+//! {"answers": <answers>, "form_id": <form_id>, "spec_version": <spec_version>}
+//! ```
+//!
+//! `meta` (including the signature itself) is not covered. Strings are not NFC
+//! normalized, so signers must send the exact characters they signed.
+//!
+//! With the `signing` feature, [`AnswerSet::sign`] stores HMAC-SHA256 of those
+//! bytes, hex encoded in lowercase, as [`Meta::signature`](crate::Meta).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::answers::{AnswerSet, CanonicalOptions, canonical_json, canonicalize};
+
+/// Algorithm identifier recorded in [`AnswerSignature::alg`].
+pub const HMAC_SHA256: &str = "hmac-sha256";
+
+/// Signature stored in an answer set's meta.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AnswerSignature {
+    pub alg: String,
+    /// Identifier of the key that produced the signature, for key rotation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    /// Lowercase hex MAC over [`AnswerSet::signing_bytes`].
+    pub value: String,
+}
+
+/// Failure raised by [`AnswerSet::verify`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SignatureError {
+    #[error("answer set is not signed")]
+    Missing,
+    #[error("unsupported signature algorithm '{0}'")]
+    UnsupportedAlgorithm(String),
+    #[error("signature was made with key '{found}', expected '{expected}'")]
+    KeyMismatch { expected: String, found: String },
+    #[error("signature does not match the answers")]
+    Invalid,
+}
+
+impl AnswerSet {
+    /// Signature stored in meta, if any.
+    pub fn signature(&self) -> Option<&AnswerSignature> {
+        self.meta.as_ref()?.signature.as_ref()
+    }
+
+    /// Bytes covered by the signature; see the [module docs](self).
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut payload = serde_json::Map::new();
+        payload.insert("answers".into(), self.answers.clone());
+        payload.insert("form_id".into(), Value::String(self.form_id.clone()));
+        payload.insert(
+            "spec_version".into(),
+            Value::String(self.spec_version.clone()),
+        );
+        let payload = canonicalize(&Value::Object(payload), &CanonicalOptions::default());
+        canonical_json(&payload).into_bytes()
+    }
+}
+
+#[cfg(feature = "signing")]
+mod hmac_sha256 {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    use super::{AnswerSignature, HMAC_SHA256, SignatureError};
+    use crate::answers::AnswerSet;
+
+    type HmacSha256 = Hmac<Sha256>;
+
+    fn mac(key: &[u8], set: &AnswerSet) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(&set.signing_bytes());
+        mac
+    }
+
+    impl AnswerSet {
+        /// Signs the answers with HMAC-SHA256, replacing any previous signature.
+        pub fn sign(&mut self, key: &[u8]) {
+            self.store_signature(key, None);
+        }
+
+        /// Same as [`AnswerSet::sign`], recording which key was used.
+        pub fn sign_with_key_id(&mut self, key_id: impl Into<String>, key: &[u8]) {
+            self.store_signature(key, Some(key_id.into()));
+        }
+
+        /// Checks the stored signature against `key`.
+        pub fn verify(&self, key: &[u8]) -> Result<(), SignatureError> {
+            let signature = self.signature().ok_or(SignatureError::Missing)?;
+            if signature.alg != HMAC_SHA256 {
+                return Err(SignatureError::UnsupportedAlgorithm(signature.alg.clone()));
+            }
+            let expected = decode_hex(&signature.value).ok_or(SignatureError::Invalid)?;
+            mac(key, self)
+                .verify_slice(&expected)
+                .map_err(|_| SignatureError::Invalid)
+        }
+
+        /// Like [`AnswerSet::verify`], also requiring the signature to name `key_id`.
+        pub fn verify_with_key_id(&self, key_id: &str, key: &[u8]) -> Result<(), SignatureError> {
+            let signature = self.signature().ok_or(SignatureError::Missing)?;
+            match &signature.key_id {
+                Some(found) if found != key_id => Err(SignatureError::KeyMismatch {
+                    expected: key_id.to_string(),
+                    found: found.clone(),
+                }),
+                _ => self.verify(key),
+            }
+        }
+
+        fn store_signature(&mut self, key: &[u8], key_id: Option<String>) {
+            let value = mac(key, self)
+                .finalize()
+                .into_bytes()
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            self.meta.get_or_insert_default().signature = Some(AnswerSignature {
+                alg: HMAC_SHA256.to_string(),
+                key_id,
+                value,
+            });
+        }
+    }
+
+    fn decode_hex(raw: &str) -> Option<Vec<u8>> {
+        if !raw.len().is_multiple_of(2) {
+            return None;
+        }
+        (0..raw.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(raw.get(index..index + 2)?, 16).ok())
+            .collect()
+    }
+}
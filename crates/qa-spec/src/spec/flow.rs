@@ -1,18 +1,30 @@
 use crate::expr::Expr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::BTreeMap;
 
 /// Identifier for QA flow steps.
 pub type StepId = String;
 
-/// Card/render modes for message steps.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+/// Card/render modes for flow steps.
+///
+/// Message steps pick their transport (`text`, `json`, `card`). For question
+/// steps `card` keeps one input per card, while `section` groups the questions
+/// sharing the pending question's include prefix, `form` (or `all`) renders
+/// every visible question behind a single submit and `page` renders them a
+/// page at a time with previous/next actions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CardMode {
     Text,
     Json,
+    #[default]
     Card,
+    Section,
+    #[serde(alias = "all")]
+    Form,
+    Page,
 }
 
 /// Single message/prompt step inside a flow.
@@ -30,9 +42,15 @@ pub struct QuestionStep {
     pub question_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next: Option<StepId>,
+    /// Card layout used while this step is active; falls back to the form presentation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_mode: Option<CardMode>,
 }
 
 /// Conditional branch case.
+///
+/// Without a selector the `if` expression must evaluate to `true`; when the
+/// decision has an `on` selector the case matches if both produce the same value.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DecisionCase {
     #[serde(rename = "if")]
@@ -43,18 +61,98 @@ pub struct DecisionCase {
 /// Decision / branching step.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct DecisionStep {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on: Option<Expr>,
     pub cases: Vec<DecisionCase>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub default_goto: Option<StepId>,
+    #[serde(
+        default,
+        alias = "default_goto",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default: Option<StepId>,
+}
+
+impl DecisionCase {
+    /// Returns whether the case matches, given the already evaluated selector value.
+    pub fn matches(&self, selector: Option<&Value>, ctx: &Value) -> bool {
+        match selector {
+            Some(selected) => self
+                .if_expr
+                .evaluate_value(ctx)
+                .is_some_and(|value| &value == selected),
+            None => self.if_expr.evaluate_bool(ctx) == Some(true),
+        }
+    }
+}
+
+impl StepSpec {
+    /// Step ids this step may continue to.
+    pub fn successors(&self) -> Vec<&StepId> {
+        match self {
+            StepSpec::Message(message) => message.next.iter().collect(),
+            StepSpec::Question(question) => question.next.iter().collect(),
+            StepSpec::Decision(decision) => decision.targets().collect(),
+            StepSpec::Action { .. } | StepSpec::End => Vec::new(),
+        }
+    }
+}
+
+impl DecisionStep {
+    /// Picks the target of the first matching case, falling back to `default`.
+    pub fn select(&self, ctx: &Value) -> Option<&StepId> {
+        let selector = self
+            .on
+            .as_ref()
+            .map(|on| on.evaluate_value(ctx).unwrap_or(Value::Null));
+        self.cases
+            .iter()
+            .find(|case| case.matches(selector.as_ref(), ctx))
+            .map(|case| &case.goto)
+            .or(self.default.as_ref())
+    }
+
+    /// Step ids this decision may jump to.
+    pub fn targets(&self) -> impl Iterator<Item = &StepId> {
+        self.cases
+            .iter()
+            .map(|case| &case.goto)
+            .chain(self.default.iter())
+    }
+}
+
+/// What the flow engine does once a run exceeds one of its limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OnLimit {
+    /// Abort the run with an error.
+    #[default]
+    Error,
+    /// Stop the run and treat the answers collected so far as complete.
+    CompletePartial,
 }
 
-/// Flow-wide policies (placeholder for future expansion).
+/// Flow-wide policies.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct FlowPolicy {
     #[serde(default)]
     pub allow_back: bool,
     #[serde(default)]
     pub allow_submit_all: bool,
+    /// Maximum number of steps a single run may enter (guards against decision loops).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_steps: Option<usize>,
+    /// Maximum number of times any one step may be entered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_repeats_per_step: Option<usize>,
+    #[serde(default)]
+    pub on_limit: OnLimit,
+}
+
+impl FlowPolicy {
+    /// Whether any execution limit is configured.
+    pub fn has_limits(&self) -> bool {
+        self.max_steps.is_some() || self.max_repeats_per_step.is_some()
+    }
 }
 
 /// A single wire-up step in QA flows.
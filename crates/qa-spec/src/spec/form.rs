@@ -1,20 +1,40 @@
+use crate::i18n::LocaleStrings;
+use crate::limits::{DEFAULT_MAX_ANSWER_BYTES, DEFAULT_MAX_TOTAL_BYTES};
+use crate::spec::flow::CardMode;
+use crate::spec::migration::MigrationStep;
 use crate::spec::question::QuestionSpec;
 use crate::spec::validation::CrossFieldValidation;
 use crate::store::StoreOp;
+use crate::template::ResolutionMode;
+use crate::theme::ThemeSpec;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Presentation hints for a form.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct FormPresentation {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub intro: Option<String>,
+    /// Built-in theme name or inline theme; see [`ThemeSpec`].
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub theme: Option<String>,
+    pub theme: Option<ThemeSpec>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_locale: Option<String>,
+    /// How unresolved template variables are handled in templated render text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_mode: Option<ResolutionMode>,
+    /// Default card layout used by `render_card` when no flow step overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_mode: Option<CardMode>,
+    /// Questions per card in `page` mode (default [`DEFAULT_CARD_PAGE_SIZE`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
 }
 
+/// Questions per card in `page` mode when the presentation sets no `page_size`.
+pub const DEFAULT_CARD_PAGE_SIZE: usize = 5;
+
 /// Execution policies shared by question navigation.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ProgressPolicy {
@@ -24,6 +44,11 @@ pub struct ProgressPolicy {
     pub autofill_defaults: bool,
     #[serde(default)]
     pub treat_default_as_answered: bool,
+    /// Quick setup: only required questions are asked and counted in
+    /// progress; optional ones keep their defaults unless answered anyway.
+    /// `ctx.progress.required_only` overrides it per call.
+    #[serde(default)]
+    pub required_only: bool,
 }
 
 impl Default for ProgressPolicy {
@@ -32,6 +57,7 @@ impl Default for ProgressPolicy {
             skip_answered: true,
             autofill_defaults: false,
             treat_default_as_answered: false,
+            required_only: false,
         }
     }
 }
@@ -79,5 +105,93 @@ pub struct FormSpec {
     pub validations: Vec<CrossFieldValidation>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub includes: Vec<IncludeSpec>,
+    /// Answer rewrites between versions, applied by [`crate::migrate_answers`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<MigrationStep>,
+    /// Largest serialized answer to a single question, in bytes
+    /// (default [`DEFAULT_MAX_ANSWER_BYTES`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_answer_bytes: Option<usize>,
+    /// Largest serialized answers object, in bytes
+    /// (default [`DEFAULT_MAX_TOTAL_BYTES`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_bytes: Option<usize>,
+    /// Per-locale form `title`, `description` and `help` (the intro), keyed
+    /// by locale; renderers fall back to the strings above.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub i18n: Option<BTreeMap<String, LocaleStrings>>,
     pub questions: Vec<QuestionSpec>,
 }
+
+impl FormSpec {
+    /// Spec-wide template resolution mode (relaxed unless the presentation opts into strict).
+    pub fn template_mode(&self) -> ResolutionMode {
+        self.presentation
+            .as_ref()
+            .and_then(|presentation| presentation.template_mode)
+            .unwrap_or_default()
+    }
+
+    /// Spec-wide card layout (one question per card unless the presentation says otherwise).
+    pub fn card_mode(&self) -> CardMode {
+        self.presentation
+            .as_ref()
+            .and_then(|presentation| presentation.card_mode)
+            .unwrap_or_default()
+    }
+
+    /// Questions per card in `page` mode.
+    pub fn card_page_size(&self) -> usize {
+        self.presentation
+            .as_ref()
+            .and_then(|presentation| presentation.page_size)
+            .unwrap_or(DEFAULT_CARD_PAGE_SIZE)
+    }
+
+    /// Byte limit for one question's answer.
+    pub fn answer_byte_limit(&self) -> usize {
+        self.max_answer_bytes.unwrap_or(DEFAULT_MAX_ANSWER_BYTES)
+    }
+
+    /// Byte limit for all answers together.
+    pub fn total_byte_limit(&self) -> usize {
+        self.max_total_bytes.unwrap_or(DEFAULT_MAX_TOTAL_BYTES)
+    }
+
+    /// Questions in the order they are asked and rendered: by `order` (0 when
+    /// unset), then by position in `questions`.
+    pub fn ordered_questions(&self) -> Vec<&QuestionSpec> {
+        let mut questions = self.questions.iter().collect::<Vec<_>>();
+        questions.sort_by_key(|question| question.order.unwrap_or_default());
+        questions
+    }
+
+    /// Expands every `choices_catalog`, including those on list and object
+    /// fields, into `choices`. Questions that already list their choices keep
+    /// them. [`FormSpec::from_value`] calls this after parsing.
+    pub fn resolve_catalogs(&mut self) {
+        resolve_question_catalogs(&mut self.questions);
+    }
+}
+
+fn resolve_question_catalogs(questions: &mut [QuestionSpec]) {
+    for question in questions {
+        if let Some(catalog) = question.choices_catalog
+            && question.choices.is_none()
+        {
+            question.choices = Some(
+                catalog
+                    .choices()
+                    .iter()
+                    .map(|code| code.to_string())
+                    .collect(),
+            );
+        }
+        if let Some(list) = &mut question.list {
+            resolve_question_catalogs(&mut list.fields);
+        }
+        if let Some(object) = &mut question.object {
+            resolve_question_catalogs(&mut object.fields);
+        }
+    }
+}
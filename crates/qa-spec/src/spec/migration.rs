@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Answer rewrites needed to move answers from one spec version to the next.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MigrationStep {
+    /// Spec version the answers were recorded against.
+    pub from: String,
+    /// Spec version the answers satisfy after `ops` ran.
+    pub to: String,
+    /// Operations applied in order.
+    #[serde(default)]
+    pub ops: Vec<MigrationOp>,
+}
+
+/// Single rewrite of top-level answer fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum MigrationOp {
+    /// Moves the answer of `from` to `to`.
+    Rename { from: String, to: String },
+    /// Splits a string answer on `separator` into one answer per `into` field.
+    Split {
+        from: String,
+        into: Vec<String>,
+        separator: String,
+    },
+    /// Joins the scalar answers of `from` with `separator` into `into`.
+    Merge {
+        from: Vec<String>,
+        into: String,
+        #[serde(default)]
+        separator: String,
+    },
+    /// Converts the answer of `field` with `convert`.
+    Retype {
+        field: String,
+        convert: AnswerConverter,
+    },
+    /// Removes the answer of `field`.
+    Drop { field: String },
+}
+
+impl MigrationOp {
+    /// Operation name as written in the spec (`rename`, `split`, ...).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rename { .. } => "rename",
+            Self::Split { .. } => "split",
+            Self::Merge { .. } => "merge",
+            Self::Retype { .. } => "retype",
+            Self::Drop { .. } => "drop",
+        }
+    }
+}
+
+/// Conversion applied by [`MigrationOp::Retype`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerConverter {
+    /// Parses a string such as `"5432"` into an integer.
+    StringToInteger,
+    /// Parses a string such as `"0.5"` into a number.
+    StringToNumber,
+    /// Parses `true`/`false`/`yes`/`no`/`1`/`0` (any case) into a boolean.
+    StringToBoolean,
+    /// Writes a scalar answer as a string.
+    ToString,
+}
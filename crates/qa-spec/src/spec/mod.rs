@@ -1,12 +1,21 @@
 pub mod flow;
 pub mod form;
+pub mod migration;
+pub mod parse;
 pub mod question;
 pub mod validation;
 
 pub use flow::{
-    CardMode, DecisionCase, DecisionStep, FlowPolicy, MessageStep, QAFlowSpec, QuestionStep,
-    StepId, StepSpec,
+    CardMode, DecisionCase, DecisionStep, FlowPolicy, MessageStep, OnLimit, QAFlowSpec,
+    QuestionStep, StepId, StepSpec,
 };
-pub use form::{FormPresentation, FormSpec, IncludeSpec, ProgressPolicy, SecretsPolicy};
-pub use question::{Constraint, ListSpec, QuestionSpec, QuestionType};
-pub use validation::CrossFieldValidation;
+pub use form::{
+    DEFAULT_CARD_PAGE_SIZE, FormPresentation, FormSpec, IncludeSpec, ProgressPolicy, SecretsPolicy,
+};
+pub use migration::{AnswerConverter, MigrationOp, MigrationStep};
+pub use parse::SpecParseError;
+pub use question::{
+    ChoiceCatalog, Constraint, DEFAULT_RATING_RANGE, ListSpec, ObjectSpec, PathKind, QuestionSpec,
+    QuestionType, StringFormat, Transform, rating_range,
+};
+pub use validation::{CrossFieldValidation, ValidationSeverity};
@@ -0,0 +1,344 @@
+use std::fmt::Write as _;
+
+use schemars::{JsonSchema, schema_for};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::limits::{SpecLimits, drop_flat, expression_too_deep, value_too_deep};
+use crate::pattern::compile_pattern;
+use crate::spec::form::FormSpec;
+use crate::spec::question::{Constraint, QuestionSpec};
+
+/// Error raised when a raw spec document does not match the spec model.
+#[derive(Debug, Error)]
+pub enum SpecParseError {
+    #[error("invalid JSON at line {line}, column {column}: {message}")]
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("unknown field '{field}' at {pointer}{}", suggestion_suffix(.suggestion))]
+    UnknownField {
+        pointer: String,
+        field: String,
+        suggestion: Option<String>,
+    },
+    #[error("invalid value at {pointer}: {message}{}", suggestion_suffix(.suggestion))]
+    InvalidValue {
+        pointer: String,
+        value: Option<Value>,
+        message: String,
+        suggestion: Option<String>,
+    },
+    #[error("document nests deeper than {limit} levels at {pointer}")]
+    TooDeep { pointer: String, limit: usize },
+    #[error("expression at {pointer} nests {depth} levels deep, more than the limit of {limit}")]
+    ExpressionTooDeep {
+        pointer: String,
+        depth: usize,
+        limit: usize,
+    },
+    #[error("invalid pattern '{pattern}' at {pointer}: {message}")]
+    InvalidPattern {
+        pointer: String,
+        pattern: String,
+        message: String,
+    },
+}
+
+impl SpecParseError {
+    /// JSON pointer of the offending location, when the error is not a syntax error.
+    pub fn pointer(&self) -> Option<&str> {
+        match self {
+            SpecParseError::Syntax { .. } => None,
+            SpecParseError::UnknownField { pointer, .. }
+            | SpecParseError::InvalidValue { pointer, .. }
+            | SpecParseError::TooDeep { pointer, .. }
+            | SpecParseError::ExpressionTooDeep { pointer, .. }
+            | SpecParseError::InvalidPattern { pointer, .. } => Some(pointer),
+        }
+    }
+
+    /// Closest known field or value name, if the input looks like a near miss.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            SpecParseError::Syntax { .. }
+            | SpecParseError::TooDeep { .. }
+            | SpecParseError::ExpressionTooDeep { .. }
+            | SpecParseError::InvalidPattern { .. } => None,
+            SpecParseError::UnknownField { suggestion, .. }
+            | SpecParseError::InvalidValue { suggestion, .. } => suggestion.as_deref(),
+        }
+    }
+}
+
+fn suggestion_suffix(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|name| format!(" (did you mean '{}'?)", name))
+        .unwrap_or_default()
+}
+
+impl FormSpec {
+    /// Parse a form spec from raw JSON, reporting pointer-precise errors and
+    /// rejecting unknown fields.
+    pub fn from_json_str(raw: &str) -> Result<Self, SpecParseError> {
+        let value: Value = serde_json::from_str(raw).map_err(|err| SpecParseError::Syntax {
+            line: err.line(),
+            column: err.column(),
+            message: err.to_string(),
+        })?;
+        Self::from_value(value)
+    }
+
+    /// Parse a form spec from an already decoded JSON value.
+    pub fn from_value(value: Value) -> Result<Self, SpecParseError> {
+        Self::from_value_with_limits(value, &SpecLimits::default())
+    }
+
+    /// Like [`FormSpec::from_value`], rejecting documents and expressions
+    /// nested deeper than `limits` before they can exhaust the stack.
+    pub fn from_value_with_limits(
+        value: Value,
+        limits: &SpecLimits,
+    ) -> Result<Self, SpecParseError> {
+        if let Some(found) = value_too_deep(&value, limits.max_value_depth) {
+            drop_flat(value);
+            return Err(SpecParseError::TooDeep {
+                pointer: found.pointer,
+                limit: limits.max_value_depth,
+            });
+        }
+        let mut spec: Self = parse_strict(value)?;
+        if let Some(found) = expression_too_deep(&spec, limits.max_expr_depth) {
+            return Err(SpecParseError::ExpressionTooDeep {
+                pointer: found.pointer,
+                depth: found.depth,
+                limit: limits.max_expr_depth,
+            });
+        }
+        spec.resolve_catalogs();
+        spec.check()?;
+        Ok(spec)
+    }
+
+    /// Checks what the document shape cannot express: every constraint
+    /// `pattern`, including those on list and object fields, must compile.
+    ///
+    /// Run by [`FormSpec::from_value`]; specs built in code or deserialized
+    /// directly can call it themselves. Validation skips patterns that do not
+    /// compile.
+    pub fn check(&self) -> Result<(), SpecParseError> {
+        check_patterns(&self.questions, "/questions")
+    }
+}
+
+fn check_patterns(questions: &[QuestionSpec], base: &str) -> Result<(), SpecParseError> {
+    for (index, question) in questions.iter().enumerate() {
+        let pointer = format!("{}/{}", base, index);
+        if let Some(constraint) = &question.constraint {
+            check_pattern(constraint, &format!("{}/constraint", pointer))?;
+            if let Some(warnings) = &constraint.warnings {
+                check_pattern(warnings, &format!("{}/constraint/warnings", pointer))?;
+            }
+        }
+        if let Some(list) = &question.list {
+            check_patterns(&list.fields, &format!("{}/list/fields", pointer))?;
+        }
+        if let Some(object) = &question.object {
+            check_patterns(&object.fields, &format!("{}/object/fields", pointer))?;
+        }
+    }
+    Ok(())
+}
+
+fn check_pattern(constraint: &Constraint, pointer: &str) -> Result<(), SpecParseError> {
+    if let Some(pattern) = &constraint.pattern
+        && let Err(err) = compile_pattern(pattern)
+    {
+        return Err(SpecParseError::InvalidPattern {
+            pointer: format!("{}/pattern", pointer),
+            pattern: pattern.clone(),
+            message: err.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn parse_strict<T: DeserializeOwned + JsonSchema>(value: Value) -> Result<T, SpecParseError> {
+    let mut unknown = Vec::new();
+    let parsed = serde_path_to_error::deserialize(serde_ignored::Deserializer::new(
+        &value,
+        &mut |path: serde_ignored::Path| unknown.push(ignored_segments(&path)),
+    ));
+    let schema = schema_for!(T);
+    let schema = schema.as_value();
+
+    match parsed {
+        Err(err) => {
+            let segments = error_segments(err.path());
+            let pointer = to_pointer(&segments);
+            let offending = value.pointer(&pointer).cloned();
+            let suggestion = offending
+                .as_ref()
+                .and_then(Value::as_str)
+                .and_then(|raw| closest(raw, known_values(schema, &segments)));
+            Err(SpecParseError::InvalidValue {
+                pointer,
+                value: offending,
+                message: err.into_inner().to_string(),
+                suggestion,
+            })
+        }
+        Ok(parsed) => match unknown.into_iter().next() {
+            Some(mut segments) => {
+                let field = segments.pop().unwrap_or_default();
+                let suggestion = closest(&field, known_fields(schema, &segments));
+                segments.push(field.clone());
+                Err(SpecParseError::UnknownField {
+                    pointer: to_pointer(&segments),
+                    field,
+                    suggestion,
+                })
+            }
+            None => Ok(parsed),
+        },
+    }
+}
+
+fn ignored_segments(path: &serde_ignored::Path) -> Vec<String> {
+    let mut segments = match path {
+        serde_ignored::Path::Root => return Vec::new(),
+        serde_ignored::Path::Seq { parent, .. }
+        | serde_ignored::Path::Map { parent, .. }
+        | serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => ignored_segments(parent),
+    };
+    match path {
+        serde_ignored::Path::Seq { index, .. } => segments.push(index.to_string()),
+        serde_ignored::Path::Map { key, .. } => segments.push(key.clone()),
+        _ => {}
+    }
+    segments
+}
+
+fn error_segments(path: &serde_path_to_error::Path) -> Vec<String> {
+    let mut segments = Vec::new();
+    for segment in path {
+        match segment {
+            serde_path_to_error::Segment::Seq { index } => segments.push(index.to_string()),
+            serde_path_to_error::Segment::Map { key } => segments.push(key.clone()),
+            serde_path_to_error::Segment::Enum { .. } => {}
+            serde_path_to_error::Segment::Unknown => break,
+        }
+    }
+    segments
+}
+
+fn to_pointer(segments: &[String]) -> String {
+    let mut pointer = String::new();
+    for segment in segments {
+        let _ = write!(
+            pointer,
+            "/{}",
+            segment.replace('~', "~0").replace('/', "~1")
+        );
+    }
+    pointer
+}
+
+/// Schema alternatives that describe the value at `segments`.
+fn schema_nodes<'a>(root: &'a Value, segments: &[String]) -> Vec<&'a Value> {
+    let mut nodes = expand(root, root);
+    for segment in segments {
+        nodes = nodes
+            .into_iter()
+            .filter_map(|node| {
+                node.get("properties")
+                    .and_then(|properties| properties.get(segment))
+                    .or_else(|| {
+                        segment
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|_| node.get("items"))
+                    })
+                    .or_else(|| node.get("additionalProperties").filter(|v| v.is_object()))
+            })
+            .flat_map(|node| expand(root, node))
+            .collect();
+    }
+    nodes
+}
+
+/// Resolve `$ref`s and flatten `anyOf`/`oneOf`/`allOf` alternatives.
+fn expand<'a>(root: &'a Value, node: &'a Value) -> Vec<&'a Value> {
+    let node = match node.get("$ref").and_then(Value::as_str) {
+        Some(reference) => reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .unwrap_or(node),
+        None => node,
+    };
+    let mut out = vec![node];
+    for key in ["anyOf", "oneOf", "allOf"] {
+        if let Some(alternatives) = node.get(key).and_then(Value::as_array) {
+            out.extend(
+                alternatives
+                    .iter()
+                    .flat_map(|alternative| expand(root, alternative)),
+            );
+        }
+    }
+    out
+}
+
+fn known_fields(root: &Value, segments: &[String]) -> Vec<String> {
+    schema_nodes(root, segments)
+        .into_iter()
+        .filter_map(|node| node.get("properties").and_then(Value::as_object))
+        .flat_map(|properties| properties.keys().cloned())
+        .collect()
+}
+
+fn known_values(root: &Value, segments: &[String]) -> Vec<String> {
+    schema_nodes(root, segments)
+        .into_iter()
+        .flat_map(|node| {
+            let listed = node
+                .get("enum")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten();
+            listed.chain(node.get("const"))
+        })
+        .filter_map(|value| value.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Closest candidate within a small edit distance of `input`.
+fn closest(input: &str, candidates: Vec<String>) -> Option<String> {
+    let threshold = (input.chars().count() / 3).clamp(1, 3);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, &candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
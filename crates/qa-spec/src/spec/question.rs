@@ -1,8 +1,11 @@
 use crate::expr::Expr;
-use crate::i18n::I18nText;
+use crate::i18n::{I18nText, LocaleStrings};
 use crate::store::StoreTarget;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
 
 /// Supported question data types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -14,10 +17,41 @@ pub enum QuestionType {
     Number,
     Enum,
     List,
+    /// ISO-8601 calendar date answered as a `YYYY-MM-DD` string.
+    Date,
+    /// Any number of `choices`, answered as an array of distinct strings.
+    MultiEnum,
+    /// A filesystem path, answered as a string.
+    Path,
+    /// A length of time, answered as whole seconds.
+    Duration,
+    /// A single structured answer whose keys are the `object.fields`.
+    Object,
+    /// Free-form strings answered as an array; `list` bounds its length and
+    /// `constraint` applies to every item.
+    StringList,
+    /// A whole-number score on the scale given by [`rating_range`].
+    Rating,
+    /// A hex color answered as a `#RRGGBB` or `#RRGGBBAA` string.
+    Color,
+    /// Markdown text, possibly spanning several lines, answered as a string.
+    Markdown,
+    /// A time of day answered as an `HH:MM:SS` string; `min`/`max` bounds are
+    /// seconds after midnight.
+    Time,
+}
+
+/// Splits a comma-separated `multi_enum` selection or `string_list` answer,
+/// as written in `default_value` or typed at a prompt, into trimmed
+/// non-empty items.
+pub fn split_choices(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|choice| !choice.is_empty())
 }
 
 /// Constraints that can be enforced per question.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Constraint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
@@ -25,10 +59,346 @@ pub struct Constraint {
     pub min: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max: Option<f64>,
+    /// Treat `min` as a strict lower bound (`value > min`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclusive_min: bool,
+    /// Treat `max` as a strict upper bound (`value < max`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclusive_max: bool,
+    /// Most digits a numeric answer may have after the decimal point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_places: Option<u8>,
+    /// Step numeric answers must be a whole multiple of, such as `10` or
+    /// `0.25`. Must be positive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_len: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_len: Option<usize>,
+    /// Well-known shape string answers must have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<StringFormat>,
+    /// Fewest choices a `multi_enum` answer may select.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_selected: Option<usize>,
+    /// Most choices a `multi_enum` answer may select.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_selected: Option<usize>,
+    /// A `path` answer must name something that exists. Only frontends with
+    /// filesystem access (such as the CLI wizard) can check this.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub must_exist: bool,
+    /// What a `path` answer must point at.
+    #[serde(default, rename = "kind", skip_serializing_if = "Option::is_none")]
+    pub path_kind: Option<PathKind>,
+    /// Messages shown instead of the built-in ones, keyed by error code
+    /// (`pattern_mismatch`, `min_length`, `min`, …). The code itself is kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages: Option<BTreeMap<String, String>>,
+    /// Softer checks, written like the constraint itself, whose failures are
+    /// reported as warnings. They run only once the answer passes the
+    /// constraint and never make answers invalid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Box<Constraint>>,
+}
+
+/// Digits after the decimal point in `value` written out in full, so
+/// `19.999` has 3 and `20.0` has none.
+pub fn decimal_places_of(value: f64) -> usize {
+    value
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+/// Lowest and highest score of a `rating` question when `min`/`max` are unset.
+pub const DEFAULT_RATING_RANGE: (i64, i64) = (1, 5);
+
+/// Scale of a `rating` question: `constraint.min..=constraint.max`, falling
+/// back to [`DEFAULT_RATING_RANGE`] for either end.
+pub fn rating_range(constraint: Option<&Constraint>) -> RangeInclusive<i64> {
+    let (low, high) = DEFAULT_RATING_RANGE;
+    let low = constraint
+        .and_then(|constraint| constraint.min)
+        .map_or(low, |min| min.ceil() as i64);
+    let high = constraint
+        .and_then(|constraint| constraint.max)
+        .map_or(high, |max| max.floor() as i64);
+    low..=high
+}
+
+/// Filesystem entry a `path` question expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PathKind {
+    File,
+    Dir,
+}
+
+impl PathKind {
+    /// Label used in specs, schemas, and hints.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PathKind::File => "file",
+            PathKind::Dir => "dir",
+        }
+    }
+}
+
+/// Well-known string shapes checked by [`Constraint::format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StringFormat {
+    /// An email address such as `user@example.com`.
+    Email,
+    /// An absolute URL with a host, such as `https://example.com/path`.
+    Url,
+    /// A hyphenated UUID such as `123e4567-e89b-12d3-a456-426614174000`.
+    Uuid,
+    /// An E.164 phone number such as `+15551234567`.
+    Phone,
+}
+
+impl StringFormat {
+    /// Label used in specs, hints, and error codes.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StringFormat::Email => "email",
+            StringFormat::Url => "url",
+            StringFormat::Uuid => "uuid",
+            StringFormat::Phone => "phone",
+        }
+    }
+
+    /// JSON Schema `format` keyword for this shape, or `None` when JSON
+    /// Schema defines none and the shape is emitted as `x-format` instead.
+    pub fn schema_format(self) -> Option<&'static str> {
+        match self {
+            StringFormat::Email => Some("email"),
+            StringFormat::Url => Some("uri"),
+            StringFormat::Uuid => Some("uuid"),
+            StringFormat::Phone => None,
+        }
+    }
+
+    /// Whether `text` has this shape.
+    pub fn matches(self, text: &str) -> bool {
+        self.check(text).is_ok()
+    }
+
+    /// Checks `text`, naming the part that failed when it does not have this
+    /// shape.
+    pub fn check(self, text: &str) -> Result<(), crate::format::FormatProblem> {
+        match self {
+            StringFormat::Email if crate::format::is_email(text) => Ok(()),
+            StringFormat::Email => Err(crate::format::FormatProblem::Invalid),
+            StringFormat::Url => crate::format::check_url(text),
+            StringFormat::Uuid if crate::format::is_uuid(text) => Ok(()),
+            StringFormat::Uuid => Err(crate::format::FormatProblem::Invalid),
+            StringFormat::Phone if crate::format::is_e164(text) => Ok(()),
+            StringFormat::Phone => Err(crate::format::FormatProblem::Invalid),
+        }
+    }
+}
+
+impl std::fmt::Display for StringFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for StringFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "email" => Ok(StringFormat::Email),
+            "url" => Ok(StringFormat::Url),
+            "uuid" => Ok(StringFormat::Uuid),
+            "phone" => Ok(StringFormat::Phone),
+            other => Err(format!("unknown string format '{}'", other)),
+        }
+    }
+}
+
+/// Built-in choice lists that expand into [`QuestionSpec::choices`] when a
+/// spec is loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ChoiceCatalog {
+    /// ISO 3166-1 alpha-2 country codes such as `DE`.
+    #[serde(rename = "iso3166-alpha2")]
+    Iso3166Alpha2,
+    /// ISO 639-1 language codes such as `de`.
+    #[serde(rename = "iso639-1")]
+    Iso6391,
+    /// IANA time zone names such as `Europe/Berlin`.
+    #[serde(rename = "timezone")]
+    Timezone,
+}
+
+impl ChoiceCatalog {
+    /// Every catalog, in the order the CLI offers them.
+    pub const ALL: [ChoiceCatalog; 3] = [
+        ChoiceCatalog::Iso3166Alpha2,
+        ChoiceCatalog::Iso6391,
+        ChoiceCatalog::Timezone,
+    ];
+
+    /// Label used in specs and render payloads.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChoiceCatalog::Iso3166Alpha2 => "iso3166-alpha2",
+            ChoiceCatalog::Iso6391 => "iso639-1",
+            ChoiceCatalog::Timezone => "timezone",
+        }
+    }
+
+    /// The sorted codes this catalog expands to.
+    pub fn choices(self) -> &'static [&'static str] {
+        match self {
+            ChoiceCatalog::Iso3166Alpha2 => crate::catalog::COUNTRIES,
+            ChoiceCatalog::Iso6391 => crate::catalog::LANGUAGES,
+            ChoiceCatalog::Timezone => crate::catalog::TIMEZONES,
+        }
+    }
+}
+
+impl std::fmt::Display for ChoiceCatalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ChoiceCatalog {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ChoiceCatalog::ALL
+            .into_iter()
+            .find(|catalog| catalog.as_str() == value)
+            .ok_or_else(|| format!("unknown choices catalog '{}'", value))
+    }
+}
+
+impl Constraint {
+    /// Error codes validation can report for this constraint, i.e. the keys
+    /// worth setting in [`Constraint::messages`].
+    pub fn error_codes(&self) -> Vec<String> {
+        let mut codes = Vec::new();
+        if self.pattern.is_some() {
+            codes.push("pattern_mismatch".to_string());
+        }
+        if let Some(format) = self.enforced_format() {
+            codes.push(format!("format_{}", format));
+        }
+        if self.min_len.is_some() {
+            codes.push("min_length".to_string());
+        }
+        if self.max_len.is_some() {
+            codes.push("max_length".to_string());
+        }
+        if self.min.is_some() {
+            let code = if self.exclusive_min {
+                "exclusive_min"
+            } else {
+                "min"
+            };
+            codes.push(code.to_string());
+        }
+        if self.max.is_some() {
+            let code = if self.exclusive_max {
+                "exclusive_max"
+            } else {
+                "max"
+            };
+            codes.push(code.to_string());
+        }
+        if self.decimal_places.is_some() {
+            codes.push("decimal_precision".to_string());
+        }
+        if self.multiple_of.is_some() {
+            codes.push("multiple_of".to_string());
+        }
+        if self.min_selected.is_some() {
+            codes.push("min_selected".to_string());
+        }
+        if self.max_selected.is_some() {
+            codes.push("max_selected".to_string());
+        }
+        codes
+    }
+
+    /// Custom message for `code`, if the spec author wrote one.
+    pub fn message_for(&self, code: &str) -> Option<&str> {
+        self.messages.as_ref()?.get(code).map(String::as_str)
+    }
+
+    /// Format that validation checks. A `phone` format defers to `pattern`
+    /// when one is set, so a spec can demand a national or prefixed shape.
+    pub fn enforced_format(&self) -> Option<StringFormat> {
+        self.format
+            .filter(|format| !(*format == StringFormat::Phone && self.pattern.is_some()))
+    }
+
+    /// Human readable numeric range, e.g. `greater than 0 and at most 10`.
+    pub fn describe_bounds(&self) -> Option<String> {
+        self.describe_bounds_with(|bound| bound.to_string())
+    }
+
+    /// Like [`Constraint::describe_bounds`], writing each bound with `show`.
+    pub fn describe_bounds_with(&self, show: impl Fn(f64) -> String) -> Option<String> {
+        let lower = self.min.map(|min| {
+            if self.exclusive_min {
+                format!("greater than {}", show(min))
+            } else {
+                format!("at least {}", show(min))
+            }
+        });
+        let upper = self.max.map(|max| {
+            if self.exclusive_max {
+                format!("less than {}", show(max))
+            } else {
+                format!("at most {}", show(max))
+            }
+        });
+        match (lower, upper) {
+            (Some(lower), Some(upper)) => Some(format!("{} and {}", lower, upper)),
+            (lower, upper) => lower.or(upper),
+        }
+    }
+}
+
+/// String transform applied to an answer before validation and storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    Trim,
+    Lowercase,
+    Uppercase,
+    CollapseWhitespace,
+    StripPrefix(String),
+    StripSuffix(String),
+}
+
+impl Transform {
+    /// Apply the transform to a single string value.
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            Transform::Trim => value.trim().to_string(),
+            Transform::Lowercase => value.to_lowercase(),
+            Transform::Uppercase => value.to_uppercase(),
+            Transform::CollapseWhitespace => value.split_whitespace().collect::<Vec<_>>().join(" "),
+            Transform::StripPrefix(prefix) => value
+                .strip_prefix(prefix.as_str())
+                .unwrap_or(value)
+                .to_string(),
+            Transform::StripSuffix(suffix) => value
+                .strip_suffix(suffix.as_str())
+                .unwrap_or(value)
+                .to_string(),
+        }
+    }
 }
 
 /// Definition of a single question inside a form.
@@ -46,12 +416,36 @@ pub struct QuestionSpec {
     pub description_i18n: Option<I18nText>,
     #[serde(default)]
     pub required: bool,
+    /// Makes the question required only while this condition holds, evaluated
+    /// against the answers like `visible_if`. Takes precedence over `required`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_if: Option<Expr>,
+    /// Shows the question with its answer but without letting it be edited
+    /// while this condition holds, e.g. for values injected from `state`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only_if: Option<Expr>,
+    /// Where the question is asked and rendered relative to the others:
+    /// questions sort by `order` (0 when unset), then by their position in
+    /// `questions`, so a negative value brings a question forward.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub choices: Option<Vec<String>>,
+    /// Built-in list that fills `choices` at load time (see [`FormSpec::resolve_catalogs`]).
+    ///
+    /// [`FormSpec::resolve_catalogs`]: crate::spec::form::FormSpec::resolve_catalogs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choices_catalog: Option<ChoiceCatalog>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_value: Option<String>,
     #[serde(default)]
     pub secret: bool,
+    /// Secrets-store path the answer is written to on submit (requires `secret`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_path: Option<String>,
+    /// Transforms applied in order to string answers before validation and storage.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub normalize: Vec<Transform>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub visible_if: Option<Expr>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -59,11 +453,49 @@ pub struct QuestionSpec {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub list: Option<ListSpec>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<ObjectSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub computed: Option<Expr>,
     #[serde(default)]
     pub policy: QuestionPolicy,
     #[serde(default)]
     pub computed_overridable: bool,
+    /// Per-locale `title`, `description` and choice labels, keyed by locale
+    /// (`id`, `en-GB`); renderers fall back to the strings above.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub i18n: Option<BTreeMap<String, LocaleStrings>>,
+}
+
+impl QuestionSpec {
+    /// Whether an answer is required given the expression context `ctx`
+    /// (see [`build_expression_context`]): `required_if` when it is set, where
+    /// a condition that cannot be evaluated counts as false, else `required`.
+    ///
+    /// [`build_expression_context`]: crate::computed::build_expression_context
+    pub fn is_required(&self, ctx: &Value) -> bool {
+        match &self.required_if {
+            Some(condition) => condition.evaluate_bool(ctx).unwrap_or(false),
+            None => self.required,
+        }
+    }
+
+    /// Whether the answer always comes from `computed`: such questions are
+    /// never asked and a differing answer fails with `computed_conflict`.
+    pub fn is_read_only(&self) -> bool {
+        self.computed.is_some() && !self.computed_overridable
+    }
+
+    /// Whether the answer cannot be edited given the expression context
+    /// `ctx`: always for [`QuestionSpec::is_read_only`] questions, otherwise
+    /// while `read_only_if` holds, where a condition that cannot be evaluated
+    /// counts as false.
+    pub fn is_read_only_in(&self, ctx: &Value) -> bool {
+        self.is_read_only()
+            || self
+                .read_only_if
+                .as_ref()
+                .is_some_and(|condition| condition.evaluate_bool(ctx) == Some(true))
+    }
 }
 
 /// Per-question overrides for progress behavior.
@@ -82,6 +514,22 @@ pub struct ListSpec {
     pub min_items: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_items: Option<usize>,
+    /// Reject repeated items: equal strings in a `string_list` answer, equal
+    /// entries in a `list` answer.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unique: bool,
+    /// Reject `list` entries that repeat another entry's values for these
+    /// fields, even when other fields differ.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_by: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<QuestionSpec>,
+}
+
+/// Sub-questions of an `object` question, answered together as one JSON
+/// object keyed by field id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Default)]
+pub struct ObjectSpec {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<QuestionSpec>,
 }
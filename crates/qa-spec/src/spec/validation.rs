@@ -2,7 +2,28 @@ use crate::expr::Expr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// How a failed check is reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationSeverity {
+    /// Listed in `errors` and makes the answers invalid.
+    #[default]
+    Error,
+    /// Listed in `warnings`; the answers stay valid.
+    Warning,
+}
+
+impl ValidationSeverity {
+    pub fn is_error(&self) -> bool {
+        matches!(self, ValidationSeverity::Error)
+    }
+}
+
 /// Cross-question validation rules expressed as reusable conditions.
+///
+/// `condition` describes the failure: when it evaluates to `true`, validation
+/// reports `message` at the first of `fields`, as an error or a warning
+/// depending on `severity`. Rules whose `fields` are all hidden are skipped.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct CrossFieldValidation {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -13,4 +34,6 @@ pub struct CrossFieldValidation {
     pub condition: Expr,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
+    #[serde(default, skip_serializing_if = "ValidationSeverity::is_error")]
+    pub severity: ValidationSeverity,
 }
@@ -4,7 +4,7 @@ use serde_json::{Map, Value};
 use thiserror::Error;
 
 use crate::secrets::{SecretAccessResult, SecretAction, evaluate};
-use crate::spec::form::SecretsPolicy;
+use crate::spec::form::{FormSpec, SecretsPolicy};
 
 /// Targets that store operations can write into.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -25,6 +25,30 @@ pub struct StoreOp {
     pub value: Value,
 }
 
+/// Store operations for a submit: the spec's explicit `store` ops followed by an
+/// implicit secrets write for every answered question with a `secret_path`.
+pub fn effective_store_ops(spec: &FormSpec, answers: &Value) -> Vec<StoreOp> {
+    let mut ops = spec.store.clone();
+    ops.extend(spec.questions.iter().filter_map(|question| {
+        let path = question
+            .secret_path
+            .as_deref()
+            .filter(|_| question.secret)?;
+        let value = answers.get(&question.id)?;
+        Some(StoreOp {
+            target: StoreTarget::Secrets,
+            path: secret_pointer(path),
+            value: value.clone(),
+        })
+    }));
+    ops
+}
+
+/// Normalize a `secret_path` (`aws/key` or `/aws/key`) into a JSON pointer.
+pub fn secret_pointer(path: &str) -> String {
+    format!("/{}", path.trim_start_matches('/'))
+}
+
 /// Context mutated by store operations.
 #[derive(Debug, Clone)]
 pub struct StoreContext {
@@ -37,9 +61,19 @@ pub struct StoreContext {
 
 impl StoreContext {
     pub fn from_value(ctx: &Value) -> Self {
+        let answers = ctx
+            .get("answers")
+            .cloned()
+            .unwrap_or_else(|| Value::Object(Map::new()));
+        Self::with_answers(ctx, answers)
+    }
+
+    /// Like [`StoreContext::from_value`], taking `answers` instead of copying
+    /// `ctx.answers`.
+    pub fn with_answers(ctx: &Value, answers: Value) -> Self {
         let default = || Value::Object(Map::new());
         Self {
-            answers: ctx.get("answers").cloned().unwrap_or_else(default),
+            answers,
             state: ctx.get("state").cloned().unwrap_or_else(default),
             config: ctx.get("config").cloned().unwrap_or_else(default),
             payload_out: ctx.get("payload_out").cloned().unwrap_or_else(default),
@@ -80,6 +114,17 @@ impl StoreContext {
         Ok(())
     }
 
+    /// Consumes the context into the same value as [`StoreContext::to_value`].
+    pub fn into_value(self) -> Value {
+        let mut map = Map::new();
+        map.insert("answers".into(), self.answers);
+        map.insert("state".into(), self.state);
+        map.insert("config".into(), self.config);
+        map.insert("payload_out".into(), self.payload_out);
+        map.insert("secrets".into(), self.secrets);
+        Value::Object(map)
+    }
+
     pub fn to_value(&self) -> Value {
         let mut map = Map::new();
         map.insert("answers".into(), self.answers.clone());
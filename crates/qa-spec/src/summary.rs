@@ -0,0 +1,153 @@
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::answers::ValidationResult;
+use crate::render::{RenderPayload, RenderQuestion, current_value_display};
+use crate::spec::question::QuestionType;
+
+/// Where a question stands on the review screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryStatus {
+    Answered,
+    /// Unanswered; only a problem when the entry is `required`.
+    Missing,
+    /// Answered, but the answer fails validation.
+    Invalid,
+}
+
+/// One visible question on the review screen.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SummaryEntry {
+    pub id: String,
+    pub title: String,
+    /// The answer as shown to users: secrets masked, lists as an item count.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    pub status: SummaryStatus,
+    pub required: bool,
+    /// Validation messages of `invalid` entries.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Answers to review before the final submit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenderSummary {
+    pub form_id: String,
+    pub form_title: String,
+    /// No required question is missing and no answer is invalid.
+    pub complete: bool,
+    pub entries: Vec<SummaryEntry>,
+    #[serde(skip)]
+    locale: Option<String>,
+}
+
+impl RenderSummary {
+    /// The summary as text, one line per entry, with missing required and
+    /// invalid answers flagged.
+    pub fn to_text(&self) -> String {
+        let labels = crate::RenderLabels::for_locale(self.locale.as_deref());
+        let mut text = format!("{}: {} ({})", labels.review, self.form_title, self.form_id);
+        for entry in &self.entries {
+            let _ = write!(text, "\n - {} ({})", entry.id, entry.title);
+            if let Some(value) = &entry.value {
+                let _ = write!(text, " = {}", value);
+            }
+            match entry.status {
+                SummaryStatus::Missing if entry.required => {
+                    let _ = write!(text, " [{}]", labels.missing);
+                }
+                SummaryStatus::Invalid => {
+                    let _ = write!(text, " [{}: {}]", labels.invalid, entry.errors.join("; "));
+                }
+                _ => {}
+            }
+        }
+        text
+    }
+}
+
+/// Review screen for `payload`'s visible questions, each `answered`,
+/// `missing` or `invalid` according to `validation`.
+pub fn render_summary(payload: &RenderPayload, validation: &ValidationResult) -> RenderSummary {
+    let labels = payload.labels();
+    let entries = payload
+        .questions
+        .iter()
+        .filter(|question| question.visible)
+        .map(|question| {
+            let errors = validation
+                .errors
+                .iter()
+                .filter(|error| {
+                    concerns(
+                        error.question_id.as_deref(),
+                        error.path.as_deref(),
+                        &question.id,
+                    )
+                })
+                .map(|error| error.message.clone())
+                .collect::<Vec<_>>();
+            let value = summary_value(question, labels.item_count);
+            let status = if validation.missing_required.contains(&question.id) || value.is_none() {
+                SummaryStatus::Missing
+            } else if errors.is_empty() {
+                SummaryStatus::Answered
+            } else {
+                SummaryStatus::Invalid
+            };
+            SummaryEntry {
+                id: question.id.clone(),
+                title: question.title.clone(),
+                value,
+                status,
+                required: question.required,
+                errors: if status == SummaryStatus::Invalid {
+                    errors
+                } else {
+                    Vec::new()
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+    let complete = entries.iter().all(|entry| match entry.status {
+        SummaryStatus::Answered => true,
+        SummaryStatus::Missing => !entry.required,
+        SummaryStatus::Invalid => false,
+    });
+    RenderSummary {
+        form_id: payload.form_id.clone(),
+        form_title: payload.form_title.clone(),
+        complete,
+        entries,
+        locale: payload.locale.clone(),
+    }
+}
+
+/// Whether an error about `question_id`, or at the pointer `path`, belongs
+/// to the question `id` (including the fields of its list entries).
+fn concerns(question_id: Option<&str>, path: Option<&str>, id: &str) -> bool {
+    if question_id == Some(id) {
+        return true;
+    }
+    path.and_then(|path| path.strip_prefix('/'))
+        .and_then(|path| path.split('/').next())
+        .is_some_and(|head| head.replace("~1", "/").replace("~0", "~") == id)
+}
+
+fn summary_value(question: &RenderQuestion, item_count: &str) -> Option<String> {
+    let value = question.current_value.as_ref()?;
+    if matches!(question.kind, QuestionType::List)
+        && let Some(entries) = value.as_array()
+    {
+        let count = question.current_value_total.unwrap_or(entries.len());
+        return Some(item_count.replace("{count}", &count.to_string()));
+    }
+    if value == &Value::Null {
+        return None;
+    }
+    current_value_display(question)
+}
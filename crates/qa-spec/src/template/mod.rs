@@ -1,18 +1,23 @@
 use crate::secrets::{SecretAccessResult, SecretAction, evaluate};
 use crate::spec::form::{FormSpec, SecretsPolicy};
+use crate::theme::ThemeSpec;
 use handlebars::{
     Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderError,
     RenderErrorReason,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use thiserror::Error;
 
 /// Modes describing how missing values are handled.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum ResolutionMode {
     /// Missing values emit an error.
     Strict,
     /// Missing values leave handlebars tokens untouched.
+    #[default]
     Relaxed,
 }
 
@@ -64,6 +69,16 @@ impl TemplateContext {
         self
     }
 
+    /// Build a context from runtime `ctx` (payload/state/config) and the current answers.
+    pub fn from_runtime(ctx: &Value, answers: &Value) -> Self {
+        let empty = || Value::Object(Map::new());
+        Self::default()
+            .with_payload(ctx.get("payload").cloned().unwrap_or_else(empty))
+            .with_state(ctx.get("state").cloned().unwrap_or_else(empty))
+            .with_config(ctx.get("config").cloned().unwrap_or_else(empty))
+            .with_answers(answers.clone())
+    }
+
     /// Set optional secrets with policy metadata.
     pub fn with_secrets(
         mut self,
@@ -198,11 +213,12 @@ impl TemplateEngine {
                 .as_ref()
                 .map(|value| self.resolve_string(value, ctx))
                 .transpose()?;
-            next.theme = presentation
-                .theme
-                .as_ref()
-                .map(|value| self.resolve_string(value, ctx))
-                .transpose()?;
+            next.theme = match &presentation.theme {
+                Some(ThemeSpec::Named(name)) => {
+                    Some(ThemeSpec::Named(self.resolve_string(name, ctx)?))
+                }
+                other => other.clone(),
+            };
             Some(next)
         } else {
             None
@@ -0,0 +1,169 @@
+//! Form themes (`presentation.theme`): an accent color, a logo, a background
+//! image and light or dark mode, given as a known name or inline.
+
+use std::fmt::Write as _;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::color::normalize_color;
+
+/// Theme used when a name is unknown.
+pub const DEFAULT_THEME: &str = "default";
+
+/// Names [`ThemeSpec::Named`] can refer to.
+pub const KNOWN_THEMES: &[&str] = &[DEFAULT_THEME, "light", "dark"];
+
+/// Light or dark surfaces, passed to hosts as `color-scheme`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeMode {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ThemeMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThemeMode::Light => "light",
+            ThemeMode::Dark => "dark",
+        }
+    }
+}
+
+/// Look of a form's cards and pages.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Theme {
+    /// Accent color, `#RRGGBB` or a color name such as `teal`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    /// Logo shown above the form title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_image: Option<String>,
+    #[serde(default)]
+    pub mode: ThemeMode,
+}
+
+impl Theme {
+    /// The built-in theme called `name`.
+    pub fn named(name: &str) -> Option<Self> {
+        let (accent, mode) = match name {
+            "default" | "light" => ("#0078d4", ThemeMode::Light),
+            "dark" => ("#4cc2ff", ThemeMode::Dark),
+            _ => return None,
+        };
+        Some(Self {
+            accent: Some(accent.to_string()),
+            mode,
+            ..Self::default()
+        })
+    }
+
+    /// CSS custom properties for hosts rendering forms as HTML:
+    /// `color-scheme` plus `--qa-accent`, `--qa-logo-url` and
+    /// `--qa-background-image` when set.
+    pub fn css_custom_properties(&self) -> String {
+        let mut css = format!(":root {{\n  color-scheme: {};\n", self.mode.as_str());
+        if let Some(accent) = &self.accent {
+            let _ = writeln!(css, "  --qa-accent: {};", accent);
+        }
+        if let Some(url) = &self.logo_url {
+            let _ = writeln!(css, "  --qa-logo-url: {};", css_url(url));
+        }
+        if let Some(url) = &self.background_image {
+            let _ = writeln!(css, "  --qa-background-image: {};", css_url(url));
+        }
+        css.push('}');
+        css
+    }
+}
+
+fn css_url(url: &str) -> String {
+    format!(
+        "url(\"{}\")",
+        url.replace('\\', "%5C")
+            .replace('"', "%22")
+            .replace('\n', "%0A")
+    )
+}
+
+/// `presentation.theme`: the name of a built-in theme or an inline [`Theme`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ThemeSpec {
+    Named(String),
+    Inline(Theme),
+}
+
+impl From<&str> for ThemeSpec {
+    fn from(name: &str) -> Self {
+        ThemeSpec::Named(name.to_string())
+    }
+}
+
+impl From<String> for ThemeSpec {
+    fn from(name: String) -> Self {
+        ThemeSpec::Named(name)
+    }
+}
+
+/// A [`ThemeSpec`] ready to apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedTheme {
+    /// Built-in theme name; `None` for inline themes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub theme: Theme,
+    /// Why the spec's theme was not applied as written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostic: Option<String>,
+}
+
+impl ThemeSpec {
+    /// Resolves the theme. An unknown name falls back to
+    /// [`DEFAULT_THEME`] and an accent that is not a color is dropped; both
+    /// are reported in `diagnostic`.
+    pub fn resolve(&self) -> ResolvedTheme {
+        match self {
+            ThemeSpec::Named(name) => match Theme::named(name) {
+                Some(theme) => ResolvedTheme {
+                    name: Some(name.clone()),
+                    theme,
+                    diagnostic: None,
+                },
+                None => ResolvedTheme {
+                    name: Some(DEFAULT_THEME.to_string()),
+                    theme: Theme::named(DEFAULT_THEME).expect("default theme is built in"),
+                    diagnostic: Some(format!(
+                        "unknown theme '{}'; using '{}' (known themes: {})",
+                        name,
+                        DEFAULT_THEME,
+                        KNOWN_THEMES.join(", ")
+                    )),
+                },
+            },
+            ThemeSpec::Inline(theme) => {
+                let mut theme = theme.clone();
+                let mut diagnostic = None;
+                if let Some(accent) = theme.accent.take() {
+                    theme.accent = normalize_color(&accent);
+                    if theme.accent.is_none() {
+                        diagnostic = Some(format!(
+                            "theme accent '{}' is not a color; leaving it out",
+                            accent
+                        ));
+                    }
+                }
+                ResolvedTheme {
+                    name: None,
+                    theme,
+                    diagnostic,
+                }
+            }
+        }
+    }
+}
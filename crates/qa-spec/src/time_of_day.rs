@@ -0,0 +1,82 @@
+//! Times of day (`HH:MM:SS`) answered by `time` questions.
+//!
+//! Bounds on a `time` question are seconds after midnight, so `min: 28800`
+//! means "08:00:00 or later".
+
+/// Accepted shapes in specs and answers, as shown to people filling in a form.
+pub const TIME_SYNTAX: &str = "HH:MM or HH:MM:SS";
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// Seconds after midnight of a time written exactly as `HH:MM` or
+/// `HH:MM:SS`, with two digits per part.
+pub fn seconds_of_day(text: &str) -> Option<u32> {
+    let (hours, minutes, seconds) = match text.split(':').collect::<Vec<_>>().as_slice() {
+        [hours, minutes] => (digits(hours, 2..=2)?, digits(minutes, 2..=2)?, 0),
+        [hours, minutes, seconds] => (
+            digits(hours, 2..=2)?,
+            digits(minutes, 2..=2)?,
+            digits(seconds, 2..=2)?,
+        ),
+        _ => return None,
+    };
+    clock_seconds(hours, minutes, seconds)
+}
+
+/// Writes seconds after midnight as `HH:MM:SS`, wrapping past midnight.
+pub fn format_time_of_day(seconds: u32) -> String {
+    let seconds = seconds % SECONDS_PER_DAY;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Normalizes a loosely written time to `HH:MM:SS`.
+///
+/// Accepts surrounding whitespace, a single-digit hour (`9:05`), and 12-hour
+/// times with an `am`/`pm` suffix, where the minutes may be left out
+/// (`9am`, `2:30 PM`). Without a suffix the minutes are required (`14:30`).
+/// Returns `None` for anything that is not a valid time of day.
+pub fn normalize_time(raw: &str) -> Option<String> {
+    let lowered = raw.trim().to_ascii_lowercase();
+    let (clock, meridiem) = match lowered
+        .strip_suffix("am")
+        .map(|clock| (clock, Some(false)))
+        .or_else(|| lowered.strip_suffix("pm").map(|clock| (clock, Some(true))))
+    {
+        Some((clock, meridiem)) => (clock.trim_end(), meridiem),
+        None => (lowered.as_str(), None),
+    };
+    let parts = clock.split(':').collect::<Vec<_>>();
+    let (hours, minutes, seconds) = match (parts.as_slice(), meridiem) {
+        ([hours], Some(_)) => (digits(hours, 1..=2)?, 0, 0),
+        ([hours, minutes], _) => (digits(hours, 1..=2)?, digits(minutes, 2..=2)?, 0),
+        ([hours, minutes, seconds], _) => (
+            digits(hours, 1..=2)?,
+            digits(minutes, 2..=2)?,
+            digits(seconds, 2..=2)?,
+        ),
+        _ => return None,
+    };
+    let hours = match meridiem {
+        Some(_) if !(1..=12).contains(&hours) => return None,
+        Some(pm) => hours % 12 + if pm { 12 } else { 0 },
+        None => hours,
+    };
+    clock_seconds(hours, minutes, seconds).map(format_time_of_day)
+}
+
+fn clock_seconds(hours: u32, minutes: u32, seconds: u32) -> Option<u32> {
+    (hours < 24 && minutes < 60 && seconds < 60).then_some(hours * 3600 + minutes * 60 + seconds)
+}
+
+fn digits(part: &str, lengths: std::ops::RangeInclusive<usize>) -> Option<u32> {
+    Some(part)
+        .filter(|part| {
+            lengths.contains(&part.len()) && part.bytes().all(|byte| byte.is_ascii_digit())
+        })
+        .and_then(|part| part.parse::<u32>().ok())
+}
@@ -1,20 +1,384 @@
-use regex::Regex;
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
 
-use crate::answers::{ValidationError, ValidationResult};
-use crate::computed::{apply_computed_answers, build_expression_context};
+use crate::answers::{
+    ValidationError, ValidationResult, canonical_eq, canonical_json, json_pointer,
+};
+use crate::coerce::coerce_answers;
+use crate::color::is_hex_color;
+use crate::compiled::Derived;
+use crate::computed::{build_expression_context_with_ctx, materialize_computed};
+use crate::date::is_iso_date;
+use crate::expr::Expr;
+use crate::limits::{DEFAULT_MAX_VALUE_DEPTH, json_size, value_too_deep};
+use crate::normalize::apply_normalization;
+use crate::pattern::{CompiledPatterns, compile_pattern};
 use crate::spec::form::FormSpec;
-use crate::spec::question::{QuestionSpec, QuestionType};
-use crate::visibility::{VisibilityMode, resolve_visibility};
+use crate::spec::question::{
+    ListSpec, QuestionSpec, QuestionType, decimal_places_of, rating_range,
+};
+use crate::spec::validation::ValidationSeverity;
+use crate::time_of_day::{format_time_of_day, seconds_of_day};
+use crate::visibility::{
+    ListItemContexts, VisibilityDiagnostic, VisibilityMap, VisibilityMode, list_field_visible,
+    list_item_context, resolve_visibility_with_ctx,
+};
 
 pub fn validate(spec: &FormSpec, answers: &Value) -> ValidationResult {
-    let computed_answers = apply_computed_answers(spec, answers);
-    let visibility = resolve_visibility(spec, &computed_answers, VisibilityMode::Visible);
+    validate_with_ctx(spec, answers, &Value::Null)
+}
+
+/// Like [`validate`], with the runtime `ctx` available to computed fields,
+/// `visible_if`, `required_if` and cross-field validations (see
+/// [`build_expression_context_with_ctx`]).
+pub fn validate_with_ctx(spec: &FormSpec, answers: &Value, ctx: &Value) -> ValidationResult {
+    if let Some(rejected) = reject_unbounded(spec, answers) {
+        return rejected;
+    }
+    let normalized = apply_normalization(spec, answers);
+    let computed_answers = materialize_computed(spec, &normalized, ctx);
+    let visibility =
+        resolve_visibility_with_ctx(spec, &computed_answers, ctx, VisibilityMode::Visible).map;
+    validate_computed(spec, None, ctx, &normalized, computed_answers, &visibility)
+}
+
+/// Like [`validate`], except that a `visible_if` that cannot be evaluated
+/// fails validation with a `visibility_error` (see [`visibility_errors`])
+/// instead of showing its question.
+pub fn validate_strict(spec: &FormSpec, answers: &Value) -> ValidationResult {
+    validate_strict_with_ctx(spec, answers, &Value::Null)
+}
+
+/// [`validate_strict`] with the runtime `ctx`, as in [`validate_with_ctx`].
+pub fn validate_strict_with_ctx(spec: &FormSpec, answers: &Value, ctx: &Value) -> ValidationResult {
+    if let Some(rejected) = reject_unbounded(spec, answers) {
+        return rejected;
+    }
+    let normalized = apply_normalization(spec, answers);
+    let computed_answers = materialize_computed(spec, &normalized, ctx);
+    let resolved = resolve_visibility_with_ctx(spec, &computed_answers, ctx, VisibilityMode::Error);
+    let mut result = validate_computed(
+        spec,
+        None,
+        ctx,
+        &normalized,
+        computed_answers,
+        &resolved.map,
+    );
+    if !resolved.diagnostics.is_empty() {
+        result.valid = false;
+        result
+            .errors
+            .extend(visibility_errors(&resolved.diagnostics));
+    }
+    result
+}
+
+/// `visibility_error` validation errors for conditions that could not be
+/// evaluated. The params give the `reason` (`missing_value`,
+/// `type_mismatch`, ...), the failing part of the condition as compact JSON
+/// in `expression`, and the `path` it reads when it reads one.
+pub fn visibility_errors(diagnostics: &[VisibilityDiagnostic]) -> Vec<ValidationError> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut params = BTreeMap::new();
+            if let Ok(Value::String(reason)) = serde_json::to_value(diagnostic.reason) {
+                params.insert("reason".into(), reason);
+            }
+            if let Ok(expression) = serde_json::to_string(&diagnostic.expr_fragment) {
+                params.insert("expression".into(), expression);
+            }
+            if let Expr::Var { path }
+            | Expr::Answer { path }
+            | Expr::IsSet { path }
+            | Expr::IsEmpty { path } = &diagnostic.expr_fragment
+            {
+                params.insert("path".into(), path.clone());
+            }
+            ValidationError {
+                question_id: Some(diagnostic.question_id.clone()),
+                path: Some(json_pointer([&diagnostic.question_id])),
+                paths: Vec::new(),
+                message: "qa_spec.visibility_error".into(),
+                code: Some("visibility_error".into()),
+                params,
+            }
+        })
+        .collect()
+}
+
+/// Why [`validate_field`] did not check a question.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FieldValidationError {
+    #[error("unknown question '{0}'")]
+    UnknownQuestion(String),
+    /// The question is hidden by `visible_if` for these answers, so there is
+    /// nothing to validate yet.
+    #[error("question '{0}' is not visible")]
+    NotVisible(String),
+}
+
+/// Validates a single question, e.g. when a live form leaves the field.
+///
+/// The result holds the question's own errors and warnings (type,
+/// constraint, choices, list items), cross-field validations that name it in
+/// `fields`, and whether it is missing although required. Other questions
+/// are not reported on, and `unknown_fields` is always empty.
+pub fn validate_field(
+    spec: &FormSpec,
+    answers: &Value,
+    question_id: &str,
+) -> Result<ValidationResult, FieldValidationError> {
+    validate_field_with_ctx(spec, answers, &Value::Null, question_id)
+}
+
+/// [`validate_field`] with the runtime `ctx`, as in [`validate_with_ctx`].
+pub fn validate_field_with_ctx(
+    spec: &FormSpec,
+    answers: &Value,
+    ctx: &Value,
+    question_id: &str,
+) -> Result<ValidationResult, FieldValidationError> {
+    if !spec
+        .questions
+        .iter()
+        .any(|question| question.id == question_id)
+    {
+        return Err(FieldValidationError::UnknownQuestion(
+            question_id.to_string(),
+        ));
+    }
+    if let Some(rejected) = reject_unbounded(spec, answers) {
+        return Ok(scope_rejection(rejected, question_id));
+    }
+    let normalized = apply_normalization(spec, answers);
+    let computed_answers = materialize_computed(spec, &normalized, ctx);
+    let visibility =
+        resolve_visibility_with_ctx(spec, &computed_answers, ctx, VisibilityMode::Visible).map;
+    validate_field_computed(
+        spec,
+        None,
+        ctx,
+        &normalized,
+        computed_answers,
+        &visibility,
+        question_id,
+    )
+}
+
+/// [`validate_field`] for a question known to exist, with computed answers.
+pub(crate) fn validate_field_computed(
+    spec: &FormSpec,
+    derived: Option<&Derived>,
+    runtime_ctx: &Value,
+    submitted: &Value,
+    computed_answers: Value,
+    visibility: &VisibilityMap,
+    question_id: &str,
+) -> Result<ValidationResult, FieldValidationError> {
+    if !visibility.get(question_id).copied().unwrap_or(true) {
+        return Err(FieldValidationError::NotVisible(question_id.to_string()));
+    }
+    let result = validate_computed(
+        spec,
+        derived,
+        runtime_ctx,
+        submitted,
+        computed_answers,
+        visibility,
+    );
+    Ok(scope_to_field(result, question_id))
+}
+
+/// Keeps the parts of `result` that concern `question_id`: errors and
+/// warnings at or below its pointer, including multi-field errors that list
+/// it in `paths`.
+pub(crate) fn scope_to_field(result: ValidationResult, question_id: &str) -> ValidationResult {
+    let pointer = json_pointer([question_id]);
+    let concerns = |error: &ValidationError| {
+        error.path.iter().chain(&error.paths).any(|path| {
+            path.strip_prefix(pointer.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    };
+    let errors = result
+        .errors
+        .into_iter()
+        .filter(concerns)
+        .collect::<Vec<_>>();
+    let missing_required = result
+        .missing_required
+        .into_iter()
+        .filter(|id| id == question_id)
+        .collect::<Vec<_>>();
+    ValidationResult {
+        valid: errors.is_empty() && missing_required.is_empty(),
+        errors,
+        missing_required,
+        unknown_fields: Vec::new(),
+        warnings: result.warnings.into_iter().filter(concerns).collect(),
+    }
+}
+
+/// Scopes an early rejection like [`scope_to_field`], except that an error
+/// about the answers as a whole (without a path) concerns every field.
+pub(crate) fn scope_rejection(rejected: ValidationResult, question_id: &str) -> ValidationResult {
+    if rejected.errors.iter().any(|error| error.path.is_none()) {
+        return rejected;
+    }
+    scope_to_field(rejected, question_id)
+}
+
+/// Options for [`validate_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Convert string answers to the types their questions expect before
+    /// validating, as described on [`coerce_answers`]. Strings that cannot be
+    /// converted still fail with `type_mismatch`.
+    pub coerce: bool,
+}
+
+/// Like [`validate`], applying `options`. Also returns the answers that were
+/// validated, which are the coerced ones when [`ValidationOptions::coerce`]
+/// is set.
+pub fn validate_with(
+    spec: &FormSpec,
+    answers: &Value,
+    options: &ValidationOptions,
+) -> (Value, ValidationResult) {
+    let answers = if options.coerce {
+        coerce_answers(spec, answers)
+    } else {
+        answers.clone()
+    };
+    let result = validate(spec, &answers);
+    (answers, result)
+}
+
+/// Like [`validate`], reusing a `visibility` already resolved for these
+/// answers (e.g. by [`crate::VisibilityCache`]) instead of evaluating every
+/// `visible_if` again.
+pub fn validate_with_visibility(
+    spec: &FormSpec,
+    answers: &Value,
+    visibility: &VisibilityMap,
+) -> ValidationResult {
+    validate_with_visibility_and_ctx(spec, answers, &Value::Null, visibility)
+}
+
+/// [`validate_with_visibility`] with the runtime `ctx`, as in [`validate_with_ctx`].
+pub fn validate_with_visibility_and_ctx(
+    spec: &FormSpec,
+    answers: &Value,
+    ctx: &Value,
+    visibility: &VisibilityMap,
+) -> ValidationResult {
+    if let Some(rejected) = reject_unbounded(spec, answers) {
+        return rejected;
+    }
+    let normalized = apply_normalization(spec, answers);
+    let computed_answers = materialize_computed(spec, &normalized, ctx);
+    validate_computed(spec, None, ctx, &normalized, computed_answers, visibility)
+}
+
+/// Rejects answers that are nested too deeply or too large before
+/// normalization or list validation walks them.
+pub(crate) fn reject_unbounded(spec: &FormSpec, answers: &Value) -> Option<ValidationResult> {
+    // Depth first: measuring the size recurses into the value.
+    reject_too_deep(answers).or_else(|| reject_oversized(spec, answers))
+}
+
+/// Rejects answers larger than the spec's byte limits
+/// ([`FormSpec::answer_byte_limit`] per question,
+/// [`FormSpec::total_byte_limit`] overall) with `payload_too_large` errors
+/// giving the measured `size` and the `limit`, both in bytes of compact JSON.
+/// Answers nested deeper than [`DEFAULT_MAX_VALUE_DEPTH`] must be rejected
+/// first.
+pub fn reject_oversized(spec: &FormSpec, answers: &Value) -> Option<ValidationResult> {
+    let too_large = |question_id: Option<&str>, size: usize, limit: usize| {
+        let mut params = BTreeMap::new();
+        params.insert("size".into(), size.to_string());
+        params.insert("limit".into(), limit.to_string());
+        ValidationError {
+            question_id: question_id.map(str::to_string),
+            path: question_id.map(|id| json_pointer([id])),
+            paths: Vec::new(),
+            message: "qa_spec.payload_too_large".into(),
+            code: Some("payload_too_large".into()),
+            params,
+        }
+    };
+    let total = json_size(answers);
+    let errors = if total > spec.total_byte_limit() {
+        vec![too_large(None, total, spec.total_byte_limit())]
+    } else {
+        answers
+            .as_object()?
+            .iter()
+            .filter_map(|(question_id, value)| {
+                let size = json_size(value);
+                (size > spec.answer_byte_limit())
+                    .then(|| too_large(Some(question_id), size, spec.answer_byte_limit()))
+            })
+            .collect()
+    };
+    (!errors.is_empty()).then(|| ValidationResult {
+        valid: false,
+        errors,
+        missing_required: Vec::new(),
+        unknown_fields: Vec::new(),
+        warnings: Vec::new(),
+    })
+}
+
+/// Rejects answers nested deeper than [`DEFAULT_MAX_VALUE_DEPTH`].
+fn reject_too_deep(answers: &Value) -> Option<ValidationResult> {
+    let found = value_too_deep(answers, DEFAULT_MAX_VALUE_DEPTH)?;
+    let question_id = found
+        .pointer
+        .split('/')
+        .nth(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"));
+    let mut params = BTreeMap::new();
+    params.insert("limit".into(), DEFAULT_MAX_VALUE_DEPTH.to_string());
+    Some(ValidationResult {
+        valid: false,
+        errors: vec![ValidationError {
+            question_id,
+            path: Some(found.pointer),
+            paths: Vec::new(),
+            message: "qa_spec.too_deep".into(),
+            code: Some("too_deep".into()),
+            params,
+        }],
+        missing_required: Vec::new(),
+        unknown_fields: Vec::new(),
+        warnings: Vec::new(),
+    })
+}
+
+/// Validates answers that already carry their computed values. `submitted`
+/// holds the answers before computation, checked with [`computed_conflicts`].
+/// `derived` comes from a [`crate::CompiledFormSpec`]; without it lookups are
+/// rebuilt. `runtime_ctx` is the host context conditions may read.
+pub(crate) fn validate_computed(
+    spec: &FormSpec,
+    derived: Option<&Derived>,
+    runtime_ctx: &Value,
+    submitted: &Value,
+    computed_answers: Value,
+    visibility: &VisibilityMap,
+) -> ValidationResult {
     let answers_map = computed_answers.as_object().cloned().unwrap_or_default();
+    let patterns = derived.map(|derived| &derived.patterns);
 
     let mut errors = Vec::new();
+    let mut warnings = Vec::new();
     let mut missing_required = Vec::new();
+    let ctx = build_expression_context_with_ctx(&computed_answers, runtime_ctx);
 
     for question in &spec.questions {
         if !visibility.get(&question.id).copied().unwrap_or(true) {
@@ -23,45 +387,97 @@ pub fn validate(spec: &FormSpec, answers: &Value) -> ValidationResult {
 
         match answers_map.get(&question.id) {
             None => {
-                if question.required {
+                if question.is_required(&ctx) {
                     missing_required.push(question.id.clone());
                 }
             }
             Some(value) => {
-                if let Some(error) = validate_value(question, value) {
-                    errors.push(error);
+                let found = match (question.kind, value.as_array()) {
+                    (QuestionType::MultiEnum, Some(selected)) => {
+                        validate_selection(question, selected)
+                    }
+                    (QuestionType::StringList, Some(items)) => {
+                        validate_string_list(question, items, patterns)
+                    }
+                    _ => validate_value(question, value, &computed_answers, patterns)
+                        .into_iter()
+                        .collect(),
+                };
+                if found.is_empty() {
+                    warnings.extend(constraint_warning(question, value, patterns));
                 }
+                errors.extend(found);
             }
         }
     }
+    errors.extend(computed_conflicts(
+        spec,
+        submitted,
+        &computed_answers,
+        visibility,
+    ));
+
+    let unknown_fields: Vec<String> = match derived {
+        Some(derived) => answers_map
+            .keys()
+            .filter(|key| !derived.question_index.contains_key(*key))
+            .cloned()
+            .collect(),
+        None => {
+            let all_ids: std::collections::BTreeSet<_> = spec
+                .questions
+                .iter()
+                .map(|question| question.id.as_str())
+                .collect();
+            answers_map
+                .keys()
+                .filter(|key| !all_ids.contains(key.as_str()))
+                .cloned()
+                .collect()
+        }
+    };
 
-    let all_ids: std::collections::BTreeSet<_> = spec
-        .questions
-        .iter()
-        .map(|question| question.id.clone())
-        .collect();
-    let unknown_fields: Vec<String> = answers_map
-        .keys()
-        .filter(|key| !all_ids.contains(*key))
-        .cloned()
-        .collect();
-
-    let ctx = build_expression_context(&computed_answers);
     for validation in &spec.validations {
+        // A rule about questions the user cannot see has nothing to report.
+        let all_hidden = !validation.fields.is_empty()
+            && validation
+                .fields
+                .iter()
+                .all(|field| !visibility.get(field).copied().unwrap_or(true));
+        if all_hidden {
+            continue;
+        }
         if let Some(true) = validation.condition.evaluate_bool(&ctx) {
             let question_id = validation
                 .fields
                 .first()
                 .cloned()
                 .or_else(|| validation.id.clone());
-            let path = validation.fields.first().map(|field| format!("/{}", field));
-            errors.push(ValidationError {
+            let paths = validation
+                .fields
+                .iter()
+                .map(|field| json_pointer([field]))
+                .collect::<Vec<_>>();
+            let path = paths.first().cloned();
+            let mut params = BTreeMap::new();
+            if let Some(id) = &validation.id {
+                params.insert("validation".into(), id.clone());
+            }
+            if !validation.fields.is_empty() {
+                params.insert("fields".into(), validation.fields.join(","));
+            }
+            let error = ValidationError {
                 question_id,
                 path,
+                paths: if paths.len() > 1 { paths } else { Vec::new() },
                 message: validation.message.clone(),
                 code: validation.code.clone(),
-                params: BTreeMap::new(),
-            });
+                params,
+            };
+            match validation.severity {
+                ValidationSeverity::Error => errors.push(error),
+                ValidationSeverity::Warning => warnings.push(error),
+            }
         }
     }
 
@@ -70,14 +486,164 @@ pub fn validate(spec: &FormSpec, answers: &Value) -> ValidationResult {
         errors,
         missing_required,
         unknown_fields,
+        warnings,
     }
 }
 
-fn validate_value(question: &QuestionSpec, value: &Value) -> Option<ValidationError> {
+/// `computed_conflict` errors for visible questions whose `computed` value
+/// cannot be overridden but whose `submitted` answer differs from it in
+/// `computed_answers`. Computation replaces such answers, so without this
+/// check a manual override would be dropped silently.
+pub fn computed_conflicts(
+    spec: &FormSpec,
+    submitted: &Value,
+    computed_answers: &Value,
+    visibility: &VisibilityMap,
+) -> Vec<ValidationError> {
+    spec.questions
+        .iter()
+        .filter(|question| question.is_read_only())
+        .filter(|question| visibility.get(&question.id).copied().unwrap_or(true))
+        .filter_map(|question| {
+            let supplied = submitted.get(&question.id)?;
+            let computed = computed_answers.get(&question.id)?;
+            if canonical_eq(supplied, computed) {
+                return None;
+            }
+            let mut error = base_error(question, "qa_spec.computed_conflict", "computed_conflict");
+            if !question.secret {
+                error.params.insert("expected".into(), value_text(computed));
+            }
+            Some(error)
+        })
+        .collect()
+}
+
+/// `read_only` errors for visible questions that are read-only for `answers`
+/// (see [`QuestionSpec::is_read_only_in`]) but whose answer differs from the
+/// one in `previous`, such as a patch editing a locked field. `ctx` is the
+/// runtime context conditions may read. Computed questions are left to
+/// [`computed_conflicts`].
+pub fn read_only_changes(
+    spec: &FormSpec,
+    previous: &Value,
+    answers: &Value,
+    ctx: &Value,
+    visibility: &VisibilityMap,
+) -> Vec<ValidationError> {
+    let expression_ctx = build_expression_context_with_ctx(answers, ctx);
+    spec.questions
+        .iter()
+        .filter(|question| !question.is_read_only())
+        .filter(|question| visibility.get(&question.id).copied().unwrap_or(true))
+        .filter(|question| question.is_read_only_in(&expression_ctx))
+        .filter(
+            |question| match (previous.get(&question.id), answers.get(&question.id)) {
+                (Some(before), Some(after)) => !canonical_eq(before, after),
+                (before, after) => before.is_some() != after.is_some(),
+            },
+        )
+        .map(|question| base_error(question, "qa_spec.read_only", "read_only"))
+        .collect()
+}
+
+/// Validates `answers` as an edit of `previous`: like [`validate`], also
+/// rejecting changes to read-only answers (see [`read_only_changes`]).
+pub fn validate_update(spec: &FormSpec, previous: &Value, answers: &Value) -> ValidationResult {
+    validate_update_with_ctx(spec, previous, answers, &Value::Null)
+}
+
+/// [`validate_update`] with the runtime `ctx`, as in [`validate_with_ctx`].
+pub fn validate_update_with_ctx(
+    spec: &FormSpec,
+    previous: &Value,
+    answers: &Value,
+    ctx: &Value,
+) -> ValidationResult {
+    if let Some(rejected) = reject_unbounded(spec, answers) {
+        return rejected;
+    }
+    let normalized = apply_normalization(spec, answers);
+    let computed_answers = materialize_computed(spec, &normalized, ctx);
+    let visibility =
+        resolve_visibility_with_ctx(spec, &computed_answers, ctx, VisibilityMode::Visible).map;
+    let changes = read_only_changes(
+        spec,
+        &apply_normalization(spec, previous),
+        &computed_answers,
+        ctx,
+        &visibility,
+    );
+    let mut result = validate_computed(spec, None, ctx, &normalized, computed_answers, &visibility);
+    if !changes.is_empty() {
+        result.valid = false;
+        result.errors.extend(changes);
+    }
+    result
+}
+
+/// The first failed check of `constraint.warnings` for an otherwise valid answer.
+fn constraint_warning(
+    question: &QuestionSpec,
+    value: &Value,
+    patterns: Option<&CompiledPatterns>,
+) -> Option<ValidationError> {
+    let warnings = question.constraint.as_ref()?.warnings.as_deref()?;
+    // Messages come from the warnings, not from the constraint around them.
+    let soft = QuestionSpec {
+        constraint: Some(warnings.clone()),
+        ..question.clone()
+    };
+    enforce_constraint(&soft, value, warnings, patterns)
+}
+
+fn validate_value(
+    question: &QuestionSpec,
+    value: &Value,
+    answers: &Value,
+    patterns: Option<&CompiledPatterns>,
+) -> Option<ValidationError> {
     if !matches_type(question, value) {
+        match question.kind {
+            QuestionType::Date => {
+                return Some(base_error(question, "qa_spec.date_invalid", "date_invalid"));
+            }
+            // Whole floats within 2^53 were normalized to integers already.
+            QuestionType::Integer | QuestionType::Rating
+                if value.as_f64().is_some_and(|number| number.fract() == 0.0) =>
+            {
+                return Some(base_error(
+                    question,
+                    "qa_spec.integer_out_of_range",
+                    "integer_out_of_range",
+                ));
+            }
+            QuestionType::Time if value.is_string() => {
+                return Some(base_error(question, "qa_spec.time_invalid", "time_invalid"));
+            }
+            QuestionType::Color if value.is_string() => {
+                return Some(base_error(
+                    question,
+                    "qa_spec.color_invalid",
+                    "color_invalid",
+                ));
+            }
+            QuestionType::Path if value.is_string() => {
+                return Some(base_error(question, "qa_spec.path_invalid", "path_invalid"));
+            }
+            QuestionType::Duration if value.is_string() => {
+                return Some(base_error(
+                    question,
+                    "qa_spec.duration_invalid",
+                    "duration_invalid",
+                ));
+            }
+            _ => {}
+        }
         return Some(ValidationError {
             question_id: Some(question.id.clone()),
-            path: Some(format!("/{}", question.id)),
+            path: Some(json_pointer([&question.id])),
+            paths: Vec::new(),
             message: "qa_spec.type_mismatch".into(),
             code: Some("type_mismatch".into()),
             params: BTreeMap::new(),
@@ -85,13 +651,55 @@ fn validate_value(question: &QuestionSpec, value: &Value) -> Option<ValidationEr
     }
 
     if matches!(question.kind, QuestionType::List)
-        && let Some(error) = validate_list(question, value)
+        && let Some(error) = validate_list(question, value, answers, patterns)
     {
         return Some(error);
     }
 
+    if matches!(question.kind, QuestionType::Object)
+        && let Some(error) = validate_object(question, value, answers, patterns)
+    {
+        return Some(error);
+    }
+
+    if matches!(question.kind, QuestionType::MultiEnum)
+        && let Some(selected) = value.as_array()
+    {
+        return validate_selection(question, selected).into_iter().next();
+    }
+
+    if matches!(question.kind, QuestionType::StringList)
+        && let Some(items) = value.as_array()
+    {
+        return validate_string_list(question, items, patterns)
+            .into_iter()
+            .next();
+    }
+
+    if matches!(question.kind, QuestionType::Rating)
+        && let Some(rating) = whole_number(value)
+    {
+        let range = rating_range(question.constraint.as_ref());
+        if rating < i128::from(*range.start()) {
+            return Some(bound_error(
+                question,
+                *range.start() as f64,
+                "qa_spec.min",
+                "min",
+            ));
+        }
+        if rating > i128::from(*range.end()) {
+            return Some(bound_error(
+                question,
+                *range.end() as f64,
+                "qa_spec.max",
+                "max",
+            ));
+        }
+    }
+
     if let Some(constraint) = &question.constraint
-        && let Some(error) = enforce_constraint(question, value, constraint)
+        && let Some(error) = enforce_constraint(question, value, constraint, patterns)
     {
         return Some(error);
     }
@@ -103,7 +711,8 @@ fn validate_value(question: &QuestionSpec, value: &Value) -> Option<ValidationEr
     {
         return Some(ValidationError {
             question_id: Some(question.id.clone()),
-            path: Some(format!("/{}", question.id)),
+            path: Some(json_pointer([&question.id])),
+            paths: Vec::new(),
             message: "qa_spec.enum_mismatch".into(),
             code: Some("enum_mismatch".into()),
             params: BTreeMap::new(),
@@ -115,15 +724,28 @@ fn validate_value(question: &QuestionSpec, value: &Value) -> Option<ValidationEr
 
 fn matches_type(question: &QuestionSpec, value: &Value) -> bool {
     match question.kind {
-        QuestionType::String | QuestionType::Enum => value.is_string(),
+        QuestionType::String | QuestionType::Enum | QuestionType::Markdown => value.is_string(),
         QuestionType::Boolean => value.is_boolean(),
-        QuestionType::Integer => value.is_i64(),
+        QuestionType::Integer | QuestionType::Rating => value.is_i64() || value.is_u64(),
         QuestionType::Number => value.is_number(),
-        QuestionType::List => value.is_array(),
+        QuestionType::List | QuestionType::MultiEnum | QuestionType::StringList => value.is_array(),
+        QuestionType::Date => value.as_str().is_some_and(is_iso_date),
+        QuestionType::Color => value.as_str().is_some_and(is_hex_color),
+        QuestionType::Time => value.as_str().and_then(seconds_of_day).is_some(),
+        QuestionType::Duration => value.is_u64(),
+        QuestionType::Object => value.is_object(),
+        QuestionType::Path => value
+            .as_str()
+            .is_some_and(|path| !path.is_empty() && !path.contains('\0')),
     }
 }
 
-fn validate_list(question: &QuestionSpec, value: &Value) -> Option<ValidationError> {
+fn validate_list(
+    question: &QuestionSpec,
+    value: &Value,
+    answers: &Value,
+    patterns: Option<&CompiledPatterns>,
+) -> Option<ValidationError> {
     let list = match &question.list {
         Some(value) => value,
         None => {
@@ -165,6 +787,7 @@ fn validate_list(question: &QuestionSpec, value: &Value) -> Option<ValidationErr
         ));
     }
 
+    let mut item_contexts = ListItemContexts::new(answers);
     for (idx, entry) in items.iter().enumerate() {
         let entry_map = match entry.as_object() {
             Some(map) => map,
@@ -173,33 +796,335 @@ fn validate_list(question: &QuestionSpec, value: &Value) -> Option<ValidationErr
             }
         };
 
+        let item_ctx = item_contexts.for_item(entry);
         for field in &list.fields {
+            if !list_field_visible(field, item_ctx) {
+                continue;
+            }
             match entry_map.get(&field.id) {
                 None => {
-                    if field.required {
+                    if field.is_required(item_ctx) {
                         return Some(list_field_missing_error(question, idx, &field.id));
                     }
                 }
                 Some(field_value) => {
-                    if let Some(error) = validate_value(field, field_value) {
+                    if let Some(error) = validate_value(field, field_value, answers, patterns) {
                         return Some(apply_list_context(question, idx, field, error));
                     }
                 }
             }
         }
+
+        if let Some(key) = entry_map
+            .keys()
+            .find(|key| !list.fields.iter().any(|field| &field.id == *key))
+        {
+            return Some(list_unknown_field_error(question, idx, key));
+        }
+    }
+
+    duplicate_entry(question, list, items)
+}
+
+/// First entry repeating an earlier one, as `unique` (whole entries) or
+/// `unique_by` (the listed fields) defines it. Values are compared in
+/// canonical form, so `1` and `1.0` repeat each other while a missing field
+/// does not repeat an empty one. The error carries the repeated values so
+/// users can tell which row to change.
+fn duplicate_entry(
+    question: &QuestionSpec,
+    list: &ListSpec,
+    items: &[Value],
+) -> Option<ValidationError> {
+    if !list.unique && list.unique_by.is_none() {
+        return None;
+    }
+    let mut seen = BTreeSet::new();
+    for (idx, entry) in items.iter().enumerate() {
+        let key = match &list.unique_by {
+            Some(fields) => fields
+                .iter()
+                .map(|field| entry.get(field).map(canonical_json))
+                .collect::<Vec<_>>(),
+            None => vec![Some(canonical_json(entry))],
+        };
+        if !seen.insert(key) {
+            return Some(selection_item_error(
+                question,
+                idx,
+                duplicate_text(list, entry).as_deref(),
+                "qa_spec.duplicate_item",
+                "duplicate_item",
+            ));
+        }
+    }
+    None
+}
+
+/// The repeated values of a duplicate entry for its error, leaving out
+/// secret fields.
+fn duplicate_text(list: &ListSpec, entry: &Value) -> Option<String> {
+    let secret = |id: &str| {
+        list.fields
+            .iter()
+            .any(|field| field.id == id && field.secret)
+    };
+    let text = match (&list.unique_by, entry) {
+        (Some(fields), _) => fields
+            .iter()
+            .filter(|field| !secret(field))
+            .map(|field| {
+                let value = entry.get(field).map_or_else(String::new, value_text);
+                format!("{}={}", field, value)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        (None, Value::Object(map)) => Value::Object(
+            map.iter()
+                .filter(|(id, _)| !secret(id))
+                .map(|(id, value)| (id.clone(), value.clone()))
+                .collect(),
+        )
+        .to_string(),
+        (None, other) => other.to_string(),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
     }
+}
+
+/// Checks the visible fields of an `object` answer. Field visibility sees
+/// the object as `item`, like a list entry, and nested errors are reported
+/// under the object's path (`/smtp/port`).
+fn validate_object(
+    question: &QuestionSpec,
+    value: &Value,
+    answers: &Value,
+    patterns: Option<&CompiledPatterns>,
+) -> Option<ValidationError> {
+    let Some(object) = &question.object else {
+        return Some(base_error(
+            question,
+            "qa_spec.missing_object_definition",
+            "missing_object_definition",
+        ));
+    };
+    let entry = value.as_object()?;
 
+    let item_ctx = list_item_context(answers, value);
+    for field in &object.fields {
+        if !list_field_visible(field, &item_ctx) {
+            continue;
+        }
+        match entry.get(&field.id) {
+            None if field.is_required(&item_ctx) => {
+                let mut params = BTreeMap::new();
+                params.insert("field".into(), field.id.clone());
+                return Some(ValidationError {
+                    question_id: Some(format!("{}.{}", question.id, field.id)),
+                    path: Some(json_pointer([&question.id, &field.id])),
+                    paths: Vec::new(),
+                    message: "qa_spec.missing_field".into(),
+                    code: Some("missing_field".into()),
+                    params,
+                });
+            }
+            None => {}
+            Some(field_value) => {
+                if let Some(mut error) = validate_value(field, field_value, answers, patterns) {
+                    error.question_id = Some(format!(
+                        "{}.{}",
+                        question.id,
+                        error.question_id.as_deref().unwrap_or(&field.id)
+                    ));
+                    error.path = Some(format!(
+                        "{}{}",
+                        json_pointer([&question.id]),
+                        error.path.as_deref().unwrap_or_default()
+                    ));
+                    return Some(error);
+                }
+            }
+        }
+    }
     None
 }
 
+/// Checks a `multi_enum` selection, reporting each offending item at its
+/// own path (`/regions/2`) after any count violation.
+fn validate_selection(question: &QuestionSpec, selected: &[Value]) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let constraint = question.constraint.as_ref();
+    if let Some(min_selected) = constraint.and_then(|constraint| constraint.min_selected)
+        && selected.len() < min_selected
+    {
+        errors.push(list_count_error(
+            question,
+            min_selected,
+            selected.len(),
+            "qa_spec.min_selected",
+            "min_selected",
+        ));
+    }
+    if let Some(max_selected) = constraint.and_then(|constraint| constraint.max_selected)
+        && selected.len() > max_selected
+    {
+        errors.push(list_count_error(
+            question,
+            max_selected,
+            selected.len(),
+            "qa_spec.max_selected",
+            "max_selected",
+        ));
+    }
+
+    let mut seen = BTreeSet::new();
+    for (idx, item) in selected.iter().enumerate() {
+        let Some(choice) = item.as_str() else {
+            errors.push(selection_item_error(
+                question,
+                idx,
+                None,
+                "qa_spec.type_mismatch",
+                "type_mismatch",
+            ));
+            continue;
+        };
+        if let Some(choices) = &question.choices
+            && !choices.iter().any(|allowed| allowed == choice)
+        {
+            let mut error = selection_item_error(
+                question,
+                idx,
+                Some(choice),
+                "qa_spec.enum_mismatch",
+                "enum_mismatch",
+            );
+            error.params.insert("choices".into(), choices.join(", "));
+            errors.push(error);
+        } else if !seen.insert(choice) {
+            errors.push(selection_item_error(
+                question,
+                idx,
+                Some(choice),
+                "qa_spec.duplicate_choice",
+                "duplicate_choice",
+            ));
+        }
+    }
+    errors
+}
+
+/// Checks a `string_list` answer: item count and uniqueness from `list`, and
+/// `constraint` applied to each item at its own path (`/hosts/1`).
+fn validate_string_list(
+    question: &QuestionSpec,
+    items: &[Value],
+    patterns: Option<&CompiledPatterns>,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let list = question.list.as_ref();
+    if let Some(min_items) = list.and_then(|list| list.min_items)
+        && items.len() < min_items
+    {
+        errors.push(list_count_error(
+            question,
+            min_items,
+            items.len(),
+            "qa_spec.min_items",
+            "min_items",
+        ));
+    }
+    if let Some(max_items) = list.and_then(|list| list.max_items)
+        && items.len() > max_items
+    {
+        errors.push(list_count_error(
+            question,
+            max_items,
+            items.len(),
+            "qa_spec.max_items",
+            "max_items",
+        ));
+    }
+
+    let unique = list.is_some_and(|list| list.unique);
+    let mut seen = BTreeSet::new();
+    for (idx, item) in items.iter().enumerate() {
+        let Some(text) = item.as_str() else {
+            errors.push(selection_item_error(
+                question,
+                idx,
+                None,
+                "qa_spec.type_mismatch",
+                "type_mismatch",
+            ));
+            continue;
+        };
+        if let Some(constraint) = &question.constraint
+            && let Some(mut error) = enforce_constraint(question, item, constraint, patterns)
+        {
+            error.path = Some(json_pointer([question.id.as_str(), &idx.to_string()]));
+            errors.push(with_value(error, question, text));
+        } else if unique && !seen.insert(text) {
+            errors.push(selection_item_error(
+                question,
+                idx,
+                Some(text),
+                "qa_spec.duplicate_item",
+                "duplicate_item",
+            ));
+        }
+    }
+    errors
+}
+
+fn selection_item_error(
+    question: &QuestionSpec,
+    idx: usize,
+    choice: Option<&str>,
+    message_key: &str,
+    code: &str,
+) -> ValidationError {
+    let mut params = BTreeMap::new();
+    if let Some(choice) = choice
+        && !question.secret
+    {
+        params.insert("value".into(), choice.to_string());
+    }
+    ValidationError {
+        question_id: Some(question.id.clone()),
+        path: Some(json_pointer([question.id.as_str(), &idx.to_string()])),
+        paths: Vec::new(),
+        message: custom_message(question, code).unwrap_or(message_key).into(),
+        code: Some(code.into()),
+        params,
+    }
+}
+
+/// Moves a field error under its list entry. The field's own path (which
+/// may reach further into an object or nested list) is kept as the suffix,
+/// so `/port` becomes `/servers/1/port`.
 fn apply_list_context(
     question: &QuestionSpec,
     idx: usize,
     field: &QuestionSpec,
     mut error: ValidationError,
 ) -> ValidationError {
+    let field_path = error
+        .path
+        .take()
+        .unwrap_or_else(|| json_pointer([&field.id]));
     error.question_id = Some(format!("{}[{}].{}", question.id, idx, field.id));
-    error.path = Some(format!("/{}/{}/{}", question.id, idx, field.id));
+    error.path = Some(format!(
+        "{}{}",
+        json_pointer([question.id.as_str(), &idx.to_string()]),
+        field_path
+    ));
     error
 }
 
@@ -215,8 +1140,9 @@ fn list_count_error(
     params.insert("actual".into(), actual.to_string());
     ValidationError {
         question_id: Some(question.id.clone()),
-        path: Some(format!("/{}", question.id)),
-        message: message_key.into(),
+        path: Some(json_pointer([&question.id])),
+        paths: Vec::new(),
+        message: custom_message(question, code).unwrap_or(message_key).into(),
         code: Some(code.into()),
         params,
     }
@@ -225,7 +1151,8 @@ fn list_count_error(
 fn list_entry_type_error(question: &QuestionSpec, idx: usize) -> ValidationError {
     ValidationError {
         question_id: Some(question.id.clone()),
-        path: Some(format!("/{}/{}", question.id, idx)),
+        path: Some(json_pointer([question.id.as_str(), &idx.to_string()])),
+        paths: Vec::new(),
         message: "qa_spec.entry_type".into(),
         code: Some("entry_type".into()),
         params: BTreeMap::new(),
@@ -235,13 +1162,27 @@ fn list_entry_type_error(question: &QuestionSpec, idx: usize) -> ValidationError
 fn list_not_array_error(question: &QuestionSpec) -> ValidationError {
     ValidationError {
         question_id: Some(question.id.clone()),
-        path: Some(format!("/{}", question.id)),
+        path: Some(json_pointer([&question.id])),
+        paths: Vec::new(),
         message: "qa_spec.list_type".into(),
         code: Some("list_type".into()),
         params: BTreeMap::new(),
     }
 }
 
+fn list_unknown_field_error(question: &QuestionSpec, idx: usize, key: &str) -> ValidationError {
+    let mut params = BTreeMap::new();
+    params.insert("field".into(), key.to_string());
+    ValidationError {
+        question_id: Some(format!("{}[{}].{}", question.id, idx, key)),
+        path: Some(json_pointer([question.id.as_str(), &idx.to_string(), key])),
+        paths: Vec::new(),
+        message: "qa_spec.unknown_item_field".into(),
+        code: Some("unknown_item_field".into()),
+        params,
+    }
+}
+
 fn list_field_missing_error(
     question: &QuestionSpec,
     idx: usize,
@@ -251,66 +1192,243 @@ fn list_field_missing_error(
     params.insert("field".into(), field_id.to_string());
     ValidationError {
         question_id: Some(format!("{}[{}].{}", question.id, idx, field_id)),
-        path: Some(format!("/{}/{}/{}", question.id, idx, field_id)),
+        path: Some(json_pointer([
+            question.id.as_str(),
+            &idx.to_string(),
+            field_id,
+        ])),
+        paths: Vec::new(),
         message: "qa_spec.missing_field".into(),
         code: Some("missing_field".into()),
         params,
     }
 }
 
+/// Whether `text` matches `pattern`; `None` when the pattern does not compile.
+fn pattern_matches(pattern: &str, text: &str, patterns: Option<&CompiledPatterns>) -> Option<bool> {
+    match patterns.and_then(|patterns| patterns.get(pattern)) {
+        Some(compiled) => compiled.as_ref().ok().map(|regex| regex.is_match(text)),
+        None => compile_pattern(pattern)
+            .ok()
+            .map(|regex| regex.is_match(text)),
+    }
+}
+
 fn enforce_constraint(
     question: &QuestionSpec,
     value: &Value,
     constraint: &crate::spec::question::Constraint,
+    patterns: Option<&CompiledPatterns>,
 ) -> Option<ValidationError> {
     if let Some(pattern) = &constraint.pattern
         && let Some(text) = value.as_str()
-        && let Ok(regex) = Regex::new(pattern)
-        && !regex.is_match(text)
+        && pattern_matches(pattern, text, patterns) == Some(false)
     {
-        return Some(base_error(
+        return Some(with_value(
+            base_error(question, "qa_spec.pattern_mismatch", "pattern_mismatch"),
             question,
-            "qa_spec.pattern_mismatch",
-            "pattern_mismatch",
+            text,
         ));
     }
 
+    if let Some(format) = constraint.enforced_format()
+        && let Some(text) = value.as_str()
+        && let Err(problem) = format.check(text)
+    {
+        let mut error = with_value(
+            base_error(
+                question,
+                &format!("qa_spec.format_{}", format),
+                &format!("format_{}", format),
+            ),
+            question,
+            text,
+        );
+        error
+            .params
+            .insert("format".into(), format.as_str().to_string());
+        error
+            .params
+            .insert("problem".into(), problem.as_str().to_string());
+        return Some(error);
+    }
+
     if let Some(min_len) = constraint.min_len
         && let Some(text) = value.as_str()
         && text.len() < min_len
     {
-        return Some(base_error(question, "qa_spec.min_length", "min_length"));
+        return Some(with_value(
+            base_error(question, "qa_spec.min_length", "min_length"),
+            question,
+            text,
+        ));
     }
 
     if let Some(max_len) = constraint.max_len
         && let Some(text) = value.as_str()
         && text.len() > max_len
     {
-        return Some(base_error(question, "qa_spec.max_length", "max_length"));
+        return Some(with_value(
+            base_error(question, "qa_spec.max_length", "max_length"),
+            question,
+            text,
+        ));
     }
 
     if let Some(min) = constraint.min
-        && let Some(value) = value.as_f64()
-        && value < min
+        && let Some(order) = bounded_value(question, value).and_then(|value| value.cmp_bound(min))
     {
-        return Some(base_error(question, "qa_spec.min", "min"));
+        if constraint.exclusive_min && order != Ordering::Greater {
+            return Some(bound_error(
+                question,
+                min,
+                "qa_spec.exclusive_min",
+                "exclusive_min",
+            ));
+        }
+        if order == Ordering::Less {
+            return Some(bound_error(question, min, "qa_spec.min", "min"));
+        }
     }
 
     if let Some(max) = constraint.max
-        && let Some(value) = value.as_f64()
-        && value > max
+        && let Some(order) = bounded_value(question, value).and_then(|value| value.cmp_bound(max))
+    {
+        if constraint.exclusive_max && order != Ordering::Less {
+            return Some(bound_error(
+                question,
+                max,
+                "qa_spec.exclusive_max",
+                "exclusive_max",
+            ));
+        }
+        if order == Ordering::Greater {
+            return Some(bound_error(question, max, "qa_spec.max", "max"));
+        }
+    }
+
+    if let Some(places) = constraint.decimal_places
+        && let Some(number) = value.as_f64()
+        && decimal_places_of(number) > usize::from(places)
+    {
+        let mut error = base_error(question, "qa_spec.decimal_precision", "decimal_precision");
+        error.params.insert("places".into(), places.to_string());
+        error.params.insert("value".into(), number.to_string());
+        return Some(error);
+    }
+
+    if let Some(step) = constraint.multiple_of
+        && step > 0.0
+        && !is_multiple_of(value, step)
     {
-        return Some(base_error(question, "qa_spec.max", "max"));
+        let mut error = base_error(question, "qa_spec.multiple_of", "multiple_of");
+        error.params.insert("multiple_of".into(), step.to_string());
+        return Some(error);
     }
 
     None
 }
 
+/// Whether a numeric `value` is a whole multiple of `step`. Whole numbers
+/// and steps are checked exactly; otherwise the rounding error of binary
+/// floats is tolerated, so `0.3` is a multiple of `0.1`.
+fn is_multiple_of(value: &Value, step: f64) -> bool {
+    if step.fract() == 0.0
+        && let Some(whole) = whole_number(value)
+    {
+        return whole % (step as i128) == 0;
+    }
+    let Some(number) = value.as_f64() else {
+        return true;
+    };
+    let steps = number / step;
+    (steps - steps.round()).abs() <= 1e-9 * steps.abs().max(1.0)
+}
+
+/// A number compared against `min`/`max`. Whole numbers are kept exact, so
+/// `2^53 + 1` is above a bound of `2^53` even though both are the same `f64`.
+#[derive(Debug, Clone, Copy)]
+enum Bounded {
+    Whole(i128),
+    Fraction(f64),
+}
+
+impl Bounded {
+    fn cmp_bound(self, bound: f64) -> Option<Ordering> {
+        match self {
+            // `as` is exact for whole floats in range and saturates beyond it.
+            Bounded::Whole(value) if value < bound.ceil() as i128 => Some(Ordering::Less),
+            Bounded::Whole(value) if value > bound.floor() as i128 => Some(Ordering::Greater),
+            Bounded::Whole(_) => Some(Ordering::Equal),
+            Bounded::Fraction(value) => value.partial_cmp(&bound),
+        }
+    }
+}
+
+/// The number `min`/`max` are compared against: seconds after midnight for
+/// `time` answers, the answer itself otherwise.
+fn bounded_value(question: &QuestionSpec, value: &Value) -> Option<Bounded> {
+    match question.kind {
+        QuestionType::Time => value
+            .as_str()
+            .and_then(seconds_of_day)
+            .map(|seconds| Bounded::Whole(seconds.into())),
+        _ => whole_number(value)
+            .map(Bounded::Whole)
+            .or_else(|| value.as_f64().map(Bounded::Fraction)),
+    }
+}
+
+/// A JSON integer of either sign, including `u64` values above `i64::MAX`.
+fn whole_number(value: &Value) -> Option<i128> {
+    value
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| value.as_u64().map(i128::from))
+}
+
+/// The spec author's message for `code` from `constraint.messages`, used in
+/// place of the built-in message key. The error keeps its machine code.
+fn custom_message<'a>(question: &'a QuestionSpec, code: &str) -> Option<&'a str> {
+    question
+        .constraint
+        .as_ref()
+        .and_then(|constraint| constraint.message_for(code))
+}
+
+fn bound_error(question: &QuestionSpec, bound: f64, message: &str, code: &str) -> ValidationError {
+    let mut error = base_error(question, message, code);
+    error
+        .params
+        .insert("expected".into(), bound_text(question, bound));
+    error
+}
+
+/// A `min` or `max` bound as people read it: a time of day for `time`
+/// questions, the number otherwise.
+pub(crate) fn bound_text(question: &QuestionSpec, bound: f64) -> String {
+    match question.kind {
+        QuestionType::Time => format_time_of_day(bound as u32),
+        _ => bound.to_string(),
+    }
+}
+
+/// Adds the checked text, after normalization, as the `value` param so
+/// messages can show what was validated. Secret answers are never copied
+/// into errors.
+fn with_value(mut error: ValidationError, question: &QuestionSpec, text: &str) -> ValidationError {
+    if !question.secret {
+        error.params.insert("value".into(), text.to_string());
+    }
+    error
+}
+
 fn base_error(question: &QuestionSpec, message: &str, code: &str) -> ValidationError {
     ValidationError {
         question_id: Some(question.id.clone()),
-        path: Some(format!("/{}", question.id)),
-        message: message.into(),
+        path: Some(json_pointer([&question.id])),
+        paths: Vec::new(),
+        message: custom_message(question, code).unwrap_or(message).into(),
         code: Some(code.into()),
         params: BTreeMap::new(),
     }
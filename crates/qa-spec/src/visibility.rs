@@ -1,36 +1,392 @@
-use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 
-use crate::computed::build_expression_context;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::answers::canonical_hash;
+use crate::computed::{CONTEXT_ROOTS, build_expression_context, build_expression_context_with_ctx};
+use crate::expr::{EvaluationFailure, Expr};
 use crate::spec::form::FormSpec;
+use crate::spec::question::QuestionSpec;
 
 pub type VisibilityMap = std::collections::BTreeMap<String, bool>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What happens to a question whose `visible_if` cannot be evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum VisibilityMode {
+    /// The question is shown.
     Visible,
+    /// The question is hidden.
     Hidden,
+    /// The question is shown by the lenient resolvers, which only list the
+    /// condition in their diagnostics; [`try_resolve_visibility`] and
+    /// [`crate::validate_strict`] report it as an error.
     Error,
 }
 
+/// Visibility of every question together with the evaluation diagnostics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedVisibility {
+    pub map: VisibilityMap,
+    /// Questions whose `visible_if` did not evaluate to a boolean (e.g. it
+    /// reads an unanswered question); they fell back to the mode's default.
+    pub unresolved: Vec<String>,
+    /// Why each `unresolved` condition failed, in the same order.
+    pub diagnostics: Vec<VisibilityDiagnostic>,
+}
+
+/// How [`resolve_visibility_with_options`] treats the answers it evaluates
+/// conditions against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VisibilityOptions {
+    /// Treat the answer of a hidden question as absent while evaluating the
+    /// conditions that read it, so a stale answer left behind in a collapsed
+    /// branch cannot keep its dependents visible. A condition that cannot be
+    /// evaluated because of such a question hides its question too, whatever
+    /// the [`VisibilityMode`], so hiding cascades down every chain of
+    /// `visible_if` dependencies.
+    pub ignore_hidden_answers: bool,
+}
+
+impl Default for VisibilityOptions {
+    fn default() -> Self {
+        Self {
+            ignore_hidden_answers: true,
+        }
+    }
+}
+
+/// Explains a `visible_if` that fell back to the [`VisibilityMode`] default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VisibilityDiagnostic {
+    pub question_id: String,
+    pub reason: EvaluationFailure,
+    /// The innermost part of the condition that failed, e.g. the `answer`
+    /// reading an unanswered question.
+    pub expr_fragment: Expr,
+}
+
 pub fn resolve_visibility(spec: &FormSpec, answers: &Value, mode: VisibilityMode) -> VisibilityMap {
-    let mut map = VisibilityMap::new();
-    let ctx = build_expression_context(answers);
-
-    for question in &spec.questions {
-        let visible = if let Some(expr) = &question.visible_if {
-            match expr.evaluate_bool(&ctx) {
-                Some(val) => val,
-                None => match mode {
+    resolve_visibility_with_diagnostics(spec, answers, mode).map
+}
+
+/// Like [`resolve_visibility`], also reporting which conditions could not be evaluated.
+pub fn resolve_visibility_with_diagnostics(
+    spec: &FormSpec,
+    answers: &Value,
+    mode: VisibilityMode,
+) -> ResolvedVisibility {
+    resolve_visibility_with_ctx(spec, answers, &Value::Null, mode)
+}
+
+/// Like [`resolve_visibility_with_diagnostics`], with the `state`, `config`
+/// and `env` members of the runtime `ctx` readable by conditions (see
+/// [`build_expression_context_with_ctx`]).
+pub fn resolve_visibility_with_ctx(
+    spec: &FormSpec,
+    answers: &Value,
+    ctx: &Value,
+    mode: VisibilityMode,
+) -> ResolvedVisibility {
+    resolve_visibility_with_options(spec, answers, ctx, mode, VisibilityOptions::default())
+}
+
+/// Strict [`resolve_visibility`]: every `visible_if` has to evaluate to a
+/// boolean. Otherwise the conditions that failed are returned, each naming
+/// the question, why it failed and the part of the expression that did.
+pub fn try_resolve_visibility(
+    spec: &FormSpec,
+    answers: &Value,
+) -> Result<VisibilityMap, Vec<VisibilityDiagnostic>> {
+    try_resolve_visibility_with_ctx(spec, answers, &Value::Null)
+}
+
+/// [`try_resolve_visibility`] with the runtime `ctx`, as in
+/// [`resolve_visibility_with_ctx`].
+pub fn try_resolve_visibility_with_ctx(
+    spec: &FormSpec,
+    answers: &Value,
+    ctx: &Value,
+) -> Result<VisibilityMap, Vec<VisibilityDiagnostic>> {
+    let resolved = resolve_visibility_with_ctx(spec, answers, ctx, VisibilityMode::Error);
+    if resolved.diagnostics.is_empty() {
+        Ok(resolved.map)
+    } else {
+        Err(resolved.diagnostics)
+    }
+}
+
+/// Like [`resolve_visibility_with_ctx`], with explicit [`VisibilityOptions`].
+///
+/// Questions are resolved in `visible_if` dependency order (spec order
+/// otherwise, and for questions on a dependency cycle). With
+/// `ignore_hidden_answers` off, every condition sees the answers as given,
+/// including those of hidden questions.
+pub fn resolve_visibility_with_options(
+    spec: &FormSpec,
+    answers: &Value,
+    runtime_ctx: &Value,
+    mode: VisibilityMode,
+    options: VisibilityOptions,
+) -> ResolvedVisibility {
+    let mut ctx = build_expression_context_with_ctx(answers, runtime_ctx);
+    let mut outcomes = vec![(true, None); spec.questions.len()];
+    let mut hidden = BTreeSet::new();
+
+    for index in visibility_order(spec) {
+        let question = &spec.questions[index];
+        let mut failure = None;
+        let visible = match &question.visible_if {
+            Some(expr) => expr.evaluate_bool(&ctx).unwrap_or_else(|| {
+                if options.ignore_hidden_answers
+                    && expr
+                        .referenced_answers()
+                        .iter()
+                        .any(|id| hidden.contains(id.as_str()))
+                {
+                    return false;
+                }
+                failure = Some(expr.explain_bool_failure(&ctx).map(|(reason, fragment)| {
+                    VisibilityDiagnostic {
+                        question_id: question.id.clone(),
+                        reason,
+                        expr_fragment: fragment.clone(),
+                    }
+                }));
+                match mode {
                     VisibilityMode::Visible => true,
                     VisibilityMode::Hidden => false,
                     VisibilityMode::Error => true,
-                },
+                }
+            }),
+            None => true,
+        };
+        if !visible && options.ignore_hidden_answers {
+            hide_answer(&mut ctx, &question.id, runtime_ctx);
+            hidden.insert(question.id.as_str());
+        }
+        outcomes[index] = (visible, failure);
+    }
+
+    let mut resolved = ResolvedVisibility::default();
+    for (question, (visible, failure)) in spec.questions.iter().zip(outcomes) {
+        if let Some(diagnostic) = failure {
+            resolved.unresolved.push(question.id.clone());
+            resolved.diagnostics.extend(diagnostic);
+        }
+        resolved.map.insert(question.id.clone(), visible);
+    }
+    resolved
+}
+
+/// Question indexes ordered so that every question comes after the
+/// questions its `visible_if` reads. Dependency cycles keep spec order.
+fn visibility_order(spec: &FormSpec) -> Vec<usize> {
+    fn visit(
+        index: usize,
+        spec: &FormSpec,
+        ids: &BTreeMap<&str, usize>,
+        state: &mut [u8],
+        order: &mut Vec<usize>,
+    ) {
+        // 0 = unvisited, 1 = on the current path, 2 = ordered.
+        if state[index] != 0 {
+            return;
+        }
+        state[index] = 1;
+        if let Some(expr) = &spec.questions[index].visible_if {
+            for dependency in expr.referenced_answers() {
+                if let Some(&dependency) = ids.get(dependency.as_str()) {
+                    visit(dependency, spec, ids, state, order);
+                }
             }
+        }
+        state[index] = 2;
+        order.push(index);
+    }
+
+    let ids = spec
+        .questions
+        .iter()
+        .enumerate()
+        .map(|(index, question)| (question.id.as_str(), index))
+        .collect::<BTreeMap<_, _>>();
+    let mut state = vec![0; spec.questions.len()];
+    let mut order = Vec::with_capacity(spec.questions.len());
+    for index in 0..spec.questions.len() {
+        visit(index, spec, &ids, &mut state, &mut order);
+    }
+    order
+}
+
+/// Removes the answer to `id` from an expression context built by
+/// [`build_expression_context_with_ctx`], restoring the runtime root it may
+/// have shadowed.
+fn hide_answer(ctx: &mut Value, id: &str, runtime_ctx: &Value) {
+    let Some(ctx) = ctx.as_object_mut() else {
+        return;
+    };
+    let Some(Value::Object(answers)) = ctx.get_mut("answers") else {
+        return;
+    };
+    if answers.remove(id).is_none() || id == "answers" {
+        return;
+    }
+    ctx.remove(id);
+    if CONTEXT_ROOTS.contains(&id)
+        && let Some(root) = runtime_ctx.get(id)
+    {
+        ctx.insert(id.to_string(), root.clone());
+    }
+}
+
+type CacheKey = (String, String, VisibilityMode, [u8; 32]);
+
+/// Memoizes [`resolve_visibility_with_diagnostics`] by form and canonical
+/// answers hash, so rendering the same state again skips every `visible_if`.
+///
+/// Answers that differ only in spelling (`3` vs `3.0`, member order) share an
+/// entry. Once `capacity` entries are stored the oldest one is evicted.
+#[derive(Debug, Clone)]
+pub struct VisibilityCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, ResolvedVisibility>,
+    order: VecDeque<CacheKey>,
+    hits: u64,
+    misses: u64,
+}
+
+impl Default for VisibilityCache {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl VisibilityCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Visibility of `spec` for `answers`, resolved on the first request only.
+    pub fn resolve(
+        &mut self,
+        spec: &FormSpec,
+        answers: &Value,
+        mode: VisibilityMode,
+    ) -> &ResolvedVisibility {
+        self.resolve_with_ctx(spec, answers, &Value::Null, mode)
+    }
+
+    /// Like [`VisibilityCache::resolve`], using [`resolve_visibility_with_ctx`].
+    /// The parts of `ctx` conditions can read are part of the cache key.
+    pub fn resolve_with_ctx(
+        &mut self,
+        spec: &FormSpec,
+        answers: &Value,
+        ctx: &Value,
+        mode: VisibilityMode,
+    ) -> &ResolvedVisibility {
+        let roots = CONTEXT_ROOTS
+            .iter()
+            .filter_map(|root| Some((root.to_string(), ctx.get(root)?.clone())))
+            .collect::<Map<_, _>>();
+        let hash = if roots.is_empty() {
+            canonical_hash(answers)
         } else {
-            true
+            canonical_hash(&Value::Array(vec![answers.clone(), Value::Object(roots)]))
         };
-        map.insert(question.id.clone(), visible);
+        let key = (spec.id.clone(), spec.version.clone(), mode, hash);
+        if self.entries.contains_key(&key) {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            if self.entries.len() >= self.capacity
+                && let Some(oldest) = self.order.pop_front()
+            {
+                self.entries.remove(&oldest);
+            }
+            self.order.push_back(key.clone());
+            self.entries.insert(
+                key.clone(),
+                resolve_visibility_with_ctx(spec, answers, ctx, mode),
+            );
+        }
+        &self.entries[&key]
+    }
+
+    /// Number of requests answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of requests that had to resolve visibility.
+    pub fn misses(&self) -> u64 {
+        self.misses
     }
 
-    map
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+/// Expression context for a single list entry: the outer answers with the
+/// entry exposed under `item`, so `item.tls` and top-level ids both resolve.
+pub fn list_item_context(answers: &Value, item: &Value) -> Value {
+    let mut scoped = answers.as_object().cloned().unwrap_or_default();
+    scoped.insert("item".into(), item.clone());
+    build_expression_context(&Value::Object(scoped))
+}
+
+/// [`list_item_context`] for every entry of a list, sharing one copy of the
+/// answers instead of cloning them per entry.
+pub(crate) struct ListItemContexts {
+    ctx: Value,
+}
+
+impl ListItemContexts {
+    pub(crate) fn new(answers: &Value) -> Self {
+        let answers = answers.as_object().cloned().unwrap_or_default();
+        let mut ctx = answers.clone();
+        ctx.insert("answers".into(), Value::Object(answers));
+        Self {
+            ctx: Value::Object(ctx),
+        }
+    }
+
+    /// Context for `item`, equal to `list_item_context(answers, item)`.
+    pub(crate) fn for_item(&mut self, item: &Value) -> &Value {
+        if let Some(ctx) = self.ctx.as_object_mut() {
+            if let Some(Value::Object(answers)) = ctx.get_mut("answers") {
+                answers.insert("item".into(), item.clone());
+            }
+            ctx.insert("item".into(), item.clone());
+        }
+        &self.ctx
+    }
+}
+
+/// Whether a list sub-field is shown for the entry described by `item_ctx`.
+/// Entries are submitted whole, so a condition that cannot be resolved (e.g.
+/// `item.tls` left out) hides the field.
+pub fn list_field_visible(field: &QuestionSpec, item_ctx: &Value) -> bool {
+    field
+        .visible_if
+        .as_ref()
+        .map(|expr| expr.evaluate_bool(item_ctx).unwrap_or(false))
+        .unwrap_or(true)
 }
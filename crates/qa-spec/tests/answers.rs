@@ -1,7 +1,10 @@
 use serde_cbor::from_slice;
 use serde_json::json;
 
-use qa_spec::{AnswerSet, answers::Meta};
+use qa_spec::{
+    AnswerSet, ChangeKind, FixedClock, FormSpec, MergeError, MergeOptions, MergeStrategy,
+    answers::Meta, is_redacted,
+};
 
 #[test]
 fn answer_set_serializes_to_cbor_and_json() {
@@ -43,6 +46,7 @@ fn answer_set_cbor_roundtrip_preserves_fields() {
         meta: Some(Meta {
             created_at: Some("2026-01-01T00:00:00Z".into()),
             updated_at: None,
+            ..Meta::default()
         }),
     };
 
@@ -50,3 +54,222 @@ fn answer_set_cbor_roundtrip_preserves_fields() {
     let decoded: AnswerSet = from_slice(&cbor).expect("cbor roundtrip succeeds");
     assert_eq!(decoded, answer_set);
 }
+
+fn answer_set(answers: serde_json::Value) -> AnswerSet {
+    let mut set = AnswerSet::new("servers", "1.0.0");
+    set.answers = answers;
+    set
+}
+
+#[test]
+fn diff_reports_single_changed_list_field() {
+    let old = answer_set(json!({
+        "name": "edge",
+        "token": "old-secret",
+        "servers": [
+            { "host": "a", "port": 80 },
+            { "host": "b", "port": 80 }
+        ]
+    }));
+    let new = answer_set(json!({
+        "token": "new-secret",
+        "region": "eu",
+        "servers": [
+            { "host": "a", "port": 80 },
+            { "host": "b", "port": 443 }
+        ]
+    }));
+
+    let diff = old.diff(&new);
+    assert_eq!(
+        (
+            diff.summary.added,
+            diff.summary.removed,
+            diff.summary.changed
+        ),
+        (1, 1, 2)
+    );
+    let port = diff
+        .changes
+        .iter()
+        .find(|change| change.path == "/servers/1/port")
+        .expect("nested change");
+    assert_eq!(port.kind, ChangeKind::Changed);
+    assert_eq!(port.old, Some(json!(80)));
+    assert_eq!(port.new, Some(json!(443)));
+    assert!(
+        !diff
+            .changes
+            .iter()
+            .any(|change| change.path.starts_with("/servers/0"))
+    );
+    assert!(old.diff(&old).is_empty());
+
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "servers",
+        "title": "Servers",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "token", "type": "string", "title": "Token", "secret": true }
+        ]
+    }))
+    .expect("spec");
+    let masked = old.diff_with_spec(&new, &spec);
+    let token = masked
+        .changes
+        .iter()
+        .find(|change| change.path == "/token")
+        .expect("token change");
+    assert!(token.masked);
+    assert_eq!((token.old.as_ref(), token.new.as_ref()), (None, None));
+    assert!(!serde_json::to_string(&masked).unwrap().contains("secret"));
+}
+
+#[test]
+fn merge_resolves_conflicts_per_strategy() {
+    let prefill = answer_set(json!({ "region": "us", "size": "small" }));
+    let interactive = answer_set(json!({ "region": "eu", "name": "edge" }));
+
+    let merged = AnswerSet::merge(
+        &prefill,
+        &interactive,
+        &MergeOptions::new(MergeStrategy::OverlayWins),
+    )
+    .expect("merge");
+    assert_eq!(
+        merged.answers,
+        json!({ "region": "eu", "size": "small", "name": "edge" })
+    );
+    let provenance = merged.meta.and_then(|meta| meta.merge).expect("provenance");
+    assert_eq!(provenance.conflicts, vec!["/region"]);
+    assert_eq!(provenance.from_overlay, vec!["/name", "/region"]);
+
+    let kept = AnswerSet::merge(
+        &prefill,
+        &interactive,
+        &MergeOptions::new(MergeStrategy::BaseWins),
+    )
+    .expect("merge");
+    assert_eq!(kept.answers["region"], "us");
+
+    let err = AnswerSet::merge(
+        &prefill,
+        &interactive,
+        &MergeOptions::new(MergeStrategy::ErrorOnConflict),
+    )
+    .expect_err("conflict");
+    assert_eq!(
+        err,
+        MergeError::Conflict {
+            keys: vec!["/region".into()]
+        }
+    );
+}
+
+#[test]
+fn merge_lists_by_key_or_wholesale() {
+    let base = answer_set(json!({
+        "servers": [{ "host": "a", "port": 80 }, { "host": "b", "port": 80 }]
+    }));
+    let overlay = answer_set(json!({
+        "servers": [{ "host": "b", "port": 443 }, { "host": "c", "port": 22 }]
+    }));
+
+    let keyed = AnswerSet::merge(
+        &base,
+        &overlay,
+        &MergeOptions::new(MergeStrategy::OverlayWins).with_list_key("servers", "host"),
+    )
+    .expect("merge");
+    assert_eq!(
+        keyed.answers["servers"],
+        json!([
+            { "host": "a", "port": 80 },
+            { "host": "b", "port": 443 },
+            { "host": "c", "port": 22 }
+        ])
+    );
+
+    let replaced = AnswerSet::merge(&base, &overlay, &MergeOptions::default()).expect("merge");
+    assert_eq!(replaced.answers["servers"], overlay.answers["servers"]);
+}
+
+#[test]
+fn meta_helpers_use_injected_clock() {
+    let mut meta = Meta::now_with(&FixedClock("2026-01-01T00:00:00Z".into()), "alice", "cli");
+    assert_eq!(meta.created_at.as_deref(), Some("2026-01-01T00:00:00Z"));
+    assert_eq!(meta.actor.as_deref(), Some("alice"));
+    assert_eq!(meta.attempts, None);
+
+    meta.touch(&FixedClock("2026-01-02T08:30:00Z".into()));
+    meta.touch(&FixedClock("2026-01-02T09:00:00Z".into()));
+    assert_eq!(meta.created_at.as_deref(), Some("2026-01-01T00:00:00Z"));
+    assert_eq!(meta.updated_at.as_deref(), Some("2026-01-02T09:00:00Z"));
+    assert_eq!(meta.attempts, Some(2));
+}
+
+#[test]
+fn legacy_answer_set_json_still_parses() {
+    let raw = json!({
+        "form_id": "f",
+        "spec_version": "1",
+        "answers": {},
+        "meta": { "created_at": "2026-01-01T00:00:00Z" }
+    });
+    let set: AnswerSet = serde_json::from_value(raw.clone()).expect("parse");
+    assert_eq!(serde_json::to_value(&set).expect("serialize"), raw);
+}
+
+#[test]
+fn rfc3339_formatting_matches_known_dates() {
+    assert_eq!(qa_spec::clock::format_rfc3339(0), "1970-01-01T00:00:00Z");
+    assert_eq!(
+        qa_spec::clock::format_rfc3339(1_709_210_096),
+        "2024-02-29T12:34:56Z"
+    );
+}
+
+#[test]
+fn redacted_serialization_hides_secret_values() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "region", "type": "string", "title": "Region" },
+            { "id": "token", "type": "string", "title": "Token", "secret": true },
+            {
+                "id": "servers",
+                "type": "list",
+                "title": "Servers",
+                "list": {
+                    "fields": [
+                        { "id": "host", "type": "string", "title": "Host" },
+                        { "id": "password", "type": "string", "title": "Password", "secret": true }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+    let set = answer_set(json!({
+        "region": "eu",
+        "token": "tok-hunter2",
+        "servers": [{ "host": "a", "password": "pw-hunter2" }, { "host": "b" }]
+    }));
+
+    let redacted = set.redacted(&spec);
+    assert!(is_redacted(&redacted.answers["token"]));
+    assert!(is_redacted(&redacted.answers["servers"][0]["password"]));
+    assert!(redacted.answers["servers"][1].get("password").is_none());
+    assert_eq!(redacted.answers["region"], "eu");
+
+    let json = set.to_json_redacted(&spec).expect("json");
+    assert!(!json.contains("hunter2"), "{json}");
+    assert!(json.contains("$redacted"));
+
+    let cbor = set.to_cbor_redacted(&spec).expect("cbor");
+    assert!(!cbor.windows(7).any(|window| window == b"hunter2"));
+    let decoded: AnswerSet = from_slice(&cbor).expect("decode");
+    assert_eq!(decoded, redacted);
+}
@@ -0,0 +1,167 @@
+use serde_json::{Value, json};
+
+use qa_spec::expr::Expr;
+use qa_spec::{ComputedStatus, FormSpec, build_render_payload, compute_answers, validate};
+
+fn order_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "order",
+        "title": "Order",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "price", "type": "number", "title": "Price", "required": true },
+            { "id": "quantity", "type": "integer", "title": "Quantity", "required": true },
+            { "id": "first_name", "type": "string", "title": "First name" },
+            { "id": "last_name", "type": "string", "title": "Last name" },
+            {
+                "id": "total",
+                "type": "number",
+                "title": "Total",
+                "computed": {
+                    "op": "mul",
+                    "left": { "op": "answer", "path": "price" },
+                    "right": { "op": "answer", "path": "quantity" }
+                }
+            },
+            {
+                "id": "unit_share",
+                "type": "number",
+                "title": "Unit share",
+                "computed": {
+                    "op": "div",
+                    "left": { "op": "literal", "value": 100 },
+                    "right": { "op": "answer", "path": "quantity" }
+                }
+            },
+            {
+                "id": "display_name",
+                "type": "string",
+                "title": "Display name",
+                "computed": {
+                    "op": "concat",
+                    "expressions": [
+                        { "op": "answer", "path": "first_name" },
+                        { "op": "literal", "value": " " },
+                        { "op": "answer", "path": "last_name" },
+                        { "op": "literal", "value": " x" },
+                        { "op": "answer", "path": "quantity" }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn eval(expr: Value) -> Option<Value> {
+    serde_json::from_value::<Expr>(expr)
+        .expect("expr")
+        .evaluate_value(&json!({}))
+}
+
+fn op(op: &str, left: Value, right: Value) -> Value {
+    json!({
+        "op": op,
+        "left": { "op": "literal", "value": left },
+        "right": { "op": "literal", "value": right }
+    })
+}
+
+#[test]
+fn arithmetic_keeps_integers_when_it_can() {
+    assert_eq!(eval(op("add", json!(2), json!(3))), Some(json!(5)));
+    assert_eq!(eval(op("sub", json!(2), json!(3))), Some(json!(-1)));
+    assert_eq!(eval(op("mul", json!(2.5), json!(4))), Some(json!(10.0)));
+    assert_eq!(eval(op("div", json!(9), json!(3))), Some(json!(3)));
+    assert_eq!(eval(op("div", json!(1), json!(4))), Some(json!(0.25)));
+    assert_eq!(
+        eval(op("add", json!(i64::MAX), json!(1))),
+        Some(json!(i64::MAX as f64 + 1.0))
+    );
+
+    assert_eq!(eval(op("div", json!(1), json!(0))), None);
+    assert_eq!(eval(op("div", json!(1.5), json!(0.0))), None);
+    assert_eq!(eval(op("add", json!("2"), json!(3))), None);
+    assert_eq!(eval(op("mul", json!(null), json!(3))), None);
+}
+
+#[test]
+fn computed_totals_and_names_fill_in_answers() {
+    let spec = order_form();
+    let answers =
+        json!({ "price": 2.5, "quantity": 4, "first_name": "Ada", "last_name": "Lovelace" });
+
+    let (computed, reports) = compute_answers(&spec, &answers, &json!({}));
+    assert_eq!(computed["total"], json!(10.0));
+    assert_eq!(computed["unit_share"], json!(25));
+    assert_eq!(computed["display_name"], "Ada Lovelace x4");
+    assert!(
+        reports
+            .iter()
+            .all(|report| report.status == ComputedStatus::Computed)
+    );
+    assert!(validate(&spec, &answers).valid);
+
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let current = |id: &str| {
+        payload
+            .questions
+            .iter()
+            .find(|question| question.id == id)
+            .and_then(|question| question.current_value.clone())
+    };
+    assert_eq!(current("total"), Some(json!(10.0)));
+    assert_eq!(current("display_name"), Some(json!("Ada Lovelace x4")));
+}
+
+#[test]
+fn failed_arithmetic_is_reported_as_unresolved() {
+    let spec = order_form();
+    let answers = json!({ "price": "free", "quantity": 0, "first_name": "Ada" });
+
+    let (computed, reports) = compute_answers(&spec, &answers, &json!({}));
+    for id in ["total", "unit_share", "display_name"] {
+        assert!(computed.get(id).is_none(), "{id}: {computed}");
+        let report = reports
+            .iter()
+            .find(|report| report.question_id == id)
+            .expect("report");
+        assert_eq!(report.status, ComputedStatus::Unresolved, "{id}");
+    }
+}
+
+#[test]
+fn coalesce_supplies_computed_defaults() {
+    let spec = FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "replicas", "type": "integer", "title": "Replicas" },
+            {
+                "id": "capacity",
+                "type": "integer",
+                "title": "Capacity",
+                "computed": {
+                    "op": "mul",
+                    "left": {
+                        "op": "coalesce",
+                        "expressions": [
+                            { "op": "answer", "path": "replicas" },
+                            { "op": "literal", "value": 1 }
+                        ]
+                    },
+                    "right": { "op": "literal", "value": 100 }
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+
+    let (computed, _) = compute_answers(&spec, &json!({}), &json!({}));
+    assert_eq!(computed["capacity"], json!(100));
+    let (computed, _) = compute_answers(&spec, &json!({ "replicas": null }), &json!({}));
+    assert_eq!(computed["capacity"], json!(100));
+    let (computed, _) = compute_answers(&spec, &json!({ "replicas": 3 }), &json!({}));
+    assert_eq!(computed["capacity"], json!(300));
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeSet;
+
+use serde_json::{Map, Number, Value, json};
+
+use qa_spec::{AnswerSet, CanonicalOptions, canonical_hash, canonical_json, canonicalize};
+
+/// SplitMix64, so generated cases are reproducible from the seed alone.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn string(&mut self) -> String {
+        const ALPHABET: &[&str] = &["a", "b", "_", "1", " ", "é", "e\u{301}", "日", "\""];
+        (0..self.below(6))
+            .map(|_| ALPHABET[self.below(ALPHABET.len() as u64) as usize])
+            .collect()
+    }
+
+    fn value(&mut self, depth: u32) -> Value {
+        match self.below(if depth == 0 { 4 } else { 6 }) {
+            0 => Value::Null,
+            1 => Value::Bool(self.below(2) == 1),
+            2 => match self.below(3) {
+                0 => json!(self.below(2_000) as i64 - 1_000),
+                1 => json!(self.below(1_000) as f64 / 8.0),
+                _ => json!(self.next()),
+            },
+            3 => Value::String(self.string()),
+            4 => Value::Array((0..self.below(4)).map(|_| self.value(depth - 1)).collect()),
+            _ => {
+                let mut map = Map::new();
+                for _ in 0..self.below(5) {
+                    let key = self.string();
+                    map.insert(key, self.value(depth - 1));
+                }
+                Value::Object(map)
+            }
+        }
+    }
+}
+
+/// Same answers written differently: integers as floats, members inserted in reverse.
+fn respell(value: &Value) -> Value {
+    match value {
+        Value::Number(number) => match number.as_i64() {
+            Some(int) if int.unsigned_abs() < 1 << 53 => {
+                Value::Number(Number::from_f64(int as f64).expect("finite"))
+            }
+            _ => value.clone(),
+        },
+        Value::Array(items) => Value::Array(items.iter().map(respell).collect()),
+        Value::Object(map) => {
+            let mut respelled = Map::new();
+            for (key, item) in map.iter().rev() {
+                respelled.insert(key.clone(), respell(item));
+            }
+            Value::Object(respelled)
+        }
+        other => other.clone(),
+    }
+}
+
+#[test]
+fn semantically_equal_answers_hash_identically() {
+    let mut rng = Rng(0xca11);
+    for case in 0..500 {
+        let value = rng.value(3);
+        let respelled = respell(&value);
+        assert_eq!(
+            canonical_hash(&value),
+            canonical_hash(&respelled),
+            "case {case}: {value}"
+        );
+        assert_eq!(
+            canonical_json(&canonicalize(&value, &CanonicalOptions::default())),
+            canonical_json(&respelled),
+            "case {case}"
+        );
+    }
+}
+
+#[test]
+fn different_answers_do_not_collide() {
+    let mut rng = Rng(0xd1ff);
+    let mut encodings = BTreeSet::new();
+    let mut hashes = BTreeSet::new();
+    for _ in 0..2_000 {
+        let value = rng.value(3);
+        if encodings.insert(canonical_json(&canonicalize(
+            &value,
+            &CanonicalOptions::default(),
+        ))) {
+            assert!(
+                hashes.insert(canonical_hash(&value)),
+                "collision for {value}"
+            );
+        }
+    }
+
+    let near_misses = [
+        json!({ "a": 1 }),
+        json!({ "a": "1" }),
+        json!({ "a": [1] }),
+        json!({ "a": true }),
+        json!({ "a": null }),
+        json!({ "a": 1.5 }),
+        json!({ "ab": "c" }),
+        json!({ "a": "bc" }),
+        json!({ "a": { "b": 1 } }),
+        json!({ "a.b": 1 }),
+    ];
+    let distinct = near_misses
+        .iter()
+        .map(canonical_hash)
+        .collect::<BTreeSet<_>>();
+    assert_eq!(distinct.len(), near_misses.len());
+}
+
+#[test]
+fn canonical_hash_matches_documented_encoding() {
+    let value = json!({ "b": 1.0, "a": [true, null, "x"] });
+    let hex = canonical_hash(&value)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    // sha256(`{"a":[true,null,"x"],"b":1}`)
+    assert_eq!(
+        hex,
+        "54a65415ad370228851a1da4b31b6fd42dc58b19a50d35cae759325f7388ce64"
+    );
+}
+
+#[test]
+fn nfc_normalization_is_opt_in() {
+    let composed = json!({ "caf\u{e9}": "\u{e9}t\u{e9}" });
+    let decomposed = json!({ "cafe\u{301}": "e\u{301}te\u{301}" });
+    let nfc = CanonicalOptions { nfc: true };
+    assert_eq!(
+        canonicalize(&composed, &nfc),
+        canonicalize(&decomposed, &nfc)
+    );
+    assert_ne!(canonical_hash(&composed), canonical_hash(&decomposed));
+}
+
+#[test]
+fn only_safe_integer_valued_floats_become_integers() {
+    let value = json!([1e300, 9007199254740992.0, -0.0]);
+    assert_eq!(
+        canonicalize(&value, &CanonicalOptions::default()),
+        json!([1e300, 9007199254740992i64, 0])
+    );
+}
+
+#[test]
+fn merge_and_diff_treat_integer_valued_floats_as_equal() {
+    let mut old = AnswerSet::new("form", "1");
+    old.answers = json!({ "replicas": 3, "ratio": 0.5 });
+    let mut new = old.clone();
+    new.answers = json!({ "replicas": 3.0, "ratio": 0.5 });
+    assert!(old.diff(&new).is_empty());
+
+    let merged = AnswerSet::merge(&old, &new, &Default::default()).expect("merge");
+    let provenance = merged.meta.and_then(|meta| meta.merge).expect("provenance");
+    assert!(provenance.conflicts.is_empty());
+}
@@ -0,0 +1,110 @@
+use serde_json::{Value, json};
+
+use qa_spec::expr::{CaseArm, Expr};
+use qa_spec::{ComputedStatus, FormSpec, build_render_payload, compute_answers, render_json_ui};
+
+fn region_form(default: Option<Value>) -> FormSpec {
+    let mut label = json!({
+        "op": "case",
+        "on": { "op": "answer", "path": "region" },
+        "arms": [
+            { "when": "us-east-1", "then": { "op": "literal", "value": "US East" } },
+            { "when": "eu-west-1", "then": { "op": "literal", "value": "EU West" } }
+        ]
+    });
+    if let Some(default) = default {
+        label["default"] = json!({ "op": "literal", "value": default });
+    }
+    FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "region",
+                "type": "enum",
+                "title": "Region",
+                "choices": ["us-east-1", "eu-west-1", "ap-south-1"]
+            },
+            { "id": "region_label", "type": "string", "title": "Region label", "computed": label }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn case_picks_the_matching_arm_or_the_default() {
+    let spec = region_form(Some(json!("Other")));
+    let label =
+        |answers: Value| compute_answers(&spec, &answers, &json!({})).0["region_label"].clone();
+
+    assert_eq!(label(json!({ "region": "us-east-1" })), "US East");
+    assert_eq!(label(json!({ "region": "eu-west-1" })), "EU West");
+    assert_eq!(label(json!({ "region": "ap-south-1" })), "Other");
+
+    // Without a value to switch on the default is not used either.
+    let (computed, reports) = compute_answers(&spec, &json!({}), &json!({}));
+    assert!(computed.get("region_label").is_none(), "{computed}");
+    assert_eq!(reports[0].status, ComputedStatus::Unresolved);
+}
+
+#[test]
+fn case_without_default_is_unresolved_when_nothing_matches() {
+    let spec = region_form(None);
+    let (computed, reports) =
+        compute_answers(&spec, &json!({ "region": "ap-south-1" }), &json!({}));
+    assert!(computed.get("region_label").is_none(), "{computed}");
+    assert_eq!(reports[0].status, ComputedStatus::Unresolved);
+}
+
+#[test]
+fn render_payload_shows_the_resolved_label() {
+    let spec = region_form(Some(json!("Other")));
+    let payload = build_render_payload(&spec, &json!({}), &json!({ "region": "eu-west-1" }));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][1]["id"], "region_label");
+    assert_eq!(ui["questions"][1]["current_value"], "EU West");
+}
+
+#[test]
+fn case_round_trips_and_matches_exact_values() {
+    let expr = Expr::Case {
+        on: Box::new(Expr::Answer {
+            path: "replicas".into(),
+        }),
+        arms: vec![
+            CaseArm {
+                when: json!(1),
+                then: Expr::Literal {
+                    value: json!("single"),
+                },
+            },
+            CaseArm {
+                when: json!([1, 2]),
+                then: Expr::Literal {
+                    value: json!("pair"),
+                },
+            },
+        ],
+        default: None,
+    };
+    let value = serde_json::to_value(&expr).expect("serialize");
+    assert!(value.get("default").is_none(), "{value}");
+    assert_eq!(serde_json::from_value::<Expr>(value).expect("parse"), expr);
+
+    assert_eq!(
+        expr.evaluate_value(&json!({ "replicas": 1 })),
+        Some(json!("single"))
+    );
+    assert_eq!(
+        expr.evaluate_value(&json!({ "replicas": [1, 2] })),
+        Some(json!("pair"))
+    );
+    assert_eq!(expr.evaluate_value(&json!({ "replicas": "1" })), None);
+    assert_eq!(expr.referenced_answers(), ["replicas"]);
+
+    let schema = serde_json::to_string(&schemars::schema_for!(Expr)).unwrap();
+    assert!(schema.contains("\"case\""), "{schema}");
+    assert!(schema.contains("CaseArm"), "{schema}");
+}
@@ -0,0 +1,145 @@
+use serde_json::json;
+
+use qa_spec::spec::ChoiceCatalog;
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_json_ui,
+    validate,
+};
+
+fn locale_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "locale",
+        "title": "Locale",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "country",
+                "type": "enum",
+                "title": "Country",
+                "required": true,
+                "choices_catalog": "iso3166-alpha2"
+            },
+            {
+                "id": "languages",
+                "type": "multi_enum",
+                "title": "Languages",
+                "choices_catalog": "iso639-1"
+            },
+            {
+                "id": "office",
+                "type": "object",
+                "title": "Office",
+                "object": {
+                    "fields": [
+                        {
+                            "id": "timezone",
+                            "type": "enum",
+                            "title": "Time zone",
+                            "choices_catalog": "timezone"
+                        }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn catalogs_expand_into_choices_at_load_time() {
+    let spec = locale_form();
+
+    let country = spec.questions[0].choices.as_ref().expect("countries");
+    assert_eq!(country.len(), ChoiceCatalog::Iso3166Alpha2.choices().len());
+    assert!(country.iter().any(|code| code == "DE"));
+
+    let languages = spec.questions[1].choices.as_ref().expect("languages");
+    assert!(languages.iter().any(|code| code == "en"));
+
+    let office = spec.questions[2].object.as_ref().expect("object");
+    let zones = office.fields[0].choices.as_ref().expect("zones");
+    assert!(zones.iter().any(|zone| zone == "Europe/Berlin"));
+    assert!(zones.iter().any(|zone| zone == "UTC"));
+}
+
+#[test]
+fn explicit_choices_are_kept_over_the_catalog() {
+    let spec = FormSpec::from_value(json!({
+        "id": "locale",
+        "title": "Locale",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "country",
+            "type": "enum",
+            "title": "Country",
+            "choices_catalog": "iso3166-alpha2",
+            "choices": ["DE", "FR"]
+        }]
+    }))
+    .expect("spec");
+
+    assert_eq!(
+        spec.questions[0].choices,
+        Some(vec!["DE".to_string(), "FR".to_string()])
+    );
+}
+
+#[test]
+fn validation_schema_and_examples_use_the_expanded_list() {
+    let spec = locale_form();
+
+    let answers = json!({
+        "country": "DE",
+        "languages": ["de", "en"],
+        "office": { "timezone": "Europe/Berlin" }
+    });
+    assert!(validate(&spec, &answers).valid);
+
+    let result = validate(&spec, &json!({ "country": "XX" }));
+    assert!(!result.valid);
+    assert_eq!(result.errors[0].path.as_deref(), Some("/country"));
+
+    let schema = answers_schema(&spec, &Default::default());
+    let countries = schema["properties"]["country"]["enum"]
+        .as_array()
+        .expect("enum");
+    assert_eq!(
+        countries.len(),
+        ChoiceCatalog::Iso3166Alpha2.choices().len()
+    );
+
+    let example = example_answers(&spec, &Default::default());
+    assert!(validate(&spec, &example).valid, "{example}");
+}
+
+#[test]
+fn renderers_mark_the_catalog() {
+    let spec = locale_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["catalog"], "iso3166-alpha2");
+    assert_eq!(ui["questions"][1]["catalog"], "iso639-1");
+    assert!(ui["questions"][0]["choices"].as_array().is_some());
+
+    let card = render_card(&payload).to_string();
+    assert!(card.contains(r#""value":"DE""#), "{card}");
+}
+
+#[test]
+fn unknown_catalogs_are_rejected_with_a_suggestion() {
+    let err = FormSpec::from_value(json!({
+        "id": "locale",
+        "title": "Locale",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "tz",
+            "type": "enum",
+            "title": "Time zone",
+            "choices_catalog": "timezones"
+        }]
+    }))
+    .expect_err("unknown catalog");
+
+    assert!(err.to_string().contains("timezone"), "{err}");
+}
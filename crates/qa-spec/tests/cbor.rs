@@ -0,0 +1,111 @@
+use serde_json::{Map, Number, Value, json};
+
+use qa_spec::{AnswerSet, answers::Meta};
+
+/// SplitMix64, so generated cases are reproducible from the seed alone.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn string(&mut self) -> String {
+        const ALPHABET: &[char] = &['a', 'b', 'z', '_', '0', ' ', 'é', 'ß', '日', '🦀', '\n'];
+        (0..self.below(12))
+            .map(|_| ALPHABET[self.below(ALPHABET.len() as u64) as usize])
+            .collect()
+    }
+
+    fn number(&mut self) -> Number {
+        match self.below(6) {
+            0 => Number::from(self.next() as i64),
+            1 => Number::from(self.next()),
+            2 => Number::from(self.below(100)),
+            3 => Number::from_f64(self.below(1_000) as f64 / 4.0).expect("finite"),
+            4 => Number::from_f64(-0.0).expect("finite"),
+            _ => {
+                let float = f64::from_bits(self.next());
+                Number::from_f64(if float.is_finite() { float } else { 1.5 }).expect("finite")
+            }
+        }
+    }
+
+    fn value(&mut self, depth: u32) -> Value {
+        let kinds = if depth == 0 { 4 } else { 6 };
+        match self.below(kinds) {
+            0 => Value::Null,
+            1 => Value::Bool(self.below(2) == 1),
+            2 => Value::Number(self.number()),
+            3 => Value::String(self.string()),
+            4 => Value::Array((0..self.below(4)).map(|_| self.value(depth - 1)).collect()),
+            _ => self.object(depth - 1),
+        }
+    }
+
+    fn object(&mut self, depth: u32) -> Value {
+        let mut map = Map::new();
+        for _ in 0..self.below(6) {
+            let key = self.string();
+            map.insert(key, self.value(depth));
+        }
+        Value::Object(map)
+    }
+
+    fn answer_set(&mut self) -> AnswerSet {
+        let mut set = AnswerSet::new(self.string(), self.string());
+        set.answers = self.object(3);
+        if self.below(2) == 1 {
+            set.meta = Some(Meta {
+                actor: Some(self.string()),
+                attempts: Some(self.below(10) as u32),
+                ..Meta::default()
+            });
+        }
+        set
+    }
+}
+
+#[test]
+fn cbor_round_trip_is_byte_stable_for_generated_answer_sets() {
+    let mut rng = Rng(0x5eed);
+    for case in 0..500 {
+        let set = rng.answer_set();
+        let bytes = set.to_cbor().expect("encode");
+        let decoded = AnswerSet::from_cbor(&bytes).expect("decode");
+        assert_eq!(decoded, set, "case {case}");
+        assert_eq!(decoded.to_cbor().expect("re-encode"), bytes, "case {case}");
+    }
+}
+
+#[test]
+fn cbor_map_keys_use_canonical_order() {
+    let mut set = AnswerSet::new("f", "1");
+    set.answers = json!({ "bb": 1, "c": 2, "a": 3 });
+    let bytes = set.to_cbor().expect("encode");
+
+    let mut expected = vec![0xa3, 0x67];
+    expected.extend_from_slice(b"answers");
+    expected.extend_from_slice(&[0xa3, 0x61, b'a', 0x03, 0x61, b'c', 0x02, 0x62, b'b', b'b']);
+    expected.extend_from_slice(&[0x01, 0x67]);
+    expected.extend_from_slice(b"form_id");
+    expected.extend_from_slice(&[0x61, b'f', 0x6c]);
+    expected.extend_from_slice(b"spec_version");
+    expected.extend_from_slice(&[0x61, b'1']);
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn from_cbor_rejects_malformed_input() {
+    assert!(AnswerSet::from_cbor(&[0xff, 0x00]).is_err());
+    let not_a_set = serde_cbor::to_vec(&json!({ "answers": {} })).expect("encode");
+    assert!(AnswerSet::from_cbor(&not_a_set).is_err());
+}
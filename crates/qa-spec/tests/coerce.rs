@@ -0,0 +1,146 @@
+use serde_json::json;
+
+use qa_spec::{FormSpec, ValidationOptions, coerce_answers, validate, validate_with};
+
+fn signup_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "signup",
+        "title": "Signup",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "newsletter", "type": "boolean", "title": "Newsletter" },
+            { "id": "age", "type": "integer", "title": "Age" },
+            { "id": "budget", "type": "number", "title": "Budget" },
+            { "id": "timeout", "type": "duration", "title": "Timeout" },
+            { "id": "plan", "type": "enum", "title": "Plan", "choices": ["free", "pro"] },
+            {
+                "id": "regions",
+                "type": "multi_enum",
+                "title": "Regions",
+                "choices": ["eu", "us", "apac"]
+            },
+            {
+                "id": "servers",
+                "type": "list",
+                "title": "Servers",
+                "list": {
+                    "fields": [
+                        { "id": "port", "type": "integer", "title": "Port" },
+                        { "id": "public", "type": "boolean", "title": "Public" }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn strings_are_converted_to_question_types() {
+    let spec = signup_form();
+    let answers = json!({
+        "newsletter": " TRUE ",
+        "age": "42",
+        "budget": "19.5",
+        "timeout": "1h30m",
+        "plan": "Pro",
+        "regions": "eu, apac",
+        "servers": [{ "port": "8080", "public": "false" }]
+    });
+
+    assert!(!validate(&spec, &answers).valid);
+
+    let (coerced, result) = validate_with(&spec, &answers, &ValidationOptions { coerce: true });
+    assert!(result.valid, "{result:?}");
+    assert_eq!(
+        coerced,
+        json!({
+            "newsletter": true,
+            "age": 42,
+            "budget": 19.5,
+            "timeout": 5400,
+            "plan": "pro",
+            "regions": ["eu", "apac"],
+            "servers": [{ "port": 8080, "public": false }]
+        })
+    );
+}
+
+#[test]
+fn ambiguous_strings_still_fail_the_type_check() {
+    let spec = signup_form();
+    let options = ValidationOptions { coerce: true };
+
+    for (id, raw) in [
+        ("newsletter", "1"),
+        ("newsletter", "yes"),
+        ("age", "4.5"),
+        ("age", "forty"),
+        ("budget", "NaN"),
+    ] {
+        let (coerced, result) = validate_with(&spec, &json!({ id: raw }), &options);
+        assert_eq!(coerced[id], raw);
+        assert_eq!(
+            result.errors[0].code.as_deref(),
+            Some("type_mismatch"),
+            "{id}: {raw}"
+        );
+    }
+
+    let (_, result) = validate_with(&spec, &json!({ "regions": "eu, mars" }), &options);
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn enum_matching_ignores_case_only_when_unambiguous() {
+    let spec = FormSpec::from_value(json!({
+        "id": "tiers",
+        "title": "Tiers",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "tier",
+            "type": "enum",
+            "title": "Tier",
+            "choices": ["Gold", "gold", "silver"]
+        }]
+    }))
+    .expect("spec");
+
+    assert_eq!(
+        coerce_answers(&spec, &json!({ "tier": "gold" }))["tier"],
+        "gold"
+    );
+    assert_eq!(
+        coerce_answers(&spec, &json!({ "tier": "GOLD" }))["tier"],
+        "GOLD"
+    );
+    assert_eq!(
+        coerce_answers(&spec, &json!({ "tier": " Silver " }))["tier"],
+        "silver"
+    );
+}
+
+#[test]
+fn coercion_is_off_by_default() {
+    let spec = signup_form();
+    let answers = json!({ "age": "42" });
+
+    let (validated, result) = validate_with(&spec, &answers, &ValidationOptions::default());
+    assert_eq!(validated, answers);
+    assert_eq!(result, validate(&spec, &answers));
+}
+
+#[test]
+fn list_answers_accept_json_text() {
+    let spec = signup_form();
+    let answers = json!({ "servers": "[{\"port\": \"8080\", \"public\": true}]" });
+
+    let coerced = coerce_answers(&spec, &answers);
+    assert_eq!(
+        coerced["servers"],
+        json!([{ "port": 8080, "public": true }])
+    );
+
+    let answers = json!({ "servers": "not json" });
+    assert_eq!(coerce_answers(&spec, &answers), answers);
+}
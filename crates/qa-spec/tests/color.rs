@@ -0,0 +1,81 @@
+use serde_json::json;
+
+use qa_spec::color::{is_hex_color, normalize_color};
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_json_ui,
+    render_text, validate,
+};
+
+fn theme_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "theme",
+        "title": "Theme",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "accent",
+            "type": "color",
+            "title": "Accent color",
+            "required": true
+        }]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn hex_colors_need_six_or_eight_digits() {
+    assert!(is_hex_color("#336699"));
+    assert!(is_hex_color("#33669980"));
+    assert!(is_hex_color("#AbCdEf"));
+    assert!(!is_hex_color("336699"));
+    assert!(!is_hex_color("#369"));
+    assert!(!is_hex_color("#3366990"));
+    assert!(!is_hex_color("#33669g"));
+
+    assert_eq!(normalize_color(" Red ").as_deref(), Some("#ff0000"));
+    assert_eq!(normalize_color("#336699").as_deref(), Some("#336699"));
+    assert_eq!(normalize_color("reddish"), None);
+}
+
+#[test]
+fn validation_reports_color_invalid() {
+    let spec = theme_form();
+
+    assert!(validate(&spec, &json!({ "accent": "#336699" })).valid);
+    assert!(validate(&spec, &json!({ "accent": "#336699cc" })).valid);
+
+    let result = validate(&spec, &json!({ "accent": "blue" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("color_invalid"));
+    assert_eq!(result.errors[0].path.as_deref(), Some("/accent"));
+
+    let result = validate(&spec, &json!({ "accent": 336699 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn schema_and_examples_use_hex() {
+    let spec = theme_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    let accent = &schema["properties"]["accent"];
+    assert_eq!(accent["type"], "string");
+    assert_eq!(accent["pattern"], "^#([0-9A-Fa-f]{6}|[0-9A-Fa-f]{8})$");
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["accent"], "#336699");
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn renderers_label_the_color_type() {
+    let spec = theme_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["type"], "color");
+
+    let card = render_card(&payload).to_string();
+    assert!(card.contains(r##""placeholder":"#RRGGBB""##), "{card}");
+
+    let text = render_text(&payload);
+    assert!(text.contains("Syntax: #RRGGBB or #RRGGBBAA"), "{text}");
+}
@@ -0,0 +1,99 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, RenderOptions, VisibilityMode, answers_schema, build_render_payload_with_options,
+    compute_answers, render_json_ui, resolve_visibility, validate,
+};
+
+fn spec() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "service",
+        "title": "Service",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "name",
+                "type": "string",
+                "title": "Name",
+                "required": true,
+                "constraint": { "pattern": "^[a-z-]+$" }
+            },
+            {
+                "id": "slug",
+                "type": "string",
+                "title": "Slug",
+                "required": false,
+                "computed": { "op": "answer", "path": "name" }
+            },
+            {
+                "id": "owner",
+                "type": "string",
+                "title": "Owner",
+                "required": false,
+                "computed": { "op": "answer", "path": "slug" },
+                "visible_if": { "op": "is_set", "path": "name" }
+            },
+            {
+                "id": "ports",
+                "type": "list",
+                "title": "Ports",
+                "required": false,
+                "list": {
+                    "fields": [
+                        {
+                            "id": "label",
+                            "type": "string",
+                            "title": "Label",
+                            "required": true,
+                            "constraint": { "pattern": "^[a-z]+$" }
+                        }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn compiled_spec_matches_the_free_functions() {
+    let spec = spec();
+    let compiled = spec.clone().compile();
+    let ctx = json!({});
+    let options = RenderOptions::for_form(&spec);
+    for answers in [
+        json!({}),
+        json!({ "name": "api" }),
+        json!({ "name": "API", "extra": true }),
+        json!({ "name": "api", "ports": [{ "label": "http" }, { "label": "HTTP" }] }),
+    ] {
+        assert_eq!(compiled.validate(&answers), validate(&spec, &answers));
+        assert_eq!(
+            compiled.compute_answers(&answers, &ctx),
+            compute_answers(&spec, &answers, &ctx)
+        );
+        assert_eq!(
+            render_json_ui(&compiled.build_render_payload(&ctx, &answers, None, &options)),
+            render_json_ui(&build_render_payload_with_options(
+                &spec, &ctx, &answers, None, &options
+            ))
+        );
+        let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+        assert_eq!(
+            compiled.answers_schema(&visibility),
+            answers_schema(&spec, &visibility)
+        );
+    }
+}
+
+#[test]
+fn compiled_spec_looks_up_questions_by_id() {
+    let compiled = spec().compile();
+    assert_eq!(
+        compiled.question("owner").map(|question| &question.title),
+        Some(&"Owner".to_string())
+    );
+    assert!(compiled.question("label").is_none());
+    assert_eq!(compiled.id, "service");
+    assert_eq!(compiled.into_spec(), spec());
+}
@@ -11,15 +11,23 @@ fn question(id: &str) -> QuestionSpec {
         description: None,
         description_i18n: None,
         required: true,
+        required_if: None,
+        read_only_if: None,
+        order: None,
         choices: None,
+        choices_catalog: None,
         default_value: None,
         secret: false,
+        secret_path: None,
+        normalize: Vec::new(),
         visible_if: None,
         constraint: None,
         list: None,
+        object: None,
         computed: None,
         policy: Default::default(),
         computed_overridable: false,
+        i18n: None,
     }
 }
 
@@ -35,6 +43,10 @@ fn form(id: &str, questions: Vec<QuestionSpec>) -> FormSpec {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions,
     }
 }
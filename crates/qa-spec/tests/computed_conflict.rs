@@ -0,0 +1,109 @@
+use serde_json::json;
+
+use qa_spec::{
+    CompiledFormSpec, FormSpec, ProgressContext, VisibilityMode, build_render_payload,
+    next_question, render_json_ui, resolve_visibility, validate, validate_field,
+};
+
+fn pricing_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "pricing",
+        "title": "Pricing",
+        "version": "1.0.0",
+        "progress_policy": { "skip_answered": true },
+        "questions": [
+            { "id": "seats", "type": "integer", "title": "Seats", "required": true },
+            {
+                "id": "total",
+                "type": "integer",
+                "title": "Total",
+                "computed": { "op": "answer", "path": "seats" }
+            },
+            {
+                "id": "discount",
+                "type": "integer",
+                "title": "Discount",
+                "computed": { "op": "literal", "value": 0 },
+                "computed_overridable": true
+            },
+            { "id": "notes", "type": "string", "title": "Notes" }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn differing_override_is_a_conflict() {
+    let spec = pricing_form();
+
+    let result = validate(&spec, &json!({ "seats": 3, "total": 99 }));
+    assert!(!result.valid);
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("computed_conflict"));
+    assert_eq!(error.message, "qa_spec.computed_conflict");
+    assert_eq!(error.path.as_deref(), Some("/total"));
+    assert_eq!(error.params["expected"], "3");
+
+    let compiled = CompiledFormSpec::new(spec.clone());
+    assert_eq!(
+        compiled.validate(&json!({ "seats": 3, "total": 99 })),
+        result
+    );
+    let field = validate_field(&spec, &json!({ "seats": 3, "total": 99 }), "total").unwrap();
+    assert_eq!(field.errors[0].code.as_deref(), Some("computed_conflict"));
+}
+
+#[test]
+fn matching_or_overridable_answers_are_accepted() {
+    let spec = pricing_form();
+
+    assert!(validate(&spec, &json!({ "seats": 3 })).valid);
+    assert!(validate(&spec, &json!({ "seats": 3, "total": 3 })).valid);
+    assert!(validate(&spec, &json!({ "seats": 3, "total": 3.0 })).valid);
+    assert!(validate(&spec, &json!({ "seats": 3, "discount": 10 })).valid);
+}
+
+#[test]
+fn render_marks_read_only_and_progress_skips_it() {
+    let spec = pricing_form();
+    let answers = json!({ "seats": 3 });
+
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let read_only = payload
+        .questions
+        .iter()
+        .map(|question| (question.id.as_str(), question.read_only))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        read_only,
+        [
+            ("seats", false),
+            ("total", true),
+            ("discount", false),
+            ("notes", false)
+        ]
+    );
+    assert_eq!(payload.next_question_id.as_deref(), Some("notes"));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][1]["read_only"], true);
+    assert!(ui["questions"][0].get("read_only").is_none());
+
+    // Even when nothing has been answered yet, the computed question is not asked.
+    let spec = FormSpec::from_value(json!({
+        "id": "only-computed",
+        "title": "Only computed",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "stamp",
+            "type": "string",
+            "title": "Stamp",
+            "computed": { "op": "var", "path": "missing" }
+        }]
+    }))
+    .expect("spec");
+    let empty = json!({});
+    let visibility = resolve_visibility(&spec, &empty, VisibilityMode::Visible);
+    let ctx = ProgressContext::new(&empty, &empty);
+    assert_eq!(next_question(&spec, &ctx, &visibility), None);
+}
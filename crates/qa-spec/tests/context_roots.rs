@@ -0,0 +1,145 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, VisibilityCache, VisibilityMode, build_render_payload, compute_answers,
+    render_json_ui, resolve_visibility, resolve_visibility_with_ctx, validate, validate_with_ctx,
+};
+
+fn deploy_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true },
+            {
+                "id": "secret_ref",
+                "type": "string",
+                "title": "Secret reference",
+                "required": true,
+                "visible_if": { "op": "var", "path": "config.secrets_host_available" }
+            },
+            {
+                "id": "endpoint",
+                "type": "string",
+                "title": "Endpoint",
+                "computed": {
+                    "op": "concat",
+                    "expressions": [
+                        { "op": "var", "path": "/env/region" },
+                        { "op": "literal", "value": "." },
+                        { "op": "answer", "path": "name" }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn ctx(secrets_host_available: bool) -> Value {
+    json!({
+        "config": { "secrets_host_available": secrets_host_available },
+        "state": { "attempt": 2 },
+        "env": { "region": "eu" }
+    })
+}
+
+#[test]
+fn config_flag_hides_a_question() {
+    let spec = deploy_form();
+    let answers = json!({ "name": "api" });
+
+    let hidden = resolve_visibility_with_ctx(&spec, &answers, &ctx(false), VisibilityMode::Visible);
+    assert!(!hidden.map["secret_ref"]);
+    assert!(hidden.unresolved.is_empty());
+    let shown = resolve_visibility_with_ctx(&spec, &answers, &ctx(true), VisibilityMode::Visible);
+    assert!(shown.map["secret_ref"]);
+
+    // Without the runtime context the condition cannot be evaluated.
+    let unresolved =
+        resolve_visibility_with_ctx(&spec, &answers, &json!({}), VisibilityMode::Hidden);
+    assert_eq!(unresolved.unresolved, vec!["secret_ref".to_string()]);
+    assert!(!resolve_visibility(&spec, &answers, VisibilityMode::Hidden)["secret_ref"]);
+}
+
+#[test]
+fn validation_skips_questions_hidden_by_config() {
+    let spec = deploy_form();
+    let answers = json!({ "name": "api" });
+
+    assert!(validate_with_ctx(&spec, &answers, &ctx(false)).valid);
+    let result = validate_with_ctx(&spec, &answers, &ctx(true));
+    assert_eq!(result.missing_required, vec!["secret_ref".to_string()]);
+    // Plain validation has no context and falls back to visible.
+    assert!(!validate(&spec, &answers).valid);
+
+    let compiled = spec.clone().compile();
+    assert_eq!(
+        compiled.validate_with_ctx(&answers, &ctx(false)),
+        validate_with_ctx(&spec, &answers, &ctx(false))
+    );
+}
+
+#[test]
+fn render_payload_reads_the_runtime_context() {
+    let spec = deploy_form();
+    let answers = json!({ "name": "api" });
+
+    let ui = render_json_ui(&build_render_payload(&spec, &ctx(false), &answers));
+    let secret = ui["questions"]
+        .as_array()
+        .and_then(|questions| questions.iter().find(|q| q["id"] == "secret_ref"))
+        .expect("secret question");
+    assert_eq!(secret["visible"], false);
+    assert_eq!(ui["progress"]["total"], 2);
+
+    let (computed, _) = compute_answers(&spec, &answers, &ctx(false));
+    assert_eq!(computed["endpoint"], "eu.api");
+}
+
+#[test]
+fn answers_shadow_context_roots() {
+    let spec = FormSpec::from_value(json!({
+        "id": "shadow",
+        "title": "Shadow",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "config", "type": "object", "title": "Config", "object": { "fields": [
+                { "id": "secrets_host_available", "type": "boolean", "title": "Available" }
+            ] } },
+            {
+                "id": "secret_ref",
+                "type": "string",
+                "title": "Secret reference",
+                "visible_if": { "op": "var", "path": "config.secrets_host_available" }
+            }
+        ]
+    }))
+    .expect("spec");
+    let answers = json!({ "config": { "secrets_host_available": true } });
+
+    let visibility =
+        resolve_visibility_with_ctx(&spec, &answers, &ctx(false), VisibilityMode::Visible);
+    assert!(visibility.map["secret_ref"]);
+}
+
+#[test]
+fn cache_keys_include_the_context_roots() {
+    let spec = deploy_form();
+    let answers = json!({ "name": "api" });
+    let mut cache = VisibilityCache::default();
+
+    let hidden = cache
+        .resolve_with_ctx(&spec, &answers, &ctx(false), VisibilityMode::Visible)
+        .map["secret_ref"];
+    let shown = cache
+        .resolve_with_ctx(&spec, &answers, &ctx(true), VisibilityMode::Visible)
+        .map["secret_ref"];
+    assert!(!hidden);
+    assert!(shown);
+    assert_eq!(cache.misses(), 2);
+
+    cache.resolve_with_ctx(&spec, &answers, &ctx(true), VisibilityMode::Visible);
+    assert_eq!(cache.hits(), 1);
+}
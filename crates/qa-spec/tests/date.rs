@@ -0,0 +1,92 @@
+use serde_json::json;
+
+use qa_spec::date::{is_iso_date, normalize_date};
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_json_ui,
+    validate,
+};
+
+fn launch_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "launch",
+        "title": "Launch",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "launch_date", "type": "date", "title": "Launch date", "required": true }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn iso_dates_must_be_real_calendar_days() {
+    assert!(is_iso_date("2026-01-31"));
+    assert!(is_iso_date("2024-02-29"));
+    assert!(is_iso_date("2000-02-29"));
+    assert!(!is_iso_date("1900-02-29"));
+    assert!(!is_iso_date("2026-02-29"));
+    assert!(!is_iso_date("2026-04-31"));
+    assert!(!is_iso_date("2026-13-01"));
+    assert!(!is_iso_date("2026-00-10"));
+    assert!(!is_iso_date("2026-1-05"));
+    assert!(!is_iso_date("2026/01/05"));
+    assert!(!is_iso_date("+026-01-05"));
+}
+
+#[test]
+fn loose_dates_normalize_to_iso() {
+    assert_eq!(normalize_date("2026-3-7").as_deref(), Some("2026-03-07"));
+    assert_eq!(
+        normalize_date(" 2026/03/07 ").as_deref(),
+        Some("2026-03-07")
+    );
+    assert_eq!(normalize_date("2026.12.31").as_deref(), Some("2026-12-31"));
+    assert_eq!(normalize_date("26-03-07"), None);
+    assert_eq!(normalize_date("07-03-2026"), None);
+    assert_eq!(normalize_date("2026-02-30"), None);
+    assert_eq!(normalize_date("2026-03-07-01"), None);
+    assert_eq!(normalize_date("2026-03"), None);
+}
+
+#[test]
+fn validation_rejects_malformed_dates() {
+    let spec = launch_form();
+
+    assert!(validate(&spec, &json!({ "launch_date": "2026-05-01" })).valid);
+    for bad in [json!("2026-02-30"), json!("May 1st"), json!(20260501)] {
+        let result = validate(&spec, &json!({ "launch_date": bad }));
+        assert!(!result.valid, "{bad} should be rejected");
+        assert_eq!(result.errors[0].code.as_deref(), Some("date_invalid"));
+        assert_eq!(result.errors[0].message, "qa_spec.date_invalid");
+    }
+}
+
+#[test]
+fn schema_and_examples_describe_dates() {
+    let spec = launch_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(
+        schema["properties"]["launch_date"],
+        json!({ "type": "string", "format": "date" })
+    );
+
+    let example = example_answers(&spec, &Default::default());
+    let value = example["launch_date"].as_str().expect("example date");
+    assert!(is_iso_date(value), "{value}");
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn renderers_expose_date_inputs() {
+    let spec = launch_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({ "launch_date": "2026-05-01" }));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["type"], "date");
+
+    let pending = build_render_payload(&spec, &json!({}), &json!({}));
+    let card = render_card(&pending);
+    let rendered = card.to_string();
+    assert!(rendered.contains(r#""type":"Input.Date""#), "{rendered}");
+}
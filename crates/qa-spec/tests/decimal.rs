@@ -0,0 +1,84 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_text,
+    spec::question::decimal_places_of, validate,
+};
+
+fn price_form(constraint: serde_json::Value) -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "shop",
+        "title": "Shop",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "price",
+                "type": "number",
+                "title": "Price",
+                "required": true,
+                "constraint": constraint
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn counts_decimal_places_as_written() {
+    assert_eq!(decimal_places_of(19.999), 3);
+    assert_eq!(decimal_places_of(19.9), 1);
+    assert_eq!(decimal_places_of(20.0), 0);
+    assert_eq!(decimal_places_of(0.0000001), 7);
+}
+
+#[test]
+fn rejects_answers_with_too_many_decimals() {
+    let spec = price_form(json!({ "decimal_places": 2 }));
+
+    assert!(validate(&spec, &json!({ "price": 19.99 })).valid);
+    assert!(validate(&spec, &json!({ "price": 19.9 })).valid);
+    assert!(validate(&spec, &json!({ "price": 20 })).valid);
+
+    let result = validate(&spec, &json!({ "price": 19.999 }));
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("decimal_precision"));
+    assert_eq!(error.path.as_deref(), Some("/price"));
+    assert_eq!(error.params["places"], "2");
+    assert_eq!(error.params["value"], "19.999");
+}
+
+#[test]
+fn schema_uses_multiple_of() {
+    let spec = price_form(json!({ "decimal_places": 2 }));
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(schema["properties"]["price"]["multipleOf"], 0.01);
+
+    let spec = price_form(json!({ "decimal_places": 0 }));
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(schema["properties"]["price"]["multipleOf"], 1.0);
+}
+
+#[test]
+fn examples_respect_precision_and_bounds() {
+    let spec = price_form(json!({
+        "decimal_places": 0,
+        "min": 0.0,
+        "max": 1.0,
+        "exclusive_min": true,
+    }));
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["price"], 1.0);
+    assert!(validate(&spec, &example).valid);
+
+    let spec = price_form(json!({ "decimal_places": 2, "min": 0.125 }));
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["price"], 0.13);
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn text_render_shows_precision() {
+    let spec = price_form(json!({ "decimal_places": 2 }));
+    let text = render_text(&build_render_payload(&spec, &json!({}), &json!({})));
+    assert!(text.contains("  Precision: 2 decimal places"), "{text}");
+}
@@ -0,0 +1,156 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, ProgressContext, QuestionSpec, VisibilityMode, autofill_defaults, next_question,
+    resolve_visibility, typed_default,
+};
+
+fn question(kind: &str, default_value: &str) -> QuestionSpec {
+    serde_json::from_value(json!({
+        "id": "q",
+        "type": kind,
+        "title": "Q",
+        "choices": ["Red", "Green"],
+        "default_value": default_value
+    }))
+    .expect("question")
+}
+
+#[test]
+fn defaults_are_converted_to_the_question_type() {
+    let cases = [
+        ("boolean", "TRUE", json!(true)),
+        ("integer", "42", json!(42)),
+        ("number", "2.5", json!(2.5)),
+        ("duration", "5m", json!(300)),
+        ("enum", "green", json!("Green")),
+        ("multi_enum", "red, Green", json!(["Red", "Green"])),
+        ("string_list", "a, b", json!(["a", "b"])),
+        ("time", "09:30", json!("09:30:00")),
+        ("string", "hello", json!("hello")),
+    ];
+    for (kind, raw, expected) in cases {
+        assert_eq!(
+            typed_default(&question(kind, raw)).expect(kind),
+            Some(expected),
+            "{kind}"
+        );
+    }
+}
+
+#[test]
+fn malformed_defaults_name_the_expected_type() {
+    for (kind, raw) in [
+        ("boolean", "yes"),
+        ("integer", "3.5"),
+        ("enum", "Blue"),
+        ("time", "25:00"),
+        ("object", "{}"),
+    ] {
+        let error = typed_default(&question(kind, raw)).expect_err(kind);
+        assert_eq!(error.question_id, "q");
+        assert_eq!(error.expected, kind);
+        assert_eq!(
+            error.to_string(),
+            format!("default_value '{raw}' of question 'q' is not a valid {kind}")
+        );
+    }
+}
+
+#[test]
+fn autofilled_questions_are_skipped_by_progress() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "progress_policy": { "skip_answered": true, "autofill_defaults": true },
+        "questions": [
+            { "id": "replicas", "type": "integer", "title": "Replicas", "default_value": "2" },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "default_value": "eu",
+                "policy": { "editable_if_from_default": true }
+            },
+            { "id": "name", "type": "string", "title": "Name" }
+        ]
+    }))
+    .expect("spec");
+
+    let (answers, filled) = autofill_defaults(&spec, &json!({}), &json!({})).expect("defaults");
+    assert_eq!(filled, vec!["replicas".to_string()]);
+    assert_eq!(answers, json!({ "replicas": 2 }));
+
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+    let next = next_question(
+        &spec,
+        &ProgressContext::new(&answers, &Value::Null),
+        &visibility,
+    );
+    assert_eq!(next.as_deref(), Some("region"));
+
+    let mut without_policy = spec.clone();
+    without_policy.progress_policy = None;
+    let (answers, filled) =
+        autofill_defaults(&without_policy, &json!({}), &json!({})).expect("defaults");
+    assert!(filled.is_empty());
+    assert_eq!(answers, json!({}));
+}
+
+fn defaults_answered_spec() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "progress_policy": { "skip_answered": true, "treat_default_as_answered": true },
+        "questions": [
+            { "id": "advanced", "type": "boolean", "title": "Advanced" },
+            {
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "required": true,
+                "default_value": "2"
+            },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "default_value": "eu",
+                "visible_if": { "op": "answer", "path": "advanced" }
+            },
+            { "id": "name", "type": "string", "title": "Name" }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn defaults_count_as_answered_without_being_written() {
+    let spec = defaults_answered_spec();
+    let answers = json!({ "advanced": false });
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+    let ctx = ProgressContext::new(&answers, &Value::Null);
+
+    assert_eq!(
+        next_question(&spec, &ctx, &visibility).as_deref(),
+        Some("name")
+    );
+    // `advanced` and the defaulted `replicas`; the hidden `region` does not count.
+    assert_eq!(ctx.answered_count(&spec, &visibility), 2);
+
+    let answers = json!({ "advanced": true });
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+    let ctx = ProgressContext::new(&answers, &Value::Null);
+    assert_eq!(ctx.answered_count(&spec, &visibility), 3);
+}
+
+#[test]
+fn required_defaults_are_written_when_only_counted() {
+    let spec = defaults_answered_spec();
+    let (answers, filled) =
+        autofill_defaults(&spec, &json!({ "advanced": true }), &json!({})).expect("defaults");
+    assert_eq!(filled, vec!["replicas".to_string()]);
+    assert_eq!(answers, json!({ "advanced": true, "replicas": 2 }));
+}
@@ -0,0 +1,107 @@
+use serde_json::json;
+
+use qa_spec::duration::parse_duration;
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_json_ui, render_text,
+    validate,
+};
+
+fn retention_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "retention",
+        "title": "Retention",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "timeout",
+                "type": "duration",
+                "title": "Request timeout",
+                "required": true,
+                "constraint": { "min": 1, "max": 3600 }
+            },
+            {
+                "id": "retention",
+                "type": "duration",
+                "title": "Retention",
+                "required": false,
+                "default_value": "2h"
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn human_durations_parse_to_seconds() {
+    assert_eq!(parse_duration("300"), Some(300));
+    assert_eq!(parse_duration("30s"), Some(30));
+    assert_eq!(parse_duration("5m"), Some(300));
+    assert_eq!(parse_duration("2h"), Some(7_200));
+    assert_eq!(parse_duration("1d"), Some(86_400));
+    assert_eq!(parse_duration(" 1h30m "), Some(5_400));
+    assert_eq!(parse_duration("1h 30m 15s"), Some(5_415));
+    assert_eq!(parse_duration("10 Minutes"), Some(600));
+    assert_eq!(parse_duration(""), None);
+    assert_eq!(parse_duration("m"), None);
+    assert_eq!(parse_duration("5"), Some(5));
+    assert_eq!(parse_duration("5x"), None);
+    assert_eq!(parse_duration("1.5h"), None);
+    assert_eq!(parse_duration("-5m"), None);
+    assert_eq!(parse_duration("99999999999999999999d"), None);
+}
+
+#[test]
+fn validation_enforces_bounds_in_seconds() {
+    let spec = retention_form();
+
+    assert!(validate(&spec, &json!({ "timeout": 300 })).valid);
+
+    let result = validate(&spec, &json!({ "timeout": 7_200 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("max"));
+    let result = validate(&spec, &json!({ "timeout": 0 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("min"));
+
+    let result = validate(&spec, &json!({ "timeout": "5m" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("duration_invalid"));
+    assert_eq!(result.errors[0].message, "qa_spec.duration_invalid");
+
+    for bad in [json!(-5), json!(1.5), json!(true)] {
+        let result = validate(&spec, &json!({ "timeout": bad }));
+        assert_eq!(
+            result.errors[0].code.as_deref(),
+            Some("type_mismatch"),
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn schema_and_examples_use_seconds() {
+    let spec = retention_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(
+        schema["properties"]["timeout"],
+        json!({ "type": "integer", "minimum": 1.0, "maximum": 3600.0, "x-unit": "seconds" })
+    );
+    assert_eq!(schema["properties"]["retention"]["default"], json!(7_200));
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example, json!({ "timeout": 300, "retention": 7_200 }));
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn renderers_explain_duration_syntax() {
+    let spec = retention_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let text = render_text(&payload);
+    assert!(
+        text.contains("  Syntax: seconds, or amounts with units"),
+        "{text}"
+    );
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["type"], "duration");
+}
@@ -0,0 +1,395 @@
+use serde_json::json;
+
+use qa_spec::spec::flow::{CardMode, OnLimit};
+use qa_spec::{
+    FlowEngine, FlowError, FlowLimit, FlowState, FormSpec, QAFlowSpec, StepOutcome,
+    next_question_in_flow,
+};
+
+fn graph_flow() -> QAFlowSpec {
+    serde_json::from_str(include_str!("fixtures/graph_flow.json")).expect("flow fixture")
+}
+
+fn simple_form() -> FormSpec {
+    serde_json::from_str(include_str!("fixtures/simple_form.json")).expect("form fixture")
+}
+
+#[test]
+fn message_step_is_shown_before_first_question() {
+    let flow = graph_flow();
+    let form = simple_form();
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert_eq!(engine.current_step(), Some("start"));
+
+    let answers = json!({});
+    let ctx = json!({});
+    assert_eq!(
+        engine.advance(&answers, &ctx),
+        Ok(StepOutcome::ShowMessage {
+            text: "Welcome to the QA wizard!".into()
+        })
+    );
+    assert_eq!(
+        engine.advance(&answers, &ctx),
+        Ok(StepOutcome::AskQuestion {
+            question_id: "q1".into()
+        })
+    );
+    // Asking again without an answer keeps the run parked on the question.
+    assert_eq!(
+        engine.advance(&answers, &ctx),
+        Ok(StepOutcome::AskQuestion {
+            question_id: "q1".into()
+        })
+    );
+    assert_eq!(engine.current_step(), Some("ask_name"));
+}
+
+#[test]
+fn decision_branches_to_end_once_answered() {
+    let flow = graph_flow();
+    let form = simple_form();
+    let mut engine = FlowEngine::new(&flow, &form);
+    let ctx = json!({});
+    engine.advance(&json!({}), &ctx).expect("message");
+
+    let answers = json!({ "q1": "yes" });
+    assert_eq!(
+        engine.advance(&answers, &ctx),
+        Ok(StepOutcome::Decided { next: "end".into() })
+    );
+    assert_eq!(engine.advance(&answers, &ctx), Ok(StepOutcome::Completed));
+    assert!(engine.state().completed);
+}
+
+#[test]
+fn decision_falls_back_to_default_goto() {
+    let flow = graph_flow();
+    let form = simple_form();
+    let mut engine = FlowEngine::new(&flow, &form);
+    let ctx = json!({});
+    engine.advance(&json!({}), &ctx).expect("message");
+
+    let answers = json!({ "q1": false });
+    assert_eq!(
+        engine.advance(&answers, &ctx),
+        Ok(StepOutcome::Decided {
+            next: "ask_name".into()
+        })
+    );
+    assert_eq!(engine.state().visits.get("ask_name"), Some(&2));
+}
+
+#[test]
+fn state_round_trips_through_json_mid_flight() {
+    let flow = graph_flow();
+    let form = simple_form();
+    let ctx = json!({});
+    let mut engine = FlowEngine::new(&flow, &form);
+    engine.advance(&json!({}), &ctx).expect("message");
+
+    let persisted = serde_json::to_value(engine.state()).expect("serialize state");
+    assert_eq!(persisted["current_step"], "ask_name");
+    let restored: FlowState = serde_json::from_value(persisted).expect("deserialize state");
+
+    let mut resumed = FlowEngine::resume(&flow, &form, restored);
+    assert_eq!(
+        resumed.advance(&json!({ "q1": "yes" }), &ctx),
+        Ok(StepOutcome::Decided { next: "end".into() })
+    );
+}
+
+#[test]
+fn unknown_question_reference_is_reported() {
+    let mut flow = graph_flow();
+    flow.entry = "ask_name".into();
+    let mut form = simple_form();
+    form.questions.retain(|question| question.id != "q1");
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert_eq!(
+        engine.advance(&json!({}), &json!({})),
+        Err(FlowError::UnknownQuestion {
+            step: "ask_name".into(),
+            question_id: "q1".into()
+        })
+    );
+}
+
+fn env_switch_flow() -> QAFlowSpec {
+    serde_json::from_value(json!({
+        "id": "deploy-flow",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "entry": "route",
+        "steps": {
+            "route": {
+                "type": "decision",
+                "on": { "op": "answer", "path": "env" },
+                "cases": [
+                    { "if": { "op": "literal", "value": "prod" }, "goto": "prod" },
+                    { "if": { "op": "literal", "value": "staging" }, "goto": "staging" }
+                ],
+                "default": "end"
+            },
+            "prod": { "type": "end" },
+            "staging": { "type": "end" },
+            "end": { "type": "end" }
+        }
+    }))
+    .expect("flow should deserialize")
+}
+
+#[test]
+fn decision_selector_matches_case_values() {
+    let flow = env_switch_flow();
+    let form = simple_form();
+    let ctx = json!({});
+
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert_eq!(
+        engine.advance(&json!({ "env": "staging" }), &ctx),
+        Ok(StepOutcome::Decided {
+            next: "staging".into()
+        })
+    );
+
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert_eq!(
+        engine.advance(&json!({ "env": "dev" }), &ctx),
+        Ok(StepOutcome::Decided { next: "end".into() })
+    );
+}
+
+fn deploy_message_flow(template: &str) -> QAFlowSpec {
+    serde_json::from_value(json!({
+        "id": "deploy-flow",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "entry": "confirm",
+        "steps": {
+            "confirm": {
+                "type": "message",
+                "mode": "text",
+                "template": template,
+                "next": "end"
+            },
+            "end": { "type": "end" }
+        }
+    }))
+    .expect("flow should deserialize")
+}
+
+#[test]
+fn message_templates_resolve_answers_and_ctx() {
+    let flow = deploy_message_flow(
+        "Great, we'll deploy {{answers.service}} to {{answers.region}} for {{config.tenant}}",
+    );
+    let form = simple_form();
+    let mut engine = FlowEngine::new(&flow, &form);
+    let outcome = engine.advance(
+        &json!({ "service": "billing", "region": "eu-west-1" }),
+        &json!({ "config": { "tenant": "acme" } }),
+    );
+    assert_eq!(
+        outcome,
+        Ok(StepOutcome::ShowMessage {
+            text: "Great, we'll deploy billing to eu-west-1 for acme".into()
+        })
+    );
+}
+
+#[test]
+fn unresolved_message_variables_follow_spec_mode() {
+    let flow = deploy_message_flow("Deploying {{answers.service}}");
+    let mut form = simple_form();
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert_eq!(
+        engine.advance(&json!({}), &json!({})),
+        Ok(StepOutcome::ShowMessage {
+            text: "Deploying {{answers.service}}".into()
+        })
+    );
+
+    form.presentation =
+        Some(serde_json::from_value(json!({ "template_mode": "strict" })).expect("presentation"));
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert!(matches!(
+        engine.advance(&json!({}), &json!({})),
+        Err(FlowError::Template { step, .. }) if step == "confirm"
+    ));
+}
+
+fn limited_flow(policy: serde_json::Value) -> QAFlowSpec {
+    let mut flow = graph_flow();
+    flow.policies = Some(serde_json::from_value(policy).expect("policy"));
+    flow
+}
+
+#[test]
+fn max_repeats_per_step_stops_decision_loops() {
+    let flow = limited_flow(json!({ "max_repeats_per_step": 2 }));
+    let form = simple_form();
+    let ctx = json!({});
+    let answers = json!({ "q1": false });
+    let mut engine = FlowEngine::new(&flow, &form);
+    engine.advance(&answers, &ctx).expect("message");
+    engine.advance(&answers, &ctx).expect("first decision");
+    engine.advance(&answers, &ctx).expect("second decision");
+
+    match engine.advance(&answers, &ctx) {
+        Ok(StepOutcome::LimitReached {
+            limit,
+            max,
+            step,
+            on_limit,
+            history,
+        }) => {
+            assert_eq!(limit, FlowLimit::MaxRepeatsPerStep);
+            assert_eq!(max, 2);
+            assert_eq!(step, "ask_name");
+            assert_eq!(on_limit, OnLimit::Error);
+            assert_eq!(
+                history,
+                vec![
+                    "start", "ask_name", "decision", "ask_name", "decision", "ask_name"
+                ]
+            );
+        }
+        other => panic!("expected limit outcome, got {:?}", other),
+    }
+    assert!(engine.state().completed);
+}
+
+#[test]
+fn max_steps_can_complete_partially() {
+    let flow = limited_flow(json!({ "max_steps": 3, "on_limit": "complete_partial" }));
+    let form = simple_form();
+    let ctx = json!({});
+    let answers = json!({ "q1": false });
+    let mut engine = FlowEngine::new(&flow, &form);
+    engine.advance(&answers, &ctx).expect("message");
+    engine.advance(&answers, &ctx).expect("decision");
+
+    assert!(matches!(
+        engine.advance(&answers, &ctx),
+        Ok(StepOutcome::LimitReached {
+            limit: FlowLimit::MaxSteps,
+            on_limit: OnLimit::CompletePartial,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn card_mode_follows_active_question_step() {
+    let mut flow = graph_flow();
+    if let Some(qa_spec::StepSpec::Question(step)) = flow.steps.get_mut("ask_name") {
+        step.card_mode = Some(CardMode::Section);
+    }
+    let form = simple_form();
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert_eq!(engine.card_mode(), CardMode::Card);
+
+    engine.advance(&json!({}), &json!({})).expect("message");
+    assert_eq!(engine.current_step(), Some("ask_name"));
+    assert_eq!(engine.card_mode(), CardMode::Section);
+}
+
+#[test]
+fn next_question_in_flow_runs_from_the_entry() {
+    let flow = graph_flow();
+    let form = simple_form();
+    let ctx = json!({});
+
+    let position = next_question_in_flow(&flow, &form, &ctx, &json!({})).expect("position");
+    assert_eq!(position.question_id(), Some("q1"));
+    assert_eq!(position.step_id.as_deref(), Some("ask_name"));
+    assert_eq!(position.messages, vec!["Welcome to the QA wizard!"]);
+
+    let position =
+        next_question_in_flow(&flow, &form, &ctx, &json!({ "q1": "yes" })).expect("position");
+    assert_eq!(position.outcome, StepOutcome::Completed);
+    assert_eq!(position.question_id(), None);
+    assert_eq!(position.step_id, None);
+}
+
+#[test]
+fn next_question_in_flow_stops_decision_loops() {
+    let form = simple_form();
+    let ctx = json!({});
+    let answers = json!({ "q1": false });
+
+    assert_eq!(
+        next_question_in_flow(&graph_flow(), &form, &ctx, &answers),
+        Err(FlowError::Cycle("ask_name".into()))
+    );
+
+    let flow = limited_flow(json!({ "max_repeats_per_step": 2 }));
+    let position = next_question_in_flow(&flow, &form, &ctx, &answers).expect("position");
+    assert_eq!(position.step_id.as_deref(), Some("ask_name"));
+    assert!(matches!(
+        position.outcome,
+        StepOutcome::LimitReached {
+            limit: FlowLimit::MaxRepeatsPerStep,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn questions_hidden_by_ctx_roots_are_passed() {
+    let form = FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true },
+            {
+                "id": "secret_ref",
+                "type": "string",
+                "title": "Secret reference",
+                "visible_if": { "op": "var", "path": "config.secrets_host_available" }
+            }
+        ]
+    }))
+    .expect("form");
+    let flow: QAFlowSpec = serde_json::from_value(json!({
+        "id": "deploy-flow",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "entry": "ask_name",
+        "steps": {
+            "ask_name": { "type": "question", "question_id": "name", "next": "ask_secret" },
+            "ask_secret": { "type": "question", "question_id": "secret_ref", "next": "route" },
+            "route": {
+                "type": "decision",
+                "cases": [
+                    {
+                        "if": { "op": "var", "path": "config.secrets_host_available" },
+                        "goto": "with_secrets"
+                    }
+                ],
+                "default": "without_secrets"
+            },
+            "with_secrets": { "type": "end" },
+            "without_secrets": { "type": "end" }
+        }
+    }))
+    .expect("flow");
+    let answers = json!({ "name": "api" });
+
+    let hidden = json!({ "config": { "secrets_host_available": false } });
+    let position = next_question_in_flow(&flow, &form, &hidden, &answers).expect("position");
+    assert_eq!(position.outcome, StepOutcome::Completed);
+    let mut engine = FlowEngine::new(&flow, &form);
+    assert_eq!(
+        engine.advance(&answers, &hidden),
+        Ok(StepOutcome::Decided {
+            next: "without_secrets".into()
+        })
+    );
+
+    let shown = json!({ "config": { "secrets_host_available": true } });
+    let position = next_question_in_flow(&flow, &form, &shown, &answers).expect("position");
+    assert_eq!(position.question_id(), Some("secret_ref"));
+}
@@ -0,0 +1,257 @@
+use serde_json::json;
+
+use qa_spec::format::{FormatProblem, check_url, is_e164, is_email, is_uuid};
+use qa_spec::spec::StringFormat;
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_text,
+    validate,
+};
+
+fn contact_form() -> FormSpec {
+    form_with_format("email", "email")
+}
+
+fn form_with_format(id: &str, format: &str) -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "contact",
+        "title": "Contact",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": id,
+                "type": "string",
+                "title": "Contact",
+                "required": true,
+                "constraint": { "format": format }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn email_addresses_are_recognised() {
+    for good in [
+        "user@example.com",
+        "first.last+tag@mail.example.co",
+        "o'brien@example-mail.org",
+    ] {
+        assert!(is_email(good), "{good}");
+    }
+    for bad in [
+        "",
+        "user",
+        "user@",
+        "@example.com",
+        "user@example",
+        "user@@example.com",
+        "user@exa mple.com",
+        ".user@example.com",
+        "us..er@example.com",
+        "user@-example.com",
+        "user@example.c",
+        "user@example.123",
+    ] {
+        assert!(!is_email(bad), "{bad}");
+    }
+}
+
+#[test]
+fn format_round_trips_through_the_spec() {
+    let spec = contact_form();
+    let constraint = spec.questions[0].constraint.as_ref().expect("constraint");
+    assert_eq!(constraint.format, Some(StringFormat::Email));
+    let value = serde_json::to_value(&spec).expect("serialize");
+    assert_eq!(
+        value["questions"][0]["constraint"],
+        json!({ "format": "email" })
+    );
+}
+
+#[test]
+fn validation_rejects_invalid_emails() {
+    let spec = contact_form();
+
+    assert!(validate(&spec, &json!({ "email": "ops@example.com" })).valid);
+    let result = validate(&spec, &json!({ "email": "not-an-email" }));
+    assert!(!result.valid);
+    assert_eq!(result.errors[0].code.as_deref(), Some("format_email"));
+    assert_eq!(result.errors[0].message, "qa_spec.format_email");
+    assert_eq!(result.errors[0].params["format"], "email");
+}
+
+#[test]
+fn schema_examples_and_cards_carry_the_format() {
+    let spec = contact_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(schema["properties"]["email"]["format"], "email");
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["email"], "user@example.com");
+    assert!(validate(&spec, &example).valid);
+
+    let card = render_card(&build_render_payload(&spec, &json!({}), &json!({})));
+    let rendered = card.to_string();
+    assert!(rendered.contains(r#""style":"Email""#), "{rendered}");
+}
+
+#[test]
+fn urls_report_the_part_that_failed() {
+    assert_eq!(check_url("https://example.com"), Ok(()));
+    assert_eq!(check_url("http://localhost:8080/health?verbose=1"), Ok(()));
+    assert_eq!(
+        check_url("example.com/path"),
+        Err(FormatProblem::MissingScheme)
+    );
+    assert_eq!(check_url("https://"), Err(FormatProblem::InvalidHost));
+    assert_eq!(
+        check_url("https://exa mple.com"),
+        Err(FormatProblem::InvalidHost)
+    );
+    assert_eq!(
+        check_url("https://example.com:99999"),
+        Err(FormatProblem::InvalidHost)
+    );
+    assert_eq!(
+        check_url("mailto:ops@example.com"),
+        Err(FormatProblem::InvalidHost)
+    );
+}
+
+#[test]
+fn url_validation_names_the_problem() {
+    let spec = form_with_format("homepage", "url");
+
+    assert!(validate(&spec, &json!({ "homepage": "https://example.com/docs" })).valid);
+    for (value, problem) in [
+        ("example.com", "missing_scheme"),
+        ("https://exa mple.com", "invalid_host"),
+    ] {
+        let result = validate(&spec, &json!({ "homepage": value }));
+        assert!(!result.valid, "{value}");
+        assert_eq!(result.errors[0].code.as_deref(), Some("format_url"));
+        assert_eq!(result.errors[0].message, "qa_spec.format_url");
+        assert_eq!(result.errors[0].params["format"], "url");
+        assert_eq!(result.errors[0].params["problem"], problem, "{value}");
+    }
+}
+
+#[test]
+fn url_format_reaches_schema_examples_and_renderers() {
+    let spec = form_with_format("homepage", "url");
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(schema["properties"]["homepage"]["format"], "uri");
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["homepage"], "https://example.com");
+    assert!(validate(&spec, &example).valid);
+
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+    let card = render_card(&payload);
+    let rendered = card.to_string();
+    assert!(
+        rendered.contains(r#""placeholder":"https://…""#),
+        "{rendered}"
+    );
+
+    let text = render_text(&payload);
+    assert!(text.contains("  Format: url"), "{text}");
+}
+
+#[test]
+fn uuids_must_be_hyphenated() {
+    assert!(is_uuid("123e4567-e89b-12d3-a456-426614174000"));
+    assert!(is_uuid("00000000-0000-0000-0000-000000000000"));
+    assert!(is_uuid("67E55044-10B1-426F-9247-BB680E5FE0C8"));
+    for bad in [
+        "",
+        "123e4567e89b12d3a456426614174000",
+        "{123e4567-e89b-12d3-a456-426614174000}",
+        "urn:uuid:123e4567-e89b-12d3-a456-426614174000",
+        "123e4567-e89b-12d3-a456-42661417400g",
+        "123e4567-e89b-12d3-a456-4266141740",
+    ] {
+        assert!(!is_uuid(bad), "{bad}");
+    }
+}
+
+#[test]
+fn uuid_format_is_validated_and_described() {
+    let spec = form_with_format("tenant_id", "uuid");
+
+    let result = validate(&spec, &json!({ "tenant_id": "tenant-42" }));
+    assert!(!result.valid);
+    assert_eq!(result.errors[0].code.as_deref(), Some("format_uuid"));
+    assert_eq!(result.errors[0].message, "qa_spec.format_uuid");
+    assert_eq!(result.errors[0].params["format"], "uuid");
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(schema["properties"]["tenant_id"]["format"], "uuid");
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example, example_answers(&spec, &Default::default()));
+    assert_eq!(example["tenant_id"], "123e4567-e89b-12d3-a456-426614174000");
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn phone_numbers_follow_e164() {
+    assert!(is_e164("+15551234567"));
+    assert!(is_e164("+4930123456"));
+    assert!(is_e164("+12345678"));
+    for bad in [
+        "",
+        "15551234567",
+        "+1555123",
+        "+0551234567",
+        "+1 555 123 4567",
+        "+1-555-123-4567",
+        "+1234567890123456",
+    ] {
+        assert!(!is_e164(bad), "{bad}");
+    }
+}
+
+#[test]
+fn phone_format_is_validated_and_described() {
+    let spec = form_with_format("mobile", "phone");
+
+    let result = validate(&spec, &json!({ "mobile": "555-1234" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("format_phone"));
+    assert_eq!(result.errors[0].params["format"], "phone");
+
+    let schema = answers_schema(&spec, &Default::default());
+    let mobile = &schema["properties"]["mobile"];
+    assert_eq!(mobile["x-format"], "phone");
+    assert!(mobile.get("format").is_none());
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["mobile"], "+15551234567");
+    assert!(validate(&spec, &example).valid);
+
+    let card = render_card(&build_render_payload(&spec, &json!({}), &json!({}))).to_string();
+    assert!(card.contains(r#""style":"Tel""#), "{card}");
+}
+
+#[test]
+fn phone_pattern_replaces_the_e164_check() {
+    let spec = FormSpec::from_value(json!({
+        "id": "contact",
+        "title": "Contact",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "mobile",
+            "type": "string",
+            "title": "Mobile",
+            "required": true,
+            "constraint": { "format": "phone", "pattern": "^0[0-9]{9}$" }
+        }]
+    }))
+    .expect("spec");
+
+    assert!(validate(&spec, &json!({ "mobile": "0612345678" })).valid);
+    let result = validate(&spec, &json!({ "mobile": "+15551234567" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("pattern_mismatch"));
+}
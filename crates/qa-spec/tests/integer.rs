@@ -0,0 +1,99 @@
+use serde_json::{Value, json};
+
+use qa_spec::{FormSpec, apply_normalization, validate};
+
+fn counter_form(constraint: Value) -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "counter",
+        "title": "Counter",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "count",
+            "type": "integer",
+            "title": "Count",
+            "constraint": constraint
+        }]
+    }))
+    .expect("spec")
+}
+
+fn first_code(spec: &FormSpec, answers: Value) -> Option<String> {
+    validate(spec, &answers)
+        .errors
+        .first()
+        .and_then(|error| error.code.clone())
+}
+
+#[test]
+fn unsigned_and_negative_integers_are_accepted() {
+    let spec = counter_form(json!({}));
+
+    assert!(validate(&spec, &json!({ "count": u64::MAX })).valid);
+    assert!(validate(&spec, &json!({ "count": i64::MIN })).valid);
+    assert!(validate(&spec, &json!({ "count": -7 })).valid);
+    assert!(validate(&spec, &json!({ "count": 9_007_199_254_740_993_u64 })).valid);
+}
+
+#[test]
+fn whole_floats_are_normalized_to_integers() {
+    let spec = counter_form(json!({}));
+
+    assert!(validate(&spec, &json!({ "count": 42.0 })).valid);
+    assert!(validate(&spec, &json!({ "count": -3.0 })).valid);
+    let normalized = apply_normalization(&spec, &json!({ "count": 42.0 }));
+    assert!(normalized["count"].is_i64());
+    assert_eq!(normalized["count"], 42);
+
+    assert_eq!(
+        first_code(&spec, json!({ "count": 4.5 })).as_deref(),
+        Some("type_mismatch")
+    );
+}
+
+#[test]
+fn whole_floats_beyond_two_pow_53_are_out_of_range() {
+    let spec = counter_form(json!({}));
+
+    assert_eq!(
+        first_code(&spec, json!({ "count": 1e20 })).as_deref(),
+        Some("integer_out_of_range")
+    );
+    assert_eq!(
+        first_code(&spec, json!({ "count": 18_014_398_509_481_984.0_f64 })).as_deref(),
+        Some("integer_out_of_range")
+    );
+}
+
+#[test]
+fn bounds_compare_large_integers_exactly() {
+    let two_pow_53 = 9_007_199_254_740_992_u64;
+    let spec = counter_form(json!({ "max": two_pow_53 as f64 }));
+
+    assert!(validate(&spec, &json!({ "count": two_pow_53 })).valid);
+    assert_eq!(
+        first_code(&spec, json!({ "count": two_pow_53 + 1 })).as_deref(),
+        Some("max")
+    );
+    assert_eq!(
+        first_code(&spec, json!({ "count": u64::MAX })).as_deref(),
+        Some("max")
+    );
+
+    let spec = counter_form(json!({ "min": -10, "exclusive_min": true }));
+    assert!(validate(&spec, &json!({ "count": -9 })).valid);
+    assert_eq!(
+        first_code(&spec, json!({ "count": -10 })).as_deref(),
+        Some("exclusive_min")
+    );
+    assert_eq!(
+        first_code(&spec, json!({ "count": i64::MIN })).as_deref(),
+        Some("exclusive_min")
+    );
+
+    let spec = counter_form(json!({ "min": 0.5 }));
+    assert_eq!(
+        first_code(&spec, json!({ "count": 0 })).as_deref(),
+        Some("min")
+    );
+    assert!(validate(&spec, &json!({ "count": 1 })).valid);
+}
@@ -0,0 +1,188 @@
+use serde_json::{Map, Value, json};
+
+use qa_spec::{
+    DEFAULT_MAX_EXPR_DEPTH, EvaluationFailure, Expr, FormSpec, SpecLimits, SpecParseError,
+    lint_form, validate,
+};
+
+const PATHOLOGICAL_DEPTH: usize = 10_000;
+
+fn not_chain_value(depth: usize) -> Value {
+    let mut expr = json!({ "op": "answer", "path": "enabled" });
+    for _ in 1..depth {
+        let mut not = Map::new();
+        not.insert("op".into(), json!("not"));
+        not.insert("expression".into(), expr);
+        expr = Value::Object(not);
+    }
+    expr
+}
+
+fn not_chain(depth: usize) -> Expr {
+    let mut expr = Expr::Answer {
+        path: "enabled".into(),
+    };
+    for _ in 1..depth {
+        expr = Expr::Not {
+            expression: Box::new(expr),
+        };
+    }
+    expr
+}
+
+/// Drops `value` without recursing through its nesting, which would overflow
+/// the test thread's stack.
+fn drop_flat(value: Value) {
+    let mut pending = vec![value];
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::Array(items) => pending.extend(items),
+            Value::Object(map) => pending.extend(map.into_iter().map(|(_, item)| item)),
+            _ => {}
+        }
+    }
+}
+
+fn form_with_visible_if(visible_if: Value) -> Value {
+    let mut form = json!({
+        "id": "deep",
+        "title": "Deep",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "enabled", "type": "boolean", "title": "Enabled", "required": true },
+            { "id": "name", "type": "string", "title": "Name", "required": false }
+        ]
+    });
+    form["questions"][1]["visible_if"] = visible_if;
+    form
+}
+
+#[test]
+fn pathological_expressions_are_rejected_when_parsed() {
+    let value = form_with_visible_if(not_chain_value(PATHOLOGICAL_DEPTH));
+
+    let err = FormSpec::from_value(value).expect_err("too deep");
+    assert!(matches!(err, SpecParseError::TooDeep { .. }), "{err}");
+    assert!(
+        err.pointer()
+            .expect("pointer")
+            .starts_with("/questions/1/visible_if/expression"),
+        "{err}"
+    );
+
+    let raw = format!(
+        r#"{{"id":"deep","title":"Deep","version":"1.0.0","questions":[{{"id":"name","type":"string","title":"Name","visible_if":{}{{"op":"answer","path":"enabled"}}{}}}]}}"#,
+        r#"{"op":"not","expression":"#.repeat(PATHOLOGICAL_DEPTH),
+        "}".repeat(PATHOLOGICAL_DEPTH)
+    );
+    let err = FormSpec::from_json_str(&raw).expect_err("too deep");
+    assert!(matches!(err, SpecParseError::Syntax { .. }), "{err}");
+}
+
+#[test]
+fn expressions_past_the_limit_name_their_location() {
+    let value = form_with_visible_if(not_chain_value(DEFAULT_MAX_EXPR_DEPTH + 1));
+
+    let err = FormSpec::from_value(value.clone()).expect_err("too deep");
+    match &err {
+        SpecParseError::ExpressionTooDeep {
+            pointer,
+            depth,
+            limit,
+        } => {
+            assert_eq!(pointer, "/questions/1/visible_if");
+            assert_eq!(*depth, DEFAULT_MAX_EXPR_DEPTH + 1);
+            assert_eq!(*limit, DEFAULT_MAX_EXPR_DEPTH);
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let relaxed = SpecLimits {
+        max_expr_depth: DEFAULT_MAX_EXPR_DEPTH + 1,
+        ..SpecLimits::default()
+    };
+    assert!(FormSpec::from_value_with_limits(value, &relaxed).is_ok());
+    assert!(
+        FormSpec::from_value(form_with_visible_if(not_chain_value(
+            DEFAULT_MAX_EXPR_DEPTH
+        )))
+        .is_ok()
+    );
+    assert!(FormSpec::from_value(form_with_visible_if(not_chain_value(64))).is_ok());
+    assert!(FormSpec::from_value(form_with_visible_if(not_chain_value(65))).is_err());
+}
+
+#[test]
+fn evaluation_stops_at_the_depth_limit() {
+    let ctx = json!({ "answers": { "enabled": true } });
+
+    let deep = not_chain(PATHOLOGICAL_DEPTH);
+    assert_eq!(deep.depth(), PATHOLOGICAL_DEPTH);
+    assert_eq!(deep.evaluate_bool(&ctx), None);
+    assert_eq!(deep.referenced_answers(), vec!["enabled".to_string()]);
+
+    let at_limit = not_chain(64);
+    assert_eq!(DEFAULT_MAX_EXPR_DEPTH, 64);
+    assert_eq!(at_limit.evaluate_bool(&ctx), Some(false));
+    assert!(at_limit.evaluate_value(&ctx).is_some());
+    let past_limit = not_chain(65);
+    assert_eq!(past_limit.evaluate_bool(&ctx), None);
+    assert_eq!(past_limit.evaluate_value(&ctx), None);
+
+    let shallow = not_chain(3);
+    assert_eq!(shallow.evaluate_bool(&ctx), Some(true));
+    assert_eq!(shallow.evaluate_bool_within(&ctx, 2), None);
+    assert_eq!(shallow.evaluate_bool_within(&ctx, 3), Some(true));
+}
+
+#[test]
+fn over_deep_chains_explain_why_they_stop() {
+    let ctx = json!({ "answers": { "enabled": true } });
+    let deep = not_chain(1_000);
+    assert_eq!(deep.evaluate_bool(&ctx), None);
+    assert_eq!(deep.evaluate_value(&ctx), None);
+    let (failure, at) = deep.explain_bool_failure(&ctx).expect("diagnostic");
+    assert_eq!(failure, EvaluationFailure::TooDeep);
+    assert_eq!(at, &deep);
+}
+
+#[test]
+fn lint_reports_over_deep_expressions() {
+    let mut spec: FormSpec = FormSpec::from_value(form_with_visible_if(
+        json!({ "op": "answer", "path": "enabled" }),
+    ))
+    .expect("spec");
+    spec.questions[1].visible_if = Some(not_chain(DEFAULT_MAX_EXPR_DEPTH + 5));
+
+    let issues = lint_form(&spec);
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].code, "expression_too_deep");
+    assert_eq!(issues[0].path, "/questions/1/visible_if");
+}
+
+#[test]
+fn deeply_nested_answers_fail_validation() {
+    let spec = FormSpec::from_value(form_with_visible_if(
+        json!({ "op": "answer", "path": "enabled" }),
+    ))
+    .expect("spec");
+    let mut nested = json!("leaf");
+    for _ in 0..PATHOLOGICAL_DEPTH {
+        nested = Value::Array(vec![nested]);
+    }
+    let mut answers = json!({ "enabled": true });
+    answers["name"] = nested;
+
+    let result = validate(&spec, &answers);
+    assert!(!result.valid);
+    assert_eq!(result.errors.len(), 1, "{result:?}");
+    assert_eq!(result.errors[0].code.as_deref(), Some("too_deep"));
+    assert_eq!(result.errors[0].question_id.as_deref(), Some("name"));
+    assert_eq!(result.errors[0].params["limit"], "128");
+    assert_eq!(
+        spec.compile().validate(&answers).errors,
+        result.errors,
+        "compiled validation applies the same guard"
+    );
+    drop_flat(answers);
+}
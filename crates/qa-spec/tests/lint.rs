@@ -0,0 +1,420 @@
+use serde_json::json;
+
+use qa_spec::{FormSpec, LintSeverity, QAFlowSpec, has_errors, lint_flow, lint_form};
+
+fn graph_flow() -> QAFlowSpec {
+    serde_json::from_str(include_str!("fixtures/graph_flow.json")).expect("flow fixture")
+}
+
+fn env_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "env",
+                "type": "enum",
+                "title": "Environment",
+                "required": true,
+                "choices": ["prod", "staging"]
+            }
+        ]
+    }))
+    .expect("form should deserialize")
+}
+
+fn switch_flow(cases: serde_json::Value) -> QAFlowSpec {
+    serde_json::from_value(json!({
+        "id": "deploy-flow",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "entry": "route",
+        "steps": {
+            "route": {
+                "type": "decision",
+                "on": { "op": "answer", "path": "env" },
+                "cases": cases
+            },
+            "prod": { "type": "end" },
+            "staging": { "type": "end" }
+        }
+    }))
+    .expect("flow should deserialize")
+}
+
+#[test]
+fn legacy_default_goto_is_accepted() {
+    let flow = graph_flow();
+    assert!(!has_errors(&lint_flow(&flow, None)));
+}
+
+#[test]
+fn decision_without_default_is_an_error() {
+    let mut flow = graph_flow();
+    if let qa_spec::StepSpec::Decision(decision) = flow.steps.get_mut("decision").unwrap() {
+        decision.default = None;
+    }
+    let issues = lint_flow(&flow, None);
+    assert!(has_errors(&issues));
+    assert_eq!(issues[0].code, "decision_without_default");
+    assert_eq!(issues[0].path, "/steps/decision");
+}
+
+#[test]
+fn enum_switch_covering_every_choice_is_exhaustive() {
+    let form = env_form();
+    let flow = switch_flow(json!([
+        { "if": { "op": "literal", "value": "prod" }, "goto": "prod" },
+        { "if": { "op": "literal", "value": "staging" }, "goto": "staging" }
+    ]));
+    assert!(lint_flow(&flow, Some(&form)).is_empty());
+
+    let partial = switch_flow(json!([
+        { "if": { "op": "literal", "value": "prod" }, "goto": "prod" }
+    ]));
+    let issues = lint_flow(&partial, Some(&form));
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "decision_without_default");
+}
+
+#[test]
+fn every_referenced_target_is_verified() {
+    let form = env_form();
+    let flow = switch_flow(json!([
+        { "if": { "op": "literal", "value": "prod" }, "goto": "prod" },
+        { "if": { "op": "literal", "value": "staging" }, "goto": "qa" }
+    ]));
+    let issues = lint_flow(&flow, Some(&form));
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, LintSeverity::Error);
+    assert_eq!(issues[0].code, "unknown_step_target");
+
+    let mut flow = graph_flow();
+    flow.entry = "missing".into();
+    let codes = lint_flow(&flow, Some(&form))
+        .into_iter()
+        .map(|issue| issue.code)
+        .collect::<Vec<_>>();
+    assert!(codes.contains(&"unknown_entry".to_string()));
+    assert!(codes.contains(&"unknown_question".to_string()));
+}
+
+#[test]
+fn message_step_templates_are_checked() {
+    let mut flow = graph_flow();
+    if let qa_spec::StepSpec::Message(message) = flow.steps.get_mut("start").unwrap() {
+        message.template = "Hello {{#if answers.q1}}".into();
+    }
+    let issues = lint_flow(&flow, None)
+        .into_iter()
+        .filter(|issue| issue.severity == LintSeverity::Error)
+        .collect::<Vec<_>>();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "invalid_template");
+    assert_eq!(issues[0].path, "/steps/start/template");
+}
+
+#[test]
+fn cycles_without_limits_warn() {
+    let mut flow = graph_flow();
+    let issues = lint_flow(&flow, None);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, LintSeverity::Warning);
+    assert_eq!(issues[0].code, "cycle_without_limits");
+
+    flow.policies =
+        Some(serde_json::from_value(json!({ "max_repeats_per_step": 3 })).expect("policy"));
+    assert!(lint_flow(&flow, None).is_empty());
+}
+
+fn secret_form(question: serde_json::Value, allow: &str) -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "provider",
+        "title": "Provider",
+        "version": "1.0.0",
+        "secrets_policy": {
+            "enabled": true,
+            "read_enabled": true,
+            "write_enabled": true,
+            "allow": [allow]
+        },
+        "questions": [question]
+    }))
+    .expect("form")
+}
+
+#[test]
+fn secret_path_requires_secret_flag() {
+    let spec = secret_form(
+        json!({ "id": "api_key", "type": "string", "title": "Key", "secret_path": "aws/api_key" }),
+        "aws/*",
+    );
+    let issues = lint_form(&spec);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "secret_path_without_secret");
+    assert_eq!(issues[0].path, "/questions/0/secret_path");
+}
+
+#[test]
+fn secret_path_is_checked_against_policy() {
+    let question = json!({
+        "id": "api_key",
+        "type": "string",
+        "title": "Key",
+        "secret": true,
+        "secret_path": "aws/api_key"
+    });
+    assert!(lint_form(&secret_form(question.clone(), "aws/*")).is_empty());
+
+    let issues = lint_form(&secret_form(question, "gcp/*"));
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "secret_path_denied");
+}
+
+fn bounded_form(kind: &str, constraint: serde_json::Value) -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "limits",
+        "title": "Limits",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "replicas", "type": kind, "title": "Replicas", "constraint": constraint }
+        ]
+    }))
+    .expect("form should deserialize")
+}
+
+#[test]
+fn constraint_bounds_are_linted() {
+    let ok = bounded_form(
+        "integer",
+        json!({ "min": 0, "exclusive_min": true, "max": 5 }),
+    );
+    assert!(lint_form(&ok).is_empty());
+
+    let empty = bounded_form(
+        "number",
+        json!({ "min": 1, "max": 1, "exclusive_max": true }),
+    );
+    let issues = lint_form(&empty);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "constraint_empty_range");
+    assert_eq!(issues[0].path, "/questions/0/constraint");
+
+    let fractional = bounded_form("integer", json!({ "min": 0.5 }));
+    let issues = lint_form(&fractional);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "fractional_integer_bound");
+    assert_eq!(issues[0].path, "/questions/0/constraint/min");
+    assert!(lint_form(&bounded_form("number", json!({ "min": 0.5 }))).is_empty());
+}
+
+#[test]
+fn unique_by_must_name_list_fields() {
+    let form = FormSpec::from_value(json!({
+        "id": "envs",
+        "title": "Environments",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "environments",
+            "type": "list",
+            "title": "Environments",
+            "list": {
+                "unique_by": ["name", "region"],
+                "fields": [{ "id": "name", "type": "string", "title": "Name" }]
+            }
+        }]
+    }))
+    .expect("form should deserialize");
+
+    let issues = lint_form(&form);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "unique_by_unknown_field");
+    assert_eq!(issues[0].path, "/questions/0/list/unique_by/1");
+}
+
+#[test]
+fn patterns_that_do_not_compile_are_linted() {
+    let issues = lint_form(&bounded_form("string", json!({ "pattern": "([a-z]" })));
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "invalid_pattern");
+    assert_eq!(issues[0].path, "/questions/0/constraint/pattern");
+
+    assert!(lint_form(&bounded_form("string", json!({ "pattern": "^[a-z]+$" }))).is_empty());
+}
+
+#[test]
+fn expression_patterns_that_do_not_compile_are_linted() {
+    let form = FormSpec::from_value(json!({
+        "id": "storage",
+        "title": "Storage",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "url", "type": "string", "title": "URL" },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "visible_if": {
+                    "op": "not",
+                    "expression": {
+                        "op": "matches",
+                        "expression": { "op": "answer", "path": "url" },
+                        "pattern": "^(s3"
+                    }
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+    let issues = lint_form(&form);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, "invalid_pattern");
+    assert_eq!(issues[0].path, "/questions/1/visible_if");
+    assert!(
+        issues[0].message.contains("'^(s3'"),
+        "{}",
+        issues[0].message
+    );
+}
+
+#[test]
+fn analyze_reports_mistakes_in_the_fixture() {
+    let spec = FormSpec::from_json_str(include_str!("fixtures/mistakes_form.json"))
+        .expect("fixture parses");
+
+    let issues = spec
+        .analyze()
+        .into_iter()
+        .map(|issue| (issue.severity, issue.code, issue.path))
+        .collect::<Vec<_>>();
+    let expected = [
+        (
+            LintSeverity::Error,
+            "default_not_in_choices",
+            "/questions/0/default_value",
+        ),
+        (
+            LintSeverity::Error,
+            "default_not_in_choices",
+            "/questions/1/default_value",
+        ),
+        (
+            LintSeverity::Error,
+            "unknown_reference",
+            "/questions/2/visible_if",
+        ),
+        (
+            LintSeverity::Warning,
+            "unreachable_question",
+            "/questions/3/visible_if",
+        ),
+        (
+            LintSeverity::Error,
+            "unknown_reference",
+            "/validations/0/condition",
+        ),
+        (LintSeverity::Error, "unknown_reference", "/store/0/path"),
+    ]
+    .map(|(severity, code, path)| (severity, code.to_string(), path.to_string()));
+    assert_eq!(issues, expected);
+
+    let messages = spec
+        .analyze()
+        .into_iter()
+        .map(|issue| issue.message)
+        .collect::<Vec<_>>();
+    assert!(messages[1].contains("'eu-central-1'"), "{messages:?}");
+    assert!(messages[2].contains("'enviroment'"), "{messages:?}");
+
+    // lint_form includes the analysis.
+    let linted = lint_form(&spec);
+    assert!(has_errors(&linted));
+    assert!(
+        linted
+            .iter()
+            .any(|issue| issue.code == "unreachable_question")
+    );
+}
+
+#[test]
+fn analyze_accepts_valid_references() {
+    let mut spec = env_form();
+    assert!(spec.analyze().is_empty());
+
+    spec.questions[0].default_value = Some("staging".into());
+    spec.questions.push(
+        serde_json::from_value(json!({
+            "id": "approver",
+            "type": "string",
+            "title": "Approver",
+            "visible_if": {
+                "op": "and",
+                "expressions": [
+                    { "op": "eq", "left": { "op": "var", "path": "/answers/env" }, "right": { "op": "literal", "value": "prod" } },
+                    { "op": "var", "path": "state.approvals_enabled" },
+                    { "op": "literal", "value": true }
+                ]
+            }
+        }))
+        .expect("question"),
+    );
+    assert!(spec.analyze().is_empty(), "{:?}", spec.analyze());
+}
+
+#[test]
+fn analyze_reports_computed_cycles_with_their_path() {
+    let mut spec = env_form();
+    for (id, reads) in [("total", "subtotal"), ("subtotal", "total")] {
+        spec.questions.push(
+            serde_json::from_value(json!({
+                "id": id,
+                "type": "number",
+                "title": id,
+                "computed": { "op": "answer", "path": reads }
+            }))
+            .expect("question"),
+        );
+    }
+
+    let issues = spec.analyze();
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].severity, LintSeverity::Error);
+    assert_eq!(issues[0].code, "computed_cycle");
+    assert_eq!(issues[0].path, "/questions/1/computed");
+    assert!(
+        issues[0].message.ends_with("total -> subtotal -> total"),
+        "{}",
+        issues[0].message
+    );
+
+    // Evaluation skips the cycle instead of looping.
+    let (answers, _) = qa_spec::compute_answers(&spec, &json!({}), &json!({}));
+    assert!(answers.get("total").is_none());
+}
+
+#[test]
+fn analyze_warns_about_shared_orders() {
+    let mut spec = env_form();
+    spec.questions[0].order = Some(1);
+    for id in ["name", "region"] {
+        spec.questions.push(
+            serde_json::from_value(json!({ "id": id, "type": "string", "title": id, "order": 1 }))
+                .expect("question"),
+        );
+    }
+
+    let issues = spec.analyze();
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert!(
+        issues
+            .iter()
+            .all(|issue| issue.severity == LintSeverity::Warning && issue.code == "duplicate_order")
+    );
+    assert_eq!(issues[0].path, "/questions/1/order");
+    assert_eq!(
+        issues[1].message,
+        "question 'region' has order 1, like 'env'; they are asked in spec order"
+    );
+    assert!(!has_errors(&lint_form(&spec)));
+}
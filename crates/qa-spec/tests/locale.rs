@@ -0,0 +1,130 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, build_render_payload, build_render_payload_in_locale, render_card, render_json_ui,
+    render_markdown, render_text,
+};
+
+fn bilingual_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "signup",
+        "title": "Signup",
+        "version": "1.0.0",
+        "description": "Create your account.",
+        "i18n": {
+            "id": { "title": "Pendaftaran", "help": "Buat akun Anda." }
+        },
+        "questions": [
+            {
+                "id": "plan",
+                "type": "enum",
+                "title": "Plan",
+                "description": "Pick a plan.",
+                "required": true,
+                "choices": ["free", "pro"],
+                "i18n": {
+                    "id": {
+                        "title": "Paket",
+                        "description": "Pilih paket.",
+                        "choices": { "free": "Gratis" }
+                    }
+                }
+            },
+            { "id": "team", "type": "string", "title": "Team" }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn the_same_spec_renders_in_english_and_indonesian() {
+    let spec = bilingual_form();
+
+    let english = build_render_payload(&spec, &json!({}), &json!({}));
+    assert_eq!(
+        render_text(&english),
+        [
+            "Form: Signup (signup)",
+            "Status: need_input (0/2)",
+            "Help: Create your account.",
+            "Next question: plan",
+            "  Title: Plan",
+            "  Description: Pick a plan.",
+            "  Required: yes",
+            "Visible questions:",
+            " - plan (Plan) [required]",
+            " - team (Team)",
+        ]
+        .join("\n")
+    );
+
+    let indonesian = build_render_payload(&spec, &json!({ "locale": "id-ID" }), &json!({}));
+    assert_eq!(
+        render_text(&indonesian),
+        [
+            "Formulir: Pendaftaran (signup)",
+            "Status: need_input (0/2)",
+            "Bantuan: Buat akun Anda.",
+            "Pertanyaan berikutnya: plan",
+            "  Judul: Paket",
+            "  Deskripsi: Pilih paket.",
+            "  Wajib: ya",
+            "Pertanyaan yang terlihat:",
+            " - plan (Paket) [wajib]",
+            " - team (Team)",
+        ]
+        .join("\n")
+    );
+
+    let card = render_card(&indonesian);
+    assert_eq!(card["body"][0]["text"], "Pendaftaran");
+    assert_eq!(card["body"][2]["facts"][0]["title"], "Terjawab");
+    assert_eq!(
+        card["body"][3]["items"][2]["choices"],
+        json!([
+            { "title": "Gratis", "value": "free" },
+            { "title": "pro", "value": "pro" }
+        ])
+    );
+    assert_eq!(card["actions"][0]["title"], "Lanjut ➡️");
+    assert_eq!(render_card(&english)["actions"][0]["title"], "Next ➡️");
+
+    let ui = render_json_ui(&indonesian);
+    assert_eq!(ui["questions"][0]["title"], "Paket");
+    assert_eq!(
+        ui["questions"][0]["choice_labels"],
+        json!({ "free": "Gratis" })
+    );
+    assert!(
+        render_json_ui(&english)["questions"][0]
+            .get("choice_labels")
+            .is_none()
+    );
+
+    let markdown = render_markdown(&indonesian);
+    assert!(markdown.starts_with("## Pendaftaran\n\n**Kemajuan:** 0 dari 2 terjawab (0%)"));
+    assert!(
+        markdown.contains("> - Pilihan: Gratis (`free`), `pro`"),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn locales_without_strings_fall_back_to_the_defaults() {
+    let spec = bilingual_form();
+
+    let payload =
+        build_render_payload_in_locale(&spec, &json!({ "locale": "id" }), &json!({}), "fr");
+    assert_eq!(payload.locale.as_deref(), Some("fr"));
+    assert_eq!(payload.form_title, "Signup");
+    assert_eq!(payload.questions[0].title, "Plan");
+    assert!(payload.questions[0].choice_labels.is_empty());
+    assert!(render_text(&payload).starts_with("Form: Signup (signup)"));
+
+    let payload = build_render_payload_in_locale(&spec, &json!({}), &json!({}), "id");
+    assert_eq!(payload.questions[1].title, "Team");
+    assert_eq!(
+        payload.questions[0].description.as_deref(),
+        Some("Pilih paket.")
+    );
+}
@@ -0,0 +1,96 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_json_ui,
+    render_text, validate,
+};
+
+fn release_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "release",
+        "title": "Release",
+        "version": "1.0.0",
+        "progress_policy": { "skip_answered": false },
+        "questions": [{
+            "id": "notes",
+            "type": "markdown",
+            "title": "Release notes",
+            "required": true,
+            "constraint": { "min_len": 10, "max_len": 400 }
+        }]
+    }))
+    .expect("spec")
+}
+
+const NOTES: &str = "## Fixes\n\n- Faster startup when the cache is cold\n- Clearer errors for \
+                     invalid specs\n- Smaller bundles";
+
+#[test]
+fn validation_checks_type_and_length() {
+    let spec = release_form();
+
+    assert!(validate(&spec, &json!({ "notes": NOTES })).valid);
+
+    let result = validate(&spec, &json!({ "notes": "# Hi" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("min_length"));
+
+    let result = validate(&spec, &json!({ "notes": "x".repeat(401) }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("max_length"));
+
+    let result = validate(&spec, &json!({ "notes": ["# Notes"] }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn schema_and_examples_treat_markdown_as_text() {
+    let spec = release_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    let notes = &schema["properties"]["notes"];
+    assert_eq!(notes["type"], "string");
+    assert_eq!(notes["contentMediaType"], "text/markdown");
+    assert_eq!(notes["minLength"], 10);
+    assert_eq!(notes["maxLength"], 400);
+
+    let example = example_answers(&spec, &Default::default());
+    assert!(validate(&spec, &example).valid, "{example}");
+}
+
+#[test]
+fn text_prompt_shows_a_single_line_preview() {
+    let spec = release_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({ "notes": NOTES }));
+
+    let text = render_text(&payload);
+    let current = text
+        .lines()
+        .find(|line| line.starts_with("  Current value: "))
+        .expect("current value line");
+    assert!(current.starts_with("  Current value: ## Fixes - Faster startup"));
+    assert!(current.ends_with('…'), "{current}");
+    assert!(!text.contains("- Smaller bundles"), "{text}");
+}
+
+#[test]
+fn card_and_json_ui_show_the_content() {
+    let spec = release_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({ "notes": NOTES }));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["type"], "markdown");
+    assert_eq!(ui["questions"][0]["current_value"], NOTES);
+
+    let card = render_card(&payload);
+    let container = card["body"]
+        .as_array()
+        .and_then(|body| body.iter().find(|item| item["type"] == "Container"))
+        .expect("question container");
+    let items = &container["items"];
+    let input = &items[1];
+    assert_eq!(input["type"], "Input.Text");
+    assert_eq!(input["isMultiline"], true);
+    let content = &items[2];
+    assert_eq!(content["type"], "TextBlock");
+    assert_eq!(content["text"], NOTES);
+    assert_eq!(content["wrap"], true);
+}
@@ -0,0 +1,111 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    ComputedStatus, FormSpec, ProgressContext, VisibilityMode, compute_answers,
+    materialize_computed, next_question, resolve_visibility,
+};
+
+fn invoice_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "invoice",
+        "title": "Invoice",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "total",
+                "type": "number",
+                "title": "Total",
+                "computed": {
+                    "op": "add",
+                    "left": { "op": "answer", "path": "subtotal" },
+                    "right": { "op": "answer", "path": "tax" }
+                }
+            },
+            {
+                "id": "subtotal",
+                "type": "number",
+                "title": "Subtotal",
+                "computed": {
+                    "op": "mul",
+                    "left": { "op": "answer", "path": "price" },
+                    "right": { "op": "answer", "path": "quantity" }
+                }
+            },
+            {
+                "id": "tax",
+                "type": "number",
+                "title": "Tax",
+                "computed": { "op": "literal", "value": 0 },
+                "computed_overridable": true
+            },
+            { "id": "price", "type": "number", "title": "Price", "required": true },
+            { "id": "quantity", "type": "integer", "title": "Quantity", "required": true }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn next(spec: &FormSpec, answers: &Value) -> Option<String> {
+    let answers = materialize_computed(spec, answers, &json!({}));
+    let visibility = resolve_visibility(spec, &answers, VisibilityMode::Visible);
+    next_question(
+        spec,
+        &ProgressContext::new(&answers, &Value::Null),
+        &visibility,
+    )
+}
+
+#[test]
+fn computed_totals_are_materialized_without_being_asked() {
+    let spec = invoice_form();
+    let mut answers = json!({});
+    let mut asked = Vec::new();
+    while let Some(id) = next(&spec, &answers) {
+        let value = match id.as_str() {
+            "price" => json!(12.5),
+            "quantity" => json!(4),
+            other => panic!("computed question '{other}' was asked"),
+        };
+        answers[&id] = value;
+        asked.push(id);
+    }
+    assert_eq!(asked, ["price", "quantity"]);
+
+    // `total` reads `subtotal`, which is listed after it.
+    let answers = materialize_computed(&spec, &answers, &json!({}));
+    assert_eq!(answers["subtotal"], json!(50.0));
+    assert_eq!(answers["total"], json!(50.0));
+
+    // An overridable field keeps a submitted value and the total follows it.
+    let answers = materialize_computed(
+        &spec,
+        &json!({ "price": 12.5, "quantity": 4, "tax": 10 }),
+        &json!({}),
+    );
+    assert_eq!(answers["tax"], 10);
+    assert_eq!(answers["total"], json!(60.0));
+}
+
+#[test]
+fn unresolvable_computed_fields_name_their_missing_sources() {
+    let spec = invoice_form();
+    let (answers, reports) = compute_answers(&spec, &json!({ "price": 12.5 }), &json!({}));
+    assert!(answers.get("total").is_none());
+    assert!(answers.get("subtotal").is_none());
+
+    let subtotal = reports
+        .iter()
+        .find(|report| report.question_id == "subtotal")
+        .expect("subtotal report");
+    assert_eq!(subtotal.status, ComputedStatus::Unresolved);
+    assert_eq!(subtotal.missing, ["quantity"]);
+    let total = reports
+        .iter()
+        .find(|report| report.question_id == "total")
+        .expect("total report");
+    assert_eq!(total.missing, ["subtotal"]);
+    assert_eq!(
+        serde_json::to_value(total).expect("json")["missing"],
+        json!(["subtotal"])
+    );
+}
@@ -0,0 +1,93 @@
+use serde_json::json;
+
+use qa_spec::{FormSpec, MessageCatalog, validate, validate_localized};
+
+fn profile_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "profile",
+        "title": "Profile",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "age", "type": "integer", "title": "Age", "constraint": { "min": 18, "max": 120 } },
+            { "id": "nickname", "type": "string", "title": "Nickname", "constraint": { "min_len": 3 } },
+            { "id": "plan", "type": "enum", "title": "Plan", "choices": ["free", "pro"] },
+            {
+                "id": "code",
+                "type": "string",
+                "title": "Code",
+                "constraint": {
+                    "pattern": "^[A-Z]+$",
+                    "messages": { "pattern_mismatch": "Use capital letters only." }
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn indonesian() -> MessageCatalog {
+    serde_json::from_value(json!({
+        "codes": {
+            "min": "Nilai minimal {min}.",
+            "enum_mismatch": "Pilih salah satu: {choices}."
+        },
+        "questions": {
+            "age": { "max": "{title} paling banyak {max}." }
+        }
+    }))
+    .expect("catalog")
+}
+
+#[test]
+fn english_catalog_fills_placeholders() {
+    let spec = profile_form();
+    let english = MessageCatalog::english();
+    let answers = json!({ "age": 12, "nickname": "al", "plan": "gold" });
+
+    let result = validate_localized(&spec, &answers, &english);
+    let messages = result
+        .errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "Value must be at least 18.",
+            "Value must be at least 3 characters long.",
+            "Value must be one of: free, pro.",
+        ]
+    );
+    assert_eq!(result.errors[0].code.as_deref(), Some("min"));
+}
+
+#[test]
+fn catalogs_pick_question_templates_first() {
+    let spec = profile_form();
+    let catalog = indonesian().or(&MessageCatalog::english());
+
+    let result = validate_localized(&spec, &json!({ "age": 12, "plan": "gold" }), &catalog);
+    assert_eq!(result.errors[0].message, "Nilai minimal 18.");
+    assert_eq!(result.errors[1].message, "Pilih salah satu: free, pro.");
+
+    let result = validate_localized(&spec, &json!({ "age": 130 }), &catalog);
+    assert_eq!(result.errors[0].message, "Age paling banyak 120.");
+
+    let result = validate_localized(&spec, &json!({ "nickname": "al" }), &catalog);
+    assert_eq!(
+        result.errors[0].message,
+        "Value must be at least 3 characters long."
+    );
+}
+
+#[test]
+fn spec_messages_and_missing_templates_are_kept() {
+    let spec = profile_form();
+
+    let result = validate_localized(&spec, &json!({ "code": "abc" }), &indonesian());
+    assert_eq!(result.errors[0].message, "Use capital letters only.");
+
+    let result = validate_localized(&spec, &json!({ "age": 130 }), &MessageCatalog::default());
+    assert_eq!(result, validate(&spec, &json!({ "age": 130 })));
+    assert_eq!(result.errors[0].message, "qa_spec.max");
+}
@@ -0,0 +1,153 @@
+use serde_json::{Value, json};
+
+use qa_spec::{AnswerSet, FormSpec, MigrationError, MigrationOp, migrate_answers};
+
+fn database_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "database",
+        "title": "Database",
+        "version": "3.0.0",
+        "migrations": [
+            {
+                "from": "1.0.0",
+                "to": "2.0.0",
+                "ops": [
+                    { "op": "rename", "from": "db_host", "to": "database_host" },
+                    { "op": "retype", "field": "db_port", "convert": "string_to_integer" },
+                    { "op": "drop", "field": "legacy_mode" }
+                ]
+            },
+            {
+                "from": "2.0.0",
+                "to": "3.0.0",
+                "ops": [
+                    { "op": "split", "from": "endpoint", "into": ["proxy_host", "proxy_port"], "separator": ":" },
+                    { "op": "merge", "from": ["first_name", "last_name"], "into": "owner", "separator": " " },
+                    { "op": "retype", "field": "proxy_port", "convert": "string_to_integer" }
+                ]
+            }
+        ],
+        "questions": [
+            { "id": "database_host", "type": "string", "title": "Host", "required": true },
+            { "id": "db_port", "type": "integer", "title": "Port", "required": true },
+            { "id": "proxy_host", "type": "string", "title": "Proxy host", "required": false },
+            { "id": "proxy_port", "type": "integer", "title": "Proxy port", "required": false },
+            { "id": "owner", "type": "string", "title": "Owner", "required": false }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn migrations_chain_across_versions() {
+    let spec = database_form();
+    let answers = json!({
+        "db_host": "db.internal",
+        "db_port": "5432",
+        "legacy_mode": true,
+        "endpoint": "proxy.internal:8080",
+        "first_name": "Ada",
+        "last_name": "Lovelace"
+    });
+
+    let (migrated, report) = migrate_answers(&answers, "1.0.0", &spec).expect("migrate");
+    assert_eq!(
+        migrated,
+        json!({
+            "database_host": "db.internal",
+            "db_port": 5432,
+            "proxy_host": "proxy.internal",
+            "proxy_port": 8080,
+            "owner": "Ada Lovelace"
+        })
+    );
+    assert_eq!(report.from_version, "1.0.0");
+    assert_eq!(report.to_version, "3.0.0");
+    let ops = report
+        .applied
+        .iter()
+        .map(|applied| applied.op.name())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ops,
+        ["rename", "retype", "drop", "split", "merge", "retype"]
+    );
+    assert!(qa_spec::validate(&spec, &migrated).valid);
+}
+
+#[test]
+fn unanswered_fields_are_left_alone() {
+    let spec = database_form();
+    let (migrated, report) =
+        migrate_answers(&json!({ "db_host": "db" }), "2.0.0", &spec).expect("migrate");
+    assert_eq!(migrated, json!({ "db_host": "db" }));
+    assert!(report.is_empty());
+
+    let (unchanged, report) =
+        migrate_answers(&json!({ "owner": "x" }), "3.0.0", &spec).expect("current version");
+    assert_eq!(unchanged, json!({ "owner": "x" }));
+    assert!(report.is_empty());
+}
+
+#[test]
+fn stuck_fields_are_all_reported() {
+    let spec = database_form();
+    let answers = json!({
+        "db_host": "db",
+        "database_host": "other",
+        "db_port": "fifty"
+    });
+
+    let err = migrate_answers(&answers, "1.0.0", &spec).expect_err("stuck");
+    let fields = err
+        .stuck_fields()
+        .iter()
+        .map(|stuck| (stuck.field.as_str(), stuck.op.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(fields, [("db_host", "rename"), ("db_port", "retype")]);
+    let message = err.to_string();
+    assert!(
+        message.contains("from version '1.0.0' to '2.0.0'"),
+        "{message}"
+    );
+    assert!(message.contains("db_port (retype: cannot parse \"fifty\" as an integer)"));
+    assert!(message.contains("target field 'database_host' is already answered"));
+
+    let err =
+        migrate_answers(&json!({ "endpoint": "no-port" }), "2.0.0", &spec).expect_err("split");
+    assert_eq!(err.stuck_fields()[0].field, "endpoint");
+}
+
+#[test]
+fn unknown_versions_have_no_path() {
+    let spec = database_form();
+    assert_eq!(
+        migrate_answers(&json!({}), "0.9.0", &spec),
+        Err(MigrationError::NoPath {
+            from: "0.9.0".into(),
+            to: "3.0.0".into()
+        })
+    );
+    assert_eq!(
+        migrate_answers(&Value::Null, "1.0.0", &spec),
+        Err(MigrationError::NotAnObject)
+    );
+
+    let mut cyclic = spec.clone();
+    cyclic.migrations[1].to = "1.0.0".into();
+    assert!(matches!(
+        migrate_answers(&json!({}), "1.0.0", &cyclic),
+        Err(MigrationError::NoPath { .. })
+    ));
+}
+
+#[test]
+fn answer_set_migration_updates_version() {
+    let spec = database_form();
+    let mut set = AnswerSet::new("database", "2.0.0");
+    set.answers = json!({ "first_name": "Ada" });
+    let (migrated, report) = set.migrate(&spec).expect("migrate");
+    assert_eq!(migrated.spec_version, "3.0.0");
+    assert_eq!(migrated.answers, json!({ "owner": "Ada" }));
+    assert!(matches!(report.applied[0].op, MigrationOp::Merge { .. }));
+}
@@ -0,0 +1,156 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_json_ui,
+    validate,
+};
+
+fn regions_form(constraint: serde_json::Value) -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "rollout",
+        "title": "Rollout",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "regions",
+                "type": "multi_enum",
+                "title": "Regions",
+                "required": true,
+                "choices": ["us", "eu", "apac"],
+                "constraint": constraint
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn selections_of_known_choices_are_valid() {
+    let spec = regions_form(json!({}));
+
+    assert!(validate(&spec, &json!({ "regions": ["eu", "us"] })).valid);
+    assert!(validate(&spec, &json!({ "regions": [] })).valid);
+
+    let result = validate(&spec, &json!({ "regions": "eu" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn each_bad_item_is_reported_at_its_own_path() {
+    let spec = regions_form(json!({}));
+
+    let result = validate(&spec, &json!({ "regions": ["us", 7, "mars", "us"] }));
+    assert!(!result.valid);
+    let reported = result
+        .errors
+        .iter()
+        .map(|error| (error.path.as_deref(), error.code.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        reported,
+        vec![
+            (Some("/regions/1"), Some("type_mismatch")),
+            (Some("/regions/2"), Some("enum_mismatch")),
+            (Some("/regions/3"), Some("duplicate_choice")),
+        ]
+    );
+    assert_eq!(result.errors[1].params["value"], "mars");
+    assert_eq!(result.errors[1].params["choices"], "us, eu, apac");
+    assert!(
+        result
+            .errors
+            .iter()
+            .all(|error| error.question_id.as_deref() == Some("regions"))
+    );
+}
+
+#[test]
+fn secret_selection_errors_leave_the_value_out() {
+    let mut spec = regions_form(json!({}));
+    spec.questions[0].secret = true;
+
+    let result = validate(&spec, &json!({ "regions": ["eu", "mars", "eu"] }));
+    let codes = result
+        .errors
+        .iter()
+        .map(|error| error.code.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(codes, [Some("enum_mismatch"), Some("duplicate_choice")]);
+    let serialized = serde_json::to_string(&result).expect("json");
+    assert!(!serialized.contains("mars"), "{serialized}");
+    assert!(
+        result
+            .errors
+            .iter()
+            .all(|error| !error.params.contains_key("value"))
+    );
+}
+
+#[test]
+fn selection_counts_are_enforced() {
+    let spec = regions_form(json!({ "min_selected": 1, "max_selected": 2 }));
+
+    let too_few = validate(&spec, &json!({ "regions": [] }));
+    assert_eq!(too_few.errors[0].code.as_deref(), Some("min_selected"));
+    assert_eq!(too_few.errors[0].params["expected"], "1");
+
+    let too_many = validate(&spec, &json!({ "regions": ["us", "eu", "apac"] }));
+    assert_eq!(too_many.errors[0].code.as_deref(), Some("max_selected"));
+    assert_eq!(too_many.errors[0].path.as_deref(), Some("/regions"));
+}
+
+#[test]
+fn schema_describes_a_unique_array_of_choices() {
+    let spec = regions_form(json!({ "min_selected": 1 }));
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(
+        schema["properties"]["regions"],
+        json!({
+            "type": "array",
+            "items": { "type": "string", "enum": ["us", "eu", "apac"] },
+            "uniqueItems": true,
+            "minItems": 1
+        })
+    );
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["regions"], json!(["us"]));
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn comma_separated_defaults_become_arrays() {
+    let mut spec = regions_form(json!({}));
+    spec.questions[0].default_value = Some("eu, apac".into());
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(
+        schema["properties"]["regions"]["default"],
+        json!(["eu", "apac"])
+    );
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["regions"], json!(["eu", "apac"]));
+}
+
+#[test]
+fn cards_offer_a_multi_select_choice_set() {
+    let spec = regions_form(json!({}));
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["type"], "multi_enum");
+
+    let card = render_card(&payload);
+    let body = card["body"].as_array().expect("body");
+    let input = body
+        .iter()
+        .flat_map(|element| {
+            std::iter::once(element).chain(element["items"].as_array().into_iter().flatten())
+        })
+        .find(|element| element["id"] == "regions")
+        .expect("choice set");
+    assert_eq!(input["type"], "Input.ChoiceSet");
+    assert_eq!(input["isMultiSelect"], true);
+    assert_eq!(input["choices"].as_array().map(Vec::len), Some(3));
+}
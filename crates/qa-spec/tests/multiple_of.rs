@@ -0,0 +1,147 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, lint_form, render_text,
+    validate,
+};
+
+fn sizing_form(memory: Value, ratio: Value) -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "sizing",
+        "title": "Sizing",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "memory",
+                "type": "integer",
+                "title": "Memory (MB)",
+                "required": true,
+                "constraint": memory
+            },
+            {
+                "id": "ratio",
+                "type": "number",
+                "title": "Ratio",
+                "required": true,
+                "constraint": ratio
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn default_form() -> FormSpec {
+    sizing_form(
+        json!({ "min": 100, "multiple_of": 256 }),
+        json!({ "min": 0.2, "multiple_of": 0.1 }),
+    )
+}
+
+fn first_code(spec: &FormSpec, answers: Value) -> Option<String> {
+    validate(spec, &answers)
+        .errors
+        .first()
+        .and_then(|error| error.code.clone())
+}
+
+#[test]
+fn answers_must_be_whole_multiples() {
+    let spec = default_form();
+
+    assert!(validate(&spec, &json!({ "memory": 512, "ratio": 0.3 })).valid);
+    assert!(
+        validate(
+            &spec,
+            &json!({ "memory": 256, "ratio": 0.7000000000000001 })
+        )
+        .valid
+    );
+
+    let result = validate(&spec, &json!({ "memory": 300, "ratio": 0.3 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("multiple_of"));
+    assert_eq!(result.errors[0].path.as_deref(), Some("/memory"));
+    assert_eq!(result.errors[0].params["multiple_of"], "256");
+
+    assert_eq!(
+        first_code(&spec, json!({ "memory": 512, "ratio": 0.35 })).as_deref(),
+        Some("multiple_of")
+    );
+}
+
+#[test]
+fn large_whole_numbers_are_checked_exactly() {
+    let spec = sizing_form(json!({ "multiple_of": 2 }), json!({}));
+
+    assert!(validate(&spec, &json!({ "memory": u64::MAX - 1, "ratio": 1 })).valid);
+    assert_eq!(
+        first_code(
+            &spec,
+            json!({ "memory": 9_007_199_254_740_993_u64, "ratio": 1 })
+        )
+        .as_deref(),
+        Some("multiple_of")
+    );
+}
+
+#[test]
+fn schema_examples_and_text_use_the_step() {
+    let spec = default_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(schema["properties"]["memory"]["multipleOf"], 256.0);
+    assert_eq!(schema["properties"]["ratio"]["multipleOf"], 0.1);
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["memory"], 256);
+    assert!(validate(&spec, &example).valid, "{example}");
+
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+    let text = render_text(&payload);
+    assert!(text.contains("Multiple of: 256"), "{text}");
+}
+
+#[test]
+fn decimal_precision_is_kept_next_to_the_step() {
+    let spec = sizing_form(
+        json!({}),
+        json!({ "decimal_places": 2, "multiple_of": 0.25 }),
+    );
+
+    let schema = answers_schema(&spec, &Default::default());
+    let ratio = &schema["properties"]["ratio"];
+    assert_eq!(ratio["multipleOf"], 0.25);
+    assert_eq!(ratio["allOf"], json!([{ "multipleOf": 0.01 }]));
+}
+
+#[test]
+fn steps_must_be_positive() {
+    let spec = sizing_form(json!({ "multiple_of": 0 }), json!({ "multiple_of": -0.5 }));
+
+    let issues = lint_form(&spec);
+    let codes = issues
+        .iter()
+        .map(|issue| (issue.code.as_str(), issue.path.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec![
+            (
+                "multiple_of_not_positive",
+                "/questions/0/constraint/multiple_of"
+            ),
+            (
+                "multiple_of_not_positive",
+                "/questions/1/constraint/multiple_of"
+            ),
+        ]
+    );
+    assert!(validate(&spec, &json!({ "memory": 3, "ratio": 0.3 })).valid);
+}
+
+#[test]
+fn examples_already_on_a_step_are_kept() {
+    let spec = default_form();
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["ratio"], 0.2);
+}
@@ -0,0 +1,67 @@
+use serde_json::json;
+
+use qa_spec::{FormSpec, apply_normalization_with_changes, validate};
+
+fn account_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "account",
+        "title": "Account",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "username",
+                "type": "string",
+                "title": "Username",
+                "normalize": ["trim", "lowercase"],
+                "constraint": { "pattern": "^[a-z]+$", "max_len": 8 }
+            },
+            {
+                "id": "token",
+                "type": "string",
+                "title": "Token",
+                "secret": true,
+                "normalize": ["trim"],
+                "constraint": { "min_len": 12 }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn changes_list_normalized_answers_and_mask_secrets() {
+    let spec = account_form();
+    let answers = json!({ "username": "  Alice\n", "token": " hunter2-secret \n" });
+
+    let (normalized, changes) = apply_normalization_with_changes(&spec, &answers);
+    assert_eq!(
+        normalized,
+        json!({ "username": "alice", "token": "hunter2-secret" })
+    );
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].path, "/token");
+    assert!(changes[0].masked);
+    assert_eq!(changes[0].new, None);
+    assert_eq!(changes[1].path, "/username");
+    assert_eq!(changes[1].new, Some(json!("alice")));
+
+    let (_, changes) = apply_normalization_with_changes(&spec, &json!({ "username": "bob" }));
+    assert!(changes.is_empty());
+}
+
+#[test]
+fn errors_carry_the_normalized_value_except_for_secrets() {
+    let spec = account_form();
+
+    let result = validate(&spec, &json!({ "username": " Bob Smith \n" }));
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("pattern_mismatch"));
+    assert_eq!(error.params["value"], "bob smith");
+
+    let result = validate(&spec, &json!({ "token": "  short-one  " }));
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("min_length"));
+    assert!(!error.params.contains_key("value"));
+    let serialized = serde_json::to_string(&result).expect("json");
+    assert!(!serialized.contains("short-one"), "{serialized}");
+}
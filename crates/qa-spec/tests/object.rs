@@ -0,0 +1,134 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, redact_answers,
+    render_json_ui, render_text, validate,
+};
+
+fn mail_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "mail",
+        "title": "Mail",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "smtp",
+                "type": "object",
+                "title": "SMTP",
+                "required": true,
+                "object": {
+                    "fields": [
+                        { "id": "host", "type": "string", "title": "Host", "required": true },
+                        {
+                            "id": "port",
+                            "type": "integer",
+                            "title": "Port",
+                            "required": true,
+                            "constraint": { "min": 1, "max": 65535 }
+                        },
+                        { "id": "tls", "type": "boolean", "title": "TLS", "required": false },
+                        {
+                            "id": "password",
+                            "type": "string",
+                            "title": "Password",
+                            "required": true,
+                            "secret": true,
+                            "visible_if": { "op": "var", "path": "item.tls" }
+                        }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn validation_recurses_into_object_fields() {
+    let spec = mail_form();
+
+    let valid = json!({ "smtp": { "host": "smtp.example.com", "port": 587 } });
+    assert!(validate(&spec, &valid).valid);
+
+    let result = validate(
+        &spec,
+        &json!({ "smtp": { "host": "smtp.example.com", "port": 0 } }),
+    );
+    assert_eq!(result.errors[0].path.as_deref(), Some("/smtp/port"));
+    assert_eq!(result.errors[0].question_id.as_deref(), Some("smtp.port"));
+    assert_eq!(result.errors[0].code.as_deref(), Some("min"));
+
+    let result = validate(&spec, &json!({ "smtp": { "port": 25 } }));
+    assert_eq!(result.errors[0].path.as_deref(), Some("/smtp/host"));
+    assert_eq!(result.errors[0].code.as_deref(), Some("missing_field"));
+
+    let result = validate(&spec, &json!({ "smtp": [] }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn object_fields_see_the_object_as_item() {
+    let spec = mail_form();
+
+    let result = validate(
+        &spec,
+        &json!({ "smtp": { "host": "smtp.example.com", "port": 465, "tls": true } }),
+    );
+    assert_eq!(result.errors[0].path.as_deref(), Some("/smtp/password"));
+    assert_eq!(result.errors[0].code.as_deref(), Some("missing_field"));
+
+    let complete = json!({
+        "smtp": { "host": "smtp.example.com", "port": 465, "tls": true, "password": "hunter2" }
+    });
+    assert!(validate(&spec, &complete).valid);
+    assert_eq!(
+        redact_answers(&spec, &complete)["smtp"]["password"],
+        json!({ "$redacted": true })
+    );
+    assert_eq!(redact_answers(&spec, &complete)["smtp"]["port"], 465);
+}
+
+#[test]
+fn schema_and_examples_nest_the_object() {
+    let spec = mail_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    let smtp = &schema["properties"]["smtp"];
+    assert_eq!(smtp["type"], "object");
+    assert_eq!(smtp["required"], json!(["host", "port"]));
+    assert_eq!(smtp["properties"]["port"]["type"], "integer");
+    assert_eq!(
+        smtp["allOf"],
+        json!([{
+            "if": { "properties": { "tls": { "const": true } }, "required": ["tls"] },
+            "then": { "required": ["password"] }
+        }])
+    );
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["smtp"]["host"], "example-host");
+    assert_eq!(example["smtp"]["port"], 1);
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn renderers_describe_object_fields() {
+    let spec = mail_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let ui = render_json_ui(&payload);
+    let question = &ui["questions"][0];
+    assert_eq!(question["type"], "object");
+    let fields = question["object"]["fields"]
+        .as_array()
+        .expect("object fields");
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[1]["id"], "port");
+    assert_eq!(fields[1]["constraint"]["max"], 65535.0);
+
+    let text = render_text(&payload);
+    assert!(
+        text.contains("  Fields: host, port, tls, password"),
+        "{text}"
+    );
+}
@@ -0,0 +1,121 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, ProgressContext, VisibilityMode, build_render_payload, next_question,
+    previous_question, render_text, resolve_visibility,
+};
+
+/// Organized by topic, but `region` and `name` are asked before `advanced`.
+fn spec() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "advanced", "type": "boolean", "title": "Advanced" },
+            {
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "order": 5,
+                "visible_if": { "op": "answer", "path": "advanced" }
+            },
+            { "id": "name", "type": "string", "title": "Name", "order": -1 },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "order": -2,
+                "visible_if": { "op": "answer", "path": "advanced" }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn next(spec: &FormSpec, answers: &Value) -> Option<String> {
+    let visibility = resolve_visibility(spec, answers, VisibilityMode::Visible);
+    next_question(
+        spec,
+        &ProgressContext::new(answers, &Value::Null),
+        &visibility,
+    )
+}
+
+#[test]
+fn questions_are_asked_by_order_then_position() {
+    let spec = spec();
+    let ids = spec
+        .ordered_questions()
+        .iter()
+        .map(|question| question.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["region", "name", "advanced", "replicas"]);
+
+    assert_eq!(next(&spec, &json!({})).as_deref(), Some("region"));
+    assert_eq!(
+        next(&spec, &json!({ "region": "eu" })).as_deref(),
+        Some("name")
+    );
+}
+
+#[test]
+fn hidden_questions_keep_their_place_without_being_asked() {
+    let spec = spec();
+    let answers = json!({ "advanced": false });
+    // `region` comes first but is hidden.
+    assert_eq!(next(&spec, &answers).as_deref(), Some("name"));
+
+    let answers = json!({ "advanced": false, "name": "api" });
+    assert_eq!(next(&spec, &answers), None);
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    assert_eq!(payload.progress.answered, 2);
+    assert_eq!(payload.progress.total, 2);
+
+    let answers = json!({ "advanced": true, "name": "api", "region": "eu" });
+    assert_eq!(next(&spec, &answers).as_deref(), Some("replicas"));
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    assert_eq!(payload.progress.answered, 3);
+    assert_eq!(payload.progress.total, 4);
+}
+
+#[test]
+fn render_payloads_list_questions_in_order() {
+    let spec = spec();
+    let answers = json!({ "advanced": true });
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let ids = payload
+        .questions
+        .iter()
+        .map(|question| question.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["region", "name", "advanced", "replicas"]);
+
+    let text = render_text(&payload);
+    let position = |title: &str| text.find(title).expect(title);
+    assert!(position("Region") < position("Name"), "{text}");
+    assert!(position("Name") < position("Advanced"), "{text}");
+    assert!(position("Advanced") < position("Replicas"), "{text}");
+}
+
+#[test]
+fn previous_question_walks_back_through_visible_questions() {
+    let spec = spec();
+    let answers = json!({ "advanced": false, "name": "api" });
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+    let ctx = ProgressContext::new(&answers, &Value::Null);
+    let previous = |current: &str| previous_question(&spec, &ctx, &visibility, current);
+
+    assert_eq!(previous("advanced").as_deref(), Some("name"));
+    // `region` is hidden, so `name` is the first question.
+    assert_eq!(previous("name"), None);
+    assert_eq!(previous("unknown"), None);
+
+    let answers = json!({ "advanced": true });
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+    let ctx = ProgressContext::new(&answers, &Value::Null);
+    assert_eq!(
+        previous_question(&spec, &ctx, &visibility, "name").as_deref(),
+        Some("region")
+    );
+}
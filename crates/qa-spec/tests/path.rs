@@ -0,0 +1,75 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_json_ui, validate,
+};
+
+fn deploy_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "config",
+                "type": "path",
+                "title": "Config file",
+                "required": true,
+                "constraint": { "must_exist": true, "kind": "file" }
+            },
+            { "id": "workdir", "type": "path", "title": "Working directory", "required": false }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn validation_only_checks_path_syntax() {
+    let spec = deploy_form();
+
+    // Existence is checked where answers are collected, not during validation.
+    let result = validate(&spec, &json!({ "config": "./does/not/exist.toml" }));
+    assert!(result.valid, "{:?}", result.errors);
+
+    for bad in [json!(""), json!("bad\0path")] {
+        let result = validate(&spec, &json!({ "config": bad }));
+        assert!(!result.valid, "{bad} should be rejected");
+        assert_eq!(result.errors[0].code.as_deref(), Some("path_invalid"));
+        assert_eq!(result.errors[0].message, "qa_spec.path_invalid");
+    }
+
+    let result = validate(&spec, &json!({ "config": 42 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn schema_and_examples_describe_paths() {
+    let spec = deploy_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(
+        schema["properties"]["config"],
+        json!({ "type": "string", "minLength": 1, "x-path-kind": "file" })
+    );
+    assert_eq!(
+        schema["properties"]["workdir"],
+        json!({ "type": "string", "minLength": 1 })
+    );
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["config"], "./config");
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn json_ui_exposes_path_constraints() {
+    let spec = deploy_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let ui = render_json_ui(&payload);
+    let question = &ui["questions"][0];
+    assert_eq!(question["type"], "path");
+    assert_eq!(question["constraint"]["must_exist"], true);
+    assert_eq!(question["constraint"]["kind"], "file");
+    assert!(ui["questions"][1].get("constraint").is_none());
+}
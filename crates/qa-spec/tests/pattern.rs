@@ -0,0 +1,105 @@
+use serde_json::json;
+
+use qa_spec::{FormSpec, PatternCache, SpecParseError, validate};
+
+#[test]
+fn cache_remembers_compiled_and_failed_patterns() {
+    let mut cache = PatternCache::new(2);
+    assert!(cache.get("^[a-z]+$").expect("valid").is_match("abc"));
+    assert!(cache.get("([a-z]").is_err());
+    assert!(cache.contains("([a-z]"));
+    assert!(cache.get("([a-z]").is_err());
+    assert_eq!(cache.len(), 2);
+
+    cache.get("^\\d+$").expect("valid");
+    assert_eq!(cache.len(), 2);
+    assert!(!cache.contains("^[a-z]+$"));
+    assert!(cache.contains("^\\d+$"));
+}
+
+#[test]
+fn pattern_constraints_are_enforced_across_calls() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "ticket",
+        "title": "Ticket",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "key",
+                "type": "string",
+                "title": "Key",
+                "required": true,
+                "constraint": { "pattern": "^[A-Z]+-\\d+$" }
+            },
+            {
+                "id": "note",
+                "type": "string",
+                "title": "Note",
+                "required": false,
+                "constraint": { "pattern": "([unclosed" }
+            }
+        ]
+    }))
+    .expect("spec");
+
+    for _ in 0..3 {
+        assert!(validate(&spec, &json!({ "key": "QA-12", "note": "x" })).valid);
+        let result = validate(&spec, &json!({ "key": "qa" }));
+        assert_eq!(result.errors[0].code.as_deref(), Some("pattern_mismatch"));
+    }
+}
+
+#[test]
+fn invalid_patterns_are_rejected_when_the_spec_loads() {
+    let err = FormSpec::from_value(json!({
+        "id": "servers",
+        "title": "Servers",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "servers",
+            "type": "list",
+            "title": "Servers",
+            "list": {
+                "fields": [
+                    { "id": "name", "type": "string", "title": "Name" },
+                    {
+                        "id": "host",
+                        "type": "string",
+                        "title": "Host",
+                        "constraint": { "pattern": "^[a-z.+$" }
+                    }
+                ]
+            }
+        }]
+    }))
+    .expect_err("invalid pattern");
+
+    assert!(
+        matches!(err, SpecParseError::InvalidPattern { .. }),
+        "{err}"
+    );
+    assert_eq!(
+        err.pointer(),
+        Some("/questions/0/list/fields/1/constraint/pattern")
+    );
+    assert!(err.to_string().contains("^[a-z.+$"), "{err}");
+}
+
+#[test]
+fn check_reports_patterns_of_deserialized_specs() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "ticket",
+        "title": "Ticket",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "note",
+            "type": "string",
+            "title": "Note",
+            "constraint": { "pattern": "([unclosed" }
+        }]
+    }))
+    .expect("spec");
+
+    let err = spec.check().expect_err("invalid pattern");
+    assert_eq!(err.pointer(), Some("/questions/0/constraint/pattern"));
+}
@@ -0,0 +1,54 @@
+//! Kept in its own test binary so no other test touches the process-wide
+//! pattern cache while the compilations are counted.
+
+use serde_json::{Map, Value, json};
+
+use qa_spec::{CompiledFormSpec, FormSpec, pattern_compilations, validate};
+
+const QUESTIONS: usize = 100;
+
+fn patterned_form() -> FormSpec {
+    let questions = (0..QUESTIONS)
+        .map(|index| {
+            json!({
+                "id": format!("q{index}"),
+                "type": "string",
+                "title": format!("Question {index}"),
+                "required": true,
+                "constraint": { "pattern": format!("^q{index}-[a-z]+$") }
+            })
+        })
+        .collect::<Vec<_>>();
+    FormSpec::from_value(json!({
+        "id": "patterns",
+        "title": "Patterns",
+        "version": "1.0.0",
+        "questions": questions
+    }))
+    .expect("spec")
+}
+
+fn answers(suffix: &str) -> Value {
+    Value::Object(
+        (0..QUESTIONS)
+            .map(|index| (format!("q{index}"), json!(format!("q{index}-{suffix}"))))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+#[test]
+fn repeated_validation_compiles_each_pattern_once() {
+    let before = pattern_compilations();
+    let spec = patterned_form();
+    assert_eq!(pattern_compilations() - before, QUESTIONS);
+
+    let compiled = CompiledFormSpec::new(spec.clone());
+    for _ in 0..50 {
+        assert!(validate(&spec, &answers("ok")).valid);
+        assert!(compiled.validate(&answers("ok")).valid);
+        let result = validate(&spec, &answers("NO"));
+        assert_eq!(result.errors.len(), QUESTIONS);
+        assert_eq!(result.errors[0].code.as_deref(), Some("pattern_mismatch"));
+    }
+    assert_eq!(pattern_compilations() - before, QUESTIONS);
+}
@@ -0,0 +1,74 @@
+use serde_json::json;
+
+use qa_spec::{
+    CompiledFormSpec, DEFAULT_MAX_ANSWER_BYTES, DEFAULT_MAX_TOTAL_BYTES, FormSpec,
+    reject_oversized, validate, validate_field,
+};
+
+fn notes_form(limits: serde_json::Value) -> FormSpec {
+    let mut spec = json!({
+        "id": "notes",
+        "title": "Notes",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "title", "type": "string", "title": "Title" },
+            { "id": "body", "type": "string", "title": "Body" }
+        ]
+    });
+    if let (Some(spec), Some(limits)) = (spec.as_object_mut(), limits.as_object()) {
+        spec.extend(limits.clone());
+    }
+    FormSpec::from_value(spec).expect("spec")
+}
+
+#[test]
+fn defaults_are_generous() {
+    let spec = notes_form(json!({}));
+    assert_eq!(spec.answer_byte_limit(), DEFAULT_MAX_ANSWER_BYTES);
+    assert_eq!(spec.total_byte_limit(), DEFAULT_MAX_TOTAL_BYTES);
+
+    let body = "x".repeat(100_000);
+    assert!(validate(&spec, &json!({ "body": body })).valid);
+
+    let body = "x".repeat(DEFAULT_MAX_ANSWER_BYTES);
+    let result = validate(&spec, &json!({ "body": body }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("payload_too_large"));
+}
+
+#[test]
+fn per_answer_limit_reports_the_measured_size() {
+    let spec = notes_form(json!({ "max_answer_bytes": 16 }));
+
+    assert!(validate(&spec, &json!({ "body": "fourteen chars" })).valid);
+
+    let result = validate(&spec, &json!({ "title": "ok", "body": "x".repeat(20) }));
+    assert!(!result.valid);
+    assert_eq!(result.errors.len(), 1);
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("payload_too_large"));
+    assert_eq!(error.path.as_deref(), Some("/body"));
+    assert_eq!(error.params["size"], "22");
+    assert_eq!(error.params["limit"], "16");
+}
+
+#[test]
+fn total_limit_rejects_the_whole_payload() {
+    let spec = notes_form(json!({ "max_total_bytes": 40 }));
+    let answers = json!({ "title": "x".repeat(20), "body": "y".repeat(20) });
+
+    let result = reject_oversized(&spec, &answers).expect("rejected");
+    assert_eq!(result.errors.len(), 1);
+    let error = &result.errors[0];
+    assert_eq!(error.path, None);
+    assert_eq!(
+        error.params["size"],
+        serde_json::to_string(&answers).unwrap().len().to_string()
+    );
+
+    assert_eq!(validate(&spec, &answers), result);
+    assert_eq!(
+        CompiledFormSpec::new(spec.clone()).validate(&answers),
+        result
+    );
+    assert_eq!(validate_field(&spec, &answers, "title"), Ok(result));
+}
@@ -1,7 +1,9 @@
 use serde_json::json;
 
 use qa_spec::{
-    ProgressContext, StoreTarget, VisibilityMode, next_question, resolve_visibility,
+    FixedClock, PROGRESS_STATE_VERSION, ProgressContext, ProgressState, QuestionStatus,
+    RenderProgress, SessionError, StoreTarget, VisibilityMode, build_render_payload, next_question,
+    resolve_visibility,
     spec::form::{FormSpec, ProgressPolicy},
     spec::question::{QuestionSpec, QuestionType},
 };
@@ -17,11 +19,16 @@ fn build_progress_form() -> FormSpec {
             skip_answered: true,
             autofill_defaults: false,
             treat_default_as_answered: false,
+            required_only: false,
         }),
         secrets_policy: None,
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "q1".into(),
@@ -31,15 +38,23 @@ fn build_progress_form() -> FormSpec {
                 description: None,
                 description_i18n: None,
                 required: true,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "q2".into(),
@@ -49,15 +64,23 @@ fn build_progress_form() -> FormSpec {
                 description: None,
                 description_i18n: None,
                 required: true,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     }
@@ -70,7 +93,7 @@ fn next_question_skips_when_config_value_present() {
     let answers = json!({});
     let ctx = json!({ "config": { "q1": "preset" } });
     let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
-    let progress_ctx = ProgressContext::new(answers.clone(), &ctx);
+    let progress_ctx = ProgressContext::new(&answers, &ctx);
     assert_eq!(
         next_question(&spec, &progress_ctx, &visibility),
         Some("q2".into())
@@ -83,7 +106,7 @@ fn next_question_skips_answered() {
     let answers = json!({ "q1": "value" });
     let ctx = json!({});
     let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
-    let progress_ctx = ProgressContext::new(answers.clone(), &ctx);
+    let progress_ctx = ProgressContext::new(&answers, &ctx);
     assert_eq!(
         next_question(&spec, &progress_ctx, &visibility),
         Some("q2".into())
@@ -97,9 +120,347 @@ fn default_progress_policy_skips_answered() {
     let answers = json!({ "q1": "value" });
     let ctx = json!({});
     let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
-    let progress_ctx = ProgressContext::new(answers.clone(), &ctx);
+    let progress_ctx = ProgressContext::new(&answers, &ctx);
     assert_eq!(
         next_question(&spec, &progress_ctx, &visibility),
         Some("q2".into())
     );
 }
+
+#[test]
+fn owned_answers_behave_like_borrowed_ones() {
+    let spec = build_progress_form();
+    let answers = json!({ "q1": "value" });
+    let ctx = json!({});
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+    let borrowed = ProgressContext::new(&answers, &ctx);
+    let owned = ProgressContext::new(answers.clone(), &ctx);
+    assert_eq!(
+        next_question(&spec, &owned, &visibility),
+        next_question(&spec, &borrowed, &visibility)
+    );
+    assert_eq!(
+        owned.answered_count(&spec, &visibility),
+        borrowed.answered_count(&spec, &visibility)
+    );
+}
+
+fn tracked_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "tls", "type": "boolean", "title": "TLS" },
+            {
+                "id": "cert",
+                "type": "string",
+                "title": "Certificate",
+                "visible_if": { "op": "var", "path": "tls" }
+            },
+            { "id": "region", "type": "string", "title": "Region" },
+            {
+                "id": "region_label",
+                "type": "string",
+                "title": "Region label",
+                "computed": { "op": "answer", "path": "region" }
+            },
+            {
+                "id": "owner",
+                "type": "string",
+                "title": "Owner",
+                "policy": { "skip_if_present_in": ["config"] }
+            },
+            { "id": "notes", "type": "string", "title": "Notes" }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn progress_state_tracks_question_statuses() {
+    let spec = tracked_form();
+    let ctx = json!({ "config": { "owner": "ops" } });
+    let mut state = ProgressState::default();
+    state.mark_skipped("notes");
+    assert_eq!(state.record_attempt("cert"), 1);
+
+    state.refresh(
+        &spec,
+        &json!({ "tls": true, "cert": "a.pem", "region": "eu" }),
+        &ctx,
+    );
+    let status = |state: &ProgressState, id: &str| state.question(id).expect(id).status;
+    assert_eq!(status(&state, "tls"), QuestionStatus::Answered);
+    assert_eq!(status(&state, "cert"), QuestionStatus::Answered);
+    assert_eq!(status(&state, "region_label"), QuestionStatus::Computed);
+    assert_eq!(status(&state, "owner"), QuestionStatus::AutoFilled);
+    assert_eq!(status(&state, "notes"), QuestionStatus::Skipped);
+
+    // Turning TLS off hides the certificate but remembers what was entered.
+    state.refresh(
+        &spec,
+        &json!({ "tls": false, "cert": "a.pem", "notes": "hi" }),
+        &ctx,
+    );
+    let cert = state.question("cert").expect("cert");
+    assert_eq!(cert.status, QuestionStatus::Hidden);
+    assert_eq!(cert.last_value, Some(json!("a.pem")));
+    assert_eq!(cert.attempts, 1);
+    assert_eq!(status(&state, "region"), QuestionStatus::Pending);
+    assert_eq!(status(&state, "notes"), QuestionStatus::Answered);
+
+    state.refresh(&spec, &json!({ "tls": true }), &ctx);
+    let cert = state.question("cert").expect("cert");
+    assert_eq!(cert.status, QuestionStatus::Pending);
+    assert_eq!(cert.last_value, Some(json!("a.pem")));
+}
+
+#[test]
+fn progress_state_serialization_is_stable_and_migrates_legacy_records() {
+    let legacy: ProgressState =
+        serde_json::from_value(json!({ "current_step": "q1", "completed": false }))
+            .expect("legacy record");
+    assert_eq!(legacy.version, 1);
+    assert!(legacy.questions.is_empty());
+
+    let mut state = legacy;
+    state.refresh(&tracked_form(), &json!({ "tls": true }), &json!({}));
+    state.record_attempt("tls");
+    assert_eq!(state.version, PROGRESS_STATE_VERSION);
+
+    let encoded = serde_json::to_value(&state).expect("encode");
+    assert_eq!(encoded["version"], PROGRESS_STATE_VERSION);
+    assert_eq!(
+        encoded["questions"]["tls"],
+        json!({ "status": "answered", "attempts": 1 })
+    );
+    assert_eq!(
+        encoded["questions"]["notes"],
+        json!({ "status": "pending" })
+    );
+    let decoded: ProgressState = serde_json::from_value(encoded).expect("decode");
+    assert_eq!(decoded, state);
+}
+
+#[test]
+fn remaining_questions_exclude_hidden_read_only_and_counted_defaults() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "progress_policy": { "skip_answered": true, "treat_default_as_answered": true },
+        "questions": [
+            { "id": "advanced", "type": "boolean", "title": "Advanced" },
+            {
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "visible_if": { "op": "answer", "path": "advanced" }
+            },
+            { "id": "region", "type": "string", "title": "Region", "default_value": "eu" },
+            {
+                "id": "slug",
+                "type": "string",
+                "title": "Slug",
+                "computed": { "op": "answer", "path": "name" }
+            },
+            { "id": "name", "type": "string", "title": "Name", "order": -1 }
+        ]
+    }))
+    .expect("spec");
+    let answers = json!({ "advanced": false });
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+
+    let progress = RenderProgress::measure(
+        &spec,
+        &ProgressContext::new(&answers, &json!({})),
+        &visibility,
+    );
+    assert_eq!(progress.remaining, ["name"]);
+    assert_eq!((progress.answered, progress.total), (2, 4));
+    assert_eq!(progress.percent, 50.0);
+}
+
+fn quick_setup_form(required_only: bool) -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "quick",
+        "title": "Quick",
+        "version": "1.0.0",
+        "progress_policy": { "skip_answered": true, "required_only": required_only },
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true },
+            { "id": "tls", "type": "boolean", "title": "TLS" },
+            {
+                "id": "cert",
+                "type": "string",
+                "title": "Certificate",
+                "required_if": { "op": "answer", "path": "tls" }
+            },
+            { "id": "port", "type": "integer", "title": "Port", "required": true }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn required_only_asks_and_counts_required_questions() {
+    let spec = quick_setup_form(true);
+    let progress = |answers: &serde_json::Value, ctx: &serde_json::Value| {
+        let visibility = resolve_visibility(&spec, answers, VisibilityMode::Visible);
+        let progress_ctx = ProgressContext::new(answers, ctx);
+        (
+            next_question(&spec, &progress_ctx, &visibility),
+            RenderProgress::measure(&spec, &progress_ctx, &visibility),
+        )
+    };
+
+    let (next, measured) = progress(&json!({ "name": "api" }), &json!({}));
+    assert_eq!(next.as_deref(), Some("port"));
+    assert_eq!((measured.answered, measured.total), (1, 2));
+    assert_eq!(measured.remaining, ["port"]);
+
+    // Optional answers are kept, and can make other questions required.
+    let (next, measured) = progress(&json!({ "name": "api", "tls": true }), &json!({}));
+    assert_eq!(next.as_deref(), Some("cert"));
+    assert_eq!((measured.answered, measured.total), (1, 3));
+
+    let (next, _) = progress(&json!({ "name": "api", "port": 80 }), &json!({}));
+    assert_eq!(next, None);
+
+    // `ctx.progress.required_only` overrides the policy either way.
+    let (next, measured) = progress(
+        &json!({ "name": "api" }),
+        &json!({ "progress": { "required_only": false } }),
+    );
+    assert_eq!(next.as_deref(), Some("tls"));
+    assert_eq!(measured.total, 4);
+
+    let spec = quick_setup_form(false);
+    let answers = json!({ "tls": false });
+    let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+    let ctx = json!({ "progress": { "required_only": true } });
+    let progress_ctx = ProgressContext::new(&answers, &ctx);
+    assert!(progress_ctx.policy(&spec).required_only);
+    assert_eq!(
+        progress_ctx.remaining_questions(&spec, &visibility),
+        ["name", "port"]
+    );
+}
+
+#[test]
+fn session_snapshots_round_trip_and_keep_answer_times() {
+    let spec = tracked_form();
+    let answers = json!({ "tls": true });
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let mut state = ProgressState::from_payload_with(
+        &FixedClock("2026-01-01T00:00:00Z".into()),
+        &payload,
+        &answers,
+    );
+    assert_eq!(state.form_id.as_deref(), Some(spec.id.as_str()));
+    assert_eq!(state.spec_version.as_deref(), Some(spec.version.as_str()));
+    assert_eq!(state.answers, Some(answers.clone()));
+    assert_eq!(state.current_question_id, payload.next_question_id);
+    assert!(!state.completed);
+    let tls = state.question("tls").expect("tls");
+    assert_eq!(tls.status, QuestionStatus::Answered);
+    assert_eq!(tls.answered_at.as_deref(), Some("2026-01-01T00:00:00Z"));
+
+    let decoded = ProgressState::from_cbor(&state.to_cbor().expect("cbor")).expect("decode");
+    assert_eq!(decoded, state);
+
+    // Unchanged answers keep their time; new ones are stamped now.
+    let answers = json!({ "tls": true, "notes": "rotate yearly" });
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    state.snapshot(
+        &FixedClock("2026-01-02T00:00:00Z".into()),
+        &payload,
+        &answers,
+    );
+    assert_eq!(
+        state
+            .question("tls")
+            .and_then(|entry| entry.answered_at.as_deref()),
+        Some("2026-01-01T00:00:00Z")
+    );
+    assert_eq!(
+        state
+            .question("notes")
+            .and_then(|entry| entry.answered_at.as_deref()),
+        Some("2026-01-02T00:00:00Z")
+    );
+    assert_eq!(state.updated_at.as_deref(), Some("2026-01-02T00:00:00Z"));
+}
+
+#[test]
+fn sessions_only_resume_against_their_spec_version() {
+    let mut spec = tracked_form();
+    let answers = json!({});
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let state = ProgressState::from_payload(&payload, &answers);
+    assert_eq!(state.check_resumable(&spec), Ok(()));
+
+    let saved = spec.version.clone();
+    spec.version = "9.0.0".into();
+    let error = state.check_resumable(&spec).expect_err("version mismatch");
+    assert_eq!(
+        error,
+        SessionError::VersionMismatch {
+            form_id: spec.id.clone(),
+            expected: "9.0.0".into(),
+            found: saved.clone(),
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "session was saved with version {saved} of form '{}', but the spec is version 9.0.0",
+            spec.id
+        )
+    );
+
+    // `track_progress` records carry no form or version.
+    assert_eq!(ProgressState::default().check_resumable(&spec), Ok(()));
+}
+
+#[test]
+fn progress_state_hides_questions_by_ctx_roots() {
+    let spec = FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true },
+            {
+                "id": "secret_ref",
+                "type": "string",
+                "title": "Secret reference",
+                "visible_if": { "op": "var", "path": "config.secrets_host_available" }
+            }
+        ]
+    }))
+    .expect("spec");
+    let answers = json!({ "name": "api" });
+
+    let mut state = ProgressState::default();
+    state.refresh(
+        &spec,
+        &answers,
+        &json!({ "config": { "secrets_host_available": false } }),
+    );
+    assert_eq!(
+        state.question("secret_ref").expect("secret_ref").status,
+        QuestionStatus::Hidden
+    );
+
+    state.refresh(
+        &spec,
+        &answers,
+        &json!({ "config": { "secrets_host_available": true } }),
+    );
+    assert_eq!(
+        state.question("secret_ref").expect("secret_ref").status,
+        QuestionStatus::Pending
+    );
+}
@@ -0,0 +1,71 @@
+use proptest::prelude::*;
+use serde_json::{Value, json};
+
+use qa_spec::generators::{Defect, SpecSizes, answers_for, malformed_answers_for};
+use qa_spec::{
+    FormSpec, VisibilityMode, build_render_payload, example_answers, render_card, render_json_ui,
+    resolve_visibility, validate,
+};
+
+fn spec_and_answers() -> impl Strategy<Value = (FormSpec, Value)> {
+    any::<FormSpec>().prop_flat_map(|spec| {
+        let answers = answers_for(&spec);
+        (Just(spec), answers)
+    })
+}
+
+fn spec_and_malformed_answers() -> impl Strategy<Value = (FormSpec, (Value, Defect))> {
+    any_with::<FormSpec>(SpecSizes {
+        max_questions: 5,
+        ..SpecSizes::default()
+    })
+    .prop_flat_map(|spec| {
+        let answers = malformed_answers_for(&spec);
+        (Just(spec), answers)
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn conforming_answers_validate((spec, answers) in spec_and_answers()) {
+        let result = validate(&spec, &answers);
+        prop_assert!(result.valid, "{result:?}\nanswers: {answers}");
+    }
+
+    #[test]
+    fn malformed_answers_are_reported((spec, (answers, defect)) in spec_and_malformed_answers()) {
+        let result = validate(&spec, &answers);
+        match defect {
+            Defect::UnknownField(id) => prop_assert!(result.unknown_fields.contains(&id)),
+            Defect::WrongType(id) => {
+                let visible = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+                if visible[&id] {
+                    prop_assert!(!result.valid);
+                }
+            }
+            Defect::NotAnObject => prop_assert_eq!(result, validate(&spec, &json!({}))),
+        }
+    }
+
+    #[test]
+    fn render_card_emits_valid_json((spec, answers) in spec_and_answers()) {
+        let payload = build_render_payload(&spec, &json!({}), &answers);
+        let card = render_card(&payload);
+        let encoded = serde_json::to_string(&card).expect("encode card");
+        prop_assert_eq!(serde_json::from_str::<Value>(&encoded).expect("decode card"), card);
+        prop_assert!(render_json_ui(&payload).is_object());
+    }
+
+    #[test]
+    fn example_answers_validate_apart_from_patterns(spec in any::<FormSpec>()) {
+        let visibility = resolve_visibility(&spec, &json!({}), VisibilityMode::Visible);
+        let examples = example_answers(&spec, &visibility);
+        let result = validate(&spec, &examples);
+        prop_assert!(result.unknown_fields.is_empty());
+        for error in &result.errors {
+            prop_assert_eq!(error.code.as_deref(), Some("pattern_mismatch"), "{:?}", error);
+        }
+    }
+}
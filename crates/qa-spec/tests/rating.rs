@@ -0,0 +1,90 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_json_ui,
+    render_text, validate,
+};
+
+fn feedback_form(constraint: Option<serde_json::Value>) -> FormSpec {
+    let mut question = json!({
+        "id": "satisfaction",
+        "type": "rating",
+        "title": "Satisfaction",
+        "required": true
+    });
+    if let Some(constraint) = constraint {
+        question["constraint"] = constraint;
+    }
+    FormSpec::from_value(json!({
+        "id": "feedback",
+        "title": "Feedback",
+        "version": "1.0.0",
+        "questions": [question]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn validation_defaults_to_one_through_five() {
+    let spec = feedback_form(None);
+
+    assert!(validate(&spec, &json!({ "satisfaction": 1 })).valid);
+    assert!(validate(&spec, &json!({ "satisfaction": 5 })).valid);
+
+    let result = validate(&spec, &json!({ "satisfaction": 0 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("min"));
+    assert_eq!(result.errors[0].params["expected"], "1");
+
+    let result = validate(&spec, &json!({ "satisfaction": 6 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("max"));
+    assert_eq!(result.errors[0].params["expected"], "5");
+
+    let result = validate(&spec, &json!({ "satisfaction": 4.5 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn custom_scale_drives_schema_and_examples() {
+    let spec = feedback_form(Some(json!({ "min": 0, "max": 10 })));
+
+    assert!(validate(&spec, &json!({ "satisfaction": 0 })).valid);
+    assert!(!validate(&spec, &json!({ "satisfaction": 11 })).valid);
+
+    let schema = answers_schema(&spec, &Default::default());
+    let rating = &schema["properties"]["satisfaction"];
+    assert_eq!(rating["type"], "integer");
+    assert_eq!(rating["minimum"], 0.0);
+    assert_eq!(rating["maximum"], 10.0);
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["satisfaction"], 10);
+    assert!(validate(&spec, &example).valid);
+
+    let schema = answers_schema(&feedback_form(None), &Default::default());
+    assert_eq!(schema["properties"]["satisfaction"]["minimum"], 1);
+    assert_eq!(schema["properties"]["satisfaction"]["maximum"], 5);
+}
+
+#[test]
+fn renderers_show_the_scale() {
+    let spec = feedback_form(None);
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let ui = render_json_ui(&payload);
+    let question = &ui["questions"][0];
+    assert_eq!(question["type"], "rating");
+    assert_eq!(question["range"], json!({ "min": 1, "max": 5 }));
+
+    let card = render_card(&payload).to_string();
+    assert!(card.contains(r#""type":"Input.ChoiceSet""#), "{card}");
+    let expected_choices = (1..=5)
+        .map(|score| json!({ "title": score.to_string(), "value": score.to_string() }))
+        .collect::<Vec<_>>();
+    assert!(
+        card.contains(&serde_json::to_string(&expected_choices).unwrap()),
+        "{card}"
+    );
+
+    let text = render_text(&payload);
+    assert!(text.contains("  Scale: 1-5"), "{text}");
+}
@@ -0,0 +1,124 @@
+use serde_json::{Value, json};
+
+use qa_spec::spec::CardMode;
+use qa_spec::{
+    FormSpec, ProgressContext, RenderOptions, VisibilityMode, build_render_payload, next_question,
+    render_card_with_options, resolve_visibility, validate, validate_update,
+};
+
+/// `region` is locked while `locked` is on, or while `state.region_pinned` is.
+fn spec() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "locked", "type": "boolean", "title": "Locked" },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "read_only_if": {
+                    "op": "or",
+                    "expressions": [
+                        { "op": "answer", "path": "locked" },
+                        { "op": "var", "path": "state.region_pinned" }
+                    ]
+                }
+            },
+            { "id": "name", "type": "string", "title": "Name" }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn next(spec: &FormSpec, answers: &Value, ctx: &Value) -> Option<String> {
+    let visibility = resolve_visibility(spec, answers, VisibilityMode::Visible);
+    next_question(spec, &ProgressContext::new(answers, ctx), &visibility)
+}
+
+#[test]
+fn another_answer_toggles_read_only() {
+    let spec = spec();
+    let ctx = json!({});
+    let unlocked = json!({ "locked": false });
+    let locked = json!({ "locked": true, "region": "eu" });
+
+    let payload = build_render_payload(&spec, &ctx, &unlocked);
+    assert!(!payload.questions[1].read_only);
+    assert_eq!(next(&spec, &unlocked, &ctx).as_deref(), Some("region"));
+
+    let payload = build_render_payload(&spec, &ctx, &locked);
+    assert!(payload.questions[1].read_only);
+    assert_eq!(payload.questions[1].current_value, Some(json!("eu")));
+    assert_eq!(next(&spec, &locked, &ctx).as_deref(), Some("name"));
+    assert_eq!(payload.next_question_id.as_deref(), Some("name"));
+
+    // Read-only values can also come from the runtime state.
+    let pinned = json!({ "state": { "region_pinned": true } });
+    assert_eq!(next(&spec, &unlocked, &pinned).as_deref(), Some("name"));
+}
+
+#[test]
+fn changing_a_read_only_answer_is_rejected() {
+    let spec = spec();
+    let previous = json!({ "locked": true, "region": "eu" });
+
+    let edited = json!({ "locked": true, "region": "us" });
+    assert!(validate(&spec, &edited).valid);
+    let result = validate_update(&spec, &previous, &edited);
+    assert!(!result.valid);
+    assert_eq!(result.errors.len(), 1, "{result:?}");
+    assert_eq!(result.errors[0].code.as_deref(), Some("read_only"));
+    assert_eq!(result.errors[0].question_id.as_deref(), Some("region"));
+
+    let cleared = json!({ "locked": true });
+    assert!(!validate_update(&spec, &previous, &cleared).valid);
+
+    // Unchanged or unlocked answers are fine.
+    assert!(
+        validate_update(
+            &spec,
+            &previous,
+            &json!({ "locked": true, "region": "eu", "name": "api" })
+        )
+        .valid
+    );
+    assert!(
+        validate_update(
+            &spec,
+            &previous,
+            &json!({ "locked": false, "region": "us" })
+        )
+        .valid
+    );
+}
+
+#[test]
+fn cards_show_read_only_answers_as_facts() {
+    let spec = spec();
+    let payload = build_render_payload(
+        &spec,
+        &json!({}),
+        &json!({ "locked": true, "region": "eu" }),
+    );
+    let card = render_card_with_options(
+        &payload,
+        &RenderOptions::default().with_card_mode(CardMode::Form),
+    );
+    let containers = card["body"]
+        .as_array()
+        .expect("body")
+        .iter()
+        .filter(|item| item["type"] == "Container")
+        .collect::<Vec<_>>();
+    assert_eq!(containers.len(), 3, "{card}");
+    assert_eq!(
+        containers[1]["items"][0],
+        json!({ "type": "FactSet", "facts": [{ "title": "Region", "value": "eu" }] })
+    );
+    assert!(
+        !card.to_string().contains(r#""id":"region""#),
+        "no input for the read-only question: {card}"
+    );
+}
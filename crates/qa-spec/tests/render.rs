@@ -4,9 +4,12 @@ use std::collections::BTreeMap;
 use qa_spec::{
     FormSpec,
     render::{
-        RenderStatus, build_render_payload, build_render_payload_with_i18n, render_card,
-        render_json_ui, render_text,
+        CardOptions, CardVersion, DEFAULT_MAX_CURRENT_VALUE_ITEMS, RenderOptions, RenderStatus,
+        build_render_payload, build_render_payload_with_i18n, build_render_payload_with_options,
+        render_card, render_card_with_options, render_json_ui, render_json_ui_to, render_markdown,
+        render_text, render_text_to,
     },
+    spec::flow::CardMode,
 };
 
 fn fixture(name: &str) -> &'static str {
@@ -42,6 +45,15 @@ fn render_json_ui_exposes_structure() {
     let ui = render_json_ui(&payload);
     assert_eq!(ui["form_id"], "example-form");
     assert_eq!(ui["progress"]["total"], 2);
+    assert_eq!(ui["progress"]["remaining"], json!(["q2"]));
+    assert_eq!(ui["progress"]["percent"], 50.0);
+
+    let card = render_card(&payload);
+    let facts = card.to_string();
+    assert!(
+        facts.contains(r#"{"title":"Complete","value":"50%"}"#),
+        "{facts}"
+    );
     let questions = ui["questions"].as_array().expect("questions array");
     assert!(questions.iter().any(|q| q["id"] == "q1"));
     assert!(matches!(questions[0]["visible"].as_bool(), Some(true)));
@@ -171,3 +183,738 @@ fn render_payload_uses_requested_then_default_locale_then_raw_fallback() {
     );
     assert_eq!(payload_raw.questions[0].title, "Raw fallback");
 }
+
+#[test]
+fn flow_message_is_rendered_inline() {
+    let spec: FormSpec = serde_json::from_str(fixture("simple_form")).expect("deserialize");
+    let payload = build_render_payload(&spec, &json!({}), &json!({}))
+        .with_message("Great, we'll deploy billing to eu-west-1");
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["message"], "Great, we'll deploy billing to eu-west-1");
+    assert!(render_text(&payload).contains("Great, we'll deploy billing to eu-west-1"));
+    let card = render_card(&payload);
+    let body = card["body"].as_array().expect("body");
+    assert!(
+        body.iter()
+            .any(|item| item["text"] == "Great, we'll deploy billing to eu-west-1")
+    );
+
+    let plain = render_json_ui(&build_render_payload(&spec, &json!({}), &json!({})));
+    assert!(plain.get("message").is_none());
+}
+
+fn sectioned_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "service", "type": "string", "title": "Service", "required": true },
+            { "id": "db.host", "type": "string", "title": "DB host", "required": true },
+            { "id": "db.port", "type": "integer", "title": "DB port", "required": true }
+        ]
+    }))
+    .expect("deserialize")
+}
+
+fn card_inputs(card: &serde_json::Value) -> Vec<String> {
+    card["body"]
+        .as_array()
+        .expect("body")
+        .iter()
+        .filter(|item| item["type"] == "Container")
+        .map(|item| {
+            item["items"]
+                .as_array()
+                .expect("items")
+                .last()
+                .expect("input")["id"]
+                .as_str()
+                .expect("id")
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn card_mode_controls_grouping_and_submit_action() {
+    let spec = sectioned_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({ "service": "billing" }));
+    let options = RenderOptions::for_form(&spec);
+
+    let single = render_card_with_options(&payload, &options);
+    assert_eq!(single, render_card(&payload));
+    assert_eq!(card_inputs(&single), vec!["db.host"]);
+    assert_eq!(single["actions"][0]["data"]["qa"]["mode"], "patch");
+
+    let section = render_card_with_options(&payload, &options.with_card_mode(CardMode::Section));
+    assert_eq!(card_inputs(&section), vec!["db.host", "db.port"]);
+    assert_eq!(section["actions"][0]["data"]["qa"]["mode"], "patch_batch");
+    assert_eq!(
+        section["actions"][0]["data"]["qa"]["questionIds"],
+        json!(["db.host", "db.port"])
+    );
+
+    let form = render_card_with_options(&payload, &options.with_card_mode(CardMode::Form));
+    assert_eq!(card_inputs(&form), vec!["service", "db.host", "db.port"]);
+    assert_eq!(form["actions"][0]["data"]["qa"]["mode"], "submit_all");
+}
+
+#[test]
+fn presentation_card_mode_is_the_default() {
+    let mut spec = sectioned_form();
+    spec.presentation =
+        Some(serde_json::from_value(json!({ "card_mode": "form" })).expect("presentation"));
+    assert_eq!(RenderOptions::for_form(&spec).card_mode, CardMode::Form);
+}
+
+#[test]
+fn list_entries_only_show_visible_fields() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "servers",
+        "title": "Servers",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "servers",
+            "type": "list",
+            "title": "Servers",
+            "list": {
+                "fields": [
+                    { "id": "host", "type": "string", "title": "Host" },
+                    { "id": "tls", "type": "boolean", "title": "TLS" },
+                    {
+                        "id": "tls_cert",
+                        "type": "string",
+                        "title": "Certificate",
+                        "visible_if": { "op": "var", "path": "item.tls" }
+                    }
+                ]
+            }
+        }, { "id": "owner", "type": "string", "title": "Owner", "required": true }]
+    }))
+    .expect("deserialize");
+    let answers = json!({
+        "servers": [
+            { "host": "a", "tls": true, "tls_cert": "a.pem" },
+            { "host": "b", "tls": false, "tls_cert": "stale.pem" }
+        ]
+    });
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    assert_eq!(
+        payload.questions[0].list_entry_fields,
+        vec![vec!["host", "tls", "tls_cert"], vec!["host", "tls"]]
+    );
+
+    let card = render_card_with_options(
+        &payload,
+        &RenderOptions::for_form(&spec).with_card_mode(CardMode::Form),
+    );
+    let facts = card["body"]
+        .as_array()
+        .expect("body")
+        .iter()
+        .find(|item| item["type"] == "Container")
+        .expect("list container")["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .filter(|item| item["type"] == "FactSet")
+        .map(|item| item["facts"].as_array().expect("facts").len())
+        .collect::<Vec<_>>();
+    assert_eq!(facts, vec![3, 2]);
+}
+
+#[test]
+fn long_list_answers_are_truncated_unless_disabled() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "hosts",
+        "title": "Hosts",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "hosts",
+            "type": "list",
+            "title": "Hosts",
+            "list": {
+                "fields": [
+                    { "id": "name", "type": "string", "title": "Name" },
+                    { "id": "token", "type": "string", "title": "Token", "secret": true }
+                ]
+            }
+        }, { "id": "owner", "type": "string", "title": "Owner", "required": true }]
+    }))
+    .expect("deserialize");
+    let total = DEFAULT_MAX_CURRENT_VALUE_ITEMS + 5;
+    let hosts = (0..total)
+        .map(|index| json!({ "name": format!("host-{index}"), "token": "t" }))
+        .collect::<Vec<_>>();
+    let answers = json!({ "hosts": hosts });
+
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let question = &payload.questions[0];
+    let shown = question
+        .current_value
+        .as_ref()
+        .and_then(|value| value.as_array())
+        .expect("list value");
+    assert_eq!(shown.len(), DEFAULT_MAX_CURRENT_VALUE_ITEMS);
+    assert_eq!(shown[0]["name"], "host-0");
+    assert_eq!(shown[0]["token"], json!({ "$redacted": true }));
+    assert_eq!(question.current_value_total, Some(total));
+    assert_eq!(
+        question.list_entry_fields.len(),
+        DEFAULT_MAX_CURRENT_VALUE_ITEMS
+    );
+    assert_eq!(
+        render_json_ui(&payload)["questions"][0]["current_value_total"],
+        total
+    );
+    assert!(render_text(&payload).contains(&format!(
+        "(first {} of {} items)",
+        DEFAULT_MAX_CURRENT_VALUE_ITEMS, total
+    )));
+    let card = render_card_with_options(
+        &payload,
+        &RenderOptions::for_form(&spec).with_card_mode(CardMode::Form),
+    );
+    assert!(
+        card.to_string()
+            .contains(&format!("List group 'Hosts' ({} entries)", total))
+    );
+
+    let options = RenderOptions::for_form(&spec).with_max_current_value_items(None);
+    let payload = build_render_payload_with_options(&spec, &json!({}), &answers, None, &options);
+    let question = &payload.questions[0];
+    assert_eq!(
+        question
+            .current_value
+            .as_ref()
+            .and_then(|value| value.as_array())
+            .map(Vec::len),
+        Some(total)
+    );
+    assert_eq!(question.current_value_total, None);
+    assert!(
+        render_json_ui(&payload)["questions"][0]
+            .get("current_value_total")
+            .is_none()
+    );
+}
+
+#[test]
+fn render_text_describes_exclusive_bounds() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "ratio",
+        "title": "Ratio",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "ratio",
+            "type": "number",
+            "title": "Ratio",
+            "constraint": { "min": 0, "exclusive_min": true, "max": 1 }
+        }]
+    }))
+    .expect("deserialize");
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+    let text = render_text(&payload);
+    assert!(text.contains("Allowed: greater than 0 and at most 1"));
+    assert_eq!(
+        render_json_ui(&payload)["questions"][0]["constraint"]["exclusive_min"],
+        true
+    );
+}
+
+#[test]
+fn render_payloads_do_not_expose_secret_values() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "login",
+        "title": "Login",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "user", "type": "string", "title": "User" },
+            { "id": "token", "type": "string", "title": "Token", "secret": true }
+        ]
+    }))
+    .expect("deserialize");
+    let answers = json!({ "user": "alice", "token": "tok-hunter2" });
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+
+    let outputs = [
+        render_text(&payload),
+        render_json_ui(&payload).to_string(),
+        render_card(&payload).to_string(),
+    ];
+    for output in outputs {
+        assert!(!output.contains("hunter2"), "{output}");
+    }
+    assert!(render_text(&payload).contains("alice"));
+}
+
+fn streaming_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "service",
+        "title": "Service",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "description": "Service name", "required": true },
+            { "id": "tier", "type": "enum", "title": "Tier", "choices": ["free", "paid"], "default_value": "free" },
+            {
+                "id": "ratio",
+                "type": "number",
+                "title": "Ratio",
+                "required": true,
+                "constraint": { "min": 0, "exclusive_min": true, "max": 1 }
+            },
+            {
+                "id": "hosts",
+                "type": "list",
+                "title": "Hosts",
+                "list": { "fields": [{ "id": "host", "type": "string", "title": "Host" }] }
+            }
+        ]
+    }))
+    .expect("deserialize")
+}
+
+#[test]
+fn streaming_renderers_match_the_buffered_output() {
+    let spec = streaming_form();
+    for answers in [
+        json!({}),
+        json!({ "name": "api", "hosts": [{ "host": "a" }, { "host": "b" }] }),
+        json!({ "name": "api", "tier": "paid", "ratio": 0.5, "hosts": [] }),
+    ] {
+        for payload in [
+            build_render_payload(&spec, &json!({}), &answers),
+            build_render_payload(&spec, &json!({}), &answers).with_message("Almost there"),
+        ] {
+            let mut streamed = Vec::new();
+            render_json_ui_to(&payload, &mut streamed).expect("stream json");
+            assert_eq!(
+                String::from_utf8(streamed).expect("utf8"),
+                render_json_ui(&payload).to_string()
+            );
+
+            let mut text = String::new();
+            render_text_to(&payload, &mut text).expect("stream text");
+            assert_eq!(text, render_text(&payload));
+        }
+    }
+}
+
+#[test]
+fn render_text_lines_are_newline_separated() {
+    let spec = streaming_form();
+    let answers = json!({ "name": "api", "hosts": [{ "host": "a" }] });
+    let payload = build_render_payload(&spec, &json!({}), &answers).with_message("Almost there");
+
+    assert_eq!(
+        render_text(&payload),
+        [
+            "Form: Service (service)",
+            "Status: need_input (2/4)",
+            "Almost there",
+            "Next question: tier",
+            "  Title: Tier",
+            "  Default: free",
+            "Visible questions:",
+            " - name (Name) [required] = api",
+            " - tier (Tier)",
+            " - ratio (Ratio) [required]",
+            " - hosts (Hosts) = [{\"host\":\"a\"}]",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn secret_values_never_reach_rendered_output() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "creds",
+        "title": "Credentials",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "api_key", "type": "string", "title": "API key", "secret": true },
+            {
+                "id": "hosts",
+                "type": "list",
+                "title": "Hosts",
+                "list": {
+                    "fields": [
+                        { "id": "name", "type": "string", "title": "Name" },
+                        { "id": "token", "type": "string", "title": "Token", "secret": true }
+                    ]
+                }
+            },
+            { "id": "owner", "type": "string", "title": "Owner", "required": true }
+        ]
+    }))
+    .expect("deserialize");
+    let answers = json!({
+        "api_key": "sk-hunter2",
+        "hosts": [{ "name": "edge", "token": "tok-hunter2" }]
+    });
+
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let mut outputs = vec![
+        render_text(&payload),
+        render_json_ui(&payload).to_string(),
+        render_card(&payload).to_string(),
+        render_markdown(&payload),
+    ];
+    for card_mode in [CardMode::Section, CardMode::Form, CardMode::Page] {
+        let options = RenderOptions::for_form(&spec).with_card_mode(card_mode);
+        outputs.push(render_card_with_options(&payload, &options).to_string());
+    }
+    for output in &outputs {
+        assert!(!output.contains("hunter2"), "{output}");
+    }
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(
+        ui["questions"][0]["current_value"],
+        json!({ "$redacted": true })
+    );
+    assert_eq!(ui["questions"][0]["has_value"], true);
+    assert!(ui["questions"][2].get("has_value").is_none());
+    assert_eq!(
+        ui["questions"][1]["current_value"][0]["token"],
+        json!({ "$redacted": true })
+    );
+    assert_eq!(ui["questions"][1]["current_value"][0]["name"], "edge");
+}
+
+fn hosts_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "hosts",
+        "title": "Hosts",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "hosts",
+            "type": "list",
+            "title": "Hosts",
+            "list": {
+                "fields": [
+                    { "id": "name", "type": "string", "title": "Name" },
+                    { "id": "port", "type": "integer", "title": "Port" }
+                ]
+            }
+        }, { "id": "owner", "type": "string", "title": "Owner", "required": true }]
+    }))
+    .expect("deserialize")
+}
+
+fn find_input<'a>(node: &'a serde_json::Value, id: &str) -> Option<&'a serde_json::Value> {
+    if node["id"] == id
+        && node["type"]
+            .as_str()
+            .is_some_and(|kind| kind.starts_with("Input."))
+    {
+        return Some(node);
+    }
+    match node {
+        serde_json::Value::Array(items) => items.iter().find_map(|item| find_input(item, id)),
+        serde_json::Value::Object(map) => map.values().find_map(|value| find_input(value, id)),
+        _ => None,
+    }
+}
+
+#[test]
+fn empty_list_questions_render_a_json_input() {
+    let spec = hosts_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["type"], "list");
+    assert_eq!(ui["questions"][0]["list"]["fields"][1]["id"], "port");
+
+    let card = render_card(&payload);
+    assert!(card.to_string().contains("List group 'Hosts' (0 entries)"));
+    let input = find_input(&card, "hosts").expect("list input");
+    assert_eq!(input["type"], "Input.Text");
+    assert_eq!(input["isMultiline"], true);
+    assert!(
+        input["placeholder"]
+            .as_str()
+            .expect("placeholder")
+            .contains("\"port\"")
+    );
+    assert!(input.get("value").is_none());
+}
+
+#[test]
+fn list_questions_with_entries_render_rows_and_prefill_the_input() {
+    let spec = hosts_form();
+    let answers = json!({
+        "hosts": [{ "name": "a", "port": 80 }, { "name": "b", "port": 443 }]
+    });
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+
+    let ui = render_json_ui(&payload);
+    assert_eq!(ui["questions"][0]["type"], "list");
+    assert_eq!(ui["questions"][0]["current_value"], answers["hosts"]);
+
+    let card = render_card_with_options(
+        &payload,
+        &RenderOptions::for_form(&spec).with_card_mode(CardMode::Form),
+    );
+    assert!(card.to_string().contains("List group 'Hosts' (2 entries)"));
+    assert!(card.to_string().contains("\"443\""), "{card}");
+    let input = find_input(&card, "hosts").expect("list input");
+    let value: serde_json::Value =
+        serde_json::from_str(input["value"].as_str().expect("prefilled")).expect("json");
+    assert_eq!(value, answers["hosts"]);
+}
+
+fn scheduling_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "schedule",
+        "title": "Schedule",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "required": true,
+                "constraint": { "min": 1, "max": 9 }
+            },
+            { "id": "ratio", "type": "number", "title": "Ratio", "constraint": { "max": 0.5 } },
+            { "id": "launch", "type": "date", "title": "Launch" },
+            { "id": "window", "type": "time", "title": "Window" },
+            {
+                "id": "notes",
+                "type": "string",
+                "title": "Notes",
+                "visible_if": { "op": "literal", "value": false }
+            }
+        ]
+    }))
+    .expect("deserialize")
+}
+
+fn form_card(spec: &FormSpec, answers: serde_json::Value, card: CardOptions) -> serde_json::Value {
+    let payload = build_render_payload(spec, &json!({}), &answers);
+    render_card_with_options(
+        &payload,
+        &RenderOptions::for_form(spec)
+            .with_card_mode(CardMode::Form)
+            .with_card_options(card),
+    )
+}
+
+fn input_types(card: &serde_json::Value) -> Vec<(String, String)> {
+    card["body"]
+        .as_array()
+        .expect("body")
+        .iter()
+        .filter_map(|item| item["items"].as_array())
+        .flat_map(|items| items.iter())
+        .filter(|item| item["id"].is_string())
+        .map(|item| {
+            (
+                item["id"].as_str().unwrap_or_default().to_string(),
+                item["type"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn card_options_pick_the_version_and_input_elements() {
+    let spec = scheduling_form();
+
+    let card = form_card(&spec, json!({}), CardOptions::default());
+    assert_eq!(card["version"], "1.3");
+    assert_eq!(
+        input_types(&card),
+        [
+            ("replicas", "Input.Text"),
+            ("ratio", "Input.Text"),
+            ("launch", "Input.Date"),
+            ("window", "Input.Time"),
+        ]
+        .map(|(id, kind)| (id.to_string(), kind.to_string()))
+    );
+
+    let card = form_card(
+        &spec,
+        json!({ "ratio": 0.25 }),
+        CardOptions::for_version(CardVersion::V1_5),
+    );
+    assert_eq!(card["version"], "1.5");
+    let replicas = &card["body"][2]["items"][1];
+    assert_eq!(replicas["type"], "Input.Number");
+    assert_eq!(replicas["min"], 1);
+    assert_eq!(replicas["max"], 9);
+    let ratio = &card["body"][3]["items"][1];
+    assert_eq!(ratio["type"], "Input.Number");
+    assert_eq!(ratio["max"], 0.5);
+    assert_eq!(ratio["value"], 0.25);
+
+    let card = form_card(
+        &spec,
+        json!({}),
+        CardOptions {
+            version: CardVersion::V1_5,
+            use_native_number_input: false,
+            use_native_date_input: false,
+            ..CardOptions::default()
+        },
+    );
+    let types = input_types(&card);
+    assert!(
+        types.iter().all(|(_, kind)| kind == "Input.Text"),
+        "{types:?}"
+    );
+    assert_eq!(card["body"][4]["items"][1]["placeholder"], "YYYY-MM-DD");
+    assert_eq!(card["body"][5]["items"][1]["placeholder"], "HH:MM");
+}
+
+#[test]
+fn non_compact_cards_keep_hidden_questions_invisible() {
+    let spec = scheduling_form();
+
+    let compact = form_card(&spec, json!({}), CardOptions::default());
+    assert!(!compact.to_string().contains("\"notes\""));
+
+    let card = form_card(
+        &spec,
+        json!({}),
+        CardOptions {
+            compact: false,
+            ..CardOptions::default()
+        },
+    );
+    let hidden = card["body"]
+        .as_array()
+        .expect("body")
+        .iter()
+        .find(|item| item["items"][1]["id"] == "notes")
+        .expect("hidden question");
+    assert_eq!(hidden["isVisible"], false);
+    assert!(card["body"][2].get("isVisible").is_none());
+}
+
+fn paged_form(presentation: serde_json::Value, count: usize) -> FormSpec {
+    let questions = (1..=count)
+        .map(|index| json!({ "id": format!("q{index}"), "type": "string", "title": format!("Q{index}") }))
+        .collect::<Vec<_>>();
+    serde_json::from_value(json!({
+        "id": "paged",
+        "title": "Paged",
+        "version": "1.0.0",
+        "presentation": presentation,
+        "questions": questions
+    }))
+    .expect("deserialize")
+}
+
+#[test]
+fn all_mode_renders_every_input_behind_one_submit_all() {
+    let spec = paged_form(json!({ "card_mode": "all" }), 3);
+    assert_eq!(spec.card_mode(), CardMode::Form);
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+    let card = render_card_with_options(&payload, &RenderOptions::for_form(&spec));
+
+    assert_eq!(card_inputs(&card), vec!["q1", "q2", "q3"]);
+    assert_eq!(
+        card["actions"],
+        json!([{
+            "type": "Action.Submit",
+            "title": "Submit ✅",
+            "data": { "qa": { "formId": "paged", "mode": "submit_all" } }
+        }])
+    );
+}
+
+#[test]
+fn page_mode_renders_a_page_of_questions_with_navigation() {
+    let spec = paged_form(json!({ "card_mode": "page", "page_size": 2 }), 5);
+    let options = RenderOptions::for_form(&spec);
+    assert_eq!(options.page_size, 2);
+
+    let payload = build_render_payload(&spec, &json!({}), &json!({}));
+    let card = render_card_with_options(&payload, &options);
+    assert_eq!(card["body"][2]["text"], "Page 1 of 3");
+    assert_eq!(card_inputs(&card), vec!["q1", "q2"]);
+    assert_eq!(
+        card["actions"],
+        json!([{
+            "type": "Action.Submit",
+            "title": "Next ➡️",
+            "data": {
+                "qa": { "formId": "paged", "mode": "patch_batch", "questionIds": ["q1", "q2"], "page": 1 }
+            }
+        }])
+    );
+
+    let answers = json!({ "q1": "a", "q2": "b" });
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let card = render_card_with_options(&payload, &options);
+    assert_eq!(card_inputs(&card), vec!["q3", "q4"]);
+    assert_eq!(card["actions"][0]["title"], "⬅️ Previous");
+    assert_eq!(
+        card["actions"][0]["data"]["qa"],
+        json!({ "formId": "paged", "mode": "page", "page": 0 })
+    );
+    assert_eq!(card["actions"][1]["data"]["qa"]["page"], 2);
+
+    let previous = options.with_card_options(CardOptions {
+        page: Some(0),
+        ..CardOptions::default()
+    });
+    let card = render_card_with_options(&payload, &previous);
+    assert_eq!(card_inputs(&card), vec!["q1", "q2"]);
+
+    let last = options.with_card_options(CardOptions {
+        page_size: Some(4),
+        ..CardOptions::default()
+    });
+    let payload = build_render_payload(
+        &spec,
+        &json!({}),
+        &json!({ "q1": "a", "q2": "b", "q3": "c", "q4": "d" }),
+    );
+    let card = render_card_with_options(&payload, &last);
+    assert_eq!(card["body"][2]["text"], "Page 2 of 2");
+    assert_eq!(card_inputs(&card), vec!["q5"]);
+    assert_eq!(
+        card["actions"][1]["data"]["qa"],
+        json!({ "formId": "paged", "mode": "patch_batch", "questionIds": ["q5"] })
+    );
+    assert_eq!(card["actions"][1]["title"], "Submit ✅");
+}
+
+#[test]
+fn trusted_hosts_can_reveal_secrets_in_render_payloads() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "creds",
+        "title": "Credentials",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "owner", "type": "string", "title": "Owner", "required": true },
+            { "id": "api_key", "type": "string", "title": "API key", "secret": true }
+        ]
+    }))
+    .expect("deserialize");
+
+    let ui = render_json_ui(&build_render_payload(&spec, &json!({}), &json!({})));
+    assert_eq!(ui["questions"][1]["has_value"], false);
+
+    let answers = json!({ "api_key": "sk-hunter2" });
+    let options = RenderOptions::for_form(&spec)
+        .with_card_mode(CardMode::Form)
+        .with_reveal_secrets(true);
+    let payload = build_render_payload_with_options(&spec, &json!({}), &answers, None, &options);
+    assert_eq!(
+        payload.questions[1].current_value,
+        Some(json!("sk-hunter2"))
+    );
+    assert_eq!(render_json_ui(&payload)["questions"][1]["has_value"], true);
+    assert!(
+        render_card_with_options(&payload, &options)
+            .to_string()
+            .contains("sk-hunter2")
+    );
+}
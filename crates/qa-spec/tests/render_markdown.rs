@@ -0,0 +1,167 @@
+use serde_json::json;
+
+use qa_spec::{FormSpec, build_render_payload, render_markdown};
+
+fn every_type_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy | staging",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true },
+            { "id": "enabled", "type": "boolean", "title": "Enabled" },
+            {
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "constraint": { "min": 1, "max": 5 }
+            },
+            {
+                "id": "ratio",
+                "type": "number",
+                "title": "Ratio",
+                "constraint": { "decimal_places": 2 }
+            },
+            {
+                "id": "tier",
+                "type": "enum",
+                "title": "Tier",
+                "description": "Billing tier.\nPaid tiers get support.",
+                "required": true,
+                "choices": ["free", "paid"],
+                "default_value": "free"
+            },
+            {
+                "id": "hosts",
+                "type": "list",
+                "title": "Hosts",
+                "list": { "fields": [{ "id": "host", "type": "string", "title": "Host" }] }
+            },
+            { "id": "launch", "type": "date", "title": "Launch" },
+            {
+                "id": "regions",
+                "type": "multi_enum",
+                "title": "Regions",
+                "choices": ["eu", "us"]
+            },
+            { "id": "root", "type": "path", "title": "Root" },
+            { "id": "timeout", "type": "duration", "title": "Timeout" },
+            {
+                "id": "owner",
+                "type": "object",
+                "title": "Owner",
+                "object": { "fields": [{ "id": "email", "type": "string", "title": "Email" }] }
+            },
+            { "id": "tags", "type": "string_list", "title": "Tags" },
+            { "id": "score", "type": "rating", "title": "Score" },
+            { "id": "accent", "type": "color", "title": "Accent" },
+            { "id": "notes", "type": "markdown", "title": "Notes" },
+            { "id": "window", "type": "time", "title": "Window" },
+            { "id": "token", "type": "string", "title": "Token", "secret": true }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn full_answers() -> serde_json::Value {
+    json!({
+        "name": "api | edge",
+        "enabled": true,
+        "replicas": 3,
+        "ratio": 0.25,
+        "tier": "paid",
+        "hosts": [{ "host": "a" }],
+        "launch": "2026-01-02",
+        "regions": ["eu"],
+        "root": "/srv",
+        "timeout": 90,
+        "owner": { "email": "ops@example.com" },
+        "tags": ["x", "y"],
+        "score": 4,
+        "accent": "#ff0000",
+        "notes": "# Hello\n\nWorld",
+        "window": "09:30",
+        "token": "hunter2"
+    })
+}
+
+fn markdown_without(id: &str) -> String {
+    let mut answers = full_answers();
+    answers.as_object_mut().expect("object").remove(id);
+    let payload = build_render_payload(&every_type_form(), &json!({}), &answers);
+    render_markdown(&payload)
+}
+
+#[test]
+fn markdown_lists_every_question_type_and_the_next_question() {
+    assert_eq!(
+        markdown_without("tier"),
+        [
+            "## Deploy | staging",
+            "",
+            "**Progress:** 16 of 17 answered (94.1%) · `need_input`",
+            "",
+            "| Question | Type | Required | Value |",
+            "| --- | --- | --- | --- |",
+            "| Name (`name`) | string | yes | api \\| edge |",
+            "| Enabled (`enabled`) | boolean |  | true |",
+            "| Replicas (`replicas`) | integer |  | 3 |",
+            "| Ratio (`ratio`) | number |  | 0.25 |",
+            "| Tier (`tier`) | enum | yes |  |",
+            "| Hosts (`hosts`) | list |  | [{\"host\":\"a\"}] |",
+            "| Launch (`launch`) | date |  | 2026-01-02 |",
+            "| Regions (`regions`) | multi_enum |  | [\"eu\"] |",
+            "| Root (`root`) | path |  | /srv |",
+            "| Timeout (`timeout`) | duration |  | 90 |",
+            "| Owner (`owner`) | object |  | {\"email\":\"ops@example.com\"} |",
+            "| Tags (`tags`) | string_list |  | [\"x\",\"y\"] |",
+            "| Score (`score`) | rating |  | 4 |",
+            "| Accent (`accent`) | color |  | #ff0000 |",
+            "| Notes (`notes`) | markdown |  | # Hello World |",
+            "| Window (`window`) | time |  | 09:30 |",
+            "| Token (`token`) | string |  | ******** |",
+            "",
+            "> **Next question:** Tier (`tier`)",
+            ">",
+            "> Billing tier.",
+            "> Paid tiers get support.",
+            ">",
+            "> - Type: enum",
+            "> - Required: yes",
+            "> - Choices: `free`, `paid`",
+            "> - Default: free",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn markdown_callout_describes_what_the_next_question_accepts() {
+    let callout = |id: &str| {
+        let markdown = markdown_without(id);
+        let start = markdown.find("> **Next question:**").expect("callout");
+        markdown[start..].to_string()
+    };
+
+    assert_eq!(
+        callout("replicas"),
+        "> **Next question:** Replicas (`replicas`)\n>\n> - Type: integer\n> - Allowed: at least 1 and at most 5"
+    );
+    assert!(callout("ratio").ends_with("> - Precision: 2 decimal places"));
+    assert!(callout("hosts").ends_with("> - Entry fields: `host`"));
+    assert!(callout("owner").ends_with("> - Fields: email"));
+    assert!(callout("score").ends_with("> - Scale: 1-5"));
+    assert!(callout("timeout").contains("> - Syntax: "));
+}
+
+#[test]
+fn markdown_reports_completion_without_secrets() {
+    let payload = build_render_payload(&every_type_form(), &json!({}), &full_answers());
+    let markdown = render_markdown(&payload);
+
+    assert!(markdown.starts_with(
+        "## Deploy | staging\n\n**Progress:** 17 of 17 answered (100%) · `complete`\n"
+    ));
+    assert!(markdown.ends_with("\n\nAll visible questions are answered."));
+    assert!(!markdown.contains("hunter2"));
+}
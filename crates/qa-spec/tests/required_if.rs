@@ -0,0 +1,121 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, ProgressContext, answers_schema, build_render_payload, next_question,
+    resolve_visibility, validate, visibility::VisibilityMode,
+};
+
+fn proxy_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "proxy",
+        "title": "Proxy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "proxy_user", "type": "string", "title": "Proxy username" },
+            {
+                "id": "proxy_password",
+                "type": "string",
+                "title": "Proxy password",
+                "required": true,
+                "required_if": { "op": "is_set", "path": "proxy_user" }
+            },
+            {
+                "id": "headers",
+                "type": "list",
+                "title": "Headers",
+                "list": {
+                    "fields": [
+                        { "id": "name", "type": "string", "title": "Name", "required": true },
+                        {
+                            "id": "value",
+                            "type": "string",
+                            "title": "Value",
+                            "required_if": { "op": "eq", "left": { "op": "var", "path": "item.name" }, "right": { "op": "literal", "value": "Authorization" } }
+                        }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn required_if_overrides_required() {
+    let spec = proxy_form();
+
+    assert!(validate(&spec, &json!({})).valid);
+
+    let result = validate(&spec, &json!({ "proxy_user": "alice" }));
+    assert_eq!(result.missing_required, vec!["proxy_password".to_string()]);
+
+    assert!(
+        validate(
+            &spec,
+            &json!({ "proxy_user": "alice", "proxy_password": "s3cret" })
+        )
+        .valid
+    );
+}
+
+#[test]
+fn list_fields_evaluate_required_if_per_entry() {
+    let spec = proxy_form();
+
+    let answers = json!({ "headers": [{ "name": "Accept" }] });
+    assert!(validate(&spec, &answers).valid);
+
+    let answers = json!({ "headers": [{ "name": "Authorization" }] });
+    let result = validate(&spec, &answers);
+    assert_eq!(result.errors[0].code.as_deref(), Some("missing_field"));
+    assert_eq!(result.errors[0].path.as_deref(), Some("/headers/0/value"));
+}
+
+#[test]
+fn render_payload_marks_requiredness_for_the_answers() {
+    let spec = proxy_form();
+    let required = |answers| {
+        let payload = build_render_payload(&spec, &json!({}), &answers);
+        payload.questions[1].required
+    };
+
+    assert!(!required(json!({})));
+    assert!(required(json!({ "proxy_user": "alice" })));
+
+    let visibility = resolve_visibility(&spec, &json!({}), VisibilityMode::Visible);
+    let answers = json!({ "proxy_user": "alice" });
+    let ctx = json!({});
+    let progress = ProgressContext::new(&answers, &ctx);
+    assert_eq!(
+        next_question(&spec, &progress, &visibility).as_deref(),
+        Some("proxy_password")
+    );
+}
+
+#[test]
+fn schema_expresses_required_if_as_if_then() {
+    let spec = proxy_form();
+    let schema = answers_schema(&spec, &Default::default());
+
+    assert!(schema.get("required").is_none(), "{schema}");
+    assert_eq!(
+        schema["allOf"],
+        json!([{
+            "if": { "required": ["proxy_user"] },
+            "then": { "required": ["proxy_password"] }
+        }])
+    );
+
+    let items = &schema["properties"]["headers"]["items"];
+    assert_eq!(items["required"], json!(["name"]));
+    assert_eq!(
+        items["allOf"],
+        json!([{
+            "if": {
+                "properties": { "name": { "const": "Authorization" } },
+                "required": ["name"]
+            },
+            "then": { "required": ["value"] }
+        }])
+    );
+}
@@ -76,3 +76,44 @@ fn execute_plan_effects_applies_only_for_valid_plan() {
     .expect("valid plan should apply effects");
     assert_eq!(valid_store.state["applied"], true);
 }
+
+#[test]
+fn plans_carry_normalized_answers() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "runner-form",
+        "title": "Runner",
+        "version": "1.0",
+        "questions": [
+            { "id": "q1", "type": "string", "title": "Question 1", "normalize": ["trim"] }
+        ]
+    }))
+    .expect("fixture should deserialize");
+
+    let plan = plan_submit_patch(&spec, &json!({}), &json!({}), "q1", json!("  ok  "));
+    assert_eq!(plan.validated_patch["q1"], "ok");
+}
+
+#[test]
+fn plans_fill_and_report_computed_answers() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "runner-form",
+        "title": "Runner",
+        "version": "1.0",
+        "questions": [
+            { "id": "q1", "type": "string", "title": "Question 1", "required": true },
+            {
+                "id": "q2",
+                "type": "string",
+                "title": "Question 2",
+                "computed": { "op": "answer", "path": "q1" }
+            }
+        ]
+    }))
+    .expect("fixture should deserialize");
+
+    let plan = plan_submit_all(&spec, &json!({}), &json!({ "q1": "copy" }));
+    assert_eq!(plan.validated_patch["q2"], "copy");
+    assert_eq!(plan.computed.len(), 1);
+    assert_eq!(plan.computed[0].question_id, "q2");
+    assert_eq!(plan.computed[0].sources, vec!["q1"]);
+}
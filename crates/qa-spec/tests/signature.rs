@@ -0,0 +1,68 @@
+use serde_json::json;
+
+use qa_spec::{AnswerSet, canonical_json};
+
+fn signed_fixture() -> AnswerSet {
+    let mut set = AnswerSet::new("support-form", "1.0.0");
+    set.answers = json!({ "retries": 3.0, "name": "Zoë", "enabled": true, "ratio": 0.5 });
+    set
+}
+
+#[test]
+fn canonical_json_sorts_keys_and_normalizes_numbers() {
+    assert_eq!(
+        canonical_json(&json!({ "b": [1.0, -0.0, 2.5], "a": "line\n\"q\"", "c": null })),
+        r#"{"a":"line\u000a\"q\"","b":[1,0,2.5],"c":null}"#
+    );
+    let numbers = json!([1.5e-7, 1e21, 123.456, 0.000001, 1e-7, 2.5e25, 100.0, -42]);
+    assert_eq!(
+        canonical_json(&numbers),
+        "[1.5e-7,1e+21,123.456,0.000001,1e-7,2.5e+25,100,-42]"
+    );
+}
+
+#[test]
+fn signing_bytes_cover_form_identity_and_answers() {
+    let bytes = signed_fixture().signing_bytes();
+    assert_eq!(
+        String::from_utf8(bytes).expect("utf8"),
+        r#"{"answers":{"enabled":true,"name":"Zoë","ratio":0.5,"retries":3},"form_id":"support-form","spec_version":"1.0.0"}"#
+    );
+}
+
+#[cfg(feature = "signing")]
+#[test]
+fn sign_and_verify_detect_tampering() {
+    use qa_spec::{HMAC_SHA256, Meta, SignatureError};
+
+    let mut set = signed_fixture();
+    set.sign_with_key_id("answers-2026", b"k3y");
+    let signature = set.signature().expect("signature").clone();
+    assert_eq!(signature.alg, HMAC_SHA256);
+    assert_eq!(signature.key_id.as_deref(), Some("answers-2026"));
+    // Independently computed HMAC-SHA256 of the documented signing bytes.
+    assert_eq!(
+        signature.value,
+        "dcabaafea6d475570739f18f67ff2b5d5e3a0ed1d5345e9c1dc45f9a73497358"
+    );
+
+    assert_eq!(set.verify(b"k3y"), Ok(()));
+    assert_eq!(set.verify(b"other"), Err(SignatureError::Invalid));
+    assert!(matches!(
+        set.verify_with_key_id("answers-2025", b"k3y"),
+        Err(SignatureError::KeyMismatch { .. })
+    ));
+
+    // Meta is not covered, so stamping it keeps the signature valid.
+    set.meta.as_mut().expect("meta").actor = Some("alice".into());
+    assert_eq!(set.verify(b"k3y"), Ok(()));
+
+    let mut tampered = set.clone();
+    tampered.answers["retries"] = json!(4);
+    assert_eq!(tampered.verify(b"k3y"), Err(SignatureError::Invalid));
+
+    let mut unsigned = signed_fixture();
+    assert_eq!(unsigned.verify(b"k3y"), Err(SignatureError::Missing));
+    unsigned.meta = Some(Meta::default());
+    assert_eq!(unsigned.verify(b"k3y"), Err(SignatureError::Missing));
+}
@@ -0,0 +1,84 @@
+use serde_json::json;
+
+use qa_spec::{FormSpec, SpecParseError};
+
+fn form_with_question(question: serde_json::Value) -> String {
+    json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "service", "type": "string", "title": "Service", "required": true },
+            question
+        ]
+    })
+    .to_string()
+}
+
+#[test]
+fn fixture_parses_strictly() {
+    let spec = FormSpec::from_json_str(include_str!("fixtures/simple_form.json"))
+        .expect("fixture should parse");
+    assert_eq!(spec.id, "example-form");
+}
+
+#[test]
+fn misspelled_key_reports_pointer_and_suggestion() {
+    let raw = form_with_question(json!({
+        "id": "region",
+        "type": "string",
+        "title": "Region",
+        "requird": true
+    }));
+    let err = FormSpec::from_json_str(&raw).expect_err("unknown field");
+    match &err {
+        SpecParseError::UnknownField {
+            pointer,
+            field,
+            suggestion,
+        } => {
+            assert_eq!(pointer, "/questions/1/requird");
+            assert_eq!(field, "requird");
+            assert_eq!(suggestion.as_deref(), Some("required"));
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert!(err.to_string().contains("did you mean 'required'"));
+}
+
+#[test]
+fn wrong_type_reports_pointer_and_value() {
+    let raw = form_with_question(json!({
+        "id": "region",
+        "type": "string",
+        "title": "Region",
+        "required": "yes"
+    }));
+    let err = FormSpec::from_json_str(&raw).expect_err("wrong type");
+    match err {
+        SpecParseError::InvalidValue { pointer, value, .. } => {
+            assert_eq!(pointer, "/questions/1/required");
+            assert_eq!(value, Some(json!("yes")));
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_question_type_suggests_closest_variant() {
+    let raw = form_with_question(json!({
+        "id": "port",
+        "type": "integr",
+        "title": "Port",
+        "required": true
+    }));
+    let err = FormSpec::from_json_str(&raw).expect_err("unknown type");
+    assert_eq!(err.pointer(), Some("/questions/1/type"));
+    assert_eq!(err.suggestion(), Some("integer"));
+}
+
+#[test]
+fn invalid_json_reports_position() {
+    let err = FormSpec::from_json_str("{\n  \"id\": ").expect_err("syntax");
+    assert!(matches!(err, SpecParseError::Syntax { line: 2, .. }));
+}
@@ -61,3 +61,51 @@ fn store_applies_secret_when_allowed() {
     let updated = store_ctx.to_value();
     assert_eq!(updated["secrets"]["aws"]["secret"], "value");
 }
+
+fn secret_path_form(secret: bool, allow: &str) -> qa_spec::FormSpec {
+    serde_json::from_value(json!({
+        "id": "provider",
+        "title": "Provider",
+        "version": "1.0.0",
+        "secrets_policy": {
+            "enabled": true,
+            "read_enabled": true,
+            "write_enabled": true,
+            "allow": [allow]
+        },
+        "questions": [
+            {
+                "id": "api_key",
+                "type": "string",
+                "title": "API key",
+                "required": true,
+                "secret": secret,
+                "secret_path": "aws/api_key"
+            }
+        ]
+    }))
+    .expect("form")
+}
+
+#[test]
+fn secret_path_becomes_implicit_secrets_op() {
+    let spec = secret_path_form(true, "aws/*");
+    let answers = json!({ "api_key": "s3cr3t" });
+    let ops = qa_spec::effective_store_ops(&spec, &answers);
+    assert_eq!(
+        ops,
+        vec![StoreOp {
+            target: StoreTarget::Secrets,
+            path: "/aws/api_key".into(),
+            value: json!("s3cr3t"),
+        }]
+    );
+
+    let mut store_ctx = StoreContext::from_value(&json!({}));
+    store_ctx
+        .apply_ops(&ops, spec.secrets_policy.as_ref(), true)
+        .expect("apply ops");
+    assert_eq!(store_ctx.to_value()["secrets"]["aws"]["api_key"], "s3cr3t");
+
+    assert!(qa_spec::effective_store_ops(&spec, &json!({})).is_empty());
+}
@@ -0,0 +1,163 @@
+use serde_json::json;
+
+use qa_spec::{
+    FormSpec, answers_schema, apply_normalization, build_render_payload, example_answers,
+    render_card, validate,
+};
+
+fn hosts_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "proxy",
+        "title": "Proxy",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "hosts",
+                "type": "string_list",
+                "title": "Allowed hostnames",
+                "required": true,
+                "normalize": ["trim", "lowercase"],
+                "constraint": { "pattern": "^[a-z0-9.-]+$", "max_len": 20 },
+                "list": { "min_items": 1, "max_items": 3, "unique": true }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn validation_checks_count_items_and_uniqueness() {
+    let spec = hosts_form();
+
+    assert!(
+        validate(
+            &spec,
+            &json!({ "hosts": ["example.com", "api.example.com"] })
+        )
+        .valid
+    );
+
+    let result = validate(&spec, &json!({ "hosts": [] }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("min_items"));
+    assert_eq!(result.errors[0].path.as_deref(), Some("/hosts"));
+
+    let result = validate(&spec, &json!({ "hosts": ["a", "b", "c", "d"] }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("max_items"));
+
+    let result = validate(&spec, &json!({ "hosts": ["example.com", 7, "bad host"] }));
+    let codes = result
+        .errors
+        .iter()
+        .map(|error| (error.path.as_deref(), error.code.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec![
+            (Some("/hosts/1"), Some("type_mismatch")),
+            (Some("/hosts/2"), Some("pattern_mismatch")),
+        ]
+    );
+
+    let result = validate(&spec, &json!({ "hosts": ["a.com", "b.com", "a.com"] }));
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].path.as_deref(), Some("/hosts/2"));
+    assert_eq!(result.errors[0].code.as_deref(), Some("duplicate_item"));
+    assert_eq!(result.errors[0].params["value"], "a.com");
+
+    let result = validate(&spec, &json!({ "hosts": "a.com, b.com" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn secret_item_errors_leave_the_value_out() {
+    let spec = FormSpec::from_value(json!({
+        "id": "vault",
+        "title": "Vault",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "keys",
+                "type": "string_list",
+                "title": "API keys",
+                "secret": true,
+                "constraint": { "pattern": "^sk_" },
+                "list": { "unique": true }
+            }
+        ]
+    }))
+    .expect("spec");
+
+    let result = validate(
+        &spec,
+        &json!({ "keys": ["sk_a", "hunter2-secret", "sk_a"] }),
+    );
+    let codes = result
+        .errors
+        .iter()
+        .map(|error| error.code.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(codes, [Some("pattern_mismatch"), Some("duplicate_item")]);
+    assert!(
+        result
+            .errors
+            .iter()
+            .all(|error| !error.params.contains_key("value"))
+    );
+    let serialized = serde_json::to_string(&result).expect("json");
+    assert!(!serialized.contains("hunter2-secret"), "{serialized}");
+    assert!(!serialized.contains("sk_a"), "{serialized}");
+}
+
+#[test]
+fn schema_is_an_array_of_constrained_strings() {
+    let spec = hosts_form();
+    let schema = answers_schema(&spec, &Default::default());
+    let hosts = &schema["properties"]["hosts"];
+    assert_eq!(hosts["type"], "array");
+    assert_eq!(hosts["minItems"], 1);
+    assert_eq!(hosts["maxItems"], 3);
+    assert_eq!(hosts["uniqueItems"], true);
+    assert_eq!(
+        hosts["items"],
+        json!({ "type": "string", "pattern": "^[a-z0-9.-]+$", "maxLength": 20 })
+    );
+    assert!(hosts.get("pattern").is_none());
+}
+
+#[test]
+fn defaults_and_examples_are_arrays() {
+    let mut spec = hosts_form();
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["hosts"], json!(["example-hosts-1"]));
+    assert!(validate(&spec, &example).valid);
+
+    spec.questions[0].default_value = Some("a.com, b.com".into());
+    let schema = answers_schema(&spec, &Default::default());
+    assert_eq!(
+        schema["properties"]["hosts"]["default"],
+        json!(["a.com", "b.com"])
+    );
+    assert_eq!(
+        example_answers(&spec, &Default::default())["hosts"],
+        json!(["a.com", "b.com"])
+    );
+}
+
+#[test]
+fn normalization_applies_to_each_item() {
+    let spec = hosts_form();
+    let normalized = apply_normalization(&spec, &json!({ "hosts": [" API.Example.com ", "x"] }));
+    assert_eq!(normalized["hosts"], json!(["api.example.com", "x"]));
+}
+
+#[test]
+fn card_uses_comma_separated_text_input() {
+    let spec = hosts_form();
+    let card = render_card(&build_render_payload(&spec, &json!({}), &json!({})));
+    let rendered = card.to_string();
+    assert!(rendered.contains(r#""type":"Input.Text""#), "{rendered}");
+    assert!(
+        rendered.contains(r#""placeholder":"Separate items with commas, e.g. a, b""#),
+        "{rendered}"
+    );
+}
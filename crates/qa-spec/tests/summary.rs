@@ -0,0 +1,138 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, RenderSummary, SummaryStatus, build_render_payload, build_render_payload_in_locale,
+    render_summary, validate,
+};
+
+fn deploy_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true },
+            {
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "constraint": { "min": 1, "max": 5 }
+            },
+            { "id": "token", "type": "string", "title": "Token", "secret": true },
+            { "id": "region", "type": "string", "title": "Region", "required": true },
+            { "id": "notes", "type": "string", "title": "Notes" },
+            {
+                "id": "internal",
+                "type": "string",
+                "title": "Internal",
+                "visible_if": { "op": "literal", "value": false }
+            },
+            {
+                "id": "hosts",
+                "type": "list",
+                "title": "Hosts",
+                "list": {
+                    "fields": [{ "id": "host", "type": "string", "title": "Host" }]
+                }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn summary_of(answers: Value) -> RenderSummary {
+    let spec = deploy_form();
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    render_summary(&payload, &validate(&spec, &answers))
+}
+
+fn answers() -> Value {
+    json!({
+        "name": "api",
+        "replicas": 9,
+        "token": "hunter2",
+        "internal": "x",
+        "hosts": [{ "host": "a" }, { "host": "b" }, { "host": "c" }]
+    })
+}
+
+#[test]
+fn summary_marks_each_visible_question() {
+    let summary = summary_of(answers());
+    assert!(!summary.complete);
+    let statuses = summary
+        .entries
+        .iter()
+        .map(|entry| (entry.id.as_str(), entry.status, entry.value.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        [
+            ("name", SummaryStatus::Answered, Some("api")),
+            ("replicas", SummaryStatus::Invalid, Some("9")),
+            ("token", SummaryStatus::Answered, Some("********")),
+            ("region", SummaryStatus::Missing, None),
+            ("notes", SummaryStatus::Missing, None),
+            ("hosts", SummaryStatus::Answered, Some("3 item(s)")),
+        ]
+    );
+    assert!(summary.entries[3].required);
+    assert!(!summary.entries[4].required);
+    assert!(!summary.entries[1].errors.is_empty());
+}
+
+#[test]
+fn summary_serializes_entries_and_renders_text() {
+    let summary = summary_of(answers());
+    let value = serde_json::to_value(&summary).expect("json");
+    assert_eq!(value["form_id"], "deploy");
+    assert_eq!(
+        value["entries"][0],
+        json!({
+            "id": "name",
+            "title": "Name",
+            "value": "api",
+            "status": "answered",
+            "required": true
+        })
+    );
+    assert_eq!(value["entries"][3]["status"], "missing");
+    assert!(!value.to_string().contains("hunter2"));
+
+    let text = summary.to_text();
+    let lines = text.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "Review: Deploy (deploy)");
+    assert_eq!(lines[1], " - name (Name) = api");
+    assert!(
+        lines[2].starts_with(" - replicas (Replicas) = 9 [invalid: "),
+        "{text}"
+    );
+    assert_eq!(lines[4], " - region (Region) [missing]");
+    assert_eq!(lines[5], " - notes (Notes)");
+    assert_eq!(lines[6], " - hosts (Hosts) = 3 item(s)");
+}
+
+#[test]
+fn summary_is_complete_once_required_answers_are_valid() {
+    let mut answers = answers();
+    answers["replicas"] = json!(2);
+    answers["region"] = json!("eu");
+    let summary = summary_of(answers);
+    assert!(summary.complete);
+    assert!(
+        summary
+            .entries
+            .iter()
+            .all(|entry| entry.status != SummaryStatus::Invalid)
+    );
+}
+
+#[test]
+fn summary_text_uses_the_payload_locale() {
+    let spec = deploy_form();
+    let answers = json!({ "name": "api" });
+    let payload = build_render_payload_in_locale(&spec, &json!({}), &answers, "id");
+    let text = render_summary(&payload, &validate(&spec, &answers)).to_text();
+    assert!(text.starts_with("Tinjauan: Deploy (deploy)"), "{text}");
+    assert!(text.contains(" - region (Region) [belum diisi]"), "{text}");
+}
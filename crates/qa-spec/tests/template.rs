@@ -17,12 +17,19 @@ fn build_sample_form() -> qa_spec::FormSpec {
             intro: Some("intro {{answers.name}}".into()),
             theme: Some("theme-default".into()),
             default_locale: None,
+            template_mode: None,
+            card_mode: None,
+            page_size: None,
         }),
         progress_policy: None,
         secrets_policy: None,
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![QuestionSpec {
             id: "q1".into(),
             kind: QuestionType::String,
@@ -31,15 +38,23 @@ fn build_sample_form() -> qa_spec::FormSpec {
             description: Some("desc {{payload.title}}".into()),
             description_i18n: None,
             required: true,
+            required_if: None,
+            read_only_if: None,
+            order: None,
             choices: None,
+            choices_catalog: None,
             default_value: Some("{{default payload.default \"fallback\"}}".into()),
             secret: false,
+            secret_path: None,
+            normalize: Vec::new(),
             visible_if: None,
             constraint: None,
             list: None,
+            object: None,
             policy: Default::default(),
             computed: None,
             computed_overridable: false,
+            i18n: None,
         }],
     }
 }
@@ -80,6 +95,10 @@ fn resolve_string_relaxed_keeps_missing_tokens() {
                 store: vec![],
                 validations: vec![],
                 includes: vec![],
+                migrations: vec![],
+                max_answer_bytes: None,
+                max_total_bytes: None,
+                i18n: None,
                 questions: vec![],
             },
             &ctx,
@@ -0,0 +1,139 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, LintSeverity, ThemeMode, ThemeSpec, build_render_payload, lint_form, render_card,
+    render_json_ui, render_json_ui_to,
+};
+
+fn themed_form(theme: Value) -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "signup",
+        "title": "Signup",
+        "version": "1.0.0",
+        "presentation": { "theme": theme },
+        "questions": [
+            { "id": "name", "type": "string", "title": "Name", "required": true }
+        ]
+    }))
+    .expect("spec")
+}
+
+fn theme_of(spec: &FormSpec) -> &ThemeSpec {
+    spec.presentation
+        .as_ref()
+        .and_then(|presentation| presentation.theme.as_ref())
+        .expect("theme")
+}
+
+#[test]
+fn named_themes_resolve_and_unknown_names_fall_back() {
+    let dark = theme_of(&themed_form(json!("dark"))).resolve();
+    assert_eq!(dark.name.as_deref(), Some("dark"));
+    assert_eq!(dark.theme.mode, ThemeMode::Dark);
+    assert!(dark.diagnostic.is_none());
+
+    let unknown = themed_form(json!("neon"));
+    let resolved = theme_of(&unknown).resolve();
+    assert_eq!(resolved.name.as_deref(), Some("default"));
+    assert_eq!(resolved.theme.accent.as_deref(), Some("#0078d4"));
+    assert_eq!(
+        resolved.diagnostic.as_deref(),
+        Some("unknown theme 'neon'; using 'default' (known themes: default, light, dark)")
+    );
+    let issues = lint_form(&unknown);
+    let issue = issues
+        .iter()
+        .find(|issue| issue.code == "theme_fallback")
+        .expect("theme warning");
+    assert_eq!(issue.severity, LintSeverity::Warning);
+    assert_eq!(issue.path, "/presentation/theme");
+    assert!(
+        lint_form(&themed_form(json!("light")))
+            .iter()
+            .all(|issue| issue.code != "theme_fallback")
+    );
+}
+
+#[test]
+fn inline_themes_normalize_the_accent() {
+    let spec = themed_form(json!({ "accent": "Teal", "mode": "dark" }));
+    let resolved = theme_of(&spec).resolve();
+    assert_eq!(resolved.name, None);
+    assert_eq!(resolved.theme.accent.as_deref(), Some("#008080"));
+    assert_eq!(resolved.theme.mode, ThemeMode::Dark);
+
+    let resolved = theme_of(&themed_form(json!({ "accent": "sparkly" }))).resolve();
+    assert_eq!(resolved.theme.accent, None);
+    assert_eq!(
+        resolved.diagnostic.as_deref(),
+        Some("theme accent 'sparkly' is not a color; leaving it out")
+    );
+}
+
+#[test]
+fn css_custom_properties_cover_the_set_fields() {
+    let spec = themed_form(json!({
+        "accent": "#336699",
+        "logo_url": "https://example.com/logo \"x\".png"
+    }));
+    assert_eq!(
+        theme_of(&spec).resolve().theme.css_custom_properties(),
+        ":root {\n  color-scheme: light;\n  --qa-accent: #336699;\n  --qa-logo-url: url(\"https://example.com/logo %22x%22.png\");\n}"
+    );
+}
+
+#[test]
+fn cards_get_an_accent_header_logo_and_background() {
+    let spec = themed_form(json!({
+        "accent": "#336699",
+        "logo_url": "https://example.com/logo.png",
+        "background_image": "https://example.com/bg.png"
+    }));
+    let card = render_card(&build_render_payload(&spec, &json!({}), &json!({})));
+    assert_eq!(
+        card["backgroundImage"],
+        json!({ "url": "https://example.com/bg.png" })
+    );
+    let header = &card["body"][0];
+    assert_eq!(header["type"], "Container");
+    assert_eq!(header["style"], "accent");
+    assert_eq!(
+        header["items"][0],
+        json!({
+            "type": "Image",
+            "url": "https://example.com/logo.png",
+            "altText": "Signup",
+            "size": "Small"
+        })
+    );
+    assert_eq!(header["items"][1]["text"], "Signup");
+
+    let plain = FormSpec::from_value(json!({
+        "id": "signup",
+        "title": "Signup",
+        "version": "1.0.0",
+        "questions": [{ "id": "name", "type": "string", "title": "Name" }]
+    }))
+    .expect("spec");
+    let card = render_card(&build_render_payload(&plain, &json!({}), &json!({})));
+    assert_eq!(card["body"][0]["type"], "TextBlock");
+    assert!(card.get("backgroundImage").is_none());
+}
+
+#[test]
+fn json_ui_passes_the_resolved_theme_through() {
+    let payload = build_render_payload(&themed_form(json!("neon")), &json!({}), &json!({}));
+    let ui = render_json_ui(&payload);
+    assert_eq!(
+        ui["theme"],
+        json!({
+            "name": "default",
+            "accent": "#0078d4",
+            "mode": "light",
+            "diagnostic": "unknown theme 'neon'; using 'default' (known themes: default, light, dark)"
+        })
+    );
+    let mut streamed = Vec::new();
+    render_json_ui_to(&payload, &mut streamed).expect("stream");
+    assert_eq!(streamed, serde_json::to_vec(&ui).expect("json"));
+}
@@ -0,0 +1,103 @@
+use serde_json::json;
+
+use qa_spec::time_of_day::{normalize_time, seconds_of_day};
+use qa_spec::{
+    FormSpec, answers_schema, build_render_payload, example_answers, render_card, render_text,
+    validate,
+};
+
+fn shift_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "shift",
+        "title": "Shift",
+        "version": "1.0.0",
+        "progress_policy": { "skip_answered": false },
+        "questions": [{
+            "id": "start",
+            "type": "time",
+            "title": "Start time",
+            "required": true,
+            "constraint": { "min": 28800, "max": 63000, "exclusive_max": true }
+        }]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn times_parse_strictly_and_normalize_loosely() {
+    assert_eq!(seconds_of_day("08:00"), Some(28_800));
+    assert_eq!(seconds_of_day("23:59:59"), Some(86_399));
+    assert_eq!(seconds_of_day("8:00"), None);
+    assert_eq!(seconds_of_day("24:00"), None);
+    assert_eq!(seconds_of_day("12:60"), None);
+
+    assert_eq!(normalize_time(" 9am ").as_deref(), Some("09:00:00"));
+    assert_eq!(normalize_time("2:30 PM").as_deref(), Some("14:30:00"));
+    assert_eq!(normalize_time("12pm").as_deref(), Some("12:00:00"));
+    assert_eq!(normalize_time("14:30").as_deref(), Some("14:30:00"));
+    assert_eq!(normalize_time("14"), None);
+    assert_eq!(normalize_time("0am"), None);
+}
+
+#[test]
+fn validation_compares_bounds_as_times() {
+    let spec = shift_form();
+
+    assert!(validate(&spec, &json!({ "start": "08:00" })).valid);
+    assert!(validate(&spec, &json!({ "start": "17:29:59" })).valid);
+
+    let result = validate(&spec, &json!({ "start": "07:59:59" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("min"));
+    assert_eq!(result.errors[0].params["expected"], "08:00:00");
+
+    let result = validate(&spec, &json!({ "start": "17:30" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("exclusive_max"));
+    assert_eq!(result.errors[0].params["expected"], "17:30:00");
+
+    let result = validate(&spec, &json!({ "start": "9am" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("time_invalid"));
+
+    let result = validate(&spec, &json!({ "start": 900 }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("type_mismatch"));
+}
+
+#[test]
+fn schema_and_examples_use_time_strings() {
+    let spec = shift_form();
+
+    let schema = answers_schema(&spec, &Default::default());
+    let start = &schema["properties"]["start"];
+    assert_eq!(start["type"], "string");
+    assert_eq!(start["format"], "time");
+    assert_eq!(start["formatMinimum"], "08:00:00");
+    assert_eq!(start["formatExclusiveMaximum"], "17:30:00");
+    assert!(start.get("minimum").is_none());
+
+    let example = example_answers(&spec, &Default::default());
+    assert_eq!(example["start"], "09:00:00");
+    assert!(validate(&spec, &example).valid);
+}
+
+#[test]
+fn renderers_show_times() {
+    let spec = shift_form();
+    let payload = build_render_payload(&spec, &json!({}), &json!({ "start": "09:15:00" }));
+
+    let card = render_card(&payload);
+    let input = card["body"]
+        .as_array()
+        .and_then(|body| body.iter().find(|item| item["type"] == "Container"))
+        .map(|container| &container["items"][1])
+        .expect("question input");
+    assert_eq!(input["type"], "Input.Time");
+    assert_eq!(input["value"], "09:15");
+    assert_eq!(input["min"], "08:00");
+    assert_eq!(input["max"], "17:30");
+
+    let text = render_text(&payload);
+    assert!(
+        text.contains("Allowed: at least 08:00:00 and less than 17:30:00"),
+        "{text}"
+    );
+    assert!(text.contains("Syntax: HH:MM or HH:MM:SS"), "{text}");
+}
@@ -0,0 +1,157 @@
+use serde_json::json;
+
+use qa_spec::{FieldValidationError, FormSpec, validate_field, validate_field_with_ctx};
+
+fn signup_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "signup",
+        "title": "Signup",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "email",
+                "type": "string",
+                "title": "Email",
+                "required": true,
+                "constraint": { "pattern": "^[^@]+@[^@]+$" }
+            },
+            { "id": "plan", "type": "enum", "title": "Plan", "required": true, "choices": ["free", "pro"] },
+            { "id": "min_seats", "type": "integer", "title": "Minimum seats", "required": true },
+            { "id": "max_seats", "type": "integer", "title": "Maximum seats", "required": true },
+            {
+                "id": "company",
+                "type": "string",
+                "title": "Company",
+                "required": true,
+                "visible_if": {
+                    "op": "eq",
+                    "left": { "op": "answer", "path": "plan" },
+                    "right": { "op": "literal", "value": "pro" }
+                }
+            }
+        ],
+        "validations": [{
+            "id": "seat_range",
+            "message": "Maximum seats must not be below the minimum",
+            "code": "seats_inverted",
+            "fields": ["max_seats", "min_seats"],
+            "condition": {
+                "op": "gt",
+                "left": { "op": "answer", "path": "min_seats" },
+                "right": { "op": "answer", "path": "max_seats" }
+            }
+        }]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn reports_only_the_field_being_checked() {
+    let spec = signup_form();
+
+    let result = validate_field(&spec, &json!({ "email": "a@b", "extra": 1 }), "email")
+        .expect("email validated");
+    assert!(result.valid, "{result:?}");
+    assert!(result.missing_required.is_empty());
+    assert!(result.unknown_fields.is_empty());
+
+    let result = validate_field(&spec, &json!({ "email": "nope", "plan": "gold" }), "email")
+        .expect("email validated");
+    assert!(!result.valid);
+    assert_eq!(result.errors.len(), 1, "{result:?}");
+    assert_eq!(result.errors[0].path.as_deref(), Some("/email"));
+
+    let result = validate_field(&spec, &json!({ "email": "nope", "plan": "gold" }), "plan")
+        .expect("plan validated");
+    assert_eq!(result.errors.len(), 1, "{result:?}");
+    assert_eq!(result.errors[0].path.as_deref(), Some("/plan"));
+}
+
+#[test]
+fn missing_required_names_only_the_field() {
+    let spec = signup_form();
+
+    let result = validate_field(&spec, &json!({}), "email").expect("email validated");
+    assert!(!result.valid);
+    assert_eq!(result.missing_required, vec!["email".to_string()]);
+}
+
+#[test]
+fn includes_cross_field_validations_naming_the_field() {
+    let spec = signup_form();
+    let answers = json!({ "min_seats": 10, "max_seats": 5 });
+
+    for field in ["min_seats", "max_seats"] {
+        let result = validate_field(&spec, &answers, field).expect("validated");
+        assert_eq!(result.errors.len(), 1, "{field}: {result:?}");
+        assert_eq!(result.errors[0].code.as_deref(), Some("seats_inverted"));
+    }
+
+    let result = validate_field(&spec, &answers, "email").expect("validated");
+    assert!(result.errors.is_empty(), "{result:?}");
+}
+
+#[test]
+fn hidden_and_unknown_questions_are_reported() {
+    let spec = signup_form();
+
+    assert_eq!(
+        validate_field(&spec, &json!({ "plan": "free" }), "company"),
+        Err(FieldValidationError::NotVisible("company".into()))
+    );
+    let result = validate_field(&spec, &json!({ "plan": "pro" }), "company").expect("visible");
+    assert_eq!(result.missing_required, vec!["company".to_string()]);
+
+    assert_eq!(
+        validate_field(&spec, &json!({}), "nickname"),
+        Err(FieldValidationError::UnknownQuestion("nickname".into()))
+    );
+}
+
+#[test]
+fn compiled_spec_scopes_the_same_way() {
+    let spec = signup_form();
+    let compiled = qa_spec::CompiledFormSpec::new(spec.clone());
+    let answers = json!({ "email": "nope", "min_seats": 10, "max_seats": 5 });
+
+    for field in ["email", "min_seats", "plan"] {
+        assert_eq!(
+            compiled.validate_field(&answers, field),
+            validate_field(&spec, &answers, field),
+            "{field}"
+        );
+    }
+}
+
+#[test]
+fn validate_field_with_ctx_sees_ctx_roots() {
+    let spec = FormSpec::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "secret_ref",
+                "type": "string",
+                "title": "Secret reference",
+                "visible_if": { "op": "var", "path": "config.secrets_host_available" },
+                "required": true
+            }
+        ]
+    }))
+    .expect("spec");
+    let hidden = json!({ "config": { "secrets_host_available": false } });
+    assert_eq!(
+        validate_field_with_ctx(&spec, &json!({}), &hidden, "secret_ref"),
+        Err(FieldValidationError::NotVisible("secret_ref".into()))
+    );
+    assert_eq!(
+        spec.clone()
+            .compile()
+            .validate_field_with_ctx(&json!({}), &hidden, "secret_ref"),
+        Err(FieldValidationError::NotVisible("secret_ref".into()))
+    );
+    let shown = json!({ "config": { "secrets_host_available": true } });
+    let result = validate_field_with_ctx(&spec, &json!({}), &shown, "secret_ref").expect("visible");
+    assert_eq!(result.missing_required, vec!["secret_ref".to_string()]);
+}
@@ -4,8 +4,8 @@ use qa_spec::spec::form::FormSpec;
 use qa_spec::spec::question::{ListSpec, QuestionSpec, QuestionType};
 use qa_spec::spec::validation::CrossFieldValidation;
 use qa_spec::{
-    Expr, VisibilityMap, VisibilityMode, answers_schema, apply_computed_answers, example_answers,
-    resolve_visibility, validate,
+    Expr, ValidationResult, VisibilityMap, VisibilityMode, answers_schema, apply_computed_answers,
+    example_answers, json_pointer, resolve_visibility, validate,
 };
 
 fn channel_field() -> QuestionSpec {
@@ -17,15 +17,23 @@ fn channel_field() -> QuestionSpec {
         description: None,
         description_i18n: None,
         required: true,
+        required_if: None,
+        read_only_if: None,
+        order: None,
         choices: None,
+        choices_catalog: None,
         default_value: None,
         secret: false,
+        secret_path: None,
+        normalize: Vec::new(),
         visible_if: None,
         constraint: None,
         list: None,
+        object: None,
         computed: None,
         policy: Default::default(),
         computed_overridable: false,
+        i18n: None,
     }
 }
 
@@ -41,6 +49,10 @@ fn build_channel_form(min_items: Option<usize>, max_items: Option<usize>) -> For
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![QuestionSpec {
             id: "channels".into(),
             kind: QuestionType::List,
@@ -49,19 +61,29 @@ fn build_channel_form(min_items: Option<usize>, max_items: Option<usize>) -> For
             description: None,
             description_i18n: None,
             required: false,
+            required_if: None,
+            read_only_if: None,
+            order: None,
             choices: None,
+            choices_catalog: None,
             default_value: None,
             secret: false,
+            secret_path: None,
+            normalize: Vec::new(),
             visible_if: None,
             constraint: None,
             list: Some(ListSpec {
                 min_items,
                 max_items,
+                unique: false,
+                unique_by: None,
                 fields: vec![channel_field()],
             }),
+            object: None,
             computed: None,
             policy: Default::default(),
             computed_overridable: false,
+            i18n: None,
         }],
     }
 }
@@ -78,6 +100,10 @@ fn make_simple_form() -> FormSpec {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "name".into(),
@@ -87,15 +113,23 @@ fn make_simple_form() -> FormSpec {
                 description: None,
                 description_i18n: None,
                 required: true,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 computed: None,
                 policy: Default::default(),
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "flag".into(),
@@ -105,15 +139,23 @@ fn make_simple_form() -> FormSpec {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 computed: None,
                 policy: Default::default(),
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     }
@@ -218,6 +260,10 @@ fn computed_fields_satisfy_required_answers() {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "name".into(),
@@ -227,15 +273,23 @@ fn computed_fields_satisfy_required_answers() {
                 description: None,
                 description_i18n: None,
                 required: true,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "slug".into(),
@@ -245,17 +299,25 @@ fn computed_fields_satisfy_required_answers() {
                 description: None,
                 description_i18n: None,
                 required: true,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: Some(Expr::Answer {
                     path: "name".into(),
                 }),
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     };
@@ -281,6 +343,10 @@ fn computed_field_overwrites_user_values_when_not_overridable() {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "source".into(),
@@ -290,15 +356,23 @@ fn computed_field_overwrites_user_values_when_not_overridable() {
                 description: None,
                 description_i18n: None,
                 required: true,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "derived".into(),
@@ -308,17 +382,25 @@ fn computed_field_overwrites_user_values_when_not_overridable() {
                 description: None,
                 description_i18n: None,
                 required: true,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: Some(Expr::Answer {
                     path: "source".into(),
                 }),
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     };
@@ -344,6 +426,10 @@ fn computed_field_respects_overrides_when_allowed() {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: Vec::new(),
     };
     spec.questions = vec![
@@ -355,15 +441,23 @@ fn computed_field_respects_overrides_when_allowed() {
             description: None,
             description_i18n: None,
             required: true,
+            required_if: None,
+            read_only_if: None,
+            order: None,
             choices: None,
+            choices_catalog: None,
             default_value: None,
             secret: false,
+            secret_path: None,
+            normalize: Vec::new(),
             visible_if: None,
             constraint: None,
             list: None,
+            object: None,
             policy: Default::default(),
             computed: None,
             computed_overridable: false,
+            i18n: None,
         },
         QuestionSpec {
             id: "derived".into(),
@@ -373,17 +467,25 @@ fn computed_field_respects_overrides_when_allowed() {
             description: None,
             description_i18n: None,
             required: true,
+            required_if: None,
+            read_only_if: None,
+            order: None,
             choices: None,
+            choices_catalog: None,
             default_value: None,
             secret: false,
+            secret_path: None,
+            normalize: Vec::new(),
             visible_if: None,
             constraint: None,
             list: None,
+            object: None,
             policy: Default::default(),
             computed: Some(Expr::Answer {
                 path: "source".into(),
             }),
             computed_overridable: true,
+            i18n: None,
         },
     ];
 
@@ -419,8 +521,13 @@ fn cross_field_validation_fails_when_required_missing() {
                 ],
             },
             code: Some("missing_dependent".into()),
+            severity: Default::default(),
         }],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "a".into(),
@@ -430,15 +537,23 @@ fn cross_field_validation_fails_when_required_missing() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "b".into(),
@@ -448,15 +563,23 @@ fn cross_field_validation_fails_when_required_missing() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     };
@@ -500,8 +623,13 @@ fn cross_field_validation_requires_at_least_one_contact() {
                 ],
             },
             code: Some("contact_required".into()),
+            severity: Default::default(),
         }],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "email".into(),
@@ -511,15 +639,23 @@ fn cross_field_validation_requires_at_least_one_contact() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "phone".into(),
@@ -529,15 +665,23 @@ fn cross_field_validation_requires_at_least_one_contact() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     };
@@ -563,6 +707,10 @@ fn answer_expression_controls_visibility() {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "trigger".into(),
@@ -572,15 +720,23 @@ fn answer_expression_controls_visibility() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "dependent".into(),
@@ -590,17 +746,25 @@ fn answer_expression_controls_visibility() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: Some(Expr::Answer {
                     path: "trigger".into(),
                 }),
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     };
@@ -625,6 +789,10 @@ fn visibility_not_expression_fires_when_trigger_unset() {
         store: vec![],
         validations: vec![],
         includes: vec![],
+        migrations: vec![],
+        max_answer_bytes: None,
+        max_total_bytes: None,
+        i18n: None,
         questions: vec![
             QuestionSpec {
                 id: "flag".into(),
@@ -634,15 +802,23 @@ fn visibility_not_expression_fires_when_trigger_unset() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: None,
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
             QuestionSpec {
                 id: "dependent".into(),
@@ -652,9 +828,15 @@ fn visibility_not_expression_fires_when_trigger_unset() {
                 description: None,
                 description_i18n: None,
                 required: false,
+                required_if: None,
+                read_only_if: None,
+                order: None,
                 choices: None,
+                choices_catalog: None,
                 default_value: None,
                 secret: false,
+                secret_path: None,
+                normalize: Vec::new(),
                 visible_if: Some(Expr::Not {
                     expression: Box::new(Expr::IsSet {
                         path: "flag".into(),
@@ -662,9 +844,11 @@ fn visibility_not_expression_fires_when_trigger_unset() {
                 }),
                 constraint: None,
                 list: None,
+                object: None,
                 policy: Default::default(),
                 computed: None,
                 computed_overridable: false,
+                i18n: None,
             },
         ],
     };
@@ -675,3 +859,883 @@ fn visibility_not_expression_fires_when_trigger_unset() {
     let hidden = resolve_visibility(&spec, &json!({ "flag": true }), VisibilityMode::Visible);
     assert!(!hidden["dependent"]);
 }
+
+fn normalized_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "contact",
+        "title": "Contact",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "email",
+                "type": "string",
+                "title": "Email",
+                "required": true,
+                "normalize": ["trim", "lowercase"],
+                "constraint": { "pattern": "^[a-z@.]+$" }
+            },
+            {
+                "id": "host",
+                "type": "string",
+                "title": "Host",
+                "normalize": ["trim", { "strip_prefix": "https://" }, { "strip_suffix": "/" }]
+            },
+            {
+                "id": "contacts",
+                "type": "list",
+                "title": "Contacts",
+                "list": {
+                    "fields": [
+                        {
+                            "id": "name",
+                            "type": "string",
+                            "title": "Name",
+                            "normalize": ["collapse_whitespace", "uppercase"]
+                        }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("deserialize")
+}
+
+#[test]
+fn normalization_runs_before_validation() {
+    let spec = normalized_form();
+    let answers = json!({ "email": "  Ops@Example.COM " });
+    assert!(validate(&spec, &answers).valid);
+}
+
+#[test]
+fn normalization_applies_transforms_in_order() {
+    let spec = normalized_form();
+    let answers = json!({
+        "email": " A@B.C",
+        "host": " https://example.com/ ",
+        "contacts": [{ "name": "  ada   lovelace " }]
+    });
+    let normalized = qa_spec::apply_normalization(&spec, &answers);
+    assert_eq!(normalized["email"], "a@b.c");
+    assert_eq!(normalized["host"], "example.com");
+    assert_eq!(normalized["contacts"][0]["name"], "ADA LOVELACE");
+}
+
+fn chained_computed_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "pricing",
+        "title": "Pricing",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "label",
+                "type": "string",
+                "title": "Label",
+                "computed": { "op": "answer", "path": "region" }
+            },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "computed": { "op": "var", "path": "/ctx/config/default_region" },
+                "computed_overridable": true
+            },
+            {
+                "id": "loop_a",
+                "type": "string",
+                "title": "Loop A",
+                "computed": { "op": "answer", "path": "loop_b" }
+            },
+            {
+                "id": "loop_b",
+                "type": "string",
+                "title": "Loop B",
+                "computed": { "op": "answer", "path": "loop_a" }
+            }
+        ]
+    }))
+    .expect("deserialize")
+}
+
+#[test]
+fn compute_answers_follows_dependency_order_and_reports() {
+    let spec = chained_computed_form();
+    let ctx = json!({ "config": { "default_region": "eu-west-1" } });
+    let (answers, reports) = qa_spec::compute_answers(&spec, &json!({}), &ctx);
+
+    // `label` is declared first but depends on `region`, which reads the ctx.
+    assert_eq!(answers["region"], "eu-west-1");
+    assert_eq!(answers["label"], "eu-west-1");
+    assert_eq!(reports[0].question_id, "label");
+    assert_eq!(reports[0].status, qa_spec::ComputedStatus::Computed);
+    assert_eq!(reports[0].sources, vec!["region"]);
+
+    let (answers, reports) =
+        qa_spec::compute_answers(&spec, &json!({ "region": "us-east-1" }), &ctx);
+    assert_eq!(answers["label"], "us-east-1");
+    assert_eq!(reports[1].status, qa_spec::ComputedStatus::KeptAnswer);
+}
+
+#[test]
+fn compute_answers_detects_cycles() {
+    let spec = chained_computed_form();
+    let (answers, reports) = qa_spec::compute_answers(&spec, &json!({}), &json!({}));
+    assert!(answers.get("loop_a").is_none());
+    let cycle = reports
+        .iter()
+        .filter(|report| report.status == qa_spec::ComputedStatus::Cycle)
+        .map(|report| report.question_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(cycle, vec!["loop_a", "loop_b"]);
+}
+
+fn servers_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "servers",
+        "title": "Servers",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "strict", "type": "boolean", "title": "Strict" },
+            {
+                "id": "servers",
+                "type": "list",
+                "title": "Servers",
+                "list": {
+                    "fields": [
+                        { "id": "host", "type": "string", "title": "Host", "required": true },
+                        { "id": "tls", "type": "boolean", "title": "TLS" },
+                        {
+                            "id": "tls_cert",
+                            "type": "string",
+                            "title": "Certificate",
+                            "required": true,
+                            "visible_if": { "op": "var", "path": "item.tls" }
+                        },
+                        {
+                            "id": "audit_log",
+                            "type": "string",
+                            "title": "Audit log",
+                            "required": true,
+                            "visible_if": { "op": "answer", "path": "strict" }
+                        }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("deserialize")
+}
+
+#[test]
+fn list_field_visibility_is_evaluated_per_item() {
+    let spec = servers_form();
+
+    let plain = validate(
+        &spec,
+        &json!({ "strict": false, "servers": [{ "host": "a", "tls": false, "tls_cert": 42 }] }),
+    );
+    assert!(plain.valid, "hidden field is neither required nor checked");
+
+    let missing_cert = validate(
+        &spec,
+        &json!({ "strict": false, "servers": [{ "host": "a" }, { "host": "b", "tls": true }] }),
+    );
+    assert!(!missing_cert.valid);
+    assert_eq!(
+        missing_cert.errors[0].path.as_deref(),
+        Some("/servers/1/tls_cert")
+    );
+
+    let outer = validate(
+        &spec,
+        &json!({ "strict": true, "servers": [{ "host": "a" }] }),
+    );
+    assert_eq!(
+        outer.errors[0].path.as_deref(),
+        Some("/servers/0/audit_log")
+    );
+}
+
+#[test]
+fn list_field_visibility_becomes_conditional_schema() {
+    let spec = servers_form();
+    let visibility = resolve_visibility(&spec, &json!({}), VisibilityMode::Visible);
+    let schema = answers_schema(&spec, &visibility);
+    let items = &schema["properties"]["servers"]["items"];
+
+    assert_eq!(items["required"], json!(["host"]));
+    assert_eq!(
+        items["allOf"],
+        json!([{
+            "if": {
+                "properties": { "tls": { "const": true } },
+                "required": ["tls"]
+            },
+            "then": { "required": ["tls_cert"] }
+        }])
+    );
+}
+
+fn bounded_number_form() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "ratio",
+        "title": "Ratio",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "ratio",
+            "type": "number",
+            "title": "Ratio",
+            "constraint": { "min": 0, "exclusive_min": true, "max": 1 }
+        }]
+    }))
+    .expect("deserialize")
+}
+
+#[test]
+fn exclusive_bounds_reject_the_boundary() {
+    let spec = bounded_number_form();
+
+    let zero = validate(&spec, &json!({ "ratio": 0 }));
+    assert_eq!(zero.errors[0].code.as_deref(), Some("exclusive_min"));
+    assert_eq!(zero.errors[0].params["expected"], "0");
+    assert!(validate(&spec, &json!({ "ratio": 1 })).valid);
+    assert_eq!(
+        validate(&spec, &json!({ "ratio": 1.5 })).errors[0]
+            .code
+            .as_deref(),
+        Some("max")
+    );
+
+    let visibility = VisibilityMap::from([("ratio".into(), true)]);
+    let schema = answers_schema(&spec, &visibility);
+    let ratio = &schema["properties"]["ratio"];
+    assert_eq!(ratio["exclusiveMinimum"], json!(0.0));
+    assert_eq!(ratio["maximum"], json!(1.0));
+    assert!(ratio.get("minimum").is_none());
+}
+
+fn shift_hours_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "shift",
+        "title": "Shift",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "overnight", "type": "boolean", "title": "Overnight" },
+            {
+                "id": "start_hour",
+                "type": "integer",
+                "title": "Start hour",
+                "visible_if": { "op": "not", "expression": { "op": "answer", "path": "overnight" } }
+            },
+            {
+                "id": "end_hour",
+                "type": "integer",
+                "title": "End hour",
+                "visible_if": { "op": "not", "expression": { "op": "answer", "path": "overnight" } }
+            }
+        ],
+        "validations": [{
+            "id": "end_after_start",
+            "message": "End hour must be after start hour",
+            "code": "end_before_start",
+            "fields": ["end_hour", "start_hour"],
+            "condition": {
+                "op": "and",
+                "expressions": [
+                    { "op": "is_set", "path": "start_hour" },
+                    { "op": "is_set", "path": "end_hour" },
+                    {
+                        "op": "gt",
+                        "left": { "op": "answer", "path": "start_hour" },
+                        "right": { "op": "answer", "path": "end_hour" }
+                    }
+                ]
+            }
+        }]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn cross_field_validation_reports_id_and_fields() {
+    let spec = shift_hours_form();
+
+    assert!(validate(&spec, &json!({ "start_hour": 9, "end_hour": 17 })).valid);
+
+    let result = validate(&spec, &json!({ "start_hour": 17, "end_hour": 9 }));
+    assert!(!result.valid);
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("end_before_start"));
+    assert_eq!(error.message, "End hour must be after start hour");
+    assert_eq!(error.question_id.as_deref(), Some("end_hour"));
+    assert_eq!(error.path.as_deref(), Some("/end_hour"));
+    assert_eq!(error.params["validation"], "end_after_start");
+    assert_eq!(error.params["fields"], "end_hour,start_hour");
+}
+
+#[test]
+fn cross_field_validation_skips_rules_on_hidden_fields() {
+    let spec = shift_hours_form();
+
+    let answers = json!({ "overnight": true, "start_hour": 22, "end_hour": 6 });
+    assert!(validate(&spec, &answers).valid);
+}
+
+fn project_key_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "project",
+        "title": "Project",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "key",
+            "type": "string",
+            "title": "Project key",
+            "constraint": {
+                "pattern": "^[A-Z]+$",
+                "min_len": 3,
+                "max_len": 10,
+                "messages": {
+                    "pattern_mismatch": "Project keys must be 3-10 uppercase letters",
+                    "min_length": "Project keys must be 3-10 uppercase letters"
+                }
+            }
+        }]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn custom_constraint_messages_replace_built_in_ones() {
+    let spec = project_key_form();
+
+    let result = validate(&spec, &json!({ "key": "ab" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("pattern_mismatch"));
+    assert_eq!(
+        result.errors[0].message,
+        "Project keys must be 3-10 uppercase letters"
+    );
+
+    let result = validate(&spec, &json!({ "key": "AB" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("min_length"));
+    assert_eq!(
+        result.errors[0].message,
+        "Project keys must be 3-10 uppercase letters"
+    );
+
+    let result = validate(&spec, &json!({ "key": "ABCDEFGHIJK" }));
+    assert_eq!(result.errors[0].code.as_deref(), Some("max_length"));
+    assert_eq!(result.errors[0].message, "qa_spec.max_length");
+
+    let constraint = spec.questions[0].constraint.as_ref().expect("constraint");
+    assert_eq!(
+        constraint.error_codes(),
+        vec!["pattern_mismatch", "min_length", "max_length"]
+    );
+}
+
+#[test]
+fn custom_constraint_messages_become_schema_error_messages() {
+    let spec = project_key_form();
+
+    let schema = answers_schema(&spec, &VisibilityMap::new());
+    assert_eq!(
+        schema["properties"]["key"]["errorMessage"],
+        json!({
+            "pattern": "Project keys must be 3-10 uppercase letters",
+            "minLength": "Project keys must be 3-10 uppercase letters"
+        })
+    );
+}
+
+fn servers_list_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "servers",
+        "title": "Servers",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "servers",
+            "type": "list",
+            "title": "Servers",
+            "list": {
+                "min_items": 1,
+                "max_items": 3,
+                "fields": [
+                    { "id": "host", "type": "string", "title": "Host", "required": true },
+                    {
+                        "id": "port",
+                        "type": "integer",
+                        "title": "Port",
+                        "constraint": { "min": 1, "max": 65535 }
+                    },
+                    {
+                        "id": "tls",
+                        "type": "object",
+                        "title": "TLS",
+                        "object": {
+                            "fields": [
+                                { "id": "cert", "type": "path", "title": "Certificate" }
+                            ]
+                        }
+                    }
+                ]
+            }
+        }]
+    }))
+    .expect("spec")
+}
+
+fn first_error(spec: &FormSpec, answers: Value) -> (String, String) {
+    let result = validate(spec, &answers);
+    let error = result.errors.first().expect("an error");
+    (
+        error.path.clone().unwrap_or_default(),
+        error.code.clone().unwrap_or_default(),
+    )
+}
+
+#[test]
+fn list_items_are_checked_field_by_field() {
+    let spec = servers_list_form();
+
+    let answers = json!({ "servers": [{ "host": "a", "port": 80 }, { "host": "b" }] });
+    assert!(validate(&spec, &answers).valid);
+
+    assert_eq!(
+        first_error(
+            &spec,
+            json!({ "servers": [{ "host": "a" }, { "host": "b", "port": "not-a-number" }] })
+        ),
+        ("/servers/1/port".into(), "type_mismatch".into())
+    );
+    assert_eq!(
+        first_error(
+            &spec,
+            json!({ "servers": [{ "host": "a", "port": 70000 }] })
+        ),
+        ("/servers/0/port".into(), "max".into())
+    );
+    assert_eq!(
+        first_error(&spec, json!({ "servers": [{ "port": 80 }] })),
+        ("/servers/0/host".into(), "missing_field".into())
+    );
+    assert_eq!(
+        first_error(
+            &spec,
+            json!({ "servers": [{ "host": "a", "tls": { "cert": "" } }] })
+        ),
+        ("/servers/0/tls/cert".into(), "path_invalid".into())
+    );
+}
+
+#[test]
+fn list_items_reject_unknown_keys_and_counts() {
+    let spec = servers_list_form();
+
+    assert_eq!(
+        first_error(
+            &spec,
+            json!({ "servers": [{ "host": "a" }, { "host": "b", "hostname": "b" }] })
+        ),
+        ("/servers/1/hostname".into(), "unknown_item_field".into())
+    );
+    assert_eq!(
+        first_error(&spec, json!({ "servers": [] })),
+        ("/servers".into(), "min_items".into())
+    );
+    assert_eq!(
+        first_error(
+            &spec,
+            json!({ "servers": [{ "host": "a" }, { "host": "b" }, { "host": "c" }, { "host": "d" }] })
+        ),
+        ("/servers".into(), "max_items".into())
+    );
+    assert_eq!(
+        first_error(&spec, json!({ "servers": { "host": "a" } })),
+        ("/servers".into(), "type_mismatch".into())
+    );
+}
+
+#[test]
+fn list_counts_only_apply_to_answered_lists() {
+    let spec = servers_list_form();
+    assert!(validate(&spec, &json!({})).valid);
+
+    let result = validate(&spec, &json!({ "servers": [] }));
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("min_items"));
+    assert_eq!(error.params["expected"], "1");
+    assert_eq!(error.params["actual"], "0");
+
+    let optional = FormSpec::from_value(json!({
+        "id": "servers",
+        "title": "Servers",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "servers",
+            "type": "list",
+            "title": "Servers",
+            "required": true,
+            "list": {
+                "min_items": 0,
+                "fields": [{ "id": "host", "type": "string", "title": "Host" }]
+            }
+        }]
+    }))
+    .expect("spec");
+    assert!(validate(&optional, &json!({ "servers": [] })).valid);
+
+    let schema = answers_schema(&spec, &VisibilityMap::new());
+    assert_eq!(schema["properties"]["servers"]["minItems"], 1);
+    assert_eq!(schema["properties"]["servers"]["maxItems"], 3);
+}
+
+fn environments_form(list: Value) -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "envs",
+        "title": "Environments",
+        "version": "1.0.0",
+        "questions": [{
+            "id": "environments",
+            "type": "list",
+            "title": "Environments",
+            "list": list
+        }]
+    }))
+    .expect("spec")
+}
+
+fn environment_fields() -> Value {
+    json!([
+        { "id": "name", "type": "string", "title": "Name", "required": true },
+        { "id": "replicas", "type": "integer", "title": "Replicas" }
+    ])
+}
+
+#[test]
+fn unique_lists_reject_repeated_entries() {
+    let spec = environments_form(json!({ "unique": true, "fields": environment_fields() }));
+
+    let answers = json!({ "environments": [
+        { "name": "prod", "replicas": 3 },
+        { "name": "prod", "replicas": 1 }
+    ] });
+    assert!(validate(&spec, &answers).valid);
+
+    let answers = json!({ "environments": [
+        { "name": "prod", "replicas": 3 },
+        { "name": "dev" },
+        { "name": "prod", "replicas": 3 }
+    ] });
+    let result = validate(&spec, &answers);
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("duplicate_item"));
+    assert_eq!(error.path.as_deref(), Some("/environments/2"));
+
+    let schema = answers_schema(&spec, &VisibilityMap::new());
+    assert_eq!(schema["properties"]["environments"]["uniqueItems"], true);
+}
+
+#[test]
+fn unique_by_compares_only_the_key_fields() {
+    let spec = environments_form(json!({
+        "unique_by": ["name"],
+        "fields": environment_fields()
+    }));
+
+    let answers = json!({ "environments": [
+        { "name": "prod", "replicas": 3 },
+        { "name": "dev", "replicas": 3 }
+    ] });
+    assert!(validate(&spec, &answers).valid);
+
+    let answers = json!({ "environments": [
+        { "name": "prod", "replicas": 3 },
+        { "name": "prod", "replicas": 1 }
+    ] });
+    let result = validate(&spec, &answers);
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("duplicate_item"));
+    assert_eq!(error.path.as_deref(), Some("/environments/1"));
+    assert_eq!(error.params["value"], "name=prod");
+
+    let schema = answers_schema(&spec, &VisibilityMap::new());
+    assert!(
+        schema["properties"]["environments"]
+            .get("uniqueItems")
+            .is_none()
+    );
+    assert_eq!(
+        schema["properties"]["environments"]["x-unique-by"],
+        json!(["name"])
+    );
+}
+
+#[test]
+fn unique_keys_compare_canonical_values() {
+    let spec = environments_form(json!({
+        "unique_by": ["name", "region"],
+        "fields": [
+            { "id": "name", "type": "string", "title": "Name", "required": true },
+            { "id": "region", "type": "string", "title": "Region" }
+        ]
+    }));
+
+    let answers = json!({ "environments": [
+        { "name": "prod" },
+        { "name": "prod", "region": "" }
+    ] });
+    assert!(
+        validate(&spec, &answers).valid,
+        "a missing field is not blank"
+    );
+
+    let answers = json!({ "environments": [
+        { "name": "a, region=b", "region": "c" },
+        { "name": "a", "region": "b, region=c" }
+    ] });
+    assert!(
+        validate(&spec, &answers).valid,
+        "values do not run together"
+    );
+
+    let spec = environments_form(json!({ "unique": true, "fields": environment_fields() }));
+    let answers = json!({ "environments": [
+        { "name": "prod", "replicas": 1 },
+        { "name": "prod", "replicas": 1.0 }
+    ] });
+    let result = validate(&spec, &answers);
+    assert_eq!(result.errors[0].code.as_deref(), Some("duplicate_item"));
+    assert_eq!(result.errors[0].path.as_deref(), Some("/environments/1"));
+}
+
+#[test]
+fn duplicate_entries_leave_secret_fields_out_of_the_error() {
+    let fields = json!([
+        { "id": "name", "type": "string", "title": "Name", "required": true },
+        { "id": "token", "type": "string", "title": "Token", "secret": true }
+    ]);
+    let answers = json!({ "environments": [
+        { "name": "prod", "token": "hunter2-secret" },
+        { "name": "prod", "token": "hunter2-secret" }
+    ] });
+
+    let spec = environments_form(json!({ "unique_by": ["name", "token"], "fields": fields }));
+    let result = validate(&spec, &answers);
+    assert_eq!(result.errors[0].code.as_deref(), Some("duplicate_item"));
+    assert_eq!(result.errors[0].params["value"], "name=prod");
+
+    let spec = environments_form(json!({ "unique": true, "fields": fields }));
+    let result = validate(&spec, &answers);
+    assert_eq!(result.errors[0].code.as_deref(), Some("duplicate_item"));
+    let serialized = serde_json::to_string(&result).expect("json");
+    assert!(!serialized.contains("hunter2-secret"), "{serialized}");
+}
+
+fn bucket_form() -> FormSpec {
+    FormSpec::from_value(json!({
+        "id": "storage",
+        "title": "Storage",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "bucket",
+                "type": "string",
+                "title": "Bucket name",
+                "required": true,
+                "constraint": {
+                    "min_len": 3,
+                    "warnings": {
+                        "pattern": "^[a-z0-9-]+$",
+                        "messages": { "pattern_mismatch": "This bucket name looks unusual" }
+                    }
+                }
+            },
+            { "id": "size_gb", "type": "integer", "title": "Size (GB)" }
+        ],
+        "validations": [{
+            "id": "large_bucket",
+            "message": "Buckets over 1000 GB are billed separately",
+            "fields": ["size_gb"],
+            "severity": "warning",
+            "condition": {
+                "op": "gt",
+                "left": { "op": "answer", "path": "size_gb" },
+                "right": { "op": "literal", "value": 1000 }
+            }
+        }]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn warnings_are_reported_without_failing_validation() {
+    let spec = bucket_form();
+
+    let result = validate(&spec, &json!({ "bucket": "Team_Bucket", "size_gb": 2000 }));
+    assert!(result.valid, "{result:?}");
+    assert!(result.errors.is_empty());
+    let warnings = result
+        .warnings
+        .iter()
+        .map(|warning| (warning.path.as_deref(), warning.message.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        warnings,
+        vec![
+            (Some("/bucket"), "This bucket name looks unusual"),
+            (
+                Some("/size_gb"),
+                "Buckets over 1000 GB are billed separately"
+            ),
+        ]
+    );
+    assert_eq!(result.warnings[0].code.as_deref(), Some("pattern_mismatch"));
+
+    // An answer that already fails its constraint only reports the error.
+    let result = validate(&spec, &json!({ "bucket": "Ab" }));
+    assert!(!result.valid);
+    assert_eq!(result.errors[0].code.as_deref(), Some("min_length"));
+    assert!(result.warnings.is_empty());
+}
+
+#[test]
+fn warnings_are_omitted_from_json_when_empty() {
+    let spec = bucket_form();
+
+    let result = validate(&spec, &json!({ "bucket": "team-bucket", "size_gb": 10 }));
+    assert!(result.warnings.is_empty());
+    assert_eq!(
+        serde_json::to_value(&result).expect("json"),
+        json!({ "valid": true })
+    );
+
+    let legacy: ValidationResult =
+        serde_json::from_value(json!({ "valid": true })).expect("legacy result");
+    assert!(legacy.warnings.is_empty());
+
+    let rule = serde_json::to_value(&spec.validations[0]).expect("json");
+    assert_eq!(rule["severity"], "warning");
+    let mut strict = spec.validations[0].clone();
+    strict.severity = Default::default();
+    assert!(
+        serde_json::to_value(&strict)
+            .expect("json")
+            .get("severity")
+            .is_none()
+    );
+}
+
+#[test]
+fn json_pointers_escape_slashes_and_tildes() {
+    assert_eq!(json_pointer(["servers", "0", "port"]), "/servers/0/port");
+    assert_eq!(json_pointer(["a/b", "c~d"]), "/a~1b/c~0d");
+    assert_eq!(json_pointer(["~1"]), "/~01");
+    assert_eq!(json_pointer(Vec::<String>::new()), "");
+}
+
+#[test]
+fn error_paths_escape_question_ids() {
+    let spec = FormSpec::from_value(json!({
+        "id": "paths",
+        "title": "Paths",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "env/name", "type": "integer", "title": "Env" },
+            {
+                "id": "hosts~v2",
+                "type": "list",
+                "title": "Hosts",
+                "list": {
+                    "fields": [
+                        { "id": "port", "type": "integer", "title": "Port", "required": true }
+                    ]
+                }
+            }
+        ],
+        "validations": [{
+            "message": "Pick one",
+            "fields": ["env/name", "hosts~v2"],
+            "condition": { "op": "is_set", "path": "env/name" }
+        }]
+    }))
+    .expect("spec");
+
+    let result = validate(
+        &spec,
+        &json!({ "env/name": "x", "hosts~v2": [{ "port": 1 }, { "port": "y" }] }),
+    );
+    let paths = result
+        .errors
+        .iter()
+        .map(|error| error.path.as_deref().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["/env~1name", "/hosts~0v2/1/port", "/env~1name"]);
+    assert_eq!(result.errors[2].paths, vec!["/env~1name", "/hosts~0v2"]);
+    assert!(result.errors[0].paths.is_empty());
+}
+
+#[test]
+fn cross_field_rules_can_test_membership() {
+    let spec = FormSpec::from_value(json!({
+        "id": "access",
+        "title": "Access",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "role", "type": "string", "title": "Role" },
+            { "id": "scopes", "type": "string_list", "title": "Scopes" },
+            { "id": "grants", "type": "string", "title": "Grants" }
+        ],
+        "validations": [
+            {
+                "id": "guest_admin",
+                "message": "Guests cannot hold the admin scope",
+                "fields": ["scopes"],
+                "condition": {
+                    "op": "and",
+                    "expressions": [
+                        {
+                            "op": "in",
+                            "needle": { "op": "answer", "path": "role" },
+                            "haystack": { "op": "literal", "value": ["guest", "contractor"] }
+                        },
+                        {
+                            "op": "contains",
+                            "left": { "op": "answer", "path": "scopes" },
+                            "right": { "op": "literal", "value": "admin" }
+                        }
+                    ]
+                }
+            },
+            {
+                "id": "grants_mention_role",
+                "message": "Grants must not mention the role",
+                "fields": ["grants"],
+                "condition": {
+                    "op": "contains",
+                    "left": { "op": "answer", "path": "grants" },
+                    "right": { "op": "answer", "path": "role" }
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+    let failed = |answers: Value| {
+        validate(&spec, &answers)
+            .errors
+            .into_iter()
+            .filter_map(|error| error.params.get("validation").cloned())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        failed(json!({ "role": "guest", "scopes": ["read", "admin"] })),
+        ["guest_admin"]
+    );
+    assert!(failed(json!({ "role": "owner", "scopes": ["admin"] })).is_empty());
+    assert_eq!(
+        failed(json!({ "role": "guest", "grants": "guest:read" })),
+        ["grants_mention_role"]
+    );
+    // Without the answers the conditions cannot be evaluated and nothing fails.
+    assert!(failed(json!({ "scopes": ["admin"] })).is_empty());
+}
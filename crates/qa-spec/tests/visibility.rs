@@ -0,0 +1,911 @@
+use serde_json::{Value, json};
+
+use qa_spec::{
+    FormSpec, RenderOptions, VisibilityCache, VisibilityMode, VisibilityOptions,
+    build_render_payload, build_render_payload_with_cache, build_render_payload_with_visibility,
+    render_json_ui, resolve_visibility, resolve_visibility_with_diagnostics,
+    resolve_visibility_with_options, try_resolve_visibility, validate, validate_strict,
+    validate_with_visibility,
+};
+
+fn spec() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "deploy",
+        "title": "Deploy",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "mode", "type": "enum", "title": "Mode", "required": true, "choices": ["simple", "advanced"] },
+            {
+                "id": "replicas",
+                "type": "integer",
+                "title": "Replicas",
+                "required": true,
+                "visible_if": { "op": "eq", "left": { "op": "answer", "path": "mode" }, "right": { "op": "literal", "value": "advanced" } }
+            },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "required": false,
+                "visible_if": { "op": "answer", "path": "cloud" }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn diagnostics_list_conditions_that_could_not_be_evaluated() {
+    let spec = spec();
+    let resolved = resolve_visibility_with_diagnostics(
+        &spec,
+        &json!({ "mode": "simple" }),
+        VisibilityMode::Hidden,
+    );
+    assert_eq!(resolved.unresolved, vec!["region".to_string()]);
+    assert!(!resolved.map["replicas"]);
+    assert!(!resolved.map["region"]);
+    assert_eq!(
+        resolved.map,
+        resolve_visibility(&spec, &json!({ "mode": "simple" }), VisibilityMode::Hidden)
+    );
+}
+
+#[test]
+fn with_visibility_variants_match_the_convenience_functions() {
+    let spec = spec();
+    for answers in [
+        json!({}),
+        json!({ "mode": "simple" }),
+        json!({ "mode": "advanced" }),
+        json!({ "mode": "advanced", "replicas": "three" }),
+    ] {
+        let visibility = resolve_visibility(&spec, &answers, VisibilityMode::Visible);
+        assert_eq!(
+            validate_with_visibility(&spec, &answers, &visibility),
+            validate(&spec, &answers)
+        );
+        let payload = build_render_payload_with_visibility(
+            &spec,
+            &json!({}),
+            &answers,
+            None,
+            &RenderOptions::for_form(&spec),
+            &visibility,
+        );
+        assert_eq!(
+            render_json_ui(&payload),
+            render_json_ui(&build_render_payload(&spec, &json!({}), &answers))
+        );
+    }
+}
+
+#[test]
+fn cache_reuses_visibility_for_equivalent_answers() {
+    let spec = spec();
+    let mut cache = VisibilityCache::new(2);
+    let render = |cache: &mut VisibilityCache, answers: &Value| {
+        render_json_ui(&build_render_payload_with_cache(
+            &spec,
+            &json!({}),
+            answers,
+            None,
+            &RenderOptions::for_form(&spec),
+            cache,
+        ))
+    };
+
+    let first = render(&mut cache, &json!({ "mode": "advanced", "replicas": 3 }));
+    let again = render(&mut cache, &json!({ "replicas": 3.0, "mode": "advanced" }));
+    assert_eq!(first["progress"], again["progress"]);
+    assert_eq!(first["next_question_id"], again["next_question_id"]);
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+    render(&mut cache, &json!({ "mode": "simple" }));
+    render(&mut cache, &json!({}));
+    assert_eq!(cache.len(), 2);
+    render(&mut cache, &json!({ "mode": "advanced", "replicas": 3 }));
+    assert_eq!((cache.hits(), cache.misses()), (1, 4));
+
+    let mut other = spec.clone();
+    other.version = "2.0.0".into();
+    cache.clear();
+    assert!(cache.is_empty());
+    cache.resolve(&spec, &json!({}), VisibilityMode::Visible);
+    cache.resolve(&other, &json!({}), VisibilityMode::Visible);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn ordered_comparisons_drive_visibility() {
+    let compare = |op: &str, right: Value| json!({ "op": op, "left": { "op": "answer", "path": "replicas" }, "right": { "op": "literal", "value": right } });
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "scale",
+        "title": "Scale",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "replicas", "type": "integer", "title": "Replicas", "required": true },
+            { "id": "sharding", "type": "boolean", "title": "Sharding", "visible_if": compare("gt", json!(3)) },
+            { "id": "single", "type": "boolean", "title": "Single node", "visible_if": compare("lte", json!(1)) },
+            { "id": "pool", "type": "string", "title": "Pool", "visible_if": compare("ne", json!(2)) },
+            {
+                "id": "zone",
+                "type": "string",
+                "title": "Zone",
+                "visible_if": { "op": "gte", "left": { "op": "answer", "path": "tier" }, "right": { "op": "literal", "value": "gold" } }
+            }
+        ]
+    }))
+    .expect("spec");
+    let visible = |answers: Value| {
+        let map = resolve_visibility_with_diagnostics(&spec, &answers, VisibilityMode::Hidden).map;
+        ["sharding", "single", "pool", "zone"].map(|id| map[id])
+    };
+
+    assert_eq!(
+        visible(json!({ "replicas": 5 })),
+        [true, false, true, false]
+    );
+    assert_eq!(
+        visible(json!({ "replicas": 3.0 })),
+        [false, false, true, false]
+    );
+    assert_eq!(
+        visible(json!({ "replicas": 1, "tier": "silver" })),
+        [false, true, true, true]
+    );
+    assert_eq!(
+        visible(json!({ "replicas": 2, "tier": "bronze" })),
+        [false, false, false, false]
+    );
+
+    // Values of different types cannot be ordered, so the condition is unresolved.
+    let resolved = resolve_visibility_with_diagnostics(
+        &spec,
+        &json!({ "replicas": "many" }),
+        VisibilityMode::Hidden,
+    );
+    assert!(resolved.unresolved.contains(&"sharding".to_string()));
+
+    let schema = serde_json::to_string(&schemars::schema_for!(qa_spec::expr::Expr)).unwrap();
+    for op in ["lt", "lte", "gt", "gte", "ne"] {
+        assert!(
+            schema.contains(&format!("\"{op}\"")),
+            "{op} missing from {schema}"
+        );
+    }
+}
+
+#[test]
+fn answer_compared_to_literal_drives_visibility() {
+    let form = |visible_if: Value| -> FormSpec {
+        serde_json::from_value(json!({
+            "id": "release",
+            "title": "Release",
+            "version": "1.0.0",
+            "questions": [
+                { "id": "env", "type": "string", "title": "Environment", "required": true },
+                { "id": "approver", "type": "string", "title": "Approver", "required": true, "visible_if": visible_if }
+            ]
+        }))
+        .expect("spec")
+    };
+    let spec = form(json!({
+        "op": "eq",
+        "left": { "op": "answer", "path": "env" },
+        "right": { "op": "literal", "value": "prod" }
+    }));
+    let visible = |spec: &FormSpec, answers: Value| {
+        resolve_visibility(spec, &answers, VisibilityMode::Hidden)["approver"]
+    };
+
+    assert!(visible(&spec, json!({ "env": "prod" })));
+    assert!(!visible(&spec, json!({ "env": "staging" })));
+    assert_eq!(
+        validate(&spec, &json!({ "env": "prod" })).missing_required,
+        ["approver"]
+    );
+    assert!(validate(&spec, &json!({ "env": "staging" })).valid);
+
+    // Older specs wrote operands as bare pointer strings.
+    let legacy = form(json!({ "op": "eq", "left": "/answers/env", "right": "/answers/target" }));
+    let condition = legacy.questions[1].visible_if.as_ref().unwrap();
+    assert_eq!(
+        serde_json::to_value(condition).unwrap(),
+        json!({
+            "op": "eq",
+            "left": { "op": "var", "path": "/answers/env" },
+            "right": { "op": "var", "path": "/answers/target" }
+        })
+    );
+    assert!(visible(&legacy, json!({ "env": "prod", "target": "prod" })));
+    assert!(!visible(&legacy, json!({ "env": "prod", "target": "dev" })));
+
+    assert!(
+        FormSpec::from_value(json!({
+            "id": "release",
+            "title": "Release",
+            "version": "1.0.0",
+            "questions": [{
+                "id": "approver",
+                "type": "string",
+                "title": "Approver",
+                "visible_if": { "op": "eq", "left": "/answers/env", "right": { "op": "literal", "value": "prod" } }
+            }]
+        }))
+        .is_ok()
+    );
+}
+
+#[test]
+fn in_and_contains_test_membership() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "rollout",
+        "title": "Rollout",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "env", "type": "string", "title": "Environment" },
+            { "id": "features", "type": "string_list", "title": "Features" },
+            {
+                "id": "change_ticket",
+                "type": "string",
+                "title": "Change ticket",
+                "visible_if": {
+                    "op": "in",
+                    "needle": { "op": "answer", "path": "env" },
+                    "haystack": { "op": "literal", "value": ["staging", "prod"] }
+                }
+            },
+            {
+                "id": "idp_url",
+                "type": "string",
+                "title": "Identity provider",
+                "visible_if": {
+                    "op": "contains",
+                    "left": { "op": "answer", "path": "features" },
+                    "right": { "op": "literal", "value": "sso" }
+                }
+            },
+            {
+                "id": "region",
+                "type": "string",
+                "title": "Region",
+                "visible_if": {
+                    "op": "contains",
+                    "left": { "op": "answer", "path": "env" },
+                    "right": { "op": "literal", "value": "eu-" }
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+    let visible = |answers: Value, mode: VisibilityMode| {
+        let map = resolve_visibility(&spec, &answers, mode);
+        ["change_ticket", "idp_url", "region"].map(|id| map[id])
+    };
+
+    assert_eq!(
+        visible(
+            json!({ "env": "prod", "features": ["sso", "audit"] }),
+            VisibilityMode::Hidden
+        ),
+        [true, true, false]
+    );
+    assert_eq!(
+        visible(
+            json!({ "env": "eu-dev", "features": ["audit"] }),
+            VisibilityMode::Hidden
+        ),
+        [false, false, true]
+    );
+
+    // A missing answer leaves the condition unresolved, so the mode decides.
+    assert_eq!(
+        visible(json!({}), VisibilityMode::Hidden),
+        [false, false, false]
+    );
+    assert_eq!(
+        visible(json!({}), VisibilityMode::Visible),
+        [true, true, true]
+    );
+    let resolved = resolve_visibility_with_diagnostics(&spec, &json!({}), VisibilityMode::Hidden);
+    assert_eq!(resolved.unresolved, ["change_ticket", "idp_url", "region"]);
+}
+
+#[test]
+fn in_and_contains_accept_legacy_pointer_operands() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "rollout",
+        "title": "Rollout",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "env", "type": "string", "title": "Environment" },
+            { "id": "envs", "type": "string_list", "title": "Environments" },
+            {
+                "id": "change_ticket",
+                "type": "string",
+                "title": "Change ticket",
+                "visible_if": { "op": "in", "needle": "/answers/env", "haystack": "/answers/envs" }
+            },
+            {
+                "id": "approver",
+                "type": "string",
+                "title": "Approver",
+                "visible_if": { "op": "contains", "left": "/answers/envs", "right": "/answers/env" }
+            }
+        ]
+    }))
+    .expect("spec");
+    assert_eq!(
+        serde_json::to_value(spec.questions[2].visible_if.as_ref().unwrap()).unwrap(),
+        json!({
+            "op": "in",
+            "needle": { "op": "var", "path": "/answers/env" },
+            "haystack": { "op": "var", "path": "/answers/envs" }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(spec.questions[3].visible_if.as_ref().unwrap()).unwrap(),
+        json!({
+            "op": "contains",
+            "left": { "op": "var", "path": "/answers/envs" },
+            "right": { "op": "var", "path": "/answers/env" }
+        })
+    );
+
+    let visible = |answers: Value| {
+        let map = resolve_visibility(&spec, &answers, VisibilityMode::Hidden);
+        ["change_ticket", "approver"].map(|id| map[id])
+    };
+    assert_eq!(
+        visible(json!({ "env": "prod", "envs": ["staging", "prod"] })),
+        [true, true]
+    );
+    assert_eq!(
+        visible(json!({ "env": "dev", "envs": ["staging", "prod"] })),
+        [false, false]
+    );
+}
+
+#[test]
+fn string_operators_drive_visibility() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "storage",
+        "title": "Storage",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "url", "type": "string", "title": "URL" },
+            {
+                "id": "s3_region",
+                "type": "string",
+                "title": "S3 region",
+                "visible_if": {
+                    "op": "starts_with",
+                    "left": { "op": "answer", "path": "url" },
+                    "right": { "op": "literal", "value": "s3://" }
+                }
+            },
+            {
+                "id": "archive_format",
+                "type": "string",
+                "title": "Archive format",
+                "visible_if": {
+                    "op": "ends_with",
+                    "left": { "op": "answer", "path": "url" },
+                    "right": { "op": "literal", "value": ".tar.gz" }
+                }
+            },
+            {
+                "id": "bucket_policy",
+                "type": "string",
+                "title": "Bucket policy",
+                "visible_if": {
+                    "op": "matches",
+                    "expression": { "op": "answer", "path": "url" },
+                    "pattern": "^(s3|gs)://[a-z0-9-]+/?$"
+                }
+            },
+            {
+                "id": "broken",
+                "type": "string",
+                "title": "Broken",
+                "visible_if": {
+                    "op": "matches",
+                    "expression": { "op": "answer", "path": "url" },
+                    "pattern": "(unclosed"
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+    let visible = |answers: Value| {
+        let map = resolve_visibility(&spec, &answers, VisibilityMode::Hidden);
+        ["s3_region", "archive_format", "bucket_policy", "broken"].map(|id| map[id])
+    };
+
+    assert_eq!(
+        visible(json!({ "url": "s3://backups" })),
+        [true, false, true, false]
+    );
+    assert_eq!(
+        visible(json!({ "url": "s3://backups/2024.tar.gz" })),
+        [true, true, false, false]
+    );
+    assert_eq!(
+        visible(json!({ "url": "gs://media/" })),
+        [false, false, true, false]
+    );
+
+    // Non-string answers and invalid patterns leave the condition unresolved.
+    let resolved =
+        resolve_visibility_with_diagnostics(&spec, &json!({ "url": 42 }), VisibilityMode::Visible);
+    assert_eq!(
+        resolved.unresolved,
+        ["s3_region", "archive_format", "bucket_policy", "broken"]
+    );
+    let resolved = resolve_visibility_with_diagnostics(
+        &spec,
+        &json!({ "url": "s3://backups" }),
+        VisibilityMode::Visible,
+    );
+    assert_eq!(resolved.unresolved, ["broken"]);
+}
+
+#[test]
+fn string_operators_accept_legacy_pointer_operands() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "storage",
+        "title": "Storage",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "url", "type": "string", "title": "URL" },
+            { "id": "scheme", "type": "string", "title": "Scheme" },
+            { "id": "suffix", "type": "string", "title": "Suffix" },
+            {
+                "id": "credentials",
+                "type": "string",
+                "title": "Credentials",
+                "visible_if": { "op": "starts_with", "left": "/answers/url", "right": "/answers/scheme" }
+            },
+            {
+                "id": "archive_format",
+                "type": "string",
+                "title": "Archive format",
+                "visible_if": { "op": "ends_with", "left": "/answers/url", "right": "/answers/suffix" }
+            }
+        ]
+    }))
+    .expect("spec");
+    assert_eq!(
+        serde_json::to_value(spec.questions[3].visible_if.as_ref().unwrap()).unwrap(),
+        json!({
+            "op": "starts_with",
+            "left": { "op": "var", "path": "/answers/url" },
+            "right": { "op": "var", "path": "/answers/scheme" }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(spec.questions[4].visible_if.as_ref().unwrap()).unwrap(),
+        json!({
+            "op": "ends_with",
+            "left": { "op": "var", "path": "/answers/url" },
+            "right": { "op": "var", "path": "/answers/suffix" }
+        })
+    );
+
+    let visible = |answers: Value| {
+        let map = resolve_visibility(&spec, &answers, VisibilityMode::Hidden);
+        ["credentials", "archive_format"].map(|id| map[id])
+    };
+    assert_eq!(
+        visible(json!({ "url": "s3://backups/db.tar", "scheme": "s3://", "suffix": ".tar" })),
+        [true, true]
+    );
+    assert_eq!(
+        visible(json!({ "url": "s3://backups/db.tar", "scheme": "gs://", "suffix": ".zip" })),
+        [false, false]
+    );
+}
+
+#[test]
+fn is_set_and_is_empty_tell_missing_from_blank_answers() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "survey",
+        "title": "Survey",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "q1", "type": "string", "title": "First" },
+            {
+                "id": "follow_up",
+                "type": "string",
+                "title": "Follow-up",
+                "visible_if": { "op": "is_set", "path": "q1" }
+            },
+            {
+                "id": "reminder",
+                "type": "string",
+                "title": "Reminder",
+                "visible_if": { "op": "is_empty", "path": "q1" }
+            }
+        ]
+    }))
+    .expect("spec");
+    let visible = |answers: Value| {
+        let map = resolve_visibility(&spec, &answers, VisibilityMode::Hidden);
+        [map["follow_up"], map["reminder"]]
+    };
+
+    assert_eq!(visible(json!({})), [false, true]);
+    assert_eq!(visible(json!({ "q1": null })), [false, true]);
+    assert_eq!(visible(json!({ "q1": "" })), [true, true]);
+    assert_eq!(visible(json!({ "q1": [] })), [true, true]);
+    assert_eq!(visible(json!({ "q1": {} })), [true, true]);
+    assert_eq!(visible(json!({ "q1": "yes" })), [true, false]);
+    assert_eq!(visible(json!({ "q1": 0 })), [true, false]);
+    assert_eq!(visible(json!({ "q1": false })), [true, false]);
+    assert_eq!(visible(json!({ "q1": [""] })), [true, false]);
+
+    let schema = serde_json::to_string(&schemars::schema_for!(qa_spec::expr::Expr)).unwrap();
+    assert!(schema.contains("\"is_empty\""), "{schema}");
+}
+
+#[test]
+fn coalesce_falls_back_while_an_answer_is_unset() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "alerts",
+        "title": "Alerts",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "notify", "type": "boolean", "title": "Notify" },
+            {
+                "id": "channel",
+                "type": "string",
+                "title": "Channel",
+                "visible_if": {
+                    "op": "coalesce",
+                    "expressions": [
+                        { "op": "answer", "path": "notify" },
+                        { "op": "literal", "value": true }
+                    ]
+                }
+            },
+            {
+                "id": "explicit_channel",
+                "type": "string",
+                "title": "Channel",
+                "visible_if": {
+                    "op": "or",
+                    "expressions": [
+                        { "op": "not", "expression": { "op": "is_set", "path": "notify" } },
+                        { "op": "answer", "path": "notify" }
+                    ]
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+    let visible = |answers: Value| {
+        let resolved = resolve_visibility_with_diagnostics(&spec, &answers, VisibilityMode::Hidden);
+        assert!(resolved.unresolved.is_empty(), "{answers}");
+        [resolved.map["channel"], resolved.map["explicit_channel"]]
+    };
+
+    // Same result as spelling the default out with `is_set`.
+    assert_eq!(visible(json!({})), [true, true]);
+    assert_eq!(visible(json!({ "notify": null })), [true, true]);
+    assert_eq!(visible(json!({ "notify": true })), [true, true]);
+    assert_eq!(visible(json!({ "notify": false })), [false, false]);
+
+    let coalesce = |expressions: Value| {
+        serde_json::from_value::<qa_spec::expr::Expr>(
+            json!({ "op": "coalesce", "expressions": expressions }),
+        )
+        .expect("expr")
+        .evaluate_value(&json!({ "empty": "", "nothing": null }))
+    };
+    // Only `null` and missing values are skipped; blank answers are kept.
+    assert_eq!(
+        coalesce(json!([{ "op": "answer", "path": "empty" }, { "op": "literal", "value": "x" }])),
+        Some(json!(""))
+    );
+    assert_eq!(
+        coalesce(
+            json!([{ "op": "answer", "path": "nothing" }, { "op": "answer", "path": "missing" }])
+        ),
+        None
+    );
+    assert_eq!(coalesce(json!([])), None);
+}
+
+#[test]
+fn length_counts_items_and_characters() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "cluster",
+        "title": "Cluster",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "hostnames", "type": "string_list", "title": "Hostnames" },
+            { "id": "label", "type": "string", "title": "Label" },
+            {
+                "id": "load_balancer",
+                "type": "string",
+                "title": "Load balancer",
+                "visible_if": {
+                    "op": "gte",
+                    "left": { "op": "length", "of": { "op": "answer", "path": "hostnames" } },
+                    "right": { "op": "literal", "value": 2 }
+                }
+            },
+            {
+                "id": "short_label",
+                "type": "boolean",
+                "title": "Short label",
+                "visible_if": {
+                    "op": "lte",
+                    "left": { "op": "length", "of": { "op": "answer", "path": "label" } },
+                    "right": { "op": "literal", "value": 3 }
+                }
+            }
+        ]
+    }))
+    .expect("spec");
+    let visible = |answers: Value| {
+        let map = resolve_visibility(&spec, &answers, VisibilityMode::Hidden);
+        [map["load_balancer"], map["short_label"]]
+    };
+
+    assert_eq!(
+        visible(json!({ "hostnames": ["a", "b"], "label": "abcd" })),
+        [true, false]
+    );
+    assert_eq!(
+        visible(json!({ "hostnames": ["a"], "label": "" })),
+        [false, true]
+    );
+    // Characters, not bytes: "Zürich" is 6 characters but 7 bytes.
+    assert_eq!(visible(json!({ "label": "äöü" })), [false, true]);
+    assert_eq!(visible(json!({ "label": "日本語!" })), [false, false]);
+
+    let length = |answers: Value| {
+        qa_spec::expr::Expr::Length {
+            of: Box::new(qa_spec::expr::Expr::Answer {
+                path: "label".into(),
+            }),
+        }
+        .evaluate_value(&answers)
+    };
+    assert_eq!(length(json!({ "label": "Zürich" })), Some(json!(6)));
+    assert_eq!(length(json!({ "label": 42 })), None);
+    assert_eq!(length(json!({})), None);
+
+    // A missing answer leaves the comparison unresolved.
+    let resolved = resolve_visibility_with_diagnostics(&spec, &json!({}), VisibilityMode::Visible);
+    assert_eq!(resolved.unresolved, ["load_balancer", "short_label"]);
+}
+
+#[test]
+fn diagnostics_explain_why_conditions_failed() {
+    let spec: FormSpec = serde_json::from_value(json!({
+        "id": "why",
+        "title": "Why",
+        "version": "1.0.0",
+        "questions": [
+            { "id": "replicas", "type": "string", "title": "Replicas" },
+            {
+                "id": "missing",
+                "type": "string",
+                "title": "Missing",
+                "visible_if": {
+                    "op": "and",
+                    "expressions": [
+                        { "op": "literal", "value": true },
+                        { "op": "eq", "left": { "op": "answer", "path": "cloud" }, "right": { "op": "literal", "value": "aws" } }
+                    ]
+                }
+            },
+            {
+                "id": "mismatch",
+                "type": "string",
+                "title": "Mismatch",
+                "visible_if": { "op": "gt", "left": { "op": "answer", "path": "replicas" }, "right": { "op": "literal", "value": 3 } }
+            },
+            {
+                "id": "pattern",
+                "type": "string",
+                "title": "Pattern",
+                "visible_if": { "op": "matches", "expression": { "op": "answer", "path": "replicas" }, "pattern": "(" }
+            },
+            {
+                "id": "not_bool",
+                "type": "string",
+                "title": "Not boolean",
+                "visible_if": { "op": "answer", "path": "replicas" }
+            }
+        ]
+    }))
+    .expect("spec");
+
+    let resolved = resolve_visibility_with_diagnostics(
+        &spec,
+        &json!({ "replicas": "many" }),
+        VisibilityMode::Visible,
+    );
+    assert_eq!(
+        resolved.unresolved,
+        ["missing", "mismatch", "pattern", "not_bool"]
+    );
+    let diagnostics = resolved
+        .diagnostics
+        .iter()
+        .map(|diagnostic| serde_json::to_value(diagnostic).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        [
+            json!({
+                "question_id": "missing",
+                "reason": "missing_value",
+                "expr_fragment": { "op": "answer", "path": "cloud" }
+            }),
+            json!({
+                "question_id": "mismatch",
+                "reason": "type_mismatch",
+                "expr_fragment": {
+                    "op": "gt",
+                    "left": { "op": "answer", "path": "replicas" },
+                    "right": { "op": "literal", "value": 3 }
+                }
+            }),
+            json!({
+                "question_id": "pattern",
+                "reason": "invalid_pattern",
+                "expr_fragment": {
+                    "op": "matches",
+                    "expression": { "op": "answer", "path": "replicas" },
+                    "pattern": "("
+                }
+            }),
+            json!({
+                "question_id": "not_bool",
+                "reason": "not_boolean",
+                "expr_fragment": { "op": "answer", "path": "replicas" }
+            }),
+        ]
+    );
+}
+
+/// `tls_cert` depends on `tls`, which depends on `expose`; `tls_cert` is
+/// declared first so resolution has to follow the dependencies.
+fn chained_spec() -> FormSpec {
+    serde_json::from_value(json!({
+        "id": "ingress",
+        "title": "Ingress",
+        "version": "1.0.0",
+        "questions": [
+            {
+                "id": "tls_cert",
+                "type": "string",
+                "title": "Certificate",
+                "required": true,
+                "visible_if": { "op": "eq", "left": { "op": "answer", "path": "tls" }, "right": { "op": "literal", "value": "yes" } }
+            },
+            { "id": "expose", "type": "boolean", "title": "Expose", "required": true },
+            {
+                "id": "tls",
+                "type": "enum",
+                "title": "TLS",
+                "required": true,
+                "choices": ["yes", "no"],
+                "visible_if": { "op": "answer", "path": "expose" }
+            }
+        ]
+    }))
+    .expect("spec")
+}
+
+#[test]
+fn hiding_cascades_past_stale_answers() {
+    let spec = chained_spec();
+    let live = json!({ "expose": true, "tls": "yes" });
+    let visibility = resolve_visibility(&spec, &live, VisibilityMode::Visible);
+    assert!(visibility["tls"]);
+    assert!(visibility["tls_cert"]);
+
+    // `tls` is hidden once `expose` is turned off, so its lingering "yes"
+    // no longer shows `tls_cert`.
+    let stale = json!({ "expose": false, "tls": "yes" });
+    let visibility = resolve_visibility(&spec, &stale, VisibilityMode::Visible);
+    assert!(!visibility["tls"]);
+    assert!(!visibility["tls_cert"]);
+    assert!(validate(&spec, &stale).valid);
+
+    let legacy = resolve_visibility_with_options(
+        &spec,
+        &stale,
+        &Value::Null,
+        VisibilityMode::Visible,
+        VisibilityOptions {
+            ignore_hidden_answers: false,
+        },
+    );
+    assert!(!legacy.map["tls"]);
+    assert!(legacy.map["tls_cert"]);
+}
+
+#[test]
+fn collapsed_branches_are_not_reported_as_fallbacks() {
+    let spec = chained_spec();
+    for answers in [
+        json!({ "expose": false }),
+        json!({ "expose": false, "tls": "no" }),
+    ] {
+        let resolved =
+            resolve_visibility_with_diagnostics(&spec, &answers, VisibilityMode::Visible);
+        assert!(!resolved.map["tls_cert"], "{answers}");
+        assert!(resolved.unresolved.is_empty(), "{answers}");
+        assert!(resolved.diagnostics.is_empty(), "{answers}");
+    }
+
+    // An unanswered but visible `tls` still falls back to the mode.
+    let resolved = resolve_visibility_with_diagnostics(
+        &spec,
+        &json!({ "expose": true }),
+        VisibilityMode::Visible,
+    );
+    assert!(resolved.map["tls_cert"]);
+    assert_eq!(resolved.unresolved, vec!["tls_cert".to_string()]);
+}
+
+#[test]
+fn error_mode_reports_conditions_that_cannot_be_evaluated() {
+    let spec = spec();
+    let map = try_resolve_visibility(&spec, &json!({ "mode": "advanced", "cloud": true }))
+        .expect("every condition evaluates");
+    assert!(map["replicas"]);
+    assert!(map["region"]);
+
+    let errors = try_resolve_visibility(&spec, &json!({ "mode": "simple" })).unwrap_err();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].question_id, "region");
+    assert_eq!(errors[0].reason, qa_spec::EvaluationFailure::MissingValue);
+
+    // The lenient resolvers still show the question.
+    let answers = json!({ "mode": "simple" });
+    assert!(resolve_visibility(&spec, &answers, VisibilityMode::Error)["region"]);
+    assert!(validate(&spec, &answers).valid);
+
+    let result = validate_strict(&spec, &answers);
+    assert!(!result.valid);
+    assert_eq!(result.errors.len(), 1, "{result:?}");
+    let error = &result.errors[0];
+    assert_eq!(error.code.as_deref(), Some("visibility_error"));
+    assert_eq!(error.question_id.as_deref(), Some("region"));
+    assert_eq!(error.path.as_deref(), Some("/region"));
+    assert_eq!(error.params["reason"], "missing_value");
+    assert_eq!(error.params["path"], "cloud");
+    assert_eq!(
+        error.params["expression"],
+        r#"{"op":"answer","path":"cloud"}"#
+    );
+
+    let answers = json!({ "mode": "simple", "cloud": false });
+    assert_eq!(validate_strict(&spec, &answers), validate(&spec, &answers));
+}
+
+#[test]
+fn answer_paths_match_keys_containing_dots() {
+    let answers = json!({
+        "db.tls": true,
+        "db.servers": [{ "port": 5432 }],
+        "app": { "name": "api" }
+    });
+    let path = |path: &str| {
+        serde_json::from_value::<qa_spec::Expr>(json!({ "op": "answer", "path": path }))
+            .expect("expr")
+            .evaluate_value(&answers)
+    };
+    assert_eq!(path("db.tls"), Some(json!(true)));
+    assert_eq!(path("db.servers.0.port"), Some(json!(5432)));
+    assert_eq!(path("app.name"), Some(json!("api")));
+    assert_eq!(path("db.missing"), None);
+}
@@ -0,0 +1,13 @@
+//! Form specs the answer-driven targets fuzz against.
+
+pub const FORMS: &[&str] = &[
+    include_str!("../../crates/qa-spec/tests/fixtures/simple_form.json"),
+    include_str!("../../crates/component-qa/tests/fixtures/generated/forms/support.form.json"),
+    include_str!("../fixtures/deploy.form.json"),
+];
+
+/// Splits fuzz input into a form index and the remaining payload bytes.
+pub fn select(data: &[u8]) -> Option<(usize, &[u8])> {
+    let (first, rest) = data.split_first()?;
+    Some((usize::from(*first) % FORMS.len(), rest))
+}
@@ -0,0 +1,23 @@
+#![no_main]
+
+//! Arbitrary bytes as form and flow specs: parsing must fail cleanly, and
+//! specs that parse must survive linting and compilation.
+
+use libfuzzer_sys::fuzz_target;
+use qa_spec::{FormSpec, QAFlowSpec, lint_flow, lint_form};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = std::str::from_utf8(data) else {
+        let _ = serde_json::from_slice::<FormSpec>(data);
+        return;
+    };
+
+    let form = FormSpec::from_json_str(raw).ok();
+    if let Some(form) = &form {
+        let _ = lint_form(form);
+        let _ = form.clone().compile();
+    }
+    if let Ok(flow) = serde_json::from_str::<QAFlowSpec>(raw) {
+        let _ = lint_flow(&flow, form.as_ref());
+    }
+});
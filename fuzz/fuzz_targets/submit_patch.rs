@@ -0,0 +1,57 @@
+#![no_main]
+
+//! Arbitrary `value_json` patched into each question of the fixture forms
+//! through component-qa; every response must be valid JSON.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use libfuzzer_sys::fuzz_target;
+use serde_json::{Value, json};
+
+mod fixtures;
+
+/// Config and question ids per fixture, with the forms written to disk once.
+fn forms() -> &'static [(String, String, Vec<String>)] {
+    static FORMS: OnceLock<Vec<(String, String, Vec<String>)>> = OnceLock::new();
+    FORMS.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("greentic-qa-fuzz-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create fixture dir");
+        fixtures::FORMS
+            .iter()
+            .enumerate()
+            .map(|(index, raw)| {
+                let path: PathBuf = dir.join(format!("form-{index}.json"));
+                std::fs::write(&path, raw).expect("write fixture form");
+                let spec: Value = serde_json::from_str(raw).expect("fixture form is JSON");
+                let form_id = spec["id"].as_str().expect("form id").to_string();
+                let question_ids = spec["questions"]
+                    .as_array()
+                    .expect("questions")
+                    .iter()
+                    .filter_map(|question| question["id"].as_str().map(str::to_string))
+                    .collect();
+                let config = json!({ "qa_form_asset_path": path }).to_string();
+                (form_id, config, question_ids)
+            })
+            .collect()
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((index, rest)) = fixtures::select(data) else {
+        return;
+    };
+    let Some((question, value)) = rest.split_first() else {
+        return;
+    };
+    let (form_id, config, question_ids) = &forms()[index];
+    let question_id = &question_ids[usize::from(*question) % question_ids.len()];
+    let value_json = String::from_utf8_lossy(value);
+
+    for ctx in ["{}", r#"{"track_progress": true, "track_meta": true}"#] {
+        let response =
+            component_qa::submit_patch(form_id, config, ctx, "{}", question_id, &value_json);
+        serde_json::from_str::<Value>(&response).expect("response is JSON");
+    }
+});
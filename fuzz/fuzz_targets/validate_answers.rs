@@ -0,0 +1,28 @@
+#![no_main]
+
+//! Arbitrary answers validated against the fixture forms. The compiled and
+//! uncompiled paths must agree, and rendering must not panic.
+
+use libfuzzer_sys::fuzz_target;
+use qa_spec::{FormSpec, build_render_payload, render_json_ui, render_text, validate};
+use serde_json::{Value, json};
+
+mod fixtures;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((index, payload)) = fixtures::select(data) else {
+        return;
+    };
+    let Ok(answers) = serde_json::from_slice::<Value>(payload) else {
+        return;
+    };
+    let spec = FormSpec::from_json_str(fixtures::FORMS[index]).expect("fixture form parses");
+
+    let result = validate(&spec, &answers);
+    let compiled = spec.clone().compile();
+    assert_eq!(compiled.validate(&answers), result);
+
+    let payload = build_render_payload(&spec, &json!({}), &answers);
+    let _ = render_text(&payload);
+    let _ = render_json_ui(&payload);
+});